@@ -0,0 +1,158 @@
+//! Spatial map maintenance benchmark on a 5k-widget scene.
+//!
+//! Run with:
+//!
+//! ```text
+//! cargo run --release --example spatial -- --rows 200 --cols 25 --frames 200
+//! ```
+//!
+//! Times `Screen::update_spatial_map` after a layout pass in three cases: a
+//! structural change (full rebuild), nothing moved, and one row resized
+//! (which moves every row below it). Layout itself is not timed.
+//!
+//! Every case walks all nodes to compare their regions, so the time is
+//! linear in the scene size even when nothing moved; only the number of
+//! entries written drops. Both are reported.
+
+use std::time::{Duration, Instant};
+
+use gilt_tui::css::scalar::Scalar;
+use gilt_tui::css::styles::{LayoutDirection, Styles};
+use gilt_tui::dom::node::{NodeData, NodeId};
+use gilt_tui::screen::Screen;
+
+struct Options {
+    rows: u16,
+    cols: u16,
+    frames: u32,
+}
+
+fn parse_args() -> Options {
+    let mut opts = Options {
+        rows: 200,
+        cols: 25,
+        frames: 200,
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let mut value = || -> u32 {
+            args.next()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(|| panic!("{flag} expects a number"))
+        };
+        let mut cells = || -> u16 {
+            let n = value();
+            u16::try_from(n).unwrap_or_else(|_| panic!("{flag} {n} is more than {}", u16::MAX))
+        };
+        match flag.as_str() {
+            "--rows" => opts.rows = cells(),
+            "--cols" => opts.cols = cells(),
+            "--frames" => opts.frames = value(),
+            other => panic!("unknown option {other} (use --rows, --cols, --frames)"),
+        }
+    }
+    opts
+}
+
+fn sized(width: Option<f32>, height: Option<f32>) -> Styles {
+    let mut styles = Styles::new();
+    styles.width = width.map(Scalar::cells);
+    styles.height = height.map(Scalar::cells);
+    styles
+}
+
+/// A root with `rows` horizontal rows of `cols` one-row cells each.
+fn build_scene(opts: &Options) -> (Screen, NodeId, Vec<NodeId>) {
+    let width = opts
+        .cols
+        .checked_mul(4)
+        .unwrap_or_else(|| panic!("--cols {} is too wide: 4 cells each exceeds u16", opts.cols));
+    let mut screen = Screen::new(width, opts.rows);
+    let root = screen.dom.insert(NodeData::new("Root"));
+    let root_height = f32::from(opts.rows) * 2.0;
+    screen
+        .styles
+        .insert(root, sized(Some(f32::from(width)), Some(root_height)));
+    let mut rows = Vec::new();
+    for _ in 0..opts.rows {
+        let row = screen.dom.insert_child(root, NodeData::new("Row"));
+        let mut row_styles = sized(None, Some(1.0));
+        row_styles.layout = Some(LayoutDirection::Horizontal);
+        screen.styles.insert(row, row_styles);
+        for _ in 0..opts.cols {
+            let cell = screen.dom.insert_child(row, NodeData::new("Cell"));
+            screen.styles.insert(cell, sized(Some(4.0), Some(1.0)));
+        }
+        rows.push(row);
+    }
+    screen.compute_layout().expect("layout");
+    (screen, root, rows)
+}
+
+/// Run `frames` iterations of `prepare` (untimed) then `update_spatial_map`
+/// (timed). Returns the average time, nodes walked, and entries written per
+/// frame.
+fn measure(
+    screen: &mut Screen,
+    frames: u32,
+    mut prepare: impl FnMut(&mut Screen, u32),
+) -> (Duration, usize, usize) {
+    let mut total = Duration::ZERO;
+    let mut walked = 0;
+    let mut written = 0;
+    for frame in 0..frames {
+        prepare(screen, frame);
+        let start = Instant::now();
+        written += screen.update_spatial_map();
+        total += start.elapsed();
+        // Each call compares (or rebuilds) the region of every node.
+        walked += screen.dom.len();
+    }
+    let frames = frames.max(1);
+    (
+        total / frames,
+        walked / frames as usize,
+        written / frames as usize,
+    )
+}
+
+fn relayout(screen: &mut Screen) {
+    let (width, height) = (screen.compositor.width, screen.compositor.height);
+    screen
+        .layout
+        .sync_tree(&screen.dom, &screen.styles, (width, height))
+        .expect("sync");
+    screen
+        .layout
+        .compute(f32::from(width), f32::from(height))
+        .expect("layout");
+}
+
+fn main() {
+    let opts = parse_args();
+    let (mut screen, root, rows) = build_scene(&opts);
+    println!("{} nodes in the spatial map", screen.spatial.len());
+
+    let rebuild = measure(&mut screen, opts.frames, |screen, _| {
+        let extra = screen.dom.insert_child(root, NodeData::new("Extra"));
+        screen.dom.remove(extra);
+    });
+    let idle = measure(&mut screen, opts.frames, |_, _| {});
+    let moved = measure(&mut screen, opts.frames, |screen, frame| {
+        let row = rows[rows.len() / 2];
+        let height = if frame % 2 == 0 { 2.0 } else { 1.0 };
+        screen.styles.get_mut(&row).expect("row styles").height = Some(Scalar::cells(height));
+        relayout(screen);
+    });
+
+    for (name, (time, walked, written)) in [
+        ("full rebuild", rebuild),
+        ("nothing moved", idle),
+        ("half the rows moved", moved),
+    ] {
+        println!(
+            "{name:>20}: {:>8.3}ms, {walked} nodes walked, {written} entries written",
+            time.as_secs_f64() * 1000.0
+        );
+    }
+}
@@ -0,0 +1,125 @@
+//! Standard profiling workload: a headless StressGrid render loop.
+//!
+//! Run with:
+//!
+//! ```text
+//! cargo run --release --example stress --features stress -- --cols 80 --rows 40 --frames 500
+//! ```
+//!
+//! Every frame ticks the grid, renders it, composites the strips, and diffs
+//! against the previous frame. Per-phase timings are recorded into
+//! [`FrameStats`] with `record_frame` — the same calls an app's render loop
+//! makes for `App::stats` — and summarized at the end. Pass `--hud` to also
+//! print the perf HUD those stats feed.
+
+use std::time::{Duration, Instant};
+
+use gilt_tui::css::styles::Styles;
+use gilt_tui::geometry::Region;
+use gilt_tui::reactive::Runtime;
+use gilt_tui::render::stats::{FrameStats, FrameTimings};
+use gilt_tui::render::Compositor;
+use gilt_tui::widget::Widget;
+use gilt_tui::widgets::perf_hud::PerfHud;
+use gilt_tui::widgets::stress::StressGrid;
+
+struct Options {
+    cols: u16,
+    rows: u16,
+    cell_width: u16,
+    frames: u32,
+    hud: bool,
+}
+
+fn parse_args() -> Options {
+    let mut opts = Options {
+        cols: 40,
+        rows: 24,
+        cell_width: 2,
+        frames: 300,
+        hud: false,
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let mut value = || -> u32 {
+            args.next()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(|| panic!("{flag} expects a number"))
+        };
+        let mut cells = || -> u16 {
+            let n = value();
+            u16::try_from(n).unwrap_or_else(|_| panic!("{flag} {n} is more than {}", u16::MAX))
+        };
+        match flag.as_str() {
+            "--cols" => opts.cols = cells(),
+            "--rows" => opts.rows = cells(),
+            "--cell-width" => opts.cell_width = cells(),
+            "--frames" => opts.frames = value(),
+            "--hud" => opts.hud = true,
+            other => panic!(
+                "unknown option {other} (use --cols, --rows, --cell-width, --frames, --hud)"
+            ),
+        }
+    }
+    opts
+}
+
+fn main() {
+    let opts = parse_args();
+    let width = opts.cols.checked_mul(opts.cell_width).unwrap_or_else(|| {
+        panic!(
+            "--cols {} x --cell-width {} is wider than {} cells",
+            opts.cols,
+            opts.cell_width,
+            u16::MAX
+        )
+    });
+    let height = opts.rows;
+    let region = Region::new(0, 0, width as i32, height as i32);
+
+    let grid = StressGrid::new(opts.cols, opts.rows).with_cell_width(opts.cell_width);
+    let styles = Styles::new();
+    let mut previous = Compositor::new(width, height);
+    let mut stats = FrameStats::new();
+    let mut total_cells = 0usize;
+    let mut total_time = Duration::ZERO;
+
+    for _ in 0..opts.frames {
+        let t0 = Instant::now();
+        grid.tick();
+        let t1 = Instant::now();
+        let strips = grid.render(region, &styles);
+        let mut current = previous.clone();
+        current.place_strips(&strips, &region);
+        let t2 = Instant::now();
+        let updates = current.diff(&previous);
+        let t3 = Instant::now();
+
+        let timings = FrameTimings {
+            event: t1 - t0,
+            render: t2 - t1,
+            flush: t3 - t2,
+            ..FrameTimings::default()
+        };
+        total_cells += updates.len();
+        total_time += timings.total();
+        stats.record_frame(timings, updates.len(), t3);
+        previous = current;
+    }
+
+    let frames = stats.frames.max(1) as f64;
+    println!(
+        "{} frames of {}x{} cells ({}x{} terminal)",
+        stats.frames, opts.cols, opts.rows, width, height
+    );
+    println!(
+        "avg frame {:.3}ms, avg cells updated {:.0}",
+        total_time.as_secs_f64() * 1000.0 / frames,
+        total_cells as f64 / frames
+    );
+    if opts.hud {
+        for line in PerfHud::new(&stats, &Runtime::current().stats()).lines() {
+            println!("{line}");
+        }
+    }
+}
@@ -20,7 +20,7 @@ pub(crate) enum CssValue {
     Float(f64, Span),
     /// A number followed by `%`.
     Percent(f64, Span),
-    /// A number followed by a unit identifier like `fr`, `vw`, `vh`.
+    /// A number followed by a unit identifier like `fr`, `vw`, `vh`, `u`.
     WithUnit(f64, String, Span),
     /// A string starting with `#` (color hex).
     Hash(String, Span),
@@ -149,7 +149,7 @@ pub(crate) fn parse_css_value(input: ParseStream) -> Result<CssValue> {
         let span = lit.span();
         let val: f64 = lit.base10_parse()?;
 
-        // Check for unit suffix: `%`, `fr`, `vw`, `vh`.
+        // Check for unit suffix: `%`, `fr`, `vw`, `vh`, `u`.
         if input.peek(Token![%]) {
             input.parse::<Token![%]>()?;
             return Ok(CssValue::Percent(val, span));
@@ -183,11 +183,11 @@ pub(crate) fn parse_css_value(input: ParseStream) -> Result<CssValue> {
             return Ok(CssValue::Percent(val as f64, span));
         }
         if input.peek(Ident) {
-            // Only consume if it looks like a unit (fr, vw, vh).
+            // Only consume if it looks like a unit (fr, vw, vh, u).
             let fork = input.fork();
             let ident: Ident = fork.parse()?;
             let unit_str = ident.to_string();
-            if matches!(unit_str.as_str(), "fr" | "vw" | "vh" | "px") {
+            if matches!(unit_str.as_str(), "fr" | "vw" | "vh" | "u" | "px") {
                 input.parse::<Ident>()?; // consume from real stream
                 return Ok(CssValue::WithUnit(val as f64, unit_str, span));
             }
@@ -230,7 +230,7 @@ pub(crate) fn parse_css_value(input: ParseStream) -> Result<CssValue> {
                 let fork = input.fork();
                 let ident: Ident = fork.parse()?;
                 let unit_str = ident.to_string();
-                if matches!(unit_str.as_str(), "fr" | "vw" | "vh" | "px") {
+                if matches!(unit_str.as_str(), "fr" | "vw" | "vh" | "u" | "px") {
                     input.parse::<Ident>()?;
                     return Ok(CssValue::WithUnit(val as f64, unit_str, span));
                 }
@@ -269,6 +269,11 @@ const KNOWN_PROPERTIES: &[&str] = &[
     "max-height",
     "margin",
     "padding",
+    "grid-size",
+    "grid-columns",
+    "grid-rows",
+    "column-span",
+    "row-span",
     "color",
     "background",
     "text-align",
@@ -293,6 +298,7 @@ fn scalar_from_value(val: &CssValue) -> Result<TokenStream> {
             "fr" => Ok(quote! { gilt_tui::css::scalar::Scalar::fr(#f as f32) }),
             "vw" => Ok(quote! { gilt_tui::css::scalar::Scalar::vw(#f as f32) }),
             "vh" => Ok(quote! { gilt_tui::css::scalar::Scalar::vh(#f as f32) }),
+            "u" => Ok(quote! { gilt_tui::css::scalar::Scalar::units(#f as f32) }),
             "px" => Ok(quote! { gilt_tui::css::scalar::Scalar::cells(#f as f32) }),
             _ => Err(Error::new(*span, format!("unknown unit `{}`", unit))),
         },
@@ -454,6 +460,48 @@ fn generate_declaration(decl: &CssDeclaration) -> Result<TokenStream> {
             Ok(quote! { __styles.#field = Some(#box_expr); })
         }
 
+        // --- Grid ---
+        "grid-size" => {
+            if decl.values.len() > 2 {
+                return Err(Error::new(
+                    decl.name_span,
+                    format!("`grid-size` expects 1 or 2 values, got {}", decl.values.len()),
+                ));
+            }
+            let columns = count_from_value(&decl.values[0])?;
+            let rows = match decl.values.get(1) {
+                Some(value) => {
+                    let rows = count_from_value(value)?;
+                    quote! { Some(#rows) }
+                }
+                None => quote! { None },
+            };
+            Ok(quote! {
+                __styles.grid_size_columns = Some(#columns);
+                __styles.grid_size_rows = #rows;
+            })
+        }
+        "grid-columns" | "grid-rows" => {
+            let field = Ident::new(&kebab_to_snake(&decl.name), decl.name_span);
+            let tracks = decl
+                .values
+                .iter()
+                .map(scalar_from_value)
+                .collect::<Result<Vec<_>>>()?;
+            Ok(quote! { __styles.#field = Some(vec![#(#tracks),*]); })
+        }
+        "column-span" | "row-span" => {
+            let field = Ident::new(&kebab_to_snake(&decl.name), decl.name_span);
+            if decl.values.len() != 1 {
+                return Err(Error::new(
+                    decl.name_span,
+                    format!("`{}` expects exactly one value", decl.name),
+                ));
+            }
+            let span = count_from_value(&decl.values[0])?;
+            Ok(quote! { __styles.#field = Some(#span); })
+        }
+
         // --- Text style ---
         "text-style" => {
             let val = single_ident(&decl.values, &decl.name)?;
@@ -586,6 +634,17 @@ fn value_to_string(val: &CssValue) -> Result<String> {
     }
 }
 
+/// Check a whole, positive number of grid tracks (`grid-size`, spans).
+fn count_from_value(val: &CssValue) -> Result<u16> {
+    match val {
+        CssValue::Integer(n, span) => u16::try_from(*n)
+            .ok()
+            .filter(|n| *n > 0)
+            .ok_or_else(|| Error::new(*span, "expected a positive whole number")),
+        other => Err(Error::new(other.span(), "expected a positive whole number")),
+    }
+}
+
 /// Generate an overflow variant token stream.
 fn overflow_variant(val: &str, css_val: &CssValue) -> Result<TokenStream> {
     match val {
@@ -849,6 +908,22 @@ mod tests {
         assert!(s.contains("bold : Some (true)"));
     }
 
+    #[test]
+    fn codegen_grid_properties() {
+        let result = gen(quote! {
+            grid-size: 3 2;
+            grid-columns: 10 1fr;
+            row-span: 2;
+        })
+        .unwrap();
+        let s = result.to_string();
+        assert!(s.contains("grid_size_columns = Some (3u16)"));
+        assert!(s.contains("grid_size_rows = Some (2u16)"));
+        assert!(s.contains("grid_columns = Some (vec !"));
+        assert!(s.contains("Scalar :: fr"));
+        assert!(s.contains("row_span = Some (2u16)"));
+    }
+
     // -----------------------------------------------------------------------
     // Error tests
     // -----------------------------------------------------------------------
@@ -874,6 +949,15 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("at least one property"));
     }
 
+    #[test]
+    fn error_invalid_grid_count() {
+        for tokens in [quote! { column-span: 0; }, quote! { grid-size: 1 2 3; }] {
+            assert!(gen(tokens).is_err());
+        }
+        let result = gen(quote! { row-span: 1.5; });
+        assert!(result.unwrap_err().to_string().contains("positive whole number"));
+    }
+
     #[test]
     fn error_too_many_padding_values() {
         let result = gen(quote! { padding: 1 2 3 4 5; });
@@ -1,4 +1,5 @@
-//! Proc macros for gilt-tui: `view!` RSX composition and `css!` inline CSS validation.
+//! Proc macros for gilt-tui: `view!` RSX composition, `css!` inline CSS validation,
+//! and `selector!` compile-time checked selector queries.
 //!
 //! This crate is not meant to be used directly — enable the `macros` feature on `gilt-tui`.
 
@@ -6,6 +7,7 @@ use proc_macro::TokenStream;
 
 mod view_macro;
 mod css_macro;
+mod selector_macro;
 
 /// RSX-style widget composition macro.
 ///
@@ -23,6 +25,23 @@ mod css_macro;
 /// - `title`, `label`, `content` — first such attribute becomes the `::new()` argument
 /// - Other string attributes become `.with_attr_name("value")` builder calls
 ///
+/// # Registry lookup
+///
+/// A leading `in registry;` builds every element by type name through that
+/// `WidgetRegistry` instead (see `LayoutLoader`), so widgets registered by
+/// plugin crates can be used. Attributes are checked against each widget's
+/// metadata at runtime, and the macro evaluates to a
+/// `Result<Vec<Box<dyn Widget>>, LayoutFileError>`:
+///
+/// ```ignore
+/// let widgets = view! {
+///     in app.widgets;
+///     <Container class="main">
+///         <Gauge value="42" />
+///     </Container>
+/// }?;
+/// ```
+///
 /// # Example
 ///
 /// ```ignore
@@ -68,3 +87,19 @@ pub fn css(input: TokenStream) -> TokenStream {
         .unwrap_or_else(|err| err.to_compile_error())
         .into()
 }
+
+/// Compile-time validated selector query.
+///
+/// Checks the selector syntax at compile time and expands to a
+/// `gilt_tui::css::query::Query`, so constant selectors never fail at runtime.
+///
+/// ```ignore
+/// let items = selector!("List > .item:hover");
+/// let found = app.query(&items);
+/// ```
+#[proc_macro]
+pub fn selector(input: TokenStream) -> TokenStream {
+    selector_macro::selector_impl(input.into())
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
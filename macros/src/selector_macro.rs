@@ -0,0 +1,206 @@
+//! CSS selector! macro: validate a selector list at compile time and build a Query.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Error, LitStr, Result};
+
+// ---------------------------------------------------------------------------
+// Validation
+// ---------------------------------------------------------------------------
+
+// These helpers mirror the regexes of the runtime tokenizer in
+// `gilt_tui::css::tokenizer`; a selector accepted here must lex the same way
+// there, or the generated `Query::compile(..).expect(..)` would panic.
+
+/// Whether `c` is skipped as whitespace (`[ \t\n\r\f]`).
+fn is_css_whitespace(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\n' | '\r' | '\x0c')
+}
+
+/// Whether `c` may start a selector identifier (`[a-zA-Z_]`).
+fn is_ident_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+/// Whether `c` may start a pseudo-class name (`:[a-zA-Z]`).
+fn is_pseudo_start(c: char) -> bool {
+    c.is_ascii_alphabetic()
+}
+
+/// Whether `c` may continue a selector identifier.
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+/// Validate a selector list such as `Container > .item:hover, #title`.
+///
+/// Mirrors the grammar accepted by the runtime CSS parser: compound selectors
+/// built from a type or `*` followed by `.class`, `#id`, and `:pseudo` parts,
+/// joined by whitespace (descendant) or `>` (child), separated by commas.
+///
+/// An `#id` starting with three or more hex digits (`#cafe`, `#abc123`) is
+/// rejected because the tokenizer lexes it as a hex colour.
+pub(crate) fn validate_selector(source: &str) -> std::result::Result<(), String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut i = 0;
+
+    let skip_ws = |i: &mut usize| {
+        while *i < chars.len() && is_css_whitespace(chars[*i]) {
+            *i += 1;
+        }
+    };
+
+    let name = |i: &mut usize, what: &str, start: fn(char) -> bool| {
+        if *i >= chars.len() || !start(chars[*i]) {
+            return Err(format!("expected {what} name at offset {i}"));
+        }
+        while *i < chars.len() && is_ident_char(chars[*i]) {
+            *i += 1;
+        }
+        Ok::<(), String>(())
+    };
+    let ident = |i: &mut usize, what: &str| name(i, what, is_ident_start);
+
+    skip_ws(&mut i);
+    if i == chars.len() {
+        return Err("selector is empty".into());
+    }
+
+    loop {
+        // One compound selector.
+        let start = i;
+        if i < chars.len() && chars[i] == '*' {
+            i += 1;
+        } else if i < chars.len() && is_ident_start(chars[i]) {
+            ident(&mut i, "type")?;
+        }
+        while i < chars.len() {
+            match chars[i] {
+                '.' => {
+                    i += 1;
+                    ident(&mut i, "class")?;
+                }
+                '#' => {
+                    i += 1;
+                    let hex = chars[i..].iter().take_while(|c| c.is_ascii_hexdigit()).count();
+                    if hex >= 3 {
+                        return Err(format!(
+                            "id at offset {} would be read as a hex colour",
+                            i - 1
+                        ));
+                    }
+                    ident(&mut i, "id")?;
+                }
+                ':' => {
+                    i += 1;
+                    name(&mut i, "pseudo-class", is_pseudo_start)?;
+                }
+                _ => break,
+            }
+        }
+        if i == start {
+            return Err(format!("expected a selector at offset {i}"));
+        }
+
+        // What follows: end, comma, or a combinator.
+        let before_ws = i;
+        skip_ws(&mut i);
+        if i == chars.len() {
+            return Ok(());
+        }
+        match chars[i] {
+            ',' => {
+                i += 1;
+                skip_ws(&mut i);
+            }
+            '>' => {
+                i += 1;
+                skip_ws(&mut i);
+            }
+            _ if i > before_ws => {} // descendant combinator
+            c => return Err(format!("unexpected '{c}' at offset {i}")),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Code generation
+// ---------------------------------------------------------------------------
+
+/// Main entry point for the selector! macro.
+pub(crate) fn selector_impl(input: TokenStream) -> Result<TokenStream> {
+    let lit: LitStr = syn::parse2(input)?;
+    let source = lit.value();
+
+    if let Err(message) = validate_selector(&source) {
+        return Err(Error::new(lit.span(), format!("invalid selector: {message}")));
+    }
+
+    Ok(quote! {
+        gilt_tui::css::query::Query::compile(#lit)
+            .expect("selector validated at compile time")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_selectors() {
+        for sel in [
+            "Button",
+            "*",
+            ".item",
+            "#title",
+            "Button.primary:hover",
+            "Container > .item:hover",
+            "Container .item",
+            "List>Item",
+            "Button, #title , .a.b",
+        ] {
+            assert!(validate_selector(sel).is_ok(), "{sel}");
+        }
+    }
+
+    #[test]
+    fn invalid_selectors() {
+        for sel in ["", "  ", "Container >", ".", "#1abc", "Button {", "a,", "> a"] {
+            assert!(validate_selector(sel).is_err(), "{sel}");
+        }
+    }
+
+    #[test]
+    fn tokenizer_mismatches_are_rejected() {
+        // Each of these lexes differently at runtime: hex-like ids become colours
+        // and a leading `-` is skipped by the tokenizer.
+        for sel in ["#cafe", "#bad", "#abc123", "Button#deadline", "-x", ".-x", "#-x", ":_x"] {
+            assert!(validate_selector(sel).is_err(), "{sel}");
+        }
+        for sel in ["#ab", "#title", "#ab-cd", "_x", ".a-b_c", ":focus-within"] {
+            assert!(validate_selector(sel).is_ok(), "{sel}");
+        }
+    }
+
+    #[test]
+    fn codegen_compiles_query() {
+        let out = selector_impl(quote! { "Container > .item" }).unwrap();
+        let s = out.to_string();
+        assert!(s.contains("Query :: compile"));
+        assert!(s.contains("\"Container > .item\""));
+    }
+
+    #[test]
+    fn codegen_reports_invalid_selector() {
+        let err = selector_impl(quote! { "Container >" }).unwrap_err();
+        assert!(err.to_string().contains("invalid selector"));
+    }
+
+    #[test]
+    fn codegen_rejects_hex_like_ids() {
+        for sel in ["#cafe", "#bad", "#abc123", ":_x", "-x"] {
+            let err = selector_impl(quote! { #sel }).unwrap_err();
+            assert!(err.to_string().contains("invalid selector"), "{sel}");
+        }
+    }
+}
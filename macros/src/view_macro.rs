@@ -1,9 +1,13 @@
 //! RSX view! macro: parse JSX-like syntax and generate gilt-tui builder code.
+//!
+//! With a leading `in registry;` the elements are instead looked up by type
+//! name in a `WidgetRegistry` at runtime, so widgets from plugin crates can be
+//! used alongside the built-in ones.
 
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::parse::{Parse, ParseStream};
-use syn::{Error, Ident, LitStr, Result, Token};
+use syn::{Error, Expr, Ident, LitStr, Result, Token};
 
 // ---------------------------------------------------------------------------
 // AST types
@@ -45,9 +49,10 @@ impl std::fmt::Debug for Element {
     }
 }
 
-/// The top-level view! input: a sequence of elements.
-#[derive(Debug)]
+/// The top-level view! input: an optional `in registry;` header and a
+/// sequence of elements.
 struct ViewInput {
+    registry: Option<Expr>,
     elements: Vec<Element>,
 }
 
@@ -64,11 +69,19 @@ const CONSTRUCTOR_ATTRS: &[&str] = &["title", "label", "content"];
 
 impl Parse for ViewInput {
     fn parse(input: ParseStream) -> Result<Self> {
+        let registry = if input.peek(Token![in]) {
+            input.parse::<Token![in]>()?;
+            let registry: Expr = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Some(registry)
+        } else {
+            None
+        };
         let mut elements = Vec::new();
         while !input.is_empty() {
             elements.push(parse_element(input)?);
         }
-        Ok(ViewInput { elements })
+        Ok(ViewInput { registry, elements })
     }
 }
 
@@ -226,6 +239,26 @@ fn generate_element(elem: &Element) -> TokenStream {
     }
 }
 
+/// Generate a `LayoutNode` for an element, to be built through a registry.
+fn generate_node(elem: &Element) -> TokenStream {
+    let tag = elem.tag.to_string();
+    let calls = elem.attrs.iter().map(|attr| {
+        let val = &attr.value;
+        match attr.name.to_string().as_str() {
+            "id" => quote! { .with_id(#val) },
+            "class" => quote! { .with_class(#val) },
+            name => quote! { .with_attr(#name, #val) },
+        }
+    });
+    let children = elem.children.iter().map(|child| {
+        let child_code = generate_node(child);
+        quote! { .with_child(#child_code) }
+    });
+    quote! {
+        gilt_tui::widget::layout_file::LayoutNode::new(#tag) #(#calls)* #(#children)*
+    }
+}
+
 /// Entry point: generate code for the entire view! macro.
 pub(crate) fn view_impl(input: TokenStream) -> Result<TokenStream> {
     let parsed: ViewInput = syn::parse2(input)?;
@@ -237,6 +270,14 @@ pub(crate) fn view_impl(input: TokenStream) -> Result<TokenStream> {
         ));
     }
 
+    if let Some(registry) = &parsed.registry {
+        let nodes = parsed.elements.iter().map(generate_node);
+        return Ok(quote! {
+            gilt_tui::widget::layout_file::LayoutLoader::new(&#registry)
+                .load_all(&[#(#nodes),*])
+        });
+    }
+
     let element_exprs: Vec<TokenStream> = parsed
         .elements
         .iter()
@@ -462,6 +503,29 @@ mod tests {
         assert!(code_str.contains("Footer :: new (\"Done\")"));
     }
 
+    #[test]
+    fn codegen_registry_view_builds_layout_nodes() {
+        let code_str = view_impl(quote! {
+            in app.widgets;
+            <Container id="main">
+                <Gauge class="wide" value="42" />
+            </Container>
+        })
+        .unwrap()
+        .to_string();
+        assert!(code_str.contains("LayoutLoader :: new (& app . widgets)"));
+        assert!(code_str.contains("LayoutNode :: new (\"Container\") . with_id (\"main\")"));
+        assert!(code_str.contains("LayoutNode :: new (\"Gauge\") . with_class (\"wide\")"));
+        assert!(code_str.contains("with_attr (\"value\" , \"42\")"));
+        assert!(!code_str.contains("gilt_tui :: widgets ::"));
+    }
+
+    #[test]
+    fn parse_error_registry_without_semicolon() {
+        let result = parse_view(quote! { in registry <Static /> });
+        assert!(result.is_err());
+    }
+
     #[test]
     fn codegen_empty_view_is_error() {
         let result = view_impl(quote! {});
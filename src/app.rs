@@ -2,15 +2,70 @@
 //!
 //! [`App`] ties together the screen, event dispatcher, key bindings, and driver.
 //! The `new_headless` constructor allows testing without a real terminal.
+//! [`App::set_mode`] switches between named [`Mode`]s.
 
+use std::any::Any;
+use std::collections::HashMap;
 use std::io;
+#[cfg(unix)]
+use std::io::Read;
+#[cfg(unix)]
+use std::os::fd::OwnedFd;
+use std::sync::mpsc::Receiver;
+use std::thread;
+use std::time::{Duration, Instant};
 
-use crate::event::binding::{BindingAction, KeyBindingRegistry};
+use crate::css::density::Density;
+use crate::css::query::{NodeRef, Query};
+use crate::dom::node::NodeId;
+use crate::dom::transaction::Transaction;
+use crate::error::Result;
+use crate::event::binding::{
+    ActiveBinding, BindingAction, BindingConflict, BindingHint, KeyBindingRegistry, KeyResolution,
+};
+use crate::event::coalesce::CoalesceConfig;
+use crate::event::delegate::{DelegateId, Delegates};
+use crate::event::gesture::{GestureConfig, GestureRecognizer};
 use crate::event::handler::EventDispatcher;
-use crate::event::input::InputEvent;
-use crate::event::message::{self, Envelope};
-use crate::render::driver::Driver;
+use crate::event::inbox::{Inbox, InboxSender};
+use crate::event::input::{
+    InputEvent, Key, KeyEvent, Modifiers, MouseAction, MouseBtn, MouseEvent,
+};
+use crate::event::message::{self, Envelope, Message};
+use crate::event::reader::{InputQueue, InputReader, InputSender};
+use crate::event::timer::{TimerHandle, Timers};
+use crate::event::translate::{KeyTable, KeyTranslator};
+use crate::event::worker::{self, WorkerContext, WorkerHandle};
+use crate::geometry::Region;
+use crate::i18n;
+use crate::mode::Mode;
+use crate::reactive::{
+    apply_sync_writes, batch, create_signal, create_sync_signal, ReadSignal, Runtime, SyncSignal,
+    WriteSignal,
+};
+use crate::render::budget::{BudgetMonitor, FrameBudget, WorkKind};
+use crate::render::compositor::Compositor;
+use crate::render::driver::{Driver, FlushPolicy};
+use crate::render::glyph::GlyphPolicy;
+use crate::render::stats::{FrameStats, FrameTimings};
+use crate::render::strip::CellStyle;
+use crate::render::transition::{SnapshotTransition, TransitionEffect};
 use crate::screen::Screen;
+use crate::widget::layout_file::{LayoutLoader, LayoutNode};
+use crate::widget::registry::WidgetRegistry;
+use crate::widget::rubber_band::{RegionSelected, RubberBand};
+use crate::widget::task::WidgetContext;
+use crate::widget::traits::Widget;
+use crate::widgets::command_palette::{Command, CommandPalette};
+use crate::widgets::context_menu::ContextMenu;
+use crate::widgets::panel::Panel;
+use crate::widgets::perf_hud::{PerfHud, HUD_WIDTH};
+
+/// Cells one scroll-wheel notch scrolls the container under the pointer.
+pub const WHEEL_SCROLL_LINES: i32 = 3;
+
+/// How long a focus announcement stays up.
+pub const ANNOUNCEMENT_DURATION: Duration = Duration::from_secs(3);
 
 // ---------------------------------------------------------------------------
 // AppConfig
@@ -25,6 +80,47 @@ pub struct AppConfig {
     pub css: Option<String>,
     /// Target frames per second for the render loop.
     pub fps: u32,
+    /// Interval between loop ticks while work is pending. `None` derives it
+    /// from `fps`.
+    pub tick_interval: Option<Duration>,
+    /// Sleep until an event or wake-up arrives instead of ticking at a fixed
+    /// rate whenever nothing is animating.
+    pub power_saving: bool,
+    /// Whether the performance HUD overlay starts visible.
+    pub perf_hud: bool,
+    /// Run on the alternate screen, with raw mode on and the cursor hidden,
+    /// restoring the normal screen when the app is dropped. Off by default,
+    /// so the app draws into the terminal as it finds it.
+    pub alt_screen: bool,
+    /// Opt into the kitty keyboard protocol (key releases, Super/Hyper,
+    /// disambiguated control keys) when the terminal supports it.
+    pub kitty_keyboard: bool,
+    /// Whether mouse reporting is enabled on startup.
+    pub mouse_capture: bool,
+    /// The UI density on startup.
+    pub density: Density,
+    /// Which input events [`App::handle_input_batch`] collapses.
+    pub coalesce: CoalesceConfig,
+    /// Double-click and drag thresholds for gesture messages.
+    pub gestures: GestureConfig,
+    /// How long handlers and renders may take before they are reported.
+    pub frame_budget: FrameBudget,
+    /// Whether Tab and the arrow keys wrap around at the ends of the focus
+    /// chain, or stop there.
+    pub focus_wrap: bool,
+    /// When the driver flushes frame output to the terminal.
+    pub flush_policy: FlushPolicy,
+    /// Skip flushing frames that produced no updates.
+    pub skip_idle_flush: bool,
+    /// Draw blinking and pulsing text steadily.
+    pub reduced_motion: bool,
+    /// The size of the root unit `1u`, in cells.
+    pub unit_scale: f32,
+    /// Read terminal input on a dedicated thread (see [`App::pump_input`]).
+    /// Turn off to read and feed events to [`App::handle_input`] yourself.
+    pub input_thread: bool,
+    /// How wide, ambiguous-width, and zero-width characters are drawn.
+    pub glyphs: GlyphPolicy,
 }
 
 impl Default for AppConfig {
@@ -33,6 +129,23 @@ impl Default for AppConfig {
             title: None,
             css: None,
             fps: 60,
+            tick_interval: None,
+            power_saving: false,
+            perf_hud: false,
+            alt_screen: false,
+            kitty_keyboard: false,
+            mouse_capture: false,
+            density: Density::Normal,
+            coalesce: CoalesceConfig::default(),
+            gestures: GestureConfig::default(),
+            frame_budget: FrameBudget::default(),
+            focus_wrap: true,
+            flush_policy: FlushPolicy::Auto,
+            skip_idle_flush: true,
+            reduced_motion: false,
+            unit_scale: 1.0,
+            input_thread: true,
+            glyphs: GlyphPolicy::default(),
         }
     }
 }
@@ -60,12 +173,136 @@ impl AppConfig {
         self.fps = fps;
         self
     }
+
+    /// Set the loop tick interval, overriding the one derived from `fps`
+    /// (builder).
+    pub fn with_tick_interval(mut self, interval: Duration) -> Self {
+        self.tick_interval = Some(interval);
+        self
+    }
+
+    /// Enable power-saving mode (builder).
+    pub fn with_power_saving(mut self, enabled: bool) -> Self {
+        self.power_saving = enabled;
+        self
+    }
+
+    /// The effective tick interval: `tick_interval`, or one frame at `fps`.
+    pub fn tick_period(&self) -> Duration {
+        self.tick_interval
+            .unwrap_or_else(|| Duration::from_secs(1) / self.fps.max(1))
+    }
+
+    /// Run on the alternate screen (builder).
+    pub fn with_alt_screen(mut self, enabled: bool) -> Self {
+        self.alt_screen = enabled;
+        self
+    }
+
+    /// Opt into the kitty keyboard protocol (builder).
+    pub fn with_kitty_keyboard(mut self, enabled: bool) -> Self {
+        self.kitty_keyboard = enabled;
+        self
+    }
+
+    /// Enable mouse reporting on startup (builder).
+    pub fn with_mouse_capture(mut self, enabled: bool) -> Self {
+        self.mouse_capture = enabled;
+        self
+    }
+
+    /// Show the performance HUD overlay on startup (builder).
+    pub fn with_perf_hud(mut self, enabled: bool) -> Self {
+        self.perf_hud = enabled;
+        self
+    }
+
+    /// Set which input events are coalesced per frame (builder).
+    pub fn with_coalesce(mut self, coalesce: CoalesceConfig) -> Self {
+        self.coalesce = coalesce;
+        self
+    }
+
+    /// Set the double-click and drag thresholds (builder).
+    pub fn with_gestures(mut self, gestures: GestureConfig) -> Self {
+        self.gestures = gestures;
+        self
+    }
+
+    /// Set the handler and render budgets (builder).
+    pub fn with_frame_budget(mut self, budget: FrameBudget) -> Self {
+        self.frame_budget = budget;
+        self
+    }
+
+    /// Set the UI density on startup (builder).
+    pub fn with_density(mut self, density: Density) -> Self {
+        self.density = density;
+        self
+    }
+
+    /// Set whether focus wraps around at the ends of the chain (builder).
+    pub fn with_focus_wrap(mut self, wrap: bool) -> Self {
+        self.focus_wrap = wrap;
+        self
+    }
+
+    /// Set when the driver flushes frame output (builder).
+    pub fn with_flush_policy(mut self, policy: FlushPolicy) -> Self {
+        self.flush_policy = policy;
+        self
+    }
+
+    /// Set whether frames with no updates skip the flush (builder).
+    pub fn with_skip_idle_flush(mut self, skip: bool) -> Self {
+        self.skip_idle_flush = skip;
+        self
+    }
+
+    /// Set whether blinking and pulsing text is drawn steadily (builder).
+    pub fn with_reduced_motion(mut self, reduced: bool) -> Self {
+        self.reduced_motion = reduced;
+        self
+    }
+
+    /// Set the size of the root unit `1u`, in cells (builder).
+    pub fn with_unit_scale(mut self, scale: f32) -> Self {
+        self.unit_scale = scale;
+        self
+    }
+
+    /// Set whether terminal input is read on a dedicated thread (builder).
+    pub fn with_input_thread(mut self, enabled: bool) -> Self {
+        self.input_thread = enabled;
+        self
+    }
+
+    /// Set how characters that may not take one column are drawn (builder).
+    pub fn with_glyph_policy(mut self, policy: GlyphPolicy) -> Self {
+        self.glyphs = policy;
+        self
+    }
 }
 
 // ---------------------------------------------------------------------------
 // App
 // ---------------------------------------------------------------------------
 
+/// A callback run when the event loop is idle.
+type IdleCallback = Box<dyn FnMut(&mut App)>;
+
+/// A callback run when a timer or interval fires.
+type TimerCallback = Box<dyn FnMut(&mut App)>;
+
+/// A callback run with the result a modal was closed with.
+type ModalCallback = Box<dyn FnOnce(&mut App, Option<Box<dyn Any>>)>;
+
+/// Both halves of the focus announcement signal.
+type AnnouncementSignal = (ReadSignal<Option<String>>, WriteSignal<Option<String>>);
+
+/// Both halves of the binding hints signal.
+type HintsSignal = (ReadSignal<Vec<BindingHint>>, WriteSignal<Vec<BindingHint>>);
+
 /// The main application struct.
 ///
 /// Owns the screen, driver, key bindings, event dispatcher, and config.
@@ -79,8 +316,74 @@ pub struct App {
     pub bindings: KeyBindingRegistry,
     /// Event dispatcher (message queue).
     pub dispatcher: EventDispatcher,
+    /// Selector-based handlers registered on containers.
+    delegates: Delegates,
     /// Application configuration.
     pub config: AppConfig,
+    /// Per-frame timing and output counters, shown by the perf HUD.
+    /// [`App::render_frame`] records every frame it presents.
+    pub stats: FrameStats,
+    /// Slow handler and render warnings, timed against `config.frame_budget`.
+    /// Delegated message handlers, timers, idle callbacks, and
+    /// [`App::render_widget`] are timed.
+    pub budget: BudgetMonitor,
+    /// Widget types available to layout files and runtime creation.
+    pub widgets: WidgetRegistry,
+    /// The frame last sent to the terminal, which [`App::render_frame`]
+    /// diffs against.
+    presented: Compositor,
+    /// Time spent handling input, messages, timers, and idle callbacks
+    /// since the last frame.
+    event_time: Duration,
+    /// Whether the performance HUD overlay is drawn.
+    perf_hud: bool,
+    /// Whether mouse events are being reported by the terminal.
+    mouse_capture: bool,
+    /// The open right-click menu, if any.
+    context_menu: Option<ContextMenu>,
+    /// Commands listed in the command palette, in registration order.
+    commands: Vec<Command>,
+    /// The open command palette, if any.
+    command_palette: Option<CommandPalette>,
+    /// The selection rectangle being dragged, if any.
+    rubber_band: Option<RubberBand>,
+    /// The container whose scrollbar thumb is being dragged, and the row
+    /// within the thumb where it was grabbed.
+    scrollbar_drag: Option<(NodeId, i32)>,
+    /// Turns routed mouse events into click and drag messages.
+    gestures: GestureRecognizer,
+    /// Result callbacks for open modals, by modal root.
+    modal_callbacks: Vec<(NodeId, ModalCallback)>,
+    /// Floating panels drawn above the screen, in insertion order.
+    panels: Vec<Panel>,
+    /// The running snapshot transition, if any.
+    transition: Option<SnapshotTransition>,
+    /// Messages posted from other threads and external event sources.
+    inbox: Inbox,
+    /// Callbacks run when the loop has nothing else to do.
+    idle_callbacks: Vec<IdleCallback>,
+    /// Pending timers and intervals.
+    timers: Timers<TimerCallback>,
+    /// The text last copied to the clipboard.
+    clipboard: Option<String>,
+    /// The focus announcement signal, once announcements are turned on.
+    announcement: Option<AnnouncementSignal>,
+    /// When the current focus announcement is cleared.
+    announcement_expires: Option<Instant>,
+    /// The binding hints signal, once hints are turned on.
+    hints: Option<HintsSignal>,
+    /// Key tables applied to key events before binding lookup.
+    key_translator: KeyTranslator,
+    /// Modes registered with `add_mode`, by name.
+    modes: HashMap<String, Mode>,
+    /// The active mode's name.
+    mode: Option<String>,
+    /// The reactive runtime input handlers and callbacks run in.
+    runtime: Runtime,
+    /// Input events from the reader thread or injected, not yet handled.
+    input: InputQueue,
+    /// The thread reading terminal input, while running.
+    input_reader: Option<InputReader>,
     /// Whether the app is still running.
     running: bool,
 }
@@ -88,311 +391,3303 @@ pub struct App {
 impl App {
     /// Create a new app with a real terminal driver.
     ///
-    /// Queries the terminal size to set the initial screen dimensions.
-    pub fn new(config: AppConfig) -> io::Result<Self> {
+    /// Queries the terminal size to set the initial screen dimensions and
+    /// compiles `config.css`, if any. With [`AppConfig::alt_screen`] set,
+    /// enters the alternate screen, which is left again when the app is
+    /// dropped.
+    pub fn new(config: AppConfig) -> Result<Self> {
         let (width, height) = Driver::terminal_size()?;
-        let driver = Driver::new()?;
-        Ok(Self {
-            screen: Screen::new(width, height),
+        let mut driver = Driver::new()?;
+        // Keyboard flags are per screen: push them once on the alternate
+        // screen; leaving it (on drop or suspend) pops them first.
+        if config.alt_screen {
+            driver.enter_alt_screen()?;
+            driver.hide_cursor()?;
+        }
+        if config.kitty_keyboard {
+            driver.enable_keyboard_enhancement()?;
+        }
+        driver.set_mouse_capture(config.mouse_capture)?;
+        driver.set_flush_policy(config.flush_policy);
+        driver.set_skip_idle_flush(config.skip_idle_flush);
+        let mut screen = Screen::new(width, height);
+        screen.set_density(config.density);
+        screen.focus.set_wrap(config.focus_wrap);
+        screen.set_reduced_motion(config.reduced_motion);
+        screen.set_unit_scale(config.unit_scale);
+        screen.compositor.set_glyph_policy(config.glyphs.clone());
+        if let Some(css) = &config.css {
+            screen.add_css(css)?;
+        }
+        let inbox = Inbox::new();
+        let presented = Compositor::new(screen.compositor.width, screen.compositor.height);
+        let mut app = Self {
+            screen,
             driver: Some(driver),
             bindings: KeyBindingRegistry::with_defaults(),
             dispatcher: EventDispatcher::new(),
+            delegates: Delegates::new(),
+            budget: BudgetMonitor::new(config.frame_budget),
+            perf_hud: config.perf_hud,
+            mouse_capture: config.mouse_capture,
+            context_menu: None,
+            commands: Vec::new(),
+            command_palette: None,
+            rubber_band: None,
+            scrollbar_drag: None,
+            gestures: GestureRecognizer::new(config.gestures),
+            modal_callbacks: Vec::new(),
+            panels: Vec::new(),
+            transition: None,
+            input: InputQueue::new(inbox.sender()),
+            input_reader: None,
+            inbox,
+            idle_callbacks: Vec::new(),
+            timers: Timers::new(),
+            clipboard: None,
+            announcement: None,
+            announcement_expires: None,
+            hints: None,
+            key_translator: KeyTranslator::new(),
+            modes: HashMap::new(),
+            mode: None,
+            runtime: Runtime::new(),
             config,
+            stats: FrameStats::new(),
+            widgets: WidgetRegistry::new(),
+            presented,
+            event_time: Duration::ZERO,
             running: true,
-        })
+        };
+        if app.config.input_thread {
+            app.start_input_reader()?;
+        }
+        Ok(app)
     }
 
     /// Create a headless app for testing (no terminal driver).
     ///
     /// Uses the given dimensions for the screen size.
     pub fn new_headless(width: u16, height: u16) -> Self {
+        let inbox = Inbox::new();
         Self {
             screen: Screen::new(width, height),
             driver: None,
             bindings: KeyBindingRegistry::with_defaults(),
             dispatcher: EventDispatcher::new(),
+            delegates: Delegates::new(),
             config: AppConfig::default(),
+            stats: FrameStats::new(),
+            budget: BudgetMonitor::new(FrameBudget::default()),
+            widgets: WidgetRegistry::new(),
+            presented: Compositor::new(width, height),
+            event_time: Duration::ZERO,
+            perf_hud: false,
+            mouse_capture: false,
+            context_menu: None,
+            commands: Vec::new(),
+            command_palette: None,
+            rubber_band: None,
+            scrollbar_drag: None,
+            gestures: GestureRecognizer::default(),
+            modal_callbacks: Vec::new(),
+            panels: Vec::new(),
+            transition: None,
+            input: InputQueue::new(inbox.sender()),
+            input_reader: None,
+            inbox,
+            idle_callbacks: Vec::new(),
+            timers: Timers::new(),
+            clipboard: None,
+            announcement: None,
+            announcement_expires: None,
+            hints: None,
+            key_translator: KeyTranslator::new(),
+            modes: HashMap::new(),
+            mode: None,
+            runtime: Runtime::new(),
             running: true,
         }
     }
 
+    /// Use `runtime` instead of the app's own (builder), e.g. to share
+    /// signals with another app or with code running outside it.
+    pub fn with_runtime(mut self, runtime: Runtime) -> Self {
+        self.runtime = runtime;
+        self
+    }
+
+    /// The reactive runtime this app's signals and effects live in.
+    pub fn runtime(&self) -> &Runtime {
+        &self.runtime
+    }
+
+    /// Run `f` with the app's runtime current, so signals it creates or
+    /// writes belong to this app. Input, message, and idle handling already
+    /// run inside it.
+    pub fn enter<R>(&mut self, f: impl FnOnce(&mut App) -> R) -> R {
+        let runtime = self.runtime.clone();
+        runtime.run(|| f(self))
+    }
+
+    /// Run `f`, which may mutate many widgets, signals, and DOM nodes, then
+    /// bring styles and layout up to date in a single pass.
+    ///
+    /// While `f` runs, screen transactions are invalidated together at the
+    /// end (see [`Screen::begin_batch`]) and signal effects are deferred as
+    /// with [`batch`], so no intermediate restyle, layout, or repaint happens.
+    /// Nested calls only run the pass when the outermost one returns.
+    pub fn batch_update<R>(&mut self, f: impl FnOnce(&mut App) -> R) -> Result<R> {
+        self.screen.begin_batch();
+        let result = self.enter(|app| {
+            let mut result = None;
+            batch(|| result = Some(f(app)));
+            result.expect("batch runs its closure")
+        });
+        self.screen.end_batch();
+        if !self.screen.is_batching() {
+            self.screen.restyle();
+            self.screen.compute_layout()?;
+        }
+        Ok(result)
+    }
+
     /// Handle an input event by resolving key bindings and pushing messages.
     ///
     /// For key events, looks up the binding and converts it to a message.
     /// For resize events, updates the screen dimensions.
-    /// Other events are currently ignored.
+    /// For mouse events, updates the `:hover` node, scrolls the scrollable
+    /// container under the pointer on wheel events, opens context menus on
+    /// right-click, starts rubber-band selections on left presses over a
+    /// rubber-band container's background, and applies the screen's focus
+    /// policy. While a context menu is open it receives all key and mouse
+    /// input, and while a rubber-band selection is being dragged it receives
+    /// all mouse input and Escape. Escape closes an open modal without a
+    /// result; otherwise floating panels get the first look at mouse events
+    /// over them and at keys for the topmost panel. Other events are
+    /// currently ignored. Afterwards the `:focus` node follows any focus
+    /// change.
     pub fn handle_input(&mut self, event: InputEvent) {
+        let start = Instant::now();
+        self.enter(|app| app.dispatch_input(event));
+        self.sync_focus();
+        self.event_time += start.elapsed();
+    }
+
+    /// Route one input event; see [`App::handle_input`].
+    fn dispatch_input(&mut self, event: InputEvent) {
+        if self.command_palette.is_some() {
+            match event {
+                InputEvent::Key(ke) => return self.handle_command_palette_key(&ke),
+                InputEvent::Mouse(me) => return self.handle_command_palette_mouse(&me),
+                _ => {}
+            }
+        }
+        if self.context_menu.is_some() {
+            match event {
+                InputEvent::Key(ke) => return self.handle_context_menu_key(&ke),
+                InputEvent::Mouse(me) => return self.handle_context_menu_mouse(&me),
+                _ => {}
+            }
+        }
+        if self.rubber_band.is_some() {
+            match event {
+                InputEvent::Mouse(me) => return self.handle_rubber_band_mouse(&me),
+                InputEvent::Key(ke) if ke.code == Key::Escape => {
+                    return self.cancel_rubber_band()
+                }
+                _ => {}
+            }
+        }
+        if self.scrollbar_drag.is_some() {
+            if let InputEvent::Mouse(me) = event {
+                return self.handle_scrollbar_mouse(&me);
+            }
+        }
+        if let InputEvent::Key(ke) = &event {
+            if ke.code == Key::Escape && self.screen.modal().is_some() {
+                self.close_modal::<()>(None);
+                return;
+            }
+        }
+        // Panels float outside the DOM, so none is inside an open modal.
+        let consumed = self.screen.modal().is_none()
+            && match &event {
+                InputEvent::Key(ke) => self.handle_panel_key(ke),
+                InputEvent::Mouse(me) => self.handle_panel_mouse(me),
+                _ => false,
+            };
+        if consumed {
+            return;
+        }
         match event {
             InputEvent::Key(ke) => {
-                if let Some(action) = self.bindings.resolve(&ke) {
-                    // We need to create a sender NodeId. Use root if available,
-                    // or skip if the DOM is empty.
-                    let sender = match self.screen.dom.root() {
-                        Some(root) => root,
-                        None => return,
-                    };
-
-                    match action {
-                        BindingAction::Quit => {
-                            self.dispatcher
-                                .push(Envelope::new(message::Quit, sender));
-                        }
-                        BindingAction::FocusNext => {
-                            self.dispatcher
-                                .push(Envelope::new(message::FocusNext, sender));
-                        }
-                        BindingAction::FocusPrevious => {
-                            self.dispatcher
-                                .push(Envelope::new(message::FocusPrevious, sender));
-                        }
-                        BindingAction::Custom(name) => {
-                            self.dispatcher
-                                .push(Envelope::new(message::Custom::new(name.clone()), sender));
-                        }
-                        BindingAction::Message(factory) => {
-                            self.dispatcher.push(Envelope {
-                                message: factory(),
-                                sender,
-                                target: None,
-                                handled: false,
-                            });
-                        }
-                    }
+                let focus = self.screen.focused_node();
+                let ke = self.key_translator.translate(&ke);
+                match self.bindings.press(&ke, focus, &self.screen.dom) {
+                    KeyResolution::Action { action, node } => self.run_binding(action, node),
+                    KeyResolution::Pending => {}
+                    KeyResolution::Unbound => self.move_focus_in_group(&ke),
                 }
             }
             InputEvent::Resize { width, height } => {
                 self.screen.resize(width, height);
             }
-            // Mouse, focus, paste events are currently unhandled at the app level.
+            InputEvent::Mouse(me) => {
+                self.screen.update_hover(me.x, me.y);
+                if let MouseAction::ScrollUp | MouseAction::ScrollDown = me.kind {
+                    self.scroll_wheel(&me);
+                    return;
+                }
+                if me.kind == MouseAction::Down(MouseBtn::Right) {
+                    if let Some(node) = self.screen.context_menu_at(me.x, me.y) {
+                        self.open_context_menu(node, me.x as i32, me.y as i32);
+                        return;
+                    }
+                }
+                if me.kind == MouseAction::Down(MouseBtn::Left) && self.start_scrollbar_drag(&me) {
+                    return;
+                }
+                if me.kind == MouseAction::Down(MouseBtn::Left) {
+                    if let Some(node) = self.screen.rubber_band_at(me.x, me.y) {
+                        self.start_rubber_band(node, me.x as i32, me.y as i32);
+                    }
+                }
+                self.screen.handle_mouse_focus(&me);
+                if self.rubber_band.is_none() {
+                    self.send_gestures(&me, Instant::now());
+                }
+            }
+            // Focus and paste events are currently unhandled at the app level.
             _ => {}
         }
     }
 
-    /// Process all pending messages in the dispatcher.
-    ///
-    /// Built-in messages (Quit, FocusNext, FocusPrevious) are handled directly.
-    /// Other messages are currently ignored (widgets will handle them in future phases).
-    pub fn handle_messages(&mut self) {
-        let messages = self.dispatcher.drain();
-        for envelope in messages {
-            if envelope.downcast_ref::<message::Quit>().is_some() {
-                self.running = false;
-            } else if envelope.downcast_ref::<message::FocusNext>().is_some() {
-                self.screen.focus.focus_next();
-            } else if envelope.downcast_ref::<message::FocusPrevious>().is_some() {
-                self.screen.focus.focus_previous();
+    /// Scroll the scrollable container under the pointer by
+    /// [`WHEEL_SCROLL_LINES`]; Shift+wheel scrolls horizontally.
+    fn scroll_wheel(&mut self, me: &MouseEvent) {
+        let delta = match me.kind {
+            MouseAction::ScrollUp => -WHEEL_SCROLL_LINES,
+            _ => WHEEL_SCROLL_LINES,
+        };
+        let horizontal = me.modifiers.contains(Modifiers::SHIFT);
+        self.screen.scroll_wheel(me.x, me.y, delta, horizontal);
+    }
+
+    /// Start dragging the scrollbar under a left press, if any. Pressing the
+    /// track first jumps the thumb's middle to the pointer.
+    fn start_scrollbar_drag(&mut self, me: &MouseEvent) -> bool {
+        let Some(container) = self.screen.scrollbar_at(me.x, me.y) else {
+            return false;
+        };
+        let (Some(bar), Some((start, length))) = (
+            self.screen.scrollbar_region(container),
+            self.screen.scrollbar_thumb(container),
+        ) else {
+            return false;
+        };
+        let row = me.y as i32 - bar.y;
+        let grab = if (start..start + length).contains(&row) {
+            row - start
+        } else {
+            self.screen.drag_scrollbar(container, row - length / 2);
+            length / 2
+        };
+        self.gestures.cancel();
+        self.scrollbar_drag = Some((container, grab));
+        true
+    }
+
+    /// Mouse handling during a scrollbar drag: left drags move the thumb
+    /// with the pointer, and releasing ends the drag.
+    fn handle_scrollbar_mouse(&mut self, me: &MouseEvent) {
+        let Some((container, grab)) = self.scrollbar_drag else {
+            return;
+        };
+        match me.kind {
+            MouseAction::Drag(MouseBtn::Left) => {
+                if let Some(bar) = self.screen.scrollbar_region(container) {
+                    self.screen.drag_scrollbar(container, me.y as i32 - bar.y - grab);
+                }
             }
-            // Refresh and Custom messages are noted but not yet actionable
-            // at this phase. They will be handled when widgets can process them.
+            MouseAction::Up(MouseBtn::Left) => self.scrollbar_drag = None,
+            _ => {}
         }
     }
 
-    /// Whether the app should quit.
-    pub fn should_quit(&self) -> bool {
-        !self.running
+    /// Whether a scrollbar thumb is being dragged.
+    pub fn is_dragging_scrollbar(&self) -> bool {
+        self.scrollbar_drag.is_some()
     }
 
-    /// Request the app to quit.
-    pub fn request_quit(&mut self) {
-        self.running = false;
+    /// Feed a routed mouse event to the gesture recognizer and send what it
+    /// recognizes from the node where each gesture began (the root if none).
+    fn send_gestures(&mut self, me: &MouseEvent, now: Instant) {
+        for gesture in self.gestures.feed(me, now) {
+            let (x, y) = gesture.origin();
+            let Some(sender) = self.screen.widget_at(x, y).or(self.screen.dom.root()) else {
+                continue;
+            };
+            self.dispatcher.push(Envelope {
+                message: gesture.into_message(),
+                sender,
+                target: None,
+                handled: false,
+            });
+        }
     }
 
-    /// Whether the app has a terminal driver (not headless).
-    pub fn has_driver(&self) -> bool {
-        self.driver.is_some()
+    /// Change the double-click and drag thresholds.
+    pub fn set_gesture_config(&mut self, config: GestureConfig) {
+        self.config.gestures = config;
+        self.gestures.set_config(config);
     }
-}
 
-// ===========================================================================
-// Tests
-// ===========================================================================
+    /// Turn a matched binding into a message, sent from the node whose scope
+    /// the binding came from (the root for global bindings).
+    fn run_binding(&mut self, action: BindingAction, node: Option<NodeId>) {
+        let message: Box<dyn Message> = match action {
+            BindingAction::CommandPalette => {
+                self.open_command_palette();
+                return;
+            }
+            BindingAction::Quit => Box::new(message::Quit),
+            BindingAction::FocusNext => Box::new(message::FocusNext),
+            BindingAction::FocusPrevious => Box::new(message::FocusPrevious),
+            BindingAction::Custom(name) => Box::new(message::Custom::new(name)),
+            BindingAction::Message(factory) => factory(),
+        };
+        let Some(sender) = node.or_else(|| self.screen.dom.root()) else {
+            return;
+        };
+        self.dispatcher.push(Envelope {
+            message,
+            sender,
+            target: None,
+            handled: false,
+        });
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::dom::node::NodeData;
-    use crate::event::input::{Key, KeyEvent, Modifiers};
-    use crate::event::message::{FocusNext, FocusPrevious, Quit, Refresh};
+    /// The key bindings reachable from the focused node, closest scope first
+    /// and without shadowed ones, e.g. for a footer or help screen.
+    pub fn active_bindings(&self) -> Vec<ActiveBinding> {
+        self.bindings
+            .active_bindings(self.screen.focused_node(), &self.screen.dom)
+    }
 
-    fn headless_app() -> App {
-        App::new_headless(80, 24)
+    /// Translate keys through `table` before looking up bindings, e.g. to
+    /// make `ctrl+1` fire on a layout that types `١`. Replaces a table with
+    /// the same name; tables apply in the order they were first added.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// app.add_key_table(KeyTable::new("arabic-digits").with_digits('٠'));
+    /// ```
+    pub fn add_key_table(&mut self, table: KeyTable) {
+        self.key_translator.add(table);
     }
 
-    fn headless_app_with_dom() -> App {
-        let mut app = App::new_headless(80, 24);
-        let root = app
-            .screen
-            .dom
-            .insert(NodeData::new("Root").focusable(false));
-        let _a = app
-            .screen
-            .dom
-            .insert_child(root, NodeData::new("A").focusable(true));
-        let _b = app
-            .screen
-            .dom
-            .insert_child(root, NodeData::new("B").focusable(true));
-        let _c = app
-            .screen
-            .dom
-            .insert_child(root, NodeData::new("C").focusable(true));
-        app.screen.focus.rebuild(&app.screen.dom);
-        app
+    /// Stop translating keys through the table called `name`. Returns
+    /// whether there was one.
+    pub fn remove_key_table(&mut self, name: &str) -> bool {
+        self.key_translator.remove(name)
     }
 
-    // ── Construction ─────────────────────────────────────────────────
+    /// The key tables applied before binding lookup.
+    pub fn key_translator(&self) -> &KeyTranslator {
+        &self.key_translator
+    }
 
-    #[test]
-    fn headless_app_no_driver() {
-        let app = headless_app();
-        assert!(!app.has_driver());
-        assert!(!app.should_quit());
+    /// Move focus within the focused node's `.focus-group` on an unmodified
+    /// arrow key.
+    fn move_focus_in_group(&mut self, key: &KeyEvent) {
+        if key.modifiers != Modifiers::NONE {
+            return;
+        }
+        let focus = &mut self.screen.focus;
+        match key.code {
+            Key::Down | Key::Right => focus.focus_next_in_group(),
+            Key::Up | Key::Left => focus.focus_previous_in_group(),
+            _ => None,
+        };
     }
 
-    #[test]
-    fn headless_app_screen_size() {
-        let app = App::new_headless(120, 40);
-        assert_eq!(app.screen.compositor.width, 120);
-        assert_eq!(app.screen.compositor.height, 40);
+    /// Keys bound twice to different actions, where the later binding won.
+    ///
+    /// Covers every binding registered through [`App::bindings`], whether by
+    /// widgets, screens, or keymap files; list them in a help screen or log
+    /// them at startup.
+    pub fn binding_conflicts(&self) -> &[BindingConflict] {
+        self.bindings.conflicts()
     }
 
-    #[test]
-    fn headless_app_has_default_bindings() {
-        let app = headless_app();
-        assert_eq!(app.bindings.len(), 3);
+    /// Handle every input event read in one frame, collapsing floods of
+    /// mouse moves, drags, and resizes per `config.coalesce` first so slow
+    /// handlers only see the latest of each.
+    pub fn handle_input_batch(&mut self, events: Vec<InputEvent>) {
+        for event in self.config.coalesce.apply(events) {
+            self.handle_input(event);
+        }
     }
 
-    // ── request_quit / should_quit ───────────────────────────────────
+    /// Handle every event the input reader or an [`InputSender`] queued
+    /// since the last call, as one [`handle_input_batch`](Self::handle_input_batch).
+    /// Returns how many events were queued.
+    ///
+    /// Queued input wakes [`wait_for_work`](Self::wait_for_work), so the loop
+    /// answers keys as soon as they arrive instead of on its next tick.
+    pub fn pump_input(&mut self) -> usize {
+        let events = self.input.drain();
+        let count = events.len();
+        if count > 0 {
+            self.handle_input_batch(events);
+        }
+        count
+    }
 
-    #[test]
-    fn request_quit() {
-        let mut app = headless_app();
-        assert!(!app.should_quit());
-        app.request_quit();
-        assert!(app.should_quit());
+    /// A handle for injecting synthetic input events from any thread, e.g.
+    /// a test driver or remote control. They are handled by
+    /// [`pump_input`](Self::pump_input) like events read from the terminal.
+    pub fn input_sender(&self) -> InputSender {
+        self.input.sender()
     }
 
-    // ── handle_input: key events ─────────────────────────────────────
+    /// Start reading terminal input on a dedicated thread, if not already
+    /// reading. [`App::new`] does this unless `config.input_thread` is off.
+    ///
+    /// A reader stopped by [`stop_input_reader`](Self::stop_input_reader)
+    /// restarts with the source it had.
+    pub fn start_input_reader(&mut self) -> io::Result<()> {
+        match self.input_reader.as_mut() {
+            Some(reader) => reader.restart(),
+            None => {
+                self.input_reader = Some(InputReader::spawn(self.input.sender())?);
+                Ok(())
+            }
+        }
+    }
 
-    #[test]
-    fn handle_input_ctrl_c_produces_quit_message() {
-        let mut app = headless_app_with_dom();
-        let event = InputEvent::Key(KeyEvent::new(Key::Char('c'), Modifiers::CTRL));
-        app.handle_input(event);
+    /// Read input with `reader` instead of the terminal reader, e.g. one
+    /// made by [`InputReader::spawn_with`]. Any previous reader is stopped.
+    pub fn set_input_reader(&mut self, reader: InputReader) {
+        self.input_reader = Some(reader);
+    }
 
-        assert_eq!(app.dispatcher.pending_count(), 1);
-        let messages = app.dispatcher.drain();
-        assert!(messages[0].downcast_ref::<Quit>().is_some());
+    /// Stop the input reader thread and wait for it to end. Injected events
+    /// are still accepted.
+    pub fn stop_input_reader(&mut self) {
+        if let Some(reader) = self.input_reader.as_mut() {
+            reader.stop();
+        }
     }
 
-    #[test]
-    fn handle_input_tab_produces_focus_next_message() {
-        let mut app = headless_app_with_dom();
-        let event = InputEvent::Key(KeyEvent::new(Key::Tab, Modifiers::NONE));
-        app.handle_input(event);
+    /// Whether the input reader thread is running.
+    pub fn is_reading_input(&self) -> bool {
+        self.input_reader.as_ref().is_some_and(InputReader::is_running)
+    }
 
-        let messages = app.dispatcher.drain();
-        assert_eq!(messages.len(), 1);
-        assert!(messages[0].downcast_ref::<FocusNext>().is_some());
+    /// Handle `M` messages bubbling up through `container` from senders that
+    /// match `selector`, e.g. every `Button` in a dynamic toolbar. The handler
+    /// returns `true` to stop the message there; see [`crate::event::delegate`].
+    pub fn on<M: Message>(
+        &mut self,
+        container: NodeId,
+        selector: &str,
+        handler: impl FnMut(&M, NodeId) -> bool + 'static,
+    ) -> Result<DelegateId> {
+        let id = self.delegates.on(container, selector, handler)?;
+        Ok(id)
     }
 
-    #[test]
-    fn handle_input_backtab_produces_focus_previous_message() {
-        let mut app = headless_app_with_dom();
-        let event = InputEvent::Key(KeyEvent::new(Key::BackTab, Modifiers::NONE));
-        app.handle_input(event);
+    /// Remove a handler registered with [`App::on`]. Returns whether it was
+    /// registered.
+    pub fn off(&mut self, id: DelegateId) -> bool {
+        self.delegates.off(id)
+    }
 
-        let messages = app.dispatcher.drain();
-        assert_eq!(messages.len(), 1);
-        assert!(messages[0].downcast_ref::<FocusPrevious>().is_some());
+    /// Process all pending messages in the dispatcher.
+    ///
+    /// Messages first bubble through delegated handlers (see [`App::on`]);
+    /// those marked handled there go no further.
+    ///
+    /// Built-in messages (Quit, FocusNext, FocusPrevious) are handled directly.
+    /// Other messages are currently ignored (widgets will handle them in future phases).
+    ///
+    /// Every message is processed even if one fails; the first error is
+    /// returned (e.g. a terminal write failing while toggling mouse capture).
+    pub fn handle_messages(&mut self) -> Result<()> {
+        let start = Instant::now();
+        let result = self.enter(Self::dispatch_messages);
+        self.sync_focus();
+        self.event_time += start.elapsed();
+        result
     }
 
-    #[test]
-    fn handle_input_unbound_key_no_message() {
-        let mut app = headless_app_with_dom();
-        let event = InputEvent::Key(KeyEvent::new(Key::Char('z'), Modifiers::NONE));
-        app.handle_input(event);
+    /// Handle every pending message; see [`App::handle_messages`].
+    fn dispatch_messages(&mut self) -> Result<()> {
+        let mut first_error = None;
+        let messages = self.dispatcher.drain();
+        for mut envelope in messages {
+            self.timed_handler(Some(envelope.sender), |app| {
+                app.delegates.dispatch(&mut envelope, &app.screen.dom)
+            });
+            if envelope.handled {
+                continue;
+            }
+            if envelope.downcast_ref::<message::Quit>().is_some()
+                || envelope.downcast_ref::<message::InputFailed>().is_some()
+            {
+                self.request_quit();
+            } else if envelope.downcast_ref::<message::FocusNext>().is_some() {
+                self.screen.focus.focus_next();
+            } else if envelope.downcast_ref::<message::FocusPrevious>().is_some() {
+                self.screen.focus.focus_previous();
+            } else if envelope.downcast_ref::<message::TogglePerfHud>().is_some() {
+                self.toggle_perf_hud();
+            } else if let Some(copy) = envelope.downcast_ref::<message::CopyToClipboard>() {
+                if let Err(error) = self.copy_to_clipboard(&copy.0) {
+                    first_error.get_or_insert(error);
+                }
+            } else if envelope.downcast_ref::<message::CycleDensity>().is_some() {
+                self.screen.cycle_density();
+            } else if envelope.downcast_ref::<message::ToggleMouseCapture>().is_some() {
+                // A failed terminal write leaves capture in its previous state.
+                if let Err(error) = self.toggle_mouse_capture() {
+                    first_error.get_or_insert(error);
+                }
+            }
+            // Refresh and Custom messages are noted but not yet actionable
+            // at this phase. They will be handled when widgets can process them.
+        }
+        first_error.map_or(Ok(()), Err)
+    }
 
-        assert!(app.dispatcher.is_empty());
+    // -----------------------------------------------------------------------
+    // External event sources
+    // -----------------------------------------------------------------------
+
+    /// A handle other threads can use to post messages and wake the loop.
+    pub fn inbox_sender(&self) -> InboxSender {
+        self.inbox.sender()
+    }
+
+    /// Forward every value received on `receiver` into the app as a message.
+    ///
+    /// A background thread blocks on the channel, so the loop is only woken
+    /// when a value actually arrives. The thread exits when the channel's
+    /// senders are dropped, or with the next value after the app is gone;
+    /// until then it stays blocked on the channel.
+    pub fn watch_channel<T, M>(
+        &self,
+        receiver: Receiver<T>,
+        mut map: impl FnMut(T) -> M + Send + 'static,
+    ) where
+        T: Send + 'static,
+        M: Message,
+    {
+        let inbox = self.inbox.sender();
+        thread::spawn(move || {
+            for value in receiver {
+                if !inbox.send(map(value)) {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Create a signal in the app's runtime that worker threads can write
+    /// through the returned [`SyncSignal`]. Each write wakes the loop and is
+    /// applied by the next [`pump_external`](Self::pump_external).
+    pub fn create_sync_signal<T: Send + 'static>(
+        &mut self,
+        initial: T,
+    ) -> (ReadSignal<T>, SyncSignal<T>) {
+        let (read, write) = self.enter(|_| create_sync_signal(initial));
+        (read, write.with_waker(self.inbox.sender()))
+    }
+
+    /// Read from `fd` on a background thread, turning each chunk of bytes
+    /// into an optional message (inotify, sockets, subprocess pipes).
+    ///
+    /// The thread owns the descriptor and exits at end-of-file, on a read
+    /// error, or after the first message produced once the app is gone. A
+    /// quiet descriptor keeps it blocked in `read` even then, holding the
+    /// descriptor open: close the writing end (or otherwise make the read
+    /// return) to stop it.
+    #[cfg(unix)]
+    pub fn watch_fd<M: Message>(
+        &self,
+        fd: impl Into<OwnedFd>,
+        mut map: impl FnMut(&[u8]) -> Option<M> + Send + 'static,
+    ) {
+        let inbox = self.inbox.sender();
+        let mut file = std::fs::File::from(fd.into());
+        thread::spawn(move || {
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = match file.read(&mut buf) {
+                    Ok(0) => break,
+                    Ok(n) => n,
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(_) => break,
+                };
+                if let Some(message) = map(&buf[..n]) {
+                    if !inbox.send(message) {
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
+    /// Block until an external source posts or wakes the app, or `timeout`
+    /// elapses (`None` waits indefinitely). Returns whether a wake-up arrived.
+    pub fn wait_external(&self, timeout: Option<Duration>) -> bool {
+        self.inbox.wait(timeout)
+    }
+
+    /// Move messages posted by external sources onto the dispatcher queue,
+    /// sent from the DOM root, or from the posting node for widget tasks
+    /// (messages for nodes that have been removed are dropped). Returns how
+    /// many were moved.
+    ///
+    /// Values written to [`SyncSignal`]s since the last call are applied
+    /// first. Messages stay in the inbox while the DOM is empty.
+    pub fn pump_external(&mut self) -> usize {
+        self.enter(|_| apply_sync_writes());
+        let Some(root) = self.screen.dom.root() else {
+            return 0;
+        };
+        let mut count = 0;
+        for (node, message) in self.inbox.drain_posted() {
+            let sender = match node {
+                // Messages from tasks of nodes that have since been removed
+                // have nowhere to go.
+                Some(node) if self.screen.dom.get(node).is_none() => continue,
+                Some(node) => node,
+                None => root,
+            };
+            self.dispatcher.push(Envelope {
+                message,
+                sender,
+                target: None,
+                handled: false,
+            });
+            count += 1;
+        }
+        count
+    }
+
+    /// Run `work` on a background thread and deliver the message it returns
+    /// from the DOM root on a later [`pump_external`](Self::pump_external),
+    /// so slow work (HTTP requests, file scans) never blocks rendering.
+    ///
+    /// A worker that panics delivers [`WorkerFailed`](worker::WorkerFailed)
+    /// instead, and a cancelled one delivers nothing. For work tied to a
+    /// widget's lifetime use [`widget_ctx`](Self::widget_ctx).
+    pub fn spawn_worker<M: Message>(
+        &self,
+        work: impl FnOnce(&WorkerContext) -> M + Send + 'static,
+    ) -> WorkerHandle {
+        worker::spawn_worker(self.inbox.sender(), work)
+    }
+
+    /// Services for code acting on behalf of `node`, such as spawning
+    /// background tasks that are cancelled when the node unmounts.
+    pub fn widget_ctx(&mut self, node: NodeId) -> WidgetContext<'_> {
+        WidgetContext::new(node, &mut self.screen.tasks, self.inbox.sender())
+    }
+
+    // -----------------------------------------------------------------------
+    // Loop cadence
+    // -----------------------------------------------------------------------
+
+    /// Whether animations, a transition, or blinking or pulsing text need
+    /// frames at the tick rate.
+    pub fn is_animating(&self) -> bool {
+        !self.screen.animator.is_empty()
+            || self.transition.is_some()
+            || self.screen.has_text_effects()
+    }
+
+    /// Whether there is no queued message, dirty region, or animation.
+    pub fn is_idle(&self) -> bool {
+        self.dispatcher.is_empty() && !self.screen.compositor.is_dirty() && !self.is_animating()
+    }
+
+    /// How long the loop may sleep before its next iteration.
+    ///
+    /// Zero while work is queued, one tick while animating, and otherwise one
+    /// tick, or `None` (sleep until woken) in power-saving mode. Never longer
+    /// than the wait for the next timer.
+    pub fn poll_timeout(&self) -> Option<Duration> {
+        let tick = self.config.tick_period();
+        let timeout = if !self.dispatcher.is_empty() || self.screen.compositor.is_dirty() {
+            Some(Duration::ZERO)
+        } else if self.is_animating() || !self.config.power_saving {
+            Some(tick)
+        } else {
+            None
+        };
+        let Some(deadline) = self.timers.next_deadline() else {
+            return timeout;
+        };
+        let until = deadline.saturating_duration_since(Instant::now());
+        Some(timeout.map_or(until, |timeout| timeout.min(until)))
+    }
+
+    /// Sleep for [`poll_timeout`](Self::poll_timeout) or until an external
+    /// source wakes the app. Returns whether a wake-up arrived.
+    pub fn wait_for_work(&self) -> bool {
+        self.wait_external(self.poll_timeout())
+    }
+
+    /// Register a callback to run whenever the loop is idle, e.g. to index
+    /// in the background one small step at a time.
+    pub fn on_idle(&mut self, callback: impl FnMut(&mut App) + 'static) {
+        self.idle_callbacks.push(Box::new(callback));
+    }
+
+    /// Run the idle callbacks once if the app [`is_idle`](Self::is_idle).
+    /// Returns whether they ran.
+    pub fn run_idle(&mut self) -> bool {
+        if !self.is_idle() || self.idle_callbacks.is_empty() {
+            return false;
+        }
+        let start = Instant::now();
+        let mut callbacks = std::mem::take(&mut self.idle_callbacks);
+        self.enter(|app| {
+            for callback in &mut callbacks {
+                app.timed_handler(None, callback);
+            }
+        });
+        self.event_time += start.elapsed();
+        // Keep callbacks registered by the callbacks themselves.
+        callbacks.append(&mut self.idle_callbacks);
+        self.idle_callbacks = callbacks;
+        true
+    }
+
+    // -----------------------------------------------------------------------
+    // Timers
+    // -----------------------------------------------------------------------
+
+    /// Run `callback` once, `delay` from now, on the main thread. The loop
+    /// fires it from [`run_timers`](Self::run_timers).
+    pub fn set_timer(
+        &mut self,
+        delay: Duration,
+        callback: impl FnOnce(&mut App) + 'static,
+    ) -> TimerHandle {
+        let mut callback = Some(callback);
+        let callback: TimerCallback = Box::new(move |app| {
+            if let Some(callback) = callback.take() {
+                callback(app);
+            }
+        });
+        self.timers.once(Instant::now() + delay, callback)
+    }
+
+    /// Run `callback` every `period`, starting one period from now, until
+    /// the returned handle is cancelled.
+    pub fn set_interval(
+        &mut self,
+        period: Duration,
+        callback: impl FnMut(&mut App) + 'static,
+    ) -> TimerHandle {
+        self.timers.every(Instant::now(), period, Box::new(callback))
+    }
+
+    /// Post `message` from the DOM root once `delay` has passed.
+    pub fn post_after(&mut self, delay: Duration, message: impl Message) -> TimerHandle {
+        self.set_timer(delay, move |app| {
+            if let Some(root) = app.screen.dom.root() {
+                app.dispatcher.push(Envelope::new(message, root));
+            }
+        })
+    }
+
+    /// Number of timers and intervals that have not been cancelled.
+    pub fn pending_timers(&self) -> usize {
+        self.timers.len()
+    }
+
+    /// Run the callbacks of every timer due at `now`, earliest first, and
+    /// reschedule the intervals. Returns how many fired.
+    pub fn run_timers(&mut self, now: Instant) -> usize {
+        let start = Instant::now();
+        let due = self.timers.take_due(now);
+        let fired = self.enter(|app| {
+            let mut count = 0;
+            for mut timer in due {
+                // A callback may cancel timers that are due later this round.
+                if timer.handle().is_cancelled() {
+                    continue;
+                }
+                app.timed_handler(None, &mut timer.callback);
+                app.timers.requeue(timer, now);
+                count += 1;
+            }
+            count
+        });
+        self.event_time += start.elapsed();
+        fired
+    }
+
+    /// Run `f` as an event handler for `node`, recording it in
+    /// [`App::budget`]. Work that belongs to no widget, such as timers and
+    /// idle callbacks, is charged to the DOM root.
+    fn timed_handler<R>(&mut self, node: Option<NodeId>, f: impl FnOnce(&mut App) -> R) -> R {
+        let start = Instant::now();
+        let result = f(self);
+        if let Some(node) = node.or_else(|| self.screen.dom.root()) {
+            self.budget.record(node, WorkKind::Handler, start.elapsed());
+        }
+        result
+    }
+
+    /// Whether the app should quit.
+    pub fn should_quit(&self) -> bool {
+        !self.running
+    }
+
+    /// Request the app to quit, stopping the input reader thread.
+    pub fn request_quit(&mut self) {
+        self.running = false;
+        self.stop_input_reader();
+    }
+
+    /// All nodes on the current screen matching a compiled query, in document order.
+    pub fn query(&self, query: &Query) -> Vec<NodeId> {
+        query.all(&self.screen.dom)
+    }
+
+    /// The first node on the current screen matching a compiled query.
+    pub fn query_one(&self, query: &Query) -> Option<NodeId> {
+        query.first(&self.screen.dom)
+    }
+
+    /// Where a node is on screen, by id or selector (`"#save"`), shifted by
+    /// scrolled containers. `Ok(None)` if nothing matches or it is not laid
+    /// out yet; fails if the selector does not parse.
+    pub fn region_of<'a>(&self, node: impl Into<NodeRef<'a>>) -> Result<Option<Region>> {
+        let id = node.into().resolve(&self.screen.dom)?;
+        Ok(id.and_then(|id| self.screen.region_of(id)))
+    }
+
+    /// The part of a node actually on screen, clipped by overflowing
+    /// containers and the screen edges. `Ok(None)` if it is out of view.
+    pub fn visible_region<'a>(&self, node: impl Into<NodeRef<'a>>) -> Result<Option<Region>> {
+        let id = node.into().resolve(&self.screen.dom)?;
+        Ok(id.and_then(|id| self.screen.visible_region(id)))
+    }
+
+    /// The frontmost visible widget at a screen position.
+    pub fn widget_at(&self, x: u16, y: u16) -> Option<NodeId> {
+        self.screen.widget_at(x, y)
+    }
+
+    /// Build a widget tree from a declarative layout, using the widget types
+    /// in [`widgets`](Self::widgets).
+    pub fn load_layout(&self, node: &LayoutNode) -> Result<Box<dyn Widget>> {
+        Ok(LayoutLoader::new(&self.widgets).load(node)?)
+    }
+
+    /// Draw and present one frame, recording it in [`App::stats`].
+    ///
+    /// Restyles and lays out the screen, lets `render` draw the widgets into
+    /// the compositor (e.g. with [`App::render_widget`]), draws scrollbars
+    /// and the perf HUD over them, then sends the cells that changed since
+    /// the last frame to the driver. Input, messages, timers, and idle
+    /// callbacks handled since the previous frame count as its event phase.
+    /// Returns how many cells changed.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// while !app.should_quit() {
+    ///     app.wait_for_work();
+    ///     app.pump_input();
+    ///     app.handle_messages()?;
+    ///     app.render_frame(|app| {
+    ///         app.render_widget(header_node, &header);
+    ///     })?;
+    /// }
+    /// ```
+    pub fn render_frame(&mut self, render: impl FnOnce(&mut App)) -> Result<usize> {
+        let start = Instant::now();
+        self.screen.restyle();
+        let styled = Instant::now();
+        self.screen.compute_layout()?;
+        let laid_out = Instant::now();
+        self.enter(render);
+        self.screen.draw_scrollbars();
+        self.draw_perf_hud();
+        let rendered = Instant::now();
+
+        let (width, height) = (self.screen.compositor.width, self.screen.compositor.height);
+        if (self.presented.width, self.presented.height) != (width, height) {
+            self.presented = Compositor::new(width, height);
+        }
+        let (scroll, updates) = self.screen.compositor.diff_with_scroll(&self.presented, None);
+        if let Some(driver) = &mut self.driver {
+            driver.present(scroll.as_ref(), &updates)?;
+        }
+        self.presented = self.screen.compositor.clone();
+        self.screen.compositor.clear_dirty();
+        let done = Instant::now();
+
+        let timings = FrameTimings {
+            event: std::mem::take(&mut self.event_time),
+            style: styled - start,
+            layout: laid_out - styled,
+            render: rendered - laid_out,
+            flush: done - rendered,
+        };
+        self.stats.record_frame(timings, updates.len(), done);
+        Ok(updates.len())
+    }
+
+    /// Render `widget` for `node` into the compositor at the node's laid-out
+    /// region, clipped to its visible part, with its computed styles. The
+    /// render is timed against [`App::budget`]. Returns `false` if the node is
+    /// not laid out or not visible.
+    pub fn render_widget(&mut self, node: NodeId, widget: &dyn Widget) -> bool {
+        let (Some(region), Some(visible)) =
+            (self.screen.region_of(node), self.screen.visible_region(node))
+        else {
+            return false;
+        };
+        let styles = self.screen.styles.get(&node).cloned().unwrap_or_default();
+        let strips = self.budget.time_render(node, || widget.render(region, &styles));
+        self.screen.compositor.place_strips(&strips, &visible);
+        true
+    }
+
+    /// Show or hide the performance HUD overlay.
+    pub fn set_perf_hud(&mut self, visible: bool) {
+        if self.perf_hud != visible {
+            self.perf_hud = visible;
+            if let Some(region) = self.perf_hud_region() {
+                self.screen.compositor.mark_dirty(region);
+            }
+        }
+    }
+
+    /// Toggle the performance HUD overlay.
+    pub fn toggle_perf_hud(&mut self) {
+        self.set_perf_hud(!self.perf_hud);
+    }
+
+    /// Whether the performance HUD overlay is visible.
+    pub fn perf_hud_visible(&self) -> bool {
+        self.perf_hud
+    }
+
+    /// Draw the performance HUD into the top-right corner of the compositor,
+    /// styled by the `PerfHud` rules of the screen's stylesheets.
+    ///
+    /// Call after the screen has been composited and before diffing, so the
+    /// overlay sits above all widgets. Does nothing while the HUD is hidden.
+    ///
+    /// The HUD shows [`App::stats`] as of the previous frame;
+    /// [`App::render_frame`] draws it and records each frame.
+    pub fn draw_perf_hud(&mut self) {
+        if !self.perf_hud {
+            return;
+        }
+        let Some(region) = self.perf_hud_region() else {
+            return;
+        };
+        let hud = PerfHud::new(&self.stats, &self.runtime.stats());
+        let styles = self.screen.overlay_styles("PerfHud");
+        let strips = hud.render(region, &styles);
+        self.screen.compositor.place_strips(&strips, &region);
+    }
+
+    /// Where the HUD is drawn for the current screen size.
+    fn perf_hud_region(&self) -> Option<Region> {
+        let width = self.screen.compositor.width as i32;
+        let height = (self.screen.compositor.height as i32).min(8);
+        if width <= 0 || height <= 0 {
+            return None;
+        }
+        let hud_width = HUD_WIDTH.min(width);
+        Some(Region::new(width - hud_width, 0, hud_width, height))
+    }
+
+    /// Snapshot `region` and transition from it to whatever is rendered there
+    /// next, replacing any running transition.
+    ///
+    /// Call just before swapping content (e.g. replacing the root's children).
+    pub fn begin_transition(
+        &mut self,
+        region: Region,
+        effect: TransitionEffect,
+        duration: Duration,
+    ) {
+        let snapshot = self.screen.compositor.snapshot(region);
+        self.transition = Some(SnapshotTransition::new(
+            snapshot,
+            effect,
+            Instant::now(),
+            duration,
+        ));
+    }
+
+    /// Whether a snapshot transition is running.
+    pub fn transition_active(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    /// Draw the running transition over the freshly composited frame.
+    ///
+    /// Like the other overlays, call after compositing and before diffing.
+    /// The region is marked dirty so the next frame re-renders the real
+    /// content underneath. Returns whether the transition is still running.
+    pub fn draw_transition(&mut self, now: Instant) -> bool {
+        let Some(transition) = &self.transition else {
+            return false;
+        };
+        let region = transition.region();
+        let strips = transition.frame(&self.screen.compositor, now);
+        let finished = transition.is_finished(now);
+        self.screen.compositor.place_strips(&strips, &region);
+        self.screen.compositor.mark_dirty(region);
+        if finished {
+            self.transition = None;
+        }
+        !finished
+    }
+
+    /// Resolve blinking and pulsing text for the current phase of the
+    /// screen's effect clock (see [`Screen::tick_text_effects`]).
+    ///
+    /// Call last, after every other overlay and before diffing, so no
+    /// effect attribute reaches the terminal.
+    pub fn draw_text_effects(&mut self) {
+        let phase = self.screen.text_effects().phase();
+        self.screen.compositor.apply_text_effects(phase);
+    }
+
+    /// Enable or disable terminal mouse reporting.
+    ///
+    /// Turning capture off hands the mouse back to the terminal so users can
+    /// select and copy text natively; turn it back on to resume mouse input.
+    /// In headless mode only the flag is updated.
+    pub fn set_mouse_capture(&mut self, enabled: bool) -> Result<()> {
+        if let Some(driver) = self.driver.as_mut() {
+            driver.set_mouse_capture(enabled)?;
+        }
+        self.mouse_capture = enabled;
+        Ok(())
+    }
+
+    /// Toggle terminal mouse reporting.
+    ///
+    /// `F9` does this by default, through the
+    /// [`ToggleMouseCapture`](message::ToggleMouseCapture) message.
+    pub fn toggle_mouse_capture(&mut self) -> Result<()> {
+        self.set_mouse_capture(!self.mouse_capture)
+    }
+
+    /// Whether terminal mouse reporting is enabled.
+    pub fn mouse_capture_enabled(&self) -> bool {
+        self.mouse_capture
+    }
+
+    /// Switch the locale strings are looked up in and repaint the screen so
+    /// built-in widgets redraw in the new language.
+    pub fn set_locale(&mut self, locale: impl Into<String>) {
+        i18n::set_locale(locale);
+        self.screen.compositor.mark_all_dirty();
+    }
+
+    /// Change how wide, ambiguous-width, and zero-width characters are
+    /// drawn, e.g. after detecting a terminal that misdraws emoji, and
+    /// repaint the screen with it.
+    pub fn set_glyph_policy(&mut self, policy: GlyphPolicy) {
+        self.config.glyphs = policy.clone();
+        self.screen.compositor.set_glyph_policy(policy);
+        self.screen.compositor.mark_all_dirty();
+    }
+
+    /// The glyph policy characters are drawn with.
+    pub fn glyph_policy(&self) -> &GlyphPolicy {
+        &self.config.glyphs
+    }
+
+    /// Copy `text` to the system clipboard (via OSC 52). In headless mode the
+    /// text is only remembered, for [`App::clipboard`].
+    pub fn copy_to_clipboard(&mut self, text: &str) -> Result<()> {
+        if let Some(driver) = self.driver.as_mut() {
+            driver.copy_to_clipboard(text)?;
+        }
+        self.clipboard = Some(text.to_owned());
+        Ok(())
+    }
+
+    /// The text last copied with [`App::copy_to_clipboard`].
+    pub fn clipboard(&self) -> Option<&str> {
+        self.clipboard.as_deref()
+    }
+
+    /// Print text to the terminal's normal scrollback, outside the UI.
+    ///
+    /// Suspends the UI (leaving the alternate screen), writes `text`, then
+    /// resumes and schedules a full repaint. Useful for dumping long command
+    /// output or diffs where the user can scroll back to it after exit.
+    /// In headless mode only the repaint is scheduled.
+    pub fn print(&mut self, text: &str) -> Result<()> {
+        self.suspended(|driver| driver.write_text(text))
+    }
+
+    /// Show text in the user's pager (`$PAGER`, defaulting to `less -R`).
+    ///
+    /// Like [`App::print`], but blocks until the pager exits before resuming.
+    pub fn page(&mut self, text: &str) -> Result<()> {
+        self.suspended(|driver| driver.run_pager(text))
+    }
+
+    /// Run `f` with the driver suspended, then resume and repaint.
+    fn suspended(&mut self, f: impl FnOnce(&mut Driver) -> io::Result<()>) -> Result<()> {
+        if self.driver.is_some() {
+            self.with_input_paused(|app| {
+                let driver = app.driver.as_mut().expect("driver checked above");
+                driver.suspend()?;
+                let result = f(driver);
+                // Always try to get the UI back, even if the write failed.
+                driver.resume()?;
+                result
+            })?;
+        }
+        self.screen.compositor.mark_all_dirty();
+        Ok(())
+    }
+
+    /// Run `f` with the input reader stopped, restarting it afterwards if it
+    /// was running. While the terminal is handed back, a pager or the shell
+    /// must get stdin rather than the reader thread.
+    fn with_input_paused(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let was_reading = self.is_reading_input();
+        self.stop_input_reader();
+        let result = f(self);
+        if was_reading {
+            self.start_input_reader()?;
+        }
+        result
+    }
+
+    /// Open the context menu declared for `node` at screen position `(x, y)`.
+    ///
+    /// Items naming a binding action show its bound key as a shortcut hint.
+    /// Returns `false` if the node declares no menu.
+    pub fn open_context_menu(&mut self, node: NodeId, x: i32, y: i32) -> bool {
+        let Some(items) = self.screen.context_menu(node) else {
+            return false;
+        };
+        let mut items = items.to_vec();
+        for item in &mut items {
+            item.resolve_shortcut(&self.bindings);
+        }
+        let menu = ContextMenu::new(node, items, x, y, self.screen_bounds());
+        self.close_context_menu();
+        self.gestures.cancel();
+        self.screen.compositor.mark_dirty(menu.region());
+        self.context_menu = Some(menu);
+        true
+    }
+
+    /// Close the open context menu, if any.
+    pub fn close_context_menu(&mut self) {
+        if let Some(menu) = self.context_menu.take() {
+            self.screen.compositor.mark_dirty(menu.region());
+        }
+    }
+
+    /// The open context menu, if any.
+    pub fn context_menu(&self) -> Option<&ContextMenu> {
+        self.context_menu.as_ref()
+    }
+
+    /// Draw the open context menu over the compositor, styled by the
+    /// `ContextMenu` rules of the screen's stylesheets.
+    ///
+    /// Call after compositing the screen (and after `draw_perf_hud`, so the
+    /// menu stays on top). Does nothing when no menu is open.
+    pub fn draw_context_menu(&mut self) {
+        let Some(menu) = &self.context_menu else {
+            return;
+        };
+        let styles = self.screen.overlay_styles("ContextMenu");
+        let region = menu.region();
+        let strips = menu.render(region, &styles);
+        self.screen.compositor.place_strips(&strips, &region);
+    }
+
+    /// Choose the highlighted context menu item: close the menu and dispatch
+    /// the item's message targeted at the node the menu was opened for.
+    fn choose_context_menu_item(&mut self) {
+        let Some(menu) = self.context_menu.take() else {
+            return;
+        };
+        self.screen.compositor.mark_dirty(menu.region());
+        if let Some(item) = menu.selected_item() {
+            let origin = menu.origin();
+            self.dispatcher.push(Envelope {
+                message: (item.action)(),
+                sender: origin,
+                target: Some(origin),
+                handled: false,
+            });
+        }
+    }
+
+    /// Keyboard navigation inside an open context menu.
+    fn handle_context_menu_key(&mut self, ke: &KeyEvent) {
+        let Some(menu) = self.context_menu.as_mut() else {
+            return;
+        };
+        match ke.code {
+            Key::Up | Key::BackTab => menu.select_previous(),
+            Key::Down | Key::Tab => menu.select_next(),
+            Key::Enter | Key::Char(' ') => return self.choose_context_menu_item(),
+            Key::Escape => return self.close_context_menu(),
+            // Everything else is swallowed while the menu is open.
+            _ => return,
+        }
+        let region = menu.region();
+        self.screen.compositor.mark_dirty(region);
+    }
+
+    /// Mouse handling while a context menu is open: hover highlights, a left
+    /// click chooses, and any click outside the menu closes it.
+    fn handle_context_menu_mouse(&mut self, me: &MouseEvent) {
+        let Some(menu) = self.context_menu.as_mut() else {
+            return;
+        };
+        let item = menu.item_at(me.x as i32, me.y as i32);
+        match (me.kind, item) {
+            (MouseAction::Moved, Some(index)) if menu.selected() != index => {
+                menu.select(index);
+                let region = menu.region();
+                self.screen.compositor.mark_dirty(region);
+            }
+            (MouseAction::Down(MouseBtn::Left), Some(index)) => {
+                menu.select(index);
+                self.choose_context_menu_item();
+            }
+            (MouseAction::Down(_), None) => self.close_context_menu(),
+            _ => {}
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Command palette
+    // -----------------------------------------------------------------------
+
+    /// List `action` in the command palette under `name`.
+    ///
+    /// Custom actions show their bound key, if any, next to the name.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// app.register_command("Toggle dark mode", BindingAction::Custom("toggle-dark".into()));
+    /// ```
+    pub fn register_command(&mut self, name: impl Into<String>, action: BindingAction) {
+        self.commands.push(Command::new(name, action));
+    }
+
+    /// The commands registered with [`register_command`](Self::register_command).
+    pub fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+
+    /// Open the command palette over the screen, closing any context menu.
+    /// `Ctrl+P` does this by default.
+    pub fn open_command_palette(&mut self) {
+        self.close_context_menu();
+        self.close_command_palette();
+        let mut commands = self.commands.clone();
+        for command in &mut commands {
+            command.resolve_shortcut(&self.bindings);
+        }
+        let palette = CommandPalette::new(commands, self.screen_bounds());
+        self.gestures.cancel();
+        self.screen.compositor.mark_dirty(palette.region());
+        self.command_palette = Some(palette);
+    }
+
+    /// Close the command palette, if open.
+    pub fn close_command_palette(&mut self) {
+        if let Some(palette) = self.command_palette.take() {
+            self.screen.compositor.mark_dirty(palette.region());
+        }
+    }
+
+    /// The open command palette, if any.
+    pub fn command_palette(&self) -> Option<&CommandPalette> {
+        self.command_palette.as_ref()
+    }
+
+    /// Draw the open command palette over the compositor, styled by the
+    /// `CommandPalette` rules of the screen's stylesheets.
+    ///
+    /// Call last, after `draw_context_menu`, so the palette stays on top.
+    /// Does nothing when the palette is closed.
+    pub fn draw_command_palette(&mut self) {
+        let Some(palette) = &self.command_palette else {
+            return;
+        };
+        let styles = self.screen.overlay_styles("CommandPalette");
+        let region = palette.region();
+        let strips = palette.render(region, &styles);
+        self.screen.compositor.place_strips(&strips, &region);
+    }
+
+    /// Run the highlighted command: close the palette and run its action as
+    /// if its key binding had been pressed.
+    fn choose_command(&mut self) {
+        let Some(palette) = self.command_palette.take() else {
+            return;
+        };
+        self.screen.compositor.mark_dirty(palette.region());
+        if let Some(command) = palette.selected_command() {
+            self.run_binding(command.action.clone(), None);
+        }
+    }
+
+    /// Editing and navigation inside the open command palette.
+    fn handle_command_palette_key(&mut self, ke: &KeyEvent) {
+        let Some(palette) = self.command_palette.as_mut() else {
+            return;
+        };
+        let before = palette.region();
+        match ke.code {
+            Key::Up | Key::BackTab => palette.select_previous(),
+            Key::Down | Key::Tab => palette.select_next(),
+            Key::Backspace => {
+                palette.pop_char();
+            }
+            Key::Enter => return self.choose_command(),
+            Key::Escape => return self.close_command_palette(),
+            Key::Char(ch)
+                if !ke.modifiers.contains(Modifiers::CTRL)
+                    && !ke.modifiers.contains(Modifiers::ALT) =>
+            {
+                palette.push_char(ch)
+            }
+            // Everything else is swallowed while the palette is open.
+            _ => return,
+        }
+        // The palette shrinks as the list narrows; repaint what it covered.
+        let after = palette.region();
+        self.screen.compositor.mark_dirty(before);
+        self.screen.compositor.mark_dirty(after);
+    }
+
+    /// Mouse handling while the command palette is open: hover highlights, a
+    /// left click runs a command, and a click outside closes the palette.
+    fn handle_command_palette_mouse(&mut self, me: &MouseEvent) {
+        let Some(palette) = self.command_palette.as_mut() else {
+            return;
+        };
+        let (x, y) = (me.x as i32, me.y as i32);
+        match (me.kind, palette.result_at(x, y)) {
+            (MouseAction::Moved, Some(index)) if palette.selected() != index => {
+                palette.select(index);
+                let region = palette.region();
+                self.screen.compositor.mark_dirty(region);
+            }
+            (MouseAction::Down(MouseBtn::Left), Some(index)) => {
+                palette.select(index);
+                self.choose_command();
+            }
+            (MouseAction::Down(_), None) if !palette.region().contains(x, y) => {
+                self.close_command_palette()
+            }
+            _ => {}
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Modals
+    // -----------------------------------------------------------------------
+
+    /// Open the subtree under `root` (a confirm dialog, a command palette)
+    /// as a modal above the rest of the screen; see [`Screen::push_modal`].
+    ///
+    /// `on_close` runs once the modal is closed with [`close_modal`], with
+    /// the result it was closed with, or `None` if it was dismissed with
+    /// Escape or closed with a result of another type.
+    ///
+    /// [`close_modal`]: Self::close_modal
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let dialog = app.screen.dom.insert_child(root, NodeData::new("Confirm"));
+    /// app.push_modal(dialog, |app, confirmed: Option<bool>| {
+    ///     if confirmed == Some(true) {
+    ///         app.post(DeleteFile);
+    ///     }
+    /// });
+    /// // Later, from the dialog's "Yes" button:
+    /// app.close_modal(Some(true));
+    /// ```
+    pub fn push_modal<T: 'static>(
+        &mut self,
+        root: NodeId,
+        on_close: impl FnOnce(&mut App, Option<T>) + 'static,
+    ) {
+        self.close_context_menu();
+        self.cancel_rubber_band();
+        self.screen.push_modal(root);
+        self.modal_callbacks.push((
+            root,
+            Box::new(move |app, result| {
+                on_close(app, result.and_then(|r| r.downcast().ok()).map(|r| *r));
+            }),
+        ));
+        self.sync_focus();
+    }
+
+    /// Close the topmost modal: remove its subtree, restore the focus from
+    /// before it opened, and pass `result` to its `on_close` callback.
+    /// Returns `false` if no modal is open.
+    pub fn close_modal<T: 'static>(&mut self, result: Option<T>) -> bool {
+        let Some(root) = self.screen.pop_modal() else {
+            return false;
+        };
+        let mut tx = Transaction::new();
+        tx.remove(root);
+        let removed = self.screen.apply(tx);
+        // Removing a node through `Screen::apply` also drops it from the
+        // modal stack, so a root popped from the stack is still in the DOM
+        // and removing it cannot fail.
+        debug_assert!(removed.is_ok(), "modal root {root:?} missing from the DOM");
+        self.sync_focus();
+        let dom = &self.screen.dom;
+        let (closed, open): (Vec<_>, Vec<_>) = std::mem::take(&mut self.modal_callbacks)
+            .into_iter()
+            .partition(|(id, _)| *id == root || !dom.contains(*id));
+        self.modal_callbacks = open;
+        // Modals whose roots were removed some other way count as dismissed.
+        let mut result = result.map(|r| Box::new(r) as Box<dyn Any>);
+        for (id, on_close) in closed {
+            on_close(self, if id == root { result.take() } else { None });
+        }
+        true
+    }
+
+    /// Dim everything outside the topmost modal.
+    ///
+    /// Call after compositing the screen and before the other overlays, so
+    /// the HUD and context menus stay bright. Does nothing when no modal is
+    /// open.
+    pub fn draw_modal_backdrop(&mut self) {
+        let modal = self.screen.modal();
+        if let Some(region) = modal.and_then(|root| self.screen.visible_region(root)) {
+            self.screen.compositor.dim_outside(region);
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Rubber-band selection
+    // -----------------------------------------------------------------------
+
+    /// Start dragging a selection rectangle in `container` at `(x, y)`.
+    ///
+    /// Normally started by a left press on the background of a container
+    /// marked with [`Screen::set_rubber_band`]. Returns `false` if the
+    /// container is not on screen.
+    pub fn start_rubber_band(&mut self, container: NodeId, x: i32, y: i32) -> bool {
+        let Some(bounds) = self.screen.visible_region(container) else {
+            return false;
+        };
+        self.cancel_rubber_band();
+        self.gestures.cancel();
+        let band = RubberBand::new(container, bounds, x, y);
+        self.screen.compositor.mark_dirty(band.region());
+        self.rubber_band = Some(band);
+        true
+    }
+
+    /// Drop the selection rectangle without reporting a selection.
+    pub fn cancel_rubber_band(&mut self) {
+        if let Some(band) = self.rubber_band.take() {
+            self.screen.compositor.mark_dirty(band.region());
+        }
+    }
+
+    /// The selection rectangle being dragged, if any.
+    pub fn rubber_band(&self) -> Option<&RubberBand> {
+        self.rubber_band.as_ref()
+    }
+
+    /// Draw the selection rectangle's outline over the compositor, in the
+    /// colour of the `RubberBand` rules of the screen's stylesheets.
+    ///
+    /// Like the other overlays, call after compositing and before diffing.
+    /// Does nothing when no drag is in progress.
+    pub fn draw_rubber_band(&mut self) {
+        let Some(band) = &self.rubber_band else {
+            return;
+        };
+        let styles = self.screen.overlay_styles("RubberBand");
+        let strips = band.render(CellStyle::from_styles(&styles));
+        self.screen.compositor.place_strips(&strips, &band.region());
+    }
+
+    /// Mouse handling during a drag: left drags move the corner, and
+    /// releasing sends [`RegionSelected`] from the container.
+    fn handle_rubber_band_mouse(&mut self, me: &MouseEvent) {
+        let Some(band) = self.rubber_band.as_mut() else {
+            return;
+        };
+        match me.kind {
+            MouseAction::Drag(MouseBtn::Left) => {
+                let before = band.region();
+                if band.drag_to(me.x as i32, me.y as i32) {
+                    let after = band.region();
+                    self.screen.compositor.mark_dirty(before);
+                    self.screen.compositor.mark_dirty(after);
+                }
+            }
+            MouseAction::Up(MouseBtn::Left) => {
+                band.drag_to(me.x as i32, me.y as i32);
+                let region = band.region();
+                let container = band.container();
+                self.cancel_rubber_band();
+                let selected = self.screen.children_in_region(container, region);
+                self.dispatcher
+                    .push(Envelope::new(RegionSelected(selected), container));
+            }
+            _ => {}
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Focus announcements
+    // -----------------------------------------------------------------------
+
+    /// Turn on focus announcements and return the signal carrying the
+    /// current one, e.g. for `Footer::with_announcements`.
+    ///
+    /// After each focus change the signal holds a description of the newly
+    /// focused widget (see [`Screen::focus_announcement`]) until
+    /// [`expire_announcement`](Self::expire_announcement) clears it,
+    /// [`ANNOUNCEMENT_DURATION`] later.
+    pub fn focus_announcements(&mut self) -> ReadSignal<Option<String>> {
+        if let Some((read, _)) = self.announcement {
+            return read;
+        }
+        let signals = self.enter(|_| create_signal(None));
+        self.announcement = Some(signals);
+        signals.0
+    }
+
+    /// Clear the focus announcement if it has been up for
+    /// [`ANNOUNCEMENT_DURATION`] at `now`. Returns whether it was cleared.
+    pub fn expire_announcement(&mut self, now: Instant) -> bool {
+        let Some((_, write)) = self.announcement else {
+            return false;
+        };
+        if self.announcement_expires.is_none_or(|at| now < at) {
+            return false;
+        }
+        self.announcement_expires = None;
+        self.runtime.run(|| write.set(None));
+        true
+    }
+
+    /// Move the `:focus` mark to the focused node, announce the change, and
+    /// update the binding hints.
+    fn sync_focus(&mut self) {
+        if !self.runtime.run(|| self.screen.sync_focus_state()) {
+            return;
+        }
+        self.refresh_binding_hints();
+        let Some((_, write)) = self.announcement else {
+            return;
+        };
+        let text = self.screen.focus_announcement();
+        self.announcement_expires = text.as_ref().map(|_| Instant::now() + ANNOUNCEMENT_DURATION);
+        self.runtime.run(|| write.set(text));
+    }
+
+    // -----------------------------------------------------------------------
+    // Binding hints
+    // -----------------------------------------------------------------------
+
+    /// Turn on binding hints and return the signal carrying them, e.g. for
+    /// `Footer::with_bindings`.
+    ///
+    /// The signal holds the described bindings reachable from the focused
+    /// node (see [`KeyBindingRegistry::hints`]) and is updated whenever focus
+    /// moves. Call [`refresh_binding_hints`](Self::refresh_binding_hints)
+    /// after changing bindings without moving focus.
+    pub fn binding_hints(&mut self) -> ReadSignal<Vec<BindingHint>> {
+        if let Some((read, _)) = self.hints {
+            return read;
+        }
+        let signals = self.enter(|_| create_signal(Vec::new()));
+        self.hints = Some(signals);
+        self.refresh_binding_hints();
+        signals.0
+    }
+
+    /// Recompute the binding hints for the focused node, if hints are on.
+    pub fn refresh_binding_hints(&mut self) {
+        let Some((read, write)) = self.hints else {
+            return;
+        };
+        let hints = self
+            .bindings
+            .hints(self.screen.focused_node(), &self.screen.dom);
+        self.runtime.run(|| {
+            if read.get_untracked() != hints {
+                write.set(hints);
+            }
+        });
+    }
+
+    // -----------------------------------------------------------------------
+    // Modes
+    // -----------------------------------------------------------------------
+
+    /// Register `mode`, replacing a mode of the same name. Replacing the
+    /// active mode does not re-apply it until the next
+    /// [`set_mode`](Self::set_mode).
+    pub fn add_mode(&mut self, mode: Mode) {
+        self.modes.insert(mode.name().to_owned(), mode);
+    }
+
+    /// Switch to the mode called `name`: the previous mode's root classes,
+    /// stylesheets, and key bindings are replaced by the new one's in one
+    /// step, then the screen is restyled and the binding hints refreshed.
+    /// Returns `false`, changing nothing, if no such mode is registered.
+    pub fn set_mode(&mut self, name: &str) -> bool {
+        let Some(mode) = self.modes.get(name) else {
+            return false;
+        };
+        let (classes, css) = (mode.classes().to_vec(), mode.stylesheets().to_vec());
+        self.bindings.set_mode_bindings(mode.bindings().to_vec());
+        self.screen.set_mode_layer(classes, css);
+        self.mode = Some(name.to_owned());
+        self.refresh_binding_hints();
+        true
+    }
+
+    /// Leave the active mode, if any, removing its classes, stylesheets,
+    /// and key bindings.
+    pub fn clear_mode(&mut self) {
+        if self.mode.take().is_some() {
+            self.bindings.set_mode_bindings(Vec::new());
+            self.screen.set_mode_layer(Vec::new(), Vec::new());
+            self.refresh_binding_hints();
+        }
+    }
+
+    /// The active mode's name.
+    pub fn mode(&self) -> Option<&str> {
+        self.mode.as_deref()
+    }
+
+    // -----------------------------------------------------------------------
+    // Floating panels
+    // -----------------------------------------------------------------------
+
+    /// Add a floating panel on top of the others, clamped to the screen.
+    /// Returns its index.
+    pub fn add_panel(&mut self, mut panel: Panel) -> usize {
+        let region = panel.region();
+        panel.set_region(region, self.screen_bounds());
+        panel.set_z_index(self.top_panel_z() + 1);
+        self.screen.compositor.mark_dirty(panel.region());
+        self.panels.push(panel);
+        self.panels.len() - 1
+    }
+
+    /// Remove the panel at `index`. Later panels shift down by one.
+    pub fn remove_panel(&mut self, index: usize) -> Option<Panel> {
+        if index >= self.panels.len() {
+            return None;
+        }
+        let panel = self.panels.remove(index);
+        self.screen.compositor.mark_dirty(panel.region());
+        Some(panel)
+    }
+
+    /// The floating panels, in insertion order.
+    pub fn panels(&self) -> &[Panel] {
+        &self.panels
+    }
+
+    /// Mutable access to one panel.
+    pub fn panel_mut(&mut self, index: usize) -> Option<&mut Panel> {
+        self.panels.get_mut(index)
+    }
+
+    /// Bring the panel at `index` above every other panel.
+    pub fn raise_panel(&mut self, index: usize) {
+        if index >= self.panels.len() || self.panels_by_z().last() == Some(&index) {
+            return;
+        }
+        let z = self.top_panel_z() + 1;
+        let panel = &mut self.panels[index];
+        panel.set_z_index(z);
+        let region = panel.region();
+        self.screen.compositor.mark_dirty(region);
+    }
+
+    /// Draw the floating panels over the compositor, lowest z-index first,
+    /// styled by the `Panel` rules of the screen's stylesheets.
+    ///
+    /// Call after compositing the screen and before `draw_context_menu`.
+    pub fn draw_panels(&mut self) {
+        let styles = self.screen.overlay_styles("Panel");
+        for index in self.panels_by_z() {
+            let panel = &self.panels[index];
+            let region = panel.region();
+            let strips = panel.render(region, &styles);
+            self.screen.compositor.place_strips(&strips, &region);
+        }
+    }
+
+    /// Panel indices from bottom to top. Ties keep insertion order.
+    fn panels_by_z(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.panels.len()).collect();
+        order.sort_by_key(|&index| self.panels[index].z_index());
+        order
+    }
+
+    fn top_panel_z(&self) -> i32 {
+        self.panels.iter().map(Panel::z_index).max().unwrap_or(0)
+    }
+
+    /// Route a mouse event to the dragging panel, or the topmost panel under
+    /// the pointer. Clicking a panel raises it.
+    fn handle_panel_mouse(&mut self, me: &MouseEvent) -> bool {
+        let (x, y) = (me.x as i32, me.y as i32);
+        let target = self.panels.iter().position(Panel::is_dragging).or_else(|| {
+            self.panels_by_z()
+                .into_iter()
+                .rev()
+                .find(|&index| self.panels[index].region().contains(x, y))
+        });
+        let Some(index) = target else {
+            return false;
+        };
+        if matches!(me.kind, MouseAction::Down(_)) {
+            self.raise_panel(index);
+        }
+        let bounds = self.screen_bounds();
+        let panel = &mut self.panels[index];
+        let before = panel.region();
+        let consumed = panel.handle_mouse(me, bounds);
+        let after = panel.region();
+        if before != after {
+            self.screen.compositor.mark_dirty(before);
+            self.screen.compositor.mark_dirty(after);
+        }
+        consumed
+    }
+
+    /// Offer a key to the topmost panel (Alt+arrows to move, Alt+Shift+arrows
+    /// to resize).
+    fn handle_panel_key(&mut self, ke: &KeyEvent) -> bool {
+        let Some(&index) = self.panels_by_z().last() else {
+            return false;
+        };
+        let bounds = self.screen_bounds();
+        let panel = &mut self.panels[index];
+        let before = panel.region();
+        let consumed = panel.handle_key(ke, bounds);
+        if consumed {
+            let after = panel.region();
+            self.screen.compositor.mark_dirty(before);
+            self.screen.compositor.mark_dirty(after);
+        }
+        consumed
+    }
+
+    /// The whole screen as a region.
+    fn screen_bounds(&self) -> Region {
+        Region::new(
+            0,
+            0,
+            self.screen.compositor.width as i32,
+            self.screen.compositor.height as i32,
+        )
+    }
+
+    /// Whether the app has a terminal driver (not headless).
+    pub fn has_driver(&self) -> bool {
+        self.driver.is_some()
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::css::styles::Styles;
+    use crate::dom::node::NodeData;
+    use crate::event::input::Modifiers;
+    use crate::event::binding::{Binding, WidgetBinding};
+    use crate::event::message::{Custom, FocusNext, FocusPrevious, Quit, Refresh};
+    use crate::render::strip::{Color, Strip};
+    use crate::widget::registry::WidgetInfo;
+    use crate::widgets::{Panel, Static};
+
+    fn headless_app() -> App {
+        App::new_headless(80, 24)
+    }
+
+    fn headless_app_with_dom() -> App {
+        let mut app = App::new_headless(80, 24);
+        let root = app
+            .screen
+            .dom
+            .insert(NodeData::new("Root").focusable(false));
+        let _a = app
+            .screen
+            .dom
+            .insert_child(root, NodeData::new("A").focusable(true));
+        let _b = app
+            .screen
+            .dom
+            .insert_child(root, NodeData::new("B").focusable(true));
+        let _c = app
+            .screen
+            .dom
+            .insert_child(root, NodeData::new("C").focusable(true));
+        app.screen.focus.rebuild(&app.screen.dom);
+        app
+    }
+
+    // ── Construction ─────────────────────────────────────────────────
+
+    #[test]
+    fn headless_app_no_driver() {
+        let app = headless_app();
+        assert!(!app.has_driver());
+        assert!(!app.should_quit());
+    }
+
+    #[test]
+    fn headless_app_screen_size() {
+        let app = App::new_headless(120, 40);
+        assert_eq!(app.screen.compositor.width, 120);
+        assert_eq!(app.screen.compositor.height, 40);
+    }
+
+    #[test]
+    fn handle_input_batch_coalesces_resizes_but_not_keys() {
+        let mut app = headless_app_with_dom();
+        let tab = InputEvent::Key(KeyEvent::new(Key::Tab, Modifiers::NONE));
+        let resize = |width| InputEvent::Resize { width, height: 30 };
+        app.handle_input_batch(vec![resize(90), tab.clone(), resize(100), tab]);
+        assert_eq!(app.screen.compositor.width, 100);
+        assert_eq!(app.dispatcher.drain().len(), 2);
+    }
+
+    #[test]
+    fn headless_app_has_default_bindings() {
+        let app = headless_app();
+        assert_eq!(app.bindings.len(), 5);
+    }
+
+    // ── floating panels ──────────────────────────────────────────────
+
+    #[test]
+    fn panels_stack_and_raise_on_click() {
+        let mut app = headless_app();
+        let a = app.add_panel(Panel::new("A", Region::new(0, 0, 10, 5)));
+        let b = app.add_panel(Panel::new("B", Region::new(5, 2, 10, 5)));
+        assert!(app.panels()[b].z_index() > app.panels()[a].z_index());
+
+        // Clicking A brings it above B.
+        app.handle_input(InputEvent::Mouse(MouseEvent {
+            kind: MouseAction::Down(MouseBtn::Left),
+            x: 1,
+            y: 1,
+            modifiers: Modifiers::NONE,
+        }));
+        assert!(app.panels()[a].z_index() > app.panels()[b].z_index());
+    }
+
+    #[test]
+    fn topmost_panel_takes_move_keys() {
+        let mut app = headless_app_with_dom();
+        app.add_panel(Panel::new("A", Region::new(0, 0, 10, 5)));
+        app.handle_input(InputEvent::Key("alt+right".parse().unwrap()));
+        assert_eq!(app.panels()[0].region(), Region::new(1, 0, 10, 5));
+        // Unmodified keys still reach the bindings.
+        app.handle_input(InputEvent::Key("ctrl+c".parse().unwrap()));
+        assert_eq!(app.dispatcher.pending_count(), 1);
+    }
+
+    #[test]
+    fn panels_take_no_input_while_a_modal_is_open() {
+        let mut app = headless_app_with_dom();
+        let a = app.add_panel(Panel::new("A", Region::new(0, 0, 10, 5)));
+        let b = app.add_panel(Panel::new("B", Region::new(5, 2, 10, 5)));
+        let root = app.screen.dom.root().unwrap();
+        let dialog = app.screen.dom.insert_child(root, NodeData::new("Dialog"));
+        app.push_modal(dialog, |_, _: Option<()>| {});
+
+        app.handle_input(InputEvent::Mouse(MouseEvent {
+            kind: MouseAction::Down(MouseBtn::Left),
+            x: 1,
+            y: 1,
+            modifiers: Modifiers::NONE,
+        }));
+        assert!(app.panels()[b].z_index() > app.panels()[a].z_index());
+        app.handle_input(InputEvent::Key("alt+right".parse().unwrap()));
+        assert_eq!(app.panels()[b].region(), Region::new(5, 2, 10, 5));
+    }
+
+    #[test]
+    fn panels_are_drawn_over_the_screen() {
+        let mut app = headless_app();
+        app.add_panel(Panel::new("A", Region::new(78, 22, 10, 5)));
+        assert_eq!(app.panels()[0].region(), Region::new(70, 19, 10, 5));
+        app.draw_panels();
+        assert_eq!(app.screen.compositor.get_cell(70, 19).unwrap().ch, '┌');
+        assert!(app.remove_panel(0).is_some());
+        assert!(app.panels().is_empty());
+    }
+
+    #[test]
+    fn panels_use_stylesheet_rules() {
+        let mut app = headless_app();
+        app.screen.add_css("Panel { background: #000080; }").unwrap();
+        app.add_panel(Panel::new("A", Region::new(0, 0, 10, 5)));
+        app.draw_panels();
+        let cell = app.screen.compositor.get_cell(0, 0).unwrap();
+        assert_eq!(cell.style.bg, Some(Color::Rgb(0, 0, 0x80)));
+        assert_eq!(cell.style.fg, Some(Color::Rgb(0xe0, 0xe0, 0xe0)));
+    }
+
+    #[test]
+    fn load_layout_uses_app_registry() {
+        let mut app = headless_app();
+        let node = LayoutNode::new("Badge").with_attr("text", "new");
+        assert!(matches!(
+            app.load_layout(&node),
+            Err(crate::error::Error::LayoutFile(_))
+        ));
+        app.widgets
+            .register(
+                WidgetInfo::new("Badge").with_attrs(&["text"]),
+                |node, _| Ok(node.decorate(Static::new(node.attr("text").unwrap_or("")))),
+            )
+            .unwrap();
+        assert_eq!(app.load_layout(&node).unwrap().widget_type(), "Static");
+    }
+
+    // ── input queue ──────────────────────────────────────────────────
+
+    #[test]
+    fn injected_input_wakes_the_loop_and_is_pumped() {
+        let mut app = headless_app_with_dom();
+        assert!(!app.is_reading_input());
+        let sender = app.input_sender();
+        std::thread::spawn(move || {
+            sender.send(InputEvent::Key("ctrl+c".parse().unwrap()));
+        })
+        .join()
+        .unwrap();
+        assert!(app.wait_external(Some(Duration::from_secs(1))));
+        assert_eq!(app.pump_input(), 1);
+        assert_eq!(app.pump_input(), 0);
+        app.handle_messages().unwrap();
+        assert!(app.should_quit());
+        // Quitting stops the reader; injected events are still accepted.
+        assert!(app.input_sender().send(InputEvent::FocusGained));
+    }
+
+    #[test]
+    fn suspending_pauses_a_running_input_reader() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        let mut app = headless_app();
+        let reading = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&reading);
+        let reader = InputReader::spawn_with(app.input_sender(), move |timeout| {
+            flag.store(true, Ordering::SeqCst);
+            std::thread::sleep(timeout.min(Duration::from_millis(2)));
+            Ok(None)
+        })
+        .unwrap();
+        app.set_input_reader(reader);
+        assert!(app.is_reading_input());
+
+        app.with_input_paused(|app| {
+            assert!(!app.is_reading_input());
+            reading.store(false, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(10));
+            // Nothing reads stdin while the terminal is handed back.
+            assert!(!reading.load(Ordering::SeqCst));
+            Ok(())
+        })
+        .unwrap();
+        assert!(app.is_reading_input());
+        while !reading.load(Ordering::SeqCst) {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        // A reader that was already stopped stays stopped.
+        app.stop_input_reader();
+        app.with_input_paused(|_| Ok(())).unwrap();
+        assert!(!app.is_reading_input());
+    }
+
+    #[test]
+    fn failed_input_reader_quits_the_app() {
+        let mut app = headless_app_with_dom();
+        let reader = InputReader::spawn_with(app.input_sender(), |_| {
+            Err(io::Error::other("terminal gone"))
+        })
+        .unwrap();
+        app.set_input_reader(reader);
+        assert!(app.wait_external(Some(Duration::from_secs(5))));
+        assert_eq!(app.pump_external(), 1);
+        app.handle_messages().unwrap();
+        assert!(app.should_quit());
+    }
+
+    // ── request_quit / should_quit ───────────────────────────────────
+
+    #[test]
+    fn request_quit() {
+        let mut app = headless_app();
+        assert!(!app.should_quit());
+        app.request_quit();
+        assert!(app.should_quit());
+    }
+
+    // ── handle_input: key events ─────────────────────────────────────
+
+    #[test]
+    fn binding_conflicts_are_reported() {
+        let mut app = headless_app();
+        assert!(app.binding_conflicts().is_empty());
+        app.bindings
+            .bind(Key::Tab, Modifiers::NONE, BindingAction::Custom("indent".into()));
+        let conflict = &app.binding_conflicts()[0];
+        assert_eq!(conflict.key, KeyEvent::new(Key::Tab, Modifiers::NONE));
+        assert_eq!(conflict.previous, "FocusNext");
+    }
+
+    #[test]
+    fn widget_bindings_are_sent_from_their_node() {
+        static TABLE: &[WidgetBinding] = &[WidgetBinding::new("g g", "top", "Top")];
+        let mut app = headless_app_with_dom();
+        let root = app.screen.dom.root().unwrap();
+        let list = app.screen.dom.children(root)[0];
+        app.screen.dom.get_mut(list).unwrap().bindings = TABLE;
+        app.screen.focus.focus_node(list);
+        assert_eq!(app.active_bindings()[0].node, Some(list));
+
+        let g = InputEvent::Key(KeyEvent::new(Key::Char('g'), Modifiers::NONE));
+        app.handle_input(g.clone());
+        assert_eq!(app.dispatcher.pending_count(), 0);
+        app.handle_input(g);
+        let messages = app.dispatcher.drain();
+        assert_eq!(messages[0].sender, list);
+        assert_eq!(messages[0].downcast_ref::<Custom>().unwrap().0, "top");
+    }
+
+    #[test]
+    fn binding_hints_follow_focus() {
+        static TABLE: &[WidgetBinding] = &[WidgetBinding::new("ctrl+s", "save", "Save")];
+        let mut app = headless_app_with_dom();
+        let help = Binding::parse("f1", BindingAction::Custom("help".into())).unwrap();
+        app.bindings.add(help.with_description("Help"));
+        let root = app.screen.dom.root().unwrap();
+        let editor = app.screen.dom.children(root)[0];
+        app.screen.dom.get_mut(editor).unwrap().bindings = TABLE;
+        let hints = app.binding_hints();
+        let shown = |app: &mut App| -> Vec<String> {
+            let hints = app.enter(|_| hints.get_untracked());
+            hints.iter().map(BindingHint::to_string).collect()
+        };
+        assert_eq!(shown(&mut app), ["F1 Help"]);
+
+        app.handle_input(InputEvent::Key(KeyEvent::new(Key::Tab, Modifiers::NONE)));
+        app.handle_messages().unwrap();
+        assert_eq!(app.screen.focused_node(), Some(editor));
+        assert_eq!(shown(&mut app), ["Ctrl+S Save", "F1 Help"]);
+    }
+
+    #[test]
+    fn set_mode_swaps_classes_and_bindings() {
+        let mut app = headless_app_with_dom();
+        let root = app.screen.dom.root().unwrap();
+        let leave = Binding::parse("escape", BindingAction::Custom("leave".into())).unwrap();
+        app.add_mode(
+            Mode::new("zen")
+                .with_class("zen")
+                .with_binding(leave.with_description("Leave")),
+        );
+        app.add_mode(Mode::new("split").with_class("split"));
+        assert!(!app.set_mode("presentation"));
+        assert_eq!(app.mode(), None);
+
+        assert!(app.set_mode("zen"));
+        assert_eq!(app.mode(), Some("zen"));
+        assert!(app.screen.dom.get(root).unwrap().has_class("zen"));
+        app.handle_input(InputEvent::Key(KeyEvent::new(Key::Escape, Modifiers::NONE)));
+        let messages = app.dispatcher.drain();
+        assert_eq!(messages[0].downcast_ref::<Custom>().unwrap().0, "leave");
+
+        assert!(app.set_mode("split"));
+        let classes = &app.screen.dom.get(root).unwrap().classes;
+        assert_eq!(classes, &["split"]);
+        app.handle_input(InputEvent::Key(KeyEvent::new(Key::Escape, Modifiers::NONE)));
+        assert_eq!(app.dispatcher.pending_count(), 0);
+
+        app.clear_mode();
+        assert_eq!(app.mode(), None);
+        assert!(app.screen.dom.get(root).unwrap().classes.is_empty());
+    }
+
+    #[test]
+    fn handle_input_ctrl_c_produces_quit_message() {
+        let mut app = headless_app_with_dom();
+        let event = InputEvent::Key("ctrl+c".parse().unwrap());
+        app.handle_input(event);
+
+        assert_eq!(app.dispatcher.pending_count(), 1);
+        let messages = app.dispatcher.drain();
+        assert!(messages[0].downcast_ref::<Quit>().is_some());
+    }
+
+    #[test]
+    fn handle_input_tab_produces_focus_next_message() {
+        let mut app = headless_app_with_dom();
+        let event = InputEvent::Key(KeyEvent::new(Key::Tab, Modifiers::NONE));
+        app.handle_input(event);
+
+        let messages = app.dispatcher.drain();
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].downcast_ref::<FocusNext>().is_some());
+    }
+
+    #[test]
+    fn handle_input_backtab_produces_focus_previous_message() {
+        let mut app = headless_app_with_dom();
+        let event = InputEvent::Key(KeyEvent::new(Key::BackTab, Modifiers::NONE));
+        app.handle_input(event);
+
+        let messages = app.dispatcher.drain();
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].downcast_ref::<FocusPrevious>().is_some());
+    }
+
+    #[test]
+    fn handle_input_unbound_key_no_message() {
+        let mut app = headless_app_with_dom();
+        let event = InputEvent::Key(KeyEvent::new(Key::Char('z'), Modifiers::NONE));
+        app.handle_input(event);
+
+        assert!(app.dispatcher.is_empty());
+    }
+
+    #[test]
+    fn handle_input_resize_updates_screen() {
+        let mut app = headless_app();
+        let event = InputEvent::Resize {
+            width: 120,
+            height: 40,
+        };
+        app.handle_input(event);
+
+        assert_eq!(app.screen.compositor.width, 120);
+        assert_eq!(app.screen.compositor.height, 40);
+    }
+
+    // ── handle_messages ──────────────────────────────────────────────
+
+    #[test]
+    fn handle_messages_quit() {
+        let mut app = headless_app_with_dom();
+        let root = app.screen.dom.root().unwrap();
+        app.dispatcher.push(Envelope::new(Quit, root));
+        app.handle_messages().unwrap();
+        assert!(app.should_quit());
+    }
+
+    #[test]
+    fn handle_messages_focus_next() {
+        let mut app = headless_app_with_dom();
+        let root = app.screen.dom.root().unwrap();
+        app.dispatcher.push(Envelope::new(FocusNext, root));
+        app.handle_messages().unwrap();
+
+        // Focus should have moved to the first focusable node.
+        assert!(app.screen.focused_node().is_some());
+        // ...and is marked for `:focus` matching.
+        let focused = app.screen.focused_node().unwrap();
+        assert!(app.screen.dom.get(focused).unwrap().focused);
+    }
+
+    #[test]
+    fn focus_changes_are_announced_then_expire() {
+        let mut app = headless_app_with_dom();
+        let root = app.screen.dom.root().unwrap();
+        app.dispatcher.push(Envelope::new(FocusNext, root));
+        app.handle_messages().unwrap();
+        // Off until asked for.
+        assert!(!app.expire_announcement(Instant::now()));
+
+        let announcement = app.focus_announcements();
+        let first = app.screen.focused_node().unwrap();
+        app.screen.dom.get_mut(first).unwrap().aria_label = Some("Save".into());
+        app.dispatcher.push(Envelope::new(FocusPrevious, root));
+        app.handle_messages().unwrap();
+        assert_eq!(
+            app.enter(|_| announcement.get_untracked()).as_deref(),
+            Some("Focused: c")
+        );
+
+        app.dispatcher.push(Envelope::new(FocusNext, root));
+        app.handle_messages().unwrap();
+        let now = Instant::now();
+        assert_eq!(
+            app.enter(|_| announcement.get_untracked()).as_deref(),
+            Some("Focused: Save a")
+        );
+        assert!(!app.expire_announcement(now));
+        assert!(app.expire_announcement(now + ANNOUNCEMENT_DURATION));
+        assert_eq!(app.enter(|_| announcement.get_untracked()), None);
+    }
+
+    #[test]
+    fn app_signals_can_be_read_outside_enter() {
+        let mut app = headless_app_with_dom();
+        let announcement = app.focus_announcements();
+        // Widgets are built and rendered outside `enter`.
+        assert_eq!(announcement.get(), None);
+        let root = app.screen.dom.root().unwrap();
+        app.dispatcher.push(Envelope::new(FocusNext, root));
+        app.handle_messages().unwrap();
+        assert_eq!(announcement.get().as_deref(), Some("Focused: a"));
+        let footer = crate::widgets::Footer::new("").with_announcements(announcement);
+        assert_eq!(footer.text(), "Focused: a");
+    }
+
+    #[test]
+    fn arrow_keys_move_within_focus_group() {
+        let mut app = App::new_headless(80, 24);
+        let root = app.screen.dom.insert(NodeData::new("Root"));
+        let group = app
+            .screen
+            .dom
+            .insert_child(root, NodeData::new("Group").with_class("focus-group"));
+        let first = app
+            .screen
+            .dom
+            .insert_child(group, NodeData::new("Radio").focusable(true));
+        let second = app
+            .screen
+            .dom
+            .insert_child(group, NodeData::new("Radio").focusable(true));
+        app.screen.focus.rebuild(&app.screen.dom);
+        app.screen.focus.focus_node(first);
+
+        app.handle_input(InputEvent::Key(KeyEvent::new(Key::Down, Modifiers::NONE)));
+        assert_eq!(app.screen.focused_node(), Some(second));
+        app.handle_input(InputEvent::Key(KeyEvent::new(Key::Left, Modifiers::NONE)));
+        assert_eq!(app.screen.focused_node(), Some(first));
+        app.handle_input(InputEvent::Key("shift+up".parse().unwrap()));
+        assert_eq!(app.screen.focused_node(), Some(first));
+    }
+
+    #[test]
+    fn handle_messages_focus_previous() {
+        let mut app = headless_app_with_dom();
+        let root = app.screen.dom.root().unwrap();
+        app.dispatcher.push(Envelope::new(FocusPrevious, root));
+        app.handle_messages().unwrap();
+
+        // Focus should have moved to the last focusable node.
+        assert!(app.screen.focused_node().is_some());
+    }
+
+    #[test]
+    fn handle_messages_multiple() {
+        let mut app = headless_app_with_dom();
+        let root = app.screen.dom.root().unwrap();
+        app.dispatcher.push(Envelope::new(FocusNext, root));
+        app.dispatcher.push(Envelope::new(FocusNext, root));
+        app.dispatcher.push(Envelope::new(Quit, root));
+        app.handle_messages().unwrap();
+
+        // Focus moved twice, then quit.
+        assert!(app.should_quit());
+    }
+
+    #[test]
+    fn handle_messages_drains_queue() {
+        let mut app = headless_app_with_dom();
+        let root = app.screen.dom.root().unwrap();
+        app.dispatcher.push(Envelope::new(Refresh, root));
+        app.handle_messages().unwrap();
+        assert!(app.dispatcher.is_empty());
+    }
+
+    // ── handle_input without DOM root ────────────────────────────────
+
+    #[test]
+    fn handle_input_no_dom_root_no_panic() {
+        let mut app = headless_app();
+        // No DOM root — should not panic.
+        let event = InputEvent::Key("ctrl+c".parse().unwrap());
+        app.handle_input(event);
+        assert!(app.dispatcher.is_empty());
+    }
+
+    // ── query ────────────────────────────────────────────────────────
+
+    #[test]
+    fn query_reuses_compiled_selector() {
+        let app = headless_app_with_dom();
+        let focusable = Query::compile("Root > A, Root > C").unwrap();
+        let found = app.query(&focusable);
+        assert_eq!(found.len(), 2);
+        assert_eq!(app.query_one(&focusable), Some(found[0]));
+        assert!(app.query_one(&Query::compile("Missing").unwrap()).is_none());
     }
 
     #[test]
-    fn handle_input_resize_updates_screen() {
+    fn geometry_queries_by_selector_or_id() {
+        let mut app = App::new_headless(20, 10);
+        let root = app.screen.dom.insert(NodeData::new("Root"));
+        let save = app
+            .screen
+            .dom
+            .insert_child(root, NodeData::new("Button").with_id("save"));
+        let mut styles = Styles::new();
+        styles.width = Some(crate::css::scalar::Scalar::cells(6.0));
+        styles.height = Some(crate::css::scalar::Scalar::cells(1.0));
+        app.screen.styles.insert(save, styles);
+        assert_eq!(app.region_of("#save").unwrap(), None);
+
+        app.screen.compute_layout().unwrap();
+        let region = Region::new(0, 0, 6, 1);
+        assert_eq!(app.region_of("#save").unwrap(), Some(region));
+        assert_eq!(app.visible_region(save).unwrap(), Some(region));
+        assert_eq!(app.widget_at(2, 0), Some(save));
+        assert_eq!(app.region_of("#missing").unwrap(), None);
+        assert!(app.region_of("Root >").is_err());
+    }
+
+    // ── perf HUD ─────────────────────────────────────────────────────
+
+    #[test]
+    fn perf_hud_hidden_by_default() {
         let mut app = headless_app();
-        let event = InputEvent::Resize {
-            width: 120,
-            height: 40,
-        };
-        app.handle_input(event);
+        assert!(!app.perf_hud_visible());
+        app.draw_perf_hud();
+        assert_eq!(app.screen.compositor.get_cell(79, 0).unwrap().ch, ' ');
+    }
 
-        assert_eq!(app.screen.compositor.width, 120);
-        assert_eq!(app.screen.compositor.height, 40);
+    #[test]
+    fn perf_hud_toggle_message() {
+        let mut app = headless_app_with_dom();
+        let root = app.screen.dom.root().unwrap();
+        app.dispatcher.push(Envelope::new(message::TogglePerfHud, root));
+        app.handle_messages().unwrap();
+        assert!(app.perf_hud_visible());
+        assert!(app.screen.compositor.is_dirty());
     }
 
-    // ── handle_messages ──────────────────────────────────────────────
+    #[test]
+    fn perf_hud_draws_top_right() {
+        let mut app = headless_app();
+        app.set_perf_hud(true);
+        app.draw_perf_hud();
+        let x = 80 - HUD_WIDTH as u16;
+        assert_eq!(app.screen.compositor.get_cell(x, 0).unwrap().ch, 'F');
+        assert_eq!(
+            app.screen.compositor.get_cell(x, 0).unwrap().style.bg,
+            Some(Color::Rgb(0x20, 0x20, 0x20))
+        );
+        // Left of the HUD is untouched.
+        assert!(app.screen.compositor.get_cell(x - 1, 0).unwrap().style.bg.is_none());
+    }
 
     #[test]
-    fn handle_messages_quit() {
+    fn render_frame_records_stats() {
+        let mut app = headless_app_with_dom();
+        app.screen.add_css("A { width: 5; height: 1; }").unwrap();
+        let a = app.screen.dom.children(app.screen.dom.root().unwrap())[0];
+        app.handle_input(key(Key::Tab));
+
+        let changed = app.render_frame(|app| {
+            app.render_widget(a, &Static::new("hi"));
+        });
+        assert_eq!(changed.unwrap(), 2);
+        assert_eq!(app.stats.frames, 1);
+        assert_eq!(app.stats.cells_updated, 2);
+        assert!(app.stats.last.event > Duration::ZERO);
+        assert!(app.stats.last.layout > Duration::ZERO);
+
+        // Nothing changed: the frame is still recorded, with no updates.
+        let changed = app.render_frame(|app| {
+            app.render_widget(a, &Static::new("hi"));
+        });
+        assert_eq!(changed.unwrap(), 0);
+        assert_eq!(app.stats.frames, 2);
+        assert_eq!(app.stats.last.event, Duration::ZERO);
+    }
+
+    #[test]
+    fn perf_hud_uses_stylesheet_rules() {
+        let mut app = headless_app();
+        app.screen.add_css("PerfHud { background: #000080; }").unwrap();
+        app.set_perf_hud(true);
+        app.draw_perf_hud();
+        let cell = app.screen.compositor.get_cell(80 - HUD_WIDTH as u16, 0).unwrap();
+        assert_eq!(cell.style.bg, Some(Color::Rgb(0, 0, 0x80)));
+        // Properties the app leaves alone keep the built-in default.
+        assert_eq!(cell.style.fg, Some(Color::Rgb(0, 0xff, 0x87)));
+    }
+
+    // ── mouse capture ────────────────────────────────────────────────
+
+    #[test]
+    fn mouse_capture_set_and_toggle() {
+        let mut app = headless_app();
+        assert!(!app.mouse_capture_enabled());
+        app.set_mouse_capture(true).unwrap();
+        assert!(app.mouse_capture_enabled());
+        app.toggle_mouse_capture().unwrap();
+        assert!(!app.mouse_capture_enabled());
+    }
+
+    #[test]
+    fn mouse_capture_toggle_message() {
         let mut app = headless_app_with_dom();
+        app.set_mouse_capture(true).unwrap();
         let root = app.screen.dom.root().unwrap();
-        app.dispatcher.push(Envelope::new(Quit, root));
-        app.handle_messages();
-        assert!(app.should_quit());
+        app.dispatcher.push(Envelope::new(message::ToggleMouseCapture, root));
+        app.handle_messages().unwrap();
+        assert!(!app.mouse_capture_enabled());
+        app.dispatcher.push(Envelope::new(message::ToggleMouseCapture, root));
+        app.handle_messages().unwrap();
+        assert!(app.mouse_capture_enabled());
     }
 
     #[test]
-    fn handle_messages_focus_next() {
+    fn f9_toggles_mouse_capture_by_default() {
+        let mut app = headless_app_with_dom();
+        app.set_mouse_capture(true).unwrap();
+        app.handle_input(InputEvent::Key("f9".parse().unwrap()));
+        app.handle_messages().unwrap();
+        assert!(!app.mouse_capture_enabled());
+        app.handle_input(InputEvent::Key("f9".parse().unwrap()));
+        app.handle_messages().unwrap();
+        assert!(app.mouse_capture_enabled());
+    }
+
+    // ── glyph policy ─────────────────────────────────────────────────
+
+    #[test]
+    fn glyph_policy_applies_to_placed_strips_and_repaints() {
+        let mut app = headless_app();
+        assert!(app.glyph_policy().is_passthrough());
+        app.screen.compositor.clear_dirty();
+        app.set_glyph_policy(GlyphPolicy::new().with_wide_fallback('#'));
+        assert_eq!(app.glyph_policy().wide_fallback, Some('#'));
+        assert!(app.screen.compositor.is_dirty());
+
+        let mut strip = Strip::new(0, 0);
+        strip.push_str("漢a", CellStyle::default());
+        let region = app.screen.compositor.screen_region();
+        app.screen.compositor.place_strips(&[strip], &region);
+        assert_eq!(app.screen.compositor.get_cell(0, 0).unwrap().ch, '#');
+        assert_eq!(app.screen.compositor.get_cell(1, 0).unwrap().ch, 'a');
+
+        let config = AppConfig::default().with_glyph_policy(GlyphPolicy::safe());
+        assert_eq!(config.glyphs, GlyphPolicy::safe());
+    }
+
+    #[test]
+    fn delegated_handlers_can_swallow_messages() {
         let mut app = headless_app_with_dom();
         let root = app.screen.dom.root().unwrap();
-        app.dispatcher.push(Envelope::new(FocusNext, root));
-        app.handle_messages();
+        let b = app.screen.dom.children(root)[1];
+        let id = app.on::<message::Quit>(root, "B", |_, _| true).unwrap();
+        assert!(app.on::<message::Quit>(root, "B >", |_, _| true).is_err());
 
-        // Focus should have moved to the first focusable node.
-        assert!(app.screen.focused_node().is_some());
+        app.dispatcher.push(Envelope::new(message::Quit, b));
+        app.handle_messages().unwrap();
+        assert!(!app.should_quit());
+
+        assert!(app.off(id));
+        app.dispatcher.push(Envelope::new(message::Quit, b));
+        app.handle_messages().unwrap();
+        assert!(app.should_quit());
     }
 
     #[test]
-    fn handle_messages_focus_previous() {
+    fn slow_handlers_are_reported_against_the_budget() {
         let mut app = headless_app_with_dom();
+        app.budget
+            .set_budget(FrameBudget::new().with_handler(Duration::from_millis(1)));
         let root = app.screen.dom.root().unwrap();
-        app.dispatcher.push(Envelope::new(FocusPrevious, root));
-        app.handle_messages();
+        let b = app.screen.dom.children(root)[1];
+        app.on::<message::Quit>(root, "B", |_, _| {
+            thread::sleep(Duration::from_millis(5));
+            true
+        })
+        .unwrap();
+        app.dispatcher.push(Envelope::new(message::Quit, b));
+        app.handle_messages().unwrap();
+        app.set_timer(Duration::ZERO, |_| thread::sleep(Duration::from_millis(5)));
+        app.run_timers(Instant::now());
+        // Fast work stays quiet.
+        app.on_idle(|_| {});
+        app.run_idle();
 
-        // Focus should have moved to the last focusable node.
-        assert!(app.screen.focused_node().is_some());
+        let warnings = app.budget.drain_warnings();
+        let nodes: Vec<_> = warnings.iter().map(|w| (w.node, w.kind)).collect();
+        assert_eq!(nodes, [(b, WorkKind::Handler), (root, WorkKind::Handler)]);
     }
 
     #[test]
-    fn handle_messages_multiple() {
+    fn render_widget_is_timed_and_placed() {
+        let mut app = headless_app_with_dom();
+        app.screen.add_css("A { width: 5; height: 1; }").unwrap();
+        app.screen.restyle();
+        app.screen.compute_layout().unwrap();
+        app.budget.set_budget(FrameBudget::new().with_render(Duration::ZERO));
+        let a = app.screen.dom.children(app.screen.dom.root().unwrap())[0];
+
+        assert!(app.render_widget(a, &Static::new("hi")));
+        assert_eq!(app.screen.compositor.get_cell(0, 0).unwrap().ch, 'h');
+        let warnings = app.budget.drain_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!((warnings[0].node, warnings[0].kind), (a, WorkKind::Render));
+    }
+
+    #[test]
+    fn copy_message_fills_clipboard() {
         let mut app = headless_app_with_dom();
         let root = app.screen.dom.root().unwrap();
-        app.dispatcher.push(Envelope::new(FocusNext, root));
-        app.dispatcher.push(Envelope::new(FocusNext, root));
-        app.dispatcher.push(Envelope::new(Quit, root));
-        app.handle_messages();
+        assert_eq!(app.clipboard(), None);
+        let copy = message::CopyToClipboard("cargo run".into());
+        app.dispatcher.push(Envelope::new(copy, root));
+        app.handle_messages().unwrap();
+        assert_eq!(app.clipboard(), Some("cargo run"));
+    }
 
-        // Focus moved twice, then quit.
+    #[test]
+    fn cycle_density_message_moves_root_class() {
+        let mut app = headless_app_with_dom();
+        let root = app.screen.dom.root().unwrap();
+        app.dispatcher.push(Envelope::new(message::CycleDensity, root));
+        app.handle_messages().unwrap();
+        assert_eq!(app.screen.density(), Density::Comfortable);
+        let node = app.screen.dom.get(root).unwrap();
+        assert!(node.has_class("density-comfortable"));
+        assert!(!node.has_class("density-normal"));
+    }
+
+    // ── print ────────────────────────────────────────────────────────
+
+    #[test]
+    fn print_headless_schedules_repaint() {
+        let mut app = headless_app();
+        app.screen.compositor.clear_dirty();
+        app.print("hello").unwrap();
+        assert!(app.screen.compositor.is_dirty());
+    }
+
+    #[test]
+    fn transition_overlays_until_finished() {
+        use crate::render::strip::{CellStyle, Strip};
+
+        let mut app = App::new_headless(10, 2);
+        let region = Region::new(0, 0, 10, 2);
+        let mut old = Strip::new(0, 0);
+        old.push_str("old", CellStyle::new());
+        app.screen.compositor.place_strips(&[old], &region);
+
+        app.begin_transition(region, TransitionEffect::SlideLeft, Duration::from_secs(60));
+        assert!(app.transition_active());
+        let mut new = Strip::new(0, 0);
+        new.push_str("new", CellStyle::new());
+        app.screen.compositor.place_strips(&[new.clone()], &region);
+
+        app.screen.compositor.clear_dirty();
+        assert!(app.draw_transition(Instant::now()));
+        // Barely started: the old content is still showing.
+        assert_eq!(app.screen.compositor.get_cell(0, 0).unwrap().ch, 'o');
+        assert!(app.screen.compositor.is_dirty());
+
+        // The next frame re-renders the real content before the overlay.
+        app.screen.compositor.place_strips(&[new], &region);
+        assert!(!app.draw_transition(Instant::now() + Duration::from_secs(61)));
+        assert!(!app.transition_active());
+        assert_eq!(app.screen.compositor.get_cell(0, 0).unwrap().ch, 'n');
+    }
+
+    // ── external sources ─────────────────────────────────────────────
+
+    #[test]
+    fn watch_channel_wakes_and_injects_messages() {
+        let mut app = headless_app_with_dom();
+        let (tx, rx) = std::sync::mpsc::channel::<u32>();
+        app.watch_channel(rx, |n| message::Custom::new(format!("job-{n}")));
+        tx.send(7).unwrap();
+        assert!(app.wait_external(Some(Duration::from_secs(5))));
+        assert_eq!(app.pump_external(), 1);
+        let envelope = app.dispatcher.drain().pop().unwrap();
+        let custom = envelope.downcast_ref::<message::Custom>().unwrap();
+        assert_eq!(custom.0, "job-7");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn watch_fd_maps_bytes_to_messages() {
+        use std::io::Write;
+        use std::os::unix::net::UnixStream;
+
+        let mut app = headless_app_with_dom();
+        let (mut writer, reader) = UnixStream::pair().unwrap();
+        app.watch_fd(reader, |bytes| (bytes == b"quit").then_some(Quit));
+        writer.write_all(b"quit").unwrap();
+        assert!(app.wait_external(Some(Duration::from_secs(5))));
+        app.pump_external();
+        app.handle_messages().unwrap();
         assert!(app.should_quit());
     }
 
     #[test]
-    fn handle_messages_drains_queue() {
+    fn pump_external_waits_for_a_dom() {
+        let mut app = headless_app();
+        assert!(app.inbox_sender().send(Refresh));
+        assert_eq!(app.pump_external(), 0);
+        app.screen.dom.insert(NodeData::new("Root"));
+        assert_eq!(app.pump_external(), 1);
+    }
+
+    #[test]
+    fn sync_signal_writes_land_on_pump() {
+        let mut app = headless_app();
+        let (progress, publish) = app.create_sync_signal(0u8);
+        thread::spawn(move || publish.set(100)).join().unwrap();
+        assert!(app.wait_external(Some(Duration::ZERO)));
+        assert_eq!(app.enter(|_| progress.get()), 0);
+        app.pump_external();
+        assert_eq!(app.enter(|_| progress.get()), 100);
+    }
+
+    #[test]
+    fn widget_tasks_are_cancelled_on_unmount() {
+        use std::sync::mpsc;
+
+        let mut app = headless_app();
+        let root = app.screen.dom.insert(NodeData::new("Root"));
+        let child = app.screen.dom.insert_child(root, NodeData::new("Log"));
+        let (go_tx, go_rx) = mpsc::channel::<()>();
+        let (done_tx, done_rx) = mpsc::channel();
+        let handle = app.widget_ctx(child).spawn(move |ctx| {
+            assert!(ctx.post(Refresh));
+            go_rx.recv().unwrap();
+            done_tx.send(ctx.post(Refresh)).unwrap();
+        });
+        assert!(app.wait_external(Some(Duration::from_secs(5))));
+
+        let mut tx = Transaction::new();
+        tx.remove(child);
+        app.screen.apply(tx).unwrap();
+        assert!(handle.is_cancelled());
+        go_tx.send(()).unwrap();
+        assert!(!done_rx.recv().unwrap());
+        // The message posted before the unmount has no live sender.
+        assert_eq!(app.pump_external(), 0);
+    }
+
+    #[test]
+    fn worker_results_arrive_as_messages() {
+        use crate::event::message::Custom;
+
+        let mut app = headless_app_with_dom();
+        let handle = app.spawn_worker(|_| Custom::new("loaded"));
+        assert!(app.wait_external(Some(Duration::from_secs(5))));
+        assert_eq!(app.pump_external(), 1);
+        let envelopes = app.dispatcher.drain();
+        let loaded = envelopes[0].downcast_ref::<Custom>().unwrap();
+        assert_eq!(loaded.0, "loaded");
+        assert_eq!(envelopes[0].sender, app.screen.dom.root().unwrap());
+        assert!(handle.is_finished());
+    }
+
+    // ── loop cadence ─────────────────────────────────────────────────
+
+    #[test]
+    fn tick_period_from_fps_or_override() {
+        assert_eq!(AppConfig::new().with_fps(50).tick_period(), Duration::from_millis(20));
+        let config = AppConfig::new().with_tick_interval(Duration::from_millis(250));
+        assert_eq!(config.tick_period(), Duration::from_millis(250));
+    }
+
+    #[test]
+    fn poll_timeout_follows_pending_work() {
         let mut app = headless_app_with_dom();
+        app.screen.compositor.clear_dirty();
+        assert_eq!(app.poll_timeout(), Some(app.config.tick_period()));
+
+        app.config.power_saving = true;
+        assert_eq!(app.poll_timeout(), None);
+
         let root = app.screen.dom.root().unwrap();
         app.dispatcher.push(Envelope::new(Refresh, root));
-        app.handle_messages();
-        assert!(app.dispatcher.is_empty());
+        assert_eq!(app.poll_timeout(), Some(Duration::ZERO));
     }
 
-    // ── handle_input without DOM root ────────────────────────────────
+    #[test]
+    fn timers_fire_once_and_intervals_repeat() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let mut app = headless_app_with_dom();
+        let ticks = Rc::new(Cell::new(0));
+        let counter = ticks.clone();
+        let interval = app.set_interval(Duration::from_millis(10), move |_| {
+            counter.set(counter.get() + 1);
+        });
+        let cancel = interval.clone();
+        app.set_timer(Duration::from_millis(25), move |_| cancel.cancel());
+        app.post_after(Duration::from_millis(5), Refresh);
+        assert_eq!(app.pending_timers(), 3);
+
+        let start = Instant::now();
+        assert_eq!(app.run_timers(start), 0);
+        assert_eq!(app.run_timers(start + Duration::from_millis(15)), 2);
+        assert!(!app.dispatcher.is_empty());
+        assert_eq!(ticks.get(), 1);
+        app.run_timers(start + Duration::from_millis(30));
+        assert!(interval.is_cancelled());
+        assert_eq!(app.pending_timers(), 0);
+        assert_eq!(app.run_timers(start + Duration::from_secs(1)), 0);
+    }
 
     #[test]
-    fn handle_input_no_dom_root_no_panic() {
-        let mut app = headless_app();
-        // No DOM root — should not panic.
-        let event = InputEvent::Key(KeyEvent::new(Key::Char('c'), Modifiers::CTRL));
-        app.handle_input(event);
-        assert!(app.dispatcher.is_empty());
+    fn poll_timeout_wakes_for_timers() {
+        let mut app = headless_app_with_dom();
+        app.screen.compositor.clear_dirty();
+        app.config.power_saving = true;
+        let timer = app.set_timer(Duration::from_secs(5), |_| {});
+        let timeout = app.poll_timeout().unwrap();
+        assert!(timeout > Duration::from_secs(4) && timeout <= Duration::from_secs(5));
+
+        app.config.power_saving = false;
+        assert_eq!(app.poll_timeout(), Some(app.config.tick_period()));
+        timer.cancel();
+        app.config.power_saving = true;
+        assert_eq!(app.poll_timeout(), None);
+    }
+
+    #[test]
+    fn idle_callbacks_run_only_when_idle() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let mut app = headless_app_with_dom();
+        let runs = Rc::new(Cell::new(0));
+        let counter = runs.clone();
+        app.on_idle(move |_| counter.set(counter.get() + 1));
+
+        app.screen.compositor.mark_all_dirty();
+        assert!(!app.run_idle());
+        app.screen.compositor.clear_dirty();
+        assert!(app.run_idle());
+        assert!(app.run_idle());
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[test]
+    fn apps_have_independent_runtimes() {
+        use crate::reactive::{create_signal, Runtime};
+
+        let mut first = App::new_headless(80, 24);
+        let mut second = App::new_headless(80, 24);
+        let count = first.enter(|_| create_signal(1).0);
+        second.enter(|_| {
+            create_signal("a");
+            create_signal("b");
+        });
+        assert_eq!(first.runtime().stats().signals, 1);
+        assert_eq!(second.runtime().stats().signals, 2);
+        assert_eq!(first.enter(|_| count.get()), 1);
+
+        let shared = App::new_headless(80, 24).with_runtime(first.runtime().clone());
+        assert_eq!(shared.runtime().stats().signals, 1);
+        assert!(!first.runtime().is_current());
+        assert_eq!(Runtime::current().stats().signals, 0);
+    }
+
+    #[test]
+    fn batch_update_runs_one_pass_at_the_end() {
+        use crate::reactive::create_effect;
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let mut app = headless_app_with_dom();
+        app.screen.add_css("Item { height: 2; }").unwrap();
+        let root = app.screen.dom.root().unwrap();
+        let runs = Rc::new(Cell::new(0));
+        let (count, set_count) = app.enter(|_| create_signal(0));
+        let counter = runs.clone();
+        app.enter(|_| {
+            create_effect(move || {
+                count.get();
+                counter.set(counter.get() + 1);
+            })
+        });
+        app.screen.compositor.clear_dirty();
+
+        let items = app
+            .batch_update(|app| {
+                let items: Vec<NodeId> = (0..3)
+                    .map(|i| {
+                        set_count.set(i + 1);
+                        app.screen
+                            .transaction(|tx| {
+                                tx.insert_child(root, NodeData::new("Item"));
+                            })
+                            .unwrap()
+                            .inserted[0]
+                    })
+                    .collect();
+                assert!(!app.screen.compositor.is_dirty());
+                items
+            })
+            .unwrap();
+        assert_eq!(runs.get(), 2);
+        assert!(app.screen.compositor.is_dirty());
+        assert_eq!(app.screen.region_of(items[2]).unwrap().y, 4);
+    }
+
+    // ── context menu ─────────────────────────────────────────────────
+
+    fn menu_app() -> (App, NodeId) {
+        use crate::widgets::context_menu::MenuItem;
+        let mut app = headless_app_with_dom();
+        let root = app.screen.dom.root().unwrap();
+        let target = app.screen.dom.children(root)[1];
+        app.screen.spatial.update_ordered(&[
+            (root, Region::new(0, 0, 80, 24)),
+            (target, Region::new(10, 5, 20, 3)),
+        ]);
+        app.screen.set_context_menu(
+            target,
+            vec![
+                MenuItem::new("Copy", || Box::new(message::Custom::new("copy"))),
+                MenuItem::new("Paste", || Box::new(message::Custom::new("paste"))),
+            ],
+        );
+        (app, target)
+    }
+
+    fn mouse(kind: MouseAction, x: u16, y: u16) -> InputEvent {
+        InputEvent::Mouse(MouseEvent {
+            kind,
+            x,
+            y,
+            modifiers: Modifiers::NONE,
+        })
+    }
+
+    fn key(code: Key) -> InputEvent {
+        InputEvent::Key(KeyEvent::new(code, Modifiers::NONE))
+    }
+
+    #[test]
+    fn right_click_opens_menu_at_pointer() {
+        let (mut app, target) = menu_app();
+        app.handle_input(mouse(MouseAction::Down(MouseBtn::Right), 12, 6));
+        let menu = app.context_menu().unwrap();
+        assert_eq!(menu.origin(), target);
+        assert_eq!(menu.region().offset(), crate::geometry::Offset::new(12, 6));
+
+        app.draw_context_menu();
+        assert_eq!(app.screen.compositor.get_cell(13, 6).unwrap().ch, 'C');
+        assert_eq!(
+            app.screen.compositor.get_cell(13, 6).unwrap().style.bg,
+            Some(Color::Rgb(0x30, 0x30, 0x30))
+        );
+    }
+
+    #[test]
+    fn context_menu_uses_stylesheet_rules() {
+        let (mut app, _) = menu_app();
+        app.screen.add_css("ContextMenu { background: #000080; }").unwrap();
+        app.handle_input(mouse(MouseAction::Down(MouseBtn::Right), 12, 6));
+        app.draw_context_menu();
+        let cell = app.screen.compositor.get_cell(13, 6).unwrap();
+        assert_eq!(cell.style.bg, Some(Color::Rgb(0, 0, 0x80)));
+    }
+
+    #[test]
+    fn right_click_without_menu_does_nothing() {
+        let (mut app, _) = menu_app();
+        app.handle_input(mouse(MouseAction::Down(MouseBtn::Right), 50, 20));
+        assert!(app.context_menu().is_none());
+    }
+
+    #[test]
+    fn keyboard_choice_dispatches_to_origin() {
+        let (mut app, target) = menu_app();
+        app.handle_input(mouse(MouseAction::Down(MouseBtn::Right), 12, 6));
+        app.handle_input(key(Key::Down));
+        // Keys are captured by the menu, not the global bindings.
+        app.handle_input(key(Key::Tab));
+        app.handle_input(key(Key::Tab));
+        app.handle_input(key(Key::Enter));
+
+        assert!(app.context_menu().is_none());
+        let messages = app.dispatcher.drain();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].target, Some(target));
+        assert_eq!(
+            messages[0].downcast_ref::<message::Custom>(),
+            Some(&message::Custom::new("paste"))
+        );
+    }
+
+    #[test]
+    fn escape_and_outside_click_close_menu() {
+        let (mut app, _) = menu_app();
+        app.handle_input(mouse(MouseAction::Down(MouseBtn::Right), 12, 6));
+        app.handle_input(key(Key::Escape));
+        assert!(app.context_menu().is_none());
+
+        app.handle_input(mouse(MouseAction::Down(MouseBtn::Right), 12, 6));
+        app.handle_input(mouse(MouseAction::Down(MouseBtn::Left), 70, 20));
+        assert!(app.context_menu().is_none());
+        assert!(app.dispatcher.drain().is_empty());
+    }
+
+    #[test]
+    fn click_on_item_chooses_it() {
+        let (mut app, _) = menu_app();
+        app.handle_input(mouse(MouseAction::Down(MouseBtn::Right), 12, 6));
+        app.handle_input(mouse(MouseAction::Down(MouseBtn::Left), 13, 6));
+        let messages = app.dispatcher.drain();
+        assert_eq!(
+            messages[0].downcast_ref::<message::Custom>(),
+            Some(&message::Custom::new("copy"))
+        );
+    }
+
+    #[test]
+    fn key_tables_translate_before_binding_lookup() {
+        let mut app = headless_app_with_dom();
+        app.bindings
+            .bind_str("ctrl+1", BindingAction::Custom("first-tab".into()))
+            .unwrap();
+        app.add_key_table(KeyTable::new("arabic-digits").with_digits('٠'));
+        app.handle_input(InputEvent::Key("ctrl+١".parse().unwrap()));
+        let messages = app.dispatcher.drain();
+        assert_eq!(
+            messages[0].downcast_ref::<message::Custom>(),
+            Some(&message::Custom::new("first-tab"))
+        );
+
+        assert!(app.remove_key_table("arabic-digits"));
+        app.handle_input(InputEvent::Key("ctrl+١".parse().unwrap()));
+        assert!(app.dispatcher.drain().is_empty());
+    }
+
+    #[test]
+    fn gestures_are_sent_from_the_node_under_the_pointer() {
+        use crate::css::scalar::Scalar;
+        let mut app = App::new_headless(20, 10);
+        let root = app.screen.dom.insert(NodeData::new("Root"));
+        let target = app.screen.dom.insert_child(root, NodeData::new("Row"));
+        let mut styles = Styles::new();
+        styles.width = Some(Scalar::cells(20.0));
+        styles.height = Some(Scalar::cells(3.0));
+        app.screen.styles.insert(target, styles);
+        app.screen.compute_layout().unwrap();
+        app.screen.update_spatial_map();
+
+        app.handle_input(mouse(MouseAction::Down(MouseBtn::Left), 2, 1));
+        app.handle_input(mouse(MouseAction::Up(MouseBtn::Left), 2, 1));
+        // Dragging off the node still reports to the node it started on.
+        app.handle_input(mouse(MouseAction::Down(MouseBtn::Left), 1, 0));
+        app.handle_input(mouse(MouseAction::Drag(MouseBtn::Left), 5, 8));
+        let messages = app.dispatcher.drain();
+        let sent: Vec<(&str, NodeId)> = messages
+            .iter()
+            .map(|m| (m.message.message_name(), m.sender))
+            .collect();
+        assert_eq!(
+            sent,
+            [("Click", target), ("DragStart", target), ("DragMove", target)]
+        );
+    }
+
+    #[test]
+    fn wheel_scrolls_container_under_pointer() {
+        use crate::css::scalar::Scalar;
+        use crate::css::styles::Overflow;
+        use crate::geometry::Offset;
+        let mut app = App::new_headless(20, 10);
+        let list = app.screen.dom.insert(NodeData::new("List"));
+        let mut styles = Styles::new();
+        styles.width = Some(Scalar::cells(10.0));
+        styles.height = Some(Scalar::cells(4.0));
+        styles.overflow_y = Some(Overflow::Scroll);
+        app.screen.styles.insert(list, styles);
+        for _ in 0..4 {
+            let item = app.screen.dom.insert_child(list, NodeData::new("Item"));
+            let mut styles = Styles::new();
+            styles.min_height = Some(Scalar::cells(3.0));
+            app.screen.styles.insert(item, styles);
+        }
+        app.screen.compute_layout().unwrap();
+
+        app.handle_input(mouse(MouseAction::ScrollDown, 2, 2));
+        assert_eq!(app.screen.scroll_offset(list), Offset::new(0, WHEEL_SCROLL_LINES));
+        app.handle_input(mouse(MouseAction::ScrollUp, 2, 2));
+        assert_eq!(app.screen.scroll_offset(list), Offset::new(0, 0));
+        // Outside the list nothing scrolls.
+        app.handle_input(mouse(MouseAction::ScrollDown, 15, 8));
+        assert_eq!(app.screen.scroll_offset(list), Offset::new(0, 0));
+        assert!(app.dispatcher.drain().is_empty());
+    }
+
+    #[test]
+    fn dragging_the_scrollbar_thumb_scrolls() {
+        use crate::css::scalar::Scalar;
+        use crate::css::styles::Overflow;
+        use crate::geometry::Offset;
+        let mut app = App::new_headless(20, 10);
+        let list = app.screen.dom.insert(NodeData::new("List"));
+        let mut styles = Styles::new();
+        styles.width = Some(Scalar::cells(10.0));
+        styles.height = Some(Scalar::cells(4.0));
+        styles.overflow_y = Some(Overflow::Scroll);
+        app.screen.styles.insert(list, styles);
+        for _ in 0..4 {
+            let item = app.screen.dom.insert_child(list, NodeData::new("Item"));
+            let mut styles = Styles::new();
+            styles.min_height = Some(Scalar::cells(3.0));
+            app.screen.styles.insert(item, styles);
+        }
+        app.screen.compute_layout().unwrap();
+
+        // 4 of 12 rows visible: a one-row thumb at the top of column 9.
+        app.handle_input(mouse(MouseAction::Down(MouseBtn::Left), 9, 0));
+        assert!(app.is_dragging_scrollbar());
+        app.handle_input(mouse(MouseAction::Drag(MouseBtn::Left), 9, 3));
+        assert_eq!(app.screen.scroll_offset(list), Offset::new(0, 8));
+        app.handle_input(mouse(MouseAction::Up(MouseBtn::Left), 9, 3));
+        assert!(!app.is_dragging_scrollbar());
+
+        // Pressing the track jumps the thumb to the pointer.
+        app.handle_input(mouse(MouseAction::Down(MouseBtn::Left), 9, 1));
+        assert_eq!(app.screen.scroll_offset(list), Offset::new(0, 3));
+        app.handle_input(mouse(MouseAction::Up(MouseBtn::Left), 9, 1));
+        assert!(app.dispatcher.drain().is_empty());
+    }
+
+    // ── command palette ──────────────────────────────────────────────
+
+    #[test]
+    fn ctrl_p_opens_palette_and_enter_runs_command() {
+        let (mut app, _) = menu_app();
+        app.register_command("Toggle Sidebar", BindingAction::Custom("sidebar".into()));
+        app.register_command("Save File", BindingAction::Custom("save".into()));
+        app.handle_input(InputEvent::Key("ctrl+p".parse().unwrap()));
+        assert_eq!(app.command_palette().unwrap().matches().len(), 2);
+
+        // Typed keys go to the query, not to the global bindings.
+        app.handle_input(key(Key::Char('s')));
+        app.handle_input(key(Key::Char('f')));
+        assert_eq!(app.command_palette().unwrap().query(), "sf");
+        app.screen.add_css("CommandPalette { color: #ffff00; }").unwrap();
+        app.draw_command_palette();
+        let region = app.command_palette().unwrap().region();
+        let (x, y) = (region.x as u16 + 1, region.y as u16 + 1);
+        assert_eq!(app.screen.compositor.get_cell(x, y).unwrap().ch, 'S');
+        // The app's rule wins; the built-in background stays.
+        let style = app.screen.compositor.get_cell(x, y).unwrap().style;
+        assert_eq!(style.fg, Some(Color::Rgb(0xff, 0xff, 0)));
+        assert_eq!(style.bg, Some(Color::Rgb(0x26, 0x26, 0x26)));
+
+        app.handle_input(key(Key::Enter));
+        assert!(app.command_palette().is_none());
+        let messages = app.dispatcher.drain();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(
+            messages[0].downcast_ref::<message::Custom>(),
+            Some(&message::Custom::new("save"))
+        );
+    }
+
+    #[test]
+    fn escape_and_outside_click_close_palette() {
+        let (mut app, _) = menu_app();
+        app.register_command("Quit", BindingAction::Quit);
+        app.open_command_palette();
+        app.handle_input(key(Key::Escape));
+        assert!(app.command_palette().is_none());
+
+        app.open_command_palette();
+        app.handle_input(mouse(MouseAction::Down(MouseBtn::Left), 0, 23));
+        assert!(app.command_palette().is_none());
+        assert!(app.dispatcher.drain().is_empty());
+    }
+
+    // ── rubber-band selection ────────────────────────────────────────
+
+    /// A 40x10 canvas with two 10x3 items stacked in its top-left corner.
+    fn canvas_app() -> (App, NodeId, Vec<NodeId>) {
+        use crate::css::scalar::Scalar;
+        let mut app = App::new_headless(80, 24);
+        let screen = &mut app.screen;
+        let canvas = screen.dom.insert(NodeData::new("Canvas"));
+        let mut styles = Styles::new();
+        styles.width = Some(Scalar::cells(40.0));
+        styles.height = Some(Scalar::cells(10.0));
+        screen.styles.insert(canvas, styles);
+        let items: Vec<NodeId> = (0..2)
+            .map(|_| {
+                let item = screen.dom.insert_child(canvas, NodeData::new("Item"));
+                let mut styles = Styles::new();
+                styles.width = Some(Scalar::cells(10.0));
+                styles.height = Some(Scalar::cells(3.0));
+                screen.styles.insert(item, styles);
+                item
+            })
+            .collect();
+        screen.compute_layout().unwrap();
+        screen.set_rubber_band(canvas, true);
+        (app, canvas, items)
+    }
+
+    #[test]
+    fn rubber_band_drag_selects_intersecting_children() {
+        let (mut app, canvas, items) = canvas_app();
+        // Pressing on a child does not start a band.
+        app.handle_input(mouse(MouseAction::Down(MouseBtn::Left), 2, 1));
+        assert!(app.rubber_band().is_none());
+
+        app.handle_input(mouse(MouseAction::Down(MouseBtn::Left), 30, 1));
+        app.handle_input(mouse(MouseAction::Drag(MouseBtn::Left), 5, 2));
+        assert_eq!(app.rubber_band().unwrap().region(), Region::new(5, 1, 26, 2));
+        app.draw_rubber_band();
+        assert_eq!(app.screen.compositor.get_cell(5, 1).unwrap().ch, '┌');
+        assert_eq!(
+            app.screen.compositor.get_cell(5, 1).unwrap().style.fg,
+            Some(Color::Rgb(0x5f, 0xaf, 0xff))
+        );
+        app.screen.add_css("RubberBand { color: #ff0000; }").unwrap();
+        app.draw_rubber_band();
+        assert_eq!(
+            app.screen.compositor.get_cell(5, 1).unwrap().style.fg,
+            Some(Color::Rgb(0xff, 0, 0))
+        );
+        // Keys other than Escape still go to the bindings.
+        app.handle_input(key(Key::Tab));
+        app.dispatcher.drain();
+
+        app.handle_input(mouse(MouseAction::Up(MouseBtn::Left), 5, 4));
+        assert!(app.rubber_band().is_none());
+        let messages = app.dispatcher.drain();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].sender, canvas);
+        assert_eq!(
+            messages[0].downcast_ref::<RegionSelected>(),
+            Some(&RegionSelected(items.clone()))
+        );
+
+        app.handle_input(mouse(MouseAction::Down(MouseBtn::Left), 30, 1));
+        app.handle_input(mouse(MouseAction::Up(MouseBtn::Left), 20, 2));
+        let messages = app.dispatcher.drain();
+        assert_eq!(
+            messages[0].downcast_ref::<RegionSelected>(),
+            Some(&RegionSelected(Vec::new()))
+        );
+    }
+
+    #[test]
+    fn escape_cancels_rubber_band() {
+        let (mut app, canvas, _) = canvas_app();
+        app.handle_input(mouse(MouseAction::Down(MouseBtn::Left), 30, 5));
+        app.handle_input(key(Key::Escape));
+        assert!(app.rubber_band().is_none());
+        app.handle_input(mouse(MouseAction::Up(MouseBtn::Left), 0, 0));
+        assert!(app.dispatcher.drain().is_empty());
+
+        app.screen.set_rubber_band(canvas, false);
+        app.handle_input(mouse(MouseAction::Down(MouseBtn::Left), 30, 5));
+        assert!(app.rubber_band().is_none());
+    }
+
+    // ── modals ───────────────────────────────────────────────────────
+
+    #[test]
+    fn modal_returns_result_and_blocks_input_below() {
+        let (mut app, target) = menu_app();
+        let root = app.screen.dom.root().unwrap();
+        let dialog = app.screen.dom.insert_child(root, NodeData::new("Dialog"));
+        let results = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let sink = results.clone();
+        app.push_modal(dialog, move |_, confirmed: Option<bool>| {
+            sink.borrow_mut().push(confirmed);
+        });
+        assert_eq!(app.screen.modal(), Some(dialog));
+        // The context menu below the modal no longer opens.
+        app.handle_input(mouse(MouseAction::Down(MouseBtn::Right), 12, 6));
+        assert!(app.context_menu().is_none());
+        assert!(!app.screen.accepts_input(target));
+
+        assert!(app.close_modal(Some(true)));
+        assert!(!app.screen.dom.contains(dialog));
+        assert!(!app.close_modal(Some(true)));
+
+        let dialog = app.screen.dom.insert_child(root, NodeData::new("Dialog"));
+        let sink = results.clone();
+        app.push_modal(dialog, move |_, confirmed: Option<bool>| {
+            sink.borrow_mut().push(confirmed);
+        });
+        app.handle_input(key(Key::Escape));
+        assert_eq!(app.screen.modal(), None);
+        assert_eq!(*results.borrow(), [Some(true), None]);
+    }
+
+    #[test]
+    fn modal_backdrop_dims_outside_the_dialog() {
+        let (mut app, _, items) = canvas_app();
+        app.push_modal(items[1], |_, _: Option<()>| {});
+        app.draw_modal_backdrop();
+        let dimmed = |app: &App, x, y| {
+            let cell = app.screen.compositor.get_cell(x, y).unwrap();
+            cell.style.attrs.contains(crate::render::strip::Attrs::DIM)
+        };
+        assert!(dimmed(&app, 1, 1));
+        assert!(!dimmed(&app, 1, 4));
     }
 
     // ── AppConfig builder ────────────────────────────────────────────
@@ -403,6 +3698,15 @@ mod tests {
         assert!(config.title.is_none());
         assert!(config.css.is_none());
         assert_eq!(config.fps, 60);
+        assert!(!config.perf_hud);
+        assert!(!config.alt_screen);
+        assert!(!config.kitty_keyboard);
+        assert!(!config.mouse_capture);
+        assert_eq!(config.density, Density::Normal);
+        assert_eq!(config.frame_budget, FrameBudget::default());
+        assert_eq!(config.flush_policy, FlushPolicy::Auto);
+        assert!(config.skip_idle_flush);
+        assert!(!config.reduced_motion);
     }
 
     #[test]
@@ -410,9 +3714,19 @@ mod tests {
         let config = AppConfig::new()
             .with_title("My App")
             .with_css("Button { color: red; }")
-            .with_fps(30);
+            .with_fps(30)
+            .with_alt_screen(true)
+            .with_flush_policy(FlushPolicy::Threshold(4096))
+            .with_skip_idle_flush(false)
+            .with_reduced_motion(true)
+            .with_unit_scale(2.0);
         assert_eq!(config.title, Some("My App".into()));
         assert_eq!(config.css, Some("Button { color: red; }".into()));
         assert_eq!(config.fps, 30);
+        assert!(config.alt_screen);
+        assert_eq!(config.flush_policy, FlushPolicy::Threshold(4096));
+        assert!(!config.skip_idle_flush);
+        assert!(config.reduced_motion);
+        assert_eq!(config.unit_scale, 2.0);
     }
 }
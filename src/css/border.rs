@@ -0,0 +1,136 @@
+//! Border glyph sets: the characters a border is drawn with.
+//!
+//! Every [`BorderKind`](crate::css::styles::BorderKind) maps to a
+//! [`BorderGlyphs`]. Beyond the built-in kinds, a stylesheet can spell out its
+//! own six glyphs, or refer to a set registered by name:
+//!
+//! ```ignore
+//! register_border_set("house", BorderGlyphs::parse("┍┑┕┙━│")?);
+//!
+//! Panel  { border: custom "╭╮╰╯─│"; }
+//! Dialog { border: custom house red; }
+//! Card   { border: house; }
+//! ```
+//!
+//! Named sets are resolved when the stylesheet is parsed, so register them
+//! before adding the stylesheets that use them.
+
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+// ---------------------------------------------------------------------------
+// BorderGlyphs
+// ---------------------------------------------------------------------------
+
+/// The six characters of a box border.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BorderGlyphs {
+    pub top_left: char,
+    pub top_right: char,
+    pub bottom_left: char,
+    pub bottom_right: char,
+    pub horizontal: char,
+    pub vertical: char,
+}
+
+/// Errors from parsing a glyph string.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("expected 6 border glyphs (corners ┌┐└┘ then ─ │), got {0}")]
+pub struct BorderGlyphsError(pub usize);
+
+impl BorderGlyphs {
+    /// `┌┐└┘─│`
+    pub const THIN: Self = Self::from_array(['┌', '┐', '└', '┘', '─', '│']);
+    /// `┏┓┗┛━┃`
+    pub const HEAVY: Self = Self::from_array(['┏', '┓', '┗', '┛', '━', '┃']);
+    /// `╔╗╚╝═║`
+    pub const DOUBLE: Self = Self::from_array(['╔', '╗', '╚', '╝', '═', '║']);
+    /// `╭╮╰╯─│`
+    pub const ROUND: Self = Self::from_array(['╭', '╮', '╰', '╯', '─', '│']);
+    /// `++++-|`
+    pub const ASCII: Self = Self::from_array(['+', '+', '+', '+', '-', '|']);
+
+    /// Build from `[top_left, top_right, bottom_left, bottom_right,
+    /// horizontal, vertical]`.
+    pub const fn from_array(glyphs: [char; 6]) -> Self {
+        let [top_left, top_right, bottom_left, bottom_right, horizontal, vertical] = glyphs;
+        Self {
+            top_left,
+            top_right,
+            bottom_left,
+            bottom_right,
+            horizontal,
+            vertical,
+        }
+    }
+
+    /// Parse a six-character string in the same order as
+    /// [`from_array`](Self::from_array), e.g. `"╭╮╰╯─│"`.
+    pub fn parse(s: &str) -> Result<Self, BorderGlyphsError> {
+        let chars: Vec<char> = s.chars().collect();
+        let glyphs: [char; 6] = chars
+            .as_slice()
+            .try_into()
+            .map_err(|_| BorderGlyphsError(chars.len()))?;
+        Ok(Self::from_array(glyphs))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Registry
+// ---------------------------------------------------------------------------
+
+static BORDER_SETS: RwLock<BTreeMap<String, BorderGlyphs>> = RwLock::new(BTreeMap::new());
+
+/// Register a named border set for use as `border: <name>` or
+/// `border: custom <name>`. Returns the set previously registered under the
+/// name. Built-in kind names (`thin`, `round`, ...) always take precedence.
+pub fn register_border_set(name: impl Into<String>, glyphs: BorderGlyphs) -> Option<BorderGlyphs> {
+    BORDER_SETS
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(name.into(), glyphs)
+}
+
+/// Look up a registered border set.
+pub fn border_set(name: &str) -> Option<BorderGlyphs> {
+    BORDER_SETS
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(name)
+        .copied()
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_six_glyphs() {
+        assert_eq!(BorderGlyphs::parse("╭╮╰╯─│"), Ok(BorderGlyphs::ROUND));
+        assert_eq!(BorderGlyphs::parse("++++-|"), Ok(BorderGlyphs::ASCII));
+    }
+
+    #[test]
+    fn parse_rejects_wrong_length() {
+        assert_eq!(BorderGlyphs::parse("┌┐└┘─"), Err(BorderGlyphsError(5)));
+        assert_eq!(BorderGlyphs::parse(""), Err(BorderGlyphsError(0)));
+    }
+
+    #[test]
+    fn registry_round_trip() {
+        let glyphs = BorderGlyphs::parse("┍┑┕┙━│").unwrap();
+        assert_eq!(register_border_set("border-test-house", glyphs), None);
+        assert_eq!(border_set("border-test-house"), Some(glyphs));
+        assert_eq!(
+            register_border_set("border-test-house", BorderGlyphs::THIN),
+            Some(glyphs)
+        );
+        assert_eq!(border_set("border-test-missing"), None);
+    }
+}
@@ -0,0 +1,120 @@
+//! UI density modes: compact, normal, and comfortable.
+//!
+//! The screen tags its root node with the class of the active [`Density`]
+//! (`density-compact`, `density-normal`, or `density-comfortable`), and the
+//! built-in [`DENSITY_CSS`] rules key heights and padding off that class. App
+//! stylesheets can do the same, e.g. `.density-compact Sidebar { width: 20; }`,
+//! so one app fits both a cramped SSH session and a large terminal.
+
+use std::fmt;
+
+// ---------------------------------------------------------------------------
+// Density
+// ---------------------------------------------------------------------------
+
+/// How much room widgets take.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum Density {
+    /// Single-row controls and no padding.
+    Compact,
+    /// The widgets' own sizes.
+    #[default]
+    Normal,
+    /// Extra padding around controls and containers.
+    Comfortable,
+}
+
+impl Density {
+    /// Every density, from tightest to roomiest.
+    pub const ALL: [Density; 3] = [Density::Compact, Density::Normal, Density::Comfortable];
+
+    /// The class put on the root node while this density is active.
+    pub fn class(self) -> &'static str {
+        match self {
+            Density::Compact => "density-compact",
+            Density::Normal => "density-normal",
+            Density::Comfortable => "density-comfortable",
+        }
+    }
+
+    /// The next density, wrapping from comfortable back to compact.
+    pub fn next(self) -> Self {
+        match self {
+            Density::Compact => Density::Normal,
+            Density::Normal => Density::Comfortable,
+            Density::Comfortable => Density::Compact,
+        }
+    }
+}
+
+impl fmt::Display for Density {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Density::Compact => "compact",
+            Density::Normal => "normal",
+            Density::Comfortable => "comfortable",
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Default rules
+// ---------------------------------------------------------------------------
+
+/// Built-in rules for the density classes, applied at default (lowest)
+/// priority so app stylesheets always win.
+pub const DENSITY_CSS: &str = "
+.density-compact Button { height: 1; min-width: 6; }
+.density-compact Container, .density-compact ScrollableContainer { padding: 0; }
+.density-compact Panel { padding: 0; }
+.density-comfortable Button { height: 3; padding: 0 2; }
+.density-comfortable Input { height: 3; padding: 1 1; }
+.density-comfortable Container, .density-comfortable ScrollableContainer { padding: 1 2; }
+.density-comfortable Panel { padding: 1 2; }
+.density-comfortable ListView, .density-comfortable DataTable { padding: 0 1; }
+.density-comfortable Header, .density-comfortable Footer, .density-comfortable StatusBar { height: 3; padding: 1 1; }
+";
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::css::parser::parse_css;
+
+    #[test]
+    fn cycles_through_all() {
+        let mut density = Density::default();
+        assert_eq!(density, Density::Normal);
+        let seen: Vec<_> = (0..3)
+            .map(|_| {
+                density = density.next();
+                density
+            })
+            .collect();
+        assert_eq!(
+            seen,
+            [Density::Comfortable, Density::Compact, Density::Normal]
+        );
+    }
+
+    #[test]
+    fn classes_and_names() {
+        let classes: Vec<_> = Density::ALL.iter().map(|d| d.class()).collect();
+        assert_eq!(
+            classes,
+            ["density-compact", "density-normal", "density-comfortable"]
+        );
+        assert_eq!(Density::Comfortable.to_string(), "comfortable");
+    }
+
+    #[test]
+    fn default_rules_parse() {
+        let sheet = parse_css(DENSITY_CSS).unwrap();
+        assert!(!sheet.rules.is_empty());
+    }
+}
@@ -1,14 +1,20 @@
-//! CSS engine: tokenizer, parser, styles, specificity, cascade.
+//! CSS engine: tokenizer, parser, styles, specificity, cascade, density modes,
+//! built-in overlay styles.
 
 pub mod scalar;
 pub mod tokenizer;
 pub mod model;
 pub mod parser;
+pub mod border;
 pub mod styles;
 pub mod properties;
 pub mod specificity;
 pub mod stylesheet;
+pub mod query;
+pub mod density;
+pub mod overlay;
 
+pub use border::{border_set, register_border_set, BorderGlyphs, BorderGlyphsError};
 pub use scalar::{Scalar, ScalarBox, Unit};
 pub use tokenizer::Token;
 pub use model::{
@@ -16,3 +22,6 @@ pub use model::{
     SelectorComponent, SelectorPart, StyleSheet,
 };
 pub use specificity::Specificity;
+pub use query::{NodeRef, Query};
+pub use density::{Density, DENSITY_CSS};
+pub use overlay::OVERLAY_CSS;
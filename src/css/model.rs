@@ -2,6 +2,7 @@
 
 /// A single CSS selector component.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SelectorComponent {
     /// Type selector: matches widget type name (e.g. `Button`).
     Type(String),
@@ -17,6 +18,7 @@ pub enum SelectorComponent {
 
 /// A combinator between selector components.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Combinator {
     /// Descendant combinator (whitespace): `A B`.
     Descendant,
@@ -29,6 +31,7 @@ pub enum Combinator {
 /// For example, `Button.primary:hover` is one `CompoundSelector` with three
 /// components: `Type("Button")`, `Class("primary")`, `PseudoClass("hover")`.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CompoundSelector {
     pub components: Vec<SelectorComponent>,
 }
@@ -61,6 +64,7 @@ impl Default for CompoundSelector {
 
 /// One element in a selector chain: either a compound selector or a combinator.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SelectorPart {
     /// A compound selector (one or more simple selectors).
     Compound(CompoundSelector),
@@ -73,6 +77,7 @@ pub enum SelectorPart {
 /// For example, `Container > Button.primary:hover` is a `Selector` with parts:
 /// `[Compound(Container), Combinator(Child), Compound(Button.primary:hover)]`.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Selector {
     /// Alternating compound selectors and combinators.
     /// Always starts and ends with a `SelectorPart::Compound`.
@@ -94,6 +99,7 @@ impl Default for Selector {
 
 /// A value token within a CSS declaration.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DeclarationValue {
     /// An identifier like `red`, `bold`, `center`.
     Ident(String),
@@ -107,10 +113,14 @@ pub enum DeclarationValue {
     String(String),
     /// A variable reference (without the `$` prefix), e.g. `"primary"`.
     Variable(String),
+    /// A function call like `linear-gradient(to right, red, blue)`: the name
+    /// and its comma-separated arguments, each a list of values.
+    Function(String, Vec<Vec<DeclarationValue>>),
 }
 
 /// A single CSS property declaration, e.g. `color: red` or `margin: 1 2`.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Declaration {
     /// The property name, e.g. `"color"`, `"margin"`.
     pub property: String,
@@ -133,6 +143,7 @@ impl Declaration {
 
 /// A CSS rule: one or more selectors paired with declarations.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RuleSet {
     /// The selectors for this rule (comma-separated in CSS).
     pub selectors: Vec<Selector>,
@@ -142,6 +153,7 @@ pub struct RuleSet {
 
 /// A parsed CSS stylesheet: a list of rule sets.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StyleSheet {
     pub rules: Vec<RuleSet>,
 }
@@ -0,0 +1,43 @@
+//! Built-in styles for the overlays the app draws itself.
+//!
+//! The perf HUD, context menu, command palette, rubber band, and floating
+//! panels are not DOM nodes, so they get no styles from the cascade on their
+//! own. [`Screen::overlay_styles`] cascades
+//! a stand-in node of the overlay's type instead, starting from the defaults
+//! in [`OVERLAY_CSS`], so app stylesheets restyle overlays like any widget:
+//!
+//! ```ignore
+//! app.screen.add_css("ContextMenu { background: #1e1e2e; color: #cdd6f4; }")?;
+//! ```
+//!
+//! [`Screen::overlay_styles`]: crate::screen::Screen::overlay_styles
+
+// ---------------------------------------------------------------------------
+// Default rules
+// ---------------------------------------------------------------------------
+
+/// Built-in overlay colours, applied at default (lowest) priority so app
+/// stylesheets always win.
+pub const OVERLAY_CSS: &str = "
+PerfHud { background: #202020; color: #00ff87; }
+ContextMenu { background: #303030; color: #e0e0e0; }
+CommandPalette { background: #262626; color: #e0e0e0; }
+RubberBand { color: #5fafff; }
+Panel { background: #202020; color: #e0e0e0; }
+";
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::css::parser::parse_css;
+
+    #[test]
+    fn overlay_css_parses() {
+        let sheet = parse_css(OVERLAY_CSS).unwrap();
+        assert!(!sheet.rules.is_empty());
+    }
+}
@@ -102,6 +102,29 @@ pub fn parse_css(input: &str) -> Result<StyleSheet, ParseError> {
     Ok(StyleSheet { rules })
 }
 
+/// Parse a standalone selector list such as `Container > .item, #title`.
+///
+/// The whole input must be consumed; trailing tokens are an error.
+pub fn parse_selectors(input: &str) -> Result<Vec<Selector>, ParseError> {
+    let cleaned = strip_comments(input);
+    let tokens = tokenize_with_spans(&cleaned);
+
+    let mut parser = Parser { tokens, cursor: 0 };
+    if parser.is_eof() {
+        return Err(ParseError::UnexpectedEof("expected a selector".into()));
+    }
+
+    let selectors = parser.parse_selector_list()?;
+    if let Some(t) = parser.peek() {
+        return Err(ParseError::UnexpectedToken {
+            position: t.pos,
+            message: format!("unexpected '{}' after selector", t.text),
+        });
+    }
+
+    Ok(selectors)
+}
+
 /// Recursive descent parser state.
 struct Parser {
     tokens: Vec<PToken>,
@@ -460,7 +483,16 @@ impl Parser {
                         })?;
                 Ok(DeclarationValue::Dimension(n, unit_str.to_string()))
             }
-            Token::Ident => Ok(DeclarationValue::Ident(tok.text.clone())),
+            Token::Ident => {
+                let name = tok.text.clone();
+                if self.peek().is_some_and(|t| t.token == Token::ParenOpen) {
+                    self.advance();
+                    let args = self.parse_function_args()?;
+                    Ok(DeclarationValue::Function(name, args))
+                } else {
+                    Ok(DeclarationValue::Ident(name))
+                }
+            }
             Token::HexColor => {
                 // Strip the leading '#' for DeclarationValue::Color
                 let hex = tok.text.strip_prefix('#').unwrap_or(&tok.text);
@@ -485,6 +517,35 @@ impl Parser {
             }),
         }
     }
+
+    /// Parse function arguments after the `(`: comma-separated groups of
+    /// values up to the matching `)`.
+    fn parse_function_args(&mut self) -> Result<Vec<Vec<DeclarationValue>>, ParseError> {
+        let mut args: Vec<Vec<DeclarationValue>> = vec![Vec::new()];
+        loop {
+            match self.peek().map(|t| &t.token) {
+                None => return Err(ParseError::UnexpectedEof("expected ')'".into())),
+                Some(Token::ParenClose) => {
+                    self.advance();
+                    break;
+                }
+                Some(Token::Comma) => {
+                    self.advance();
+                    args.push(Vec::new());
+                }
+                Some(_) => {
+                    let value = self.parse_declaration_value()?;
+                    if let Some(arg) = args.last_mut() {
+                        arg.push(value);
+                    }
+                }
+            }
+        }
+        if args.len() == 1 && args[0].is_empty() {
+            args.clear();
+        }
+        Ok(args)
+    }
 }
 
 /// Split a dimension string like "50%" or "1fr" into (number_part, unit_part).
@@ -646,6 +707,32 @@ mod tests {
         );
     }
 
+    // ── Function values ──────────────────────────────────────────────
+
+    #[test]
+    fn parse_function_value() {
+        let rule = first_rule("Header { background: linear-gradient(to right, red, #00f); }");
+        assert_eq!(
+            rule.declarations[0].values,
+            vec![DeclarationValue::Function(
+                "linear-gradient".into(),
+                vec![
+                    vec![
+                        DeclarationValue::Ident("to".into()),
+                        DeclarationValue::Ident("right".into()),
+                    ],
+                    vec![DeclarationValue::Ident("red".into())],
+                    vec![DeclarationValue::Color("00f".into())],
+                ],
+            )]
+        );
+    }
+
+    #[test]
+    fn parse_function_value_unclosed() {
+        assert!(parse_css("Header { background: stripes(red, blue").is_err());
+    }
+
     // ── !important ───────────────────────────────────────────────────
 
     #[test]
@@ -904,4 +991,29 @@ mod tests {
     fn split_dimension_float() {
         assert_eq!(split_dimension("1.5fr"), Some(("1.5", "fr")));
     }
+
+    // ── parse_selectors ──────────────────────────────────────────────
+
+    #[test]
+    fn parse_selectors_single() {
+        let sels = parse_selectors("Container > .item:hover").unwrap();
+        assert_eq!(sels.len(), 1);
+        assert_eq!(sels[0].parts.len(), 3);
+    }
+
+    #[test]
+    fn parse_selectors_list() {
+        let sels = parse_selectors("Button, #title").unwrap();
+        assert_eq!(sels.len(), 2);
+    }
+
+    #[test]
+    fn parse_selectors_rejects_trailing_tokens() {
+        assert!(parse_selectors("Button { color: red; }").is_err());
+    }
+
+    #[test]
+    fn parse_selectors_rejects_empty() {
+        assert!(parse_selectors("   ").is_err());
+    }
 }
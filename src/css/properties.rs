@@ -3,6 +3,7 @@
 //! Parses string/token-based CSS declaration values into the typed fields
 //! on [`crate::css::styles::Styles`].
 
+use crate::css::border::{border_set, BorderGlyphs};
 use crate::css::model::DeclarationValue;
 use crate::css::scalar::{Scalar, ScalarBox};
 use crate::css::styles::*;
@@ -25,6 +26,7 @@ pub fn parse_scalar(value: &DeclarationValue) -> Result<Scalar, PropertyError> {
             "%" => Ok(Scalar::percent(*n)),
             "vw" => Ok(Scalar::vw(*n)),
             "vh" => Ok(Scalar::vh(*n)),
+            "u" => Ok(Scalar::units(*n)),
             other => Err(PropertyError::InvalidValue {
                 property: "scalar".into(),
                 message: format!("unknown unit: {other}"),
@@ -77,6 +79,19 @@ pub fn parse_scalar_box(values: &[DeclarationValue]) -> Result<ScalarBox, Proper
     }
 }
 
+/// Parse a whole, positive number of grid tracks (`grid-size`, `column-span`).
+fn parse_count(value: &DeclarationValue, property: &str) -> Result<u16, PropertyError> {
+    match value {
+        DeclarationValue::Number(n) if *n >= 1.0 && n.fract() == 0.0 && *n <= u16::MAX as f32 => {
+            Ok(*n as u16)
+        }
+        other => Err(PropertyError::InvalidValue {
+            property: property.into(),
+            message: format!("expected a positive whole number, got: {other:?}"),
+        }),
+    }
+}
+
 /// Extract a single identifier from values, returning an error using the given property name.
 fn require_single_ident<'a>(
     values: &'a [DeclarationValue],
@@ -118,6 +133,49 @@ fn require_color_value(
     }
 }
 
+/// Background fill functions accepted in place of a color.
+const BACKGROUND_FILLS: &[&str] = &["linear-gradient", "stripes", "dither"];
+
+/// Parse a background fill function into its canonical string form, e.g.
+/// `linear-gradient(to right, red, #0000ff)`.
+///
+/// Only the shape is checked here; the colors and options are interpreted by
+/// the renderer, like plain color strings.
+fn parse_background_fill(
+    name: &str,
+    args: &[Vec<DeclarationValue>],
+) -> Result<String, PropertyError> {
+    let invalid = |message: String| PropertyError::InvalidValue {
+        property: "background".into(),
+        message,
+    };
+    if !BACKGROUND_FILLS.contains(&name) {
+        return Err(invalid(format!(
+            "expected a color or one of {}, got: {name}()",
+            BACKGROUND_FILLS.join("|")
+        )));
+    }
+    let args = args
+        .iter()
+        .map(|arg| {
+            arg.iter()
+                .map(|value| match value {
+                    DeclarationValue::Ident(ident) => Ok(ident.clone()),
+                    DeclarationValue::Color(hex) => Ok(format!("#{hex}")),
+                    DeclarationValue::Number(n) => Ok(n.to_string()),
+                    DeclarationValue::Dimension(n, unit) => Ok(format!("{n}{unit}")),
+                    other => Err(invalid(format!("unexpected {name}() argument: {other:?}"))),
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(|parts| parts.join(" "))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if args.iter().any(String::is_empty) {
+        return Err(invalid(format!("empty argument in {name}()")));
+    }
+    Ok(format!("{name}({})", args.join(", ")))
+}
+
 /// Parse an overflow ident.
 fn parse_overflow(name: &str, property: &str) -> Result<Overflow, PropertyError> {
     match name {
@@ -131,42 +189,56 @@ fn parse_overflow(name: &str, property: &str) -> Result<Overflow, PropertyError>
     }
 }
 
-/// Parse border values: `<kind>` or `<kind> <color>`.
+/// Parse border values: `<kind> [<color>]`, where the kind is a built-in
+/// name, a registered border set, or `custom "<glyphs>"` / `custom <set>`.
 fn parse_border(values: &[DeclarationValue]) -> Result<Border, PropertyError> {
+    let invalid = |message: String| PropertyError::InvalidValue {
+        property: "border".into(),
+        message,
+    };
     if values.is_empty() {
-        return Err(PropertyError::InvalidValue {
-            property: "border".into(),
-            message: "expected at least 1 value for border".into(),
-        });
+        return Err(invalid("expected at least 1 value for border".into()));
     }
 
     let kind_str = match &values[0] {
         DeclarationValue::Ident(name) => name.as_str(),
         other => {
-            return Err(PropertyError::InvalidValue {
-                property: "border".into(),
-                message: format!("expected border kind identifier, got: {other:?}"),
-            });
+            return Err(invalid(format!(
+                "expected border kind identifier, got: {other:?}"
+            )));
         }
     };
 
-    let kind = match kind_str {
-        "none" => BorderKind::None,
-        "thin" => BorderKind::Thin,
-        "heavy" => BorderKind::Heavy,
-        "double" => BorderKind::Double,
-        "round" => BorderKind::Round,
-        "ascii" => BorderKind::Ascii,
-        other => {
-            return Err(PropertyError::InvalidValue {
-                property: "border".into(),
-                message: format!("unknown border kind: {other}"),
-            });
+    let (kind, values) = match kind_str {
+        "none" => (BorderKind::None, &values[1..]),
+        "thin" => (BorderKind::Thin, &values[1..]),
+        "heavy" => (BorderKind::Heavy, &values[1..]),
+        "double" => (BorderKind::Double, &values[1..]),
+        "round" => (BorderKind::Round, &values[1..]),
+        "ascii" => (BorderKind::Ascii, &values[1..]),
+        "custom" => {
+            let glyphs = match values.get(1) {
+                Some(DeclarationValue::String(glyphs)) => {
+                    BorderGlyphs::parse(glyphs).map_err(|e| invalid(e.to_string()))?
+                }
+                Some(DeclarationValue::Ident(name)) => border_set(name)
+                    .ok_or_else(|| invalid(format!("unknown border set: {name}")))?,
+                _ => {
+                    return Err(invalid(
+                        "expected glyph string or border set name after custom".into(),
+                    ));
+                }
+            };
+            (BorderKind::Custom(glyphs), &values[2..])
         }
+        other => match border_set(other) {
+            Some(glyphs) => (BorderKind::Custom(glyphs), &values[1..]),
+            None => return Err(invalid(format!("unknown border kind: {other}"))),
+        },
     };
 
-    let color = if values.len() > 1 {
-        match &values[1] {
+    let color = if let Some(value) = values.first() {
+        match value {
             DeclarationValue::Ident(name) => Some(name.clone()),
             DeclarationValue::Color(hex) => Some(format!("#{hex}")),
             other => {
@@ -183,7 +255,8 @@ fn parse_border(values: &[DeclarationValue]) -> Result<Border, PropertyError> {
     Ok(Border { kind, color })
 }
 
-/// Parse text-style values: one or more of bold, dim, italic, underline, strikethrough, reverse.
+/// Parse text-style values: one or more of bold, dim, italic, underline, strikethrough, reverse,
+/// blink, pulse.
 fn parse_text_style(values: &[DeclarationValue]) -> Result<TextStyleFlags, PropertyError> {
     let mut flags = TextStyleFlags::default();
 
@@ -204,6 +277,8 @@ fn parse_text_style(values: &[DeclarationValue]) -> Result<TextStyleFlags, Prope
             "underline" => flags.underline = Some(true),
             "strikethrough" => flags.strikethrough = Some(true),
             "reverse" => flags.reverse = Some(true),
+            "blink" => flags.blink = Some(true),
+            "pulse" => flags.pulse = Some(true),
             "none" => {
                 // Reset all flags
                 flags.bold = Some(false);
@@ -212,6 +287,8 @@ fn parse_text_style(values: &[DeclarationValue]) -> Result<TextStyleFlags, Prope
                 flags.underline = Some(false);
                 flags.strikethrough = Some(false);
                 flags.reverse = Some(false);
+                flags.blink = Some(false);
+                flags.pulse = Some(false);
             }
             other => {
                 return Err(PropertyError::InvalidValue {
@@ -297,6 +374,64 @@ pub fn apply_declaration(
             styles.overflow_x = Some(overflow);
             styles.overflow_y = Some(overflow);
         }
+        "scrollbar-gutter" => {
+            let name = require_single_ident(values, "scrollbar-gutter")?;
+            styles.scrollbar_gutter = Some(match name {
+                "auto" => ScrollbarGutter::Auto,
+                "stable" => ScrollbarGutter::Stable,
+                other => {
+                    return Err(PropertyError::InvalidValue {
+                        property: "scrollbar-gutter".into(),
+                        message: format!("expected auto|stable, got: {other}"),
+                    });
+                }
+            });
+        }
+        "overscroll-behavior" => {
+            let name = require_single_ident(values, "overscroll-behavior")?;
+            styles.overscroll_behavior = Some(match name {
+                "clamp" => OverscrollBehavior::Clamp,
+                "none" => OverscrollBehavior::None,
+                other => {
+                    return Err(PropertyError::InvalidValue {
+                        property: "overscroll-behavior".into(),
+                        message: format!("expected clamp|none, got: {other}"),
+                    });
+                }
+            });
+        }
+        "scroll-indicator" => {
+            let name = require_single_ident(values, "scroll-indicator")?;
+            styles.scroll_indicator = Some(match name {
+                "none" => ScrollIndicator::None,
+                "shadow" => ScrollIndicator::Shadow,
+                "marker" => ScrollIndicator::Marker,
+                other => {
+                    return Err(PropertyError::InvalidValue {
+                        property: "scroll-indicator".into(),
+                        message: format!("expected none|shadow|marker, got: {other}"),
+                    });
+                }
+            });
+        }
+        "scrollbar-color" => {
+            styles.scrollbar_color = Some(require_color_value(values, "scrollbar-color")?);
+        }
+        "scrollbar-size" => {
+            styles.scrollbar_size = Some(match values {
+                [DeclarationValue::Number(n)]
+                    if *n >= 0.0 && n.fract() == 0.0 && *n <= u16::MAX as f32 =>
+                {
+                    *n as u16
+                }
+                _ => {
+                    return Err(PropertyError::InvalidValue {
+                        property: "scrollbar-size".into(),
+                        message: format!("expected a whole number of cells, got: {values:?}"),
+                    });
+                }
+            });
+        }
         "overflow-x" => {
             let name = require_single_ident(values, "overflow-x")?;
             styles.overflow_x = Some(parse_overflow(name, "overflow-x")?);
@@ -369,13 +504,91 @@ pub fn apply_declaration(
         "padding" => {
             styles.padding = Some(parse_scalar_box(values)?);
         }
+        "gap" => {
+            let (row, column) = match values {
+                [both] => (parse_scalar(both)?, parse_scalar(both)?),
+                [row, column] => (parse_scalar(row)?, parse_scalar(column)?),
+                _ => {
+                    return Err(PropertyError::InvalidValue {
+                        property: "gap".into(),
+                        message: format!("expected 1 or 2 values, got {}", values.len()),
+                    });
+                }
+            };
+            styles.row_gap = Some(row);
+            styles.column_gap = Some(column);
+        }
+        "row-gap" | "column-gap" => {
+            if values.len() != 1 {
+                return Err(PropertyError::InvalidValue {
+                    property: property.into(),
+                    message: format!("expected 1 value, got {}", values.len()),
+                });
+            }
+            let gap = Some(parse_scalar(&values[0])?);
+            if property == "row-gap" {
+                styles.row_gap = gap;
+            } else {
+                styles.column_gap = gap;
+            }
+        }
+
+        // Grid
+        "grid-size" => {
+            let (columns, rows) = match values {
+                [columns] => (parse_count(columns, property)?, None),
+                [columns, rows] => (
+                    parse_count(columns, property)?,
+                    Some(parse_count(rows, property)?),
+                ),
+                _ => {
+                    return Err(PropertyError::InvalidValue {
+                        property: property.into(),
+                        message: format!("expected 1 or 2 values, got {}", values.len()),
+                    });
+                }
+            };
+            styles.grid_size_columns = Some(columns);
+            styles.grid_size_rows = rows;
+        }
+        "grid-columns" | "grid-rows" => {
+            if values.is_empty() {
+                return Err(PropertyError::InvalidValue {
+                    property: property.into(),
+                    message: "expected at least 1 value".into(),
+                });
+            }
+            let tracks = values.iter().map(parse_scalar).collect::<Result<_, _>>()?;
+            if property == "grid-columns" {
+                styles.grid_columns = Some(tracks);
+            } else {
+                styles.grid_rows = Some(tracks);
+            }
+        }
+        "column-span" | "row-span" => {
+            if values.len() != 1 {
+                return Err(PropertyError::InvalidValue {
+                    property: property.into(),
+                    message: format!("expected 1 value, got {}", values.len()),
+                });
+            }
+            let span = Some(parse_count(&values[0], property)?);
+            if property == "column-span" {
+                styles.column_span = span;
+            } else {
+                styles.row_span = span;
+            }
+        }
 
         // Colors
         "color" => {
             styles.color = Some(require_color_value(values, "color")?);
         }
         "background" => {
-            styles.background = Some(require_color_value(values, "background")?);
+            styles.background = Some(match values {
+                [DeclarationValue::Function(name, args)] => parse_background_fill(name, args)?,
+                _ => require_color_value(values, "background")?,
+            });
         }
 
         // Text
@@ -393,6 +606,33 @@ pub fn apply_declaration(
                 }
             });
         }
+        "text-overflow" => {
+            let name = require_single_ident(values, "text-overflow")?;
+            styles.text_overflow = Some(match name {
+                "clip" => TextOverflow::Clip,
+                "ellipsis" => TextOverflow::Ellipsis,
+                "shrink" => TextOverflow::Shrink,
+                other => {
+                    return Err(PropertyError::InvalidValue {
+                        property: "text-overflow".into(),
+                        message: format!("expected clip|ellipsis|shrink, got: {other}"),
+                    });
+                }
+            });
+        }
+        "text-wrap" => {
+            let name = require_single_ident(values, "text-wrap")?;
+            styles.text_wrap = Some(match name {
+                "nowrap" => TextWrap::Nowrap,
+                "wrap" => TextWrap::Wrap,
+                other => {
+                    return Err(PropertyError::InvalidValue {
+                        property: "text-wrap".into(),
+                        message: format!("expected nowrap|wrap, got: {other}"),
+                    });
+                }
+            });
+        }
         "text-style" => {
             styles.text_style = Some(parse_text_style(values)?);
         }
@@ -447,6 +687,12 @@ mod tests {
         assert_eq!(s, Scalar::vw(100.0));
     }
 
+    #[test]
+    fn parse_scalar_units() {
+        let v = DeclarationValue::Dimension(2.0, "u".into());
+        assert_eq!(parse_scalar(&v).unwrap(), Scalar::units(2.0));
+    }
+
     #[test]
     fn parse_scalar_vh() {
         let v = DeclarationValue::Dimension(80.0, "vh".into());
@@ -674,6 +920,47 @@ mod tests {
         assert!(s.overflow_x.is_none());
     }
 
+    // ── apply_declaration: scrolling ─────────────────────────────────
+
+    #[test]
+    fn apply_scroll_properties() {
+        let mut s = Styles::new();
+        let ident = |name: &str| [DeclarationValue::Ident(name.into())];
+        apply_declaration(&mut s, "scrollbar-gutter", &ident("stable")).unwrap();
+        apply_declaration(&mut s, "overscroll-behavior", &ident("none")).unwrap();
+        apply_declaration(&mut s, "scroll-indicator", &ident("marker")).unwrap();
+        assert_eq!(s.scrollbar_gutter, Some(ScrollbarGutter::Stable));
+        assert_eq!(s.overscroll_behavior, Some(OverscrollBehavior::None));
+        assert_eq!(s.scroll_indicator, Some(ScrollIndicator::Marker));
+    }
+
+    #[test]
+    fn apply_scrollbar_color_and_size() {
+        let mut s = Styles::new();
+        let color = [DeclarationValue::Color("ff8800".into())];
+        apply_declaration(&mut s, "scrollbar-color", &color).unwrap();
+        apply_declaration(&mut s, "scrollbar-size", &[DeclarationValue::Number(2.0)]).unwrap();
+        assert_eq!(s.scrollbar_color.as_deref(), Some("#ff8800"));
+        assert_eq!(s.scrollbar_size, Some(2));
+        apply_declaration(&mut s, "scrollbar-size", &[DeclarationValue::Number(0.0)]).unwrap();
+        assert_eq!(s.scrollbar_size, Some(0));
+        for bad in [-1.0, 1.5] {
+            let size = [DeclarationValue::Number(bad)];
+            assert!(apply_declaration(&mut s, "scrollbar-size", &size).is_err());
+        }
+    }
+
+    #[test]
+    fn apply_scroll_indicator_invalid() {
+        let mut s = Styles::new();
+        let result = apply_declaration(
+            &mut s,
+            "scroll-indicator",
+            &[DeclarationValue::Ident("arrows".into())],
+        );
+        assert!(result.is_err());
+    }
+
     // ── apply_declaration: sizing ────────────────────────────────────
 
     #[test]
@@ -750,6 +1037,72 @@ mod tests {
         assert_eq!(s.padding, Some(ScalarBox::all(Scalar::cells(3.0))));
     }
 
+    #[test]
+    fn apply_gap_shorthand_and_longhands() {
+        let mut s = Styles::new();
+        apply_declaration(&mut s, "gap", &[DeclarationValue::Number(1.0)]).unwrap();
+        assert_eq!(s.row_gap, Some(Scalar::cells(1.0)));
+        assert_eq!(s.column_gap, Some(Scalar::cells(1.0)));
+
+        apply_declaration(
+            &mut s,
+            "gap",
+            &[DeclarationValue::Number(1.0), DeclarationValue::Number(2.0)],
+        )
+        .unwrap();
+        assert_eq!(s.column_gap, Some(Scalar::cells(2.0)));
+
+        apply_declaration(&mut s, "row-gap", &[DeclarationValue::Number(0.0)]).unwrap();
+        assert_eq!(s.row_gap, Some(Scalar::cells(0.0)));
+        assert_eq!(s.column_gap, Some(Scalar::cells(2.0)));
+        assert!(apply_declaration(&mut s, "gap", &[]).is_err());
+        assert!(apply_declaration(
+            &mut s,
+            "column-gap",
+            &[DeclarationValue::Number(1.0), DeclarationValue::Number(2.0)],
+        )
+        .is_err());
+    }
+
+    // ── apply_declaration: grid ──────────────────────────────────────
+
+    #[test]
+    fn apply_grid_size_and_tracks() {
+        let mut s = Styles::new();
+        apply_declaration(&mut s, "grid-size", &[DeclarationValue::Number(3.0)]).unwrap();
+        assert_eq!((s.grid_size_columns, s.grid_size_rows), (Some(3), None));
+        let two = [DeclarationValue::Number(2.0), DeclarationValue::Number(4.0)];
+        apply_declaration(&mut s, "grid-size", &two).unwrap();
+        assert_eq!((s.grid_size_columns, s.grid_size_rows), (Some(2), Some(4)));
+
+        let tracks = [
+            DeclarationValue::Number(10.0),
+            DeclarationValue::Dimension(1.0, "fr".into()),
+            DeclarationValue::Ident("auto".into()),
+        ];
+        apply_declaration(&mut s, "grid-columns", &tracks).unwrap();
+        assert_eq!(
+            s.grid_columns,
+            Some(vec![Scalar::cells(10.0), Scalar::fr(1.0), Scalar::auto()])
+        );
+        apply_declaration(&mut s, "grid-rows", &tracks[1..2]).unwrap();
+        assert_eq!(s.grid_rows, Some(vec![Scalar::fr(1.0)]));
+        assert!(apply_declaration(&mut s, "grid-rows", &[]).is_err());
+    }
+
+    #[test]
+    fn apply_spans_require_positive_whole_numbers() {
+        let mut s = Styles::new();
+        apply_declaration(&mut s, "column-span", &[DeclarationValue::Number(2.0)]).unwrap();
+        apply_declaration(&mut s, "row-span", &[DeclarationValue::Number(3.0)]).unwrap();
+        assert_eq!((s.column_span, s.row_span), (Some(2), Some(3)));
+        for bad in [0.0, 1.5, -1.0] {
+            let span = [DeclarationValue::Number(bad)];
+            assert!(apply_declaration(&mut s, "row-span", &span).is_err());
+        }
+        assert!(apply_declaration(&mut s, "grid-size", &[DeclarationValue::Number(0.0)]).is_err());
+    }
+
     // ── apply_declaration: colors ────────────────────────────────────
 
     #[test]
@@ -783,8 +1136,49 @@ mod tests {
         assert_eq!(s.background, Some("#fff".into()));
     }
 
+    #[test]
+    fn apply_background_fill_function() {
+        let mut s = Styles::new();
+        let args = vec![
+            vec![
+                DeclarationValue::Ident("to".into()),
+                DeclarationValue::Ident("right".into()),
+            ],
+            vec![DeclarationValue::Ident("red".into())],
+            vec![DeclarationValue::Color("0000ff".into())],
+        ];
+        apply_declaration(
+            &mut s,
+            "background",
+            &[DeclarationValue::Function("linear-gradient".into(), args)],
+        )
+        .unwrap();
+        assert_eq!(
+            s.background.as_deref(),
+            Some("linear-gradient(to right, red, #0000ff)")
+        );
+
+        let err = apply_declaration(
+            &mut s,
+            "background",
+            &[DeclarationValue::Function("radial-gradient".into(), vec![])],
+        );
+        assert!(err.is_err());
+    }
+
     // ── apply_declaration: text ──────────────────────────────────────
 
+    #[test]
+    fn apply_text_overflow_and_wrap() {
+        let mut s = Styles::new();
+        let ident = |name: &str| [DeclarationValue::Ident(name.into())];
+        apply_declaration(&mut s, "text-overflow", &ident("ellipsis")).unwrap();
+        apply_declaration(&mut s, "text-wrap", &ident("wrap")).unwrap();
+        assert_eq!(s.text_overflow, Some(TextOverflow::Ellipsis));
+        assert_eq!(s.text_wrap, Some(TextWrap::Wrap));
+        assert!(apply_declaration(&mut s, "text-overflow", &ident("fade")).is_err());
+    }
+
     #[test]
     fn apply_text_align() {
         let mut s = Styles::new();
@@ -829,6 +1223,24 @@ mod tests {
         assert_eq!(flags.bold, Some(false));
         assert_eq!(flags.italic, Some(false));
         assert_eq!(flags.underline, Some(false));
+        assert_eq!(flags.blink, Some(false));
+    }
+
+    #[test]
+    fn apply_text_style_effects() {
+        let mut s = Styles::new();
+        apply_declaration(
+            &mut s,
+            "text-style",
+            &[
+                DeclarationValue::Ident("blink".into()),
+                DeclarationValue::Ident("pulse".into()),
+            ],
+        )
+        .unwrap();
+        let flags = s.text_style.unwrap();
+        assert_eq!((flags.blink, flags.pulse), (Some(true), Some(true)));
+        assert!(flags.bold.is_none());
     }
 
     // ── apply_declaration: border ────────────────────────────────────
@@ -895,6 +1307,52 @@ mod tests {
         assert!(border.color.is_none());
     }
 
+    #[test]
+    fn apply_border_custom_glyphs_and_color() {
+        let mut s = Styles::new();
+        apply_declaration(
+            &mut s,
+            "border",
+            &[
+                DeclarationValue::Ident("custom".into()),
+                DeclarationValue::String("┍┑┕┙━│".into()),
+                DeclarationValue::Ident("red".into()),
+            ],
+        )
+        .unwrap();
+        let border = s.border.clone().unwrap();
+        assert_eq!(
+            border.kind.glyphs().map(|g| (g.top_left, g.horizontal)),
+            Some(('┍', '━'))
+        );
+        assert_eq!(border.color, Some("red".into()));
+
+        let short = [
+            DeclarationValue::Ident("custom".into()),
+            DeclarationValue::String("┌┐".into()),
+        ];
+        assert!(apply_declaration(&mut s, "border", &short).is_err());
+    }
+
+    #[test]
+    fn apply_border_registered_set() {
+        crate::css::border::register_border_set("properties-test-set", BorderGlyphs::ROUND);
+        let mut s = Styles::new();
+        apply_declaration(
+            &mut s,
+            "border",
+            &[DeclarationValue::Ident("properties-test-set".into())],
+        )
+        .unwrap();
+        assert_eq!(s.border.as_ref().unwrap().kind, BorderKind::Custom(BorderGlyphs::ROUND));
+
+        let unknown = [
+            DeclarationValue::Ident("custom".into()),
+            DeclarationValue::Ident("properties-test-missing".into()),
+        ];
+        assert!(apply_declaration(&mut s, "border", &unknown).is_err());
+    }
+
     // ── apply_declaration: unknown ───────────────────────────────────
 
     #[test]
@@ -0,0 +1,217 @@
+//! Compiled selector queries: parse once, match many times.
+//!
+//! A [`Query`] wraps a parsed selector list and is the single matcher used by
+//! both the stylesheet cascade and the DOM query APIs (`App::query`). Compiling
+//! a selector string up front avoids re-parsing it on every lookup.
+//! [`NodeRef`] names a node either directly or by selector.
+
+use std::fmt;
+
+use crate::css::model::Selector;
+use crate::css::parser::{parse_selectors, ParseError};
+use crate::css::stylesheet::matches_selector;
+use crate::dom::node::NodeId;
+use crate::dom::tree::Dom;
+
+/// A pre-parsed, reusable selector list such as `Container > .item, #title`.
+///
+/// A node matches the query if it matches any selector in the list.
+///
+/// # Examples
+///
+/// ```ignore
+/// let items = Query::compile("List > .item")?;
+/// for id in items.all(&dom) {
+///     // ...
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    source: String,
+    selectors: Vec<Selector>,
+}
+
+impl Query {
+    /// Parse a selector list into a reusable query.
+    pub fn compile(source: &str) -> Result<Self, ParseError> {
+        let selectors = parse_selectors(source)?;
+        Ok(Self {
+            source: source.trim().to_owned(),
+            selectors,
+        })
+    }
+
+    /// Build a query from already-parsed selectors (e.g. a stylesheet rule).
+    pub fn from_selectors(selectors: Vec<Selector>) -> Self {
+        Self {
+            source: String::new(),
+            selectors,
+        }
+    }
+
+    /// The selector text this query was compiled from.
+    ///
+    /// Empty for queries built with [`Query::from_selectors`].
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// The parsed selectors.
+    pub fn selectors(&self) -> &[Selector] {
+        &self.selectors
+    }
+
+    /// Whether `node` matches any selector in this query.
+    pub fn matches(&self, node: NodeId, dom: &Dom) -> bool {
+        self.selectors
+            .iter()
+            .any(|sel| matches_selector(sel, node, dom))
+    }
+
+    /// All matching nodes in depth-first document order.
+    pub fn all(&self, dom: &Dom) -> Vec<NodeId> {
+        match dom.root() {
+            Some(root) => dom
+                .walk_depth_first(root)
+                .into_iter()
+                .filter(|&id| self.matches(id, dom))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// The first matching node in depth-first document order.
+    pub fn first(&self, dom: &Dom) -> Option<NodeId> {
+        let root = dom.root()?;
+        dom.walk_depth_first(root)
+            .into_iter()
+            .find(|&id| self.matches(id, dom))
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// NodeRef
+// ---------------------------------------------------------------------------
+
+/// A node named by id or by selector, for APIs that accept either
+/// (`app.region_of("#save")` or `app.region_of(node)`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeRef<'a> {
+    /// A specific node.
+    Id(NodeId),
+    /// The first node matching a selector, in document order.
+    Selector(&'a str),
+}
+
+impl NodeRef<'_> {
+    /// Look up the node in `dom`. Fails if the selector does not parse.
+    pub fn resolve(self, dom: &Dom) -> Result<Option<NodeId>, ParseError> {
+        match self {
+            NodeRef::Id(id) => Ok(dom.contains(id).then_some(id)),
+            NodeRef::Selector(selector) => Ok(Query::compile(selector)?.first(dom)),
+        }
+    }
+}
+
+impl From<NodeId> for NodeRef<'_> {
+    fn from(id: NodeId) -> Self {
+        NodeRef::Id(id)
+    }
+}
+
+impl<'a> From<&'a str> for NodeRef<'a> {
+    fn from(selector: &'a str) -> Self {
+        NodeRef::Selector(selector)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dom::node::NodeData;
+
+    fn build_dom() -> (Dom, NodeId, NodeId, NodeId) {
+        let mut dom = Dom::new();
+        let root = dom.insert(NodeData::new("Container"));
+        let a = dom.insert_child(root, NodeData::new("Static").with_class("item"));
+        let list = dom.insert_child(root, NodeData::new("List"));
+        let b = dom.insert_child(list, NodeData::new("Static").with_class("item"));
+        (dom, root, a, b)
+    }
+
+    #[test]
+    fn compile_valid() {
+        let q = Query::compile("  Container > .item ").unwrap();
+        assert_eq!(q.source(), "Container > .item");
+        assert_eq!(q.selectors().len(), 1);
+        assert_eq!(q.to_string(), "Container > .item");
+    }
+
+    #[test]
+    fn compile_invalid() {
+        assert!(Query::compile("Container >").is_err());
+        assert!(Query::compile("").is_err());
+    }
+
+    #[test]
+    fn compile_agrees_with_selector_macro_grammar() {
+        // Kept in step with the `selector!` tests in the macros crate. The
+        // macro also rejects a leading `-` in names: the tokenizer silently
+        // skips it, so `.-x` would compile to `.x`.
+        for sel in ["#cafe", "#bad", "#abc123", "Button#deadline", ":_x"] {
+            assert!(Query::compile(sel).is_err(), "{sel}");
+        }
+        for sel in ["#ab", "#title", "#ab-cd", "_x", ".a-b_c", ":focus-within"] {
+            assert!(Query::compile(sel).is_ok(), "{sel}");
+        }
+    }
+
+    #[test]
+    fn all_in_document_order() {
+        let (dom, _, a, b) = build_dom();
+        let q = Query::compile(".item").unwrap();
+        assert_eq!(q.all(&dom), vec![a, b]);
+        assert_eq!(q.first(&dom), Some(a));
+    }
+
+    #[test]
+    fn child_combinator() {
+        let (dom, _, a, b) = build_dom();
+        let q = Query::compile("Container > .item").unwrap();
+        assert!(q.matches(a, &dom));
+        assert!(!q.matches(b, &dom));
+    }
+
+    #[test]
+    fn selector_list_matches_any() {
+        let (dom, root, a, b) = build_dom();
+        let q = Query::compile("List > Static, Container").unwrap();
+        assert_eq!(q.all(&dom), vec![root, b]);
+        assert!(!q.matches(a, &dom));
+    }
+
+    #[test]
+    fn empty_dom_matches_nothing() {
+        let q = Query::compile("*").unwrap();
+        assert!(q.all(&Dom::new()).is_empty());
+        assert!(q.first(&Dom::new()).is_none());
+    }
+
+    #[test]
+    fn node_ref_resolves_ids_and_selectors() {
+        let (mut dom, root, a, b) = build_dom();
+        assert_eq!(NodeRef::from(b).resolve(&dom).unwrap(), Some(b));
+        assert_eq!(NodeRef::from(".item").resolve(&dom).unwrap(), Some(a));
+        assert_eq!(NodeRef::from("Missing").resolve(&dom).unwrap(), None);
+        assert!(NodeRef::from("List >").resolve(&dom).is_err());
+        dom.remove(b);
+        assert_eq!(NodeRef::from(b).resolve(&dom).unwrap(), None);
+        assert_eq!(NodeRef::from(root).resolve(&dom).unwrap(), Some(root));
+    }
+}
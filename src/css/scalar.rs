@@ -4,6 +4,7 @@ use std::fmt;
 
 /// A CSS unit type.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Unit {
     /// Cell count (default terminal unit, like "px" in CSS).
     Cells,
@@ -15,12 +16,16 @@ pub enum Unit {
     Vw,
     /// Viewport height percentage.
     Vh,
+    /// Root units (`u`): multiples of the app-wide unit scale, 1 cell by
+    /// default.
+    Root,
     /// Auto-size (content-based).
     Auto,
 }
 
 /// A scalar value with a unit, e.g. `10`, `1fr`, `50%`, `auto`.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Scalar {
     pub value: f32,
     pub unit: Unit,
@@ -67,6 +72,14 @@ impl Scalar {
         }
     }
 
+    /// Create a scalar in root units.
+    pub fn units(value: f32) -> Self {
+        Self {
+            value,
+            unit: Unit::Root,
+        }
+    }
+
     /// Create an auto scalar.
     pub fn auto() -> Self {
         Self {
@@ -120,12 +133,20 @@ impl fmt::Display for Scalar {
                     write!(f, "{}vh", self.value)
                 }
             }
+            Unit::Root => {
+                if self.value.fract() == 0.0 {
+                    write!(f, "{}u", self.value as i64)
+                } else {
+                    write!(f, "{}u", self.value)
+                }
+            }
         }
     }
 }
 
 /// Four-sided scalar values (top, right, bottom, left) like CSS margin/padding.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ScalarBox {
     pub top: Scalar,
     pub right: Scalar,
@@ -184,6 +205,14 @@ mod tests {
         assert_eq!(s.to_string(), "2.5");
     }
 
+    #[test]
+    fn test_scalar_units() {
+        let s = Scalar::units(2.0);
+        assert_eq!(s.unit, Unit::Root);
+        assert_eq!(s.to_string(), "2u");
+        assert_eq!(Scalar::units(0.5).to_string(), "0.5u");
+    }
+
     #[test]
     fn test_scalar_fr() {
         let s = Scalar::fr(1.0);
@@ -3,18 +3,49 @@
 //! This is the central style representation. Every CSS property has a typed
 //! `Option<T>` field. `None` means "not set" (inherits from parent or uses default).
 
+use crate::css::border::BorderGlyphs;
 use crate::css::scalar::{Scalar, ScalarBox};
 
 /// Text alignment options.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 pub enum TextAlign {
     Left,
     Center,
     Right,
 }
 
+/// What happens to text wider than its box (`text-overflow`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum TextOverflow {
+    /// Cut the text at the edge.
+    #[default]
+    Clip,
+    /// Cut the text and end it with `…`.
+    Ellipsis,
+    /// Abbreviate words from the end (`Save As` → `Save A.`), then ellipsize.
+    Shrink,
+}
+
+/// Whether text may wrap onto further rows (`text-wrap`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum TextWrap {
+    /// Keep text on one row.
+    #[default]
+    Nowrap,
+    /// Break between words onto the rows available.
+    Wrap,
+}
+
 /// Display property options.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 pub enum Display {
     Block,
     None,
@@ -22,6 +53,8 @@ pub enum Display {
 
 /// Visibility property.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 pub enum Visibility {
     Visible,
     Hidden,
@@ -29,14 +62,57 @@ pub enum Visibility {
 
 /// Overflow behavior.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 pub enum Overflow {
     Hidden,
     Scroll,
     Auto,
 }
 
+/// Whether space for a vertical scrollbar is reserved (`scrollbar-gutter`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum ScrollbarGutter {
+    /// Reserve a column only while a scrollbar is shown.
+    #[default]
+    Auto,
+    /// Always reserve a column, so content does not reflow when a scrollbar
+    /// appears.
+    Stable,
+}
+
+/// What happens to scroll input at the content edge (`overscroll-behavior`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum OverscrollBehavior {
+    /// Stop at the edge and absorb further input.
+    #[default]
+    Clamp,
+    /// Stop at the edge and leave further input for an enclosing scrollable.
+    None,
+}
+
+/// How a scrollable with hidden scrollbars hints at more content
+/// (`scroll-indicator`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum ScrollIndicator {
+    #[default]
+    None,
+    /// Dim the first/last visible row when more content lies beyond it.
+    Shadow,
+    /// Replace the first/last visible row's end with a "more…" marker.
+    Marker,
+}
+
 /// Layout direction.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 pub enum LayoutDirection {
     Vertical,
     Horizontal,
@@ -45,6 +121,8 @@ pub enum LayoutDirection {
 
 /// Dock position.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 pub enum Dock {
     Top,
     Right,
@@ -54,6 +132,8 @@ pub enum Dock {
 
 /// Border style.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 pub enum BorderKind {
     None,
     Thin,
@@ -61,10 +141,28 @@ pub enum BorderKind {
     Double,
     Round,
     Ascii,
+    /// User-defined glyphs (`border: custom "╭╮╰╯─│"` or a registered set).
+    Custom(BorderGlyphs),
+}
+
+impl BorderKind {
+    /// The characters this border is drawn with, or `None` for no border.
+    pub fn glyphs(self) -> Option<BorderGlyphs> {
+        match self {
+            BorderKind::None => None,
+            BorderKind::Thin => Some(BorderGlyphs::THIN),
+            BorderKind::Heavy => Some(BorderGlyphs::HEAVY),
+            BorderKind::Double => Some(BorderGlyphs::DOUBLE),
+            BorderKind::Round => Some(BorderGlyphs::ROUND),
+            BorderKind::Ascii => Some(BorderGlyphs::ASCII),
+            BorderKind::Custom(glyphs) => Some(glyphs),
+        }
+    }
 }
 
 /// A border with kind and optional color.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Border {
     pub kind: BorderKind,
     pub color: Option<String>,
@@ -72,6 +170,7 @@ pub struct Border {
 
 /// Text style flags (bold, italic, etc.)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TextStyleFlags {
     pub bold: Option<bool>,
     pub dim: Option<bool>,
@@ -79,12 +178,17 @@ pub struct TextStyleFlags {
     pub underline: Option<bool>,
     pub strikethrough: Option<bool>,
     pub reverse: Option<bool>,
+    /// Software blink, see [`effects`](crate::render::effects).
+    pub blink: Option<bool>,
+    /// Software pulse between bold and dim.
+    pub pulse: Option<bool>,
 }
 
 /// All CSS properties for a node. Each field is `Option<T>` — None means unset (inherit).
 ///
 /// Phase 1 properties: layout-critical subset.
 #[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Styles {
     // Display & Layout
     pub display: Option<Display>,
@@ -94,6 +198,15 @@ pub struct Styles {
     pub overflow_x: Option<Overflow>,
     pub overflow_y: Option<Overflow>,
 
+    // Scrolling
+    pub scrollbar_gutter: Option<ScrollbarGutter>,
+    pub overscroll_behavior: Option<OverscrollBehavior>,
+    pub scroll_indicator: Option<ScrollIndicator>,
+    /// Color of the vertical scrollbar's thumb and track (`scrollbar-color`).
+    pub scrollbar_color: Option<String>,
+    /// Width of the vertical scrollbar in cells (`scrollbar-size`); `0` hides it.
+    pub scrollbar_size: Option<u16>,
+
     // Sizing
     pub width: Option<Scalar>,
     pub height: Option<Scalar>,
@@ -105,6 +218,25 @@ pub struct Styles {
     // Spacing
     pub margin: Option<ScalarBox>,
     pub padding: Option<ScalarBox>,
+    /// Space between rows of a flex or grid container's children.
+    pub row_gap: Option<Scalar>,
+    /// Space between columns of a flex or grid container's children.
+    pub column_gap: Option<Scalar>,
+
+    // Grid
+    /// Number of columns in a grid container (`grid-size`).
+    pub grid_size_columns: Option<u16>,
+    /// Number of rows in a grid container (`grid-size`); further rows are
+    /// added as needed.
+    pub grid_size_rows: Option<u16>,
+    /// Column widths of a grid container, repeated to fill its columns.
+    pub grid_columns: Option<Vec<Scalar>>,
+    /// Row heights of a grid container, repeated to fill its rows.
+    pub grid_rows: Option<Vec<Scalar>>,
+    /// Number of columns a grid child spans.
+    pub column_span: Option<u16>,
+    /// Number of rows a grid child spans.
+    pub row_span: Option<u16>,
 
     // Colors
     pub color: Option<String>,
@@ -112,6 +244,8 @@ pub struct Styles {
 
     // Text
     pub text_align: Option<TextAlign>,
+    pub text_overflow: Option<TextOverflow>,
+    pub text_wrap: Option<TextWrap>,
     pub text_style: Option<TextStyleFlags>,
 
     // Border
@@ -145,6 +279,12 @@ impl Styles {
             overflow_x: merge_opt(&self.overflow_x, &other.overflow_x),
             overflow_y: merge_opt(&self.overflow_y, &other.overflow_y),
 
+            scrollbar_gutter: merge_opt(&self.scrollbar_gutter, &other.scrollbar_gutter),
+            overscroll_behavior: merge_opt(&self.overscroll_behavior, &other.overscroll_behavior),
+            scroll_indicator: merge_opt(&self.scroll_indicator, &other.scroll_indicator),
+            scrollbar_color: merge_opt(&self.scrollbar_color, &other.scrollbar_color),
+            scrollbar_size: merge_opt(&self.scrollbar_size, &other.scrollbar_size),
+
             width: merge_opt(&self.width, &other.width),
             height: merge_opt(&self.height, &other.height),
             min_width: merge_opt(&self.min_width, &other.min_width),
@@ -154,11 +294,22 @@ impl Styles {
 
             margin: merge_opt(&self.margin, &other.margin),
             padding: merge_opt(&self.padding, &other.padding),
+            row_gap: merge_opt(&self.row_gap, &other.row_gap),
+            column_gap: merge_opt(&self.column_gap, &other.column_gap),
+
+            grid_size_columns: merge_opt(&self.grid_size_columns, &other.grid_size_columns),
+            grid_size_rows: merge_opt(&self.grid_size_rows, &other.grid_size_rows),
+            grid_columns: merge_opt(&self.grid_columns, &other.grid_columns),
+            grid_rows: merge_opt(&self.grid_rows, &other.grid_rows),
+            column_span: merge_opt(&self.column_span, &other.column_span),
+            row_span: merge_opt(&self.row_span, &other.row_span),
 
             color: merge_opt(&self.color, &other.color),
             background: merge_opt(&self.background, &other.background),
 
             text_align: merge_opt(&self.text_align, &other.text_align),
+            text_overflow: merge_opt(&self.text_overflow, &other.text_overflow),
+            text_wrap: merge_opt(&self.text_wrap, &other.text_wrap),
             text_style: merge_opt(&self.text_style, &other.text_style),
 
             border: merge_opt(&self.border, &other.border),
@@ -173,6 +324,11 @@ impl Styles {
             && self.dock.is_none()
             && self.overflow_x.is_none()
             && self.overflow_y.is_none()
+            && self.scrollbar_gutter.is_none()
+            && self.overscroll_behavior.is_none()
+            && self.scroll_indicator.is_none()
+            && self.scrollbar_color.is_none()
+            && self.scrollbar_size.is_none()
             && self.width.is_none()
             && self.height.is_none()
             && self.min_width.is_none()
@@ -181,9 +337,19 @@ impl Styles {
             && self.max_height.is_none()
             && self.margin.is_none()
             && self.padding.is_none()
+            && self.row_gap.is_none()
+            && self.column_gap.is_none()
+            && self.grid_size_columns.is_none()
+            && self.grid_size_rows.is_none()
+            && self.grid_columns.is_none()
+            && self.grid_rows.is_none()
+            && self.column_span.is_none()
+            && self.row_span.is_none()
             && self.color.is_none()
             && self.background.is_none()
             && self.text_align.is_none()
+            && self.text_overflow.is_none()
+            && self.text_wrap.is_none()
             && self.text_style.is_none()
             && self.border.is_none()
     }
@@ -435,4 +601,27 @@ mod tests {
         assert_eq!(a, b);
         assert_ne!(a, c);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_keywords_use_css_spelling() {
+        use serde::de::value::{Error, StrDeserializer};
+        use serde::de::{Deserialize, IntoDeserializer};
+
+        let de: StrDeserializer<Error> = "top".into_deserializer();
+        assert_eq!(Dock::deserialize(de).unwrap(), Dock::Top);
+        let de: StrDeserializer<Error> = "horizontal".into_deserializer();
+        assert_eq!(LayoutDirection::deserialize(de).unwrap(), LayoutDirection::Horizontal);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn config_types_implement_serde() {
+        fn assert_serde<T: serde::Serialize + serde::de::DeserializeOwned>() {}
+        assert_serde::<Styles>();
+        assert_serde::<crate::css::model::StyleSheet>();
+        assert_serde::<crate::geometry::Region>();
+        assert_serde::<crate::geometry::Spacing>();
+        assert_serde::<crate::event::binding::KeyBindingDef>();
+    }
 }
@@ -4,10 +4,11 @@
 //! nodes, computing specificity, and merging styles via the CSS cascade.
 
 use crate::css::model::{
-    Combinator, CompoundSelector, Declaration, RuleSet, Selector, SelectorComponent, SelectorPart,
+    Combinator, CompoundSelector, Declaration, Selector, SelectorComponent, SelectorPart,
     StyleSheet,
 };
 use crate::css::properties::apply_declaration;
+use crate::css::query::Query;
 use crate::css::specificity::Specificity;
 use crate::css::styles::Styles;
 use crate::dom::node::{NodeData, NodeId};
@@ -23,7 +24,9 @@ pub struct CompiledStylesheet {
 /// A single rule with its pre-computed specificity.
 #[derive(Debug)]
 struct CompiledRule {
-    rule: RuleSet,
+    /// The rule's selector list, shared matcher with the query API.
+    query: Query,
+    declarations: Vec<Declaration>,
     specificity: Specificity,
     /// Source order index for stable sorting.
     source_order: usize,
@@ -51,7 +54,8 @@ impl CompiledStylesheet {
                 .unwrap_or_default();
 
             rules.push(CompiledRule {
-                rule: rule.clone(),
+                query: Query::from_selectors(rule.selectors.clone()),
+                declarations: rule.declarations.clone(),
                 specificity,
                 source_order: i,
             });
@@ -69,17 +73,11 @@ impl CompiledStylesheet {
         let mut matches: Vec<(Specificity, usize, &[Declaration])> = Vec::new();
 
         for compiled_rule in &self.rules {
-            let rule = &compiled_rule.rule;
-            let any_selector_matches = rule
-                .selectors
-                .iter()
-                .any(|sel| matches_selector(sel, node_id, dom));
-
-            if any_selector_matches {
+            if compiled_rule.query.matches(node_id, dom) {
                 matches.push((
                     compiled_rule.specificity,
                     compiled_rule.source_order,
-                    &rule.declarations,
+                    &compiled_rule.declarations,
                 ));
             }
         }
@@ -101,13 +99,29 @@ impl CompiledStylesheet {
 
         result
     }
+
+    /// Whether any rule's selector contains the pseudo-class `name`
+    /// (without the colon), so state changes it depends on need a restyle.
+    pub fn uses_pseudo_class(&self, name: &str) -> bool {
+        self.rules.iter().any(|compiled_rule| {
+            compiled_rule.query.selectors().iter().any(|selector| {
+                selector.parts.iter().any(|part| match part {
+                    SelectorPart::Compound(compound) => compound
+                        .components
+                        .iter()
+                        .any(|c| matches!(c, SelectorComponent::PseudoClass(p) if p == name)),
+                    SelectorPart::Combinator(_) => false,
+                })
+            })
+        })
+    }
 }
 
 /// Check whether a full selector matches a given node.
 ///
 /// Walks the selector parts from right to left, matching compound selectors
 /// and navigating the DOM tree via combinators.
-fn matches_selector(selector: &Selector, node_id: NodeId, dom: &Dom) -> bool {
+pub(crate) fn matches_selector(selector: &Selector, node_id: NodeId, dom: &Dom) -> bool {
     let parts = &selector.parts;
     if parts.is_empty() {
         return false;
@@ -203,10 +217,7 @@ fn matches_compound(compound: &CompoundSelector, node: &NodeData) -> bool {
         SelectorComponent::Class(name) => node.has_class(name),
         SelectorComponent::Id(name) => node.id.as_deref() == Some(name.as_str()),
         SelectorComponent::Universal => true,
-        SelectorComponent::PseudoClass(_) => {
-            // Pseudo-classes need runtime state; skip for Phase 1.
-            false
-        }
+        SelectorComponent::PseudoClass(name) => node.matches_pseudo_class(name),
     })
 }
 
@@ -490,16 +501,17 @@ mod tests {
     }
 
     #[test]
-    fn matches_compound_pseudo_class_returns_false() {
-        let node = NodeData::new("Button");
+    fn matches_compound_pseudo_class_uses_node_state() {
+        let mut node = NodeData::new("Button");
         let compound = CompoundSelector {
             components: vec![
                 SelectorComponent::Type("Button".into()),
                 SelectorComponent::PseudoClass("hover".into()),
             ],
         };
-        // Pseudo-classes are skipped (return false) in Phase 1
         assert!(!matches_compound(&compound, &node));
+        node.hovered = true;
+        assert!(matches_compound(&compound, &node));
     }
 
     #[test]
@@ -25,8 +25,8 @@ pub enum Token {
     #[regex(r"#[0-9a-fA-F]{3,8}")]
     HexColor,
 
-    /// Dimension: number with unit suffix like `1fr`, `50%`, `10vw`, `80vh`.
-    #[regex(r"-?[0-9]+(\.[0-9]+)?(fr|%|vw|vh)")]
+    /// Dimension: number with unit suffix like `1fr`, `50%`, `10vw`, `80vh`, `2u`.
+    #[regex(r"-?[0-9]+(\.[0-9]+)?(fr|%|vw|vh|u)")]
     Dimension,
 
     /// Pseudo-class: `:hover`, `:focus`, `:disabled`, etc.
@@ -75,6 +75,14 @@ pub enum Token {
     #[token(",")]
     Comma,
 
+    /// `(`
+    #[token("(")]
+    ParenOpen,
+
+    /// `)`
+    #[token(")")]
+    ParenClose,
+
     /// `.`
     #[token(".")]
     Dot,
@@ -165,11 +173,12 @@ mod tests {
 
     #[test]
     fn test_dimensions() {
-        let result = tokens_with_text("1fr 50% 100vw 80vh");
+        let result = tokens_with_text("1fr 50% 100vw 80vh 2u");
         assert_eq!(result[0], (Token::Dimension, "1fr".into()));
         assert_eq!(result[1], (Token::Dimension, "50%".into()));
         assert_eq!(result[2], (Token::Dimension, "100vw".into()));
         assert_eq!(result[3], (Token::Dimension, "80vh".into()));
+        assert_eq!(result[4], (Token::Dimension, "2u".into()));
     }
 
     #[test]
@@ -1,8 +1,12 @@
-//! DOM arena: slotmap-backed widget tree with CSS class/id queries.
+//! DOM arena: slotmap-backed widget tree with CSS class/id queries, generated
+//! widget ids, and duplicate-id diagnostics.
 
 pub mod node;
 pub mod tree;
 pub mod query;
+pub mod transaction;
 
 pub use node::{NodeId, NodeData};
+pub use query::DuplicateId;
 pub use tree::Dom;
+pub use transaction::{DomChanges, PendingNode, Target, Transaction, TransactionError};
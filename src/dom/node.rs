@@ -2,6 +2,8 @@
 
 use slotmap::new_key_type;
 
+use crate::event::binding::WidgetBinding;
+
 new_key_type! {
     /// Unique identifier for a DOM node. Copy, lightweight (u64).
     pub struct NodeId;
@@ -14,6 +16,12 @@ pub struct NodeData {
     pub widget_type: String,
     /// Optional unique id (CSS #id selector).
     pub id: Option<String>,
+    /// Generated id such as `button-3`, assigned by the [`Dom`] on
+    /// insertion and stable for the node's lifetime; see
+    /// [`widget_id`](Self::widget_id).
+    ///
+    /// [`Dom`]: crate::dom::tree::Dom
+    pub auto_id: Option<String>,
     /// CSS classes (for .class selector).
     pub classes: Vec<String>,
     /// Whether this node is visible.
@@ -22,6 +30,32 @@ pub struct NodeData {
     pub focusable: bool,
     /// Whether this node is disabled.
     pub disabled: bool,
+    /// Reconciliation key, used to match the node across keyed refreshes.
+    pub key: Option<String>,
+    /// Tooltip text shown on hover.
+    pub tooltip: Option<String>,
+    /// Title drawn in the top border.
+    pub border_title: Option<String>,
+    /// Label announced when the node gains focus.
+    pub aria_label: Option<String>,
+    /// Text the widget displays, compared on reconcile so a re-render that
+    /// only changes the text still updates the node.
+    pub content: Option<String>,
+    /// The widget's key bindings table, active while the node or a
+    /// descendant has focus.
+    pub bindings: &'static [WidgetBinding],
+    /// Whether the pointer is over this node (`:hover`). Runtime state kept
+    /// by the screen.
+    pub hovered: bool,
+    /// Whether this node has focus (`:focus`). Runtime state kept by the
+    /// screen.
+    pub focused: bool,
+    /// Whether this container's content is scrolled away from the start
+    /// (`:scrolled`). Runtime state kept by the screen.
+    pub scrolled: bool,
+    /// Whether this container is scrolled as far as it goes
+    /// (`:scroll-end`). Runtime state kept by the screen.
+    pub scroll_end: bool,
 }
 
 impl NodeData {
@@ -30,13 +64,36 @@ impl NodeData {
         Self {
             widget_type: widget_type.into(),
             id: None,
+            auto_id: None,
             classes: Vec::new(),
             visible: true,
             focusable: false,
             disabled: false,
+            key: None,
+            tooltip: None,
+            border_title: None,
+            aria_label: None,
+            content: None,
+            bindings: &[],
+            hovered: false,
+            focused: false,
+            scrolled: false,
+            scroll_end: false,
         }
     }
 
+    /// Set the reconciliation key (builder).
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// The id tools and tests refer to the node by: its explicit id if it
+    /// has one, otherwise its generated id.
+    pub fn widget_id(&self) -> Option<&str> {
+        self.id.as_deref().or(self.auto_id.as_deref())
+    }
+
     /// Set the CSS id (builder).
     pub fn with_id(mut self, id: impl Into<String>) -> Self {
         self.id = Some(id.into());
@@ -75,6 +132,24 @@ impl NodeData {
         self
     }
 
+    /// Set the tooltip text (builder).
+    pub fn with_tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Set the border title (builder).
+    pub fn with_border_title(mut self, title: impl Into<String>) -> Self {
+        self.border_title = Some(title.into());
+        self
+    }
+
+    /// Set the accessible label (builder).
+    pub fn with_aria_label(mut self, label: impl Into<String>) -> Self {
+        self.aria_label = Some(label.into());
+        self
+    }
+
     /// Check whether this node has a given CSS class.
     pub fn has_class(&self, class: &str) -> bool {
         self.classes.iter().any(|c| c == class)
@@ -100,6 +175,21 @@ impl NodeData {
             self.add_class(class);
         }
     }
+
+    /// Whether the node is in the state a pseudo-class (without the colon)
+    /// names: `hover`, `focus`, `disabled`, `enabled`, `scrolled`, or
+    /// `scroll-end`. Unknown pseudo-classes never match.
+    pub fn matches_pseudo_class(&self, name: &str) -> bool {
+        match name {
+            "hover" => self.hovered,
+            "focus" => self.focused,
+            "disabled" => self.disabled,
+            "enabled" => !self.disabled,
+            "scrolled" => self.scrolled,
+            "scroll-end" => self.scroll_end,
+            _ => false,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -115,6 +205,13 @@ mod tests {
         assert!(data.visible);
         assert!(!data.focusable);
         assert!(!data.disabled);
+        assert!(data.key.is_none());
+    }
+
+    #[test]
+    fn builder_with_key() {
+        let data = NodeData::new("Row").with_key("user-42");
+        assert_eq!(data.key.as_deref(), Some("user-42"));
     }
 
     #[test]
@@ -154,6 +251,23 @@ mod tests {
         assert!(data.disabled);
     }
 
+    #[test]
+    fn pseudo_classes_follow_state() {
+        let mut data = NodeData::new("Button");
+        assert!(data.matches_pseudo_class("enabled"));
+        assert!(!data.matches_pseudo_class("hover"));
+        data.hovered = true;
+        data.focused = true;
+        data.disabled = true;
+        data.scrolled = true;
+        data.scroll_end = true;
+        for name in ["hover", "focus", "disabled", "scrolled", "scroll-end"] {
+            assert!(data.matches_pseudo_class(name));
+        }
+        assert!(!data.matches_pseudo_class("enabled"));
+        assert!(!data.matches_pseudo_class("visited"));
+    }
+
     #[test]
     fn has_class() {
         let data = NodeData::new("X").with_class("active");
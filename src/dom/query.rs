@@ -1,8 +1,31 @@
 //! DOM queries: by id, class, type; generic predicate matching.
+//!
+//! [`Dom::duplicate_ids`] is the validation pass for explicit ids: two nodes
+//! sharing an id make `#id` selectors and [`Dom::query_by_id`] match an
+//! arbitrary one of them, so duplicates are reported as [`DuplicateId`]
+//! diagnostics instead.
+
+use std::collections::HashMap;
+use std::fmt;
 
 use super::node::{NodeData, NodeId};
 use super::tree::Dom;
 
+/// An explicit id carried by more than one node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateId {
+    /// The contested id.
+    pub id: String,
+    /// Every node carrying it, in tree order.
+    pub nodes: Vec<NodeId>,
+}
+
+impl fmt::Display for DuplicateId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "id #{} is used by {} nodes", self.id, self.nodes.len())
+    }
+}
+
 impl Dom {
     /// Find the first node whose `id` field matches the given string.
     ///
@@ -37,6 +60,45 @@ impl Dom {
             .collect()
     }
 
+    /// Find the node with the given [`widget_id`](NodeData::widget_id): an
+    /// explicit id, or failing that a generated one like `button-3`.
+    pub fn query_by_widget_id(&self, id: &str) -> Option<NodeId> {
+        self.query_by_id(id).or_else(|| {
+            self.iter_nodes()
+                .find(|(_, data)| data.auto_id.as_deref() == Some(id))
+                .map(|(node_id, _)| node_id)
+        })
+    }
+
+    /// Explicit ids used by more than one node in the tree under the root,
+    /// in order of first use.
+    pub fn duplicate_ids(&self) -> Vec<DuplicateId> {
+        let Some(root) = self.root() else {
+            return Vec::new();
+        };
+        let mut order: Vec<&str> = Vec::new();
+        let mut users: HashMap<&str, Vec<NodeId>> = HashMap::new();
+        for node in self.walk_depth_first(root) {
+            if let Some(id) = self.get(node).and_then(|data| data.id.as_deref()) {
+                let nodes = users.entry(id).or_default();
+                if nodes.is_empty() {
+                    order.push(id);
+                }
+                nodes.push(node);
+            }
+        }
+        order
+            .into_iter()
+            .filter_map(|id| {
+                let nodes = users.remove(id)?;
+                (nodes.len() > 1).then(|| DuplicateId {
+                    id: id.to_owned(),
+                    nodes,
+                })
+            })
+            .collect()
+    }
+
     /// Iterate over all `(NodeId, &NodeData)` pairs in the arena.
     ///
     /// This is a helper used by the query methods. It iterates in slotmap
@@ -175,6 +237,33 @@ mod tests {
         assert_eq!(focusable.len(), 2);
     }
 
+    #[test]
+    fn query_by_widget_id_falls_back_to_generated_ids() {
+        let dom = build_query_tree();
+        let save = dom.query_by_id("save").unwrap();
+        assert_eq!(dom.query_by_widget_id("save"), Some(save));
+        let panels = dom.query_by_type("Panel");
+        assert_eq!(dom.query_by_widget_id("panel-2"), Some(panels[1]));
+        assert_eq!(dom.query_by_widget_id("panel-3"), None);
+    }
+
+    #[test]
+    fn duplicate_ids_are_reported_in_tree_order() {
+        let mut dom = build_query_tree();
+        assert!(dom.duplicate_ids().is_empty());
+        let root = dom.root().unwrap();
+        let late = dom.insert_child(root, NodeData::new("Button").with_id("save"));
+        let later = dom.insert_child(root, NodeData::new("Label").with_id("save"));
+        dom.insert_child(root, NodeData::new("Label").with_id("main"));
+
+        let duplicates = dom.duplicate_ids();
+        assert_eq!(duplicates.len(), 2);
+        let save = dom.query_by_id("save").unwrap();
+        assert_eq!(duplicates[0].id, "save");
+        assert_eq!(duplicates[0].nodes, [save, late, later]);
+        assert_eq!(duplicates[1].to_string(), "id #main is used by 2 nodes");
+    }
+
     #[test]
     fn query_on_empty_dom() {
         let dom = Dom::new();
@@ -0,0 +1,670 @@
+//! Batched DOM mutations: Transaction, DomChanges.
+//!
+//! A [`Transaction`] buffers inserts, removals, reparents, and node-data
+//! updates. [`Dom::transaction`] validates the whole batch up front and then
+//! applies it in one go, returning a [`DomChanges`] summary so that callers can
+//! run a single combined invalidation (restyle, relayout, repaint) instead of
+//! one per mutation.
+
+use std::collections::{HashMap, HashSet};
+
+use super::node::{NodeData, NodeId};
+use super::tree::Dom;
+
+// ---------------------------------------------------------------------------
+// Target
+// ---------------------------------------------------------------------------
+
+/// Handle to a node inserted within a transaction that has not been applied yet.
+///
+/// Pending nodes can be used as parents or targets for later operations in the
+/// same transaction. After the transaction is applied, the real [`NodeId`] can
+/// be looked up with [`DomChanges::resolve`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PendingNode(usize);
+
+/// The target of a transaction operation: an existing node or a pending one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Target {
+    /// A node already present in the DOM.
+    Existing(NodeId),
+    /// A node inserted earlier in the same transaction.
+    Pending(PendingNode),
+}
+
+impl From<NodeId> for Target {
+    fn from(id: NodeId) -> Self {
+        Target::Existing(id)
+    }
+}
+
+impl From<PendingNode> for Target {
+    fn from(pending: PendingNode) -> Self {
+        Target::Pending(pending)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// TransactionError
+// ---------------------------------------------------------------------------
+
+/// Errors that cause a transaction to be rejected without touching the DOM.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TransactionError {
+    /// An operation referenced a node that does not exist (or was removed
+    /// earlier in the same transaction).
+    #[error("operation {op} targets a missing node")]
+    MissingNode { op: usize },
+    /// A reparent would move a node underneath itself.
+    #[error("operation {op} would create a cycle")]
+    Cycle { op: usize },
+}
+
+// ---------------------------------------------------------------------------
+// Transaction
+// ---------------------------------------------------------------------------
+
+/// A single buffered DOM operation.
+enum Op {
+    Insert {
+        parent: Option<Target>,
+        data: Box<NodeData>,
+    },
+    Remove(Target),
+    Reparent {
+        node: Target,
+        new_parent: Target,
+    },
+    Update {
+        node: Target,
+        f: Box<dyn FnOnce(&mut NodeData)>,
+    },
+}
+
+/// A buffer of DOM operations applied atomically by [`Dom::transaction`].
+///
+/// # Examples
+///
+/// ```ignore
+/// let changes = dom.transaction(|tx| {
+///     for old in &items {
+///         tx.remove(*old);
+///     }
+///     for label in labels {
+///         tx.insert_child(list, NodeData::new("Static").with_class("item"));
+///     }
+/// })?;
+/// ```
+#[derive(Default)]
+pub struct Transaction {
+    ops: Vec<Op>,
+    pending: usize,
+}
+
+impl Transaction {
+    /// Create an empty transaction.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Buffer insertion of a root-level node.
+    pub fn insert(&mut self, data: NodeData) -> PendingNode {
+        self.push_insert(None, data)
+    }
+
+    /// Buffer insertion of a node as the last child of `parent`.
+    pub fn insert_child(&mut self, parent: impl Into<Target>, data: NodeData) -> PendingNode {
+        self.push_insert(Some(parent.into()), data)
+    }
+
+    /// Buffer removal of a node and its subtree.
+    pub fn remove(&mut self, node: impl Into<Target>) {
+        self.ops.push(Op::Remove(node.into()));
+    }
+
+    /// Buffer moving `node` (with its subtree) under `new_parent`.
+    pub fn reparent(&mut self, node: impl Into<Target>, new_parent: impl Into<Target>) {
+        self.ops.push(Op::Reparent {
+            node: node.into(),
+            new_parent: new_parent.into(),
+        });
+    }
+
+    /// Buffer an arbitrary change to a node's data.
+    pub fn update(&mut self, node: impl Into<Target>, f: impl FnOnce(&mut NodeData) + 'static) {
+        self.ops.push(Op::Update {
+            node: node.into(),
+            f: Box::new(f),
+        });
+    }
+
+    /// Buffer adding a CSS class to a node.
+    pub fn add_class(&mut self, node: impl Into<Target>, class: impl Into<String>) {
+        let class = class.into();
+        self.update(node, move |data| data.add_class(&class));
+    }
+
+    /// Buffer removing a CSS class from a node.
+    pub fn remove_class(&mut self, node: impl Into<Target>, class: impl Into<String>) {
+        let class = class.into();
+        self.update(node, move |data| data.remove_class(&class));
+    }
+
+    /// Buffer a visibility change.
+    pub fn set_visible(&mut self, node: impl Into<Target>, visible: bool) {
+        self.update(node, move |data| data.visible = visible);
+    }
+
+    /// Number of buffered operations.
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// Whether no operations have been buffered.
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    fn push_insert(&mut self, parent: Option<Target>, data: NodeData) -> PendingNode {
+        let pending = PendingNode(self.pending);
+        self.pending += 1;
+        self.ops.push(Op::Insert {
+            parent,
+            data: Box::new(data),
+        });
+        pending
+    }
+}
+
+// ---------------------------------------------------------------------------
+// DomChanges
+// ---------------------------------------------------------------------------
+
+/// Summary of everything a transaction changed.
+///
+/// Node lists are deduplicated and in first-touched order. Nodes that were
+/// inserted and then removed within the same transaction appear in neither
+/// `inserted` nor `updated`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DomChanges {
+    /// Nodes added to the DOM.
+    pub inserted: Vec<NodeId>,
+    /// Nodes removed from the DOM, including all removed descendants.
+    pub removed: Vec<NodeId>,
+    /// Existing nodes whose data or position in the tree changed.
+    pub updated: Vec<NodeId>,
+    /// Real ids for each pending node, indexed by insertion order.
+    pending: Vec<Option<NodeId>>,
+}
+
+impl DomChanges {
+    /// Whether the transaction changed nothing.
+    pub fn is_empty(&self) -> bool {
+        self.inserted.is_empty() && self.removed.is_empty() && self.updated.is_empty()
+    }
+
+    /// Every node the transaction touched, in the order inserted, removed, updated.
+    pub fn affected(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.inserted
+            .iter()
+            .chain(&self.removed)
+            .chain(&self.updated)
+            .copied()
+    }
+
+    /// The real id assigned to a pending node, or `None` if it was removed
+    /// again within the same transaction.
+    pub fn resolve(&self, pending: PendingNode) -> Option<NodeId> {
+        self.pending.get(pending.0).copied().flatten()
+    }
+
+    /// Fold in the changes of a transaction applied after this one, as if
+    /// both had been one transaction. Pending nodes still resolve against
+    /// this transaction only.
+    pub fn merge(&mut self, later: DomChanges) {
+        let mut inserted: HashSet<NodeId> = self.inserted.iter().copied().collect();
+        let mut removed: HashSet<NodeId> = self.removed.iter().copied().collect();
+        let mut updated: HashSet<NodeId> = self.updated.iter().copied().collect();
+        for node in later.removed {
+            if inserted.remove(&node) {
+                self.inserted.retain(|&n| n != node);
+            } else if removed.insert(node) {
+                self.removed.push(node);
+            }
+            if updated.remove(&node) {
+                self.updated.retain(|&n| n != node);
+            }
+        }
+        for node in later.inserted {
+            if inserted.insert(node) {
+                self.inserted.push(node);
+            }
+        }
+        for node in later.updated {
+            if !inserted.contains(&node) && updated.insert(node) {
+                self.updated.push(node);
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Dom integration
+// ---------------------------------------------------------------------------
+
+impl Dom {
+    /// Build a transaction with `f` and apply it atomically.
+    ///
+    /// The whole batch is validated before any mutation happens; if any
+    /// operation targets a missing node, the DOM is left untouched and an error
+    /// is returned.
+    pub fn transaction(
+        &mut self,
+        f: impl FnOnce(&mut Transaction),
+    ) -> Result<DomChanges, TransactionError> {
+        let mut tx = Transaction::new();
+        f(&mut tx);
+        self.apply(tx)
+    }
+
+    /// Validate and apply a prepared transaction.
+    pub fn apply(&mut self, tx: Transaction) -> Result<DomChanges, TransactionError> {
+        self.validate(&tx)?;
+
+        let mut changes = DomChanges {
+            pending: Vec::with_capacity(tx.pending),
+            ..DomChanges::default()
+        };
+        let mut inserted = HashSet::new();
+        let mut removed = HashSet::new();
+        let mut updated = HashSet::new();
+
+        for (index, op) in tx.ops.into_iter().enumerate() {
+            match op {
+                Op::Insert { parent, data } => {
+                    let id = match parent {
+                        Some(p) => {
+                            let p = resolve_target(&changes, p, index)?;
+                            self.insert_child(p, *data)
+                        }
+                        None => self.insert(*data),
+                    };
+                    changes.pending.push(Some(id));
+                    inserted.insert(id);
+                    changes.inserted.push(id);
+                }
+                Op::Remove(target) => {
+                    let id = resolve_target(&changes, target, index)?;
+                    let subtree = self.walk_depth_first(id);
+                    self.remove(id);
+                    for node in subtree {
+                        if inserted.remove(&node) {
+                            changes.inserted.retain(|&n| n != node);
+                            for slot in changes.pending.iter_mut() {
+                                if *slot == Some(node) {
+                                    *slot = None;
+                                }
+                            }
+                        } else if removed.insert(node) {
+                            changes.removed.push(node);
+                        }
+                        if updated.remove(&node) {
+                            changes.updated.retain(|&n| n != node);
+                        }
+                    }
+                }
+                Op::Reparent { node, new_parent } => {
+                    let node = resolve_target(&changes, node, index)?;
+                    let new_parent = resolve_target(&changes, new_parent, index)?;
+                    self.reparent(node, new_parent);
+                    if !inserted.contains(&node) && updated.insert(node) {
+                        changes.updated.push(node);
+                    }
+                }
+                Op::Update { node, f } => {
+                    let node = resolve_target(&changes, node, index)?;
+                    if let Some(data) = self.get_mut(node) {
+                        f(data);
+                    }
+                    if !inserted.contains(&node) && updated.insert(node) {
+                        changes.updated.push(node);
+                    }
+                }
+            }
+        }
+
+        Ok(changes)
+    }
+
+    /// Dry-run the transaction against a simulation of the tree as each
+    /// operation leaves it, so later operations are checked against earlier
+    /// inserts, removals, and moves rather than the tree as it is now.
+    fn validate(&self, tx: &Transaction) -> Result<(), TransactionError> {
+        let mut sim = Simulation::new(self);
+
+        for (op, entry) in tx.ops.iter().enumerate() {
+            match entry {
+                Op::Insert { parent, .. } => {
+                    if let Some(p) = parent {
+                        if !sim.is_live(*p) {
+                            return Err(TransactionError::MissingNode { op });
+                        }
+                    }
+                    let pending = Target::Pending(PendingNode(sim.pending));
+                    sim.parents.insert(pending, *parent);
+                    sim.pending += 1;
+                }
+                Op::Remove(target) => {
+                    if !sim.is_live(*target) {
+                        return Err(TransactionError::MissingNode { op });
+                    }
+                    sim.removed.insert(*target);
+                }
+                Op::Reparent { node, new_parent } => {
+                    if !sim.is_live(*node) || !sim.is_live(*new_parent) {
+                        return Err(TransactionError::MissingNode { op });
+                    }
+                    if sim.is_ancestor_or_self(*node, *new_parent) {
+                        return Err(TransactionError::Cycle { op });
+                    }
+                    sim.parents.insert(*node, Some(*new_parent));
+                }
+                Op::Update { node, .. } => {
+                    if !sim.is_live(*node) {
+                        return Err(TransactionError::MissingNode { op });
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The tree as a transaction leaves it, op by op, used while validating:
+/// parent links changed by inserts and reparents override the DOM's.
+struct Simulation<'a> {
+    dom: &'a Dom,
+    /// Parents set by the transaction so far, for pending and moved nodes.
+    parents: HashMap<Target, Option<Target>>,
+    /// Nodes removed so far; their descendants are removed with them.
+    removed: HashSet<Target>,
+    /// Number of pending nodes inserted so far.
+    pending: usize,
+}
+
+impl<'a> Simulation<'a> {
+    fn new(dom: &'a Dom) -> Self {
+        Self {
+            dom,
+            parents: HashMap::new(),
+            removed: HashSet::new(),
+            pending: 0,
+        }
+    }
+
+    fn parent(&self, target: Target) -> Option<Target> {
+        match self.parents.get(&target) {
+            Some(parent) => *parent,
+            None => match target {
+                Target::Existing(id) => self.dom.parent(id).map(Target::Existing),
+                Target::Pending(_) => None,
+            },
+        }
+    }
+
+    /// Whether `target` exists and neither it nor any ancestor was removed.
+    fn is_live(&self, target: Target) -> bool {
+        let exists = match target {
+            Target::Existing(id) => self.dom.contains(id),
+            Target::Pending(p) => p.0 < self.pending,
+        };
+        exists && !self.is_ancestor_or_self_where(target, |t| self.removed.contains(&t))
+    }
+
+    /// Whether `ancestor` is `node` or one of its ancestors.
+    fn is_ancestor_or_self(&self, ancestor: Target, node: Target) -> bool {
+        self.is_ancestor_or_self_where(node, |t| t == ancestor)
+    }
+
+    /// Whether `node` or any of its ancestors matches `f`. Validation keeps
+    /// the simulated tree acyclic, so the walk ends.
+    fn is_ancestor_or_self_where(&self, node: Target, f: impl Fn(Target) -> bool) -> bool {
+        let mut current = Some(node);
+        while let Some(target) = current {
+            if f(target) {
+                return true;
+            }
+            current = self.parent(target);
+        }
+        false
+    }
+}
+
+fn resolve_target(
+    changes: &DomChanges,
+    target: Target,
+    op: usize,
+) -> Result<NodeId, TransactionError> {
+    match target {
+        Target::Existing(id) => Ok(id),
+        Target::Pending(p) => changes
+            .pending
+            .get(p.0)
+            .copied()
+            .flatten()
+            .ok_or(TransactionError::MissingNode { op }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list_dom() -> (Dom, NodeId, Vec<NodeId>) {
+        let mut dom = Dom::new();
+        let root = dom.insert(NodeData::new("List"));
+        let items = (0..3)
+            .map(|_| dom.insert_child(root, NodeData::new("Item")))
+            .collect();
+        (dom, root, items)
+    }
+
+    #[test]
+    fn empty_transaction_changes_nothing() {
+        let (mut dom, _, _) = list_dom();
+        let changes = dom.transaction(|_| {}).unwrap();
+        assert!(changes.is_empty());
+        assert_eq!(dom.len(), 4);
+    }
+
+    #[test]
+    fn bulk_refill() {
+        let (mut dom, root, items) = list_dom();
+        let old = items.clone();
+        let changes = dom
+            .transaction(|tx| {
+                for id in old {
+                    tx.remove(id);
+                }
+                for _ in 0..5 {
+                    tx.insert_child(root, NodeData::new("Item").with_class("new"));
+                }
+            })
+            .unwrap();
+
+        assert_eq!(changes.removed, items);
+        assert_eq!(changes.inserted.len(), 5);
+        assert_eq!(dom.children(root), changes.inserted.as_slice());
+        assert_eq!(dom.query_by_class("new").len(), 5);
+    }
+
+    #[test]
+    fn pending_nodes_can_be_parents() {
+        let mut dom = Dom::new();
+        let root = dom.insert(NodeData::new("Root"));
+        let mut panel = None;
+        let changes = dom
+            .transaction(|tx| {
+                let p = tx.insert_child(root, NodeData::new("Panel"));
+                tx.insert_child(p, NodeData::new("Button"));
+                panel = Some(p);
+            })
+            .unwrap();
+
+        let panel_id = changes.resolve(panel.unwrap()).unwrap();
+        assert_eq!(dom.children(panel_id).len(), 1);
+        assert_eq!(changes.inserted.len(), 2);
+    }
+
+    #[test]
+    fn updates_are_recorded_once() {
+        let (mut dom, _, items) = list_dom();
+        let target = items[1];
+        let changes = dom
+            .transaction(|tx| {
+                tx.add_class(target, "selected");
+                tx.set_visible(target, false);
+            })
+            .unwrap();
+
+        assert_eq!(changes.updated, vec![target]);
+        let data = dom.get(target).unwrap();
+        assert!(data.has_class("selected"));
+        assert!(!data.visible);
+    }
+
+    #[test]
+    fn insert_then_remove_cancels_out() {
+        let (mut dom, root, _) = list_dom();
+        let changes = dom
+            .transaction(|tx| {
+                let tmp = tx.insert_child(root, NodeData::new("Tmp"));
+                tx.add_class(tmp, "x");
+                tx.remove(tmp);
+            })
+            .unwrap();
+
+        assert!(changes.inserted.is_empty());
+        assert!(changes.removed.is_empty());
+        assert_eq!(dom.len(), 4);
+    }
+
+    #[test]
+    fn merged_changes_match_one_transaction() {
+        let (mut dom, root, items) = list_dom();
+        let mut changes = dom
+            .transaction(|tx| {
+                tx.insert_child(root, NodeData::new("Tmp"));
+                tx.add_class(items[0], "x");
+            })
+            .unwrap();
+        let tmp = changes.inserted[0];
+        let later = dom
+            .transaction(|tx| {
+                tx.remove(tmp);
+                tx.remove(items[0]);
+                tx.add_class(items[1], "y");
+            })
+            .unwrap();
+        changes.merge(later);
+
+        assert!(changes.inserted.is_empty());
+        assert_eq!(changes.removed, vec![items[0]]);
+        assert_eq!(changes.updated, vec![items[1]]);
+    }
+
+    #[test]
+    fn removal_reports_descendants() {
+        let (mut dom, root, items) = list_dom();
+        let child = dom.insert_child(items[0], NodeData::new("Label"));
+        let changes = dom.transaction(|tx| tx.remove(items[0])).unwrap();
+        assert_eq!(changes.removed, vec![items[0], child]);
+        assert_eq!(dom.children(root).len(), 2);
+    }
+
+    #[test]
+    fn missing_node_rejects_whole_batch() {
+        let (mut dom, root, items) = list_dom();
+        let gone = items[2];
+        dom.remove(gone);
+
+        let err = dom
+            .transaction(|tx| {
+                tx.insert_child(root, NodeData::new("Item"));
+                tx.add_class(gone, "x");
+            })
+            .unwrap_err();
+
+        assert_eq!(err, TransactionError::MissingNode { op: 1 });
+        assert_eq!(dom.children(root).len(), 2, "nothing applied");
+    }
+
+    #[test]
+    fn use_after_remove_is_rejected() {
+        let (mut dom, _, items) = list_dom();
+        let a = items[0];
+        let err = dom
+            .transaction(|tx| {
+                tx.remove(a);
+                tx.set_visible(a, false);
+            })
+            .unwrap_err();
+        assert_eq!(err, TransactionError::MissingNode { op: 1 });
+        assert!(dom.contains(a));
+    }
+
+    #[test]
+    fn reparent_cycle_is_rejected() {
+        let (mut dom, root, items) = list_dom();
+        let err = dom
+            .transaction(|tx| tx.reparent(root, items[0]))
+            .unwrap_err();
+        assert_eq!(err, TransactionError::Cycle { op: 0 });
+    }
+
+    #[test]
+    fn pending_node_under_removed_ancestor_is_rejected() {
+        let (mut dom, root, items) = list_dom();
+        let err = dom
+            .transaction(|tx| {
+                let p = tx.insert_child(items[0], NodeData::new("Label"));
+                tx.remove(items[0]);
+                tx.add_class(p, "x");
+            })
+            .unwrap_err();
+        assert_eq!(err, TransactionError::MissingNode { op: 2 });
+        assert_eq!(dom.children(root).len(), 3, "nothing applied");
+    }
+
+    #[test]
+    fn reparent_under_pending_descendant_is_rejected() {
+        let (mut dom, root, items) = list_dom();
+        let err = dom
+            .transaction(|tx| {
+                let p = tx.insert_child(items[0], NodeData::new("Label"));
+                tx.reparent(items[0], p);
+            })
+            .unwrap_err();
+        assert_eq!(err, TransactionError::Cycle { op: 1 });
+
+        // Moves earlier in the batch count too: a is under b once moved.
+        let err = dom
+            .transaction(|tx| {
+                tx.reparent(items[1], items[0]);
+                tx.reparent(items[0], items[1]);
+            })
+            .unwrap_err();
+        assert_eq!(err, TransactionError::Cycle { op: 1 });
+        assert_eq!(dom.ancestors(items[0]), vec![root]);
+    }
+
+    #[test]
+    fn reparent_marks_updated() {
+        let (mut dom, _, items) = list_dom();
+        let changes = dom
+            .transaction(|tx| tx.reparent(items[2], items[0]))
+            .unwrap();
+        assert_eq!(changes.updated, vec![items[2]]);
+        assert_eq!(dom.parent(items[2]), Some(items[0]));
+    }
+}
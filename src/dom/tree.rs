@@ -1,6 +1,6 @@
 //! Tree operations: insert, remove, reparent, walk.
 
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 
 use slotmap::{SecondaryMap, SlotMap};
 
@@ -18,6 +18,10 @@ pub struct Dom {
     children: SecondaryMap<NodeId, Vec<NodeId>>,
     parent: SecondaryMap<NodeId, NodeId>,
     root: Option<NodeId>,
+    /// Bumped whenever nodes are inserted, removed or moved.
+    structure_version: u64,
+    /// Generated ids handed out so far, per widget type.
+    auto_ids: HashMap<String, u32>,
 }
 
 impl Dom {
@@ -28,15 +32,19 @@ impl Dom {
             children: SecondaryMap::new(),
             parent: SecondaryMap::new(),
             root: None,
+            structure_version: 0,
+            auto_ids: HashMap::new(),
         }
     }
 
     /// Insert a root-level node (no parent).
     ///
     /// If no root has been set yet, this node becomes the root.
-    pub fn insert(&mut self, data: NodeData) -> NodeId {
+    pub fn insert(&mut self, mut data: NodeData) -> NodeId {
+        self.assign_auto_id(&mut data);
         let id = self.nodes.insert(data);
         self.children.insert(id, Vec::new());
+        self.structure_version += 1;
         if self.root.is_none() {
             self.root = Some(id);
         }
@@ -48,14 +56,16 @@ impl Dom {
     /// # Panics
     ///
     /// Panics (debug) if `parent` does not exist in the tree.
-    pub fn insert_child(&mut self, parent: NodeId, data: NodeData) -> NodeId {
+    pub fn insert_child(&mut self, parent: NodeId, mut data: NodeData) -> NodeId {
         debug_assert!(
             self.nodes.contains_key(parent),
             "parent node does not exist"
         );
+        self.assign_auto_id(&mut data);
         let id = self.nodes.insert(data);
         self.children.insert(id, Vec::new());
         self.parent.insert(id, parent);
+        self.structure_version += 1;
         self.children
             .get_mut(parent)
             .expect("parent must have children vec")
@@ -63,6 +73,15 @@ impl Dom {
         id
     }
 
+    /// Give `data` the next generated id for its widget type, e.g. the
+    /// third `DataTable` inserted becomes `data-table-3`. Numbers are never
+    /// reused, so an id keeps pointing at the same node.
+    fn assign_auto_id(&mut self, data: &mut NodeData) {
+        let count = self.auto_ids.entry(data.widget_type.clone()).or_insert(0);
+        *count += 1;
+        data.auto_id = Some(format!("{}-{count}", kebab_case(&data.widget_type)));
+    }
+
     /// Remove a node and all its descendants recursively.
     ///
     /// Returns the `NodeData` for the removed node, or `None` if it didn't exist.
@@ -70,6 +89,7 @@ impl Dom {
         if !self.nodes.contains_key(id) {
             return None;
         }
+        self.structure_version += 1;
 
         // Detach from parent's children list.
         if let Some(parent_id) = self.parent.remove(id) {
@@ -120,6 +140,8 @@ impl Dom {
             "new_parent does not exist"
         );
 
+        self.structure_version += 1;
+
         // Detach from old parent.
         if let Some(old_parent) = self.parent.remove(node) {
             if let Some(siblings) = self.children.get_mut(old_parent) {
@@ -181,6 +203,14 @@ impl Dom {
     /// Explicitly set the root node.
     pub fn set_root(&mut self, id: NodeId) {
         self.root = Some(id);
+        self.structure_version += 1;
+    }
+
+    /// A counter that changes whenever nodes are inserted, removed or moved,
+    /// or the root changes, for caches derived from the tree's shape. Edits
+    /// to node data leave it alone.
+    pub fn structure_version(&self) -> u64 {
+        self.structure_version
     }
 
     /// Number of nodes in the DOM.
@@ -240,6 +270,18 @@ impl Default for Dom {
     }
 }
 
+/// `DataTable` -> `data-table`.
+fn kebab_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, ch) in name.chars().enumerate() {
+        if ch.is_uppercase() && i > 0 {
+            out.push('-');
+        }
+        out.extend(ch.to_lowercase());
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -262,6 +304,24 @@ mod tests {
         (dom, root, a, b, c, d)
     }
 
+    #[test]
+    fn auto_ids_count_per_type_and_are_not_reused() {
+        let mut dom = Dom::new();
+        let root = dom.insert(NodeData::new("Container"));
+        let first = dom.insert_child(root, NodeData::new("DataTable"));
+        let second = dom.insert_child(root, NodeData::new("DataTable").with_id("orders"));
+        assert_eq!(dom.get(root).unwrap().widget_id(), Some("container-1"));
+        assert_eq!(dom.get(first).unwrap().widget_id(), Some("data-table-1"));
+        // Explicit ids win, but the generated one is still kept.
+        let second = dom.get(second).unwrap();
+        assert_eq!(second.widget_id(), Some("orders"));
+        assert_eq!(second.auto_id.as_deref(), Some("data-table-2"));
+
+        dom.remove(first);
+        let third = dom.insert_child(root, NodeData::new("DataTable"));
+        assert_eq!(dom.get(third).unwrap().widget_id(), Some("data-table-3"));
+    }
+
     #[test]
     fn insert_sets_root() {
         let mut dom = Dom::new();
@@ -420,4 +480,23 @@ mod tests {
         assert!(dom.is_empty());
         assert_eq!(dom.root(), None);
     }
+
+    #[test]
+    fn structure_version_tracks_shape_only() {
+        let (mut dom, root, a, b, c, _d) = build_tree();
+        let version = dom.structure_version();
+        dom.get_mut(a).unwrap().add_class("x");
+        assert_eq!(dom.structure_version(), version);
+
+        dom.reparent(c, b);
+        let moved = dom.structure_version();
+        assert_ne!(moved, version);
+        dom.remove(a);
+        assert_ne!(dom.structure_version(), moved);
+        let removed = dom.structure_version();
+        dom.remove(a);
+        assert_eq!(dom.structure_version(), removed);
+        dom.insert_child(root, NodeData::new("E"));
+        assert_ne!(dom.structure_version(), removed);
+    }
 }
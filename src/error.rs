@@ -0,0 +1,129 @@
+//! Crate-wide error hierarchy.
+//!
+//! Fallible public operations return one of the subsystem errors
+//! ([`LayoutError`], [`RenderError`], [`CssError`], [`DriverError`],
+//! [`LayoutFileError`], [`RegistryError`]); the
+//! top-level [`Error`] wraps all of them so applications can use `?` across
+//! subsystems and match on the source when they care.
+
+use std::io;
+
+use crate::css::parser::ParseError;
+use crate::css::properties::PropertyError;
+use crate::widget::layout_file::LayoutFileError;
+use crate::widget::registry::RegistryError;
+
+/// Shorthand for results carrying the crate [`Error`].
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+// ---------------------------------------------------------------------------
+// Error
+// ---------------------------------------------------------------------------
+
+/// Any error gilt-tui can return.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Layout(#[from] LayoutError),
+    #[error(transparent)]
+    Render(#[from] RenderError),
+    #[error(transparent)]
+    Css(#[from] CssError),
+    #[error(transparent)]
+    Driver(#[from] DriverError),
+    #[error(transparent)]
+    LayoutFile(#[from] LayoutFileError),
+    #[error(transparent)]
+    Registry(#[from] RegistryError),
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Driver(DriverError::Io(error))
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Error::Css(CssError::Parse(error))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Subsystem errors
+// ---------------------------------------------------------------------------
+
+/// Errors from the layout engine.
+#[derive(Debug, thiserror::Error)]
+pub enum LayoutError {
+    /// The underlying taffy tree rejected an operation.
+    #[error("layout tree error: {0}")]
+    Taffy(#[from] taffy::TaffyError),
+}
+
+/// Errors from the compositor.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RenderError {
+    /// A cell coordinate fell outside the screen buffer.
+    #[error("cell ({x}, {y}) is outside the {width}x{height} screen")]
+    OutOfBounds {
+        x: u16,
+        y: u16,
+        width: u16,
+        height: u16,
+    },
+}
+
+/// Errors from parsing or applying CSS.
+#[derive(Debug, thiserror::Error)]
+pub enum CssError {
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    #[error(transparent)]
+    Property(#[from] PropertyError),
+}
+
+/// Errors from the terminal driver.
+#[derive(Debug, thiserror::Error)]
+pub enum DriverError {
+    /// Writing to or querying the terminal failed.
+    #[error("terminal I/O failed: {0}")]
+    Io(#[from] io::Error),
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::css::parser::parse_css;
+
+    #[test]
+    fn io_errors_become_driver_errors() {
+        let error: Error = io::Error::other("gone").into();
+        assert!(matches!(error, Error::Driver(DriverError::Io(_))));
+        assert_eq!(error.to_string(), "terminal I/O failed: gone");
+    }
+
+    #[test]
+    fn css_errors_propagate_with_question_mark() {
+        fn load() -> Result<()> {
+            parse_css("Button { color: red")?;
+            Ok(())
+        }
+        assert!(matches!(load(), Err(Error::Css(CssError::Parse(_)))));
+    }
+
+    #[test]
+    fn render_error_message() {
+        let error = RenderError::OutOfBounds {
+            x: 9,
+            y: 1,
+            width: 4,
+            height: 2,
+        };
+        assert_eq!(error.to_string(), "cell (9, 1) is outside the 4x2 screen");
+    }
+}
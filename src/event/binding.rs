@@ -2,17 +2,39 @@
 //!
 //! [`KeyBindingRegistry`] maps key+modifier combinations to [`BindingAction`]s.
 //! The `with_defaults()` constructor installs standard bindings (Ctrl+C -> Quit, etc.).
+//! Rebinding a key to a different action is recorded as a [`BindingConflict`]
+//! so apps can surface accidental overrides instead of silently losing one.
+//!
+//! Bindings can also be written as strings: `"ctrl+shift+p"`, `"f5"`, or a
+//! space-separated chord like `"g g"` (see [`keys`](super::keys)). Besides the
+//! global bindings, the registry holds bindings scoped to DOM nodes, and
+//! widgets declare their own table through `Widget::bindings`. A key press is
+//! resolved along the focus chain: the focused node's bindings first, then
+//! each ancestor's, then the global ones, so the binding closest to focus
+//! wins a conflict. [`KeyBindingRegistry::active_bindings`] lists what is
+//! reachable for the current focus, e.g. for a footer or help screen.
+//!
+//! ```ignore
+//! app.bindings.bind_str("g g", BindingAction::Custom("top".into()))?;
+//! app.bindings.bind_node(editor, Binding::parse("ctrl+s", save)?.with_description("Save"));
+//! ```
 
 use std::collections::HashMap;
+use std::fmt;
 
 use super::input::{Key, KeyEvent, Modifiers};
-use super::message::Message;
+pub use super::keys::{parse_key, parse_keys, KeyParseError};
+use super::keys::{display_keys, KeySequence};
+use super::message::{self, Message};
+use crate::dom::node::NodeId;
+use crate::dom::tree::Dom;
 
 // ---------------------------------------------------------------------------
 // BindingAction
 // ---------------------------------------------------------------------------
 
 /// Action to take when a key binding is matched.
+#[derive(Clone)]
 pub enum BindingAction {
     /// Quit the application.
     Quit,
@@ -20,6 +42,8 @@ pub enum BindingAction {
     FocusNext,
     /// Move focus to the previous focusable widget.
     FocusPrevious,
+    /// Open the command palette.
+    CommandPalette,
     /// A named custom action.
     Custom(String),
     /// Produce a message via a factory function.
@@ -32,12 +56,156 @@ impl std::fmt::Debug for BindingAction {
             Self::Quit => write!(f, "Quit"),
             Self::FocusNext => write!(f, "FocusNext"),
             Self::FocusPrevious => write!(f, "FocusPrevious"),
+            Self::CommandPalette => write!(f, "CommandPalette"),
             Self::Custom(name) => write!(f, "Custom({name:?})"),
             Self::Message(_) => write!(f, "Message(<fn>)"),
         }
     }
 }
 
+impl BindingAction {
+    /// Whether `other` does the same thing. Message factories can't be
+    /// compared, so two of them never count as the same.
+    fn same_as(&self, other: &BindingAction) -> bool {
+        match (self, other) {
+            (Self::Quit, Self::Quit)
+            | (Self::FocusNext, Self::FocusNext)
+            | (Self::FocusPrevious, Self::FocusPrevious)
+            | (Self::CommandPalette, Self::CommandPalette) => true,
+            (Self::Custom(a), Self::Custom(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// BindingConflict
+// ---------------------------------------------------------------------------
+
+/// A key that was bound again to a different action, replacing the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BindingConflict {
+    /// Keys typed before [`key`](Self::key) in a chord; empty for single keys.
+    pub prefix: Vec<KeyEvent>,
+    /// The contested key.
+    pub key: KeyEvent,
+    /// The action that was replaced, as its debug text.
+    pub previous: String,
+    /// The action now bound, as its debug text.
+    pub replacement: String,
+}
+
+impl fmt::Display for BindingConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for key in &self.prefix {
+            write!(f, "{key} ")?;
+        }
+        write!(
+            f,
+            "{}: {} replaced by {}",
+            self.key, self.previous, self.replacement
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Binding / WidgetBinding
+// ---------------------------------------------------------------------------
+
+/// A key sequence bound to an action, with an optional description for
+/// footers and help screens.
+#[derive(Debug, Clone)]
+pub struct Binding {
+    /// The keys to press, in order; more than one makes a chord.
+    pub keys: Vec<KeyEvent>,
+    pub action: BindingAction,
+    pub description: Option<String>,
+}
+
+impl Binding {
+    /// Bind `keys` to `action`.
+    pub fn new(keys: Vec<KeyEvent>, action: BindingAction) -> Self {
+        Self {
+            keys,
+            action,
+            description: None,
+        }
+    }
+
+    /// Bind the keys written in `text` (see [`parse_keys`]) to `action`.
+    pub fn parse(text: &str, action: BindingAction) -> Result<Self, KeyParseError> {
+        Ok(Self::new(parse_keys(text)?, action))
+    }
+
+    /// Set the description (builder pattern).
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+/// An entry in a widget's static bindings table: keys, custom action name,
+/// description.
+///
+/// ```ignore
+/// const BINDINGS: &[WidgetBinding] = &[
+///     WidgetBinding::new("ctrl+s", "save", "Save"),
+///     WidgetBinding::new("g g", "top", "Go to top"),
+/// ];
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WidgetBinding {
+    pub keys: &'static str,
+    /// Sent as [`BindingAction::Custom`] from the widget's node.
+    pub action: &'static str,
+    pub description: &'static str,
+}
+
+impl WidgetBinding {
+    /// Create a table entry.
+    pub const fn new(keys: &'static str, action: &'static str, description: &'static str) -> Self {
+        Self {
+            keys,
+            action,
+            description,
+        }
+    }
+
+    /// The entry as a [`Binding`], or the error in its key string.
+    pub fn to_binding(&self) -> Result<Binding, KeyParseError> {
+        let action = BindingAction::Custom(self.action.to_owned());
+        Ok(Binding::parse(self.keys, action)?.with_description(self.description))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Resolution
+// ---------------------------------------------------------------------------
+
+/// The outcome of [`KeyBindingRegistry::press`].
+#[derive(Debug, Clone)]
+pub enum KeyResolution {
+    /// A binding matched. `node` is the node whose scope it came from, or
+    /// `None` for a global binding.
+    Action {
+        action: BindingAction,
+        node: Option<NodeId>,
+    },
+    /// The keys so far start a chord; wait for the next one.
+    Pending,
+    /// Nothing is bound to the key.
+    Unbound,
+}
+
+/// A binding reachable from the current focus, as listed by
+/// [`KeyBindingRegistry::active_bindings`].
+#[derive(Debug, Clone)]
+pub struct ActiveBinding {
+    /// The node whose scope the binding belongs to; `None` for global.
+    pub node: Option<NodeId>,
+    pub binding: Binding,
+}
+
 // ---------------------------------------------------------------------------
 // KeyBinding
 // ---------------------------------------------------------------------------
@@ -50,21 +218,84 @@ pub struct KeyBinding {
     pub action: BindingAction,
 }
 
+// ---------------------------------------------------------------------------
+// Keymap definitions
+// ---------------------------------------------------------------------------
+
+/// A binding action that can be written in a keymap file.
+///
+/// Mirrors [`BindingAction`] minus `Message`, whose factory only exists in code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum ActionDef {
+    Quit,
+    FocusNext,
+    FocusPrevious,
+    CommandPalette,
+    Custom(String),
+}
+
+impl From<ActionDef> for BindingAction {
+    fn from(def: ActionDef) -> Self {
+        match def {
+            ActionDef::Quit => BindingAction::Quit,
+            ActionDef::FocusNext => BindingAction::FocusNext,
+            ActionDef::FocusPrevious => BindingAction::FocusPrevious,
+            ActionDef::CommandPalette => BindingAction::CommandPalette,
+            ActionDef::Custom(name) => BindingAction::Custom(name),
+        }
+    }
+}
+
+/// A key binding as loaded from a keymap file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyBindingDef {
+    /// The keys as a descriptor, e.g. `"ctrl+s"` or `"g g"`.
+    pub keys: KeySequence,
+    pub action: ActionDef,
+}
+
+/// A described binding as shown in a footer: its keys and what they do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BindingHint {
+    /// The keys as shown in shortcut hints, e.g. `Ctrl+S` or `G G`.
+    pub keys: String,
+    pub description: String,
+}
+
+impl fmt::Display for BindingHint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.keys, self.description)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // KeyBindingRegistry
 // ---------------------------------------------------------------------------
 
-/// Registry of key bindings, mapping (Key, Modifiers) -> BindingAction.
+/// Registry of key bindings: global ones plus bindings scoped to DOM nodes.
 #[derive(Debug)]
 pub struct KeyBindingRegistry {
-    bindings: HashMap<(Key, Modifiers), BindingAction>,
+    bindings: Vec<Binding>,
+    /// The active app mode's bindings, searched before `bindings`.
+    mode_bindings: Vec<Binding>,
+    scoped: HashMap<NodeId, Vec<Binding>>,
+    /// Keys of a chord typed so far.
+    pending: Vec<KeyEvent>,
+    conflicts: Vec<BindingConflict>,
 }
 
 impl KeyBindingRegistry {
     /// Create an empty registry.
     pub fn new() -> Self {
         Self {
-            bindings: HashMap::new(),
+            bindings: Vec::new(),
+            mode_bindings: Vec::new(),
+            scoped: HashMap::new(),
+            pending: Vec::new(),
+            conflicts: Vec::new(),
         }
     }
 
@@ -74,49 +305,277 @@ impl KeyBindingRegistry {
     /// - `Ctrl+C` -> Quit
     /// - `Tab` -> FocusNext
     /// - `BackTab` (Shift+Tab) -> FocusPrevious
+    /// - `Ctrl+P` -> CommandPalette
+    /// - `F9` -> [`ToggleMouseCapture`](crate::event::message::ToggleMouseCapture),
+    ///   so mouse reporting can be turned off for native text selection
     pub fn with_defaults() -> Self {
         let mut registry = Self::new();
-        registry.bind(Key::Char('c'), Modifiers::CTRL, BindingAction::Quit);
-        registry.bind(Key::Tab, Modifiers::NONE, BindingAction::FocusNext);
-        registry.bind(
-            Key::BackTab,
-            Modifiers::NONE,
-            BindingAction::FocusPrevious,
-        );
+        let defaults = [
+            ("ctrl+c", BindingAction::Quit),
+            ("tab", BindingAction::FocusNext),
+            ("backtab", BindingAction::FocusPrevious),
+            ("ctrl+p", BindingAction::CommandPalette),
+            (
+                "f9",
+                BindingAction::Message(|| Box::new(message::ToggleMouseCapture)),
+            ),
+        ];
+        for (keys, action) in defaults {
+            registry
+                .bind_str(keys, action)
+                .expect("default bindings parse");
+        }
         registry
     }
 
     /// Register a key binding.
     ///
-    /// If a binding already exists for this key+modifier combination, it is replaced.
+    /// If a binding already exists for this key+modifier combination, it is
+    /// replaced; a different action replaced this way is recorded in
+    /// [`conflicts`](Self::conflicts). Use [`unbind`](Self::unbind) first to
+    /// override a key on purpose.
     pub fn bind(&mut self, key: Key, modifiers: Modifiers, action: BindingAction) {
-        self.bindings.insert((key, modifiers), action);
+        self.add(Binding::new(vec![KeyEvent::new(key, modifiers)], action));
+    }
+
+    /// Register the keys written in `keys` (see [`parse_keys`]), e.g.
+    /// `"ctrl+shift+p"` or the chord `"g g"`.
+    pub fn bind_str(&mut self, keys: &str, action: BindingAction) -> Result<(), KeyParseError> {
+        self.add(Binding::parse(keys, action)?);
+        Ok(())
+    }
+
+    /// Register a global binding, replacing (and recording a conflict with)
+    /// any binding for the same keys, like [`bind`](Self::bind).
+    pub fn add(&mut self, binding: Binding) {
+        if let Some(i) = self.bindings.iter().position(|b| b.keys == binding.keys) {
+            let previous = self.bindings.remove(i);
+            if !previous.action.same_as(&binding.action) {
+                let (key, prefix) = binding.keys.split_last().expect("bindings have keys");
+                self.conflicts.push(BindingConflict {
+                    prefix: prefix.to_vec(),
+                    key: *key,
+                    previous: format!("{:?}", previous.action),
+                    replacement: format!("{:?}", binding.action),
+                });
+            }
+        }
+        self.bindings.push(binding);
+    }
+
+    /// Register every binding in `defs`, e.g. a keymap loaded from a config
+    /// file. Later definitions replace earlier ones for the same key.
+    pub fn bind_defs(&mut self, defs: impl IntoIterator<Item = KeyBindingDef>) {
+        for def in defs {
+            self.add(Binding::new(def.keys.into_keys(), def.action.into()));
+        }
     }
 
     /// Remove a key binding.
     ///
     /// Returns the removed action, if any.
     pub fn unbind(&mut self, key: Key, modifiers: Modifiers) -> Option<BindingAction> {
-        self.bindings.remove(&(key, modifiers))
+        self.unbind_keys(&[KeyEvent::new(key, modifiers)])
     }
 
-    /// Look up the action for a given key event.
+    /// Remove the global binding for exactly `keys`, returning its action.
+    pub fn unbind_keys(&mut self, keys: &[KeyEvent]) -> Option<BindingAction> {
+        let i = self.bindings.iter().position(|b| b.keys == keys)?;
+        Some(self.bindings.remove(i).action)
+    }
+
+    /// Look up the global action for a given key event.
     ///
-    /// First tries exact match of key + modifiers. Returns `None` if no
-    /// matching binding is found.
+    /// Only single-key bindings match; chords and node-scoped bindings go
+    /// through [`press`](Self::press). Returns `None` if no matching binding
+    /// is found.
     pub fn resolve(&self, event: &KeyEvent) -> Option<&BindingAction> {
-        self.bindings.get(&(event.code, event.modifiers))
+        self.bindings
+            .iter()
+            .find(|b| b.keys.len() == 1 && b.keys[0] == *event)
+            .map(|b| &b.action)
+    }
+
+    /// The key bound to the custom action `name`, for showing as a shortcut
+    /// hint. When several keys are bound, the one with the fewest modifiers
+    /// wins, ties broken by its display text.
+    pub fn key_for(&self, name: &str) -> Option<KeyEvent> {
+        self.bindings
+            .iter()
+            .filter(|b| b.keys.len() == 1)
+            .filter(|b| matches!(&b.action, BindingAction::Custom(n) if n == name))
+            .map(|b| b.keys[0])
+            .min_by_key(|event| (event.modifiers.0.count_ones(), event.to_string()))
+    }
+
+    /// Keys rebound to a different action so far, oldest first.
+    pub fn conflicts(&self) -> &[BindingConflict] {
+        &self.conflicts
     }
 
-    /// Number of registered bindings.
+    /// Forget the recorded conflicts, e.g. once they have been reported.
+    pub fn clear_conflicts(&mut self) {
+        self.conflicts.clear();
+    }
+
+    /// Number of registered global bindings.
     pub fn len(&self) -> usize {
         self.bindings.len()
     }
 
-    /// Whether the registry has no bindings.
+    /// Whether the registry has no global bindings.
     pub fn is_empty(&self) -> bool {
         self.bindings.is_empty()
     }
+
+    /// Replace the active app mode's bindings. They are global but searched
+    /// before the other global bindings, so a mode can override them
+    /// without a recorded conflict.
+    pub fn set_mode_bindings(&mut self, bindings: Vec<Binding>) {
+        self.mode_bindings = bindings;
+        self.pending.clear();
+    }
+
+    /// The active app mode's bindings.
+    pub fn mode_bindings(&self) -> &[Binding] {
+        &self.mode_bindings
+    }
+
+    // -- Node scopes --------------------------------------------------------
+
+    /// Bind keys while `node` or one of its descendants has focus. Replaces a
+    /// binding of `node` for the same keys; node bindings take precedence
+    /// over the node's widget table.
+    pub fn bind_node(&mut self, node: NodeId, binding: Binding) {
+        let scope = self.scoped.entry(node).or_default();
+        scope.retain(|b| b.keys != binding.keys);
+        scope.push(binding);
+    }
+
+    /// Remove every binding scoped to `node`, e.g. once it is unmounted.
+    pub fn unbind_node(&mut self, node: NodeId) {
+        self.scoped.remove(&node);
+    }
+
+    /// The bindings registered for `node` with [`bind_node`](Self::bind_node).
+    pub fn node_bindings(&self, node: NodeId) -> &[Binding] {
+        self.scoped.get(&node).map_or(&[], Vec::as_slice)
+    }
+
+    /// The bindings in effect in `node`'s scope: its registered bindings,
+    /// then its widget's table. Table entries whose keys don't parse are
+    /// skipped.
+    fn scope(&self, node: NodeId, dom: &Dom) -> Vec<Binding> {
+        let mut bindings = self.node_bindings(node).to_vec();
+        if let Some(data) = dom.get(node) {
+            bindings.extend(data.bindings.iter().filter_map(|b| b.to_binding().ok()));
+        }
+        bindings
+    }
+
+    /// Every scope from `focus` outwards: the focused node, its ancestors,
+    /// the mode's bindings, then the global scope (both `None`).
+    fn scopes(&self, focus: Option<NodeId>, dom: &Dom) -> Vec<(Option<NodeId>, Vec<Binding>)> {
+        let mut chain = Vec::new();
+        if let Some(focus) = focus.filter(|&f| dom.contains(f)) {
+            chain.push(focus);
+            chain.extend(dom.ancestors(focus));
+        }
+        let mut scopes: Vec<_> = chain
+            .into_iter()
+            .map(|node| (Some(node), self.scope(node, dom)))
+            .collect();
+        scopes.push((None, self.mode_bindings.clone()));
+        scopes.push((None, self.bindings.clone()));
+        scopes
+    }
+
+    // -- Resolution ---------------------------------------------------------
+
+    /// Feed one key press through the focus chain.
+    ///
+    /// Scopes are searched from `focus` outwards, then the mode's and the
+    /// global bindings; the first scope with a binding for the keys typed so
+    /// far, or a chord starting with them, decides. A key that continues no
+    /// chord drops the chord and is looked up on its own.
+    pub fn press(&mut self, event: &KeyEvent, focus: Option<NodeId>, dom: &Dom) -> KeyResolution {
+        let scopes = self.scopes(focus, dom);
+        let mut typed = std::mem::take(&mut self.pending);
+        let retry = !typed.is_empty();
+        typed.push(*event);
+        if let Some(resolution) = self.lookup(&scopes, typed) {
+            return resolution;
+        }
+        if retry {
+            if let Some(resolution) = self.lookup(&scopes, vec![*event]) {
+                return resolution;
+            }
+        }
+        KeyResolution::Unbound
+    }
+
+    fn lookup(
+        &mut self,
+        scopes: &[(Option<NodeId>, Vec<Binding>)],
+        typed: Vec<KeyEvent>,
+    ) -> Option<KeyResolution> {
+        for (node, bindings) in scopes {
+            if let Some(binding) = bindings.iter().find(|b| b.keys == typed) {
+                return Some(KeyResolution::Action {
+                    action: binding.action.clone(),
+                    node: *node,
+                });
+            }
+            if bindings
+                .iter()
+                .any(|b| b.keys.len() > typed.len() && b.keys.starts_with(&typed))
+            {
+                self.pending = typed;
+                return Some(KeyResolution::Pending);
+            }
+        }
+        None
+    }
+
+    /// The keys of a chord typed so far.
+    pub fn pending_keys(&self) -> &[KeyEvent] {
+        &self.pending
+    }
+
+    /// Abandon a chord in progress.
+    pub fn cancel_chord(&mut self) {
+        self.pending.clear();
+    }
+
+    /// The bindings reachable with `focus` focused, closest scope first.
+    /// Bindings shadowed by one for the same keys in a closer scope are
+    /// left out.
+    pub fn active_bindings(&self, focus: Option<NodeId>, dom: &Dom) -> Vec<ActiveBinding> {
+        let mut active: Vec<ActiveBinding> = Vec::new();
+        for (node, bindings) in self.scopes(focus, dom) {
+            for binding in bindings {
+                if !active.iter().any(|a| a.binding.keys == binding.keys) {
+                    active.push(ActiveBinding { node, binding });
+                }
+            }
+        }
+        active
+    }
+
+    /// Hints for the active bindings that have a description, closest scope
+    /// first; undescribed bindings are left out.
+    pub fn hints(&self, focus: Option<NodeId>, dom: &Dom) -> Vec<BindingHint> {
+        self.active_bindings(focus, dom)
+            .into_iter()
+            .filter_map(|active| {
+                let description = active.binding.description?;
+                Some(BindingHint {
+                    keys: display_keys(&active.binding.keys),
+                    description,
+                })
+            })
+            .collect()
+    }
 }
 
 impl Default for KeyBindingRegistry {
@@ -132,6 +591,7 @@ impl Default for KeyBindingRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dom::node::NodeData;
 
     // ── Construction ─────────────────────────────────────────────────
 
@@ -149,13 +609,35 @@ mod tests {
     }
 
     #[test]
-    fn with_defaults_has_three_bindings() {
+    fn with_defaults_has_five_bindings() {
         let reg = KeyBindingRegistry::with_defaults();
-        assert_eq!(reg.len(), 3);
+        assert_eq!(reg.len(), 5);
     }
 
     // ── Bind / Unbind ────────────────────────────────────────────────
 
+    #[test]
+    fn bind_defs_registers_named_actions() {
+        let mut reg = KeyBindingRegistry::new();
+        reg.bind_defs([
+            KeyBindingDef {
+                keys: "ctrl+q".parse().unwrap(),
+                action: ActionDef::Quit,
+            },
+            KeyBindingDef {
+                keys: "f1".parse().unwrap(),
+                action: ActionDef::Custom("help".into()),
+            },
+            KeyBindingDef {
+                keys: "g h".parse().unwrap(),
+                action: ActionDef::Custom("home".into()),
+            },
+        ]);
+        assert_eq!(reg.len(), 3);
+        let help = reg.resolve(&KeyEvent::new(Key::F(1), Modifiers::NONE));
+        assert!(matches!(help, Some(BindingAction::Custom(name)) if name == "help"));
+    }
+
     #[test]
     fn bind_and_resolve() {
         let mut reg = KeyBindingRegistry::new();
@@ -232,6 +714,130 @@ mod tests {
         }
     }
 
+    #[test]
+    fn rebinding_to_another_action_is_a_conflict() {
+        let mut reg = KeyBindingRegistry::with_defaults();
+        assert!(reg.conflicts().is_empty());
+        reg.bind(Key::Char('s'), Modifiers::CTRL, BindingAction::Custom("save".into()));
+        reg.bind(Key::Char('s'), Modifiers::CTRL, BindingAction::Custom("save".into()));
+        assert!(reg.conflicts().is_empty());
+
+        reg.bind(Key::Char('c'), Modifiers::CTRL, BindingAction::Custom("copy".into()));
+        assert_eq!(reg.conflicts().len(), 1);
+        assert_eq!(
+            reg.conflicts()[0].to_string(),
+            "Ctrl+C: Quit replaced by Custom(\"copy\")"
+        );
+
+        // Deliberate overrides unbind first.
+        reg.unbind(Key::Tab, Modifiers::NONE);
+        reg.bind(Key::Tab, Modifiers::NONE, BindingAction::Custom("indent".into()));
+        assert_eq!(reg.conflicts().len(), 1);
+        reg.clear_conflicts();
+        assert!(reg.conflicts().is_empty());
+    }
+
+    #[test]
+    fn key_for_finds_the_simplest_binding() {
+        let mut reg = KeyBindingRegistry::new();
+        let save = || BindingAction::Custom("save".into());
+        reg.bind(Key::Char('s'), Modifiers::CTRL | Modifiers::SHIFT, save());
+        reg.bind(Key::Char('s'), Modifiers::CTRL, save());
+        reg.bind(Key::Char('w'), Modifiers::CTRL, save());
+        assert_eq!(
+            reg.key_for("save"),
+            Some(KeyEvent::new(Key::Char('s'), Modifiers::CTRL))
+        );
+        assert_eq!(reg.key_for("open"), None);
+    }
+
+    // ── Key strings and chords ───────────────────────────────────────
+
+    fn custom(name: &str) -> BindingAction {
+        BindingAction::Custom(name.into())
+    }
+
+    fn action_name(resolution: KeyResolution) -> Option<(String, Option<NodeId>)> {
+        match resolution {
+            KeyResolution::Action {
+                action: BindingAction::Custom(name),
+                node,
+            } => Some((name, node)),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn chords_wait_for_their_next_key() {
+        let dom = Dom::new();
+        let mut reg = KeyBindingRegistry::new();
+        reg.bind_str("g g", custom("top")).unwrap();
+        reg.bind_str("x", custom("cut")).unwrap();
+        let g = KeyEvent::new(Key::Char('g'), Modifiers::NONE);
+        let x = KeyEvent::new(Key::Char('x'), Modifiers::NONE);
+
+        assert!(matches!(reg.press(&g, None, &dom), KeyResolution::Pending));
+        assert_eq!(reg.pending_keys(), [g]);
+        assert_eq!(action_name(reg.press(&g, None, &dom)), Some(("top".into(), None)));
+        assert!(reg.pending_keys().is_empty());
+
+        // A key that breaks the chord is looked up on its own.
+        reg.press(&g, None, &dom);
+        assert_eq!(action_name(reg.press(&x, None, &dom)), Some(("cut".into(), None)));
+        reg.press(&g, None, &dom);
+        reg.cancel_chord();
+        assert!(matches!(reg.press(&x, None, &dom), KeyResolution::Action { .. }));
+    }
+
+    #[test]
+    fn closest_scope_to_focus_wins() {
+        static TABLE: &[WidgetBinding] = &[
+            WidgetBinding::new("ctrl+s", "save-doc", "Save"),
+            WidgetBinding::new("ctrl+f", "find", "Find"),
+        ];
+        let mut dom = Dom::new();
+        let root = dom.insert(NodeData::new("Screen"));
+        let mut editor_data = NodeData::new("Editor");
+        editor_data.bindings = TABLE;
+        let editor = dom.insert_child(root, editor_data);
+        let input = dom.insert_child(editor, NodeData::new("Input"));
+
+        let mut reg = KeyBindingRegistry::new();
+        reg.bind_str("ctrl+s", custom("save-all")).unwrap();
+        reg.bind_str("ctrl+q", BindingAction::Quit).unwrap();
+        reg.bind_node(input, Binding::parse("ctrl+f", custom("find-in-input")).unwrap());
+
+        let ctrl = |c| KeyEvent::new(Key::Char(c), Modifiers::CTRL);
+        let save = reg.press(&ctrl('s'), Some(input), &dom);
+        assert_eq!(action_name(save), Some(("save-doc".into(), Some(editor))));
+        let find = reg.press(&ctrl('f'), Some(input), &dom);
+        assert_eq!(action_name(find), Some(("find-in-input".into(), Some(input))));
+        let save = reg.press(&ctrl('s'), Some(root), &dom);
+        assert_eq!(action_name(save), Some(("save-all".into(), None)));
+
+        let active: Vec<(String, Option<NodeId>)> = reg
+            .active_bindings(Some(input), &dom)
+            .into_iter()
+            .map(|a| (a.binding.keys[0].to_string(), a.node))
+            .collect();
+        assert_eq!(
+            active,
+            [
+                ("Ctrl+F".to_owned(), Some(input)),
+                ("Ctrl+S".to_owned(), Some(editor)),
+                ("Ctrl+Q".to_owned(), None),
+            ]
+        );
+        let hints: Vec<String> = reg
+            .hints(Some(input), &dom)
+            .iter()
+            .map(|h| h.to_string())
+            .collect();
+        assert_eq!(hints, ["Ctrl+S Save"]);
+        reg.unbind_node(input);
+        assert!(reg.node_bindings(input).is_empty());
+    }
+
     // ── Default bindings ─────────────────────────────────────────────
 
     #[test]
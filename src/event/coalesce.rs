@@ -0,0 +1,184 @@
+//! Input coalescing: collapse floods of redundant events before dispatch.
+//!
+//! Terminals report every cell the pointer crosses and every intermediate
+//! size while a window is dragged. Only the latest of these matters, so a
+//! batch read in one frame is passed through [`CoalesceConfig::apply`], which
+//! keeps the last of each run of mouse moves (or drags with the same button)
+//! and the last resize, and leaves everything else in order. Which kinds are
+//! collapsed is configured per event type.
+
+use super::input::{InputEvent, MouseAction};
+
+// ---------------------------------------------------------------------------
+// CoalesceConfig
+// ---------------------------------------------------------------------------
+
+/// Which event types are collapsed to their latest value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoalesceConfig {
+    /// Keep only the last of consecutive pointer moves.
+    pub mouse_move: bool,
+    /// Keep only the last of consecutive drags with the same button.
+    pub drag: bool,
+    /// Keep only the last resize in the batch.
+    pub resize: bool,
+}
+
+impl Default for CoalesceConfig {
+    fn default() -> Self {
+        Self {
+            mouse_move: true,
+            drag: true,
+            resize: true,
+        }
+    }
+}
+
+impl CoalesceConfig {
+    /// Collapse every event type (the default).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Dispatch every event as read.
+    pub fn disabled() -> Self {
+        Self {
+            mouse_move: false,
+            drag: false,
+            resize: false,
+        }
+    }
+
+    /// Set whether pointer moves are collapsed (builder).
+    pub fn with_mouse_move(mut self, enabled: bool) -> Self {
+        self.mouse_move = enabled;
+        self
+    }
+
+    /// Set whether drags are collapsed (builder).
+    pub fn with_drag(mut self, enabled: bool) -> Self {
+        self.drag = enabled;
+        self
+    }
+
+    /// Set whether resizes are collapsed (builder).
+    pub fn with_resize(mut self, enabled: bool) -> Self {
+        self.resize = enabled;
+        self
+    }
+
+    /// Whether mouse events of this kind are collapsed.
+    fn collapses(&self, kind: MouseAction) -> bool {
+        match kind {
+            MouseAction::Moved => self.mouse_move,
+            MouseAction::Drag(_) => self.drag,
+            _ => false,
+        }
+    }
+
+    /// Collapse a batch of events read in one frame.
+    ///
+    /// A mouse move or drag is dropped when the next event is the same kind
+    /// of motion; any other event in between (a click, a key) keeps both, so
+    /// handlers still see where the pointer was at that moment. Resizes don't
+    /// depend on order, so only the last one is kept, where it was read.
+    pub fn apply(&self, events: Vec<InputEvent>) -> Vec<InputEvent> {
+        let mut kept = Vec::with_capacity(events.len());
+        let mut resized = false;
+        // The motion kind of the event after the current one, if collapsible.
+        let mut next_motion = None;
+        for event in events.into_iter().rev() {
+            match &event {
+                InputEvent::Resize { .. } if self.resize => {
+                    if !resized {
+                        resized = true;
+                        kept.push(event);
+                    }
+                    continue;
+                }
+                InputEvent::Mouse(mouse) if self.collapses(mouse.kind) => {
+                    if next_motion != Some(mouse.kind) {
+                        next_motion = Some(mouse.kind);
+                        kept.push(event);
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+            next_motion = None;
+            kept.push(event);
+        }
+        kept.reverse();
+        kept
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::input::{Key, KeyEvent, Modifiers, MouseBtn, MouseEvent};
+
+    fn mouse(kind: MouseAction, x: u16) -> InputEvent {
+        InputEvent::Mouse(MouseEvent {
+            kind,
+            x,
+            y: 0,
+            modifiers: Modifiers::NONE,
+        })
+    }
+
+    fn resize(width: u16) -> InputEvent {
+        InputEvent::Resize { width, height: 24 }
+    }
+
+    #[test]
+    fn runs_of_motion_keep_the_last() {
+        let left = MouseAction::Drag(MouseBtn::Left);
+        let events = vec![
+            mouse(MouseAction::Moved, 1),
+            mouse(MouseAction::Moved, 2),
+            mouse(MouseAction::Down(MouseBtn::Left), 2),
+            mouse(left, 3),
+            mouse(left, 4),
+            mouse(MouseAction::Drag(MouseBtn::Right), 5),
+            mouse(MouseAction::Moved, 6),
+        ];
+        let kept = CoalesceConfig::new().apply(events);
+        assert_eq!(
+            kept,
+            [
+                mouse(MouseAction::Moved, 2),
+                mouse(MouseAction::Down(MouseBtn::Left), 2),
+                mouse(left, 4),
+                mouse(MouseAction::Drag(MouseBtn::Right), 5),
+                mouse(MouseAction::Moved, 6),
+            ]
+        );
+    }
+
+    #[test]
+    fn only_the_last_resize_survives() {
+        let key = InputEvent::Key(KeyEvent::new(Key::Enter, Modifiers::NONE));
+        let events = vec![resize(80), key.clone(), resize(90), resize(100)];
+        assert_eq!(CoalesceConfig::new().apply(events), [key, resize(100)]);
+    }
+
+    #[test]
+    fn per_type_configuration() {
+        let events = vec![
+            mouse(MouseAction::Moved, 1),
+            mouse(MouseAction::Moved, 2),
+            resize(80),
+            resize(90),
+        ];
+        let kept = CoalesceConfig::new()
+            .with_mouse_move(false)
+            .apply(events.clone());
+        assert_eq!(kept.len(), 3);
+        assert_eq!(CoalesceConfig::disabled().apply(events.clone()), events);
+    }
+}
@@ -0,0 +1,231 @@
+//! Selector-based event delegation.
+//!
+//! Instead of registering a handler on every child of a dynamic list, a
+//! container registers one delegated handler with a selector:
+//!
+//! ```ignore
+//! app.on::<ButtonPressed>(toolbar, ".toolbar Button", |pressed, button| {
+//!     run_tool(button);
+//!     true
+//! })?;
+//! ```
+//!
+//! While a message bubbles from its sender, each ancestor's delegated
+//! handlers for that message type run if the sender matches their selector.
+//! A handler returns `true` to mark the message handled, which stops it from
+//! bubbling further. Handlers on nodes that have been removed are dropped.
+
+use crate::css::parser::ParseError;
+use crate::css::query::Query;
+use crate::dom::node::NodeId;
+use crate::dom::tree::Dom;
+
+use super::handler::EventDispatcher;
+use super::message::{Envelope, Message};
+
+/// A type-erased delegated handler: returns `None` if the message is of
+/// another type, or whether it handled the message.
+type ErasedHandler = Box<dyn FnMut(&dyn Message, NodeId) -> Option<bool>>;
+
+/// Identifies a delegated handler, for removing it again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DelegateId(u64);
+
+struct Delegate {
+    id: DelegateId,
+    container: NodeId,
+    query: Query,
+    handler: ErasedHandler,
+}
+
+// ---------------------------------------------------------------------------
+// Delegates
+// ---------------------------------------------------------------------------
+
+/// Delegated handlers, keyed by the container they are registered on.
+#[derive(Default)]
+pub struct Delegates {
+    delegates: Vec<Delegate>,
+    next_id: u64,
+}
+
+impl std::fmt::Debug for Delegates {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Delegates")
+            .field("len", &self.delegates.len())
+            .finish()
+    }
+}
+
+impl Delegates {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Handle `M` messages bubbling through `container` whose sender matches
+    /// `selector`. The handler gets the message and its sender, and returns
+    /// whether it handled the message.
+    pub fn on<M: Message>(
+        &mut self,
+        container: NodeId,
+        selector: &str,
+        mut handler: impl FnMut(&M, NodeId) -> bool + 'static,
+    ) -> Result<DelegateId, ParseError> {
+        let query = Query::compile(selector)?;
+        let id = DelegateId(self.next_id);
+        self.next_id += 1;
+        self.delegates.push(Delegate {
+            id,
+            container,
+            query,
+            handler: Box::new(move |message, sender| {
+                let message = message.as_any().downcast_ref::<M>()?;
+                Some(handler(message, sender))
+            }),
+        });
+        Ok(id)
+    }
+
+    /// Remove a delegated handler. Returns whether it was registered.
+    pub fn off(&mut self, id: DelegateId) -> bool {
+        let before = self.delegates.len();
+        self.delegates.retain(|delegate| delegate.id != id);
+        self.delegates.len() != before
+    }
+
+    /// Number of registered handlers.
+    pub fn len(&self) -> usize {
+        self.delegates.len()
+    }
+
+    /// Whether no handlers are registered.
+    pub fn is_empty(&self) -> bool {
+        self.delegates.is_empty()
+    }
+
+    /// Bubble `envelope` from its sender through the sender's ancestors,
+    /// running matching handlers until one handles it. Targeted and already
+    /// handled messages are left alone. Returns how many handlers ran.
+    pub fn dispatch(&mut self, envelope: &mut Envelope, dom: &Dom) -> usize {
+        self.delegates
+            .retain(|delegate| dom.contains(delegate.container));
+        if envelope.handled || envelope.target.is_some() || self.delegates.is_empty() {
+            return 0;
+        }
+        let sender = envelope.sender;
+        let mut ran = 0;
+        for container in EventDispatcher::bubble_path(dom, sender)
+            .into_iter()
+            .skip(1)
+        {
+            for delegate in &mut self.delegates {
+                if delegate.container != container || !delegate.query.matches(sender, dom) {
+                    continue;
+                }
+                let Some(handled) = (delegate.handler)(envelope.message.as_ref(), sender) else {
+                    continue;
+                };
+                ran += 1;
+                if handled {
+                    envelope.handled = true;
+                    return ran;
+                }
+            }
+        }
+        ran
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dom::node::NodeData;
+    use crate::event::message::{Custom, Refresh};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// ```text
+    /// root
+    /// └── toolbar (.toolbar)
+    ///     ├── save (Button)
+    ///     └── label (Static)
+    /// ```
+    fn build_tree() -> (Dom, NodeId, NodeId, NodeId, NodeId) {
+        let mut dom = Dom::new();
+        let root = dom.insert(NodeData::new("Screen"));
+        let toolbar = dom.insert_child(root, NodeData::new("Container").with_class("toolbar"));
+        let save = dom.insert_child(toolbar, NodeData::new("Button").with_id("save"));
+        let label = dom.insert_child(toolbar, NodeData::new("Static"));
+        (dom, root, toolbar, save, label)
+    }
+
+    #[test]
+    fn matching_sender_runs_handler() {
+        let (dom, _, toolbar, save, label) = build_tree();
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let log = seen.clone();
+        let mut delegates = Delegates::new();
+        delegates
+            .on::<Custom>(toolbar, ".toolbar Button", move |msg, sender| {
+                log.borrow_mut().push((msg.0.clone(), sender));
+                true
+            })
+            .unwrap();
+
+        let mut pressed = Envelope::new(Custom::new("pressed"), save);
+        assert_eq!(delegates.dispatch(&mut pressed, &dom), 1);
+        assert!(pressed.handled);
+        // Non-matching sender, or another message type: nothing runs.
+        assert_eq!(
+            delegates.dispatch(&mut Envelope::new(Custom::new("x"), label), &dom),
+            0
+        );
+        assert_eq!(
+            delegates.dispatch(&mut Envelope::new(Refresh, save), &dom),
+            0
+        );
+        assert_eq!(*seen.borrow(), [("pressed".to_owned(), save)]);
+    }
+
+    #[test]
+    fn unhandled_messages_keep_bubbling() {
+        let (dom, root, toolbar, save, _) = build_tree();
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let mut delegates = Delegates::new();
+        for (container, name, handled) in [(toolbar, "toolbar", false), (root, "root", true)] {
+            let calls = calls.clone();
+            delegates
+                .on::<Refresh>(container, "Button", move |_, _| {
+                    calls.borrow_mut().push(name);
+                    handled
+                })
+                .unwrap();
+        }
+        let mut refresh = Envelope::new(Refresh, save);
+        assert_eq!(delegates.dispatch(&mut refresh, &dom), 2);
+        assert_eq!(*calls.borrow(), ["toolbar", "root"]);
+        assert!(refresh.handled);
+    }
+
+    #[test]
+    fn off_and_removed_containers() {
+        let (mut dom, root, toolbar, save, _) = build_tree();
+        let mut delegates = Delegates::new();
+        let id = delegates.on::<Refresh>(root, "*", |_, _| true).unwrap();
+        delegates.on::<Refresh>(toolbar, "*", |_, _| false).unwrap();
+        assert!(delegates.off(id));
+        assert!(!delegates.off(id));
+
+        dom.remove(toolbar);
+        delegates.dispatch(&mut Envelope::new(Refresh, root), &dom);
+        assert!(delegates.is_empty());
+        assert!(delegates
+            .on::<Refresh>(save, "Button >", |_, _| true)
+            .is_err());
+    }
+}
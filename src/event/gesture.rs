@@ -0,0 +1,464 @@
+//! Gesture recognition: clicks, double-clicks, and drags from raw mouse
+//! events.
+//!
+//! Terminals only report button presses, releases, and pointer motion. A
+//! [`GestureRecognizer`] turns that stream into [`Click`], [`DoubleClick`],
+//! and [`DragStart`] / [`DragMove`] / [`DragEnd`] messages, so widgets handle
+//! "clicked" instead of each pairing up downs and ups themselves:
+//!
+//! - A release near where the button went down is a [`Click`]; a second
+//!   click with the same button, soon after and close to the first, is also
+//!   a [`DoubleClick`].
+//! - Moving [`drag_threshold`](GestureConfig::drag_threshold) cells or more
+//!   with a button held starts a drag instead: [`DragStart`], a [`DragMove`]
+//!   per motion event, and [`DragEnd`] on release. A drag is never a click.
+//!
+//! The app feeds every mouse event it routes through its recognizer and
+//! sends the gestures from the node under the pointer (for drags, the node
+//! the drag started on), bubbling like any other message:
+//!
+//! ```ignore
+//! app.on::<DoubleClick>(list, "ListItem", |_, item| open(item));
+//! ```
+
+use std::any::Any;
+use std::time::{Duration, Instant};
+
+use super::input::{Modifiers, MouseAction, MouseBtn, MouseEvent};
+use super::message::Message;
+
+// ---------------------------------------------------------------------------
+// GestureConfig
+// ---------------------------------------------------------------------------
+
+/// Timing and distance thresholds for gesture recognition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GestureConfig {
+    /// Longest time between two clicks that still makes a double-click.
+    pub double_click_interval: Duration,
+    /// Cells the pointer must move, in either direction, with a button held
+    /// before a press becomes a drag. Also how far apart two clicks may be
+    /// and still make a double-click.
+    pub drag_threshold: u16,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        Self {
+            double_click_interval: Duration::from_millis(500),
+            drag_threshold: 1,
+        }
+    }
+}
+
+impl GestureConfig {
+    /// The default thresholds: 500 ms double-clicks, 1-cell drags.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the double-click interval (builder).
+    pub fn with_double_click_interval(mut self, interval: Duration) -> Self {
+        self.double_click_interval = interval;
+        self
+    }
+
+    /// Set the drag threshold in cells (builder). Raised to at least 1.
+    pub fn with_drag_threshold(mut self, cells: u16) -> Self {
+        self.drag_threshold = cells.max(1);
+        self
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Gesture messages
+// ---------------------------------------------------------------------------
+
+/// A button pressed and released without dragging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Click {
+    pub button: MouseBtn,
+    pub x: u16,
+    pub y: u16,
+    /// Modifiers held on release.
+    pub modifiers: Modifiers,
+}
+
+/// A second [`Click`] with the same button, soon after and close to the
+/// first. Sent right after that second click.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DoubleClick {
+    pub button: MouseBtn,
+    pub x: u16,
+    pub y: u16,
+    pub modifiers: Modifiers,
+}
+
+/// A held button moved past the drag threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DragStart {
+    pub button: MouseBtn,
+    /// Where the button went down.
+    pub x: u16,
+    pub y: u16,
+    pub modifiers: Modifiers,
+}
+
+/// The pointer moved during a drag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DragMove {
+    pub button: MouseBtn,
+    /// Where the drag started.
+    pub start_x: u16,
+    pub start_y: u16,
+    /// Where the pointer is now.
+    pub x: u16,
+    pub y: u16,
+    pub modifiers: Modifiers,
+}
+
+impl DragMove {
+    /// Distance from the drag's start, in cells.
+    pub fn delta(&self) -> (i32, i32) {
+        (
+            self.x as i32 - self.start_x as i32,
+            self.y as i32 - self.start_y as i32,
+        )
+    }
+}
+
+/// The button was released, ending a drag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DragEnd {
+    pub button: MouseBtn,
+    /// Where the drag started.
+    pub start_x: u16,
+    pub start_y: u16,
+    /// Where the button was released.
+    pub x: u16,
+    pub y: u16,
+    pub modifiers: Modifiers,
+}
+
+macro_rules! gesture_message {
+    ($($name:ident),*) => {
+        $(
+            impl Message for $name {
+                fn as_any(&self) -> &dyn Any {
+                    self
+                }
+                fn message_name(&self) -> &str {
+                    stringify!($name)
+                }
+            }
+        )*
+    };
+}
+
+gesture_message!(Click, DoubleClick, DragStart, DragMove, DragEnd);
+
+/// One recognized gesture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gesture {
+    Click(Click),
+    DoubleClick(DoubleClick),
+    DragStart(DragStart),
+    DragMove(DragMove),
+    DragEnd(DragEnd),
+}
+
+impl Gesture {
+    /// Where the gesture began: the click position, or a drag's start. The
+    /// app sends the gesture from the node there.
+    pub fn origin(&self) -> (u16, u16) {
+        match self {
+            Gesture::Click(g) => (g.x, g.y),
+            Gesture::DoubleClick(g) => (g.x, g.y),
+            Gesture::DragStart(g) => (g.x, g.y),
+            Gesture::DragMove(g) => (g.start_x, g.start_y),
+            Gesture::DragEnd(g) => (g.start_x, g.start_y),
+        }
+    }
+
+    /// Box the gesture as a message.
+    pub fn into_message(self) -> Box<dyn Message> {
+        match self {
+            Gesture::Click(g) => Box::new(g),
+            Gesture::DoubleClick(g) => Box::new(g),
+            Gesture::DragStart(g) => Box::new(g),
+            Gesture::DragMove(g) => Box::new(g),
+            Gesture::DragEnd(g) => Box::new(g),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// GestureRecognizer
+// ---------------------------------------------------------------------------
+
+/// A button held down, possibly dragging.
+#[derive(Debug, Clone, Copy)]
+struct Press {
+    button: MouseBtn,
+    x: u16,
+    y: u16,
+    dragging: bool,
+}
+
+/// The last click, for double-click detection.
+#[derive(Debug, Clone, Copy)]
+struct LastClick {
+    button: MouseBtn,
+    x: u16,
+    y: u16,
+    at: Instant,
+}
+
+/// Turns raw mouse events into gestures; see the [module docs](self).
+#[derive(Debug, Clone, Default)]
+pub struct GestureRecognizer {
+    config: GestureConfig,
+    press: Option<Press>,
+    last_click: Option<LastClick>,
+}
+
+impl GestureRecognizer {
+    /// Create a recognizer with the given thresholds.
+    pub fn new(config: GestureConfig) -> Self {
+        Self {
+            config,
+            press: None,
+            last_click: None,
+        }
+    }
+
+    /// The recognizer's thresholds.
+    pub fn config(&self) -> GestureConfig {
+        self.config
+    }
+
+    /// Change the thresholds. A press in progress keeps going.
+    pub fn set_config(&mut self, config: GestureConfig) {
+        self.config = config;
+    }
+
+    /// Whether a drag is in progress.
+    pub fn is_dragging(&self) -> bool {
+        self.press.is_some_and(|p| p.dragging)
+    }
+
+    /// Forget any press in progress without finishing it, e.g. when a modal
+    /// or menu takes over the mouse mid-drag.
+    pub fn cancel(&mut self) {
+        self.press = None;
+    }
+
+    /// Feed one mouse event received at `now` and return the gestures it
+    /// completes, in order.
+    pub fn feed(&mut self, event: &MouseEvent, now: Instant) -> Vec<Gesture> {
+        let (x, y, modifiers) = (event.x, event.y, event.modifiers);
+        match event.kind {
+            MouseAction::Down(button) => {
+                self.press = Some(Press {
+                    button,
+                    x,
+                    y,
+                    dragging: false,
+                });
+                Vec::new()
+            }
+            MouseAction::Drag(_) => {
+                let Some(press) = self.press.as_mut() else {
+                    return Vec::new();
+                };
+                let mut gestures = Vec::new();
+                if !press.dragging {
+                    if distance((press.x, press.y), (x, y)) < self.config.drag_threshold {
+                        return gestures;
+                    }
+                    press.dragging = true;
+                    gestures.push(Gesture::DragStart(DragStart {
+                        button: press.button,
+                        x: press.x,
+                        y: press.y,
+                        modifiers,
+                    }));
+                }
+                gestures.push(Gesture::DragMove(DragMove {
+                    button: press.button,
+                    start_x: press.x,
+                    start_y: press.y,
+                    x,
+                    y,
+                    modifiers,
+                }));
+                gestures
+            }
+            MouseAction::Up(button) => {
+                let Some(press) = self.press.take().filter(|p| p.button == button) else {
+                    return Vec::new();
+                };
+                if press.dragging {
+                    self.last_click = None;
+                    return vec![Gesture::DragEnd(DragEnd {
+                        button,
+                        start_x: press.x,
+                        start_y: press.y,
+                        x,
+                        y,
+                        modifiers,
+                    })];
+                }
+                self.click(button, x, y, modifiers, now)
+            }
+            MouseAction::Moved | MouseAction::ScrollUp | MouseAction::ScrollDown => Vec::new(),
+        }
+    }
+
+    /// A completed click, plus a double-click if it pairs with the last one.
+    /// A double-click does not start another pair, so a triple click is one
+    /// double-click and one click.
+    fn click(
+        &mut self,
+        button: MouseBtn,
+        x: u16,
+        y: u16,
+        modifiers: Modifiers,
+        now: Instant,
+    ) -> Vec<Gesture> {
+        let mut gestures = vec![Gesture::Click(Click {
+            button,
+            x,
+            y,
+            modifiers,
+        })];
+        let doubled = self.last_click.take().is_some_and(|last| {
+            last.button == button
+                && now.duration_since(last.at) <= self.config.double_click_interval
+                && distance((last.x, last.y), (x, y)) < self.config.drag_threshold
+        });
+        if doubled {
+            gestures.push(Gesture::DoubleClick(DoubleClick {
+                button,
+                x,
+                y,
+                modifiers,
+            }));
+        } else {
+            self.last_click = Some(LastClick {
+                button,
+                x,
+                y,
+                at: now,
+            });
+        }
+        gestures
+    }
+}
+
+/// Chebyshev distance between two cells.
+fn distance(a: (u16, u16), b: (u16, u16)) -> u16 {
+    a.0.abs_diff(b.0).max(a.1.abs_diff(b.1))
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mouse(kind: MouseAction, x: u16, y: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            x,
+            y,
+            modifiers: Modifiers::NONE,
+        }
+    }
+
+    fn names(gestures: &[Gesture]) -> Vec<String> {
+        gestures
+            .iter()
+            .map(|g| g.into_message().message_name().to_owned())
+            .collect()
+    }
+
+    const LEFT: MouseBtn = MouseBtn::Left;
+
+    #[test]
+    fn press_and_release_is_a_click_and_quick_repeat_doubles() {
+        let mut rec = GestureRecognizer::default();
+        let t0 = Instant::now();
+        assert!(rec
+            .feed(&mouse(MouseAction::Down(LEFT), 3, 4), t0)
+            .is_empty());
+        let first = rec.feed(&mouse(MouseAction::Up(LEFT), 3, 4), t0);
+        assert_eq!(names(&first), ["Click"]);
+
+        let t1 = t0 + Duration::from_millis(200);
+        rec.feed(&mouse(MouseAction::Down(LEFT), 3, 4), t1);
+        let second = rec.feed(&mouse(MouseAction::Up(LEFT), 3, 4), t1);
+        assert_eq!(names(&second), ["Click", "DoubleClick"]);
+
+        // A third click starts a new pair.
+        rec.feed(&mouse(MouseAction::Down(LEFT), 3, 4), t1);
+        let third = rec.feed(&mouse(MouseAction::Up(LEFT), 3, 4), t1);
+        assert_eq!(names(&third), ["Click"]);
+    }
+
+    #[test]
+    fn slow_or_distant_clicks_do_not_double() {
+        let config = GestureConfig::new().with_double_click_interval(Duration::from_millis(100));
+        let mut rec = GestureRecognizer::new(config);
+        let t0 = Instant::now();
+        let mut click = |x, at| {
+            rec.feed(&mouse(MouseAction::Down(LEFT), x, 0), at);
+            names(&rec.feed(&mouse(MouseAction::Up(LEFT), x, 0), at))
+        };
+        click(0, t0);
+        assert_eq!(click(0, t0 + Duration::from_millis(150)), ["Click"]);
+        assert_eq!(click(5, t0 + Duration::from_millis(160)), ["Click"]);
+        assert_eq!(
+            click(5, t0 + Duration::from_millis(170)),
+            ["Click", "DoubleClick"]
+        );
+    }
+
+    #[test]
+    fn moving_past_threshold_drags_instead_of_clicking() {
+        let mut rec = GestureRecognizer::new(GestureConfig::new().with_drag_threshold(2));
+        let now = Instant::now();
+        rec.feed(&mouse(MouseAction::Down(LEFT), 10, 10), now);
+        assert!(rec
+            .feed(&mouse(MouseAction::Drag(LEFT), 11, 10), now)
+            .is_empty());
+        let started = rec.feed(&mouse(MouseAction::Drag(LEFT), 12, 11), now);
+        assert_eq!(names(&started), ["DragStart", "DragMove"]);
+        assert!(rec.is_dragging());
+        let Gesture::DragMove(moved) = started[1] else {
+            panic!("expected a DragMove");
+        };
+        assert_eq!(moved.delta(), (2, 1));
+        assert_eq!(started[1].origin(), (10, 10));
+
+        let ended = rec.feed(&mouse(MouseAction::Up(LEFT), 15, 12), now);
+        assert_eq!(
+            ended,
+            [Gesture::DragEnd(DragEnd {
+                button: LEFT,
+                start_x: 10,
+                start_y: 10,
+                x: 15,
+                y: 12,
+                modifiers: Modifiers::NONE,
+            })]
+        );
+        assert!(!rec.is_dragging());
+        // A release with no press, or another button's, is ignored.
+        assert!(rec
+            .feed(&mouse(MouseAction::Up(LEFT), 15, 12), now)
+            .is_empty());
+    }
+}
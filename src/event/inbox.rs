@@ -0,0 +1,201 @@
+//! Cross-thread message inbox that wakes the event loop.
+//!
+//! Background threads (channel forwarders, fd readers, workers) hold an
+//! [`InboxSender`] and post boxed messages into the app's [`Inbox`]. Each send
+//! also raises a wake flag, so the loop can block in [`Inbox::wait`] instead of
+//! polling external sources at a fixed rate.
+
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+use super::message::Message;
+use crate::dom::node::NodeId;
+
+/// A message posted into the inbox, with the node it was posted on behalf of.
+type Posted = (Option<NodeId>, Box<dyn Message>);
+
+// ---------------------------------------------------------------------------
+// Wake flag
+// ---------------------------------------------------------------------------
+
+/// A sticky "something happened" flag with a condition variable.
+#[derive(Debug, Default)]
+struct Wake {
+    pending: Mutex<bool>,
+    signal: Condvar,
+}
+
+impl Wake {
+    fn raise(&self) {
+        *self.pending.lock().unwrap_or_else(|e| e.into_inner()) = true;
+        self.signal.notify_all();
+    }
+
+    /// Block until raised or `timeout` elapses, then clear the flag.
+    fn wait(&self, timeout: Option<Duration>) -> bool {
+        let guard = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        let mut guard = match timeout {
+            Some(timeout) => {
+                self.signal
+                    .wait_timeout_while(guard, timeout, |pending| !*pending)
+                    .unwrap_or_else(|e| e.into_inner())
+                    .0
+            }
+            None => self
+                .signal
+                .wait_while(guard, |pending| !*pending)
+                .unwrap_or_else(|e| e.into_inner()),
+        };
+        std::mem::replace(&mut *guard, false)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// InboxSender
+// ---------------------------------------------------------------------------
+
+/// A cloneable, `Send` handle for posting messages into an [`Inbox`].
+#[derive(Debug, Clone)]
+pub struct InboxSender {
+    tx: Sender<Posted>,
+    wake: Arc<Wake>,
+}
+
+impl InboxSender {
+    /// Post a message and wake the loop. Returns `false` if the inbox has
+    /// been dropped.
+    pub fn send(&self, message: impl Message) -> bool {
+        self.send_boxed(Box::new(message))
+    }
+
+    /// Post an already boxed message and wake the loop.
+    pub fn send_boxed(&self, message: Box<dyn Message>) -> bool {
+        self.post(None, message)
+    }
+
+    /// Post a message on behalf of a node. The loop delivers it with that
+    /// node as the sender, or drops it if the node is gone by then.
+    pub fn send_from(&self, node: NodeId, message: impl Message) -> bool {
+        self.post(Some(node), Box::new(message))
+    }
+
+    fn post(&self, node: Option<NodeId>, message: Box<dyn Message>) -> bool {
+        let sent = self.tx.send((node, message)).is_ok();
+        if sent {
+            self.wake.raise();
+        }
+        sent
+    }
+
+    /// Wake the loop without posting a message (e.g. after mutating shared
+    /// state that the next frame should pick up).
+    pub fn wake(&self) {
+        self.wake.raise();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Inbox
+// ---------------------------------------------------------------------------
+
+/// The receiving end of cross-thread messages, owned by the event loop.
+#[derive(Debug)]
+pub struct Inbox {
+    rx: Receiver<Posted>,
+    sender: InboxSender,
+}
+
+impl Inbox {
+    /// Create an empty inbox.
+    pub fn new() -> Self {
+        let (tx, rx) = mpsc::channel();
+        Self {
+            rx,
+            sender: InboxSender {
+                tx,
+                wake: Arc::new(Wake::default()),
+            },
+        }
+    }
+
+    /// A new handle for posting into this inbox.
+    pub fn sender(&self) -> InboxSender {
+        self.sender.clone()
+    }
+
+    /// Take every message posted so far, without blocking.
+    pub fn drain(&self) -> Vec<Box<dyn Message>> {
+        self.rx.try_iter().map(|(_, message)| message).collect()
+    }
+
+    /// Take every message posted so far along with the node each was posted
+    /// for (`None` for plain [`InboxSender::send`]), without blocking.
+    pub fn drain_posted(&self) -> Vec<(Option<NodeId>, Box<dyn Message>)> {
+        self.rx.try_iter().collect()
+    }
+
+    /// Block until a sender posts or wakes, or `timeout` elapses (`None`
+    /// waits indefinitely). Returns whether a wake-up arrived.
+    pub fn wait(&self, timeout: Option<Duration>) -> bool {
+        self.sender.wake.wait(timeout)
+    }
+}
+
+impl Default for Inbox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::message::{Custom, Refresh};
+    use std::thread;
+
+    #[test]
+    fn drain_returns_posted_messages() {
+        let inbox = Inbox::new();
+        let tx = inbox.sender();
+        assert!(tx.send(Refresh));
+        assert!(tx.send(Custom::new("x")));
+        let names: Vec<String> = inbox
+            .drain()
+            .iter()
+            .map(|m| m.message_name().to_owned())
+            .collect();
+        assert_eq!(names.len(), 2);
+        assert!(inbox.drain().is_empty());
+    }
+
+    #[test]
+    fn wait_times_out_without_senders() {
+        let inbox = Inbox::new();
+        assert!(!inbox.wait(Some(Duration::from_millis(5))));
+    }
+
+    #[test]
+    fn send_from_thread_wakes_waiter() {
+        let inbox = Inbox::new();
+        let tx = inbox.sender();
+        let worker = thread::spawn(move || tx.send(Refresh));
+        assert!(inbox.wait(None));
+        worker.join().unwrap();
+        assert_eq!(inbox.drain().len(), 1);
+        // The wake flag was consumed.
+        assert!(!inbox.wait(Some(Duration::ZERO)));
+    }
+
+    #[test]
+    fn send_fails_after_inbox_dropped() {
+        let inbox = Inbox::new();
+        let tx = inbox.sender();
+        drop(inbox);
+        assert!(!tx.send(Refresh));
+    }
+}
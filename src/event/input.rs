@@ -4,6 +4,7 @@
 //! Crossterm events are converted via `From` impls so the rest of the
 //! framework never depends on crossterm directly.
 
+use std::fmt;
 use std::ops::{BitAnd, BitOr};
 
 // ---------------------------------------------------------------------------
@@ -11,7 +12,13 @@ use std::ops::{BitAnd, BitOr};
 // ---------------------------------------------------------------------------
 
 /// Keyboard key, decoupled from crossterm.
+///
+/// Legacy terminals encode some control chords as other keys (Ctrl+I arrives
+/// as `Tab`, Ctrl+M as `Enter`). With the kitty keyboard protocol enabled
+/// these are disambiguated and arrive as `Char('i')` / `Char('m')` with
+/// [`Modifiers::CTRL`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Key {
     Char(char),
     Enter,
@@ -28,15 +35,32 @@ pub enum Key {
     End,
     PageUp,
     PageDown,
+    Insert,
     F(u8),
 }
 
+impl fmt::Display for Key {
+    /// The key as shown in shortcut hints: letters upper-cased, named keys
+    /// spelled out (`Enter`, `PageDown`, `F5`).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Key::Char(' ') => f.write_str("Space"),
+            Key::Char(c) => write!(f, "{}", c.to_uppercase()),
+            Key::Escape => f.write_str("Esc"),
+            Key::BackTab => f.write_str("Shift+Tab"),
+            Key::F(n) => write!(f, "F{n}"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Modifiers
 // ---------------------------------------------------------------------------
 
 /// Modifier key bitmask.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Modifiers(pub u8);
 
 impl Modifiers {
@@ -44,6 +68,12 @@ impl Modifiers {
     pub const SHIFT: Modifiers = Modifiers(1);
     pub const CTRL: Modifiers = Modifiers(2);
     pub const ALT: Modifiers = Modifiers(4);
+    /// Super / Windows / Command key (kitty keyboard protocol only).
+    pub const SUPER: Modifiers = Modifiers(8);
+    /// Hyper key (kitty keyboard protocol only).
+    pub const HYPER: Modifiers = Modifiers(16);
+    /// Meta key (kitty keyboard protocol only).
+    pub const META: Modifiers = Modifiers(32);
 
     /// Check whether `self` contains all the bits in `other`.
     pub fn contains(self, other: Modifiers) -> bool {
@@ -56,6 +86,31 @@ impl Modifiers {
     }
 }
 
+impl fmt::Display for Modifiers {
+    /// `+`-joined modifier names, e.g. `Ctrl+Shift`; empty for none.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const NAMES: [(Modifiers, &str); 6] = [
+            (Modifiers::CTRL, "Ctrl"),
+            (Modifiers::ALT, "Alt"),
+            (Modifiers::SHIFT, "Shift"),
+            (Modifiers::SUPER, "Super"),
+            (Modifiers::HYPER, "Hyper"),
+            (Modifiers::META, "Meta"),
+        ];
+        let mut first = true;
+        for (modifier, name) in NAMES {
+            if self.contains(modifier) {
+                if !first {
+                    f.write_str("+")?;
+                }
+                f.write_str(name)?;
+                first = false;
+            }
+        }
+        Ok(())
+    }
+}
+
 impl BitOr for Modifiers {
     type Output = Modifiers;
     fn bitor(self, rhs: Self) -> Self::Output {
@@ -76,6 +131,7 @@ impl BitAnd for Modifiers {
 
 /// A keyboard event with key and modifiers.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct KeyEvent {
     pub code: Key,
     pub modifiers: Modifiers,
@@ -88,6 +144,17 @@ impl KeyEvent {
     }
 }
 
+impl fmt::Display for KeyEvent {
+    /// The chord as shown in shortcut hints, e.g. `Ctrl+S` or `Alt+F4`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.is_empty() {
+            write!(f, "{}", self.code)
+        } else {
+            write!(f, "{}+{}", self.modifiers, self.code)
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // MouseBtn / MouseAction / MouseEvent
 // ---------------------------------------------------------------------------
@@ -127,7 +194,11 @@ pub struct MouseEvent {
 /// Top-level input event.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum InputEvent {
+    /// A key press (or auto-repeat).
     Key(KeyEvent),
+    /// A key release. Only reported when the kitty keyboard protocol is
+    /// enabled (see `Driver::enable_keyboard_enhancement`).
+    KeyRelease(KeyEvent),
     Mouse(MouseEvent),
     Resize { width: u16, height: u16 },
     FocusGained,
@@ -151,6 +222,15 @@ fn convert_modifiers(m: crossterm::event::KeyModifiers) -> Modifiers {
     if m.contains(crossterm::event::KeyModifiers::ALT) {
         out = out | Modifiers::ALT;
     }
+    if m.contains(crossterm::event::KeyModifiers::SUPER) {
+        out = out | Modifiers::SUPER;
+    }
+    if m.contains(crossterm::event::KeyModifiers::HYPER) {
+        out = out | Modifiers::HYPER;
+    }
+    if m.contains(crossterm::event::KeyModifiers::META) {
+        out = out | Modifiers::META;
+    }
     out
 }
 
@@ -175,6 +255,7 @@ impl From<crossterm::event::KeyEvent> for KeyEvent {
             crossterm::event::KeyCode::End => Key::End,
             crossterm::event::KeyCode::PageUp => Key::PageUp,
             crossterm::event::KeyCode::PageDown => Key::PageDown,
+            crossterm::event::KeyCode::Insert => Key::Insert,
             crossterm::event::KeyCode::F(n) => Key::F(n),
             // Map unsupported key codes to Escape as a fallback.
             _ => Key::Escape,
@@ -199,7 +280,13 @@ fn convert_mouse_button(b: crossterm::event::MouseButton) -> MouseBtn {
 impl From<crossterm::event::Event> for InputEvent {
     fn from(ct: crossterm::event::Event) -> Self {
         match ct {
-            crossterm::event::Event::Key(ke) => InputEvent::Key(KeyEvent::from(ke)),
+            crossterm::event::Event::Key(ke) => {
+                if ke.kind == crossterm::event::KeyEventKind::Release {
+                    InputEvent::KeyRelease(KeyEvent::from(ke))
+                } else {
+                    InputEvent::Key(KeyEvent::from(ke))
+                }
+            }
             crossterm::event::Event::Mouse(me) => {
                 let modifiers = convert_modifiers(me.modifiers);
                 let kind = match me.kind {
@@ -303,6 +390,81 @@ mod tests {
         assert!(ke.modifiers.contains(Modifiers::CTRL));
     }
 
+    #[test]
+    fn key_event_display() {
+        let show = |code, modifiers| KeyEvent::new(code, modifiers).to_string();
+        assert_eq!(show(Key::Char('s'), Modifiers::CTRL), "Ctrl+S");
+        assert_eq!(
+            show(Key::Char('z'), Modifiers::CTRL | Modifiers::SHIFT),
+            "Ctrl+Shift+Z"
+        );
+        assert_eq!(show(Key::F(4), Modifiers::ALT), "Alt+F4");
+        assert_eq!(show(Key::PageDown, Modifiers::NONE), "PageDown");
+        assert_eq!(show(Key::Char(' '), Modifiers::NONE), "Space");
+    }
+
+    // ── Kitty keyboard protocol ──────────────────────────────────────
+
+    #[test]
+    fn from_crossterm_super_hyper_meta() {
+        let ct = crossterm::event::KeyEvent::new(
+            crossterm::event::KeyCode::Char('k'),
+            crossterm::event::KeyModifiers::SUPER
+                | crossterm::event::KeyModifiers::HYPER
+                | crossterm::event::KeyModifiers::META,
+        );
+        let ke = KeyEvent::from(ct);
+        assert!(ke.modifiers.contains(Modifiers::SUPER));
+        assert!(ke.modifiers.contains(Modifiers::HYPER));
+        assert!(ke.modifiers.contains(Modifiers::META));
+        assert!(!ke.modifiers.contains(Modifiers::CTRL));
+    }
+
+    #[test]
+    fn from_crossterm_ctrl_i_is_not_tab() {
+        let ct = crossterm::event::KeyEvent::new(
+            crossterm::event::KeyCode::Char('i'),
+            crossterm::event::KeyModifiers::CONTROL,
+        );
+        let ke = KeyEvent::from(ct);
+        assert_eq!(ke, KeyEvent::new(Key::Char('i'), Modifiers::CTRL));
+        assert_ne!(ke.code, Key::Tab);
+    }
+
+    #[test]
+    fn from_crossterm_key_release() {
+        let ct = crossterm::event::KeyEvent::new_with_kind(
+            crossterm::event::KeyCode::Char('w'),
+            crossterm::event::KeyModifiers::NONE,
+            crossterm::event::KeyEventKind::Release,
+        );
+        let event = InputEvent::from(crossterm::event::Event::Key(ct));
+        assert_eq!(
+            event,
+            InputEvent::KeyRelease(KeyEvent::new(Key::Char('w'), Modifiers::NONE))
+        );
+    }
+
+    #[test]
+    fn from_crossterm_key_repeat_is_press() {
+        let ct = crossterm::event::KeyEvent::new_with_kind(
+            crossterm::event::KeyCode::Char('w'),
+            crossterm::event::KeyModifiers::NONE,
+            crossterm::event::KeyEventKind::Repeat,
+        );
+        let event = InputEvent::from(crossterm::event::Event::Key(ct));
+        assert!(matches!(event, InputEvent::Key(_)));
+    }
+
+    #[test]
+    fn from_crossterm_insert() {
+        let ct = crossterm::event::KeyEvent::new(
+            crossterm::event::KeyCode::Insert,
+            crossterm::event::KeyModifiers::NONE,
+        );
+        assert_eq!(KeyEvent::from(ct).code, Key::Insert);
+    }
+
     // ── From<crossterm::event::KeyEvent> ─────────────────────────────
 
     #[test]
@@ -0,0 +1,361 @@
+//! Key descriptors: the one text form for keys and key sequences.
+//!
+//! Bindings, widget binding tables, keymap files, key translation tables and
+//! the test [`Pilot`](crate::testing::Pilot) all write keys the same way: a
+//! `+`-joined list of modifiers ending in a key name (`"ctrl+shift+f5"`,
+//! `"alt+enter"`, `"space"`), and sequences of those separated by spaces
+//! (`"g g"`, `"ctrl+x ctrl+s"`). [`parse_key`] and [`parse_keys`] read that
+//! syntax; [`format_key`] and [`format_keys`] write it back in canonical form
+//! (lowercase names, modifiers in a fixed order), so parsing a formatted key
+//! gives the same key back. [`display_keys`] renders keys for people instead,
+//! as footers and menus show them (`Ctrl+S`, `G G`).
+//!
+//! ```ignore
+//! let save: KeyEvent = "Ctrl+S".parse()?;
+//! assert_eq!(format_key(&save), "ctrl+s");
+//! assert_eq!(display_keys(&[save]), "Ctrl+S");
+//! ```
+
+use std::fmt::{self, Write as _};
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use super::input::{Key, KeyEvent, Modifiers};
+
+/// Modifier names in canonical order, as written by [`format_key`].
+const MODIFIER_NAMES: [(Modifiers, &str); 6] = [
+    (Modifiers::CTRL, "ctrl"),
+    (Modifiers::ALT, "alt"),
+    (Modifiers::SHIFT, "shift"),
+    (Modifiers::SUPER, "super"),
+    (Modifiers::HYPER, "hyper"),
+    (Modifiers::META, "meta"),
+];
+
+// ---------------------------------------------------------------------------
+// Parsing
+// ---------------------------------------------------------------------------
+
+/// Error parsing a key descriptor.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum KeyParseError {
+    /// The string contained no keys.
+    #[error("empty key binding")]
+    Empty,
+    /// The last `+`-separated part is not a key name.
+    #[error("unknown key: {0:?}")]
+    UnknownKey(String),
+    /// A part before the key is not a modifier name.
+    #[error("unknown modifier: {0:?}")]
+    UnknownModifier(String),
+}
+
+/// Parse one key such as `"ctrl+shift+p"`, `"f5"`, `"pageup"` or `"space"`.
+///
+/// Names are case-insensitive; single characters are kept as written. Use
+/// `plus` and `minus` for those characters after a modifier.
+pub fn parse_key(text: &str) -> Result<KeyEvent, KeyParseError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(KeyParseError::Empty);
+    }
+    // A lone "+" is the key itself, not a separator.
+    let (mods, key) = match text.rsplit_once('+') {
+        Some(("", "")) => ("", "+"),
+        Some((mods, key)) => (mods, key),
+        None => ("", text),
+    };
+    let mut modifiers = Modifiers::NONE;
+    for part in mods.split('+').filter(|part| !part.is_empty()) {
+        modifiers = modifiers | parse_modifier(part)?;
+    }
+    Ok(KeyEvent::new(parse_key_name(key)?, modifiers))
+}
+
+/// Parse a space-separated key sequence: `"ctrl+x ctrl+s"` or `"g g"`.
+pub fn parse_keys(text: &str) -> Result<Vec<KeyEvent>, KeyParseError> {
+    let keys = text
+        .split_whitespace()
+        .map(parse_key)
+        .collect::<Result<Vec<_>, _>>()?;
+    if keys.is_empty() {
+        return Err(KeyParseError::Empty);
+    }
+    Ok(keys)
+}
+
+fn parse_modifier(name: &str) -> Result<Modifiers, KeyParseError> {
+    Ok(match name.to_ascii_lowercase().as_str() {
+        "ctrl" | "control" => Modifiers::CTRL,
+        "shift" => Modifiers::SHIFT,
+        "alt" | "option" => Modifiers::ALT,
+        "super" | "cmd" | "win" => Modifiers::SUPER,
+        "hyper" => Modifiers::HYPER,
+        "meta" => Modifiers::META,
+        _ => return Err(KeyParseError::UnknownModifier(name.to_owned())),
+    })
+}
+
+fn parse_key_name(name: &str) -> Result<Key, KeyParseError> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Ok(Key::Char(c));
+    }
+    let lower = name.to_ascii_lowercase();
+    Ok(match lower.as_str() {
+        "enter" | "return" => Key::Enter,
+        "escape" | "esc" => Key::Escape,
+        "tab" => Key::Tab,
+        "backtab" => Key::BackTab,
+        "backspace" => Key::Backspace,
+        "delete" | "del" => Key::Delete,
+        "left" => Key::Left,
+        "right" => Key::Right,
+        "up" => Key::Up,
+        "down" => Key::Down,
+        "home" => Key::Home,
+        "end" => Key::End,
+        "pageup" | "pgup" => Key::PageUp,
+        "pagedown" | "pgdn" => Key::PageDown,
+        "insert" | "ins" => Key::Insert,
+        "space" => Key::Char(' '),
+        "plus" => Key::Char('+'),
+        "minus" => Key::Char('-'),
+        _ => match lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+            Some(n) if (1..=24).contains(&n) => Key::F(n),
+            _ => return Err(KeyParseError::UnknownKey(name.to_owned())),
+        },
+    })
+}
+
+impl FromStr for KeyEvent {
+    type Err = KeyParseError;
+
+    /// Parse a single key descriptor; see [`parse_key`].
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        parse_key(text)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Formatting
+// ---------------------------------------------------------------------------
+
+/// The canonical descriptor for `key`, e.g. `"ctrl+shift+f5"` or
+/// `"alt+enter"`: lowercase modifiers in the order ctrl, alt, shift, super,
+/// hyper, meta, then the key name. [`parse_key`] reads it back unchanged.
+pub fn format_key(key: &KeyEvent) -> String {
+    let mut out = String::new();
+    for (modifier, name) in MODIFIER_NAMES {
+        if key.modifiers.contains(modifier) {
+            out.push_str(name);
+            out.push('+');
+        }
+    }
+    let name = match key.code {
+        Key::Char(' ') => "space",
+        Key::Char('+') => "plus",
+        Key::Char('-') => "minus",
+        Key::Char(c) => {
+            out.push(c);
+            return out;
+        }
+        Key::F(n) => {
+            let _ = write!(out, "f{n}");
+            return out;
+        }
+        Key::Enter => "enter",
+        Key::Escape => "escape",
+        Key::Tab => "tab",
+        Key::BackTab => "backtab",
+        Key::Backspace => "backspace",
+        Key::Delete => "delete",
+        Key::Left => "left",
+        Key::Right => "right",
+        Key::Up => "up",
+        Key::Down => "down",
+        Key::Home => "home",
+        Key::End => "end",
+        Key::PageUp => "pageup",
+        Key::PageDown => "pagedown",
+        Key::Insert => "insert",
+    };
+    out.push_str(name);
+    out
+}
+
+/// The canonical descriptor for a key sequence: each key as
+/// [`format_key`] writes it, separated by spaces, e.g. `"g g"`.
+pub fn format_keys(keys: &[KeyEvent]) -> String {
+    let keys: Vec<String> = keys.iter().map(format_key).collect();
+    keys.join(" ")
+}
+
+/// Keys as shown to people in footers, menus and help screens, e.g.
+/// `"Ctrl+S"` or `"G G"`; each key uses [`KeyEvent`]'s `Display`.
+pub fn display_keys(keys: &[KeyEvent]) -> String {
+    let keys: Vec<String> = keys.iter().map(KeyEvent::to_string).collect();
+    keys.join(" ")
+}
+
+// ---------------------------------------------------------------------------
+// KeySequence
+// ---------------------------------------------------------------------------
+
+/// A parsed key sequence that reads and writes as a descriptor string, e.g.
+/// in keymap files: `"ctrl+s"` or `"g g"`.
+///
+/// Always holds at least one key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct KeySequence(Vec<KeyEvent>);
+
+impl KeySequence {
+    /// The keys, in the order they are pressed.
+    pub fn keys(&self) -> &[KeyEvent] {
+        &self.0
+    }
+
+    /// Take the keys out.
+    pub fn into_keys(self) -> Vec<KeyEvent> {
+        self.0
+    }
+}
+
+impl From<KeyEvent> for KeySequence {
+    fn from(key: KeyEvent) -> Self {
+        Self(vec![key])
+    }
+}
+
+impl FromStr for KeySequence {
+    type Err = KeyParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        parse_keys(text).map(Self)
+    }
+}
+
+impl TryFrom<String> for KeySequence {
+    type Error = KeyParseError;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        text.parse()
+    }
+}
+
+impl From<KeySequence> for String {
+    fn from(keys: KeySequence) -> Self {
+        keys.to_string()
+    }
+}
+
+impl fmt::Display for KeySequence {
+    /// The canonical descriptor, as [`format_keys`] writes it.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format_keys(&self.0))
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ev(code: Key, modifiers: Modifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn parses_descriptors() {
+        assert_eq!(
+            parse_key("ctrl+shift+p"),
+            Ok(ev(Key::Char('p'), Modifiers::CTRL | Modifiers::SHIFT))
+        );
+        assert_eq!(parse_key("F5"), Ok(ev(Key::F(5), Modifiers::NONE)));
+        assert_eq!(parse_key("+"), Ok(ev(Key::Char('+'), Modifiers::NONE)));
+        assert_eq!(
+            parse_key("alt+plus"),
+            Ok(ev(Key::Char('+'), Modifiers::ALT))
+        );
+        assert_eq!("Alt+Enter".parse(), Ok(ev(Key::Enter, Modifiers::ALT)));
+        assert_eq!(
+            parse_keys("g g").unwrap(),
+            [ev(Key::Char('g'), Modifiers::NONE); 2]
+        );
+        assert_eq!(parse_keys("  "), Err(KeyParseError::Empty));
+        assert_eq!(
+            parse_key("f25"),
+            Err(KeyParseError::UnknownKey("f25".into()))
+        );
+        assert_eq!(
+            parse_key("hyperctrl+x"),
+            Err(KeyParseError::UnknownModifier("hyperctrl".into()))
+        );
+    }
+
+    #[test]
+    fn formats_canonically_and_round_trips() {
+        let chord = ev(Key::F(5), Modifiers::SHIFT | Modifiers::CTRL);
+        assert_eq!(format_key(&chord), "ctrl+shift+f5");
+        assert_eq!(format_key(&"Control+Esc".parse().unwrap()), "ctrl+escape");
+        assert_eq!(format_key(&ev(Key::Char('+'), Modifiers::ALT)), "alt+plus");
+        assert_eq!(format_key(&ev(Key::Char(' '), Modifiers::NONE)), "space");
+
+        let mut keys = vec![
+            Key::Enter,
+            Key::Escape,
+            Key::Tab,
+            Key::BackTab,
+            Key::Backspace,
+            Key::Delete,
+            Key::Left,
+            Key::Right,
+            Key::Up,
+            Key::Down,
+            Key::Home,
+            Key::End,
+            Key::PageUp,
+            Key::PageDown,
+            Key::Insert,
+            Key::F(1),
+            Key::F(24),
+        ];
+        keys.extend("aZ1 +-é١".chars().map(Key::Char));
+        let modifiers = [
+            Modifiers::NONE,
+            Modifiers::CTRL,
+            Modifiers::ALT | Modifiers::SHIFT,
+            Modifiers::SUPER | Modifiers::HYPER | Modifiers::META,
+        ];
+        for code in keys {
+            for modifiers in modifiers {
+                let key = ev(code, modifiers);
+                let text = format_key(&key);
+                assert_eq!(parse_key(&text), Ok(key), "{text}");
+                assert_eq!(format_key(&parse_key(&text).unwrap()), text);
+            }
+        }
+    }
+
+    #[test]
+    fn sequences_round_trip_and_display_for_people() {
+        let sequence: KeySequence = "Ctrl+x  CONTROL+s".parse().unwrap();
+        assert_eq!(sequence.to_string(), "ctrl+x ctrl+s");
+        assert_eq!(sequence.to_string().parse(), Ok(sequence.clone()));
+        assert_eq!(display_keys(sequence.keys()), "Ctrl+X Ctrl+S");
+        assert_eq!(format_keys(&parse_keys("g g").unwrap()), "g g");
+        assert_eq!(
+            KeySequence::try_from(String::new()),
+            Err(KeyParseError::Empty)
+        );
+    }
+}
@@ -140,6 +140,72 @@ impl Message for FocusPrevious {
     }
 }
 
+/// Show or hide the performance HUD overlay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TogglePerfHud;
+
+impl Message for TogglePerfHud {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn message_name(&self) -> &str {
+        "TogglePerfHud"
+    }
+}
+
+/// Turn mouse capture off (for native text selection) or back on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToggleMouseCapture;
+
+impl Message for ToggleMouseCapture {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn message_name(&self) -> &str {
+        "ToggleMouseCapture"
+    }
+}
+
+/// Switch to the next UI density (compact, normal, comfortable).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CycleDensity;
+
+impl Message for CycleDensity {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn message_name(&self) -> &str {
+        "CycleDensity"
+    }
+}
+
+/// Copy text to the system clipboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyToClipboard(pub String);
+
+impl Message for CopyToClipboard {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn message_name(&self) -> &str {
+        "CopyToClipboard"
+    }
+}
+
+/// Terminal input could not be read, with the error; the input reader has
+/// stopped. The app quits unless a handler marks the message handled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputFailed(pub String);
+
+impl Message for InputFailed {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn message_name(&self) -> &str {
+        "InputFailed"
+    }
+}
+
 /// User-defined string message.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Custom(pub String);
@@ -1,11 +1,39 @@
-//! Event system: messages, input, key bindings, dispatch.
+//! Event system: messages, input, key bindings, dispatch, selector-based
+//! delegation, cross-thread inbox, input coalescing, gesture recognition,
+//! timers, background workers, key translation tables, key descriptors,
+//! threaded terminal input.
 
 pub mod binding;
+pub mod coalesce;
+pub mod delegate;
+pub mod gesture;
 pub mod handler;
+pub mod inbox;
 pub mod input;
+pub mod keys;
 pub mod message;
+pub mod reader;
+pub mod timer;
+pub mod translate;
+pub mod worker;
 
-pub use binding::{BindingAction, KeyBindingRegistry};
+pub use binding::{
+    ActionDef, ActiveBinding, Binding, BindingAction, BindingConflict, BindingHint, KeyBindingDef,
+    KeyBindingRegistry, KeyResolution, WidgetBinding,
+};
+pub use coalesce::CoalesceConfig;
+pub use delegate::{DelegateId, Delegates};
+pub use gesture::{
+    Click, DoubleClick, DragEnd, DragMove, DragStart, Gesture, GestureConfig, GestureRecognizer,
+};
 pub use handler::EventDispatcher;
+pub use inbox::{Inbox, InboxSender};
 pub use input::{InputEvent, Key, KeyEvent, Modifiers, MouseAction, MouseBtn, MouseEvent};
+pub use keys::{
+    display_keys, format_key, format_keys, parse_key, parse_keys, KeyParseError, KeySequence,
+};
 pub use message::{Custom, Envelope, FocusNext, FocusPrevious, Message, Quit, Refresh};
+pub use reader::{InputQueue, InputReader, InputSender};
+pub use timer::{TimerHandle, Timers};
+pub use translate::{KeyTable, KeyTranslator};
+pub use worker::{BackgroundThread, WorkerContext, WorkerFailed, WorkerHandle};
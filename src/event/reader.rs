@@ -0,0 +1,323 @@
+//! Terminal input read on a dedicated thread.
+//!
+//! Reading crossterm events blocks, so doing it on the loop thread ties input
+//! latency to frame timing: the loop either polls with a short timeout and
+//! burns wake-ups, or sleeps through a frame and answers keys late. An
+//! [`InputReader`] does the blocking reads on its own thread instead and
+//! sends each event into an [`InputQueue`], waking the loop through the app's
+//! inbox so `App::wait_for_work` returns as soon as input arrives.
+//!
+//! The queue also takes synthetic events from any thread through an
+//! [`InputSender`]: tests, workers, or a remote-control socket can inject
+//! keys and clicks that go through exactly the same path as typed ones.
+//! Stopping or dropping the reader ends its thread within one
+//! [`POLL_INTERVAL`], so the app shuts down without leaving a reader behind.
+//! If reading fails, the thread posts an [`InputFailed`] message to the inbox
+//! before ending, so a loop waiting for input wakes up instead of hanging.
+//!
+//! ```ignore
+//! let keys = app.input_sender();
+//! std::thread::spawn(move || keys.send(InputEvent::Key("ctrl+s".parse().unwrap())));
+//! app.wait_for_work();
+//! app.pump_input();
+//! ```
+
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use super::inbox::InboxSender;
+use super::input::InputEvent;
+use super::message::InputFailed;
+
+/// How long the reader waits for input before checking whether it should
+/// stop; bounds how long [`InputReader::stop`] takes.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+// ---------------------------------------------------------------------------
+// InputSender / InputQueue
+// ---------------------------------------------------------------------------
+
+/// A cloneable, `Send` handle for injecting input events into an
+/// [`InputQueue`].
+#[derive(Debug, Clone)]
+pub struct InputSender {
+    tx: Sender<InputEvent>,
+    wake: InboxSender,
+}
+
+impl InputSender {
+    /// Queue `event` and wake the loop. Returns `false` if the queue has
+    /// been dropped.
+    pub fn send(&self, event: InputEvent) -> bool {
+        let sent = self.tx.send(event).is_ok();
+        if sent {
+            self.wake.wake();
+        }
+        sent
+    }
+}
+
+/// Input events waiting for the loop, from the reader thread or injected.
+#[derive(Debug)]
+pub struct InputQueue {
+    rx: Receiver<InputEvent>,
+    sender: InputSender,
+}
+
+impl InputQueue {
+    /// Create an empty queue whose senders wake the loop through `wake`.
+    pub fn new(wake: InboxSender) -> Self {
+        let (tx, rx) = mpsc::channel();
+        Self {
+            rx,
+            sender: InputSender { tx, wake },
+        }
+    }
+
+    /// A new handle for injecting events into this queue.
+    pub fn sender(&self) -> InputSender {
+        self.sender.clone()
+    }
+
+    /// Take every event queued so far, in arrival order, without blocking.
+    pub fn drain(&self) -> Vec<InputEvent> {
+        self.rx.try_iter().collect()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// InputReader
+// ---------------------------------------------------------------------------
+
+/// Where an [`InputReader`] gets its events: called repeatedly with
+/// [`POLL_INTERVAL`], returning the next event or `None` if none arrived.
+type InputSource = Box<dyn FnMut(Duration) -> io::Result<Option<InputEvent>> + Send>;
+
+/// A thread reading terminal events into an [`InputQueue`].
+///
+/// The thread ends when [`stop`](Self::stop) is called or the reader is
+/// dropped, when the queue is gone, or when reading fails (after posting
+/// [`InputFailed`] to the inbox). A stopped reader
+/// can be [`restart`](Self::restart)ed with the same source.
+pub struct InputReader {
+    sender: InputSender,
+    /// The source, while no thread owns it.
+    source: Option<InputSource>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<InputSource>>,
+}
+
+impl std::fmt::Debug for InputReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InputReader")
+            .field("running", &self.is_running())
+            .finish_non_exhaustive()
+    }
+}
+
+impl InputReader {
+    /// Start reading crossterm events into `sender`'s queue.
+    pub fn spawn(sender: InputSender) -> io::Result<Self> {
+        Self::spawn_with(sender, |timeout| {
+            if crossterm::event::poll(timeout)? {
+                crossterm::event::read().map(|event| Some(event.into()))
+            } else {
+                Ok(None)
+            }
+        })
+    }
+
+    /// Start reading from `source` instead of the terminal. `source` is
+    /// called repeatedly with [`POLL_INTERVAL`] and returns the next event,
+    /// or `None` if none arrived within it.
+    pub fn spawn_with(
+        sender: InputSender,
+        source: impl FnMut(Duration) -> io::Result<Option<InputEvent>> + Send + 'static,
+    ) -> io::Result<Self> {
+        let mut reader = Self {
+            sender,
+            source: Some(Box::new(source)),
+            stop: Arc::new(AtomicBool::new(false)),
+            thread: None,
+        };
+        reader.restart()?;
+        Ok(reader)
+    }
+
+    /// Start reading again after [`stop`](Self::stop), from the same source.
+    /// Does nothing if the thread is running; fails if the source was lost
+    /// to a panic.
+    pub fn restart(&mut self) -> io::Result<()> {
+        if self.is_running() {
+            return Ok(());
+        }
+        self.stop();
+        let mut source = self
+            .source
+            .take()
+            .ok_or_else(|| io::Error::other("input source panicked"))?;
+        self.stop.store(false, Ordering::Release);
+        let stopped = Arc::clone(&self.stop);
+        let sender = self.sender.clone();
+        let thread = thread::Builder::new()
+            .name("gilt-input".into())
+            .spawn(move || {
+                while !stopped.load(Ordering::Acquire) {
+                    match source(POLL_INTERVAL) {
+                        Ok(Some(event)) => {
+                            if !sender.send(event) {
+                                break;
+                            }
+                        }
+                        Ok(None) => {}
+                        Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                        Err(e) => {
+                            sender.wake.send(InputFailed(e.to_string()));
+                            break;
+                        }
+                    }
+                }
+                source
+            })?;
+        self.thread = Some(thread);
+        Ok(())
+    }
+
+    /// Whether the reader thread is still running.
+    pub fn is_running(&self) -> bool {
+        self.thread.as_ref().is_some_and(|t| !t.is_finished())
+    }
+
+    /// Stop the thread and wait for it to end, at most about one
+    /// [`POLL_INTERVAL`]. Calling it again does nothing.
+    pub fn stop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        if let Some(thread) = self.thread.take() {
+            if let Ok(source) = thread.join() {
+                self.source = Some(source);
+            }
+        }
+    }
+}
+
+impl Drop for InputReader {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::inbox::Inbox;
+    use crate::event::input::{Key, KeyEvent, Modifiers};
+
+    fn key(c: char) -> InputEvent {
+        InputEvent::Key(KeyEvent::new(Key::Char(c), Modifiers::NONE))
+    }
+
+    #[test]
+    fn injected_events_queue_in_order_and_wake() {
+        let inbox = Inbox::new();
+        let queue = InputQueue::new(inbox.sender());
+        let sender = queue.sender();
+        thread::spawn(move || {
+            sender.send(key('a'));
+            sender.send(key('b'));
+        })
+        .join()
+        .unwrap();
+        assert!(inbox.wait(Some(Duration::ZERO)));
+        assert_eq!(queue.drain(), [key('a'), key('b')]);
+        assert!(queue.drain().is_empty());
+
+        let sender = queue.sender();
+        drop(queue);
+        assert!(!sender.send(key('c')));
+    }
+
+    #[test]
+    fn reader_forwards_source_events_until_stopped() {
+        let inbox = Inbox::new();
+        let queue = InputQueue::new(inbox.sender());
+        let mut script = vec![key('x'), key('y')].into_iter();
+        let mut reader =
+            InputReader::spawn_with(queue.sender(), move |timeout| match script.next() {
+                Some(event) => Ok(Some(event)),
+                None => {
+                    thread::sleep(timeout.min(Duration::from_millis(5)));
+                    Ok(None)
+                }
+            })
+            .unwrap();
+        let mut events = Vec::new();
+        while events.len() < 2 {
+            inbox.wait(Some(Duration::from_millis(100)));
+            events.extend(queue.drain());
+        }
+        assert_eq!(events, [key('x'), key('y')]);
+        assert!(reader.is_running());
+        reader.stop();
+        assert!(!reader.is_running());
+        reader.stop();
+    }
+
+    #[test]
+    fn stopped_reader_restarts_with_its_source() {
+        let inbox = Inbox::new();
+        let queue = InputQueue::new(inbox.sender());
+        let mut next = 'a';
+        let mut reader = InputReader::spawn_with(queue.sender(), move |timeout| {
+            thread::sleep(timeout.min(Duration::from_millis(2)));
+            let event = key(next);
+            next = char::from(next as u8 + 1);
+            Ok(Some(event))
+        })
+        .unwrap();
+        let mut before = Vec::new();
+        while before.is_empty() {
+            inbox.wait(Some(Duration::from_millis(100)));
+            before.extend(queue.drain());
+        }
+        reader.stop();
+        assert!(!reader.is_running());
+        before.extend(queue.drain());
+
+        reader.restart().unwrap();
+        assert!(reader.is_running());
+        let mut after = Vec::new();
+        while after.is_empty() {
+            inbox.wait(Some(Duration::from_millis(100)));
+            after.extend(queue.drain());
+        }
+        // The same source carries on where it stopped.
+        assert_eq!(after[0], key((b'a' + before.len() as u8) as char));
+        reader.stop();
+    }
+
+    #[test]
+    fn reader_ends_on_read_errors() {
+        let inbox = Inbox::new();
+        let queue = InputQueue::new(inbox.sender());
+        let reader =
+            InputReader::spawn_with(queue.sender(), |_| Err(io::Error::other("terminal gone")))
+                .unwrap();
+        while reader.is_running() {
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert!(queue.drain().is_empty());
+        // The failure wakes the loop and says why.
+        assert!(inbox.wait(Some(Duration::ZERO)));
+        let posted = inbox.drain();
+        let failed = posted[0].as_any().downcast_ref::<InputFailed>();
+        assert_eq!(failed, Some(&InputFailed("terminal gone".into())));
+    }
+}
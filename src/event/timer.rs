@@ -0,0 +1,241 @@
+//! One-shot timers and repeating intervals run on the main thread.
+//!
+//! [`Timers`] keeps the pending deadlines; the app fires the due ones each
+//! loop iteration and shortens its poll timeout so it wakes in time for the
+//! next one. Every timer comes with a [`TimerHandle`] that cancels it.
+//!
+//! An interval that falls behind (a slow frame, a suspended terminal) fires
+//! once and then resumes one period after the late firing, rather than
+//! bursting to catch up.
+//!
+//! ```ignore
+//! let spinner = app.set_interval(Duration::from_millis(80), |app| {
+//!     app.screen.compositor.mark_all_dirty();
+//! });
+//! app.set_timer(Duration::from_secs(5), move |_| spinner.cancel());
+//! ```
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// The shortest interval period; shorter periods are raised to it.
+pub const MIN_PERIOD: Duration = Duration::from_millis(1);
+
+// ---------------------------------------------------------------------------
+// TimerHandle
+// ---------------------------------------------------------------------------
+
+/// A handle to a scheduled timer. Cloning shares the same timer.
+#[derive(Debug, Clone, Default)]
+pub struct TimerHandle {
+    cancelled: Rc<Cell<bool>>,
+}
+
+impl TimerHandle {
+    /// Stop the timer. A one-shot timer that has not fired never will, and
+    /// an interval fires no more.
+    pub fn cancel(&self) {
+        self.cancelled.set(true);
+    }
+
+    /// Whether the timer was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.get()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Timer
+// ---------------------------------------------------------------------------
+
+/// A scheduled timer and the callback it runs.
+#[derive(Debug)]
+pub struct Timer<C> {
+    deadline: Instant,
+    /// `Some` for intervals.
+    period: Option<Duration>,
+    handle: TimerHandle,
+    /// What to run when the timer fires.
+    pub callback: C,
+}
+
+impl<C> Timer<C> {
+    /// When the timer is (or was) due.
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    /// The repeat period, for intervals.
+    pub fn period(&self) -> Option<Duration> {
+        self.period
+    }
+
+    /// The timer's handle.
+    pub fn handle(&self) -> &TimerHandle {
+        &self.handle
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Timers
+// ---------------------------------------------------------------------------
+
+/// Pending timers, in no particular order.
+#[derive(Debug)]
+pub struct Timers<C> {
+    timers: Vec<Timer<C>>,
+}
+
+impl<C> Timers<C> {
+    /// Create an empty set.
+    pub fn new() -> Self {
+        Self { timers: Vec::new() }
+    }
+
+    /// Schedule `callback` to fire once at `deadline`.
+    pub fn once(&mut self, deadline: Instant, callback: C) -> TimerHandle {
+        self.insert(deadline, None, callback)
+    }
+
+    /// Schedule `callback` to fire every `period`, first at `start + period`.
+    pub fn every(&mut self, start: Instant, period: Duration, callback: C) -> TimerHandle {
+        let period = period.max(MIN_PERIOD);
+        self.insert(start + period, Some(period), callback)
+    }
+
+    /// Number of timers that have not been cancelled.
+    pub fn len(&self) -> usize {
+        self.timers
+            .iter()
+            .filter(|t| !t.handle.is_cancelled())
+            .count()
+    }
+
+    /// Whether no timer is pending.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The earliest deadline of a timer that has not been cancelled.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.timers
+            .iter()
+            .filter(|t| !t.handle.is_cancelled())
+            .map(|t| t.deadline)
+            .min()
+    }
+
+    /// Remove and return the timers due at `now`, earliest first, dropping
+    /// cancelled ones. Pass intervals back to [`requeue`](Self::requeue)
+    /// after running them.
+    pub fn take_due(&mut self, now: Instant) -> Vec<Timer<C>> {
+        self.timers.retain(|t| !t.handle.is_cancelled());
+        let (mut due, pending) = std::mem::take(&mut self.timers)
+            .into_iter()
+            .partition(|t: &Timer<C>| t.deadline <= now);
+        self.timers = pending;
+        due.sort_by_key(|t| t.deadline);
+        due
+    }
+
+    /// Schedule a fired interval's next run, unless it was cancelled. One-shot
+    /// timers are dropped. Returns whether the timer was rescheduled.
+    pub fn requeue(&mut self, mut timer: Timer<C>, now: Instant) -> bool {
+        let Some(period) = timer.period else {
+            return false;
+        };
+        if timer.handle.is_cancelled() {
+            return false;
+        }
+        timer.deadline += period;
+        if timer.deadline <= now {
+            timer.deadline = now + period;
+        }
+        self.timers.push(timer);
+        true
+    }
+
+    fn insert(&mut self, deadline: Instant, period: Option<Duration>, callback: C) -> TimerHandle {
+        let handle = TimerHandle::default();
+        self.timers.push(Timer {
+            deadline,
+            period,
+            handle: handle.clone(),
+            callback,
+        });
+        handle
+    }
+}
+
+impl<C> Default for Timers<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn due_timers_fire_in_deadline_order() {
+        let start = Instant::now();
+        let mut timers = Timers::new();
+        timers.once(start + ms(30), "late");
+        timers.once(start + ms(10), "early");
+        timers.once(start + ms(100), "pending");
+        assert_eq!(timers.next_deadline(), Some(start + ms(10)));
+
+        let due: Vec<&str> = timers
+            .take_due(start + ms(50))
+            .into_iter()
+            .map(|t| t.callback)
+            .collect();
+        assert_eq!(due, ["early", "late"]);
+        assert_eq!(timers.len(), 1);
+        assert_eq!(timers.next_deadline(), Some(start + ms(100)));
+    }
+
+    #[test]
+    fn intervals_repeat_without_bursting() {
+        let start = Instant::now();
+        let mut timers = Timers::new();
+        timers.every(start, ms(10), ());
+        let fired = timers.take_due(start + ms(10)).pop().unwrap();
+        assert!(timers.requeue(fired, start + ms(10)));
+        assert_eq!(timers.next_deadline(), Some(start + ms(20)));
+
+        // Far behind schedule: one firing, then a full period from now.
+        let fired = timers.take_due(start + ms(95)).pop().unwrap();
+        assert!(timers.take_due(start + ms(95)).is_empty());
+        timers.requeue(fired, start + ms(95));
+        assert_eq!(timers.next_deadline(), Some(start + ms(105)));
+    }
+
+    #[test]
+    fn cancelled_timers_never_fire() {
+        let start = Instant::now();
+        let mut timers = Timers::new();
+        let once = timers.once(start, 1);
+        let interval = timers.every(start, ms(5), 2);
+        once.cancel();
+        assert_eq!(timers.len(), 1);
+
+        let fired = timers.take_due(start + ms(5)).pop().unwrap();
+        assert_eq!(fired.callback, 2);
+        fired.handle().cancel();
+        assert!(interval.is_cancelled());
+        assert!(!timers.requeue(fired, start + ms(5)));
+        assert!(timers.is_empty());
+        assert_eq!(timers.next_deadline(), None);
+    }
+}
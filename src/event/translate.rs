@@ -0,0 +1,238 @@
+//! Key translation tables applied before binding lookup.
+//!
+//! Shortcuts are bound to Latin keys (`ctrl+1`, `g g`), but a user on an
+//! Arabic, Persian or Devanagari layout types `١` where the binding expects
+//! `1`. A [`KeyTable`] maps the keys a layout produces to the keys bindings
+//! are written with, so the app can support such layouts without binding
+//! every shortcut twice. Tables can also remap whole key events, e.g. to
+//! turn a spare key into a leader chord.
+//!
+//! Tables are registered per app with `App::add_key_table` and collected in
+//! a [`KeyTranslator`]. Translation only affects binding lookup: panels, the
+//! command palette and widgets' own key handling see the key that was
+//! actually typed.
+//!
+//! ```ignore
+//! app.add_key_table(KeyTable::new("arabic-digits").with_digits('٠'));
+//! app.add_key_table(KeyTable::new("leader").with_event_str("§", "ctrl+b")?);
+//! ```
+
+use std::collections::HashMap;
+
+use super::keys::{parse_key, KeyParseError};
+use super::input::{Key, KeyEvent};
+
+// ---------------------------------------------------------------------------
+// KeyTable
+// ---------------------------------------------------------------------------
+
+/// A named set of key translations.
+///
+/// Key mappings keep the event's modifiers (`ctrl+١` becomes `ctrl+1`);
+/// event mappings replace the whole event and are checked first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyTable {
+    name: String,
+    keys: HashMap<Key, Key>,
+    events: HashMap<KeyEvent, KeyEvent>,
+}
+
+impl KeyTable {
+    /// Create an empty table called `name`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            keys: HashMap::new(),
+            events: HashMap::new(),
+        }
+    }
+
+    /// Translate `from` to `to` under any modifiers (builder pattern).
+    pub fn with_key(mut self, from: Key, to: Key) -> Self {
+        self.keys.insert(from, to);
+        self
+    }
+
+    /// Translate the ten consecutive characters starting at `zero` to the
+    /// digits `0`–`9` (builder pattern), e.g. `'٠'` for Arabic-Indic or
+    /// `'०'` for Devanagari digits.
+    pub fn with_digits(mut self, zero: char) -> Self {
+        for (offset, digit) in ('0'..='9').enumerate() {
+            if let Some(local) = char::from_u32(zero as u32 + offset as u32) {
+                self.keys.insert(Key::Char(local), Key::Char(digit));
+            }
+        }
+        self
+    }
+
+    /// Translate each character of `from` to the character at the same
+    /// position in `to` (builder pattern); extra characters are ignored.
+    pub fn with_chars(mut self, from: &str, to: &str) -> Self {
+        for (local, latin) in from.chars().zip(to.chars()) {
+            self.keys.insert(Key::Char(local), Key::Char(latin));
+        }
+        self
+    }
+
+    /// Translate the exact event `from`, modifiers included, to `to`
+    /// (builder pattern).
+    pub fn with_event(mut self, from: KeyEvent, to: KeyEvent) -> Self {
+        self.events.insert(from, to);
+        self
+    }
+
+    /// Like [`with_event`](Self::with_event), with both events written in
+    /// binding syntax, e.g. `("f12", "ctrl+b")`.
+    pub fn with_event_str(self, from: &str, to: &str) -> Result<Self, KeyParseError> {
+        let (from, to) = (parse_key(from)?, parse_key(to)?);
+        Ok(self.with_event(from, to))
+    }
+
+    /// The table's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The translation of `event`, or `None` if the table does not cover it.
+    pub fn translate(&self, event: &KeyEvent) -> Option<KeyEvent> {
+        if let Some(to) = self.events.get(event) {
+            return Some(*to);
+        }
+        let code = self.keys.get(&event.code)?;
+        Some(KeyEvent::new(*code, event.modifiers))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// KeyTranslator
+// ---------------------------------------------------------------------------
+
+/// The key tables registered with an app, applied in registration order.
+#[derive(Debug, Clone, Default)]
+pub struct KeyTranslator {
+    tables: Vec<KeyTable>,
+}
+
+impl KeyTranslator {
+    /// Create a translator with no tables.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `table`, replacing a table with the same name in place.
+    pub fn add(&mut self, table: KeyTable) {
+        match self.tables.iter_mut().find(|t| t.name == table.name) {
+            Some(existing) => *existing = table,
+            None => self.tables.push(table),
+        }
+    }
+
+    /// Remove the table called `name`. Returns whether there was one.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.tables.len();
+        self.tables.retain(|t| t.name != name);
+        self.tables.len() != before
+    }
+
+    /// The registered tables, in the order they are applied.
+    pub fn tables(&self) -> &[KeyTable] {
+        &self.tables
+    }
+
+    /// Whether no table is registered.
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    /// Run `event` through every table in turn; each table sees the previous
+    /// one's output. Keys no table covers come back unchanged.
+    pub fn translate(&self, event: &KeyEvent) -> KeyEvent {
+        self.tables.iter().fold(*event, |event, table| {
+            table.translate(&event).unwrap_or(event)
+        })
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::input::Modifiers;
+
+    fn ch(c: char, modifiers: Modifiers) -> KeyEvent {
+        KeyEvent::new(Key::Char(c), modifiers)
+    }
+
+    #[test]
+    fn digit_tables_keep_modifiers() {
+        let table = KeyTable::new("arabic").with_digits('٠');
+        assert_eq!(
+            table.translate(&ch('٣', Modifiers::CTRL)),
+            Some(ch('3', Modifiers::CTRL))
+        );
+        assert_eq!(
+            table.translate(&ch('٩', Modifiers::NONE)),
+            Some(ch('9', Modifiers::NONE))
+        );
+        assert_eq!(table.translate(&ch('3', Modifiers::NONE)), None);
+
+        let table = KeyTable::new("russian").with_chars("йцук", "qwer");
+        assert_eq!(
+            table.translate(&ch('ц', Modifiers::ALT)),
+            Some(ch('w', Modifiers::ALT))
+        );
+    }
+
+    #[test]
+    fn event_mappings_win_and_parse_binding_syntax() {
+        let table = KeyTable::new("leader")
+            .with_chars("§", "x")
+            .with_event_str("§", "ctrl+b")
+            .unwrap();
+        assert_eq!(
+            table.translate(&ch('§', Modifiers::NONE)),
+            Some(ch('b', Modifiers::CTRL))
+        );
+        // Other modifiers fall back to the key mapping.
+        assert_eq!(
+            table.translate(&ch('§', Modifiers::ALT)),
+            Some(ch('x', Modifiers::ALT))
+        );
+        assert!(KeyTable::new("bad")
+            .with_event_str("hyperspace", "a")
+            .is_err());
+    }
+
+    #[test]
+    fn translator_chains_tables_in_order() {
+        let mut translator = KeyTranslator::new();
+        translator.add(KeyTable::new("persian").with_digits('۰'));
+        translator.add(
+            KeyTable::new("leader")
+                .with_event_str("1", "ctrl+b")
+                .unwrap(),
+        );
+        assert_eq!(
+            translator.translate(&ch('۱', Modifiers::NONE)),
+            ch('b', Modifiers::CTRL)
+        );
+        assert_eq!(
+            translator.translate(&ch('q', Modifiers::NONE)),
+            ch('q', Modifiers::NONE)
+        );
+
+        // Re-adding a name replaces the table where it was.
+        translator.add(KeyTable::new("persian"));
+        assert_eq!(translator.tables()[0].name(), "persian");
+        assert_eq!(
+            translator.translate(&ch('۱', Modifiers::NONE)),
+            ch('۱', Modifiers::NONE)
+        );
+        assert!(translator.remove("leader"));
+        assert!(!translator.remove("leader"));
+        assert_eq!(translator.tables().len(), 1);
+    }
+}
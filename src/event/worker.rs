@@ -0,0 +1,251 @@
+//! Background workers whose results come back as messages.
+//!
+//! [`spawn_worker`] runs a closure on its own thread and posts the message it
+//! returns into the app's [`Inbox`](super::inbox::Inbox), waking the loop;
+//! `App::pump_external` then moves it onto the dispatcher like any other
+//! message. A worker can also post progress messages along the way through
+//! its [`WorkerContext`].
+//!
+//! A cancelled worker's result is dropped, and a worker that panics posts
+//! [`WorkerFailed`] instead of a result, so rendering never blocks on or
+//! loses track of background work. Both rules live in [`BackgroundThread`],
+//! which per-widget tasks (`crate::widget::task`) are built on as well.
+//!
+//! ```ignore
+//! app.spawn_worker(|ctx| {
+//!     let body = http_get("https://example.com/feed.json");
+//!     FeedLoaded(parse(body))
+//! });
+//! ```
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use super::inbox::InboxSender;
+use super::message::Message;
+
+/// Posted in place of a result when a worker or widget task panics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkerFailed {
+    /// The panic message, if it was a string.
+    pub reason: String,
+}
+
+impl Message for WorkerFailed {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn message_name(&self) -> &str {
+        "WorkerFailed"
+    }
+}
+
+// ---------------------------------------------------------------------------
+// BackgroundThread
+// ---------------------------------------------------------------------------
+
+/// A cancellable background thread: the primitive under workers and
+/// widget tasks. Cloning shares the same thread.
+///
+/// The thread's result is delivered only if it was not cancelled, and a
+/// panic is caught and delivered as [`WorkerFailed`], so the thread is
+/// always marked finished.
+#[derive(Debug, Clone, Default)]
+pub struct BackgroundThread {
+    cancelled: Arc<AtomicBool>,
+    finished: Arc<AtomicBool>,
+}
+
+impl BackgroundThread {
+    /// A thread that has not been spawned yet, so contexts can share its
+    /// cancellation flag before it starts.
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `work` on a new thread, then hand its result (or its panic as
+    /// [`WorkerFailed`]) to `deliver` unless the thread was cancelled.
+    pub(crate) fn spawn<T: 'static>(
+        &self,
+        work: impl FnOnce() -> T + Send + 'static,
+        deliver: impl FnOnce(Result<T, WorkerFailed>) + Send + 'static,
+    ) {
+        let thread = self.clone();
+        thread::spawn(move || {
+            let result = panic::catch_unwind(AssertUnwindSafe(work));
+            thread.finished.store(true, Ordering::Release);
+            if thread.is_cancelled() {
+                return;
+            }
+            deliver(result.map_err(|payload| WorkerFailed {
+                reason: panic_reason(payload.as_ref()),
+            }));
+        });
+    }
+
+    /// Ask the thread to stop and drop its result.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    /// Whether the thread was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Whether the thread's closure has returned (or panicked).
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Acquire)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// WorkerHandle
+// ---------------------------------------------------------------------------
+
+/// A handle to a running worker. Cloning shares the same worker.
+#[derive(Debug, Clone)]
+pub struct WorkerHandle {
+    thread: BackgroundThread,
+}
+
+impl WorkerHandle {
+    /// Ask the worker to stop and drop its result and any further progress.
+    pub fn cancel(&self) {
+        self.thread.cancel();
+    }
+
+    /// Whether the worker was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.thread.is_cancelled()
+    }
+
+    /// Whether the worker's closure has returned (or panicked).
+    pub fn is_finished(&self) -> bool {
+        self.thread.is_finished()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// WorkerContext
+// ---------------------------------------------------------------------------
+
+/// What a running worker can see of the app.
+#[derive(Debug)]
+pub struct WorkerContext {
+    thread: BackgroundThread,
+    sender: InboxSender,
+}
+
+impl WorkerContext {
+    /// Whether the worker has been cancelled. Long-running workers should
+    /// check between steps and return early.
+    pub fn is_cancelled(&self) -> bool {
+        self.thread.is_cancelled()
+    }
+
+    /// Post a progress message. Returns `false` once the worker is cancelled
+    /// or the app has gone away.
+    pub fn post(&self, message: impl Message) -> bool {
+        !self.is_cancelled() && self.sender.send(message)
+    }
+}
+
+/// Run `work` on a new thread and post the message it returns through
+/// `sender`.
+pub fn spawn_worker<M: Message>(
+    sender: InboxSender,
+    work: impl FnOnce(&WorkerContext) -> M + Send + 'static,
+) -> WorkerHandle {
+    let thread = BackgroundThread::new();
+    let ctx = WorkerContext {
+        thread: thread.clone(),
+        sender: sender.clone(),
+    };
+    thread.spawn(
+        move || work(&ctx),
+        move |result| {
+            match result {
+                Ok(message) => sender.send(message),
+                Err(failed) => sender.send(failed),
+            };
+        },
+    );
+    WorkerHandle { thread }
+}
+
+/// The text of a panic payload.
+fn panic_reason(payload: &(dyn Any + Send)) -> String {
+    if let Some(text) = payload.downcast_ref::<&str>() {
+        (*text).to_owned()
+    } else if let Some(text) = payload.downcast_ref::<String>() {
+        text.clone()
+    } else {
+        "background thread panicked".to_owned()
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::inbox::Inbox;
+    use crate::event::message::{Custom, Refresh};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    fn wait(inbox: &Inbox) {
+        assert!(inbox.wait(Some(Duration::from_secs(5))), "timed out");
+    }
+
+    #[test]
+    fn result_and_progress_are_posted() {
+        let inbox = Inbox::new();
+        let handle = spawn_worker(inbox.sender(), |ctx| {
+            assert!(ctx.post(Refresh));
+            Custom::new("done")
+        });
+        let mut names = Vec::new();
+        while names.len() < 2 {
+            wait(&inbox);
+            names.extend(inbox.drain().iter().map(|m| m.message_name().to_owned()));
+        }
+        assert_eq!(names, ["Refresh", "Custom"]);
+        assert!(handle.is_finished());
+    }
+
+    #[test]
+    fn cancelled_worker_posts_nothing() {
+        let inbox = Inbox::new();
+        let (go_tx, go_rx) = mpsc::channel::<()>();
+        let (done_tx, done_rx) = mpsc::channel();
+        let handle = spawn_worker(inbox.sender(), move |ctx| {
+            go_rx.recv().unwrap();
+            done_tx.send(ctx.post(Refresh)).unwrap();
+            Refresh
+        });
+        handle.cancel();
+        go_tx.send(()).unwrap();
+        assert!(!done_rx.recv().unwrap());
+        while !handle.is_finished() {
+            thread::yield_now();
+        }
+        assert!(inbox.drain().is_empty());
+    }
+
+    #[test]
+    fn panics_become_worker_failed() {
+        let inbox = Inbox::new();
+        spawn_worker(inbox.sender(), |_| -> Refresh { panic!("no network") });
+        wait(&inbox);
+        let posted = inbox.drain();
+        let failed = posted[0].as_any().downcast_ref::<WorkerFailed>().unwrap();
+        assert_eq!(failed.reason, "no network");
+    }
+}
@@ -11,6 +11,7 @@ use std::ops::{Add, Neg, Sub, Mul};
 
 /// A 2D displacement or position delta in terminal cells.
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Offset {
     pub x: i32,
     pub y: i32,
@@ -80,6 +81,7 @@ impl Mul<i32> for Offset {
 
 /// A 2D size in terminal cells (width x height).
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Size {
     pub width: i32,
     pub height: i32,
@@ -139,6 +141,7 @@ impl Sub for Size {
 /// This is the most heavily-used geometry type. The `intersection`, `contains`,
 /// and property methods are marked `#[inline]` for performance.
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Region {
     pub x: i32,
     pub y: i32,
@@ -156,16 +159,16 @@ impl Region {
         Self { x, y, width, height }
     }
 
-    /// The right edge (exclusive): `x + width`.
+    /// The right edge (exclusive): `x + width`, saturating at `i32::MAX`.
     #[inline]
     pub const fn right(self) -> i32 {
-        self.x + self.width
+        self.x.saturating_add(self.width)
     }
 
-    /// The bottom edge (exclusive): `y + height`.
+    /// The bottom edge (exclusive): `y + height`, saturating at `i32::MAX`.
     #[inline]
     pub const fn bottom(self) -> i32 {
-        self.y + self.height
+        self.y.saturating_add(self.height)
     }
 
     /// The top-left corner as an [`Offset`].
@@ -221,8 +224,8 @@ impl Region {
         let ob = other.bottom();
         let y2 = if sb < ob { sb } else { ob };
 
-        let w = x2 - x1;
-        let h = y2 - y1;
+        let w = x2.saturating_sub(x1);
+        let h = y2.saturating_sub(y1);
 
         if w <= 0 || h <= 0 {
             Region::EMPTY
@@ -342,6 +345,7 @@ impl Region {
 
 /// Spacing around the four sides of a rectangle, used for margin and padding.
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Spacing {
     pub top: i32,
     pub right: i32,
@@ -454,7 +458,7 @@ mod tests {
     #[test]
     fn offset_mul_scalar() {
         assert_eq!(Offset::new(2, -3) * 4, Offset::new(8, -12));
-        assert_eq!(Offset::new(1, 1) * 0, Offset::new(0, 0));
+        assert_eq!(Offset::new(1, 1) * -1, Offset::new(-1, -1));
     }
 
     #[test]
@@ -635,6 +639,16 @@ mod tests {
         assert_eq!(inner.intersection(outer), inner);
     }
 
+    #[test]
+    fn region_edges_saturate() {
+        let huge = Region::new(i32::MAX - 1, i32::MIN, 10, 10);
+        assert_eq!(huge.right(), i32::MAX);
+        assert_eq!(huge.bottom(), i32::MIN + 10);
+        let screen = Region::new(0, 0, 80, 24);
+        assert_eq!(Region::new(i32::MIN, 0, i32::MAX, 1).intersection(screen), Region::EMPTY);
+        assert_eq!(huge.intersection(screen), Region::EMPTY);
+    }
+
     // -----------------------------------------------------------------------
     // Region — union
     // -----------------------------------------------------------------------
@@ -0,0 +1,227 @@
+//! Runtime string catalogs for built-in and app text.
+//!
+//! Text is looked up by key in the catalog of the active locale, falling back
+//! to the built-in English strings and finally to the key itself. Apps add
+//! their own catalogs (or override built-in keys) with [`add_catalog`] and
+//! switch languages at runtime with [`set_locale`]; widgets look their strings
+//! up while rendering, so the next frame is drawn in the new language. Use
+//! [`App::set_locale`](crate::app::App::set_locale) to also repaint the
+//! screen.
+//!
+//! Messages may contain `{name}` placeholders filled in by [`t!`](crate::t):
+//!
+//! ```ignore
+//! i18n::add_catalog(
+//!     Catalog::new("de")
+//!         .with("common.cancel", "Abbrechen")
+//!         .with("inbox.unread", "{count} ungelesen"),
+//! );
+//! i18n::set_locale("de");
+//! view! {
+//!     Button(t!("common.cancel"))
+//!     Static(t!("inbox.unread", count = unread))
+//! }
+//! ```
+//!
+//! Catalogs are per thread, like the reactive runtime.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+
+/// The locale the built-in strings are written in.
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Keys used by built-in widgets, with their English text.
+const BUILTIN: &[(&str, &str)] = &[
+    ("common.ok", "OK"),
+    ("common.cancel", "Cancel"),
+    ("code-view.copy", "copy"),
+    ("code-view.copied", "copied ✓"),
+    ("data-table.loading", "Loading…"),
+    ("error-boundary.error", "Error: {message}"),
+    ("filter-bar.matches", "{count} matches"),
+    ("list-view.loading", "Loading…"),
+    ("multi-progress.completed", "✓ {count} completed"),
+];
+
+// ---------------------------------------------------------------------------
+// Catalog
+// ---------------------------------------------------------------------------
+
+/// The messages of one locale, keyed by message id.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Catalog {
+    locale: String,
+    messages: HashMap<String, String>,
+}
+
+impl Catalog {
+    /// Create an empty catalog for `locale`.
+    pub fn new(locale: impl Into<String>) -> Self {
+        Self {
+            locale: locale.into(),
+            messages: HashMap::new(),
+        }
+    }
+
+    /// Add a message (builder pattern).
+    pub fn with(mut self, key: impl Into<String>, message: impl Into<String>) -> Self {
+        self.insert(key, message);
+        self
+    }
+
+    /// Add a message, replacing any with the same key.
+    pub fn insert(&mut self, key: impl Into<String>, message: impl Into<String>) {
+        self.messages.insert(key.into(), message.into());
+    }
+
+    /// The locale these messages are for.
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// The message for `key`, if any.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.messages.get(key).map(String::as_str)
+    }
+
+    /// Number of messages.
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Whether the catalog has no messages.
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> Extend<(K, V)> for Catalog {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, messages: I) {
+        for (key, message) in messages {
+            self.insert(key, message);
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Active locale
+// ---------------------------------------------------------------------------
+
+struct I18n {
+    locale: String,
+    catalogs: HashMap<String, Catalog>,
+}
+
+thread_local! {
+    static I18N: RefCell<I18n> = RefCell::new(I18n {
+        locale: DEFAULT_LOCALE.to_owned(),
+        catalogs: HashMap::new(),
+    });
+}
+
+/// Merge `catalog` into the messages of its locale. Later messages replace
+/// earlier ones with the same key, including built-in ones.
+pub fn add_catalog(catalog: Catalog) {
+    I18N.with(|i18n| {
+        let mut i18n = i18n.borrow_mut();
+        let locale = catalog.locale.clone();
+        i18n.catalogs
+            .entry(locale.clone())
+            .or_insert_with(|| Catalog::new(locale))
+            .extend(catalog.messages);
+    });
+}
+
+/// Switch the active locale. Locales without a catalog fall back to the
+/// built-in strings.
+pub fn set_locale(locale: impl Into<String>) {
+    I18N.with(|i18n| i18n.borrow_mut().locale = locale.into());
+}
+
+/// The active locale.
+pub fn locale() -> String {
+    I18N.with(|i18n| i18n.borrow().locale.clone())
+}
+
+/// The message for `key` in the active locale.
+pub fn translate(key: &str) -> String {
+    I18N.with(|i18n| {
+        let i18n = i18n.borrow();
+        let lookup = |locale: &str| i18n.catalogs.get(locale).and_then(|c| c.get(key));
+        lookup(&i18n.locale)
+            .or_else(|| lookup(DEFAULT_LOCALE))
+            .or_else(|| {
+                BUILTIN
+                    .iter()
+                    .find(|(builtin, _)| *builtin == key)
+                    .map(|(_, message)| *message)
+            })
+            .unwrap_or(key)
+            .to_owned()
+    })
+}
+
+/// The message for `key` with each `{name}` replaced by its argument.
+pub fn translate_with(key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut message = translate(key);
+    for (name, value) in args {
+        message = message.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    message
+}
+
+/// Look up a message in the active locale: `t!("key")`, or
+/// `t!("key", name = value, ...)` to fill in `{name}` placeholders.
+#[macro_export]
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::translate($key)
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::translate_with(
+            $key,
+            &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),+],
+        )
+    };
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_strings_and_missing_keys() {
+        assert_eq!(translate("common.cancel"), "Cancel");
+        assert_eq!(translate("no.such.key"), "no.such.key");
+        assert_eq!(t!("filter-bar.matches", count = 3), "3 matches");
+    }
+
+    #[test]
+    fn switching_locale_and_falling_back() {
+        add_catalog(Catalog::new("fr").with("common.cancel", "Annuler"));
+        set_locale("fr");
+        assert_eq!(locale(), "fr");
+        assert_eq!(t!("common.cancel"), "Annuler");
+        // Keys the catalog lacks fall back to English.
+        assert_eq!(t!("common.ok"), "OK");
+        set_locale("en");
+        assert_eq!(t!("common.cancel"), "Cancel");
+    }
+
+    #[test]
+    fn apps_extend_and_override() {
+        add_catalog(Catalog::new("en").with("inbox.unread", "{count} unread in {folder}"));
+        add_catalog(Catalog::new("en").with("common.ok", "Okay"));
+        assert_eq!(t!("common.ok"), "Okay");
+        assert_eq!(
+            t!("inbox.unread", count = 2, folder = "Work"),
+            "2 unread in Work"
+        );
+    }
+}
@@ -10,9 +10,10 @@ use taffy::prelude::*;
 use crate::css::styles::Styles;
 use crate::dom::node::NodeId;
 use crate::dom::tree::Dom;
+use crate::error::LayoutError;
 use crate::geometry::Region;
 
-use super::resolve::resolve_styles;
+use super::resolve::{apply_fr, resolve_styles};
 
 /// Wraps a [`TaffyTree`] and maintains a mapping from DOM [`NodeId`]s to
 /// taffy node ids. Provides methods to sync, compute, and query layout.
@@ -23,6 +24,8 @@ pub struct LayoutEngine {
     node_map: HashMap<NodeId, taffy::prelude::NodeId>,
     /// The taffy root node, if a layout has been synced.
     root: Option<taffy::prelude::NodeId>,
+    /// The size of the root unit `1u`, in cells.
+    unit_scale: f32,
 }
 
 impl LayoutEngine {
@@ -32,9 +35,21 @@ impl LayoutEngine {
             tree: TaffyTree::new(),
             node_map: HashMap::new(),
             root: None,
+            unit_scale: 1.0,
         }
     }
 
+    /// The size of the root unit `1u`, in cells.
+    pub fn unit_scale(&self) -> f32 {
+        self.unit_scale
+    }
+
+    /// Set the size of the root unit `1u`, in cells. Takes effect at the
+    /// next [`sync_tree`](Self::sync_tree).
+    pub fn set_unit_scale(&mut self, scale: f32) {
+        self.unit_scale = scale;
+    }
+
     /// Synchronize the taffy tree with the DOM structure.
     ///
     /// Walks the DOM depth-first from the root, creating or updating taffy nodes
@@ -49,13 +64,13 @@ impl LayoutEngine {
         dom: &Dom,
         styles: &HashMap<NodeId, Styles>,
         viewport: (u16, u16),
-    ) {
+    ) -> Result<(), LayoutError> {
         let dom_root = match dom.root() {
             Some(r) => r,
             None => {
                 // Empty DOM: clear everything.
                 self.clear();
-                return;
+                return Ok(());
             }
         };
 
@@ -72,24 +87,26 @@ impl LayoutEngine {
             .collect();
         for key in stale_keys {
             if let Some(taffy_id) = self.node_map.remove(&key) {
-                let _ = self.tree.remove(taffy_id);
+                self.tree.remove(taffy_id)?;
             }
         }
 
         // Create or update taffy nodes for all live DOM nodes.
+        let unstyled = Styles::default();
         for &dom_id in &live_nodes {
             let node_styles = styles.get(&dom_id).cloned().unwrap_or_default();
-            let taffy_style = resolve_styles(&node_styles, viewport);
+            let mut taffy_style = resolve_styles(&node_styles, viewport, self.unit_scale);
+            let parent_styles = dom
+                .parent(dom_id)
+                .map(|parent| styles.get(&parent).unwrap_or(&unstyled));
+            apply_fr(&mut taffy_style, &node_styles, parent_styles);
 
             if let Some(&taffy_id) = self.node_map.get(&dom_id) {
                 // Update existing node's style.
-                let _ = self.tree.set_style(taffy_id, taffy_style);
+                self.tree.set_style(taffy_id, taffy_style)?;
             } else {
                 // Create new taffy node.
-                let taffy_id = self
-                    .tree
-                    .new_leaf_with_context(taffy_style, dom_id)
-                    .expect("taffy node creation should not fail");
+                let taffy_id = self.tree.new_leaf_with_context(taffy_style, dom_id)?;
                 self.node_map.insert(dom_id, taffy_id);
             }
         }
@@ -103,28 +120,35 @@ impl LayoutEngine {
                 .collect();
 
             if let Some(&taffy_id) = self.node_map.get(&dom_id) {
-                let _ = self.tree.set_children(taffy_id, &taffy_children);
+                self.tree.set_children(taffy_id, &taffy_children)?;
             }
         }
 
         // Set the taffy root.
         self.root = self.node_map.get(&dom_root).copied();
+        Ok(())
     }
 
     /// Run taffy layout computation on the root node.
     ///
     /// `available_width` and `available_height` define the available space,
-    /// typically the terminal size in cells.
-    pub fn compute(&mut self, available_width: f32, available_height: f32) {
+    /// typically the terminal size in cells. Does nothing before a tree has
+    /// been synced.
+    pub fn compute(
+        &mut self,
+        available_width: f32,
+        available_height: f32,
+    ) -> Result<(), LayoutError> {
         if let Some(root) = self.root {
-            let _ = self.tree.compute_layout(
+            self.tree.compute_layout(
                 root,
                 taffy::geometry::Size {
                     width: AvailableSpace::Definite(available_width),
                     height: AvailableSpace::Definite(available_height),
                 },
-            );
+            )?;
         }
+        Ok(())
     }
 
     /// Get the layout result for a single DOM node as a [`Region`].
@@ -163,12 +187,9 @@ impl LayoutEngine {
 
     /// Clear all state, removing all taffy nodes and mappings.
     fn clear(&mut self) {
-        // Remove all nodes from taffy.
-        let keys: Vec<_> = self.node_map.drain().map(|(_, v)| v).collect();
-        for taffy_id in keys {
-            let _ = self.tree.remove(taffy_id);
-        }
         self.root = None;
+        self.node_map.clear();
+        self.tree.clear();
     }
 }
 
@@ -215,7 +236,7 @@ mod tests {
         let dom = Dom::new();
         let styles = HashMap::new();
         let mut engine = LayoutEngine::new();
-        engine.sync_tree(&dom, &styles, VP);
+        engine.sync_tree(&dom, &styles, VP).unwrap();
         assert!(engine.root.is_none());
         assert!(engine.node_map.is_empty());
     }
@@ -226,7 +247,7 @@ mod tests {
         let root = dom.insert(NodeData::new("Root"));
         let styles = HashMap::new();
         let mut engine = LayoutEngine::new();
-        engine.sync_tree(&dom, &styles, VP);
+        engine.sync_tree(&dom, &styles, VP).unwrap();
         assert!(engine.root.is_some());
         assert!(engine.node_map.contains_key(&root));
     }
@@ -236,7 +257,7 @@ mod tests {
         let (dom, root, a, b) = simple_dom();
         let styles = HashMap::new();
         let mut engine = LayoutEngine::new();
-        engine.sync_tree(&dom, &styles, VP);
+        engine.sync_tree(&dom, &styles, VP).unwrap();
 
         assert!(engine.node_map.contains_key(&root));
         assert!(engine.node_map.contains_key(&a));
@@ -268,8 +289,8 @@ mod tests {
         styles.insert(b, b_style);
 
         let mut engine = LayoutEngine::new();
-        engine.sync_tree(&dom, &styles, VP);
-        engine.compute(80.0, 24.0);
+        engine.sync_tree(&dom, &styles, VP).unwrap();
+        engine.compute(80.0, 24.0).unwrap();
 
         let root_layout = engine.get_layout(root).unwrap();
         assert_eq!(root_layout.width, 80);
@@ -306,8 +327,8 @@ mod tests {
         styles.insert(b, b_style);
 
         let mut engine = LayoutEngine::new();
-        engine.sync_tree(&dom, &styles, VP);
-        engine.compute(80.0, 24.0);
+        engine.sync_tree(&dom, &styles, VP).unwrap();
+        engine.compute(80.0, 24.0).unwrap();
 
         let a_layout = engine.get_layout(a).unwrap();
         assert_eq!(a_layout.x, 0);
@@ -318,6 +339,67 @@ mod tests {
         assert_eq!(b_layout.width, 50);
     }
 
+    #[test]
+    fn gap_spaces_children() {
+        let (dom, root, a, b) = simple_dom();
+        let mut styles = HashMap::new();
+
+        let mut root_style = Styles::new();
+        root_style.layout = Some(LayoutDirection::Horizontal);
+        root_style.width = Some(Scalar::cells(80.0));
+        root_style.height = Some(Scalar::cells(24.0));
+        root_style.column_gap = Some(Scalar::cells(2.0));
+        styles.insert(root, root_style);
+        for child in [a, b] {
+            let mut child_style = Styles::new();
+            child_style.width = Some(Scalar::cells(10.0));
+            styles.insert(child, child_style);
+        }
+
+        let mut engine = LayoutEngine::new();
+        engine.sync_tree(&dom, &styles, VP).unwrap();
+        engine.compute(80.0, 24.0).unwrap();
+
+        assert_eq!(engine.get_layout(a).unwrap().x, 0);
+        assert_eq!(engine.get_layout(b).unwrap().x, 12);
+    }
+
+    #[test]
+    fn grid_places_children_in_tracks() {
+        let mut dom = Dom::new();
+        let root = dom.insert(NodeData::new("Root"));
+        let cells: Vec<NodeId> = (0..4)
+            .map(|_| dom.insert_child(root, NodeData::new("Cell")))
+            .collect();
+        let mut styles = HashMap::new();
+
+        let mut root_style = Styles::new();
+        root_style.layout = Some(LayoutDirection::Grid);
+        root_style.width = Some(Scalar::cells(80.0));
+        root_style.height = Some(Scalar::cells(24.0));
+        root_style.grid_size_columns = Some(3);
+        root_style.grid_size_rows = Some(2);
+        root_style.grid_columns = Some(vec![Scalar::cells(20.0), Scalar::fr(1.0), Scalar::fr(2.0)]);
+        styles.insert(root, root_style);
+        let mut wide = Styles::new();
+        wide.column_span = Some(2);
+        styles.insert(cells[1], wide);
+        let mut tall = Styles::new();
+        tall.row_span = Some(2);
+        styles.insert(cells[2], tall);
+
+        let mut engine = LayoutEngine::new();
+        engine.sync_tree(&dom, &styles, VP).unwrap();
+        engine.compute(80.0, 24.0).unwrap();
+
+        let layout = |id| engine.get_layout(id).unwrap();
+        assert_eq!(layout(cells[0]), Region::new(0, 0, 20, 12));
+        // Spans the 1fr and 2fr columns: 20 + 40 cells.
+        assert_eq!(layout(cells[1]), Region::new(20, 0, 60, 12));
+        assert_eq!(layout(cells[2]), Region::new(0, 12, 20, 12));
+        assert_eq!(layout(cells[3]), Region::new(20, 12, 20, 12));
+    }
+
     #[test]
     fn get_all_layouts() {
         let (dom, root, a, b) = simple_dom();
@@ -329,8 +411,8 @@ mod tests {
         styles.insert(root, root_style);
 
         let mut engine = LayoutEngine::new();
-        engine.sync_tree(&dom, &styles, VP);
-        engine.compute(80.0, 24.0);
+        engine.sync_tree(&dom, &styles, VP).unwrap();
+        engine.compute(80.0, 24.0).unwrap();
 
         let all = engine.get_all_layouts();
         assert!(all.contains_key(&root));
@@ -352,13 +434,13 @@ mod tests {
         let (mut dom, root, a, b) = simple_dom();
         let styles = HashMap::new();
         let mut engine = LayoutEngine::new();
-        engine.sync_tree(&dom, &styles, VP);
+        engine.sync_tree(&dom, &styles, VP).unwrap();
 
         assert_eq!(engine.node_map.len(), 3);
 
         // Remove node 'b' from DOM.
         dom.remove(b);
-        engine.sync_tree(&dom, &styles, VP);
+        engine.sync_tree(&dom, &styles, VP).unwrap();
 
         assert_eq!(engine.node_map.len(), 2);
         assert!(!engine.node_map.contains_key(&b));
@@ -381,8 +463,8 @@ mod tests {
         styles.insert(a, a_style);
 
         let mut engine = LayoutEngine::new();
-        engine.sync_tree(&dom, &styles, VP);
-        engine.compute(80.0, 24.0);
+        engine.sync_tree(&dom, &styles, VP).unwrap();
+        engine.compute(80.0, 24.0).unwrap();
 
         let a_layout = engine.get_layout(a).unwrap();
         assert_eq!(a_layout.height, 5);
@@ -392,8 +474,8 @@ mod tests {
         a_style2.height = Some(Scalar::cells(12.0));
         styles.insert(a, a_style2);
 
-        engine.sync_tree(&dom, &styles, VP);
-        engine.compute(80.0, 24.0);
+        engine.sync_tree(&dom, &styles, VP).unwrap();
+        engine.compute(80.0, 24.0).unwrap();
 
         let a_layout2 = engine.get_layout(a).unwrap();
         assert_eq!(a_layout2.height, 12);
@@ -419,8 +501,8 @@ mod tests {
         styles.insert(child, child_style);
 
         let mut engine = LayoutEngine::new();
-        engine.sync_tree(&dom, &styles, VP);
-        engine.compute(80.0, 24.0);
+        engine.sync_tree(&dom, &styles, VP).unwrap();
+        engine.compute(80.0, 24.0).unwrap();
 
         let child_layout = engine.get_layout(child).unwrap();
         // Child should be offset by the padding.
@@ -451,8 +533,8 @@ mod tests {
         styles.insert(child, child_style);
 
         let mut engine = LayoutEngine::new();
-        engine.sync_tree(&dom, &styles, VP);
-        engine.compute(80.0, 24.0);
+        engine.sync_tree(&dom, &styles, VP).unwrap();
+        engine.compute(80.0, 24.0).unwrap();
 
         let child_layout = engine.get_layout(child).unwrap();
         // Child should be offset by border (1 cell each side).
@@ -481,8 +563,8 @@ mod tests {
         styles.insert(c, c_style);
 
         let mut engine = LayoutEngine::new();
-        engine.sync_tree(&dom, &styles, VP);
-        engine.compute(80.0, 24.0);
+        engine.sync_tree(&dom, &styles, VP).unwrap();
+        engine.compute(80.0, 24.0).unwrap();
 
         let c_layout = engine.get_layout(c).unwrap();
         assert_eq!(c_layout.width, 10);
@@ -494,14 +576,15 @@ mod tests {
         let (dom, _root, _a, _b) = simple_dom();
         let styles = HashMap::new();
         let mut engine = LayoutEngine::new();
-        engine.sync_tree(&dom, &styles, VP);
+        engine.sync_tree(&dom, &styles, VP).unwrap();
         assert_eq!(engine.node_map.len(), 3);
 
         // Sync with empty DOM.
         let empty_dom = Dom::new();
-        engine.sync_tree(&empty_dom, &styles, VP);
+        engine.sync_tree(&empty_dom, &styles, VP).unwrap();
         assert!(engine.node_map.is_empty());
         assert!(engine.root.is_none());
+        assert_eq!(engine.tree.total_node_count(), 0);
     }
 
     #[test]
@@ -523,8 +606,8 @@ mod tests {
         styles.insert(docked, docked_style);
 
         let mut engine = LayoutEngine::new();
-        engine.sync_tree(&dom, &styles, VP);
-        engine.compute(80.0, 24.0);
+        engine.sync_tree(&dom, &styles, VP).unwrap();
+        engine.compute(80.0, 24.0).unwrap();
 
         let docked_layout = engine.get_layout(docked).unwrap();
         assert_eq!(docked_layout.y, 0);
@@ -551,11 +634,113 @@ mod tests {
         styles.insert(hidden, hidden_style);
 
         let mut engine = LayoutEngine::new();
-        engine.sync_tree(&dom, &styles, VP);
-        engine.compute(80.0, 24.0);
+        engine.sync_tree(&dom, &styles, VP).unwrap();
+        engine.compute(80.0, 24.0).unwrap();
 
         let hidden_layout = engine.get_layout(hidden).unwrap();
         assert_eq!(hidden_layout.width, 0);
         assert_eq!(hidden_layout.height, 0);
     }
+
+    /// Styles with the given width and height.
+    fn sized(width: Option<Scalar>, height: Option<Scalar>) -> Styles {
+        let mut styles = Styles::new();
+        styles.width = width;
+        styles.height = height;
+        styles
+    }
+
+    fn laid_out(dom: &Dom, styles: &HashMap<NodeId, Styles>) -> LayoutEngine {
+        let mut engine = LayoutEngine::new();
+        engine.sync_tree(dom, styles, VP).unwrap();
+        engine.compute(80.0, 24.0).unwrap();
+        engine
+    }
+
+    #[test]
+    fn fr_children_share_leftover_height() {
+        let mut dom = Dom::new();
+        let root = dom.insert(NodeData::new("Root"));
+        let header = dom.insert_child(root, NodeData::new("Header"));
+        let a = dom.insert_child(root, NodeData::new("A"));
+        let b = dom.insert_child(root, NodeData::new("B"));
+        let styles = HashMap::from([
+            (root, sized(Some(Scalar::cells(80.0)), Some(Scalar::cells(24.0)))),
+            (header, sized(None, Some(Scalar::cells(6.0)))),
+            (a, sized(None, Some(Scalar::fr(1.0)))),
+            (b, sized(None, Some(Scalar::fr(2.0)))),
+        ]);
+        let engine = laid_out(&dom, &styles);
+
+        assert_eq!(engine.get_layout(a).unwrap(), Region::new(0, 6, 80, 6));
+        assert_eq!(engine.get_layout(b).unwrap(), Region::new(0, 12, 80, 12));
+    }
+
+    #[test]
+    fn fr_widths_in_horizontal_parent() {
+        let (dom, root, a, b) = simple_dom();
+        let mut root_style = sized(Some(Scalar::cells(80.0)), Some(Scalar::cells(24.0)));
+        root_style.layout = Some(LayoutDirection::Horizontal);
+        let styles = HashMap::from([
+            (root, root_style),
+            (a, sized(Some(Scalar::fr(1.0)), Some(Scalar::fr(1.0)))),
+            (b, sized(Some(Scalar::fr(3.0)), None)),
+        ]);
+        let engine = laid_out(&dom, &styles);
+
+        assert_eq!(engine.get_layout(a).unwrap(), Region::new(0, 0, 20, 24));
+        assert_eq!(engine.get_layout(b).unwrap(), Region::new(20, 0, 60, 24));
+    }
+
+    #[test]
+    fn root_fr_fills_viewport() {
+        let mut dom = Dom::new();
+        let root = dom.insert(NodeData::new("Root"));
+        let styles = HashMap::from([(root, sized(Some(Scalar::fr(1.0)), Some(Scalar::fr(1.0))))]);
+        let engine = laid_out(&dom, &styles);
+        assert_eq!(engine.get_layout(root).unwrap(), Region::new(0, 0, 80, 24));
+    }
+
+    #[test]
+    fn percent_height_in_auto_and_fr_parents() {
+        let mut dom = Dom::new();
+        let root = dom.insert(NodeData::new("Root"));
+        let auto = dom.insert_child(root, NodeData::new("Auto"));
+        let auto_leaf = dom.insert_child(auto, NodeData::new("Leaf"));
+        let flexed = dom.insert_child(root, NodeData::new("Flexed"));
+        let flexed_leaf = dom.insert_child(flexed, NodeData::new("Leaf"));
+        let half = sized(None, Some(Scalar::percent(50.0)));
+        let styles = HashMap::from([
+            (root, sized(Some(Scalar::cells(80.0)), Some(Scalar::cells(24.0)))),
+            (auto_leaf, half.clone()),
+            (flexed, sized(None, Some(Scalar::fr(1.0)))),
+            (flexed_leaf, half),
+        ]);
+        let engine = laid_out(&dom, &styles);
+
+        // An auto-height parent has no height to take a percentage of, so
+        // the percentage behaves as auto and the parent collapses.
+        assert_eq!(engine.get_layout(auto).unwrap().height, 0);
+        assert_eq!(engine.get_layout(auto_leaf).unwrap().height, 0);
+        // A flexed parent's height is definite.
+        assert_eq!(engine.get_layout(flexed).unwrap().height, 24);
+        assert_eq!(engine.get_layout(flexed_leaf).unwrap().height, 12);
+    }
+
+    #[test]
+    fn percent_height_in_stretched_parent() {
+        let (mut dom, root, a, _) = simple_dom();
+        let leaf = dom.insert_child(a, NodeData::new("Leaf"));
+        let mut root_style = sized(Some(Scalar::cells(80.0)), Some(Scalar::cells(24.0)));
+        root_style.layout = Some(LayoutDirection::Horizontal);
+        let styles = HashMap::from([
+            (root, root_style),
+            (leaf, sized(Some(Scalar::cells(4.0)), Some(Scalar::percent(100.0)))),
+        ]);
+        let engine = laid_out(&dom, &styles);
+
+        // `a` has auto height but is stretched across the row, so 100%
+        // resolves against the stretched height.
+        assert_eq!(engine.get_layout(leaf).unwrap().height, 24);
+    }
 }
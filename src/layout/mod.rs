@@ -1,8 +1,11 @@
-//! Layout engine: taffy integration, CSS scalar resolution, spatial map.
+//! Layout engine: taffy integration, CSS scalar resolution, spatial map,
+//! visibility observers.
 
 pub mod engine;
 pub mod resolve;
 pub mod spatial;
+pub mod visibility;
 
 pub use engine::LayoutEngine;
 pub use spatial::SpatialMap;
+pub use visibility::VisibilityObservers;
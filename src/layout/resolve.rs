@@ -9,23 +9,27 @@ use crate::css::scalar::{Scalar, ScalarBox, Unit};
 use crate::css::styles::{BorderKind, Dock, LayoutDirection, Styles};
 
 /// Convert a [`Scalar`] to a [`LengthPercentageAuto`], resolving viewport-relative
-/// units against the given viewport size.
+/// units against the given viewport size and root units against `unit`, the
+/// size of `1u` in cells.
 ///
 /// - `Cells` -> points (length)
 /// - `Percent` -> percent (0..100 range mapped to 0..1)
 /// - `Vw` -> resolved to absolute length against viewport width
 /// - `Vh` -> resolved to absolute length against viewport height
+/// - `Root` -> resolved to absolute length as a multiple of `unit`
 /// - `Auto` -> auto
-/// - `Fr` -> auto (fr is handled at grid track level, not per-node)
+/// - `Fr` -> auto (see [`apply_fr`] for sizes along a flex parent's main axis)
 pub fn resolve_scalar(
     scalar: &Scalar,
     viewport: taffy::geometry::Size<f32>,
+    unit: f32,
 ) -> LengthPercentageAuto {
     match scalar.unit {
         Unit::Cells => LengthPercentageAuto::from_length(scalar.value),
         Unit::Percent => LengthPercentageAuto::from_percent(scalar.value / 100.0),
         Unit::Vw => LengthPercentageAuto::from_length(scalar.value / 100.0 * viewport.width),
         Unit::Vh => LengthPercentageAuto::from_length(scalar.value / 100.0 * viewport.height),
+        Unit::Root => LengthPercentageAuto::from_length(scalar.value * unit),
         Unit::Auto | Unit::Fr => LengthPercentageAuto::AUTO,
     }
 }
@@ -37,12 +41,14 @@ pub fn resolve_scalar(
 pub fn resolve_scalar_definite(
     scalar: &Scalar,
     viewport: taffy::geometry::Size<f32>,
+    unit: f32,
 ) -> LengthPercentage {
     match scalar.unit {
         Unit::Cells => LengthPercentage::from_length(scalar.value),
         Unit::Percent => LengthPercentage::from_percent(scalar.value / 100.0),
         Unit::Vw => LengthPercentage::from_length(scalar.value / 100.0 * viewport.width),
         Unit::Vh => LengthPercentage::from_length(scalar.value / 100.0 * viewport.height),
+        Unit::Root => LengthPercentage::from_length(scalar.value * unit),
         Unit::Auto | Unit::Fr => LengthPercentage::ZERO,
     }
 }
@@ -51,17 +57,19 @@ pub fn resolve_scalar_definite(
 ///
 /// - `Cells` -> length
 /// - `Percent` -> percent
-/// - `Vw`/`Vh` -> resolved absolute length
+/// - `Vw`/`Vh`/`Root` -> resolved absolute length
 /// - `Auto`/`Fr` -> auto
 fn resolve_scalar_dimension(
     scalar: &Scalar,
     viewport: taffy::geometry::Size<f32>,
+    unit: f32,
 ) -> Dimension {
     match scalar.unit {
         Unit::Cells => Dimension::from_length(scalar.value),
         Unit::Percent => Dimension::from_percent(scalar.value / 100.0),
         Unit::Vw => Dimension::from_length(scalar.value / 100.0 * viewport.width),
         Unit::Vh => Dimension::from_length(scalar.value / 100.0 * viewport.height),
+        Unit::Root => Dimension::from_length(scalar.value * unit),
         Unit::Auto | Unit::Fr => Dimension::AUTO,
     }
 }
@@ -70,12 +78,13 @@ fn resolve_scalar_dimension(
 pub fn resolve_scalar_box(
     box_: &ScalarBox,
     viewport: taffy::geometry::Size<f32>,
+    unit: f32,
 ) -> taffy::geometry::Rect<LengthPercentageAuto> {
     taffy::geometry::Rect {
-        top: resolve_scalar(&box_.top, viewport),
-        right: resolve_scalar(&box_.right, viewport),
-        bottom: resolve_scalar(&box_.bottom, viewport),
-        left: resolve_scalar(&box_.left, viewport),
+        top: resolve_scalar(&box_.top, viewport, unit),
+        right: resolve_scalar(&box_.right, viewport, unit),
+        bottom: resolve_scalar(&box_.bottom, viewport, unit),
+        left: resolve_scalar(&box_.left, viewport, unit),
     }
 }
 
@@ -83,12 +92,13 @@ pub fn resolve_scalar_box(
 fn resolve_scalar_box_definite(
     box_: &ScalarBox,
     viewport: taffy::geometry::Size<f32>,
+    unit: f32,
 ) -> taffy::geometry::Rect<LengthPercentage> {
     taffy::geometry::Rect {
-        top: resolve_scalar_definite(&box_.top, viewport),
-        right: resolve_scalar_definite(&box_.right, viewport),
-        bottom: resolve_scalar_definite(&box_.bottom, viewport),
-        left: resolve_scalar_definite(&box_.left, viewport),
+        top: resolve_scalar_definite(&box_.top, viewport, unit),
+        right: resolve_scalar_definite(&box_.right, viewport, unit),
+        bottom: resolve_scalar_definite(&box_.bottom, viewport, unit),
+        left: resolve_scalar_definite(&box_.left, viewport, unit),
     }
 }
 
@@ -102,10 +112,82 @@ fn resolve_overflow(overflow: &crate::css::styles::Overflow) -> taffy::style::Ov
     }
 }
 
+/// Convert a [`Scalar`] to a grid track size: `fr` tracks share the space
+/// left after fixed tracks, `auto` tracks fit their content.
+fn resolve_track(
+    scalar: &Scalar,
+    viewport: taffy::geometry::Size<f32>,
+    unit: f32,
+) -> TrackSizingFunction {
+    match scalar.unit {
+        Unit::Cells => length(scalar.value),
+        Unit::Percent => percent(scalar.value / 100.0),
+        Unit::Vw => length(scalar.value / 100.0 * viewport.width),
+        Unit::Vh => length(scalar.value / 100.0 * viewport.height),
+        Unit::Root => length(scalar.value * unit),
+        Unit::Fr => fr(scalar.value),
+        Unit::Auto => auto(),
+    }
+}
+
+/// `count` tracks sized by `sizes` repeated in order; `1fr` each without
+/// sizes.
+fn resolve_tracks(
+    count: usize,
+    sizes: Option<&[Scalar]>,
+    viewport: taffy::geometry::Size<f32>,
+    unit: f32,
+) -> Vec<TrackSizingFunction> {
+    let sizes = sizes.filter(|sizes| !sizes.is_empty());
+    (0..count)
+        .map(|i| match sizes {
+            Some(sizes) => resolve_track(&sizes[i % sizes.len()], viewport, unit),
+            None => fr(1.0),
+        })
+        .collect()
+}
+
+/// Implement `fr` sizes, which taffy has no per-node unit for.
+///
+/// A flex child sized in `fr` along its parent's main axis (`height` in a
+/// vertical parent, `width` in a horizontal one) becomes `flex-grow: <fr>`
+/// with a zero basis, so `fr` siblings split the parent's leftover space in
+/// proportion to their values after fixed-size siblings are placed. Because
+/// the parent's size is definite, percentages inside such a child resolve
+/// against its flexed size. `fr` across the main axis stays auto, which
+/// stretches to the parent. The root (no parent) fills the viewport. Docked
+/// nodes and grid children are left alone.
+pub fn apply_fr(style: &mut taffy::Style, styles: &Styles, parent: Option<&Styles>) {
+    let is_fr = |s: &Option<Scalar>| s.as_ref().is_some_and(|s| s.unit == Unit::Fr);
+    let Some(parent) = parent else {
+        if is_fr(&styles.width) {
+            style.size.width = Dimension::from_percent(1.0);
+        }
+        if is_fr(&styles.height) {
+            style.size.height = Dimension::from_percent(1.0);
+        }
+        return;
+    };
+    if styles.dock.is_some() {
+        return;
+    }
+    let main = match parent.layout {
+        Some(LayoutDirection::Grid) => return,
+        Some(LayoutDirection::Horizontal) => &styles.width,
+        Some(LayoutDirection::Vertical) | None => &styles.height,
+    };
+    if let Some(fr) = main.as_ref().filter(|s| s.unit == Unit::Fr) {
+        style.flex_grow = fr.value;
+        style.flex_shrink = 1.0;
+        style.flex_basis = Dimension::from_length(0.0);
+    }
+}
+
 /// Convert a full [`Styles`] into a [`taffy::Style`].
 ///
 /// The `viewport_size` is `(columns, rows)` representing the terminal dimensions.
-/// This is used to resolve `vw` and `vh` units.
+/// This is used to resolve `vw` and `vh` units. `unit` is the size of `1u` in
+/// cells.
 ///
 /// Mapping summary:
 /// - `display: Block` -> `Display::Flex`, `display: None` -> `Display::None`
@@ -115,10 +197,13 @@ fn resolve_overflow(overflow: &crate::css::styles::Overflow) -> taffy::style::Ov
 /// - `max_width/max_height` -> `max_size`
 /// - `margin` -> `margin`
 /// - `padding` -> `padding`
+/// - `row_gap/column_gap` -> `gap`
+/// - `grid_size/grid_columns/grid_rows` -> grid templates (grid containers)
+/// - `column_span/row_span` -> grid placement spans
 /// - `overflow_x/overflow_y` -> `overflow`
 /// - `dock` -> `position: absolute` with inset
 /// - `border` with non-None kind -> 1 cell border on each side
-pub fn resolve_styles(styles: &Styles, viewport_size: (u16, u16)) -> taffy::Style {
+pub fn resolve_styles(styles: &Styles, viewport_size: (u16, u16), unit: f32) -> taffy::Style {
     let viewport = taffy::geometry::Size {
         width: viewport_size.0 as f32,
         height: viewport_size.1 as f32,
@@ -157,36 +242,78 @@ pub fn resolve_styles(styles: &Styles, viewport_size: (u16, u16)) -> taffy::Styl
 
     // Size
     if let Some(ref w) = styles.width {
-        style.size.width = resolve_scalar_dimension(w, viewport);
+        style.size.width = resolve_scalar_dimension(w, viewport, unit);
     }
     if let Some(ref h) = styles.height {
-        style.size.height = resolve_scalar_dimension(h, viewport);
+        style.size.height = resolve_scalar_dimension(h, viewport, unit);
     }
 
     // Min size
     if let Some(ref w) = styles.min_width {
-        style.min_size.width = resolve_scalar_dimension(w, viewport);
+        style.min_size.width = resolve_scalar_dimension(w, viewport, unit);
     }
     if let Some(ref h) = styles.min_height {
-        style.min_size.height = resolve_scalar_dimension(h, viewport);
+        style.min_size.height = resolve_scalar_dimension(h, viewport, unit);
     }
 
     // Max size
     if let Some(ref w) = styles.max_width {
-        style.max_size.width = resolve_scalar_dimension(w, viewport);
+        style.max_size.width = resolve_scalar_dimension(w, viewport, unit);
     }
     if let Some(ref h) = styles.max_height {
-        style.max_size.height = resolve_scalar_dimension(h, viewport);
+        style.max_size.height = resolve_scalar_dimension(h, viewport, unit);
     }
 
     // Margin
     if let Some(ref m) = styles.margin {
-        style.margin = resolve_scalar_box(m, viewport);
+        style.margin = resolve_scalar_box(m, viewport, unit);
     }
 
     // Padding
     if let Some(ref p) = styles.padding {
-        style.padding = resolve_scalar_box_definite(p, viewport);
+        style.padding = resolve_scalar_box_definite(p, viewport, unit);
+    }
+
+    // Gap between children
+    if let Some(ref g) = styles.row_gap {
+        style.gap.height = resolve_scalar_definite(g, viewport, unit);
+    }
+    if let Some(ref g) = styles.column_gap {
+        style.gap.width = resolve_scalar_definite(g, viewport, unit);
+    }
+
+    // Grid tracks: `grid-size` fixes the number of columns (and rows), with
+    // `grid-columns`/`grid-rows` sizes repeated across them. Without a size,
+    // the track lists themselves define the columns, and rows past the
+    // template are implicit, sized by `grid-rows`.
+    if is_grid {
+        let columns = styles.grid_columns.as_deref();
+        let count = styles
+            .grid_size_columns
+            .map(usize::from)
+            .or(columns.map(<[Scalar]>::len));
+        if let Some(count) = count {
+            style.grid_template_columns = resolve_tracks(count, columns, viewport, unit)
+                .into_iter()
+                .map(GridTemplateComponent::Single)
+                .collect();
+        }
+        let rows = styles.grid_rows.as_deref();
+        if let Some(count) = styles.grid_size_rows {
+            style.grid_template_rows = resolve_tracks(count.into(), rows, viewport, unit)
+                .into_iter()
+                .map(GridTemplateComponent::Single)
+                .collect();
+        }
+        if let Some(rows) = rows.filter(|rows| !rows.is_empty()) {
+            style.grid_auto_rows = resolve_tracks(rows.len(), Some(rows), viewport, unit);
+        }
+    }
+    if let Some(columns) = styles.column_span {
+        style.grid_column = span(columns);
+    }
+    if let Some(rows) = styles.row_span {
+        style.grid_row = span(rows);
     }
 
     // Overflow
@@ -276,21 +403,21 @@ mod tests {
     #[test]
     fn resolve_cells() {
         let s = Scalar::cells(10.0);
-        let result = resolve_scalar(&s, VIEWPORT);
+        let result = resolve_scalar(&s, VIEWPORT, 1.0);
         assert_eq!(result, LengthPercentageAuto::from_length(10.0));
     }
 
     #[test]
     fn resolve_percent() {
         let s = Scalar::percent(50.0);
-        let result = resolve_scalar(&s, VIEWPORT);
+        let result = resolve_scalar(&s, VIEWPORT, 1.0);
         assert_eq!(result, LengthPercentageAuto::from_percent(0.5));
     }
 
     #[test]
     fn resolve_vw() {
         let s = Scalar::vw(50.0);
-        let result = resolve_scalar(&s, VIEWPORT);
+        let result = resolve_scalar(&s, VIEWPORT, 1.0);
         // 50% of 80 = 40
         assert_eq!(result, LengthPercentageAuto::from_length(40.0));
     }
@@ -298,22 +425,33 @@ mod tests {
     #[test]
     fn resolve_vh() {
         let s = Scalar::vh(100.0);
-        let result = resolve_scalar(&s, VIEWPORT);
+        let result = resolve_scalar(&s, VIEWPORT, 1.0);
         // 100% of 24 = 24
         assert_eq!(result, LengthPercentageAuto::from_length(24.0));
     }
 
+    #[test]
+    fn resolve_root_units() {
+        let s = Scalar::units(3.0);
+        assert_eq!(resolve_scalar(&s, VIEWPORT, 1.0), LengthPercentageAuto::from_length(3.0));
+        assert_eq!(resolve_scalar_definite(&s, VIEWPORT, 2.0), LengthPercentage::from_length(6.0));
+        let mut styles = Styles::new();
+        styles.height = Some(s);
+        let style = resolve_styles(&styles, VP_TUPLE, 0.5);
+        assert_eq!(style.size.height, Dimension::from_length(1.5));
+    }
+
     #[test]
     fn resolve_auto() {
         let s = Scalar::auto();
-        let result = resolve_scalar(&s, VIEWPORT);
+        let result = resolve_scalar(&s, VIEWPORT, 1.0);
         assert_eq!(result, LengthPercentageAuto::AUTO);
     }
 
     #[test]
     fn resolve_fr_as_auto() {
         let s = Scalar::fr(2.0);
-        let result = resolve_scalar(&s, VIEWPORT);
+        let result = resolve_scalar(&s, VIEWPORT, 1.0);
         assert_eq!(result, LengthPercentageAuto::AUTO);
     }
 
@@ -324,35 +462,35 @@ mod tests {
     #[test]
     fn resolve_definite_cells() {
         let s = Scalar::cells(5.0);
-        let result = resolve_scalar_definite(&s, VIEWPORT);
+        let result = resolve_scalar_definite(&s, VIEWPORT, 1.0);
         assert_eq!(result, LengthPercentage::from_length(5.0));
     }
 
     #[test]
     fn resolve_definite_percent() {
         let s = Scalar::percent(25.0);
-        let result = resolve_scalar_definite(&s, VIEWPORT);
+        let result = resolve_scalar_definite(&s, VIEWPORT, 1.0);
         assert_eq!(result, LengthPercentage::from_percent(0.25));
     }
 
     #[test]
     fn resolve_definite_auto_becomes_zero() {
         let s = Scalar::auto();
-        let result = resolve_scalar_definite(&s, VIEWPORT);
+        let result = resolve_scalar_definite(&s, VIEWPORT, 1.0);
         assert_eq!(result, LengthPercentage::ZERO);
     }
 
     #[test]
     fn resolve_definite_vw() {
         let s = Scalar::vw(10.0);
-        let result = resolve_scalar_definite(&s, VIEWPORT);
+        let result = resolve_scalar_definite(&s, VIEWPORT, 1.0);
         assert_eq!(result, LengthPercentage::from_length(8.0)); // 10% of 80
     }
 
     #[test]
     fn resolve_definite_vh() {
         let s = Scalar::vh(50.0);
-        let result = resolve_scalar_definite(&s, VIEWPORT);
+        let result = resolve_scalar_definite(&s, VIEWPORT, 1.0);
         assert_eq!(result, LengthPercentage::from_length(12.0)); // 50% of 24
     }
 
@@ -363,7 +501,7 @@ mod tests {
     #[test]
     fn resolve_box_uniform() {
         let b = ScalarBox::all(Scalar::cells(2.0));
-        let result = resolve_scalar_box(&b, VIEWPORT);
+        let result = resolve_scalar_box(&b, VIEWPORT, 1.0);
         let expected = LengthPercentageAuto::from_length(2.0);
         assert_eq!(result.top, expected);
         assert_eq!(result.right, expected);
@@ -379,7 +517,7 @@ mod tests {
             Scalar::vw(10.0),
             Scalar::auto(),
         );
-        let result = resolve_scalar_box(&b, VIEWPORT);
+        let result = resolve_scalar_box(&b, VIEWPORT, 1.0);
         assert_eq!(result.top, LengthPercentageAuto::from_length(1.0));
         assert_eq!(result.right, LengthPercentageAuto::from_percent(0.5));
         assert_eq!(result.bottom, LengthPercentageAuto::from_length(8.0)); // 10% of 80
@@ -393,7 +531,7 @@ mod tests {
     #[test]
     fn styles_default_is_flex_column() {
         let styles = Styles::new();
-        let taffy_style = resolve_styles(&styles, VP_TUPLE);
+        let taffy_style = resolve_styles(&styles, VP_TUPLE, 1.0);
         assert_eq!(taffy_style.display, Display::Flex);
         assert_eq!(taffy_style.flex_direction, FlexDirection::Column);
     }
@@ -402,7 +540,7 @@ mod tests {
     fn styles_display_none() {
         let mut styles = Styles::new();
         styles.display = Some(crate::css::styles::Display::None);
-        let taffy_style = resolve_styles(&styles, VP_TUPLE);
+        let taffy_style = resolve_styles(&styles, VP_TUPLE, 1.0);
         assert_eq!(taffy_style.display, Display::None);
     }
 
@@ -410,7 +548,7 @@ mod tests {
     fn styles_horizontal_layout() {
         let mut styles = Styles::new();
         styles.layout = Some(LayoutDirection::Horizontal);
-        let taffy_style = resolve_styles(&styles, VP_TUPLE);
+        let taffy_style = resolve_styles(&styles, VP_TUPLE, 1.0);
         assert_eq!(taffy_style.display, Display::Flex);
         assert_eq!(taffy_style.flex_direction, FlexDirection::Row);
     }
@@ -419,16 +557,40 @@ mod tests {
     fn styles_grid_layout() {
         let mut styles = Styles::new();
         styles.layout = Some(LayoutDirection::Grid);
-        let taffy_style = resolve_styles(&styles, VP_TUPLE);
+        let taffy_style = resolve_styles(&styles, VP_TUPLE, 1.0);
         assert_eq!(taffy_style.display, Display::Grid);
     }
 
+    #[test]
+    fn styles_grid_tracks_repeat_to_size() {
+        let mut styles = Styles::new();
+        styles.layout = Some(LayoutDirection::Grid);
+        styles.grid_size_columns = Some(3);
+        styles.grid_columns = Some(vec![Scalar::cells(10.0), Scalar::fr(1.0)]);
+        styles.grid_rows = Some(vec![Scalar::cells(2.0)]);
+        let taffy_style = resolve_styles(&styles, VP_TUPLE, 1.0);
+        let single = GridTemplateComponent::Single;
+        assert_eq!(
+            taffy_style.grid_template_columns,
+            [single(length(10.0)), single(fr(1.0)), single(length(10.0))]
+        );
+        assert!(taffy_style.grid_template_rows.is_empty());
+        assert_eq!(taffy_style.grid_auto_rows, [length(2.0)]);
+
+        // Tracks are ignored outside grid containers; spans apply anywhere.
+        styles.layout = None;
+        styles.column_span = Some(2);
+        let taffy_style = resolve_styles(&styles, VP_TUPLE, 1.0);
+        assert!(taffy_style.grid_template_columns.is_empty());
+        assert_eq!(taffy_style.grid_column, span(2));
+    }
+
     #[test]
     fn styles_sizing() {
         let mut styles = Styles::new();
         styles.width = Some(Scalar::cells(40.0));
         styles.height = Some(Scalar::percent(50.0));
-        let taffy_style = resolve_styles(&styles, VP_TUPLE);
+        let taffy_style = resolve_styles(&styles, VP_TUPLE, 1.0);
         assert_eq!(taffy_style.size.width, Dimension::from_length(40.0));
         assert_eq!(taffy_style.size.height, Dimension::from_percent(0.5));
     }
@@ -438,7 +600,7 @@ mod tests {
         let mut styles = Styles::new();
         styles.min_width = Some(Scalar::cells(10.0));
         styles.max_height = Some(Scalar::vh(100.0));
-        let taffy_style = resolve_styles(&styles, VP_TUPLE);
+        let taffy_style = resolve_styles(&styles, VP_TUPLE, 1.0);
         assert_eq!(taffy_style.min_size.width, Dimension::from_length(10.0));
         assert_eq!(
             taffy_style.max_size.height,
@@ -450,7 +612,7 @@ mod tests {
     fn styles_margin() {
         let mut styles = Styles::new();
         styles.margin = Some(ScalarBox::all(Scalar::cells(2.0)));
-        let taffy_style = resolve_styles(&styles, VP_TUPLE);
+        let taffy_style = resolve_styles(&styles, VP_TUPLE, 1.0);
         assert_eq!(
             taffy_style.margin.top,
             LengthPercentageAuto::from_length(2.0)
@@ -465,7 +627,7 @@ mod tests {
     fn styles_padding() {
         let mut styles = Styles::new();
         styles.padding = Some(ScalarBox::symmetric(Scalar::cells(1.0), Scalar::cells(3.0)));
-        let taffy_style = resolve_styles(&styles, VP_TUPLE);
+        let taffy_style = resolve_styles(&styles, VP_TUPLE, 1.0);
         assert_eq!(
             taffy_style.padding.top,
             LengthPercentage::from_length(1.0)
@@ -476,12 +638,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn styles_gap() {
+        let mut styles = Styles::new();
+        styles.row_gap = Some(Scalar::cells(1.0));
+        styles.column_gap = Some(Scalar::percent(50.0));
+        let taffy_style = resolve_styles(&styles, VP_TUPLE, 1.0);
+        assert_eq!(taffy_style.gap.height, LengthPercentage::from_length(1.0));
+        assert_eq!(taffy_style.gap.width, LengthPercentage::from_percent(0.5));
+    }
+
     #[test]
     fn styles_overflow() {
         let mut styles = Styles::new();
         styles.overflow_x = Some(Overflow::Scroll);
         styles.overflow_y = Some(Overflow::Hidden);
-        let taffy_style = resolve_styles(&styles, VP_TUPLE);
+        let taffy_style = resolve_styles(&styles, VP_TUPLE, 1.0);
         assert_eq!(taffy_style.overflow.x, taffy::style::Overflow::Scroll);
         assert_eq!(taffy_style.overflow.y, taffy::style::Overflow::Hidden);
     }
@@ -490,14 +662,14 @@ mod tests {
     fn styles_overflow_auto_becomes_scroll() {
         let mut styles = Styles::new();
         styles.overflow_x = Some(Overflow::Auto);
-        let taffy_style = resolve_styles(&styles, VP_TUPLE);
+        let taffy_style = resolve_styles(&styles, VP_TUPLE, 1.0);
         assert_eq!(taffy_style.overflow.x, taffy::style::Overflow::Scroll);
     }
 
     #[test]
     fn styles_overflow_default_visible() {
         let styles = Styles::new();
-        let taffy_style = resolve_styles(&styles, VP_TUPLE);
+        let taffy_style = resolve_styles(&styles, VP_TUPLE, 1.0);
         assert_eq!(taffy_style.overflow.x, taffy::style::Overflow::Visible);
         assert_eq!(taffy_style.overflow.y, taffy::style::Overflow::Visible);
     }
@@ -509,7 +681,7 @@ mod tests {
             kind: BorderKind::Thin,
             color: None,
         });
-        let taffy_style = resolve_styles(&styles, VP_TUPLE);
+        let taffy_style = resolve_styles(&styles, VP_TUPLE, 1.0);
         assert_eq!(
             taffy_style.border.top,
             LengthPercentage::from_length(1.0)
@@ -527,7 +699,7 @@ mod tests {
             kind: BorderKind::None,
             color: None,
         });
-        let taffy_style = resolve_styles(&styles, VP_TUPLE);
+        let taffy_style = resolve_styles(&styles, VP_TUPLE, 1.0);
         assert_eq!(taffy_style.border.top, LengthPercentage::ZERO);
     }
 
@@ -535,7 +707,7 @@ mod tests {
     fn styles_dock_top() {
         let mut styles = Styles::new();
         styles.dock = Some(Dock::Top);
-        let taffy_style = resolve_styles(&styles, VP_TUPLE);
+        let taffy_style = resolve_styles(&styles, VP_TUPLE, 1.0);
         assert_eq!(taffy_style.position, Position::Absolute);
         assert_eq!(
             taffy_style.inset.top,
@@ -548,7 +720,7 @@ mod tests {
     fn styles_dock_bottom() {
         let mut styles = Styles::new();
         styles.dock = Some(Dock::Bottom);
-        let taffy_style = resolve_styles(&styles, VP_TUPLE);
+        let taffy_style = resolve_styles(&styles, VP_TUPLE, 1.0);
         assert_eq!(taffy_style.position, Position::Absolute);
         assert_eq!(taffy_style.inset.top, LengthPercentageAuto::AUTO);
         assert_eq!(
@@ -561,7 +733,7 @@ mod tests {
     fn styles_dock_left() {
         let mut styles = Styles::new();
         styles.dock = Some(Dock::Left);
-        let taffy_style = resolve_styles(&styles, VP_TUPLE);
+        let taffy_style = resolve_styles(&styles, VP_TUPLE, 1.0);
         assert_eq!(taffy_style.position, Position::Absolute);
         assert_eq!(
             taffy_style.inset.left,
@@ -574,7 +746,7 @@ mod tests {
     fn styles_dock_right() {
         let mut styles = Styles::new();
         styles.dock = Some(Dock::Right);
-        let taffy_style = resolve_styles(&styles, VP_TUPLE);
+        let taffy_style = resolve_styles(&styles, VP_TUPLE, 1.0);
         assert_eq!(taffy_style.position, Position::Absolute);
         assert_eq!(taffy_style.inset.left, LengthPercentageAuto::AUTO);
         assert_eq!(
@@ -596,7 +768,7 @@ mod tests {
             color: Some("red".into()),
         });
 
-        let taffy_style = resolve_styles(&styles, VP_TUPLE);
+        let taffy_style = resolve_styles(&styles, VP_TUPLE, 1.0);
         assert_eq!(taffy_style.display, Display::Flex);
         assert_eq!(taffy_style.flex_direction, FlexDirection::Row);
         assert_eq!(taffy_style.size.width, Dimension::from_percent(1.0));
@@ -614,14 +786,14 @@ mod tests {
     #[test]
     fn resolve_zero_cells() {
         let s = Scalar::cells(0.0);
-        let result = resolve_scalar(&s, VIEWPORT);
+        let result = resolve_scalar(&s, VIEWPORT, 1.0);
         assert_eq!(result, LengthPercentageAuto::from_length(0.0));
     }
 
     #[test]
     fn resolve_negative_cells() {
         let s = Scalar::cells(-5.0);
-        let result = resolve_scalar(&s, VIEWPORT);
+        let result = resolve_scalar(&s, VIEWPORT, 1.0);
         assert_eq!(result, LengthPercentageAuto::from_length(-5.0));
     }
 
@@ -632,7 +804,7 @@ mod tests {
             height: 0.0,
         };
         let s = Scalar::vw(50.0);
-        let result = resolve_scalar(&s, viewport);
+        let result = resolve_scalar(&s, viewport, 1.0);
         assert_eq!(result, LengthPercentageAuto::from_length(0.0));
     }
 }
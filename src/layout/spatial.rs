@@ -3,6 +3,13 @@
 //! [`SpatialMap`] maintains a list of node regions ordered by z-order (insertion
 //! order) and provides hit-testing queries to find which nodes are at a given
 //! point or overlap a given region.
+//!
+//! Rebuilding the map is linear in the number of nodes. When the set of nodes
+//! and their order are unchanged, [`SpatialMap::set_region`] moves a single
+//! node in place instead, so entries that did not move are not rewritten.
+//! Finding out what moved is up to the caller: `Screen::update_spatial_map`
+//! still compares every node's region against its entry, so its cost stays
+//! linear in the number of nodes; only the writes shrink.
 
 use std::collections::HashMap;
 
@@ -18,6 +25,8 @@ use crate::geometry::{Offset, Region};
 pub struct SpatialMap {
     /// Entries ordered by z-order (last = frontmost).
     entries: Vec<(NodeId, Region)>,
+    /// Position of each node in `entries`.
+    index: HashMap<NodeId, usize>,
 }
 
 impl SpatialMap {
@@ -25,6 +34,7 @@ impl SpatialMap {
     pub fn new() -> Self {
         Self {
             entries: Vec::new(),
+            index: HashMap::new(),
         }
     }
 
@@ -43,6 +53,7 @@ impl SpatialMap {
         for (&node_id, &region) in layouts {
             self.entries.push((node_id, region));
         }
+        self.reindex();
     }
 
     /// Rebuild the spatial map from an ordered list of `(NodeId, Region)` pairs.
@@ -54,6 +65,46 @@ impl SpatialMap {
         self.entries.clear();
         self.entries.reserve(entries.len());
         self.entries.extend_from_slice(entries);
+        self.reindex();
+    }
+
+    /// Move `node` to `region`, keeping its z-order. Returns `false` (and
+    /// changes nothing) if the node is not in the map; adding or removing
+    /// nodes needs a rebuild to place them in z-order.
+    pub fn set_region(&mut self, node: NodeId, region: Region) -> bool {
+        match self.index.get(&node) {
+            Some(&i) => {
+                self.entries[i].1 = region;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The region stored for `node`, if it is in the map.
+    pub fn region(&self, node: NodeId) -> Option<Region> {
+        self.index.get(&node).map(|&i| self.entries[i].1)
+    }
+
+    /// Every `(node, region)` entry, back to front.
+    pub fn entries(&self) -> &[(NodeId, Region)] {
+        &self.entries
+    }
+
+    /// Whether `node` is in the map.
+    pub fn contains(&self, node: NodeId) -> bool {
+        self.index.contains_key(&node)
+    }
+
+    /// Rebuild the node -> position index after replacing the entries.
+    fn reindex(&mut self) {
+        self.index.clear();
+        self.index.extend(
+            self.entries
+                .iter()
+                .enumerate()
+                .map(|(i, &(node, _))| (node, i)),
+        );
     }
 
     /// Return all nodes whose region contains the given point, ordered
@@ -308,6 +359,26 @@ mod tests {
         assert_eq!(map.node_at(Offset::new(22, 22)), None);
     }
 
+    #[test]
+    fn set_region_moves_in_place() {
+        let (_dom, ids) = make_ids(3);
+        let mut map = SpatialMap::new();
+        map.update_ordered(&[
+            (ids[0], Region::new(0, 0, 20, 20)),
+            (ids[1], Region::new(0, 0, 5, 5)),
+        ]);
+
+        assert!(map.set_region(ids[1], Region::new(10, 10, 5, 5)));
+        assert_eq!(map.region(ids[1]), Some(Region::new(10, 10, 5, 5)));
+        // Still frontmost at its new position, gone from the old one.
+        assert_eq!(map.node_at(Offset::new(12, 12)), Some(ids[1]));
+        assert_eq!(map.node_at(Offset::new(1, 1)), Some(ids[0]));
+
+        assert!(!map.set_region(ids[2], Region::new(0, 0, 1, 1)));
+        assert!(!map.contains(ids[2]));
+        assert_eq!(map.len(), 2);
+    }
+
     #[test]
     fn hit_test_edge_cases() {
         let (_dom, ids) = make_ids(1);
@@ -0,0 +1,216 @@
+//! Visibility observers: callbacks when nodes enter or leave view.
+//!
+//! A node is visible when some part of its layout region survives clipping:
+//! against every ancestor whose `overflow-x` / `overflow-y` is set (on that
+//! axis only) and against the screen. [`VisibilityObservers::update`] runs
+//! after each layout pass and calls an observer whenever its node's
+//! visibility differs from the last time it was reported, including the first
+//! time it is laid out. Useful for loading content lazily or tracking what
+//! was read in a long list.
+
+use std::collections::HashMap;
+
+use crate::css::styles::Styles;
+use crate::dom::node::NodeId;
+use crate::dom::tree::Dom;
+use crate::geometry::Region;
+use crate::layout::engine::LayoutEngine;
+
+/// Called with the node and whether it is now visible.
+type Callback = Box<dyn FnMut(NodeId, bool)>;
+
+/// One registered callback and the visibility it last reported.
+struct Observer {
+    callback: Callback,
+    visible: Option<bool>,
+}
+
+// ---------------------------------------------------------------------------
+// VisibilityObservers
+// ---------------------------------------------------------------------------
+
+/// Visibility callbacks registered per node.
+#[derive(Default)]
+pub struct VisibilityObservers {
+    observers: HashMap<NodeId, Vec<Observer>>,
+}
+
+impl VisibilityObservers {
+    /// Create an empty set of observers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call `callback` whenever `id` enters or leaves view.
+    pub fn observe(&mut self, id: NodeId, callback: impl FnMut(NodeId, bool) + 'static) {
+        self.observers.entry(id).or_default().push(Observer {
+            callback: Box::new(callback),
+            visible: None,
+        });
+    }
+
+    /// Drop every observer of `id`. Returns how many there were.
+    pub fn unobserve(&mut self, id: NodeId) -> usize {
+        self.observers
+            .remove(&id)
+            .map_or(0, |observers| observers.len())
+    }
+
+    /// Number of observed nodes.
+    pub fn len(&self) -> usize {
+        self.observers.len()
+    }
+
+    /// Whether no node is observed.
+    pub fn is_empty(&self) -> bool {
+        self.observers.is_empty()
+    }
+
+    /// Recompute visibility for every observed node and notify changes.
+    /// Nodes without a layout yet are skipped.
+    pub fn update(
+        &mut self,
+        dom: &Dom,
+        layout: &LayoutEngine,
+        styles: &HashMap<NodeId, Styles>,
+        bounds: Region,
+    ) {
+        for (&id, observers) in &mut self.observers {
+            let Some(region) = layout.get_layout(id) else {
+                continue;
+            };
+            let part = visible_part(region, dom, &|a| layout.get_layout(a), styles, id, bounds);
+            let visible = part.width > 0 && part.height > 0;
+            for observer in observers.iter_mut() {
+                if observer.visible != Some(visible) {
+                    observer.visible = Some(visible);
+                    (observer.callback)(id, visible);
+                }
+            }
+        }
+    }
+
+    /// Forget a removed node, telling its observers it left view if they
+    /// last saw it visible.
+    pub fn remove(&mut self, id: NodeId) {
+        for mut observer in self.observers.remove(&id).unwrap_or_default() {
+            if observer.visible == Some(true) {
+                (observer.callback)(id, false);
+            }
+        }
+    }
+}
+
+/// `region` clipped by the overflow of `id`'s ancestors and by `bounds`.
+/// Ancestors' clip areas are looked up through `region_of`.
+pub(crate) fn visible_part(
+    region: Region,
+    dom: &Dom,
+    region_of: &dyn Fn(NodeId) -> Option<Region>,
+    styles: &HashMap<NodeId, Styles>,
+    id: NodeId,
+    bounds: Region,
+) -> Region {
+    let mut visible = region.intersection(bounds);
+    for ancestor in dom.ancestors(id) {
+        let (Some(style), Some(clip)) = (styles.get(&ancestor), region_of(ancestor)) else {
+            continue;
+        };
+        // Only clip the axes the ancestor actually cuts off.
+        let (x, width) = match style.overflow_x {
+            Some(_) => (clip.x, clip.width),
+            None => (visible.x, visible.width),
+        };
+        let (y, height) = match style.overflow_y {
+            Some(_) => (clip.y, clip.height),
+            None => (visible.y, visible.height),
+        };
+        visible = visible.intersection(Region::new(x, y, width, height));
+    }
+    visible
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::css::scalar::Scalar;
+    use crate::css::styles::Overflow;
+    use crate::dom::node::NodeData;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// A 10x4 clipping list with three 3-row items stacked vertically.
+    fn list() -> (Dom, LayoutEngine, HashMap<NodeId, Styles>, Vec<NodeId>) {
+        let mut dom = Dom::new();
+        let root = dom.insert(NodeData::new("List"));
+        let items: Vec<NodeId> = (0..3)
+            .map(|_| dom.insert_child(root, NodeData::new("Item")))
+            .collect();
+        let mut styles = HashMap::new();
+        let mut list = Styles::new();
+        list.width = Some(Scalar::cells(10.0));
+        list.height = Some(Scalar::cells(4.0));
+        list.overflow_y = Some(Overflow::Hidden);
+        styles.insert(root, list);
+        for &item in &items {
+            let mut s = Styles::new();
+            s.min_height = Some(Scalar::cells(3.0));
+            styles.insert(item, s);
+        }
+        let mut layout = LayoutEngine::new();
+        layout.sync_tree(&dom, &styles, (40, 20)).unwrap();
+        layout.compute(40.0, 20.0).unwrap();
+        (dom, layout, styles, items)
+    }
+
+    fn recorder(observers: &mut VisibilityObservers, id: NodeId) -> Rc<RefCell<Vec<bool>>> {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let sink = log.clone();
+        observers.observe(id, move |_, visible| sink.borrow_mut().push(visible));
+        log
+    }
+
+    #[test]
+    fn clipped_items_report_hidden() {
+        let (dom, layout, styles, items) = list();
+        let mut observers = VisibilityObservers::new();
+        let logs: Vec<_> = items
+            .iter()
+            .map(|&id| recorder(&mut observers, id))
+            .collect();
+        observers.update(&dom, &layout, &styles, Region::new(0, 0, 40, 20));
+        assert_eq!(*logs[0].borrow(), [true]);
+        assert_eq!(*logs[1].borrow(), [true]);
+        assert_eq!(*logs[2].borrow(), [false]);
+
+        // Unchanged visibility is not reported again.
+        observers.update(&dom, &layout, &styles, Region::new(0, 0, 40, 20));
+        assert_eq!(logs[0].borrow().len(), 1);
+    }
+
+    #[test]
+    fn screen_bounds_clip() {
+        let (dom, layout, styles, items) = list();
+        let mut observers = VisibilityObservers::new();
+        let log = recorder(&mut observers, items[1]);
+        observers.update(&dom, &layout, &styles, Region::new(0, 0, 40, 3));
+        observers.update(&dom, &layout, &styles, Region::new(0, 0, 40, 20));
+        assert_eq!(*log.borrow(), [false, true]);
+    }
+
+    #[test]
+    fn remove_reports_leaving() {
+        let (dom, layout, styles, items) = list();
+        let mut observers = VisibilityObservers::new();
+        let log = recorder(&mut observers, items[0]);
+        observers.update(&dom, &layout, &styles, Region::new(0, 0, 40, 20));
+        observers.remove(items[0]);
+        assert_eq!(*log.borrow(), [true, false]);
+        assert!(observers.is_empty());
+        assert_eq!(observers.unobserve(items[0]), 0);
+    }
+}
@@ -17,13 +17,20 @@
 //! - **[`event`]** — Input events, message bubbling, key bindings
 //! - **[`reactive`]** — Signals, effects, memos (Leptos-style auto-tracking)
 //! - **[`render`]** — Strip-based compositor with dirty tracking and crossterm driver
+//! - **[`text`]** — Styled text spans and the `[bold red]…[/]` markup mini-language
 //! - **[`app`]** — Application struct tying everything together
+//! - **[`mode`]** — Named app modes bundling root classes, stylesheets, and bindings
 //! - **[`screen`]** — Screen management with focus chain
+//! - **[`i18n`]** — Runtime string catalogs and the [`t!`] lookup macro
 //! - **[`geometry`]** — Offset, Size, Region, Spacing primitives
+//! - **[`error`]** — [`Error`] hierarchy returned by fallible operations
 
 // Foundation
+pub mod error;
 pub mod geometry;
 
+pub use error::{Error, Result};
+
 // Core systems
 pub mod css;
 pub mod dom;
@@ -39,14 +46,19 @@ pub mod reactive;
 
 // Rendering
 pub mod render;
+pub mod text;
 
 // Application
 pub mod app;
+pub mod mode;
 pub mod screen;
 
+// Localization
+pub mod i18n;
+
 // Testing
 pub mod testing;
 
 // Proc macros (feature-gated)
 #[cfg(feature = "macros")]
-pub use gilt_tui_macros::{view, css};
+pub use gilt_tui_macros::{view, css, selector};
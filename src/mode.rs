@@ -0,0 +1,121 @@
+//! Named app modes: bundles of root classes, stylesheets, and key bindings.
+//!
+//! A [`Mode`] describes how the app looks and behaves in, say, a "zen" or
+//! "presentation" mode: classes put on the root node, stylesheets cascaded
+//! above the app's own, and global key bindings searched before the app's
+//! own. Register modes with `App::add_mode` and switch with `App::set_mode`;
+//! the previous mode's classes, stylesheets, and bindings are swapped out for
+//! the new one's in a single step, so no frame ever shows half of each.
+//!
+//! ```ignore
+//! app.add_mode(
+//!     Mode::new("zen")
+//!         .with_class("zen")
+//!         .with_css(".zen Sidebar { display: none; }")?
+//!         .with_binding(Binding::parse("escape", BindingAction::Custom("leave-zen".into()))?),
+//! );
+//! app.set_mode("zen");
+//! ```
+
+use std::rc::Rc;
+
+use crate::css::parser::parse_css;
+use crate::css::stylesheet::CompiledStylesheet;
+use crate::error::CssError;
+use crate::event::binding::Binding;
+
+/// A named bundle of root classes, stylesheets, and key bindings.
+#[derive(Debug, Clone)]
+pub struct Mode {
+    name: String,
+    classes: Vec<String>,
+    css: Vec<Rc<CompiledStylesheet>>,
+    bindings: Vec<Binding>,
+}
+
+impl Mode {
+    /// Create an empty mode called `name`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            classes: Vec::new(),
+            css: Vec::new(),
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Put `class` on the root node while the mode is active (builder
+    /// pattern).
+    pub fn with_class(mut self, class: impl Into<String>) -> Self {
+        let class = class.into();
+        if !self.classes.contains(&class) {
+            self.classes.push(class);
+        }
+        self
+    }
+
+    /// Cascade `source` above the app's stylesheets while the mode is active
+    /// (builder pattern). Fails if the CSS does not parse.
+    pub fn with_css(mut self, source: &str) -> Result<Self, CssError> {
+        let sheet = parse_css(source)?;
+        self.css
+            .push(Rc::new(CompiledStylesheet::compile(&sheet, false)));
+        Ok(self)
+    }
+
+    /// Add a global key binding that takes precedence over the app's own
+    /// while the mode is active (builder pattern).
+    pub fn with_binding(mut self, binding: Binding) -> Self {
+        self.bindings.push(binding);
+        self
+    }
+
+    /// The mode's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The classes put on the root node.
+    pub fn classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    /// The mode's stylesheets, in cascade order.
+    pub fn stylesheets(&self) -> &[Rc<CompiledStylesheet>] {
+        &self.css
+    }
+
+    /// The mode's key bindings.
+    pub fn bindings(&self) -> &[Binding] {
+        &self.bindings
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::binding::BindingAction;
+
+    #[test]
+    fn builder_collects_classes_css_and_bindings() {
+        let mode = Mode::new("zen")
+            .with_class("zen")
+            .with_class("zen")
+            .with_css("Sidebar { display: none; }")
+            .unwrap()
+            .with_binding(Binding::parse("escape", BindingAction::Quit).unwrap());
+        assert_eq!(mode.name(), "zen");
+        assert_eq!(mode.classes(), ["zen"]);
+        assert_eq!(mode.stylesheets().len(), 1);
+        assert_eq!(mode.bindings().len(), 1);
+    }
+
+    #[test]
+    fn invalid_css_is_rejected() {
+        assert!(Mode::new("broken").with_css("Sidebar { width: ; ").is_err());
+    }
+}
@@ -6,9 +6,22 @@
 //! - [`create_effect`] — auto-tracking side effect.
 //! - [`create_memo`] — cached derived computation.
 //! - [`batch`] — coalesce multiple writes into one notification pass.
+//! - [`create_resource`] — a value that is loading, loaded, or failed.
+//! - [`Runtime`] — an isolated set of signals and effects, e.g. one per app.
+//! - [`create_sync_signal`] — a signal worker threads can write to.
+//! - [`trace`] — opt-in recording of why each effect re-ran.
 
 pub mod signal;
 pub mod effect;
+pub mod resource;
+pub mod sync_signal;
+pub mod trace;
 
-pub use signal::{create_signal, ReadSignal, WriteSignal};
+pub use signal::{
+    create_signal, runtime_stats, ReadSignal, Runtime, RuntimeId, RuntimeStats, SignalId,
+    WriteSignal,
+};
 pub use effect::{batch, create_effect, create_effect_with_id, create_memo, dispose_effect, EffectId};
+pub use resource::{create_resource, Resource, ResourceState};
+pub use sync_signal::{apply_sync_writes, create_sync_signal, SyncSignal};
+pub use trace::EffectRun;
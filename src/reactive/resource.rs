@@ -0,0 +1,202 @@
+//! Resources: signals for values that arrive later.
+//!
+//! A [`Resource`] starts out loading and is settled when the load finishes,
+//! typically by the app when a background task posts its result back.
+//! Reading it subscribes the running effect like any signal, and also reports
+//! its state to the enclosing
+//! [`Suspense`](crate::widget::control_flow::Suspense) boundary, if any, so
+//! the boundary can show a fallback until everything inside it has loaded.
+//!
+//! ```ignore
+//! let profile = create_resource::<Profile, String>();
+//! app.widget_ctx(node).spawn(move |ctx| {
+//!     ctx.post(ProfileLoaded(fetch_profile()));
+//! });
+//! // ... in the ProfileLoaded handler:
+//! profile.settle(result);
+//! ```
+
+use std::cell::RefCell;
+use std::fmt;
+
+use super::signal::{create_signal, ReadSignal, WriteSignal};
+
+// ---------------------------------------------------------------------------
+// ResourceState
+// ---------------------------------------------------------------------------
+
+/// Where a resource is in its load.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceState<T, E> {
+    Loading,
+    Ready(T),
+    Failed(E),
+}
+
+// ---------------------------------------------------------------------------
+// Suspense tracking
+// ---------------------------------------------------------------------------
+
+/// A resource's state as seen by a suspense boundary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ResourceStatus {
+    Loading,
+    Ready,
+    Failed(String),
+}
+
+thread_local! {
+    /// One frame per suspense boundary currently rendering, innermost last.
+    static SUSPENSE: RefCell<Vec<Vec<ResourceStatus>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Start collecting the resources read while rendering a suspense boundary.
+pub(crate) fn begin_suspense() {
+    SUSPENSE.with(|frames| frames.borrow_mut().push(Vec::new()));
+}
+
+/// Stop collecting, returning the state of each resource read since the
+/// matching [`begin_suspense`].
+pub(crate) fn end_suspense() -> Vec<ResourceStatus> {
+    SUSPENSE.with(|frames| frames.borrow_mut().pop().unwrap_or_default())
+}
+
+fn report(status: ResourceStatus) {
+    SUSPENSE.with(|frames| {
+        if let Some(frame) = frames.borrow_mut().last_mut() {
+            frame.push(status);
+        }
+    });
+}
+
+// ---------------------------------------------------------------------------
+// Resource
+// ---------------------------------------------------------------------------
+
+/// Create a resource in the loading state.
+pub fn create_resource<T: 'static, E: 'static>() -> Resource<T, E> {
+    let (read, write) = create_signal(ResourceState::Loading);
+    Resource { read, write }
+}
+
+/// A value that is loading, loaded, or failed to load. `Copy` — only stores
+/// signal ids.
+pub struct Resource<T: 'static, E: 'static> {
+    read: ReadSignal<ResourceState<T, E>>,
+    write: WriteSignal<ResourceState<T, E>>,
+}
+
+impl<T: 'static, E: 'static> Copy for Resource<T, E> {}
+impl<T: 'static, E: 'static> Clone for Resource<T, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static, E: 'static> fmt::Debug for Resource<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Resource")
+            .field("read", &self.read)
+            .finish()
+    }
+}
+
+impl<T: 'static, E: fmt::Display + 'static> Resource<T, E> {
+    /// Read the state by reference, subscribing the running effect and
+    /// reporting to the enclosing suspense boundary.
+    pub fn with<R>(&self, f: impl FnOnce(&ResourceState<T, E>) -> R) -> R {
+        self.read.with(|state| {
+            report(match state {
+                ResourceState::Loading => ResourceStatus::Loading,
+                ResourceState::Ready(_) => ResourceStatus::Ready,
+                ResourceState::Failed(error) => ResourceStatus::Failed(error.to_string()),
+            });
+            f(state)
+        })
+    }
+
+    /// The loaded value, if ready.
+    pub fn get(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.with(|state| match state {
+            ResourceState::Ready(value) => Some(value.clone()),
+            _ => None,
+        })
+    }
+
+    /// Whether the resource is still loading.
+    pub fn is_loading(&self) -> bool {
+        self.with(|state| matches!(state, ResourceState::Loading))
+    }
+}
+
+impl<T: 'static, E: 'static> Resource<T, E> {
+    /// Finish loading with `value`.
+    pub fn resolve(&self, value: T) {
+        self.write.set(ResourceState::Ready(value));
+    }
+
+    /// Finish loading with an error.
+    pub fn fail(&self, error: E) {
+        self.write.set(ResourceState::Failed(error));
+    }
+
+    /// Finish loading with a result.
+    pub fn settle(&self, result: Result<T, E>) {
+        match result {
+            Ok(value) => self.resolve(value),
+            Err(error) => self.fail(error),
+        }
+    }
+
+    /// Go back to loading, e.g. before fetching again.
+    pub fn reload(&self) {
+        self.write.set(ResourceState::Loading);
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reactive::create_effect;
+    use crate::reactive::signal::reset_runtime;
+    use std::rc::Rc;
+
+    #[test]
+    fn settles_and_notifies() {
+        reset_runtime();
+        let resource = create_resource::<u32, String>();
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let log = seen.clone();
+        create_effect(move || log.borrow_mut().push(resource.get()));
+        assert!(resource.is_loading());
+        resource.resolve(7);
+        resource.reload();
+        resource.settle(Err("offline".into()));
+        assert_eq!(*seen.borrow(), [None, Some(7), None, None]);
+        resource.with(|state| assert_eq!(state, &ResourceState::Failed("offline".into())));
+    }
+
+    #[test]
+    fn reads_report_to_innermost_boundary() {
+        reset_runtime();
+        let a = create_resource::<u32, String>();
+        let b = create_resource::<u32, String>();
+        b.fail("boom".into());
+        a.get(); // outside any boundary: not collected
+
+        begin_suspense();
+        a.get();
+        begin_suspense();
+        b.get();
+        assert_eq!(end_suspense(), [ResourceStatus::Failed("boom".into())]);
+        assert_eq!(end_suspense(), [ResourceStatus::Loading]);
+        assert!(end_suspense().is_empty());
+    }
+}
@@ -2,13 +2,25 @@
 //!
 //! Fine-grained reactive primitives: signals store values, effects auto-track
 //! reads, and memos cache derived computations. Modeled after Leptos's
-//! client-side reactivity (single-threaded, synchronous, thread-local runtime).
+//! client-side reactivity (single-threaded, synchronous).
+//!
+//! Signals and effects live in a [`Runtime`]. Each thread has a current
+//! runtime, which the free functions here operate on; [`Runtime::run`] makes
+//! another one current for the duration of a closure, so independent apps
+//! (or tests) on one thread don't share state. A signal remembers the
+//! runtime it was created in and is always read and written there, so a
+//! signal created inside [`App::enter`](crate::app::App::enter) can still be
+//! read by a widget rendering outside it.
 
 use std::any::Any;
 use std::cell::RefCell;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::marker::PhantomData;
+use std::rc::{Rc, Weak};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::trace::{EffectRun, TraceLog};
 
 // ---------------------------------------------------------------------------
 // IDs
@@ -22,6 +34,24 @@ pub struct SignalId(usize);
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct EffectId(usize);
 
+/// Identifies a [`Runtime`], unique for the life of the process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RuntimeId(u64);
+
+impl SignalId {
+    /// The slot index, unique within the runtime.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+impl EffectId {
+    /// The slot index, unique within the runtime.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Runtime internals
 // ---------------------------------------------------------------------------
@@ -40,7 +70,8 @@ struct EffectState {
     active: bool,
 }
 
-struct Runtime {
+struct RuntimeState {
+    id: RuntimeId,
     signals: Vec<SignalState>,
     effects: Vec<EffectState>,
     /// The effect currently executing (for auto-tracking).
@@ -52,23 +83,209 @@ struct Runtime {
     /// Guard against recursive effect execution triggered by `set` inside an
     /// effect that is itself being executed by the notification loop.
     running_effects: bool,
+    /// Appliers for writes queued by `SyncSignal`s from other threads.
+    sync_appliers: Vec<SyncApplier>,
+    /// Whether effect runs are recorded in `trace`.
+    tracing: bool,
+    /// While tracing, the signal whose write last scheduled each effect.
+    triggers: HashMap<EffectId, SignalId>,
+    /// Recorded effect runs.
+    trace: TraceLog,
 }
 
-impl Runtime {
+/// Applies the latest cross-thread write queued for one signal. Returns
+/// whether a value was written, or `None` once no handle can write any more
+/// and the applier should be dropped.
+pub(super) type SyncApplier = Box<dyn FnMut() -> Option<bool>>;
+
+impl RuntimeState {
     fn new() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
         Self {
+            id: RuntimeId(NEXT_ID.fetch_add(1, Ordering::Relaxed)),
             signals: Vec::new(),
             effects: Vec::new(),
             tracking: None,
             batch_depth: 0,
             pending_effects: Vec::new(),
             running_effects: false,
+            sync_appliers: Vec::new(),
+            tracing: false,
+            triggers: HashMap::new(),
+            trace: TraceLog::default(),
         }
     }
 }
 
+// ---------------------------------------------------------------------------
+// Runtime
+// ---------------------------------------------------------------------------
+
+/// A handle to an isolated reactive runtime.
+///
+/// Cloning the handle shares the runtime. Signals and effects belong to the
+/// runtime that was current when they were created. Signals switch to it
+/// whenever they are read or written, so they work while another runtime is
+/// current, but only on the thread that created them and only while the
+/// runtime is alive. Such a read is not tracked: an effect only re-runs for
+/// signals of its own runtime.
+///
+/// # Examples
+///
+/// ```ignore
+/// let runtime = Runtime::new();
+/// let count = runtime.run(|| {
+///     let (count, set_count) = create_signal(0);
+///     set_count.set(1);
+///     count
+/// });
+/// assert_eq!(runtime.run(|| count.get()), 1);
+/// ```
+#[derive(Clone)]
+pub struct Runtime(Rc<RefCell<RuntimeState>>);
+
+impl Default for Runtime {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Runtime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Runtime").field(&self.stats()).finish()
+    }
+}
+
+impl Runtime {
+    /// Create a new, empty runtime.
+    pub fn new() -> Self {
+        let state = Rc::new(RefCell::new(RuntimeState::new()));
+        let id = state.borrow().id;
+        REGISTRY.with(|registry| registry.borrow_mut().insert(id, Rc::downgrade(&state)));
+        Self(state)
+    }
+
+    /// This runtime's id.
+    pub fn id(&self) -> RuntimeId {
+        self.0.borrow().id
+    }
+
+    /// The live runtime with `id` on this thread, if any.
+    fn lookup(id: RuntimeId) -> Option<Self> {
+        REGISTRY
+            .with(|registry| registry.borrow().get(&id).and_then(Weak::upgrade))
+            .map(Self)
+    }
+
+    /// A handle to the current thread's current runtime.
+    pub fn current() -> Self {
+        CURRENT.with(|current| current.borrow().clone())
+    }
+
+    /// Whether this runtime is the current one.
+    pub fn is_current(&self) -> bool {
+        CURRENT.with(|current| Rc::ptr_eq(&current.borrow().0, &self.0))
+    }
+
+    /// Run `f` with this runtime as the current one, restoring the previous
+    /// runtime afterwards (even if `f` panics).
+    pub fn run<R>(&self, f: impl FnOnce() -> R) -> R {
+        struct Restore(Option<Runtime>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                if let Some(previous) = self.0.take() {
+                    CURRENT.with(|current| *current.borrow_mut() = previous);
+                }
+            }
+        }
+
+        let previous = CURRENT.with(|current| current.replace(self.clone()));
+        let _restore = Restore(Some(previous));
+        f()
+    }
+
+    /// Turn recording of effect runs on or off (see [`super::trace`]).
+    /// Turning it off keeps the runs recorded so far.
+    pub fn set_tracing(&self, enabled: bool) {
+        let mut rt = self.0.borrow_mut();
+        rt.tracing = enabled;
+        rt.triggers.clear();
+    }
+
+    /// Whether effect runs are being recorded.
+    pub fn is_tracing(&self) -> bool {
+        self.0.borrow().tracing
+    }
+
+    /// Recorded effect runs, oldest first.
+    pub fn trace(&self) -> Vec<EffectRun> {
+        self.0.borrow().trace.runs().cloned().collect()
+    }
+
+    /// Take every recorded effect run, oldest first.
+    pub fn drain_trace(&self) -> Vec<EffectRun> {
+        self.0.borrow_mut().trace.drain()
+    }
+
+    /// The most recent recorded run of `effect`: why it last ran and what
+    /// it read.
+    pub fn last_run(&self, effect: EffectId) -> Option<EffectRun> {
+        self.0.borrow().trace.last_run(effect).cloned()
+    }
+
+    /// Snapshot this runtime's arena sizes.
+    pub fn stats(&self) -> RuntimeStats {
+        let rt = self.0.borrow();
+        RuntimeStats {
+            signals: rt.signals.len(),
+            effects: rt.effects.len(),
+            active_effects: rt.effects.iter().filter(|e| e.active).count(),
+        }
+    }
+}
+
+impl Drop for RuntimeState {
+    fn drop(&mut self) {
+        // The registry may already be gone while the thread exits.
+        let _ = REGISTRY.try_with(|registry| registry.borrow_mut().remove(&self.id));
+    }
+}
+
 thread_local! {
-    pub(crate) static RUNTIME: RefCell<Runtime> = RefCell::new(Runtime::new());
+    static CURRENT: RefCell<Runtime> = RefCell::new(Runtime::new());
+    /// Every live runtime on this thread, for signals to find their owner.
+    static REGISTRY: RefCell<HashMap<RuntimeId, Weak<RefCell<RuntimeState>>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Run `f` against the current runtime's state.
+fn with_runtime<R>(f: impl FnOnce(&RefCell<RuntimeState>) -> R) -> R {
+    let runtime = Runtime::current();
+    f(&runtime.0)
+}
+
+/// Run `f` with the runtime `owner` current, switching to it if needed.
+///
+/// Dependency tracking does not cross runtimes: after a switch the tracking
+/// effect is the owner's (normally none), so an effect in one runtime that
+/// reads a signal owned by another is not subscribed to it.
+///
+/// # Panics
+///
+/// Panics if the runtime has been dropped or belongs to another thread.
+fn with_owner<R>(owner: RuntimeId, f: impl FnOnce() -> R) -> R {
+    let Some(runtime) = Runtime::lookup(owner) else {
+        panic!(
+            "signal used after its runtime was dropped, or on a thread other than the one \
+             that created it"
+        );
+    };
+    if runtime.is_current() {
+        f()
+    } else {
+        runtime.run(f)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -80,23 +297,25 @@ thread_local! {
 /// Returns a `(ReadSignal<T>, WriteSignal<T>)` pair. Reading inside an effect
 /// automatically subscribes that effect to changes.
 pub fn create_signal<T: 'static>(initial: T) -> (ReadSignal<T>, WriteSignal<T>) {
-    let id = RUNTIME.with(|rt| {
+    let (id, runtime) = with_runtime(|rt| {
         let mut rt = rt.borrow_mut();
         let id = SignalId(rt.signals.len());
         rt.signals.push(SignalState {
             value: Box::new(initial),
             subscribers: HashSet::new(),
         });
-        id
+        (id, rt.id)
     });
 
     (
         ReadSignal {
             id,
+            runtime,
             _marker: PhantomData,
         },
         WriteSignal {
             id,
+            runtime,
             _marker: PhantomData,
         },
     )
@@ -106,9 +325,10 @@ pub fn create_signal<T: 'static>(initial: T) -> (ReadSignal<T>, WriteSignal<T>)
 // ReadSignal
 // ---------------------------------------------------------------------------
 
-/// Read-half of a signal. `Copy` — only stores an id.
+/// Read-half of a signal. `Copy` — only stores its id and its runtime's.
 pub struct ReadSignal<T: 'static> {
     id: SignalId,
+    runtime: RuntimeId,
     _marker: PhantomData<T>,
 }
 
@@ -129,6 +349,11 @@ impl<T: 'static> fmt::Debug for ReadSignal<T> {
 }
 
 impl<T: 'static> ReadSignal<T> {
+    /// The signal's id, as reported by tracing.
+    pub fn id(&self) -> SignalId {
+        self.id
+    }
+
     /// Read the current value, subscribing the running effect (if any).
     pub fn get(&self) -> T
     where
@@ -139,7 +364,7 @@ impl<T: 'static> ReadSignal<T> {
 
     /// Read by reference without cloning. Still subscribes the running effect.
     pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
-        RUNTIME.with(|rt| {
+        with_owner(self.runtime, || with_runtime(|rt| {
             // -- track dependency --
             {
                 let mut rt_ref = rt.borrow_mut();
@@ -152,7 +377,7 @@ impl<T: 'static> ReadSignal<T> {
             let rt_ref = rt.borrow();
             let any_ref = &rt_ref.signals[self.id.0].value;
             f(any_ref.downcast_ref::<T>().expect("signal type mismatch"))
-        })
+        }))
     }
 
     /// Read without tracking — will not subscribe any running effect.
@@ -160,14 +385,14 @@ impl<T: 'static> ReadSignal<T> {
     where
         T: Clone,
     {
-        RUNTIME.with(|rt| {
+        with_owner(self.runtime, || with_runtime(|rt| {
             let rt_ref = rt.borrow();
             let any_ref = &rt_ref.signals[self.id.0].value;
             any_ref
                 .downcast_ref::<T>()
                 .expect("signal type mismatch")
                 .clone()
-        })
+        }))
     }
 }
 
@@ -175,9 +400,10 @@ impl<T: 'static> ReadSignal<T> {
 // WriteSignal
 // ---------------------------------------------------------------------------
 
-/// Write-half of a signal. `Copy` — only stores an id.
+/// Write-half of a signal. `Copy` — only stores its id and its runtime's.
 pub struct WriteSignal<T: 'static> {
     id: SignalId,
+    runtime: RuntimeId,
     _marker: PhantomData<T>,
 }
 
@@ -197,36 +423,37 @@ impl<T: 'static> fmt::Debug for WriteSignal<T> {
 }
 
 impl<T: 'static> WriteSignal<T> {
+    /// The signal's id, as reported by tracing.
+    pub fn id(&self) -> SignalId {
+        self.id
+    }
+
     /// Overwrite the signal value and notify subscribers.
     pub fn set(&self, value: T) {
-        let subs = RUNTIME.with(|rt| {
-            let mut rt_ref = rt.borrow_mut();
-            rt_ref.signals[self.id.0].value = Box::new(value);
-            rt_ref.signals[self.id.0]
-                .subscribers
-                .iter()
-                .copied()
-                .collect::<Vec<_>>()
-        });
-        notify_subscribers(subs);
+        with_owner(self.runtime, || {
+            let subs = with_runtime(|rt| {
+                let mut rt_ref = rt.borrow_mut();
+                rt_ref.signals[self.id.0].value = Box::new(value);
+                written(&mut rt_ref, self.id)
+            });
+            notify_subscribers(subs);
+        })
     }
 
     /// Mutate the value in-place and notify subscribers.
     pub fn update(&self, f: impl FnOnce(&mut T)) {
-        let subs = RUNTIME.with(|rt| {
-            let mut rt_ref = rt.borrow_mut();
-            let any_mut = &mut rt_ref.signals[self.id.0].value;
-            let val = any_mut
-                .downcast_mut::<T>()
-                .expect("signal type mismatch");
-            f(val);
-            rt_ref.signals[self.id.0]
-                .subscribers
-                .iter()
-                .copied()
-                .collect::<Vec<_>>()
-        });
-        notify_subscribers(subs);
+        with_owner(self.runtime, || {
+            let subs = with_runtime(|rt| {
+                let mut rt_ref = rt.borrow_mut();
+                let any_mut = &mut rt_ref.signals[self.id.0].value;
+                let val = any_mut
+                    .downcast_mut::<T>()
+                    .expect("signal type mismatch");
+                f(val);
+                written(&mut rt_ref, self.id)
+            });
+            notify_subscribers(subs);
+        })
     }
 }
 
@@ -239,7 +466,7 @@ impl<T: 'static> WriteSignal<T> {
 /// The closure runs immediately once (establishing initial subscriptions),
 /// then re-runs whenever any tracked signal changes.
 pub fn create_effect(f: impl FnMut() + 'static) {
-    let eid = RUNTIME.with(|rt| {
+    let eid = with_runtime(|rt| {
         let mut rt_ref = rt.borrow_mut();
         let eid = EffectId(rt_ref.effects.len());
         rt_ref.effects.push(EffectState {
@@ -282,7 +509,7 @@ pub fn create_memo<T: Clone + PartialEq + 'static>(
 
     // Simplest approach: create signal with a value computed eagerly, then
     // wrap in an effect that keeps it up-to-date.
-    let first_value: T = RUNTIME.with(|rt| {
+    let first_value: T = with_runtime(|rt| {
         // Temporarily clear tracking so the eager evaluation doesn't
         // subscribe a parent effect.
         let prev = rt.borrow_mut().tracking.take();
@@ -297,7 +524,8 @@ pub fn create_memo<T: Clone + PartialEq + 'static>(
     // conditionally write the new value (only if it differs).
     create_effect(move || {
         let new_val = f();
-        let changed = read.with(|old| old != &new_val);
+        // Compare untracked: the memo must not depend on its own output.
+        let changed = read.get_untracked() != new_val;
         if changed {
             write.set(new_val);
         }
@@ -320,13 +548,13 @@ pub fn create_memo<T: Clone + PartialEq + 'static>(
 /// // Effects that depend on a and/or b run once here.
 /// ```
 pub fn batch(f: impl FnOnce()) {
-    RUNTIME.with(|rt| {
+    with_runtime(|rt| {
         rt.borrow_mut().batch_depth += 1;
     });
 
     f();
 
-    let pending = RUNTIME.with(|rt| {
+    let pending = with_runtime(|rt| {
         let mut rt_ref = rt.borrow_mut();
         rt_ref.batch_depth -= 1;
         if rt_ref.batch_depth == 0 {
@@ -354,7 +582,7 @@ pub fn batch(f: impl FnOnce()) {
 
 /// Deactivate an effect so it no longer re-runs when its dependencies change.
 pub fn dispose_effect(eid: EffectId) {
-    RUNTIME.with(|rt| {
+    with_runtime(|rt| {
         let mut rt_ref = rt.borrow_mut();
         if eid.0 < rt_ref.effects.len() {
             rt_ref.effects[eid.0].active = false;
@@ -369,7 +597,7 @@ pub fn dispose_effect(eid: EffectId) {
 
 /// Create an effect and return its [`EffectId`] so it can later be disposed.
 pub fn create_effect_with_id(f: impl FnMut() + 'static) -> EffectId {
-    let eid = RUNTIME.with(|rt| {
+    let eid = with_runtime(|rt| {
         let mut rt_ref = rt.borrow_mut();
         let eid = EffectId(rt_ref.effects.len());
         rt_ref.effects.push(EffectState {
@@ -387,10 +615,20 @@ pub fn create_effect_with_id(f: impl FnMut() + 'static) -> EffectId {
 // Internal helpers
 // ---------------------------------------------------------------------------
 
+/// The subscribers to notify after `sid` was written, noting the write as
+/// their trigger while tracing.
+fn written(rt: &mut RuntimeState, sid: SignalId) -> Vec<EffectId> {
+    let subs: Vec<EffectId> = rt.signals[sid.0].subscribers.iter().copied().collect();
+    if rt.tracing {
+        rt.triggers.extend(subs.iter().map(|&eid| (eid, sid)));
+    }
+    subs
+}
+
 /// Run a single effect: clear old deps, set tracking, execute callback.
 fn run_effect(eid: EffectId) {
     // Check if effect is still active; take the callback out.
-    let maybe_cb = RUNTIME.with(|rt| {
+    let maybe_cb = with_runtime(|rt| {
         let mut rt_ref = rt.borrow_mut();
         if eid.0 >= rt_ref.effects.len() || !rt_ref.effects[eid.0].active {
             return None;
@@ -409,7 +647,7 @@ fn run_effect(eid: EffectId) {
     };
 
     // Set tracking context.
-    let prev_tracking = RUNTIME.with(|rt| {
+    let prev_tracking = with_runtime(|rt| {
         let mut rt_ref = rt.borrow_mut();
         let prev = rt_ref.tracking.take();
         rt_ref.tracking = Some(eid);
@@ -420,9 +658,20 @@ fn run_effect(eid: EffectId) {
     cb();
 
     // Restore tracking and put the callback back.
-    RUNTIME.with(|rt| {
+    with_runtime(|rt| {
         let mut rt_ref = rt.borrow_mut();
         rt_ref.tracking = prev_tracking;
+        if rt_ref.tracing {
+            let trigger = rt_ref.triggers.remove(&eid);
+            let mut dependencies: Vec<SignalId> =
+                rt_ref.effects[eid.0].dependencies.iter().copied().collect();
+            dependencies.sort_by_key(|sid| sid.0);
+            rt_ref.trace.push(EffectRun {
+                effect: eid,
+                trigger,
+                dependencies,
+            });
+        }
         // Put callback back (only if effect still active).
         if eid.0 < rt_ref.effects.len() && rt_ref.effects[eid.0].active {
             rt_ref.effects[eid.0].callback = Some(cb);
@@ -436,13 +685,13 @@ fn notify_subscribers(subs: Vec<EffectId>) {
         return;
     }
 
-    let batching = RUNTIME.with(|rt| {
+    let batching = with_runtime(|rt| {
         let rt_ref = rt.borrow();
         rt_ref.batch_depth > 0
     });
 
     if batching {
-        RUNTIME.with(|rt| {
+        with_runtime(|rt| {
             let mut rt_ref = rt.borrow_mut();
             rt_ref.pending_effects.extend(subs);
         });
@@ -450,21 +699,21 @@ fn notify_subscribers(subs: Vec<EffectId>) {
     }
 
     // Guard against re-entrant notification (effect -> set -> effect -> ...).
-    let already_running = RUNTIME.with(|rt| {
+    let already_running = with_runtime(|rt| {
         let rt_ref = rt.borrow();
         rt_ref.running_effects
     });
 
     if already_running {
         // We're already inside the notification loop. Queue for later.
-        RUNTIME.with(|rt| {
+        with_runtime(|rt| {
             let mut rt_ref = rt.borrow_mut();
             rt_ref.pending_effects.extend(subs);
         });
         return;
     }
 
-    RUNTIME.with(|rt| {
+    with_runtime(|rt| {
         rt.borrow_mut().running_effects = true;
     });
 
@@ -472,7 +721,7 @@ fn notify_subscribers(subs: Vec<EffectId>) {
     while !queue.is_empty() {
         let current_batch = std::mem::take(&mut queue);
         for eid in current_batch {
-            let active = RUNTIME.with(|rt| {
+            let active = with_runtime(|rt| {
                 let rt_ref = rt.borrow();
                 eid.0 < rt_ref.effects.len() && rt_ref.effects[eid.0].active
             });
@@ -481,26 +730,67 @@ fn notify_subscribers(subs: Vec<EffectId>) {
             }
         }
         // Check if running effects triggered more pending effects.
-        RUNTIME.with(|rt| {
+        with_runtime(|rt| {
             let mut rt_ref = rt.borrow_mut();
             queue.append(&mut rt_ref.pending_effects);
         });
     }
 
-    RUNTIME.with(|rt| {
+    with_runtime(|rt| {
         rt.borrow_mut().running_effects = false;
     });
 }
 
 // ---------------------------------------------------------------------------
-// Test helper: reset the thread-local runtime between tests
+// Cross-thread writes
+// ---------------------------------------------------------------------------
+
+/// Register an applier for a `SyncSignal` on the current runtime.
+pub(super) fn register_sync_applier(applier: SyncApplier) {
+    with_runtime(|rt| rt.borrow_mut().sync_appliers.push(applier));
+}
+
+/// Run `f` over the current runtime's appliers without holding the runtime
+/// borrowed, so appliers can write signals. Appliers registered meanwhile
+/// are kept.
+pub(super) fn with_sync_appliers<R>(f: impl FnOnce(&mut Vec<SyncApplier>) -> R) -> R {
+    let mut appliers = with_runtime(|rt| std::mem::take(&mut rt.borrow_mut().sync_appliers));
+    let result = f(&mut appliers);
+    with_runtime(|rt| {
+        let mut rt = rt.borrow_mut();
+        appliers.append(&mut rt.sync_appliers);
+        rt.sync_appliers = appliers;
+    });
+    result
+}
+
+// ---------------------------------------------------------------------------
+// Stats
+// ---------------------------------------------------------------------------
+
+/// Size of a reactive runtime's arenas.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RuntimeStats {
+    /// Signals ever created (slots are never reclaimed).
+    pub signals: usize,
+    /// Effects ever created, including disposed ones.
+    pub effects: usize,
+    /// Effects that are still active.
+    pub active_effects: usize,
+}
+
+/// Snapshot the current runtime's arena sizes.
+pub fn runtime_stats() -> RuntimeStats {
+    Runtime::current().stats()
+}
+
+// ---------------------------------------------------------------------------
+// Test helper: reset the current runtime between tests
 // ---------------------------------------------------------------------------
 
 #[cfg(test)]
 pub(crate) fn reset_runtime() {
-    RUNTIME.with(|rt| {
-        *rt.borrow_mut() = Runtime::new();
-    });
+    CURRENT.with(|current| *current.borrow_mut() = Runtime::new());
 }
 
 // ---------------------------------------------------------------------------
@@ -917,4 +1207,117 @@ mod tests {
         // 0+1+100+3+4 = 108
         assert_eq!(sum.get(), 108);
     }
+
+    #[test]
+    fn runtime_stats_counts_arenas() {
+        setup();
+        let (a, _) = create_signal(1);
+        let _b = create_signal("x");
+        let eid = create_effect_with_id(move || {
+            a.get();
+        });
+        create_effect(|| {});
+        dispose_effect(eid);
+
+        let stats = runtime_stats();
+        assert_eq!(stats.signals, 2);
+        assert_eq!(stats.effects, 2);
+        assert_eq!(stats.active_effects, 1);
+    }
+
+    #[test]
+    fn runtimes_are_isolated() {
+        setup();
+        let outer = Runtime::current();
+        let (a, b) = (Runtime::new(), Runtime::new());
+        let count = a.run(|| {
+            let (count, set_count) = create_signal(0);
+            create_effect(move || {
+                count.get();
+            });
+            set_count.set(5);
+            count
+        });
+        b.run(|| {
+            create_signal("other");
+            assert!(b.is_current());
+        });
+
+        assert_eq!(a.run(|| count.get()), 5);
+        assert_eq!(a.stats().effects, 1);
+        assert_eq!(b.stats().signals, 1);
+        assert_eq!(b.stats().effects, 0);
+        assert_eq!(runtime_stats(), RuntimeStats::default());
+        assert!(outer.is_current());
+    }
+
+    #[test]
+    fn signals_are_used_in_their_own_runtime() {
+        setup();
+        let (a, b) = (Runtime::new(), Runtime::new());
+        let runs = Rc::new(Cell::new(0));
+        let runs_c = runs.clone();
+        let (count, set_count) = a.run(|| {
+            let (count, set_count) = create_signal(1);
+            create_effect(move || {
+                count.get();
+                runs_c.set(runs_c.get() + 1);
+            });
+            (count, set_count)
+        });
+        // Slot 0 of `b` holds a different type; the signal still finds `a`.
+        b.run(|| {
+            create_signal("other");
+            assert_eq!(count.get(), 1);
+            set_count.set(2);
+            assert!(b.is_current());
+        });
+        assert_eq!(count.get_untracked(), 2);
+        assert_eq!(runs.get(), 2);
+        assert_eq!(runtime_stats(), RuntimeStats::default());
+    }
+
+    #[test]
+    fn effects_do_not_track_signals_of_other_runtimes() {
+        setup();
+        let (a, b) = (Runtime::new(), Runtime::new());
+        let (count, set_count) = a.run(|| create_signal(0));
+        let runs = Rc::new(Cell::new(0));
+        let runs_c = runs.clone();
+        b.run(|| {
+            create_effect(move || {
+                count.get();
+                runs_c.set(runs_c.get() + 1);
+            });
+        });
+        set_count.set(1);
+        assert_eq!(runs.get(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "signal used after its runtime was dropped")]
+    fn signal_of_dropped_runtime_panics_clearly() {
+        setup();
+        let runtime = Runtime::new();
+        let (count, _) = runtime.run(|| create_signal(0));
+        drop(runtime);
+        count.get();
+    }
+
+    #[test]
+    fn run_restores_previous_runtime_on_panic() {
+        use std::panic::AssertUnwindSafe;
+
+        setup();
+        let inner = Runtime::new();
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| inner.run(|| panic!("boom"))));
+        assert!(result.is_err());
+        assert!(!inner.is_current());
+        // Nested runs restore in order.
+        let (x, y) = (Runtime::new(), Runtime::new());
+        x.run(|| {
+            y.run(|| assert!(y.is_current()));
+            assert!(x.is_current());
+        });
+    }
 }
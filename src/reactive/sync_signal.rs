@@ -0,0 +1,193 @@
+//! Thread-safe signal handles for worker threads.
+//!
+//! [`create_sync_signal`] pairs a normal [`ReadSignal`] with a [`SyncSignal`]:
+//! a `Send + Sync` write handle that background threads can hold. Their
+//! writes are queued rather than applied, since signals and effects live on
+//! the UI thread; [`apply_sync_writes`] (called by the app once per frame)
+//! writes the latest queued value of each signal, running effects once.
+//! Intermediate values written between frames are dropped.
+//!
+//! ```ignore
+//! let (progress, publish) = app.create_sync_signal(0.0);
+//! std::thread::spawn(move || {
+//!     for (i, chunk) in chunks.iter().enumerate() {
+//!         process(chunk);
+//!         publish.set(i as f32 / chunks.len() as f32);
+//!     }
+//! });
+//! ```
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use super::signal::{batch, create_signal, register_sync_applier, with_sync_appliers, ReadSignal};
+use crate::event::inbox::InboxSender;
+
+/// The latest value written from another thread, not yet applied.
+type Pending<T> = Arc<Mutex<Option<T>>>;
+
+// ---------------------------------------------------------------------------
+// SyncSignal
+// ---------------------------------------------------------------------------
+
+/// A write handle that can be sent to and shared between threads.
+///
+/// Created by [`create_sync_signal`]. Writes take effect on the UI thread at
+/// the next [`apply_sync_writes`].
+pub struct SyncSignal<T> {
+    pending: Pending<T>,
+    waker: Option<InboxSender>,
+}
+
+impl<T> Clone for SyncSignal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            pending: self.pending.clone(),
+            waker: self.waker.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for SyncSignal<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SyncSignal")
+            .field("pending", &self.has_pending())
+            .finish()
+    }
+}
+
+impl<T> SyncSignal<T> {
+    /// Wake the app's loop through `sender` on every write (builder pattern).
+    pub fn with_waker(mut self, sender: InboxSender) -> Self {
+        self.waker = Some(sender);
+        self
+    }
+
+    /// Queue `value` to be written at the next frame, replacing any value
+    /// queued since the last one.
+    pub fn set(&self, value: T) {
+        *self.pending.lock().unwrap_or_else(|e| e.into_inner()) = Some(value);
+        if let Some(waker) = &self.waker {
+            waker.wake();
+        }
+    }
+
+    /// Whether a write is waiting to be applied.
+    pub fn has_pending(&self) -> bool {
+        self.pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_some()
+    }
+}
+
+/// Create a signal that worker threads can write through a [`SyncSignal`].
+///
+/// Must be called on the UI thread; the signal belongs to the current runtime,
+/// and its writes are applied by [`apply_sync_writes`] on that runtime.
+pub fn create_sync_signal<T: Send + 'static>(initial: T) -> (ReadSignal<T>, SyncSignal<T>) {
+    let (read, write) = create_signal(initial);
+    let pending: Pending<T> = Arc::new(Mutex::new(None));
+    let queued = pending.clone();
+    register_sync_applier(Box::new(move || {
+        let value = queued.lock().unwrap_or_else(|e| e.into_inner()).take();
+        let applied = value.map(|value| write.set(value)).is_some();
+        // Once every handle is dropped and the last write applied, only the
+        // applier holds the queue.
+        (applied || Arc::strong_count(&queued) > 1).then_some(applied)
+    }));
+    (
+        read,
+        SyncSignal {
+            pending,
+            waker: None,
+        },
+    )
+}
+
+/// Write the values queued by every [`SyncSignal`] of the current runtime.
+/// Effects run once, after all writes. Returns how many signals changed.
+pub fn apply_sync_writes() -> usize {
+    let mut applied = 0;
+    batch(|| {
+        with_sync_appliers(|appliers| {
+            appliers.retain_mut(|apply| match apply() {
+                Some(written) => {
+                    applied += usize::from(written);
+                    true
+                }
+                None => false,
+            });
+        });
+    });
+    applied
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::inbox::Inbox;
+    use crate::reactive::signal::reset_runtime;
+    use crate::reactive::{create_effect, runtime_stats};
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn worker_writes_apply_at_next_frame() {
+        reset_runtime();
+        let (progress, publish) = create_sync_signal(0);
+        let runs = Rc::new(Cell::new(0));
+        let counter = runs.clone();
+        create_effect(move || {
+            progress.get();
+            counter.set(counter.get() + 1);
+        });
+
+        thread::spawn(move || {
+            for i in 1..=10 {
+                publish.set(i);
+            }
+        })
+        .join()
+        .unwrap();
+        assert_eq!(progress.get(), 0);
+
+        assert_eq!(apply_sync_writes(), 1);
+        assert_eq!(progress.get(), 10);
+        assert_eq!(runs.get(), 2);
+        // Nothing new queued.
+        assert_eq!(apply_sync_writes(), 0);
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[test]
+    fn writes_wake_the_loop() {
+        reset_runtime();
+        let inbox = Inbox::new();
+        let (_, status) = create_sync_signal(String::new());
+        let status = status.with_waker(inbox.sender());
+        thread::spawn(move || status.set("done".into()));
+        assert!(inbox.wait(Some(Duration::from_secs(5))));
+    }
+
+    #[test]
+    fn dropped_handles_release_the_applier() {
+        reset_runtime();
+        let (value, handle) = create_sync_signal(1);
+        handle.set(2);
+        drop(handle);
+        // The last queued write still lands.
+        assert_eq!(apply_sync_writes(), 1);
+        assert_eq!(value.get(), 2);
+        // The next pass finds nothing queued and no handles left.
+        assert_eq!(apply_sync_writes(), 0);
+        with_sync_appliers(|appliers| assert!(appliers.is_empty()));
+        assert_eq!(runtime_stats().signals, 1);
+    }
+}
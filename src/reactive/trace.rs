@@ -0,0 +1,167 @@
+//! Reactive tracing: why did this effect re-run?
+//!
+//! With tracing on ([`Runtime::set_tracing`]), every effect or memo run is
+//! recorded as an [`EffectRun`]: which signal write scheduled it (if any) and
+//! the full set of signals it read. Query the log to track down effects and
+//! components that re-render more often than expected:
+//!
+//! ```ignore
+//! app.runtime().set_tracing(true);
+//! // ... interact ...
+//! for run in app.runtime().trace() {
+//!     println!("{run}");
+//! }
+//! let why = app.runtime().last_run(host.effect_id());
+//! ```
+//!
+//! The log is bounded; the oldest runs are dropped first.
+//!
+//! [`Runtime::set_tracing`]: super::Runtime::set_tracing
+
+use std::collections::VecDeque;
+use std::fmt;
+
+use super::signal::{EffectId, SignalId};
+
+/// Runs kept before the oldest are dropped.
+pub(super) const MAX_RUNS: usize = 256;
+
+// ---------------------------------------------------------------------------
+// EffectRun
+// ---------------------------------------------------------------------------
+
+/// One recorded run of an effect or memo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EffectRun {
+    /// The effect that ran.
+    pub effect: EffectId,
+    /// The signal whose write scheduled the run; `None` for the first run.
+    /// When several writes are batched, the last one.
+    pub trigger: Option<SignalId>,
+    /// Every signal the run read, in ascending order.
+    pub dependencies: Vec<SignalId>,
+}
+
+impl fmt::Display for EffectRun {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "effect {}", self.effect.index())?;
+        match self.trigger {
+            Some(signal) => write!(f, " re-ran after signal {} was written", signal.index())?,
+            None => f.write_str(" ran for the first time")?,
+        }
+        f.write_str("; reads [")?;
+        for (i, signal) in self.dependencies.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", signal.index())?;
+        }
+        f.write_str("]")
+    }
+}
+
+// ---------------------------------------------------------------------------
+// TraceLog
+// ---------------------------------------------------------------------------
+
+/// Bounded log of effect runs.
+#[derive(Debug, Default)]
+pub(super) struct TraceLog {
+    runs: VecDeque<EffectRun>,
+}
+
+impl TraceLog {
+    /// Record a run, dropping the oldest if the log is full.
+    pub(super) fn push(&mut self, run: EffectRun) {
+        if self.runs.len() == MAX_RUNS {
+            self.runs.pop_front();
+        }
+        self.runs.push_back(run);
+    }
+
+    /// Recorded runs, oldest first.
+    pub(super) fn runs(&self) -> impl Iterator<Item = &EffectRun> {
+        self.runs.iter()
+    }
+
+    /// Take every recorded run, oldest first.
+    pub(super) fn drain(&mut self) -> Vec<EffectRun> {
+        self.runs.drain(..).collect()
+    }
+
+    /// The most recent run of `effect`.
+    pub(super) fn last_run(&self, effect: EffectId) -> Option<&EffectRun> {
+        self.runs.iter().rev().find(|run| run.effect == effect)
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use crate::reactive::signal::reset_runtime;
+    use crate::reactive::{batch, create_effect_with_id, create_memo, create_signal, Runtime};
+
+    #[test]
+    fn records_trigger_and_dependencies() {
+        reset_runtime();
+        let runtime = Runtime::current();
+        runtime.set_tracing(true);
+        let (a, set_a) = create_signal(1);
+        let (b, _) = create_signal(2);
+        let effect = create_effect_with_id(move || {
+            a.get();
+            b.get();
+        });
+        set_a.set(3);
+
+        let runs = runtime.trace();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].trigger, None);
+        assert_eq!(runs[1].effect, effect);
+        assert_eq!(runs[1].trigger, Some(set_a.id()));
+        assert_eq!(runs[1].dependencies, [a.id(), b.id()]);
+        assert_eq!(runtime.last_run(effect), Some(runs[1].clone()));
+    }
+
+    #[test]
+    fn off_by_default_and_drainable() {
+        reset_runtime();
+        let runtime = Runtime::current();
+        let (a, set_a) = create_signal(0);
+        let doubled = create_memo(move || a.get() * 2);
+        set_a.set(1);
+        assert!(runtime.trace().is_empty());
+
+        runtime.set_tracing(true);
+        batch(|| set_a.set(2));
+        assert_eq!(doubled.get_untracked(), 4);
+        let runs = runtime.drain_trace();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].trigger, Some(set_a.id()));
+        assert!(runtime.trace().is_empty());
+    }
+
+    #[test]
+    fn display_explains_the_run() {
+        reset_runtime();
+        let runtime = Runtime::current();
+        runtime.set_tracing(true);
+        let (a, set_a) = create_signal(0);
+        create_effect_with_id(move || {
+            a.get();
+        });
+        set_a.set(1);
+        let runs = runtime.trace();
+        assert_eq!(
+            runs[0].to_string(),
+            "effect 0 ran for the first time; reads [0]"
+        );
+        assert_eq!(
+            runs[1].to_string(),
+            "effect 0 re-ran after signal 0 was written; reads [0]"
+        );
+    }
+}
@@ -0,0 +1,549 @@
+//! Animation clock: time-based style transitions between two computed styles.
+//!
+//! A [`StyleTransition`] interpolates the animatable properties of a node's
+//! [`Styles`] (colors, sizes, margin, padding) from a "before" snapshot to an
+//! "after" snapshot. A [`PropertyAnimation`], built with [`animate`], drives a
+//! single numeric CSS property between two values. The [`Animator`] owns all
+//! running animations and is ticked once per frame with the current time;
+//! finished ones settle on their final values and are dropped.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::css::model::DeclarationValue;
+use crate::css::properties::{apply_declaration, PropertyError};
+use crate::css::scalar::{Scalar, ScalarBox};
+use crate::css::styles::Styles;
+use crate::dom::node::NodeId;
+use crate::render::easing::Easing;
+use crate::render::strip::Color;
+
+// ---------------------------------------------------------------------------
+// Interpolation helpers
+// ---------------------------------------------------------------------------
+
+/// Linear interpolation between two floats.
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+/// Parse a `#rrggbb` color into its components.
+fn parse_hex(s: &str) -> Option<(u8, u8, u8)> {
+    let hex = s.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// Interpolate two RGB colors channel by channel.
+pub(crate) fn lerp_rgb(from: (u8, u8, u8), to: (u8, u8, u8), t: f32) -> Color {
+    let mix = |a: u8, b: u8| lerp(a as f32, b as f32, t).round() as u8;
+    Color::Rgb(mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+}
+
+/// Interpolate two `#rrggbb` colors. Other color forms jump to `to`.
+fn lerp_color(from: &str, to: &str, t: f32) -> String {
+    match (parse_hex(from), parse_hex(to)) {
+        (Some(a), Some(b)) => lerp_rgb(a, b, t).to_string(),
+        _ => to.to_owned(),
+    }
+}
+
+/// Interpolate two scalars of the same unit. Mixed units jump to `to`.
+fn lerp_scalar(from: Scalar, to: Scalar, t: f32) -> Scalar {
+    if from.unit == to.unit {
+        Scalar {
+            value: lerp(from.value, to.value, t),
+            unit: to.unit,
+        }
+    } else {
+        to
+    }
+}
+
+/// Interpolate each side of a scalar box.
+fn lerp_box(from: &ScalarBox, to: &ScalarBox, t: f32) -> ScalarBox {
+    ScalarBox {
+        top: lerp_scalar(from.top, to.top, t),
+        right: lerp_scalar(from.right, to.right, t),
+        bottom: lerp_scalar(from.bottom, to.bottom, t),
+        left: lerp_scalar(from.left, to.left, t),
+    }
+}
+
+/// Interpolate an optional property present on both sides; otherwise take `to`.
+fn lerp_opt<T: Clone>(from: &Option<T>, to: &Option<T>, f: impl Fn(&T, &T) -> T) -> Option<T> {
+    match (from, to) {
+        (Some(a), Some(b)) => Some(f(a, b)),
+        _ => to.clone(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// StyleTransition
+// ---------------------------------------------------------------------------
+
+/// A running transition of one node's styles.
+#[derive(Debug, Clone)]
+pub struct StyleTransition {
+    /// Styles at the start of the transition.
+    pub from: Styles,
+    /// Styles the transition settles on.
+    pub to: Styles,
+    /// When the transition started.
+    pub start: Instant,
+    /// How long the transition runs.
+    pub duration: Duration,
+    /// Curve applied to the linear progress.
+    pub easing: Easing,
+}
+
+impl StyleTransition {
+    /// Create a linear transition from `from` to `to` starting at `start`.
+    pub fn new(from: Styles, to: Styles, start: Instant, duration: Duration) -> Self {
+        Self {
+            from,
+            to,
+            start,
+            duration,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Set the easing curve (builder).
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Linear progress in `0.0..=1.0` at time `now`.
+    pub fn progress(&self, now: Instant) -> f32 {
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+        let elapsed = now.saturating_duration_since(self.start);
+        (elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+    }
+
+    /// Whether the transition has reached its end at time `now`.
+    pub fn is_finished(&self, now: Instant) -> bool {
+        self.progress(now) >= 1.0
+    }
+
+    /// Interpolated styles at time `now`.
+    ///
+    /// Colors (`#rrggbb`), sizes, margin, padding, and gaps are interpolated
+    /// when both ends set them with compatible units. All other properties
+    /// take their final value immediately.
+    pub fn styles_at(&self, now: Instant) -> Styles {
+        if self.is_finished(now) {
+            return self.to.clone();
+        }
+        let t = self.easing.apply(self.progress(now));
+        let (a, b) = (&self.from, &self.to);
+        let scalar = |x: &Scalar, y: &Scalar| lerp_scalar(*x, *y, t);
+        let color = |x: &String, y: &String| lerp_color(x, y, t);
+        let boxed = |x: &ScalarBox, y: &ScalarBox| lerp_box(x, y, t);
+
+        Styles {
+            width: lerp_opt(&a.width, &b.width, scalar),
+            height: lerp_opt(&a.height, &b.height, scalar),
+            min_width: lerp_opt(&a.min_width, &b.min_width, scalar),
+            min_height: lerp_opt(&a.min_height, &b.min_height, scalar),
+            max_width: lerp_opt(&a.max_width, &b.max_width, scalar),
+            max_height: lerp_opt(&a.max_height, &b.max_height, scalar),
+            margin: lerp_opt(&a.margin, &b.margin, boxed),
+            padding: lerp_opt(&a.padding, &b.padding, boxed),
+            row_gap: lerp_opt(&a.row_gap, &b.row_gap, scalar),
+            column_gap: lerp_opt(&a.column_gap, &b.column_gap, scalar),
+            color: lerp_opt(&a.color, &b.color, color),
+            background: lerp_opt(&a.background, &b.background, color),
+            ..b.clone()
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// PropertyAnimation
+// ---------------------------------------------------------------------------
+
+/// Errors from building a [`PropertyAnimation`].
+#[derive(Debug, thiserror::Error)]
+pub enum AnimationError {
+    /// No property was named with [`AnimationBuilder::style`].
+    #[error("animation has no style property")]
+    MissingProperty,
+    /// The property is unknown or does not accept a number.
+    #[error(transparent)]
+    Property(#[from] PropertyError),
+}
+
+/// Write `value` into `styles` as the numeric CSS property `property`.
+fn set_numeric(styles: &mut Styles, property: &str, value: f32) -> Result<(), PropertyError> {
+    apply_declaration(styles, property, &[DeclarationValue::Number(value)])
+}
+
+/// An imperative animation of one numeric CSS property of one node.
+#[derive(Debug, Clone)]
+pub struct PropertyAnimation {
+    /// The animated node.
+    pub node: NodeId,
+    /// CSS property name, e.g. `"width"` or `"padding"`.
+    pub property: String,
+    /// Value at the start.
+    pub from: f32,
+    /// Value at the end.
+    pub to: f32,
+    /// When the animation started.
+    pub start: Instant,
+    /// How long it runs.
+    pub duration: Duration,
+    /// Curve applied to the linear progress.
+    pub easing: Easing,
+}
+
+impl PropertyAnimation {
+    /// Linear progress in `0.0..=1.0` at time `now`.
+    pub fn progress(&self, now: Instant) -> f32 {
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+        let elapsed = now.saturating_duration_since(self.start);
+        (elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+    }
+
+    /// Whether the animation has reached its end at time `now`.
+    pub fn is_finished(&self, now: Instant) -> bool {
+        self.progress(now) >= 1.0
+    }
+
+    /// The eased value at time `now`.
+    pub fn value_at(&self, now: Instant) -> f32 {
+        lerp(self.from, self.to, self.easing.apply(self.progress(now)))
+    }
+
+    /// Write the value at `now` into `styles`.
+    pub fn apply(&self, styles: &mut Styles, now: Instant) {
+        // The property was validated when the animation was built.
+        let _ = set_numeric(styles, &self.property, self.value_at(now));
+    }
+}
+
+/// Start building an animation of `node`.
+///
+/// # Examples
+///
+/// ```ignore
+/// let slide = animate(sidebar)
+///     .style("width")
+///     .from(0.0)
+///     .to(30.0)
+///     .duration(Duration::from_millis(200))
+///     .easing(Easing::EaseOut);
+/// screen.run_animation(slide)?;
+/// ```
+pub fn animate(node: NodeId) -> AnimationBuilder {
+    AnimationBuilder {
+        node,
+        property: None,
+        from: 0.0,
+        to: 0.0,
+        duration: Duration::from_millis(200),
+        easing: Easing::Linear,
+    }
+}
+
+/// Builder returned by [`animate`].
+#[derive(Debug, Clone)]
+pub struct AnimationBuilder {
+    node: NodeId,
+    property: Option<String>,
+    from: f32,
+    to: f32,
+    duration: Duration,
+    easing: Easing,
+}
+
+impl AnimationBuilder {
+    /// The numeric CSS property to animate.
+    pub fn style(mut self, property: impl Into<String>) -> Self {
+        self.property = Some(property.into());
+        self
+    }
+
+    /// The starting value (default 0).
+    pub fn from(mut self, value: f32) -> Self {
+        self.from = value;
+        self
+    }
+
+    /// The final value (default 0).
+    pub fn to(mut self, value: f32) -> Self {
+        self.to = value;
+        self
+    }
+
+    /// How long the animation runs (default 200ms).
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// The easing curve (default linear).
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Finish the animation, starting at `start`.
+    ///
+    /// Fails if no property was named or it does not take a number.
+    pub fn build(self, start: Instant) -> Result<PropertyAnimation, AnimationError> {
+        let property = self.property.ok_or(AnimationError::MissingProperty)?;
+        set_numeric(&mut Styles::new(), &property, self.from)?;
+        Ok(PropertyAnimation {
+            node: self.node,
+            property,
+            from: self.from,
+            to: self.to,
+            start,
+            duration: self.duration,
+            easing: self.easing,
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Animator
+// ---------------------------------------------------------------------------
+
+/// Owner of all running style transitions (keyed by node) and property
+/// animations (keyed by node and property).
+#[derive(Debug, Default)]
+pub struct Animator {
+    transitions: HashMap<NodeId, StyleTransition>,
+    properties: Vec<PropertyAnimation>,
+}
+
+impl Animator {
+    /// Create an animator with no running transitions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start (or replace) the transition for `node`.
+    pub fn start(&mut self, node: NodeId, transition: StyleTransition) {
+        self.transitions.insert(node, transition);
+    }
+
+    /// Start a property animation, replacing any running one for the same
+    /// node and property.
+    pub fn run(&mut self, animation: PropertyAnimation) {
+        self.properties
+            .retain(|a| a.node != animation.node || a.property != animation.property);
+        self.properties.push(animation);
+    }
+
+    /// Stop everything running for `node`, returning its style transition if
+    /// one was running.
+    pub fn cancel(&mut self, node: NodeId) -> Option<StyleTransition> {
+        self.properties.retain(|a| a.node != node);
+        self.transitions.remove(&node)
+    }
+
+    /// Whether `node` has a running transition or property animation.
+    pub fn is_animating(&self, node: NodeId) -> bool {
+        self.transitions.contains_key(&node) || self.properties.iter().any(|a| a.node == node)
+    }
+
+    /// Whether nothing is running.
+    pub fn is_empty(&self) -> bool {
+        self.transitions.is_empty() && self.properties.is_empty()
+    }
+
+    /// The interpolated styles of `node` at `now`, if it is animating.
+    pub fn styles_at(&self, node: NodeId, now: Instant) -> Option<Styles> {
+        self.transitions.get(&node).map(|t| t.styles_at(now))
+    }
+
+    /// Advance all transitions to `now`.
+    ///
+    /// Returns the current styles of every animating node. Transitions that
+    /// finished are reported once with their final styles and then dropped.
+    pub fn tick(&mut self, now: Instant) -> Vec<(NodeId, Styles)> {
+        let frame = self
+            .transitions
+            .iter()
+            .map(|(&id, t)| (id, t.styles_at(now)))
+            .collect();
+        self.transitions.retain(|_, t| !t.is_finished(now));
+        frame
+    }
+
+    /// Advance all property animations to `now`.
+    ///
+    /// Returns the running animations' states; finished animations are
+    /// returned once (settled on their final value) and then dropped.
+    pub fn tick_properties(&mut self, now: Instant) -> Vec<PropertyAnimation> {
+        let frame = self.properties.clone();
+        self.properties.retain(|a| !a.is_finished(now));
+        frame
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dom::node::NodeData;
+    use crate::dom::tree::Dom;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    fn styles(width: f32, background: &str) -> Styles {
+        Styles {
+            width: Some(Scalar::cells(width)),
+            background: Some(background.into()),
+            ..Styles::new()
+        }
+    }
+
+    #[test]
+    fn color_interpolation() {
+        assert_eq!(lerp_color("#000000", "#ff8000", 0.5), "#804000");
+        assert_eq!(lerp_color("red", "#ffffff", 0.5), "#ffffff");
+    }
+
+    #[test]
+    fn styles_midway() {
+        let start = Instant::now();
+        let t = StyleTransition::new(
+            styles(10.0, "#000000"),
+            styles(20.0, "#ffffff"),
+            start,
+            ms(100),
+        );
+        let mid = t.styles_at(start + ms(50));
+        assert_eq!(mid.width, Some(Scalar::cells(15.0)));
+        assert_eq!(mid.background.as_deref(), Some("#808080"));
+        assert!(!t.is_finished(start + ms(50)));
+    }
+
+    #[test]
+    fn mixed_units_jump_to_target() {
+        let start = Instant::now();
+        let from = Styles {
+            width: Some(Scalar::cells(10.0)),
+            ..Styles::new()
+        };
+        let to = Styles {
+            width: Some(Scalar::percent(50.0)),
+            ..Styles::new()
+        };
+        let t = StyleTransition::new(from, to, start, ms(100));
+        assert_eq!(
+            t.styles_at(start + ms(10)).width,
+            Some(Scalar::percent(50.0))
+        );
+    }
+
+    #[test]
+    fn zero_duration_is_finished() {
+        let start = Instant::now();
+        let t = StyleTransition::new(
+            styles(1.0, "#000000"),
+            styles(2.0, "#000000"),
+            start,
+            Duration::ZERO,
+        );
+        assert!(t.is_finished(start));
+        assert_eq!(t.styles_at(start).width, Some(Scalar::cells(2.0)));
+    }
+
+    #[test]
+    fn animator_settles_and_drops() {
+        let mut dom = Dom::new();
+        let node = dom.insert(NodeData::new("Static"));
+        let start = Instant::now();
+        let mut animator = Animator::new();
+        animator.start(
+            node,
+            StyleTransition::new(
+                styles(0.0, "#000000"),
+                styles(10.0, "#000000"),
+                start,
+                ms(100),
+            ),
+        );
+
+        let frame = animator.tick(start + ms(40));
+        assert_eq!(frame.len(), 1);
+        assert!(animator.is_animating(node));
+
+        let frame = animator.tick(start + ms(200));
+        assert_eq!(frame[0].1.width, Some(Scalar::cells(10.0)));
+        assert!(animator.is_empty());
+        assert!(animator.tick(start + ms(300)).is_empty());
+    }
+
+    #[test]
+    fn eased_transition() {
+        let start = Instant::now();
+        let t = StyleTransition::new(
+            styles(0.0, "#000000"),
+            styles(100.0, "#000000"),
+            start,
+            ms(100),
+        )
+        .with_easing(Easing::EaseIn);
+        let width = t.styles_at(start + ms(30)).width.unwrap().value;
+        assert!(width < 30.0);
+    }
+
+    #[test]
+    fn builder_runs_property_animation() {
+        let mut dom = Dom::new();
+        let node = dom.insert(NodeData::new("Static"));
+        let start = Instant::now();
+        let animation = animate(node)
+            .style("width")
+            .from(0.0)
+            .to(10.0)
+            .duration(ms(100))
+            .easing(Easing::EaseOut)
+            .build(start)
+            .unwrap();
+        assert!(animation.value_at(start + ms(50)) > 5.0);
+
+        let mut animator = Animator::new();
+        animator.run(animation);
+        assert!(animator.is_animating(node));
+        let frame = animator.tick_properties(start + ms(100));
+        let mut styles = Styles::new();
+        frame[0].apply(&mut styles, start + ms(100));
+        assert_eq!(styles.width, Some(Scalar::cells(10.0)));
+        assert!(animator.is_empty());
+    }
+
+    #[test]
+    fn builder_rejects_bad_properties() {
+        let mut dom = Dom::new();
+        let node = dom.insert(NodeData::new("Static"));
+        let now = Instant::now();
+        assert!(matches!(
+            animate(node).to(1.0).build(now),
+            Err(AnimationError::MissingProperty)
+        ));
+        assert!(matches!(
+            animate(node).style("offset-x").build(now),
+            Err(AnimationError::Property(_))
+        ));
+    }
+}
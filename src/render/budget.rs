@@ -0,0 +1,259 @@
+//! Frame budgets: warnings for event handlers and renders that run too long.
+//!
+//! A [`BudgetMonitor`] times the work it is handed, per widget, against a
+//! [`FrameBudget`]. Anything over budget becomes a [`SlowWarning`] carrying
+//! the widget's node id, what it was doing, and how long it took, queued on
+//! the monitor's diagnostics channel for the app to log or display. The queue
+//! is bounded; the oldest warnings are dropped first.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::dom::node::NodeId;
+
+/// Warnings kept before the oldest are dropped.
+const MAX_WARNINGS: usize = 64;
+
+// ---------------------------------------------------------------------------
+// FrameBudget
+// ---------------------------------------------------------------------------
+
+/// How long a single handler or render may take before it is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameBudget {
+    /// Budget for one event handler call.
+    pub handler: Duration,
+    /// Budget for one widget render.
+    pub render: Duration,
+}
+
+impl Default for FrameBudget {
+    /// A quarter of a 60 FPS frame for handlers, an eighth for renders.
+    fn default() -> Self {
+        Self {
+            handler: Duration::from_micros(4_000),
+            render: Duration::from_micros(2_000),
+        }
+    }
+}
+
+impl FrameBudget {
+    /// Create the default budget.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the handler budget (builder).
+    pub fn with_handler(mut self, budget: Duration) -> Self {
+        self.handler = budget;
+        self
+    }
+
+    /// Set the render budget (builder).
+    pub fn with_render(mut self, budget: Duration) -> Self {
+        self.render = budget;
+        self
+    }
+
+    /// The budget for `kind`.
+    pub fn for_kind(&self, kind: WorkKind) -> Duration {
+        match kind {
+            WorkKind::Handler => self.handler,
+            WorkKind::Render => self.render,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// SlowWarning
+// ---------------------------------------------------------------------------
+
+/// What a timed piece of work was.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WorkKind {
+    /// An event handler.
+    Handler,
+    /// A widget render.
+    Render,
+}
+
+impl fmt::Display for WorkKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            WorkKind::Handler => "handler",
+            WorkKind::Render => "render",
+        })
+    }
+}
+
+/// A handler or render that exceeded its budget.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlowWarning {
+    /// The widget the work was for.
+    pub node: NodeId,
+    /// What the work was.
+    pub kind: WorkKind,
+    /// How long it took.
+    pub duration: Duration,
+    /// The budget it exceeded.
+    pub budget: Duration,
+}
+
+impl fmt::Display for SlowWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "slow {} for {:?}: {:.1}ms (budget {:.1}ms)",
+            self.kind,
+            self.node,
+            self.duration.as_secs_f64() * 1000.0,
+            self.budget.as_secs_f64() * 1000.0,
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// BudgetMonitor
+// ---------------------------------------------------------------------------
+
+/// Times handlers and renders and queues warnings for slow ones.
+#[derive(Debug, Clone, Default)]
+pub struct BudgetMonitor {
+    budget: FrameBudget,
+    warnings: VecDeque<SlowWarning>,
+    /// Warnings dropped because the queue was full.
+    dropped: u64,
+}
+
+impl BudgetMonitor {
+    /// Create a monitor for `budget`.
+    pub fn new(budget: FrameBudget) -> Self {
+        Self {
+            budget,
+            ..Self::default()
+        }
+    }
+
+    /// The budget being enforced.
+    pub fn budget(&self) -> FrameBudget {
+        self.budget
+    }
+
+    /// Replace the budget.
+    pub fn set_budget(&mut self, budget: FrameBudget) {
+        self.budget = budget;
+    }
+
+    /// Run an event handler for `node`, warning if it runs over budget.
+    pub fn time_handler<R>(&mut self, node: NodeId, f: impl FnOnce() -> R) -> R {
+        self.time(node, WorkKind::Handler, f)
+    }
+
+    /// Run a render for `node`, warning if it runs over budget.
+    pub fn time_render<R>(&mut self, node: NodeId, f: impl FnOnce() -> R) -> R {
+        self.time(node, WorkKind::Render, f)
+    }
+
+    /// Run `f` as work of `kind` for `node`, warning if it runs over budget.
+    pub fn time<R>(&mut self, node: NodeId, kind: WorkKind, f: impl FnOnce() -> R) -> R {
+        let start = Instant::now();
+        let result = f();
+        self.record(node, kind, start.elapsed());
+        result
+    }
+
+    /// Report work timed elsewhere. Returns whether it was over budget.
+    pub fn record(&mut self, node: NodeId, kind: WorkKind, duration: Duration) -> bool {
+        let budget = self.budget.for_kind(kind);
+        if duration <= budget {
+            return false;
+        }
+        if self.warnings.len() == MAX_WARNINGS {
+            self.warnings.pop_front();
+            self.dropped += 1;
+        }
+        self.warnings.push_back(SlowWarning {
+            node,
+            kind,
+            duration,
+            budget,
+        });
+        true
+    }
+
+    /// Pending warnings, oldest first.
+    pub fn warnings(&self) -> impl Iterator<Item = &SlowWarning> {
+        self.warnings.iter()
+    }
+
+    /// Take every pending warning, oldest first.
+    pub fn drain_warnings(&mut self) -> Vec<SlowWarning> {
+        self.warnings.drain(..).collect()
+    }
+
+    /// Warnings dropped because too many were pending.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dom::node::NodeData;
+    use crate::dom::tree::Dom;
+
+    fn node() -> NodeId {
+        Dom::new().insert(NodeData::new("Chart"))
+    }
+
+    #[test]
+    fn over_budget_work_is_reported() {
+        let id = node();
+        let mut monitor = BudgetMonitor::new(FrameBudget::new().with_handler(Duration::ZERO));
+        let value = monitor.time_handler(id, || {
+            std::thread::sleep(Duration::from_millis(1));
+            7
+        });
+        assert_eq!(value, 7);
+        monitor.time_render(id, || ());
+
+        let warnings = monitor.drain_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].node, id);
+        assert_eq!(warnings[0].kind, WorkKind::Handler);
+        assert!(warnings[0].duration >= Duration::from_millis(1));
+        assert_eq!(monitor.warnings().count(), 0);
+    }
+
+    #[test]
+    fn queue_is_bounded() {
+        let id = node();
+        let mut monitor = BudgetMonitor::new(FrameBudget::new());
+        for ms in 0..MAX_WARNINGS as u64 + 3 {
+            monitor.record(id, WorkKind::Render, Duration::from_millis(10 + ms));
+        }
+        assert_eq!(monitor.warnings().count(), MAX_WARNINGS);
+        assert_eq!(monitor.dropped(), 3);
+        let oldest = monitor.warnings().next().unwrap();
+        assert_eq!(oldest.duration, Duration::from_millis(13));
+    }
+
+    #[test]
+    fn warning_display() {
+        let warning = SlowWarning {
+            node: node(),
+            kind: WorkKind::Render,
+            duration: Duration::from_micros(12_500),
+            budget: Duration::from_millis(2),
+        };
+        let text = warning.to_string();
+        assert!(text.starts_with("slow render for NodeId("));
+        assert!(text.ends_with(": 12.5ms (budget 2.0ms)"));
+    }
+}
@@ -4,9 +4,33 @@
 //! terminal screen. Widgets render into `Strip`s, which are placed into the screen
 //! buffer via `place_strips`. The `diff` method compares two frames and produces
 //! only the `CellUpdate`s needed to transition between them.
+//!
+//! Layout regions and strips use signed `i32` coordinates, so a widget
+//! scrolled partly off the top or left of the screen simply has negative
+//! positions. The screen itself, like the terminal, is addressed with `u16`.
+//! Every conversion between the two goes through [`screen_coord`] or
+//! [`Compositor::clip_strip`], which never wrap or panic on overflow.
+
+use std::ops::Range;
 
-use crate::geometry::Region;
-use super::strip::{Strip, StyledCell, CellStyle};
+use crate::geometry::{Offset, Region};
+use super::effects::{resolve_cell, EffectPhase};
+use super::glyph::GlyphPolicy;
+use super::strip::{Attrs, Strip, StyledCell, CellStyle};
+use super::transition::Snapshot;
+use crate::error::RenderError;
+
+// ---------------------------------------------------------------------------
+// Coordinates
+// ---------------------------------------------------------------------------
+
+/// Convert a signed layout coordinate to a screen coordinate.
+///
+/// Returns `None` for negative values and values beyond `u16::MAX`; whether
+/// the result lies on screen is still up to the caller.
+pub fn screen_coord(value: i32) -> Option<u16> {
+    u16::try_from(value).ok()
+}
 
 // ---------------------------------------------------------------------------
 // CellUpdate
@@ -22,6 +46,74 @@ pub struct CellUpdate {
     pub cell: StyledCell,
 }
 
+// ---------------------------------------------------------------------------
+// ScrollHint
+// ---------------------------------------------------------------------------
+
+/// A block of whole rows that moved vertically between frames.
+///
+/// The driver turns this into a terminal scroll-region shift (CSR + SU/SD),
+/// which moves existing rows without repainting them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScrollHint {
+    /// First row of the scroll region (inclusive).
+    pub top: u16,
+    /// Last row of the scroll region (exclusive).
+    pub bottom: u16,
+    /// Rows the content moved: positive scrolls up (content moves toward the
+    /// top, like `ScrollState` offset increasing), negative scrolls down.
+    pub delta: i32,
+}
+
+impl ScrollHint {
+    /// Build a hint from a scroll container's offset change.
+    ///
+    /// `region` is the container's viewport on screen; `old` and `new` are its
+    /// `ScrollState` offsets. The region is cut off at `screen_height`, since
+    /// terminals ignore a scroll region reaching past the last row. Returns
+    /// `None` if the offset did not change vertically, changed horizontally
+    /// too (rows can't be reused), moved by at least the visible viewport, or
+    /// if the viewport starts above or below the screen.
+    pub fn from_scroll(
+        region: Region,
+        old: Offset,
+        new: Offset,
+        screen_height: u16,
+    ) -> Option<Self> {
+        let delta = new.y.checked_sub(old.y)?;
+        if delta == 0 || old.x != new.x {
+            return None;
+        }
+        Self {
+            top: screen_coord(region.y)?,
+            bottom: screen_coord(region.bottom()).unwrap_or(u16::MAX),
+            delta,
+        }
+        .clamp_to(screen_height)
+    }
+
+    /// This hint with its region cut off at `screen_height`, or `None` if no
+    /// rows of the region are left or the shift covers all of them.
+    pub fn clamp_to(self, screen_height: u16) -> Option<Self> {
+        let hint = Self {
+            bottom: self.bottom.min(screen_height),
+            ..self
+        };
+        if hint.delta == 0 || i64::from(hint.delta).abs() >= i64::from(hint.height()) {
+            return None;
+        }
+        Some(hint)
+    }
+
+    /// Number of rows inside the scroll region.
+    pub fn height(&self) -> u16 {
+        self.bottom.saturating_sub(self.top)
+    }
+}
+
+/// Minimum number of reused rows before a scroll shift is worth emitting.
+const MIN_SCROLL_RUN: usize = 3;
+
 // ---------------------------------------------------------------------------
 // Compositor
 // ---------------------------------------------------------------------------
@@ -36,35 +128,51 @@ pub struct CellUpdate {
 /// 5. Changed cells are sent to the `Driver` for terminal output.
 #[derive(Debug, Clone)]
 pub struct Compositor {
-    /// The 2D screen buffer. `screen[y][x]` is the cell at column x, row y.
-    screen: Vec<Vec<StyledCell>>,
+    /// The screen buffer in row-major order: `screen[y * width + x]` is the
+    /// cell at column x, row y.
+    screen: Vec<StyledCell>,
     /// Terminal width.
     pub width: u16,
     /// Terminal height.
     pub height: u16,
     /// Regions that need redrawing.
     dirty_regions: Vec<Region>,
+    /// How characters that may not take one column are drawn.
+    glyphs: GlyphPolicy,
 }
 
 impl Compositor {
     /// Create a new compositor with a blank screen of the given dimensions.
     pub fn new(width: u16, height: u16) -> Self {
-        let screen = Self::blank_screen(width, height);
         Self {
-            screen,
+            screen: vec![StyledCell::blank(); width as usize * height as usize],
             width,
             height,
             dirty_regions: Vec::new(),
+            glyphs: GlyphPolicy::default(),
         }
     }
 
+    /// Set how strips placed from now on draw wide, ambiguous, and
+    /// zero-width characters (see [`glyph`](crate::render::glyph)).
+    pub fn set_glyph_policy(&mut self, policy: GlyphPolicy) {
+        self.glyphs = policy;
+    }
+
+    /// The glyph policy applied to placed strips.
+    pub fn glyph_policy(&self) -> &GlyphPolicy {
+        &self.glyphs
+    }
+
     /// Resize the screen buffer. All cells are reset to blank.
     ///
     /// After resize, the entire screen is marked dirty.
     pub fn resize(&mut self, width: u16, height: u16) {
         self.width = width;
         self.height = height;
-        self.screen = Self::blank_screen(width, height);
+        self.screen.clear();
+        self.screen
+            .resize(width as usize * height as usize, StyledCell::blank());
         self.mark_all_dirty();
     }
 
@@ -76,12 +184,12 @@ impl Compositor {
     /// Mark the entire screen as dirty.
     pub fn mark_all_dirty(&mut self) {
         self.dirty_regions.clear();
-        self.dirty_regions.push(Region::new(
-            0,
-            0,
-            self.width as i32,
-            self.height as i32,
-        ));
+        self.dirty_regions.push(self.screen_region());
+    }
+
+    /// The whole screen as a region.
+    pub fn screen_region(&self) -> Region {
+        Region::new(0, 0, i32::from(self.width), i32::from(self.height))
     }
 
     /// Whether any regions need redrawing.
@@ -98,38 +206,51 @@ impl Compositor {
     ///
     /// Each strip's cells are written into the screen at their (x_offset + i, y) position,
     /// but only if that position falls within both the clip `region` and the screen bounds.
+    /// Characters are drawn as the glyph policy says.
     pub fn place_strips(&mut self, strips: &[Strip], region: &Region) {
-        let screen_region = Region::new(0, 0, self.width as i32, self.height as i32);
-        let clip = region.intersection(screen_region);
-
-        if clip.width <= 0 || clip.height <= 0 {
-            return;
-        }
-
         for strip in strips {
-            let y = strip.y;
-            if y < clip.y || y >= clip.bottom() {
+            let Some((x, y, cells)) = self.clip_strip(strip, region) else {
                 continue;
-            }
-
-            let row = y as usize;
-            if row >= self.screen.len() {
+            };
+            let Some(at) = self.index(x, y) else {
                 continue;
-            }
-
-            for (i, cell) in strip.cells.iter().enumerate() {
-                let x = strip.x_offset + i as i32;
-                if x < clip.x || x >= clip.right() {
-                    continue;
-                }
-                let col = x as usize;
-                if col < self.screen[row].len() {
-                    self.screen[row][col] = cell.clone();
+            };
+            let target = &mut self.screen[at..at + cells.len()];
+            target.copy_from_slice(&strip.cells[cells]);
+            if !self.glyphs.is_passthrough() {
+                for cell in target {
+                    cell.ch = self.glyphs.apply(cell.ch);
                 }
             }
         }
     }
 
+    /// The part of `strip` that lands on screen inside `region`.
+    ///
+    /// Returns the screen position of the first visible cell and the range of
+    /// the strip's cells that are visible, or `None` if none are. Cells left
+    /// of the screen or of `region` (a negative `x_offset`, say) are skipped,
+    /// as are strips above or below them.
+    pub fn clip_strip(
+        &self,
+        strip: &Strip,
+        region: &Region,
+    ) -> Option<(u16, u16, Range<usize>)> {
+        let clip = region.intersection(self.screen_region());
+        if clip.width <= 0 || strip.y < clip.y || strip.y >= clip.bottom() {
+            return None;
+        }
+        // Widen to i64 so far-off offsets cannot overflow.
+        let offset = i64::from(strip.x_offset);
+        let start = (i64::from(clip.x) - offset).max(0);
+        let end = (i64::from(clip.right()) - offset).min(strip.cells.len() as i64);
+        if start >= end {
+            return None;
+        }
+        let x = screen_coord(i32::try_from(offset + start).ok()?)?;
+        Some((x, screen_coord(strip.y)?, start as usize..end as usize))
+    }
+
     /// Compare this frame against a previous frame and return only the changed cells.
     ///
     /// This is the core of the differential rendering optimization: instead of
@@ -137,32 +258,31 @@ impl Compositor {
     /// to the terminal.
     pub fn diff(&self, previous: &Compositor) -> Vec<CellUpdate> {
         let mut updates = Vec::new();
-        let h = self.height.min(previous.height) as usize;
-        let w = self.width.min(previous.width) as usize;
-
-        for y in 0..h {
-            for x in 0..w {
-                if self.screen[y][x] != previous.screen[y][x] {
-                    updates.push(CellUpdate {
-                        x: x as u16,
-                        y: y as u16,
-                        cell: self.screen[y][x].clone(),
-                    });
+        let changed = |updates: &mut Vec<CellUpdate>, x: usize, y: usize, cell: StyledCell| {
+            updates.push(CellUpdate {
+                x: x as u16,
+                y: y as u16,
+                cell,
+            });
+        };
+
+        if self.width == previous.width && self.height == previous.height {
+            // Same shape: one linear pass over both buffers.
+            let w = (self.width as usize).max(1);
+            for (i, (cur, prev)) in self.screen.iter().zip(&previous.screen).enumerate() {
+                if cur != prev {
+                    changed(&mut updates, i % w, i / w, *cur);
                 }
             }
+            return updates;
         }
 
-        // If the new frame is larger, all new cells are updates.
-        if self.height > previous.height || self.width > previous.width {
-            for y in 0..self.height as usize {
-                for x in 0..self.width as usize {
-                    if y >= previous.height as usize || x >= previous.width as usize {
-                        updates.push(CellUpdate {
-                            x: x as u16,
-                            y: y as u16,
-                            cell: self.screen[y][x].clone(),
-                        });
-                    }
+        for (y, row) in self.rows().enumerate() {
+            let prev_row = previous.row(y as u16).unwrap_or(&[]);
+            for (x, cell) in row.iter().enumerate() {
+                // Cells outside the previous frame are always updates.
+                if prev_row.get(x) != Some(cell) {
+                    changed(&mut updates, x, y, *cell);
                 }
             }
         }
@@ -170,6 +290,105 @@ impl Compositor {
         updates
     }
 
+    /// Detect a vertical block shift between `previous` and this frame.
+    ///
+    /// Looks for the longest run of full rows that reappear `delta` rows
+    /// higher or lower (for `1 <= |delta| <= max_shift`). Returns a hint only
+    /// when enough rows would be reused to beat a plain repaint.
+    pub fn detect_scroll(&self, previous: &Compositor, max_shift: u16) -> Option<ScrollHint> {
+        if self.width != previous.width || self.height != previous.height {
+            return None;
+        }
+        let h = self.height as i32;
+        let mut best: Option<(usize, ScrollHint)> = None;
+
+        for shift in 1..=(max_shift as i32).min(h - 1) {
+            for delta in [shift, -shift] {
+                // Find runs of rows y where current[y] == previous[y + delta].
+                let mut run_start: Option<i32> = None;
+                for y in 0..=h {
+                    let src = y + delta;
+                    let matches = y < h
+                        && (0..h).contains(&src)
+                        && self.row(y as u16) == previous.row(src as u16)
+                        && self.row(y as u16) != previous.row(y as u16);
+                    match (matches, run_start) {
+                        (true, None) => run_start = Some(y),
+                        (false, Some(start)) => {
+                            let len = (y - start) as usize;
+                            let (top, bottom) = if delta > 0 {
+                                (start, y + delta)
+                            } else {
+                                (start + delta, y)
+                            };
+                            let hint = ScrollHint {
+                                top: top as u16,
+                                bottom: bottom as u16,
+                                delta,
+                            };
+                            if len >= MIN_SCROLL_RUN && best.is_none_or(|(n, _)| len > n) {
+                                best = Some((len, hint));
+                            }
+                            run_start = None;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        best.map(|(_, hint)| hint)
+    }
+
+    /// Shift the rows of a scroll region by `hint.delta`, blanking vacated rows.
+    ///
+    /// This mirrors what the terminal does for a CSR + SU/SD sequence, so the
+    /// shifted buffer can be diffed to find the cells still left to paint.
+    pub fn scroll_rows(&mut self, hint: &ScrollHint) {
+        let w = self.width as usize;
+        let top = hint.top as usize;
+        let bottom = (hint.bottom as usize).min(self.height as usize);
+        if top >= bottom || hint.delta == 0 {
+            return;
+        }
+        let n = (hint.delta.unsigned_abs() as usize).min(bottom - top) * w;
+        let cells = &mut self.screen[top * w..bottom * w];
+        let len = cells.len();
+        if hint.delta > 0 {
+            cells.rotate_left(n);
+            cells[len - n..].fill(StyledCell::blank());
+        } else {
+            cells.rotate_right(n);
+            cells[..n].fill(StyledCell::blank());
+        }
+    }
+
+    /// Diff against `previous`, using a scroll shift where one pays off.
+    ///
+    /// `hint` (e.g. from [`ScrollHint::from_scroll`]) is tried first; otherwise
+    /// the shift is detected from the frame contents. Returns the shift to emit
+    /// before the cell updates, which are computed against the shifted frame.
+    pub fn diff_with_scroll(
+        &self,
+        previous: &Compositor,
+        hint: Option<ScrollHint>,
+    ) -> (Option<ScrollHint>, Vec<CellUpdate>) {
+        let plain = self.diff(previous);
+        let hint = hint.and_then(|hint| hint.clamp_to(self.height));
+        let Some(hint) = hint.or_else(|| self.detect_scroll(previous, self.height / 2)) else {
+            return (None, plain);
+        };
+
+        let mut shifted = previous.clone();
+        shifted.scroll_rows(&hint);
+        let updates = self.diff(&shifted);
+        if updates.len() < plain.len() {
+            (Some(hint), updates)
+        } else {
+            (None, plain)
+        }
+    }
+
     /// Clear dirty regions after a render cycle.
     pub fn clear_dirty(&mut self) {
         self.dirty_regions.clear();
@@ -179,29 +398,92 @@ impl Compositor {
     ///
     /// Returns `None` if coordinates are out of bounds.
     pub fn get_cell(&self, x: u16, y: u16) -> Option<&StyledCell> {
-        self.screen
-            .get(y as usize)
-            .and_then(|row| row.get(x as usize))
+        self.index(x, y).map(|i| &self.screen[i])
     }
 
-    /// Fill the entire screen with a given style (useful for background).
-    pub fn fill(&mut self, style: CellStyle) {
-        for row in &mut self.screen {
-            for cell in row.iter_mut() {
-                *cell = StyledCell::blank_styled(style.clone());
+    /// Get a mutable reference to the screen buffer cell at (x, y).
+    ///
+    /// Returns `None` if coordinates are out of bounds.
+    pub fn get_cell_mut(&mut self, x: u16, y: u16) -> Option<&mut StyledCell> {
+        self.index(x, y).map(|i| &mut self.screen[i])
+    }
+
+    /// The cell at signed position (x, y), or `None` if it is off screen.
+    pub fn cell_at(&self, x: i32, y: i32) -> Option<&StyledCell> {
+        self.get_cell(screen_coord(x)?, screen_coord(y)?)
+    }
+
+    /// Overwrite the cell at (x, y).
+    pub fn set_cell(&mut self, x: u16, y: u16, cell: StyledCell) -> Result<(), RenderError> {
+        let (width, height) = (self.width, self.height);
+        let slot = self.get_cell_mut(x, y).ok_or(RenderError::OutOfBounds {
+            x,
+            y,
+            width,
+            height,
+        })?;
+        *slot = cell;
+        Ok(())
+    }
+
+    /// The cells of row `y`, or `None` if it is off screen.
+    pub fn row(&self, y: u16) -> Option<&[StyledCell]> {
+        let w = self.width as usize;
+        let start = y as usize * w;
+        (y < self.height).then(|| &self.screen[start..start + w])
+    }
+
+    /// Iterate over the screen's rows from top to bottom.
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[StyledCell]> {
+        let w = self.width as usize;
+        (0..self.height as usize).map(move |y| &self.screen[y * w..(y + 1) * w])
+    }
+
+    /// The whole screen buffer in row-major order.
+    pub fn cells(&self) -> &[StyledCell] {
+        &self.screen
+    }
+
+    /// Copy the cells inside `region`, e.g. to transition away from them.
+    pub fn snapshot(&self, region: Region) -> Snapshot {
+        Snapshot::capture(self, region)
+    }
+
+    /// Dim every cell outside `keep`, e.g. the layers beneath a modal
+    /// dialog drawn at `keep`. Dimming twice changes nothing, so it can be
+    /// reapplied every frame.
+    pub fn dim_outside(&mut self, keep: Region) {
+        let width = i32::from(self.width);
+        for (i, cell) in self.screen.iter_mut().enumerate() {
+            let (x, y) = (i as i32 % width, i as i32 / width);
+            if !keep.contains(x, y) {
+                cell.style.attrs |= Attrs::DIM;
             }
         }
     }
 
-    /// Create a blank screen buffer.
-    fn blank_screen(width: u16, height: u16) -> Vec<Vec<StyledCell>> {
-        (0..height as usize)
-            .map(|_| {
-                (0..width as usize)
-                    .map(|_| StyledCell::blank())
-                    .collect()
-            })
-            .collect()
+    /// Resolve blink and pulse cells for `phase` (see
+    /// [`effects`](crate::render::effects)), so the effect attributes never
+    /// reach the terminal. Cells are only resolved once; mark the regions of
+    /// nodes with effects dirty whenever the phase changes so they are
+    /// re-rendered before the next call.
+    pub fn apply_text_effects(&mut self, phase: EffectPhase) {
+        let effects = Attrs::BLINK | Attrs::PULSE;
+        for cell in &mut self.screen {
+            if cell.style.attrs.0 & effects.0 != 0 {
+                resolve_cell(cell, phase);
+            }
+        }
+    }
+
+    /// Fill the entire screen with a given style (useful for background).
+    pub fn fill(&mut self, style: CellStyle) {
+        self.screen.fill(StyledCell::blank_styled(style));
+    }
+
+    /// Buffer index of cell (x, y), or `None` if it is off screen.
+    fn index(&self, x: u16, y: u16) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| y as usize * self.width as usize + x as usize)
     }
 }
 
@@ -212,6 +494,7 @@ impl Compositor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::render::strip::{Attrs, Color};
 
     fn make_strip(y: i32, x_offset: i32, text: &str, style: CellStyle) -> Strip {
         let mut strip = Strip::new(y, x_offset);
@@ -329,6 +612,16 @@ mod tests {
         assert_eq!(c.get_cell(5, 2).unwrap().ch, ' ');
     }
 
+    #[test]
+    fn place_strips_applies_glyph_policy() {
+        let mut c = Compositor::new(10, 1);
+        c.set_glyph_policy(GlyphPolicy::safe().with_replacement('✓', 'v'));
+        let strip = make_strip(0, 0, "😀\u{FE0F}|✓|a", CellStyle::default());
+        c.place_strips(&[strip], &Region::new(0, 0, 10, 1));
+        let row: String = c.row(0).unwrap().iter().map(|cell| cell.ch).collect();
+        assert_eq!(row, "? |v|a    ");
+    }
+
     #[test]
     fn place_strips_clipped() {
         let mut c = Compositor::new(10, 5);
@@ -370,12 +663,12 @@ mod tests {
     #[test]
     fn place_strips_with_style() {
         let style = CellStyle {
-            fg: Some("red".into()),
-            bold: true,
+            fg: Some(Color::Red),
+            attrs: Attrs::BOLD,
             ..CellStyle::default()
         };
         let mut c = Compositor::new(10, 5);
-        let strip = make_strip(0, 0, "X", style.clone());
+        let strip = make_strip(0, 0, "X", style);
         c.place_strips(&[strip], &Region::new(0, 0, 10, 5));
 
         let cell = c.get_cell(0, 0).unwrap();
@@ -383,6 +676,37 @@ mod tests {
         assert_eq!(cell.style, style);
     }
 
+    #[test]
+    fn place_strips_scrolled_off_left_and_top() {
+        let mut c = Compositor::new(6, 2);
+        let strips = [
+            make_strip(-1, 0, "hidden", CellStyle::default()),
+            make_strip(0, -3, "abcdef", CellStyle::default()),
+            make_strip(1, -6, "abcdef", CellStyle::default()),
+        ];
+        // A widget scrolled up and left: its region starts off screen too.
+        c.place_strips(&strips, &Region::new(-3, -1, 9, 3));
+        let text = |y| c.row(y).unwrap().iter().map(|cell| cell.ch).collect::<String>();
+        assert_eq!(text(0), "def   ");
+        assert_eq!(text(1), "      ");
+    }
+
+    #[test]
+    fn clip_strip_handles_extreme_offsets() {
+        let c = Compositor::new(10, 2);
+        let screen = c.screen_region();
+        let strip = make_strip(1, i32::MIN, "abc", CellStyle::default());
+        assert_eq!(c.clip_strip(&strip, &screen), None);
+        let strip = make_strip(1, i32::MAX, "abc", CellStyle::default());
+        assert_eq!(c.clip_strip(&strip, &screen), None);
+        let strip = make_strip(i32::MAX, 0, "abc", CellStyle::default());
+        assert_eq!(c.clip_strip(&strip, &Region::new(0, 0, i32::MAX, i32::MAX)), None);
+        let strip = make_strip(1, 8, "abc", CellStyle::default());
+        assert_eq!(c.clip_strip(&strip, &screen), Some((8, 1, 0..2)));
+        assert!(c.cell_at(-1, 0).is_none());
+        assert!(c.cell_at(9, 1).is_some());
+    }
+
     // -----------------------------------------------------------------------
     // diff
     // -----------------------------------------------------------------------
@@ -428,7 +752,7 @@ mod tests {
 
         let mut curr = Compositor::new(10, 5);
         let style = CellStyle {
-            fg: Some("red".into()),
+            fg: Some(Color::Red),
             ..CellStyle::default()
         };
         let strip2 = make_strip(0, 0, "X", style);
@@ -436,7 +760,7 @@ mod tests {
 
         let updates = curr.diff(&prev);
         assert_eq!(updates.len(), 1);
-        assert_eq!(updates[0].cell.style.fg, Some("red".into()));
+        assert_eq!(updates[0].cell.style.fg, Some(Color::Red));
     }
 
     // -----------------------------------------------------------------------
@@ -447,10 +771,10 @@ mod tests {
     fn fill_screen() {
         let mut c = Compositor::new(5, 3);
         let style = CellStyle {
-            bg: Some("blue".into()),
+            bg: Some(Color::Blue),
             ..CellStyle::default()
         };
-        c.fill(style.clone());
+        c.fill(style);
 
         for y in 0..3u16 {
             for x in 0..5u16 {
@@ -460,4 +784,186 @@ mod tests {
             }
         }
     }
+
+    // -----------------------------------------------------------------------
+    // Scroll detection
+    // -----------------------------------------------------------------------
+
+    /// Build a 6x8 frame whose rows read "row0".."row7" shifted by `start`.
+    fn numbered_frame(start: usize) -> Compositor {
+        let mut c = Compositor::new(6, 8);
+        let strips: Vec<Strip> = (0..8)
+            .map(|y| make_strip(y, 0, &format!("row{}", y as usize + start), CellStyle::default()))
+            .collect();
+        c.place_strips(&strips, &Region::new(0, 0, 6, 8));
+        c
+    }
+
+    #[test]
+    fn detect_scroll_up() {
+        let prev = numbered_frame(0);
+        let cur = numbered_frame(2);
+        let hint = cur.detect_scroll(&prev, 4).unwrap();
+        assert_eq!(hint, ScrollHint { top: 0, bottom: 8, delta: 2 });
+    }
+
+    #[test]
+    fn detect_scroll_down() {
+        let prev = numbered_frame(2);
+        let cur = numbered_frame(0);
+        let hint = cur.detect_scroll(&prev, 4).unwrap();
+        assert_eq!(hint.delta, -2);
+        assert_eq!((hint.top, hint.bottom), (0, 8));
+    }
+
+    #[test]
+    fn detect_scroll_none_for_unrelated_frames() {
+        let prev = numbered_frame(0);
+        let mut cur = Compositor::new(6, 8);
+        cur.fill(CellStyle {
+            bg: Some(Color::Red),
+            ..CellStyle::default()
+        });
+        assert!(cur.detect_scroll(&prev, 4).is_none());
+        assert!(prev.detect_scroll(&prev, 4).is_none());
+    }
+
+    #[test]
+    fn scroll_rows_blanks_vacated_rows() {
+        let mut c = numbered_frame(0);
+        c.scroll_rows(&ScrollHint { top: 2, bottom: 6, delta: 1 });
+        assert_eq!(c.get_cell(3, 2).unwrap().ch, '3');
+        assert_eq!(c.get_cell(3, 4).unwrap().ch, '5');
+        assert_eq!(c.get_cell(3, 5).unwrap().ch, ' ');
+        // Outside the region is untouched.
+        assert_eq!(c.get_cell(3, 6).unwrap().ch, '6');
+    }
+
+    #[test]
+    fn diff_with_scroll_reduces_updates() {
+        let prev = numbered_frame(0);
+        let cur = numbered_frame(1);
+        let plain = cur.diff(&prev);
+        let (hint, updates) = cur.diff_with_scroll(&prev, None);
+        assert_eq!(hint.map(|h| h.delta), Some(1));
+        assert!(updates.len() < plain.len());
+
+        // Applying the shift then the updates reproduces the new frame.
+        let mut shifted = prev.clone();
+        shifted.scroll_rows(&hint.unwrap());
+        for u in &updates {
+            shifted.set_cell(u.x, u.y, u.cell).unwrap();
+        }
+        assert!(cur.diff(&shifted).is_empty());
+    }
+
+    #[test]
+    fn scroll_hint_from_scroll_state() {
+        let region = Region::new(0, 2, 40, 10);
+        let hint =
+            ScrollHint::from_scroll(region, Offset::new(0, 5), Offset::new(0, 8), 24).unwrap();
+        assert_eq!(hint, ScrollHint { top: 2, bottom: 12, delta: 3 });
+        assert_eq!(hint.height(), 10);
+        let sideways = Offset::new(1, 6);
+        assert!(ScrollHint::from_scroll(region, Offset::new(0, 5), sideways, 24).is_none());
+        let whole_page = Offset::new(0, 10);
+        assert!(ScrollHint::from_scroll(region, Offset::new(0, 0), whole_page, 24).is_none());
+    }
+
+    #[test]
+    fn scroll_hint_checks_coordinates() {
+        let origin = Offset::new(0, 0);
+        let above = Region::new(0, -2, 40, 10);
+        assert!(ScrollHint::from_scroll(above, origin, Offset::new(0, 1), 24).is_none());
+        let tall = Region::new(0, 2, 40, i32::MAX);
+        let hint = ScrollHint::from_scroll(tall, origin, Offset::new(0, 1), 24).unwrap();
+        assert_eq!(hint.bottom, 24);
+        let far = Offset::new(0, i32::MIN);
+        assert!(ScrollHint::from_scroll(tall, Offset::new(0, 1), far, 24).is_none());
+        let below = Region::new(0, 30, 40, 10);
+        assert!(ScrollHint::from_scroll(below, origin, Offset::new(0, 1), 24).is_none());
+    }
+
+    #[test]
+    fn scroll_hint_for_viewport_taller_than_screen_is_clamped() {
+        // A 20-row viewport starting at row 2 of a 10-row screen.
+        let tall = Region::new(0, 2, 40, 20);
+        let hint = ScrollHint::from_scroll(tall, Offset::new(0, 0), Offset::new(0, 3), 10).unwrap();
+        assert_eq!(hint, ScrollHint { top: 2, bottom: 10, delta: 3 });
+        // A shift as large as the visible part reuses no rows.
+        assert!(ScrollHint::from_scroll(tall, Offset::new(0, 0), Offset::new(0, 8), 10).is_none());
+
+        // Hints handed to the diff are clamped to the frame as well.
+        let prev = numbered_frame(0);
+        let cur = numbered_frame(1);
+        let unclamped = ScrollHint { top: 0, bottom: 40, delta: 1 };
+        let (hint, _) = cur.diff_with_scroll(&prev, Some(unclamped));
+        assert_eq!(hint, Some(ScrollHint { top: 0, bottom: 8, delta: 1 }));
+    }
+
+    // -----------------------------------------------------------------------
+    // Flat buffer accessors
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn rows_iterate_top_to_bottom() {
+        let c = numbered_frame(0);
+        assert_eq!(c.rows().len(), 8);
+        assert_eq!(c.cells().len(), 6 * 8);
+        for (y, row) in c.rows().enumerate() {
+            assert_eq!(row.len(), 6);
+            assert_eq!(row[3].ch, char::from_digit(y as u32, 10).unwrap());
+        }
+        assert_eq!(c.row(7).unwrap()[3].ch, '7');
+        assert!(c.row(8).is_none());
+    }
+
+    #[test]
+    fn resize_keeps_buffer_flat() {
+        let mut c = numbered_frame(0);
+        c.resize(3, 2);
+        assert_eq!(c.cells().len(), 6);
+        assert!(c.cells().iter().all(|cell| *cell == StyledCell::blank()));
+        assert!(c.get_cell(2, 1).is_some());
+        assert!(c.get_cell(3, 0).is_none());
+    }
+
+    #[test]
+    fn place_strips_clips_both_edges() {
+        let mut c = Compositor::new(10, 1);
+        let strip = make_strip(0, -2, "abcdefgh", CellStyle::default());
+        c.place_strips(&[strip], &Region::new(1, 0, 3, 1));
+        let text: String = c.row(0).unwrap().iter().map(|cell| cell.ch).collect();
+        assert_eq!(text, " def      ");
+    }
+
+    #[test]
+    fn set_cell_rejects_out_of_bounds() {
+        let mut c = Compositor::new(4, 2);
+        assert!(c.set_cell(3, 1, StyledCell::new('Z', CellStyle::new())).is_ok());
+        assert_eq!(c.get_cell(3, 1).unwrap().ch, 'Z');
+        assert_eq!(
+            c.set_cell(4, 0, StyledCell::blank()),
+            Err(RenderError::OutOfBounds { x: 4, y: 0, width: 4, height: 2 })
+        );
+    }
+
+    #[test]
+    fn get_cell_mut_writes_through() {
+        let mut c = Compositor::new(4, 2);
+        c.get_cell_mut(1, 1).unwrap().ch = 'Q';
+        assert_eq!(c.cells()[5].ch, 'Q');
+        assert!(c.get_cell_mut(4, 1).is_none());
+    }
+
+    #[test]
+    fn dim_outside_keeps_region() {
+        let mut c = Compositor::new(4, 3);
+        c.dim_outside(Region::new(1, 1, 2, 1));
+        c.dim_outside(Region::new(1, 1, 2, 1));
+        let dimmed = |x, y| c.get_cell(x, y).unwrap().style.attrs.contains(Attrs::DIM);
+        assert!(dimmed(0, 0) && dimmed(3, 1) && dimmed(1, 2));
+        assert!(!dimmed(1, 1) && !dimmed(2, 1));
+        assert_eq!(c.get_cell(0, 0).unwrap().style.attrs, Attrs::DIM);
+    }
 }
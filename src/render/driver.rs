@@ -2,17 +2,47 @@
 //!
 //! The `Driver` wraps a buffered stdout writer and provides methods for entering/leaving
 //! alternate screen, applying cell updates from the compositor, and controlling the cursor.
-//! Color strings are parsed as named colors or `#rrggbb` hex values.
+//! Color strings are parsed as named colors or `#rrggbb` hex values. Linked
+//! cells are wrapped in OSC 8 hyperlinks when the terminal supports them and
+//! underlined otherwise. Text is copied to the system clipboard with OSC 52.
+//!
+//! Frame output is encoded into a buffer the driver keeps between frames and
+//! sent to the terminal according to its [`FlushPolicy`]: after every frame,
+//! only when asked, or once enough bytes have queued up. Frames with no
+//! updates are not flushed at all by default, so idle apps make no writes.
 
 use std::io::{self, Write, BufWriter, Stdout};
+use std::process::{Command, Stdio};
 use crossterm::{
     cursor, execute, queue,
+    event::{
+        DisableMouseCapture, EnableMouseCapture, KeyboardEnhancementFlags,
+        PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
     style::{SetForegroundColor, SetBackgroundColor, SetAttribute, ResetColor, Print, Color, Attribute},
     terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
 };
 
-use super::compositor::CellUpdate;
-use super::strip::CellStyle;
+use super::compositor::{CellUpdate, ScrollHint};
+use super::link::{hyperlinks_supported, osc8_open, OSC8_CLOSE};
+use super::strip::{Attrs, CellStyle, Color as CellColor};
+
+// ---------------------------------------------------------------------------
+// FlushPolicy
+// ---------------------------------------------------------------------------
+
+/// When [`Driver::present`] sends queued frame output to the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FlushPolicy {
+    /// Flush after every frame.
+    #[default]
+    Auto,
+    /// Never flush on its own; call [`Driver::flush`] explicitly.
+    Manual,
+    /// Flush once at least this many bytes are queued, batching small frames.
+    /// Anything still queued is flushed on the next idle frame.
+    Threshold(usize),
+}
 
 // ---------------------------------------------------------------------------
 // Driver
@@ -22,8 +52,33 @@ use super::strip::CellStyle;
 ///
 /// Wraps a `BufWriter<Stdout>` for efficient batched writes. The driver does NOT
 /// automatically enter alternate screen on creation — call `enter_alt_screen` explicitly.
+/// Dropping the driver leaves the alternate screen if it is still active.
 pub struct Driver {
     writer: BufWriter<Stdout>,
+    /// Encoded frame output not yet written; cleared, not freed, on flush.
+    frame: Vec<u8>,
+    /// When `present` flushes `frame`.
+    flush_policy: FlushPolicy,
+    /// Whether `present` skips frames with nothing to draw.
+    skip_idle_flush: bool,
+    /// Whether the alternate screen is active.
+    alt_screen: bool,
+    /// Whether kitty keyboard enhancement flags have been pushed.
+    keyboard_enhanced: bool,
+    /// Whether mouse reporting is enabled.
+    mouse_captured: bool,
+    /// Terminal modes to restore on `resume`, set while suspended.
+    suspended: Option<SuspendedModes>,
+    /// Whether linked cells are emitted as OSC 8 hyperlinks.
+    hyperlinks: bool,
+}
+
+/// Input modes that were active when the driver was suspended.
+#[derive(Debug, Clone, Copy)]
+struct SuspendedModes {
+    alt_screen: bool,
+    mouse_captured: bool,
+    keyboard_enhanced: bool,
 }
 
 impl Driver {
@@ -31,43 +86,338 @@ impl Driver {
     pub fn new() -> io::Result<Self> {
         Ok(Self {
             writer: BufWriter::new(io::stdout()),
+            frame: Vec::new(),
+            flush_policy: FlushPolicy::default(),
+            skip_idle_flush: true,
+            alt_screen: false,
+            keyboard_enhanced: false,
+            mouse_captured: false,
+            suspended: None,
+            hyperlinks: hyperlinks_supported(|name| std::env::var(name).ok()),
         })
     }
 
     /// Enter alternate screen and enable raw mode.
+    ///
+    /// Terminals keep a kitty keyboard flag stack per screen, so call
+    /// [`enable_keyboard_enhancement`](Self::enable_keyboard_enhancement)
+    /// after this, not before.
     pub fn enter_alt_screen(&mut self) -> io::Result<()> {
+        self.write_frame()?;
         execute!(self.writer, EnterAlternateScreen)?;
+        self.alt_screen = true;
         terminal::enable_raw_mode()?;
         Ok(())
     }
 
     /// Leave alternate screen and disable raw mode.
+    ///
+    /// Also releases mouse capture and pops the kitty keyboard flags if they
+    /// were enabled, before leaving so the pop reaches the alternate screen's
+    /// flag stack.
     pub fn leave_alt_screen(&mut self) -> io::Result<()> {
+        self.set_mouse_capture(false)?;
+        self.disable_keyboard_enhancement()?;
         terminal::disable_raw_mode()?;
+        self.write_frame()?;
         execute!(self.writer, LeaveAlternateScreen)?;
+        self.alt_screen = false;
+        Ok(())
+    }
+
+    /// Whether the alternate screen is active.
+    pub fn in_alt_screen(&self) -> bool {
+        self.alt_screen
+    }
+
+    /// Temporarily hand the terminal back to the normal screen.
+    ///
+    /// Leaves the alternate screen (if active), disables raw mode, and shows
+    /// the cursor, remembering the screen, mouse capture and keyboard
+    /// enhancement for `resume`.
+    pub fn suspend(&mut self) -> io::Result<()> {
+        if self.suspended.is_some() {
+            return Ok(());
+        }
+        let modes = SuspendedModes {
+            alt_screen: self.alt_screen,
+            mouse_captured: self.mouse_captured,
+            keyboard_enhanced: self.keyboard_enhanced,
+        };
+        // The alternate screen is about to go; `resume` repaints anyway.
+        self.frame.clear();
+        if self.alt_screen {
+            self.leave_alt_screen()?;
+        } else {
+            self.set_mouse_capture(false)?;
+            self.disable_keyboard_enhancement()?;
+        }
+        self.show_cursor()?;
+        self.suspended = Some(modes);
+        Ok(())
+    }
+
+    /// Re-enter the alternate screen after `suspend` if it was active,
+    /// restoring input modes.
+    ///
+    /// The terminal contents are lost; the caller must repaint everything.
+    pub fn resume(&mut self) -> io::Result<()> {
+        let Some(modes) = self.suspended.take() else {
+            return Ok(());
+        };
+        if modes.alt_screen {
+            self.enter_alt_screen()?;
+        }
+        self.hide_cursor()?;
+        if modes.keyboard_enhanced {
+            self.enable_keyboard_enhancement()?;
+        }
+        self.set_mouse_capture(modes.mouse_captured)
+    }
+
+    /// Whether the driver is currently suspended.
+    pub fn is_suspended(&self) -> bool {
+        self.suspended.is_some()
+    }
+
+    /// Write plain text to the terminal's normal scrollback.
+    ///
+    /// Intended for use while suspended. A trailing newline is added if the
+    /// text does not end with one.
+    pub fn write_text(&mut self, text: &str) -> io::Result<()> {
+        self.write_frame()?;
+        self.writer.write_all(text.as_bytes())?;
+        if !text.ends_with('\n') {
+            self.writer.write_all(b"\n")?;
+        }
+        self.writer.flush()
+    }
+
+    /// Show text in the user's pager (`$PAGER`, defaulting to `less -R`).
+    ///
+    /// Blocks until the pager exits. Intended for use while suspended.
+    pub fn run_pager(&mut self, text: &str) -> io::Result<()> {
+        self.flush()?;
+        let pager = std::env::var("PAGER").unwrap_or_else(|_| "less -R".into());
+        let mut parts = pager.split_whitespace();
+        let program = parts.next().unwrap_or("less");
+        let mut child = Command::new(program)
+            .args(parts)
+            .stdin(Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // The pager may exit before reading everything (e.g. user quits
+            // early); a broken pipe is not an error here.
+            match stdin.write_all(text.as_bytes()) {
+                Err(e) if e.kind() != io::ErrorKind::BrokenPipe => return Err(e),
+                _ => {}
+            }
+        }
+        child.wait()?;
         Ok(())
     }
 
+    /// Negotiate the kitty keyboard protocol.
+    ///
+    /// Queries the terminal for support and, if available, pushes flags that
+    /// disambiguate escape codes, report key releases/repeats, and report
+    /// alternate keys. Returns whether the protocol is now active. Terminals
+    /// without support are left in legacy mode.
+    pub fn enable_keyboard_enhancement(&mut self) -> io::Result<bool> {
+        if self.keyboard_enhanced {
+            return Ok(true);
+        }
+        if !terminal::supports_keyboard_enhancement()? {
+            return Ok(false);
+        }
+        self.write_frame()?;
+        execute!(
+            self.writer,
+            PushKeyboardEnhancementFlags(
+                KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+                    | KeyboardEnhancementFlags::REPORT_EVENT_TYPES
+                    | KeyboardEnhancementFlags::REPORT_ALTERNATE_KEYS
+            )
+        )?;
+        self.keyboard_enhanced = true;
+        Ok(true)
+    }
+
+    /// Pop the kitty keyboard flags pushed by `enable_keyboard_enhancement`.
+    pub fn disable_keyboard_enhancement(&mut self) -> io::Result<()> {
+        if self.keyboard_enhanced {
+            self.write_frame()?;
+            execute!(self.writer, PopKeyboardEnhancementFlags)?;
+            self.keyboard_enhanced = false;
+        }
+        Ok(())
+    }
+
+    /// Whether the kitty keyboard protocol is active.
+    pub fn keyboard_enhanced(&self) -> bool {
+        self.keyboard_enhanced
+    }
+
+    /// Enable or disable mouse reporting.
+    ///
+    /// While capture is off the terminal handles the mouse itself, so users
+    /// can select and copy text with its native selection.
+    pub fn set_mouse_capture(&mut self, enabled: bool) -> io::Result<()> {
+        if self.mouse_captured == enabled {
+            return Ok(());
+        }
+        self.write_frame()?;
+        if enabled {
+            execute!(self.writer, EnableMouseCapture)?;
+        } else {
+            execute!(self.writer, DisableMouseCapture)?;
+        }
+        self.mouse_captured = enabled;
+        Ok(())
+    }
+
+    /// Whether mouse reporting is enabled.
+    pub fn mouse_captured(&self) -> bool {
+        self.mouse_captured
+    }
+
+    /// Copy `text` to the system clipboard using OSC 52.
+    ///
+    /// Works over SSH, but only in terminals that allow clipboard writes.
+    pub fn copy_to_clipboard(&mut self, text: &str) -> io::Result<()> {
+        self.write_frame()?;
+        self.writer.write_all(osc52_sequence(text).as_bytes())?;
+        self.writer.flush()
+    }
+
+    /// Emit linked cells as OSC 8 hyperlinks (`true`) or underline them
+    /// (`false`). Detected from the environment on creation.
+    pub fn set_hyperlinks(&mut self, enabled: bool) {
+        self.hyperlinks = enabled;
+    }
+
+    /// Whether linked cells are emitted as OSC 8 hyperlinks.
+    pub fn hyperlinks(&self) -> bool {
+        self.hyperlinks
+    }
+
+    /// Choose when `present` flushes frame output.
+    pub fn set_flush_policy(&mut self, policy: FlushPolicy) {
+        self.flush_policy = policy;
+    }
+
+    /// When `present` flushes frame output.
+    pub fn flush_policy(&self) -> FlushPolicy {
+        self.flush_policy
+    }
+
+    /// Whether `present` skips frames with no scroll and no cell updates
+    /// (`true` by default), saving a write for every idle frame.
+    pub fn set_skip_idle_flush(&mut self, skip: bool) {
+        self.skip_idle_flush = skip;
+    }
+
+    /// Whether `present` skips frames with nothing to draw.
+    pub fn skip_idle_flush(&self) -> bool {
+        self.skip_idle_flush
+    }
+
+    /// Bytes of frame output queued but not yet flushed.
+    pub fn pending_bytes(&self) -> usize {
+        self.frame.len()
+    }
+
+    /// Queue one frame — an optional scroll shift, then the cell updates —
+    /// and flush according to the flush policy.
+    ///
+    /// Returns whether the terminal was flushed.
+    pub fn present(
+        &mut self,
+        scroll: Option<&ScrollHint>,
+        updates: &[CellUpdate],
+    ) -> io::Result<bool> {
+        if scroll.is_none() && updates.is_empty() {
+            // An idle frame ends a burst: send what a threshold held back, so
+            // a small last frame never waits for the next change.
+            if matches!(self.flush_policy, FlushPolicy::Threshold(_)) && !self.frame.is_empty() {
+                self.flush()?;
+                return Ok(true);
+            }
+            if self.skip_idle_flush {
+                return Ok(false);
+            }
+        }
+        if let Some(hint) = scroll {
+            self.apply_scroll(hint)?;
+        }
+        self.apply_updates(updates)?;
+        let due = match self.flush_policy {
+            FlushPolicy::Auto => true,
+            FlushPolicy::Manual => false,
+            FlushPolicy::Threshold(bytes) => self.frame.len() >= bytes,
+        };
+        if due {
+            self.flush()?;
+        }
+        Ok(due)
+    }
+
     /// Apply a batch of cell updates to the terminal.
     ///
     /// For each update, the cursor is moved to the cell's position, the style
-    /// is applied, and the character is printed. Uses `queue!` for batching;
-    /// call `flush()` afterward to send to the terminal.
+    /// is applied, and the character is printed. Output is queued in the
+    /// frame buffer; call `flush()` afterward (or use `present`) to send it
+    /// to the terminal.
     pub fn apply_updates(&mut self, updates: &[CellUpdate]) -> io::Result<()> {
         for update in updates {
             queue!(
-                self.writer,
+                self.frame,
                 cursor::MoveTo(update.x, update.y)
             )?;
-            self.apply_cell_style(&update.cell.style)?;
-            queue!(self.writer, Print(update.cell.ch))?;
-            queue!(self.writer, ResetColor)?;
+            let style = &update.cell.style;
+            self.apply_cell_style(style)?;
+            let mut attributes = !style.attrs.is_empty();
+            match style.link {
+                Some(link) if self.hyperlinks => {
+                    queue!(self.frame, Print(osc8_open(&link.url())))?;
+                    queue!(self.frame, Print(update.cell.ch), Print(OSC8_CLOSE))?;
+                }
+                Some(_) => {
+                    queue!(self.frame, SetAttribute(Attribute::Underlined))?;
+                    queue!(self.frame, Print(update.cell.ch))?;
+                    attributes = true;
+                }
+                None => queue!(self.frame, Print(update.cell.ch))?,
+            }
+            // Attributes must not reach the next cell. `ResetColor` only
+            // promises to reset colours (the Windows console API leaves
+            // attributes alone); `Attribute::Reset` clears both everywhere.
+            if attributes {
+                queue!(self.frame, SetAttribute(Attribute::Reset))?;
+            } else {
+                queue!(self.frame, ResetColor)?;
+            }
         }
         Ok(())
     }
 
-    /// Flush the internal write buffer to the terminal.
+    /// Shift a block of rows using a terminal scroll region.
+    ///
+    /// Sets the scroll region (CSR) to the hint's rows, scrolls up (SU) or down
+    /// (SD) by `delta`, then restores the full-screen region. Rows shifted this
+    /// way are not repainted; follow with the cell updates from
+    /// `Compositor::diff_with_scroll`.
+    pub fn apply_scroll(&mut self, hint: &ScrollHint) -> io::Result<()> {
+        if hint.delta == 0 || hint.height() == 0 {
+            return Ok(());
+        }
+        queue!(self.frame, Print(scroll_sequence(hint)))
+    }
+
+    /// Flush queued frame output and the internal write buffer to the
+    /// terminal.
     pub fn flush(&mut self) -> io::Result<()> {
+        self.write_frame()?;
         self.writer.flush()
     }
 
@@ -78,46 +428,95 @@ impl Driver {
 
     /// Hide the cursor.
     pub fn hide_cursor(&mut self) -> io::Result<()> {
+        self.write_frame()?;
         execute!(self.writer, cursor::Hide)
     }
 
     /// Show the cursor.
     pub fn show_cursor(&mut self) -> io::Result<()> {
+        self.write_frame()?;
         execute!(self.writer, cursor::Show)
     }
 
+    /// Move queued frame output into the writer, keeping the frame buffer's
+    /// allocation for the next frame. Anything written straight to the
+    /// writer calls this first so it stays in order with the frame.
+    fn write_frame(&mut self) -> io::Result<()> {
+        if !self.frame.is_empty() {
+            self.writer.write_all(&self.frame)?;
+            self.frame.clear();
+        }
+        Ok(())
+    }
+
     /// Queue crossterm style commands for a given `CellStyle`.
     fn apply_cell_style(&mut self, style: &CellStyle) -> io::Result<()> {
-        if let Some(ref fg) = style.fg {
-            if let Some(color) = parse_color(fg) {
-                queue!(self.writer, SetForegroundColor(color))?;
-            }
-        }
-        if let Some(ref bg) = style.bg {
-            if let Some(color) = parse_color(bg) {
-                queue!(self.writer, SetBackgroundColor(color))?;
-            }
+        if let Some(fg) = style.fg {
+            queue!(self.frame, SetForegroundColor(to_crossterm(fg)))?;
         }
-        if style.bold {
-            queue!(self.writer, SetAttribute(Attribute::Bold))?;
+        if let Some(bg) = style.bg {
+            queue!(self.frame, SetBackgroundColor(to_crossterm(bg)))?;
         }
-        if style.dim {
-            queue!(self.writer, SetAttribute(Attribute::Dim))?;
-        }
-        if style.italic {
-            queue!(self.writer, SetAttribute(Attribute::Italic))?;
-        }
-        if style.underline {
-            queue!(self.writer, SetAttribute(Attribute::Underlined))?;
+        const ATTRIBUTES: [(Attrs, Attribute); 6] = [
+            (Attrs::BOLD, Attribute::Bold),
+            (Attrs::DIM, Attribute::Dim),
+            (Attrs::ITALIC, Attribute::Italic),
+            (Attrs::UNDERLINE, Attribute::Underlined),
+            (Attrs::STRIKETHROUGH, Attribute::CrossedOut),
+            (Attrs::REVERSE, Attribute::Reverse),
+        ];
+        for (flag, attribute) in ATTRIBUTES {
+            if style.attrs.contains(flag) {
+                queue!(self.frame, SetAttribute(attribute))?;
+            }
         }
-        if style.strikethrough {
-            queue!(self.writer, SetAttribute(Attribute::CrossedOut))?;
+        Ok(())
+    }
+}
+
+impl Drop for Driver {
+    fn drop(&mut self) {
+        // Restore the terminal even when the app exits through an error.
+        if self.alt_screen {
+            let _ = self.leave_alt_screen();
+            let _ = self.show_cursor();
+        } else {
+            let _ = self.set_mouse_capture(false);
+            let _ = self.disable_keyboard_enhancement();
         }
-        if style.reverse {
-            queue!(self.writer, SetAttribute(Attribute::Reverse))?;
+    }
+}
+
+/// Escape sequence for a scroll-region shift: CSR, SU/SD, CSR reset.
+fn scroll_sequence(hint: &ScrollHint) -> String {
+    let amount = hint.delta.unsigned_abs();
+    let op = if hint.delta > 0 { 'S' } else { 'T' };
+    format!(
+        "\x1b[{};{}r\x1b[{}{}\x1b[r",
+        hint.top + 1,
+        hint.bottom,
+        amount,
+        op
+    )
+}
+
+/// Escape sequence setting the clipboard to `text`: OSC 52 with base64.
+fn osc52_sequence(text: &str) -> String {
+    const ALPHABET: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(text.len().div_ceil(3) * 4);
+    for chunk in text.as_bytes().chunks(3) {
+        let bytes = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
         }
-        Ok(())
     }
+    format!("\x1b]52;c;{encoded}\x07")
 }
 
 // ---------------------------------------------------------------------------
@@ -126,66 +525,33 @@ impl Driver {
 
 /// Parse a color string into a crossterm `Color`.
 ///
-/// Supports:
-/// - Hex colors: `#rrggbb` or `#rgb`
-/// - Named colors: `black`, `red`, `green`, `yellow`, `blue`, `magenta`, `cyan`, `white`,
-///   `dark_red`, `dark_green`, `dark_yellow`, `dark_blue`, `dark_magenta`, `dark_cyan`, `dark_grey`/`dark_gray`,
-///   `grey`/`gray`
-///
-/// Returns `None` if the color string cannot be parsed.
+/// Accepts the same syntax as [`strip::Color::parse`](super::strip::Color::parse):
+/// `#rrggbb`, `#rgb`, and the named ANSI colors. Returns `None` if the color
+/// string cannot be parsed.
 pub fn parse_color(s: &str) -> Option<Color> {
-    let s = s.trim();
-
-    // Hex color
-    if let Some(hex) = s.strip_prefix('#') {
-        return parse_hex_color(hex);
-    }
-
-    // Named colors (case-insensitive)
-    match s.to_ascii_lowercase().as_str() {
-        "black" => Some(Color::Black),
-        "red" => Some(Color::Red),
-        "green" => Some(Color::Green),
-        "yellow" => Some(Color::Yellow),
-        "blue" => Some(Color::Blue),
-        "magenta" => Some(Color::Magenta),
-        "cyan" => Some(Color::Cyan),
-        "white" => Some(Color::White),
-        "dark_red" | "darkred" => Some(Color::DarkRed),
-        "dark_green" | "darkgreen" => Some(Color::DarkGreen),
-        "dark_yellow" | "darkyellow" => Some(Color::DarkYellow),
-        "dark_blue" | "darkblue" => Some(Color::DarkBlue),
-        "dark_magenta" | "darkmagenta" => Some(Color::DarkMagenta),
-        "dark_cyan" | "darkcyan" => Some(Color::DarkCyan),
-        "dark_grey" | "dark_gray" | "darkgrey" | "darkgray" => Some(Color::DarkGrey),
-        "grey" | "gray" => Some(Color::Grey),
-        _ => None,
-    }
+    CellColor::parse(s).map(to_crossterm)
 }
 
-/// Parse a hex color string (without the leading `#`).
-///
-/// Supports 6-digit (`rrggbb`) and 3-digit (`rgb`) formats.
-fn parse_hex_color(hex: &str) -> Option<Color> {
-    match hex.len() {
-        6 => {
-            let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
-            let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
-            let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
-            Some(Color::Rgb { r, g, b })
-        }
-        3 => {
-            let r = u8::from_str_radix(&hex[0..1], 16).ok()?;
-            let g = u8::from_str_radix(&hex[1..2], 16).ok()?;
-            let b = u8::from_str_radix(&hex[2..3], 16).ok()?;
-            // Expand: 0xA -> 0xAA
-            Some(Color::Rgb {
-                r: r * 16 + r,
-                g: g * 16 + g,
-                b: b * 16 + b,
-            })
-        }
-        _ => None,
+/// Convert a packed cell color into crossterm's representation.
+fn to_crossterm(color: CellColor) -> Color {
+    match color {
+        CellColor::Black => Color::Black,
+        CellColor::Red => Color::Red,
+        CellColor::Green => Color::Green,
+        CellColor::Yellow => Color::Yellow,
+        CellColor::Blue => Color::Blue,
+        CellColor::Magenta => Color::Magenta,
+        CellColor::Cyan => Color::Cyan,
+        CellColor::White => Color::White,
+        CellColor::DarkRed => Color::DarkRed,
+        CellColor::DarkGreen => Color::DarkGreen,
+        CellColor::DarkYellow => Color::DarkYellow,
+        CellColor::DarkBlue => Color::DarkBlue,
+        CellColor::DarkMagenta => Color::DarkMagenta,
+        CellColor::DarkCyan => Color::DarkCyan,
+        CellColor::DarkGrey => Color::DarkGrey,
+        CellColor::Grey => Color::Grey,
+        CellColor::Rgb(r, g, b) => Color::Rgb { r, g, b },
     }
 }
 
@@ -196,6 +562,7 @@ fn parse_hex_color(hex: &str) -> Option<Color> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::render::strip::StyledCell;
     use crossterm::style::Color;
 
     // -----------------------------------------------------------------------
@@ -255,6 +622,18 @@ mod tests {
         assert_eq!(parse_color("#gghhii"), None);
     }
 
+    // -----------------------------------------------------------------------
+    // Clipboard
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn osc52_encodes_base64() {
+        assert_eq!(osc52_sequence("hi"), "\x1b]52;c;aGk=\x07");
+        assert_eq!(osc52_sequence("abc"), "\x1b]52;c;YWJj\x07");
+        assert_eq!(osc52_sequence("a"), "\x1b]52;c;YQ==\x07");
+        assert_eq!(osc52_sequence(""), "\x1b]52;c;\x07");
+    }
+
     // -----------------------------------------------------------------------
     // Color parsing — named
     // -----------------------------------------------------------------------
@@ -321,4 +700,127 @@ mod tests {
         let driver = Driver::new();
         assert!(driver.is_ok());
     }
+
+    #[test]
+    fn driver_keyboard_enhancement_off_by_default() {
+        let mut driver = Driver::new().unwrap();
+        assert!(!driver.keyboard_enhanced());
+        // Disabling when never enabled writes nothing and succeeds.
+        assert!(driver.disable_keyboard_enhancement().is_ok());
+    }
+
+    #[test]
+    fn driver_mouse_capture_off_by_default() {
+        let mut driver = Driver::new().unwrap();
+        assert!(!driver.mouse_captured());
+        // Disabling when already off is a no-op.
+        assert!(driver.set_mouse_capture(false).is_ok());
+        assert!(!driver.mouse_captured());
+    }
+
+    #[test]
+    fn driver_skips_idle_frames() {
+        let mut driver = Driver::new().unwrap();
+        assert!(driver.skip_idle_flush());
+        assert_eq!(driver.flush_policy(), FlushPolicy::Auto);
+        assert!(!driver.present(None, &[]).unwrap());
+        assert_eq!(driver.pending_bytes(), 0);
+    }
+
+    #[test]
+    fn driver_manual_and_threshold_policies_queue() {
+        let mut driver = Driver::new().unwrap();
+        let update = CellUpdate {
+            x: 1,
+            y: 2,
+            cell: StyledCell::new('x', CellStyle::new()),
+        };
+        driver.set_flush_policy(FlushPolicy::Manual);
+        assert!(!driver.present(None, std::slice::from_ref(&update)).unwrap());
+        let one_frame = driver.pending_bytes();
+        assert!(one_frame > 0);
+
+        driver.set_flush_policy(FlushPolicy::Threshold(one_frame * 3));
+        assert!(!driver.present(None, &[update]).unwrap());
+        assert_eq!(driver.pending_bytes(), one_frame * 2);
+    }
+
+    #[test]
+    fn driver_threshold_flushes_pending_bytes_on_idle_frame() {
+        let mut driver = Driver::new().unwrap();
+        let update = CellUpdate {
+            x: 0,
+            y: 0,
+            cell: StyledCell::new('x', CellStyle::new()),
+        };
+        driver.set_flush_policy(FlushPolicy::Threshold(4096));
+        assert!(!driver.present(None, &[update]).unwrap());
+        assert!(driver.pending_bytes() > 0);
+        assert!(driver.present(None, &[]).unwrap());
+        assert_eq!(driver.pending_bytes(), 0);
+        // Nothing left to send: later idle frames are skipped again.
+        assert!(!driver.present(None, &[]).unwrap());
+    }
+
+    #[test]
+    fn driver_resets_attributes_after_styled_cells() {
+        let mut driver = Driver::new().unwrap();
+        driver.set_flush_policy(FlushPolicy::Manual);
+        driver.set_hyperlinks(false);
+        let cell = |style| CellUpdate {
+            x: 0,
+            y: 0,
+            cell: StyledCell::new('x', style),
+        };
+        let linked = cell(CellStyle::new().with_link("https://example.com"));
+        let bold = cell(CellStyle {
+            attrs: Attrs::BOLD,
+            ..CellStyle::new()
+        });
+        for update in [linked, bold] {
+            driver.frame.clear();
+            driver.apply_updates(&[update]).unwrap();
+            assert!(driver.frame.ends_with(b"x\x1b[0m"), "{:?}", driver.frame);
+        }
+    }
+
+    #[test]
+    fn driver_starts_on_the_main_screen() {
+        let driver = Driver::new().unwrap();
+        assert!(!driver.in_alt_screen());
+        assert!(!driver.keyboard_enhanced());
+    }
+
+    #[test]
+    fn suspend_and_resume_stay_on_the_main_screen() {
+        let mut driver = Driver::new().unwrap();
+        driver.suspend().unwrap();
+        assert!(driver.is_suspended());
+        driver.resume().unwrap();
+        assert!(!driver.in_alt_screen());
+    }
+
+    #[test]
+    fn driver_resume_without_suspend_is_noop() {
+        let mut driver = Driver::new().unwrap();
+        assert!(!driver.is_suspended());
+        assert!(driver.resume().is_ok());
+        assert!(!driver.is_suspended());
+    }
+
+    // -----------------------------------------------------------------------
+    // Scroll regions
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn scroll_sequence_up() {
+        let hint = ScrollHint { top: 2, bottom: 12, delta: 3 };
+        assert_eq!(scroll_sequence(&hint), "\x1b[3;12r\x1b[3S\x1b[r");
+    }
+
+    #[test]
+    fn scroll_sequence_down() {
+        let hint = ScrollHint { top: 0, bottom: 24, delta: -1 };
+        assert_eq!(scroll_sequence(&hint), "\x1b[1;24r\x1b[1T\x1b[r");
+    }
 }
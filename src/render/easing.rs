@@ -0,0 +1,137 @@
+//! Easing curves mapping linear animation progress to eased progress.
+//!
+//! Every curve maps `0.0` to `0.0` and `1.0` to `1.0`. The named CSS curves
+//! are cubic Béziers with the standard control points; [`Easing::Spring`]
+//! overshoots and settles like a damped spring.
+
+// ---------------------------------------------------------------------------
+// Easing
+// ---------------------------------------------------------------------------
+
+/// An easing curve.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Easing {
+    /// Constant speed.
+    #[default]
+    Linear,
+    /// Starts slow (CSS `ease-in`).
+    EaseIn,
+    /// Ends slow (CSS `ease-out`).
+    EaseOut,
+    /// Starts and ends slow (CSS `ease-in-out`).
+    EaseInOut,
+    /// A CSS `cubic-bezier(x1, y1, x2, y2)` curve. `x1` and `x2` are clamped
+    /// to `0.0..=1.0`.
+    CubicBezier(f32, f32, f32, f32),
+    /// A damped spring. Higher `stiffness` oscillates faster, higher
+    /// `damping` settles sooner.
+    Spring {
+        /// Angular frequency of the oscillation.
+        stiffness: f32,
+        /// Exponential decay rate.
+        damping: f32,
+    },
+}
+
+impl Easing {
+    /// A spring that overshoots slightly before settling.
+    pub const SPRING: Easing = Easing::Spring {
+        stiffness: 12.0,
+        damping: 6.0,
+    };
+
+    /// Map linear progress `t` (clamped to `0.0..=1.0`) through the curve.
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        if t == 0.0 || t == 1.0 {
+            return t;
+        }
+        match *self {
+            Easing::Linear => t,
+            Easing::EaseIn => cubic_bezier(0.42, 0.0, 1.0, 1.0, t),
+            Easing::EaseOut => cubic_bezier(0.0, 0.0, 0.58, 1.0, t),
+            Easing::EaseInOut => cubic_bezier(0.42, 0.0, 0.58, 1.0, t),
+            Easing::CubicBezier(x1, y1, x2, y2) => {
+                cubic_bezier(x1.clamp(0.0, 1.0), y1, x2.clamp(0.0, 1.0), y2, t)
+            }
+            Easing::Spring { stiffness, damping } => {
+                1.0 - (-damping * t).exp() * (stiffness * t).cos()
+            }
+        }
+    }
+}
+
+/// One coordinate of a Bézier from (0,0) to (1,1) with control values `a`, `b`.
+fn bezier(a: f32, b: f32, s: f32) -> f32 {
+    let inv = 1.0 - s;
+    3.0 * inv * inv * s * a + 3.0 * inv * s * s * b + s * s * s
+}
+
+/// Evaluate a CSS cubic Bézier at horizontal position `x`.
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, x: f32) -> f32 {
+    // x(s) is monotonic for x1, x2 in [0, 1], so bisection always converges.
+    let (mut lo, mut hi) = (0.0f32, 1.0f32);
+    let mut s = x;
+    for _ in 0..32 {
+        let current = bezier(x1, x2, s);
+        if (current - x).abs() < 1e-5 {
+            break;
+        }
+        if current < x {
+            lo = s;
+        } else {
+            hi = s;
+        }
+        s = (lo + hi) / 2.0;
+    }
+    bezier(y1, y2, s)
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Easing; 6] = [
+        Easing::Linear,
+        Easing::EaseIn,
+        Easing::EaseOut,
+        Easing::EaseInOut,
+        Easing::CubicBezier(0.1, 0.7, 1.0, 0.1),
+        Easing::SPRING,
+    ];
+
+    #[test]
+    fn endpoints_are_fixed() {
+        for easing in ALL {
+            assert_eq!(easing.apply(0.0), 0.0, "{easing:?}");
+            assert_eq!(easing.apply(1.0), 1.0, "{easing:?}");
+            assert_eq!(easing.apply(2.0), 1.0, "{easing:?}");
+        }
+    }
+
+    #[test]
+    fn named_curves_shape() {
+        assert_eq!(Easing::Linear.apply(0.3), 0.3);
+        assert!(Easing::EaseIn.apply(0.3) < 0.3);
+        assert!(Easing::EaseOut.apply(0.3) > 0.3);
+        assert!((Easing::EaseInOut.apply(0.5) - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn bezier_matches_linear_control_points() {
+        let linear = Easing::CubicBezier(1.0 / 3.0, 1.0 / 3.0, 2.0 / 3.0, 2.0 / 3.0);
+        assert!((linear.apply(0.7) - 0.7).abs() < 1e-3);
+    }
+
+    #[test]
+    fn spring_overshoots() {
+        let peak = (1..100)
+            .map(|i| Easing::SPRING.apply(i as f32 / 100.0))
+            .fold(0.0f32, f32::max);
+        assert!(peak > 1.0);
+    }
+}
@@ -0,0 +1,185 @@
+//! Software text effects: `text-style: blink` and `text-style: pulse`.
+//!
+//! Terminal blink (SGR 5) is ignored or rendered inconsistently by many
+//! terminals, so neither effect is ever sent to the terminal. Cells carrying
+//! [`Attrs::BLINK`] or [`Attrs::PULSE`] are resolved in the compositor once
+//! per frame, driven by a shared clock that flips between two phases every
+//! [`DEFAULT_PERIOD`] (or the configured period):
+//!
+//! - blink shows the text in the on phase and blanks it in the off phase;
+//! - pulse draws the text bold in the on phase and dim in the off phase.
+//!
+//! In reduced-motion mode the clock stays [`EffectPhase::Steady`]: blinking
+//! text is shown normally and pulsing text is drawn without either attribute.
+
+use std::time::{Duration, Instant};
+
+use crate::render::strip::{Attrs, StyledCell};
+
+/// How long each phase lasts by default.
+pub const DEFAULT_PERIOD: Duration = Duration::from_millis(500);
+
+/// The state text effects are drawn in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EffectPhase {
+    /// Effects are off (reduced motion).
+    Steady,
+    /// Blinking text is shown and pulsing text is bold.
+    On,
+    /// Blinking text is hidden and pulsing text is dim.
+    Off,
+}
+
+/// Replace a cell's effect attributes with what they look like in `phase`.
+pub fn resolve_cell(cell: &mut StyledCell, phase: EffectPhase) {
+    let attrs = &mut cell.style.attrs;
+    let (blink, pulse) = (attrs.contains(Attrs::BLINK), attrs.contains(Attrs::PULSE));
+    attrs.set(Attrs::BLINK | Attrs::PULSE, false);
+    if blink && phase == EffectPhase::Off {
+        cell.ch = ' ';
+    }
+    if pulse {
+        match phase {
+            EffectPhase::On => attrs.set(Attrs::BOLD, true),
+            EffectPhase::Off => attrs.set(Attrs::DIM, true),
+            EffectPhase::Steady => {}
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// TextEffects
+// ---------------------------------------------------------------------------
+
+/// The clock behind blink and pulse, with the reduced-motion switch.
+#[derive(Debug, Clone)]
+pub struct TextEffects {
+    period: Duration,
+    reduced_motion: bool,
+    start: Instant,
+    phase: EffectPhase,
+}
+
+impl TextEffects {
+    /// Start a clock now, in the on phase, with the default period.
+    pub fn new() -> Self {
+        Self {
+            period: DEFAULT_PERIOD,
+            reduced_motion: false,
+            start: Instant::now(),
+            phase: EffectPhase::On,
+        }
+    }
+
+    /// Set how long each phase lasts (builder pattern).
+    pub fn with_period(mut self, period: Duration) -> Self {
+        self.set_period(period);
+        self
+    }
+
+    /// Set how long each phase lasts. A zero period is treated as 1ms.
+    pub fn set_period(&mut self, period: Duration) {
+        self.period = period.max(Duration::from_millis(1));
+    }
+
+    /// How long each phase lasts.
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Turn reduced-motion mode on or off. While on, the phase is
+    /// [`EffectPhase::Steady`].
+    pub fn set_reduced_motion(&mut self, reduced: bool) {
+        self.reduced_motion = reduced;
+        self.phase = if reduced {
+            EffectPhase::Steady
+        } else {
+            EffectPhase::On
+        };
+        self.start = Instant::now();
+    }
+
+    /// Whether reduced-motion mode is on.
+    pub fn reduced_motion(&self) -> bool {
+        self.reduced_motion
+    }
+
+    /// The phase as of the last [`tick`](Self::tick).
+    pub fn phase(&self) -> EffectPhase {
+        self.phase
+    }
+
+    /// Advance the clock to `now`. Returns whether the phase changed.
+    pub fn tick(&mut self, now: Instant) -> bool {
+        if self.reduced_motion {
+            return false;
+        }
+        let elapsed = now.saturating_duration_since(self.start);
+        let phase = if (elapsed.as_millis() / self.period.as_millis()) % 2 == 0 {
+            EffectPhase::On
+        } else {
+            EffectPhase::Off
+        };
+        let changed = phase != self.phase;
+        self.phase = phase;
+        changed
+    }
+}
+
+impl Default for TextEffects {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::strip::CellStyle;
+
+    fn cell(attrs: Attrs) -> StyledCell {
+        StyledCell::new('x', CellStyle::new().with_attrs(attrs))
+    }
+
+    #[test]
+    fn clock_alternates_every_period() {
+        let mut effects = TextEffects::new().with_period(Duration::from_millis(100));
+        let start = effects.start;
+        let at = |ms| start + Duration::from_millis(ms);
+        assert!(!effects.tick(at(50)));
+        assert!(effects.tick(at(150)));
+        assert_eq!(effects.phase(), EffectPhase::Off);
+        assert!(effects.tick(at(210)));
+        assert_eq!(effects.phase(), EffectPhase::On);
+    }
+
+    #[test]
+    fn cells_resolve_per_phase() {
+        let mut blink = cell(Attrs::BLINK | Attrs::ITALIC);
+        resolve_cell(&mut blink, EffectPhase::Off);
+        assert_eq!((blink.ch, blink.style.attrs), (' ', Attrs::ITALIC));
+
+        let mut pulse = cell(Attrs::PULSE);
+        resolve_cell(&mut pulse, EffectPhase::On);
+        assert_eq!((pulse.ch, pulse.style.attrs), ('x', Attrs::BOLD));
+        let mut pulse = cell(Attrs::PULSE);
+        resolve_cell(&mut pulse, EffectPhase::Off);
+        assert_eq!(pulse.style.attrs, Attrs::DIM);
+    }
+
+    #[test]
+    fn reduced_motion_holds_steady() {
+        let mut effects = TextEffects::new().with_period(Duration::from_millis(1));
+        effects.set_reduced_motion(true);
+        assert!(!effects.tick(Instant::now() + Duration::from_secs(1)));
+        assert_eq!(effects.phase(), EffectPhase::Steady);
+
+        let mut both = cell(Attrs::BLINK | Attrs::PULSE);
+        resolve_cell(&mut both, EffectPhase::Steady);
+        assert_eq!((both.ch, both.style.attrs), ('x', Attrs::NONE));
+    }
+}
@@ -0,0 +1,505 @@
+//! Patterned background fills: gradients, stripes and dithering.
+//!
+//! Besides a plain color, `background` accepts three fill functions:
+//!
+//! ```ignore
+//! Header      { background: linear-gradient(to right, #1e3a8a, #7c3aed); }
+//! ProgressBar { background: stripes(dark_blue, blue, 2); }
+//! #overlay    { background: dither(black, dark_grey, 25%); }
+//! ```
+//!
+//! Like other colors the value is kept as a string in
+//! [`Styles`](crate::css::styles::Styles). [`BackgroundFill::parse`] reads it
+//! at render time, and [`paint_background`] recolors the background of a
+//! widget's rendered strips cell by cell. Colors are quantized to the
+//! terminal's [`ColorDepth`]; on 16-color terminals gradients are
+//! ordered-dithered between neighbouring stops instead of banding.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use super::animation::lerp_rgb;
+use super::strip::{Color, Strip};
+use crate::css::styles::Styles;
+use crate::geometry::Region;
+
+// ---------------------------------------------------------------------------
+// ColorDepth
+// ---------------------------------------------------------------------------
+
+/// How many colors the terminal can show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorDepth {
+    /// The 16 named ANSI colors.
+    Ansi16,
+    /// The xterm 256-color palette.
+    Indexed256,
+    /// 24-bit RGB.
+    TrueColor,
+}
+
+/// The process-wide depth: 0 until detected or set, otherwise `depth as u8 + 1`.
+static CURRENT_DEPTH: AtomicU8 = AtomicU8::new(0);
+
+/// The 16 named colors, for quantizing to [`ColorDepth::Ansi16`].
+const ANSI16: [Color; 16] = [
+    Color::Black,
+    Color::DarkRed,
+    Color::DarkGreen,
+    Color::DarkYellow,
+    Color::DarkBlue,
+    Color::DarkMagenta,
+    Color::DarkCyan,
+    Color::Grey,
+    Color::DarkGrey,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::White,
+];
+
+/// Channel levels of the xterm 6x6x6 color cube.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+impl ColorDepth {
+    /// Infer the depth from `COLORTERM` and `TERM` values.
+    pub fn from_env(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        if matches!(colorterm, Some("truecolor" | "24bit")) {
+            ColorDepth::TrueColor
+        } else if term.is_some_and(|term| term.contains("256color")) {
+            ColorDepth::Indexed256
+        } else {
+            ColorDepth::Ansi16
+        }
+    }
+
+    /// Detect the depth from the environment.
+    pub fn detect() -> Self {
+        Self::from_env(
+            std::env::var("COLORTERM").ok().as_deref(),
+            std::env::var("TERM").ok().as_deref(),
+        )
+    }
+
+    /// The depth fills are rendered at: the one last passed to
+    /// [`ColorDepth::set_current`], or the detected depth.
+    pub fn current() -> Self {
+        match CURRENT_DEPTH.load(Ordering::Relaxed) {
+            1 => ColorDepth::Ansi16,
+            2 => ColorDepth::Indexed256,
+            3 => ColorDepth::TrueColor,
+            _ => {
+                let depth = Self::detect();
+                Self::set_current(depth);
+                depth
+            }
+        }
+    }
+
+    /// Override the depth fills are rendered at.
+    pub fn set_current(depth: ColorDepth) {
+        CURRENT_DEPTH.store(depth as u8 + 1, Ordering::Relaxed);
+    }
+
+    /// The closest color this depth can show.
+    pub fn quantize(self, color: Color) -> Color {
+        let Color::Rgb(r, g, b) = color else {
+            return color;
+        };
+        match self {
+            ColorDepth::TrueColor => color,
+            ColorDepth::Indexed256 => {
+                let snap = |c: u8| {
+                    CUBE_LEVELS
+                        .into_iter()
+                        .min_by_key(|level| level.abs_diff(c))
+                        .unwrap_or(c)
+                };
+                Color::Rgb(snap(r), snap(g), snap(b))
+            }
+            ColorDepth::Ansi16 => ANSI16
+                .into_iter()
+                .min_by_key(|named| {
+                    let (nr, ng, nb) = named.approx_rgb();
+                    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+                    d(nr, r) + d(ng, g) + d(nb, b)
+                })
+                .unwrap_or(color),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// BackgroundFill
+// ---------------------------------------------------------------------------
+
+/// The axis a fill varies along.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillDirection {
+    /// Top to bottom (`to bottom`, the default).
+    #[default]
+    Vertical,
+    /// Left to right (`to right`).
+    Horizontal,
+}
+
+/// A parsed patterned background.
+///
+/// [`BackgroundFill::parse`] always yields at least two colors, but the
+/// variants can be built by hand: a gradient or stripes with a single color
+/// paint it solid, and with none they paint nothing.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BackgroundFill {
+    /// `linear-gradient([to <side>,] <color>, <color>...)`: colors blended
+    /// evenly between the stops.
+    LinearGradient {
+        direction: FillDirection,
+        stops: Vec<Color>,
+    },
+    /// `stripes([to <side>,] <color>, <color>... [, <width>])`: bands of
+    /// `width` cells cycling through the colors.
+    Stripes {
+        direction: FillDirection,
+        colors: Vec<Color>,
+        width: u16,
+    },
+    /// `dither(<color>, <color> [, <percent>])`: an ordered-dither mix with
+    /// `density` of the cells in the second color.
+    Dither { colors: [Color; 2], density: f32 },
+}
+
+/// 4x4 Bayer matrix for ordered dithering.
+const BAYER4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// The dither threshold in `0.0..1.0` for a cell.
+fn threshold(x: i32, y: i32) -> f32 {
+    let cell = BAYER4[y.rem_euclid(4) as usize][x.rem_euclid(4) as usize];
+    (cell as f32 + 0.5) / 16.0
+}
+
+/// Split `to <side>` off the front of the arguments.
+fn take_direction(args: &mut Vec<&str>) -> (FillDirection, bool) {
+    let direction = match args.first().and_then(|arg| arg.strip_prefix("to ")) {
+        Some("bottom") => (FillDirection::Vertical, false),
+        Some("top") => (FillDirection::Vertical, true),
+        Some("right") => (FillDirection::Horizontal, false),
+        Some("left") => (FillDirection::Horizontal, true),
+        _ => return (FillDirection::default(), false),
+    };
+    args.remove(0);
+    direction
+}
+
+fn parse_colors(args: &[&str]) -> Option<Vec<Color>> {
+    let colors = args
+        .iter()
+        .map(|arg| Color::parse(arg))
+        .collect::<Option<Vec<_>>>()?;
+    (colors.len() >= 2).then_some(colors)
+}
+
+impl BackgroundFill {
+    /// Parse a `background` value. Returns `None` for plain colors and
+    /// malformed fills.
+    pub fn parse(s: &str) -> Option<Self> {
+        let (name, rest) = s.trim().split_once('(')?;
+        let inner = rest.strip_suffix(')')?;
+        let mut args: Vec<&str> = inner.split(',').map(str::trim).collect();
+        match name.trim() {
+            "linear-gradient" => {
+                let (direction, reverse) = take_direction(&mut args);
+                let mut stops = parse_colors(&args)?;
+                if reverse {
+                    stops.reverse();
+                }
+                Some(BackgroundFill::LinearGradient { direction, stops })
+            }
+            "stripes" => {
+                let (direction, reverse) = take_direction(&mut args);
+                let width = match args.last().map(|arg| arg.parse::<u16>()) {
+                    Some(Ok(width)) => {
+                        args.pop();
+                        width.max(1)
+                    }
+                    _ => 1,
+                };
+                let mut colors = parse_colors(&args)?;
+                if reverse {
+                    colors.reverse();
+                }
+                Some(BackgroundFill::Stripes {
+                    direction,
+                    colors,
+                    width,
+                })
+            }
+            "dither" => {
+                if args.len() > 3 {
+                    return None;
+                }
+                let density = match args.get(2) {
+                    Some(pct) => pct.strip_suffix('%')?.parse::<f32>().ok()? / 100.0,
+                    None => 0.5,
+                };
+                let colors = parse_colors(&args[..args.len().min(2)])?;
+                Some(BackgroundFill::Dither {
+                    colors: [colors[0], colors[1]],
+                    density: density.clamp(0.0, 1.0),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// The solid color the fill starts from, used where a single background
+    /// color is needed (text cells, `color: auto`). `None` if the fill has
+    /// no colors.
+    pub fn base_color(&self) -> Option<Color> {
+        match self {
+            BackgroundFill::LinearGradient { stops: colors, .. }
+            | BackgroundFill::Stripes { colors, .. } => colors.first().copied(),
+            BackgroundFill::Dither { colors, .. } => Some(colors[0]),
+        }
+    }
+
+    /// The color of cell (`x`, `y`) of a `width` x `height` area, quantized
+    /// to `depth`. `None` if the fill has no colors.
+    pub fn color_at(
+        &self,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        depth: ColorDepth,
+    ) -> Option<Color> {
+        let along = |direction: FillDirection| match direction {
+            FillDirection::Vertical => (y, height),
+            FillDirection::Horizontal => (x, width),
+        };
+        let color = match self {
+            BackgroundFill::LinearGradient { stops, .. } if stops.len() < 2 => *stops.first()?,
+            BackgroundFill::LinearGradient { direction, stops } => {
+                let (pos, len) = along(*direction);
+                let t = if len > 1 {
+                    pos.clamp(0, len - 1) as f32 / (len - 1) as f32
+                } else {
+                    0.0
+                };
+                let segment = t * (stops.len() - 1) as f32;
+                let index = (segment.floor() as usize).min(stops.len() - 2);
+                let frac = segment - index as f32;
+                let (from, to) = (stops[index], stops[index + 1]);
+                if depth == ColorDepth::Ansi16 {
+                    if frac > threshold(x, y) {
+                        to
+                    } else {
+                        from
+                    }
+                } else {
+                    lerp_rgb(from.approx_rgb(), to.approx_rgb(), frac)
+                }
+            }
+            BackgroundFill::Stripes {
+                direction,
+                colors,
+                width: band,
+            } => {
+                if colors.is_empty() {
+                    return None;
+                }
+                let (pos, _) = along(*direction);
+                colors[(pos.max(0) as usize / (*band).max(1) as usize) % colors.len()]
+            }
+            BackgroundFill::Dither { colors, density } => {
+                if threshold(x, y) < *density {
+                    colors[1]
+                } else {
+                    colors[0]
+                }
+            }
+        };
+        Some(depth.quantize(color))
+    }
+}
+
+/// The single color a `background` value stands for: the color itself, or a
+/// fill's [`base_color`](BackgroundFill::base_color).
+pub fn background_color(s: &str) -> Option<Color> {
+    Color::parse(s).or_else(|| BackgroundFill::parse(s).and_then(|fill| fill.base_color()))
+}
+
+// ---------------------------------------------------------------------------
+// Background fill pass
+// ---------------------------------------------------------------------------
+
+/// Paint a patterned `background` over strips rendered for `region`.
+///
+/// Cells showing the plain background (the fill's base color, or none) are
+/// recolored by position; cells a widget gave a different background, such
+/// as a selection highlight, are left alone. Does nothing for plain colors.
+pub fn paint_background(strips: &mut [Strip], region: Region, styles: &Styles) {
+    let Some(fill) = styles.background.as_deref().and_then(BackgroundFill::parse) else {
+        return;
+    };
+    let Some(base) = fill.base_color() else {
+        return;
+    };
+    let depth = ColorDepth::current();
+    for strip in strips {
+        let y = strip.y - region.y;
+        for (i, cell) in strip.cells.iter_mut().enumerate() {
+            if cell.style.bg.is_some_and(|bg| bg != base) {
+                continue;
+            }
+            let x = strip.x_offset + i as i32 - region.x;
+            cell.style.bg = fill.color_at(x, y, region.width, region.height, depth);
+        }
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::strip::CellStyle;
+
+    #[test]
+    fn parses_fill_functions() {
+        assert_eq!(
+            BackgroundFill::parse("linear-gradient(to left, red, #0000ff)"),
+            Some(BackgroundFill::LinearGradient {
+                direction: FillDirection::Horizontal,
+                stops: vec![Color::Rgb(0, 0, 255), Color::Red],
+            })
+        );
+        assert_eq!(
+            BackgroundFill::parse("stripes(red, blue, 2)"),
+            Some(BackgroundFill::Stripes {
+                direction: FillDirection::Vertical,
+                colors: vec![Color::Red, Color::Blue],
+                width: 2,
+            })
+        );
+        assert!(BackgroundFill::parse("red").is_none());
+        assert!(BackgroundFill::parse("linear-gradient(red)").is_none());
+        assert!(BackgroundFill::parse("dither(red, nope)").is_none());
+        assert_eq!(background_color("stripes(red, blue)"), Some(Color::Red));
+    }
+
+    #[test]
+    fn gradient_interpolates_in_truecolor() {
+        let fill = BackgroundFill::parse("linear-gradient(to right, #000000, #ffffff)").unwrap();
+        let at = |x| fill.color_at(x, 0, 5, 1, ColorDepth::TrueColor).unwrap();
+        assert_eq!(at(0), Color::Rgb(0, 0, 0));
+        assert_eq!(at(2), Color::Rgb(128, 128, 128));
+        assert_eq!(at(4), Color::Rgb(255, 255, 255));
+    }
+
+    #[test]
+    fn gradient_dithers_in_ansi16() {
+        let fill = BackgroundFill::parse("linear-gradient(red, blue)").unwrap();
+        let row: Vec<Color> = (0..4)
+            .map(|x| fill.color_at(x, 2, 4, 5, ColorDepth::Ansi16).unwrap())
+            .collect();
+        assert!(row.contains(&Color::Red) && row.contains(&Color::Blue));
+        assert_eq!(
+            fill.color_at(0, 0, 4, 5, ColorDepth::Ansi16),
+            Some(Color::Red)
+        );
+        assert_eq!(
+            fill.color_at(0, 4, 4, 5, ColorDepth::Ansi16),
+            Some(Color::Blue)
+        );
+    }
+
+    #[test]
+    fn fills_with_fewer_than_two_colors() {
+        let at = |fill: &BackgroundFill| fill.color_at(2, 3, 5, 5, ColorDepth::TrueColor);
+        let single = BackgroundFill::LinearGradient {
+            direction: FillDirection::Vertical,
+            stops: vec![Color::Red],
+        };
+        assert_eq!(single.base_color(), Some(Color::Red));
+        assert_eq!(at(&single), Some(Color::Red));
+
+        let empty = BackgroundFill::LinearGradient {
+            direction: FillDirection::Vertical,
+            stops: Vec::new(),
+        };
+        assert_eq!(empty.base_color(), None);
+        assert_eq!(at(&empty), None);
+
+        let stripes = |colors: Vec<Color>| BackgroundFill::Stripes {
+            direction: FillDirection::Vertical,
+            colors,
+            width: 0,
+        };
+        assert_eq!(at(&stripes(vec![Color::Blue])), Some(Color::Blue));
+        assert_eq!(at(&stripes(Vec::new())), None);
+    }
+
+    #[test]
+    fn stripes_and_dither_patterns() {
+        let stripes = BackgroundFill::parse("stripes(to right, red, blue, 2)").unwrap();
+        let cols: Vec<Color> = (0..5)
+            .map(|x| stripes.color_at(x, 0, 5, 1, ColorDepth::TrueColor).unwrap())
+            .collect();
+        assert_eq!(
+            cols,
+            [Color::Red, Color::Red, Color::Blue, Color::Blue, Color::Red]
+        );
+
+        let checker = BackgroundFill::parse("dither(black, white)").unwrap();
+        let at = |x, y| checker.color_at(x, y, 4, 4, ColorDepth::TrueColor).unwrap();
+        assert_ne!(at(0, 0), at(1, 0));
+        assert_eq!(at(0, 0), at(1, 1));
+    }
+
+    #[test]
+    fn quantizes_to_depth() {
+        let orange = Color::Rgb(250, 120, 10);
+        assert_eq!(ColorDepth::TrueColor.quantize(orange), orange);
+        assert_eq!(
+            ColorDepth::Indexed256.quantize(orange),
+            Color::Rgb(255, 135, 0)
+        );
+        assert_eq!(ColorDepth::Ansi16.quantize(orange), Color::Red);
+        assert_eq!(
+            ColorDepth::from_env(Some("truecolor"), None),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            ColorDepth::from_env(None, Some("xterm-256color")),
+            ColorDepth::Indexed256
+        );
+    }
+
+    #[test]
+    fn paint_background_keeps_highlighted_cells() {
+        let mut styles = Styles::new();
+        styles.background = Some("stripes(red, blue)".into());
+        let base = CellStyle::from_styles(&styles);
+        let highlight = CellStyle {
+            bg: Some(Color::Green),
+            ..base
+        };
+        let region = Region::new(3, 5, 2, 2);
+        let mut strips: Vec<Strip> = (0..2)
+            .map(|row| {
+                let mut strip = Strip::new(5 + row, 3);
+                strip.push_str("a", base);
+                strip.push_str("b", highlight);
+                strip
+            })
+            .collect();
+        paint_background(&mut strips, region, &styles);
+        assert_eq!(strips[0].cells[0].style.bg, Some(Color::Red));
+        assert_eq!(strips[1].cells[0].style.bg, Some(Color::Blue));
+        assert_eq!(strips[1].cells[1].style.bg, Some(Color::Green));
+    }
+}
@@ -0,0 +1,385 @@
+//! Glyph policy: keeping every cell one column wide on any terminal.
+//!
+//! Layout and rendering count one cell per `char`, but terminals do not all
+//! agree: emoji and CJK characters take two columns, East Asian "ambiguous"
+//! characters (`…`, `±`, Greek, many Nerd Font icons) take one or two
+//! depending on the terminal and locale, and variation selectors, joiners,
+//! and combining marks take none. Any of these in a table row shifts every
+//! column after it.
+//!
+//! A [`GlyphPolicy`] rewrites such characters as strips are placed in the
+//! compositor: wide and ambiguous characters can be replaced by a fallback
+//! glyph, zero-width characters can be dropped (leaving their cell blank, so
+//! the columns after them stay put), and individual characters can be mapped
+//! to replacements. The default policy changes nothing; set one per app with
+//! [`App::set_glyph_policy`](crate::app::App::set_glyph_policy):
+//!
+//! ```ignore
+//! let config = AppConfig::default().with_glyph_policy(
+//!     GlyphPolicy::safe().with_replacement('✓', 'v'),
+//! );
+//! ```
+//!
+//! Widths come from a compact table of the common ranges rather than the full
+//! Unicode width data, which is enough to catch what misaligns in practice.
+
+use std::collections::HashMap;
+
+// ---------------------------------------------------------------------------
+// GlyphWidth
+// ---------------------------------------------------------------------------
+
+/// How many columns a terminal may draw a character in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GlyphWidth {
+    /// No columns: variation selectors, joiners, combining marks.
+    Zero,
+    /// One column everywhere.
+    Narrow,
+    /// One or two columns, depending on the terminal.
+    Ambiguous,
+    /// Two columns: emoji, CJK, fullwidth forms.
+    Wide,
+}
+
+/// Characters that take no columns.
+const ZERO: &[(u32, u32)] = &[
+    (0x0300, 0x036F),
+    (0x0483, 0x0489),
+    (0x0591, 0x05BD),
+    (0x0610, 0x061A),
+    (0x064B, 0x065F),
+    (0x1AB0, 0x1AFF),
+    (0x1DC0, 0x1DFF),
+    (0x200B, 0x200F),
+    (0x202A, 0x202E),
+    (0x2060, 0x2064),
+    (0x20D0, 0x20FF),
+    (0xFE00, 0xFE0F),
+    (0xFE20, 0xFE2F),
+    (0xFEFF, 0xFEFF),
+    (0x1F3FB, 0x1F3FF),
+    (0xE0000, 0xE007F),
+    (0xE0100, 0xE01EF),
+];
+
+/// Characters that take two columns.
+const WIDE: &[(u32, u32)] = &[
+    (0x1100, 0x115F),
+    (0x231A, 0x231B),
+    (0x2329, 0x232A),
+    (0x23E9, 0x23EC),
+    (0x23F0, 0x23F0),
+    (0x23F3, 0x23F3),
+    (0x25FD, 0x25FE),
+    (0x2614, 0x2615),
+    (0x2648, 0x2653),
+    (0x267F, 0x267F),
+    (0x2693, 0x2693),
+    (0x26A1, 0x26A1),
+    (0x26AA, 0x26AB),
+    (0x26BD, 0x26BE),
+    (0x26C4, 0x26C5),
+    (0x26CE, 0x26CE),
+    (0x26D4, 0x26D4),
+    (0x26EA, 0x26EA),
+    (0x26F2, 0x26F3),
+    (0x26F5, 0x26F5),
+    (0x26FA, 0x26FA),
+    (0x26FD, 0x26FD),
+    (0x2705, 0x2705),
+    (0x270A, 0x270B),
+    (0x2728, 0x2728),
+    (0x274C, 0x274C),
+    (0x274E, 0x274E),
+    (0x2753, 0x2755),
+    (0x2757, 0x2757),
+    (0x2795, 0x2797),
+    (0x27B0, 0x27B0),
+    (0x27BF, 0x27BF),
+    (0x2B1B, 0x2B1C),
+    (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55),
+    (0x2E80, 0x303E),
+    (0x3041, 0x33FF),
+    (0x3400, 0x4DBF),
+    (0x4E00, 0x9FFF),
+    (0xA000, 0xA4CF),
+    (0xA960, 0xA97F),
+    (0xAC00, 0xD7A3),
+    (0xF900, 0xFAFF),
+    (0xFE10, 0xFE19),
+    (0xFE30, 0xFE6F),
+    (0xFF00, 0xFF60),
+    (0xFFE0, 0xFFE6),
+    (0x16FE0, 0x18CFF),
+    (0x1B000, 0x1B2FF),
+    (0x1F004, 0x1F004),
+    (0x1F0CF, 0x1F0CF),
+    (0x1F18E, 0x1F18E),
+    (0x1F191, 0x1F19A),
+    (0x1F200, 0x1F251),
+    (0x1F300, 0x1F64F),
+    (0x1F680, 0x1F6FF),
+    (0x1F7E0, 0x1F7EB),
+    (0x1F90C, 0x1F9FF),
+    (0x1FA70, 0x1FAFF),
+    (0x20000, 0x3FFFD),
+];
+
+/// Characters whose width depends on the terminal.
+const AMBIGUOUS: &[(u32, u32)] = &[
+    (0x00A1, 0x00A1),
+    (0x00A4, 0x00A4),
+    (0x00A7, 0x00A8),
+    (0x00AA, 0x00AA),
+    (0x00AD, 0x00AE),
+    (0x00B0, 0x00B4),
+    (0x00B6, 0x00BA),
+    (0x00BC, 0x00BF),
+    (0x00C6, 0x00C6),
+    (0x00D0, 0x00D0),
+    (0x00D7, 0x00D8),
+    (0x00DE, 0x00E1),
+    (0x00E6, 0x00E6),
+    (0x00E8, 0x00EA),
+    (0x00EC, 0x00ED),
+    (0x00F0, 0x00F0),
+    (0x00F2, 0x00F3),
+    (0x00F7, 0x00FA),
+    (0x00FC, 0x00FC),
+    (0x00FE, 0x00FE),
+    (0x0391, 0x03A9),
+    (0x03B1, 0x03C9),
+    (0x0401, 0x0401),
+    (0x0410, 0x044F),
+    (0x0451, 0x0451),
+    (0x2010, 0x2010),
+    (0x2013, 0x2016),
+    (0x2018, 0x2019),
+    (0x201C, 0x201D),
+    (0x2020, 0x2022),
+    (0x2024, 0x2027),
+    (0x2030, 0x2030),
+    (0x2032, 0x2033),
+    (0x2035, 0x2035),
+    (0x203B, 0x203B),
+    (0x203E, 0x203E),
+    (0x20AC, 0x20AC),
+    (0x2103, 0x2103),
+    (0x2109, 0x2109),
+    (0x2116, 0x2116),
+    (0x2121, 0x2122),
+    (0x2160, 0x216B),
+    (0x2170, 0x2179),
+    (0x2190, 0x2199),
+    (0x21D2, 0x21D2),
+    (0x21D4, 0x21D4),
+    (0x2200, 0x2200),
+    (0x2202, 0x2203),
+    (0x2207, 0x2208),
+    (0x220B, 0x220B),
+    (0x220F, 0x220F),
+    (0x2211, 0x2211),
+    (0x221A, 0x221A),
+    (0x221D, 0x2220),
+    (0x2227, 0x222C),
+    (0x2234, 0x2237),
+    (0x2248, 0x2248),
+    (0x2260, 0x2261),
+    (0x2264, 0x2267),
+    (0x2282, 0x2283),
+    (0x2460, 0x24E9),
+    (0x24EB, 0x254B),
+    (0x2550, 0x2573),
+    (0x2580, 0x258F),
+    (0x2592, 0x2595),
+    (0x25A0, 0x25A1),
+    (0x25B2, 0x25B3),
+    (0x25B6, 0x25B7),
+    (0x25BC, 0x25BD),
+    (0x25C0, 0x25C1),
+    (0x25C6, 0x25C8),
+    (0x25CB, 0x25CB),
+    (0x25CE, 0x25D1),
+    (0x2605, 0x2606),
+    (0x260E, 0x260F),
+    (0x261C, 0x261C),
+    (0x261E, 0x261E),
+    (0x2640, 0x2640),
+    (0x2642, 0x2642),
+    (0x2660, 0x2661),
+    (0x2663, 0x2665),
+    (0x2667, 0x266A),
+    (0x266C, 0x266D),
+    (0x266F, 0x266F),
+    (0x273D, 0x273D),
+    (0x2776, 0x277F),
+    (0xE000, 0xF8FF),
+    (0xFFFD, 0xFFFD),
+    (0xF0000, 0x10FFFD),
+];
+
+fn in_table(table: &[(u32, u32)], ch: char) -> bool {
+    let c = ch as u32;
+    table
+        .binary_search_by(|&(start, end)| {
+            if end < c {
+                std::cmp::Ordering::Less
+            } else if start > c {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// How many columns a terminal may draw `ch` in.
+pub fn glyph_width(ch: char) -> GlyphWidth {
+    if ch.is_ascii() {
+        GlyphWidth::Narrow
+    } else if in_table(ZERO, ch) {
+        GlyphWidth::Zero
+    } else if in_table(WIDE, ch) {
+        GlyphWidth::Wide
+    } else if in_table(AMBIGUOUS, ch) {
+        GlyphWidth::Ambiguous
+    } else {
+        GlyphWidth::Narrow
+    }
+}
+
+// ---------------------------------------------------------------------------
+// GlyphPolicy
+// ---------------------------------------------------------------------------
+
+/// How characters that may not take exactly one column are drawn.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GlyphPolicy {
+    /// Drawn in place of wide characters; `None` keeps them.
+    pub wide_fallback: Option<char>,
+    /// Drawn in place of ambiguous-width characters; `None` keeps them.
+    pub ambiguous_fallback: Option<char>,
+    /// Drop zero-width characters such as variation selectors, leaving
+    /// their cell blank.
+    pub strip_zero_width: bool,
+    /// Per-character replacements, applied before the rules above.
+    replacements: HashMap<char, char>,
+}
+
+impl GlyphPolicy {
+    /// A policy that draws every character as written.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A policy for terminals that misdraw emoji: wide characters become
+    /// `?` and zero-width characters are dropped. Ambiguous characters, which
+    /// include box drawing, are kept.
+    pub fn safe() -> Self {
+        Self::new()
+            .with_wide_fallback('?')
+            .with_strip_zero_width(true)
+    }
+
+    /// Draw `fallback` in place of wide characters (builder pattern).
+    pub fn with_wide_fallback(mut self, fallback: char) -> Self {
+        self.wide_fallback = Some(fallback);
+        self
+    }
+
+    /// Draw `fallback` in place of ambiguous-width characters (builder
+    /// pattern).
+    pub fn with_ambiguous_fallback(mut self, fallback: char) -> Self {
+        self.ambiguous_fallback = Some(fallback);
+        self
+    }
+
+    /// Set whether zero-width characters are dropped (builder pattern).
+    pub fn with_strip_zero_width(mut self, strip: bool) -> Self {
+        self.strip_zero_width = strip;
+        self
+    }
+
+    /// Always draw `replacement` in place of `ch` (builder pattern).
+    pub fn with_replacement(mut self, ch: char, replacement: char) -> Self {
+        self.replacements.insert(ch, replacement);
+        self
+    }
+
+    /// Whether the policy draws every character as written.
+    pub fn is_passthrough(&self) -> bool {
+        self.wide_fallback.is_none()
+            && self.ambiguous_fallback.is_none()
+            && !self.strip_zero_width
+            && self.replacements.is_empty()
+    }
+
+    /// The character to draw for `ch`.
+    pub fn apply(&self, ch: char) -> char {
+        if let Some(&replacement) = self.replacements.get(&ch) {
+            return replacement;
+        }
+        match glyph_width(ch) {
+            GlyphWidth::Narrow => ch,
+            GlyphWidth::Zero if self.strip_zero_width => ' ',
+            GlyphWidth::Zero => ch,
+            GlyphWidth::Wide => self.wide_fallback.unwrap_or(ch),
+            GlyphWidth::Ambiguous => self.ambiguous_fallback.unwrap_or(ch),
+        }
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(table: &[(u32, u32)]) -> bool {
+        table
+            .windows(2)
+            .all(|pair| pair[0].0 <= pair[0].1 && pair[0].1 < pair[1].0)
+    }
+
+    #[test]
+    fn classifies_common_glyphs() {
+        assert!(sorted(ZERO) && sorted(WIDE) && sorted(AMBIGUOUS));
+        assert_eq!(glyph_width('a'), GlyphWidth::Narrow);
+        assert_eq!(glyph_width('é'), GlyphWidth::Ambiguous);
+        assert_eq!(glyph_width('ñ'), GlyphWidth::Narrow);
+        assert_eq!(glyph_width('…'), GlyphWidth::Ambiguous);
+        assert_eq!(glyph_width('─'), GlyphWidth::Ambiguous);
+        assert_eq!(glyph_width('😀'), GlyphWidth::Wide);
+        assert_eq!(glyph_width('✅'), GlyphWidth::Wide);
+        assert_eq!(glyph_width('漢'), GlyphWidth::Wide);
+        assert_eq!(glyph_width('\u{FE0F}'), GlyphWidth::Zero);
+        assert_eq!(glyph_width('\u{200D}'), GlyphWidth::Zero);
+        assert_eq!(glyph_width('\u{1F3FD}'), GlyphWidth::Zero);
+    }
+
+    #[test]
+    fn default_policy_passes_everything_through() {
+        let policy = GlyphPolicy::default();
+        assert!(policy.is_passthrough());
+        for ch in ['a', '😀', '\u{FE0F}', '…'] {
+            assert_eq!(policy.apply(ch), ch);
+        }
+    }
+
+    #[test]
+    fn fallbacks_and_replacements_apply() {
+        let policy = GlyphPolicy::safe()
+            .with_ambiguous_fallback('.')
+            .with_replacement('✅', 'v');
+        assert!(!policy.is_passthrough());
+        let drawn: String = "ok ✅ 😀\u{FE0F} … x"
+            .chars()
+            .map(|c| policy.apply(c))
+            .collect();
+        assert_eq!(drawn, "ok v ?  . x");
+    }
+}
@@ -0,0 +1,147 @@
+//! Hyperlinks: interned URLs and OSC 8 escape sequences.
+//!
+//! Cells carry a [`LinkId`] rather than the URL itself so [`CellStyle`]
+//! stays `Copy` and small; URLs are interned process-wide on first use, up to
+//! 65 535 distinct URLs, after which new ones are left unlinked. Terminals that
+//! understand OSC 8 render linked cells as real, clickable hyperlinks; for the
+//! rest the driver falls back to underlining them, and widgets like `Link`
+//! handle the click themselves.
+//!
+//! [`CellStyle`]: super::strip::CellStyle
+
+use std::collections::HashMap;
+use std::num::NonZeroU16;
+use std::sync::{Mutex, OnceLock};
+
+// ---------------------------------------------------------------------------
+// LinkId
+// ---------------------------------------------------------------------------
+
+/// An interned hyperlink target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LinkId(NonZeroU16);
+
+#[derive(Default)]
+struct Links {
+    urls: Vec<String>,
+    ids: HashMap<String, LinkId>,
+}
+
+fn links() -> &'static Mutex<Links> {
+    static LINKS: OnceLock<Mutex<Links>> = OnceLock::new();
+    LINKS.get_or_init(Mutex::default)
+}
+
+impl LinkId {
+    /// The id for `url`, interning it on first use. `None` once the table
+    /// is full.
+    pub fn intern(url: &str) -> Option<Self> {
+        let mut links = links().lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(&id) = links.ids.get(url) {
+            return Some(id);
+        }
+        let index = u16::try_from(links.urls.len() + 1).ok()?;
+        let id = LinkId(NonZeroU16::new(index)?);
+        links.urls.push(url.to_owned());
+        links.ids.insert(url.to_owned(), id);
+        Some(id)
+    }
+
+    /// The URL this id was interned from.
+    pub fn url(self) -> String {
+        let links = links().lock().unwrap_or_else(|e| e.into_inner());
+        links.urls[self.0.get() as usize - 1].clone()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// OSC 8
+// ---------------------------------------------------------------------------
+
+/// Ends the current hyperlink.
+pub const OSC8_CLOSE: &str = "\x1b]8;;\x1b\\";
+
+/// Starts a hyperlink to `url`. Control characters are stripped so a URL
+/// can't terminate the sequence early.
+pub fn osc8_open(url: &str) -> String {
+    let url: String = url.chars().filter(|c| !c.is_control()).collect();
+    format!("\x1b]8;;{url}\x1b\\")
+}
+
+/// Whether the terminal described by `env` is known to support OSC 8
+/// hyperlinks. `env` looks up an environment variable, e.g.
+/// `|name| std::env::var(name).ok()`.
+///
+/// `FORCE_HYPERLINK=1` / `FORCE_HYPERLINK=0` override the detection.
+pub fn hyperlinks_supported(env: impl Fn(&str) -> Option<String>) -> bool {
+    if let Some(force) = env("FORCE_HYPERLINK") {
+        return force != "0";
+    }
+    if env("KITTY_WINDOW_ID").is_some() || env("WT_SESSION").is_some() {
+        return true;
+    }
+    if let Some(program) = env("TERM_PROGRAM") {
+        if matches!(
+            program.as_str(),
+            "iTerm.app" | "WezTerm" | "vscode" | "ghostty"
+        ) {
+            return true;
+        }
+    }
+    if let Some(vte) = env("VTE_VERSION").and_then(|v| v.parse::<u32>().ok()) {
+        // GNOME Terminal and friends, since VTE 0.50.
+        return vte >= 5000;
+    }
+    env("TERM").is_some_and(|term| {
+        ["kitty", "foot", "alacritty", "wezterm"]
+            .iter()
+            .any(|name| term.contains(name))
+    })
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env<'a>(vars: &'a [(&str, &str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        }
+    }
+
+    #[test]
+    fn interning_is_stable() {
+        let a = LinkId::intern("https://example.com/a").unwrap();
+        let b = LinkId::intern("https://example.com/b").unwrap();
+        assert_ne!(a, b);
+        assert_eq!(LinkId::intern("https://example.com/a"), Some(a));
+        assert_eq!(b.url(), "https://example.com/b");
+    }
+
+    #[test]
+    fn osc8_sequences() {
+        assert_eq!(osc8_open("https://x.io"), "\x1b]8;;https://x.io\x1b\\");
+        assert_eq!(
+            osc8_open("https://x.io/\x1b\x07"),
+            "\x1b]8;;https://x.io/\x1b\\"
+        );
+    }
+
+    #[test]
+    fn detects_supporting_terminals() {
+        assert!(hyperlinks_supported(env(&[("TERM_PROGRAM", "WezTerm")])));
+        assert!(hyperlinks_supported(env(&[("VTE_VERSION", "6003")])));
+        assert!(!hyperlinks_supported(env(&[("VTE_VERSION", "4601")])));
+        assert!(!hyperlinks_supported(env(&[("TERM", "xterm-256color")])));
+        assert!(!hyperlinks_supported(env(&[
+            ("FORCE_HYPERLINK", "0"),
+            ("KITTY_WINDOW_ID", "1")
+        ])));
+    }
+}
@@ -1,9 +1,33 @@
-//! Rendering pipeline: compositor, strip assembly, terminal driver.
+//! Rendering pipeline: compositor, strip assembly, background fills, text
+//! fitting, hyperlinks, terminal driver, frame stats and budgets, animation,
+//! easing, snapshot transitions, blink and pulse text effects, and the glyph
+//! policy for characters that may not take one column.
 
 pub mod compositor;
 pub mod strip;
+pub mod fill;
+pub mod text;
+pub mod link;
 pub mod driver;
+pub mod stats;
+pub mod budget;
+pub mod animation;
+pub mod easing;
+pub mod transition;
+pub mod effects;
+pub mod glyph;
 
-pub use strip::{Strip, StyledCell, CellStyle};
-pub use compositor::{Compositor, CellUpdate};
-pub use driver::Driver;
+pub use strip::{Attrs, CellStyle, Color, ContrastPair, ParseColorError, Strip, StyledCell};
+pub use fill::{paint_background, BackgroundFill, ColorDepth, FillDirection};
+pub use compositor::{Compositor, CellUpdate, ScrollHint};
+pub use driver::{Driver, FlushPolicy};
+pub use link::LinkId;
+pub use stats::{FrameStats, FrameTimings};
+pub use budget::{BudgetMonitor, FrameBudget, SlowWarning, WorkKind};
+pub use animation::{
+    animate, AnimationBuilder, AnimationError, Animator, PropertyAnimation, StyleTransition,
+};
+pub use easing::Easing;
+pub use transition::{Snapshot, SnapshotTransition, TransitionEffect};
+pub use effects::{EffectPhase, TextEffects};
+pub use glyph::{glyph_width, GlyphPolicy, GlyphWidth};
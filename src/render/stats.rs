@@ -0,0 +1,136 @@
+//! Frame statistics: per-phase timings, cells updated, rolling FPS.
+//!
+//! [`FrameStats`] is the counter set behind `App::stats`, which
+//! `App::render_frame` records once per frame it presents. It backs the
+//! performance HUD overlay and can also be read directly by apps that want to
+//! log or assert on rendering cost.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+// ---------------------------------------------------------------------------
+// FrameTimings
+// ---------------------------------------------------------------------------
+
+/// Time spent in each phase of a single frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FrameTimings {
+    /// Input handling and message dispatch.
+    pub event: Duration,
+    /// CSS cascade / style computation.
+    pub style: Duration,
+    /// Layout sync and computation.
+    pub layout: Duration,
+    /// Widget rendering and compositing.
+    pub render: Duration,
+    /// Diffing and writing to the terminal.
+    pub flush: Duration,
+}
+
+impl FrameTimings {
+    /// Sum of all phases.
+    pub fn total(&self) -> Duration {
+        self.event + self.style + self.layout + self.render + self.flush
+    }
+}
+
+// ---------------------------------------------------------------------------
+// FrameStats
+// ---------------------------------------------------------------------------
+
+/// Window over which frames are counted for the FPS figure.
+const FPS_WINDOW: Duration = Duration::from_secs(1);
+
+/// Rolling frame counters maintained by the app.
+#[derive(Debug, Clone, Default)]
+pub struct FrameStats {
+    /// Timings of the most recent frame.
+    pub last: FrameTimings,
+    /// Cells written to the terminal in the most recent frame.
+    pub cells_updated: usize,
+    /// Total frames recorded since startup.
+    pub frames: u64,
+    /// Timestamps of frames inside the FPS window.
+    recent: VecDeque<Instant>,
+}
+
+impl FrameStats {
+    /// Create empty stats.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a finished frame that completed at `at`.
+    pub fn record_frame(&mut self, timings: FrameTimings, cells_updated: usize, at: Instant) {
+        self.last = timings;
+        self.cells_updated = cells_updated;
+        self.frames += 1;
+        self.recent.push_back(at);
+        while let Some(&front) = self.recent.front() {
+            if at.duration_since(front) > FPS_WINDOW {
+                self.recent.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Frames recorded within the last second (up to the latest frame).
+    pub fn fps(&self) -> f32 {
+        self.recent.len() as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn timings_total() {
+        let t = FrameTimings {
+            event: ms(1),
+            style: ms(2),
+            layout: ms(3),
+            render: ms(4),
+            flush: ms(5),
+        };
+        assert_eq!(t.total(), ms(15));
+    }
+
+    #[test]
+    fn new_stats_are_empty() {
+        let stats = FrameStats::new();
+        assert_eq!(stats.frames, 0);
+        assert_eq!(stats.fps(), 0.0);
+        assert_eq!(stats.last, FrameTimings::default());
+    }
+
+    #[test]
+    fn record_frame_updates_last() {
+        let mut stats = FrameStats::new();
+        let timings = FrameTimings {
+            render: ms(3),
+            ..FrameTimings::default()
+        };
+        stats.record_frame(timings, 42, Instant::now());
+        assert_eq!(stats.frames, 1);
+        assert_eq!(stats.cells_updated, 42);
+        assert_eq!(stats.last.render, ms(3));
+    }
+
+    #[test]
+    fn fps_counts_frames_in_window() {
+        let mut stats = FrameStats::new();
+        let start = Instant::now();
+        for i in 0..30 {
+            stats.record_frame(FrameTimings::default(), 0, start + ms(i * 50));
+        }
+        // 30 frames over 1.45s: only those within the last second remain.
+        assert_eq!(stats.fps(), 21.0);
+        assert_eq!(stats.frames, 30);
+    }
+}
@@ -3,8 +3,327 @@
 //! A `Strip` is the fundamental rendering primitive in gilt-tui. It represents
 //! a single horizontal row of `StyledCell`s that can be placed into the compositor's
 //! screen buffer. Widgets produce `Vec<Strip>` from their `render()` method.
+//!
+//! `StyledCell` and `CellStyle` are small `Copy` values (colors packed into
+//! [`Color`], attributes into [`Attrs`] bitflags), so cell buffers are a single
+//! flat allocation and cloning or diffing them is a plain memory compare.
+//!
+//! Code written against the older field-per-flag `CellStyle` converts with
+//! `From`: boolean [`TextStyleFlags`] become [`Attrs`], and `Attrs` or a
+//! [`Color`] become a `CellStyle`. The old `style.bold` fields are now
+//! getters such as [`CellStyle::bold`], and string colors go through
+//! [`CellStyle::with_fg`] / [`CellStyle::with_bg`].
+
+use std::fmt;
+use std::ops::{BitOr, BitOrAssign};
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::css::styles::{Styles, TextStyleFlags};
+use crate::render::fill::background_color;
+use crate::render::link::LinkId;
+
+// ---------------------------------------------------------------------------
+// Color
+// ---------------------------------------------------------------------------
+
+/// A terminal color: one of the 16 named ANSI colors or 24-bit RGB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    DarkRed,
+    DarkGreen,
+    DarkYellow,
+    DarkBlue,
+    DarkMagenta,
+    DarkCyan,
+    DarkGrey,
+    Grey,
+    Rgb(u8, u8, u8),
+}
+
+/// Error returned when a color string cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid color: {0:?}")]
+pub struct ParseColorError(pub String);
+
+impl Color {
+    /// Parse a color string.
+    ///
+    /// Supports `#rrggbb` and `#rgb` hex values and the named colors `black`,
+    /// `red`, `green`, `yellow`, `blue`, `magenta`, `cyan`, `white`, their
+    /// `dark_*` variants, and `grey`/`gray` (case-insensitive). Returns `None`
+    /// if the string is not a recognised color.
+    pub fn parse(s: &str) -> Option<Color> {
+        let s = s.trim();
+        if let Some(hex) = s.strip_prefix('#') {
+            return parse_hex(hex);
+        }
+        match s.to_ascii_lowercase().as_str() {
+            "black" => Some(Color::Black),
+            "red" => Some(Color::Red),
+            "green" => Some(Color::Green),
+            "yellow" => Some(Color::Yellow),
+            "blue" => Some(Color::Blue),
+            "magenta" => Some(Color::Magenta),
+            "cyan" => Some(Color::Cyan),
+            "white" => Some(Color::White),
+            "dark_red" | "darkred" => Some(Color::DarkRed),
+            "dark_green" | "darkgreen" => Some(Color::DarkGreen),
+            "dark_yellow" | "darkyellow" => Some(Color::DarkYellow),
+            "dark_blue" | "darkblue" => Some(Color::DarkBlue),
+            "dark_magenta" | "darkmagenta" => Some(Color::DarkMagenta),
+            "dark_cyan" | "darkcyan" => Some(Color::DarkCyan),
+            "dark_grey" | "dark_gray" | "darkgrey" | "darkgray" => Some(Color::DarkGrey),
+            "grey" | "gray" => Some(Color::Grey),
+            _ => None,
+        }
+    }
+
+    /// The RGB components, if this is an RGB color.
+    pub fn rgb(self) -> Option<(u8, u8, u8)> {
+        match self {
+            Color::Rgb(r, g, b) => Some((r, g, b)),
+            _ => None,
+        }
+    }
 
-use crate::css::styles::Styles;
+    /// The RGB components, using the standard VGA palette for named colors.
+    ///
+    /// Terminals may remap named colors, so this is only an approximation
+    /// for them.
+    pub fn approx_rgb(self) -> (u8, u8, u8) {
+        match self {
+            Color::Rgb(r, g, b) => (r, g, b),
+            Color::Black => (0, 0, 0),
+            Color::DarkRed => (128, 0, 0),
+            Color::DarkGreen => (0, 128, 0),
+            Color::DarkYellow => (128, 128, 0),
+            Color::DarkBlue => (0, 0, 128),
+            Color::DarkMagenta => (128, 0, 128),
+            Color::DarkCyan => (0, 128, 128),
+            Color::Grey => (192, 192, 192),
+            Color::DarkGrey => (128, 128, 128),
+            Color::Red => (255, 0, 0),
+            Color::Green => (0, 255, 0),
+            Color::Yellow => (255, 255, 0),
+            Color::Blue => (0, 0, 255),
+            Color::Magenta => (255, 0, 255),
+            Color::Cyan => (0, 255, 255),
+            Color::White => (255, 255, 255),
+        }
+    }
+
+    /// WCAG relative luminance in `0.0..=1.0`.
+    pub fn luminance(self) -> f32 {
+        let channel = |c: u8| {
+            let c = c as f32 / 255.0;
+            if c <= 0.040_45 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        let (r, g, b) = self.approx_rgb();
+        0.2126 * channel(r) + 0.7152 * channel(g) + 0.0722 * channel(b)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// ContrastPair
+// ---------------------------------------------------------------------------
+
+/// The foreground colors `color: auto` chooses between.
+///
+/// Themes replace the default black/white pair with their own light and
+/// dark text colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContrastPair {
+    /// Text color for dark backgrounds.
+    pub light: Color,
+    /// Text color for light backgrounds.
+    pub dark: Color,
+}
+
+impl ContrastPair {
+    /// The CSS keyword that requests an automatic foreground.
+    pub const AUTO: &'static str = "auto";
+
+    /// Create a pair from light and dark text colors.
+    pub fn new(light: Color, dark: Color) -> Self {
+        Self { light, dark }
+    }
+
+    /// The member of the pair that contrasts more with `background`.
+    pub fn pick(&self, background: Color) -> Color {
+        let bg = background.luminance();
+        let ratio = |fg: Color| {
+            let (hi, lo) = (fg.luminance().max(bg), fg.luminance().min(bg));
+            (hi + 0.05) / (lo + 0.05)
+        };
+        if ratio(self.dark) >= ratio(self.light) {
+            self.dark
+        } else {
+            self.light
+        }
+    }
+
+    /// Replace `color: auto` in resolved styles with the pair member that
+    /// contrasts with the background. Without a background the color is
+    /// unset, leaving the terminal's default foreground.
+    pub fn resolve(&self, styles: &mut Styles) {
+        if styles.color.as_deref() != Some(Self::AUTO) {
+            return;
+        }
+        styles.color = styles
+            .background
+            .as_deref()
+            .and_then(background_color)
+            .map(|bg| self.pick(bg).to_string());
+    }
+}
+
+impl Default for ContrastPair {
+    fn default() -> Self {
+        Self::new(Color::White, Color::Black)
+    }
+}
+
+/// Parse a hex color (without the leading `#`) in `rrggbb` or `rgb` form.
+fn parse_hex(hex: &str) -> Option<Color> {
+    let digit = |i: usize, len: usize| {
+        hex.get(i..i + len)
+            .and_then(|d| u8::from_str_radix(d, 16).ok())
+    };
+    match hex.len() {
+        6 => Some(Color::Rgb(digit(0, 2)?, digit(2, 2)?, digit(4, 2)?)),
+        3 => {
+            // Expand: 0xA -> 0xAA
+            let (r, g, b) = (digit(0, 1)?, digit(1, 1)?, digit(2, 1)?);
+            Some(Color::Rgb(r * 17, g * 17, b * 17))
+        }
+        _ => None,
+    }
+}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Color::parse(s).ok_or_else(|| ParseColorError(s.to_owned()))
+    }
+}
+
+impl From<(u8, u8, u8)> for Color {
+    fn from((r, g, b): (u8, u8, u8)) -> Self {
+        Color::Rgb(r, g, b)
+    }
+}
+
+/// Formats as the string [`Color::parse`] accepts: `#rrggbb` or a name.
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Color::Rgb(r, g, b) => return write!(f, "#{r:02x}{g:02x}{b:02x}"),
+            Color::Black => "black",
+            Color::Red => "red",
+            Color::Green => "green",
+            Color::Yellow => "yellow",
+            Color::Blue => "blue",
+            Color::Magenta => "magenta",
+            Color::Cyan => "cyan",
+            Color::White => "white",
+            Color::DarkRed => "dark_red",
+            Color::DarkGreen => "dark_green",
+            Color::DarkYellow => "dark_yellow",
+            Color::DarkBlue => "dark_blue",
+            Color::DarkMagenta => "dark_magenta",
+            Color::DarkCyan => "dark_cyan",
+            Color::DarkGrey => "dark_grey",
+            Color::Grey => "grey",
+        };
+        f.write_str(name)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Attrs
+// ---------------------------------------------------------------------------
+
+/// Text attribute bitflags for a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Attrs(pub u8);
+
+impl Attrs {
+    pub const NONE: Attrs = Attrs(0);
+    pub const BOLD: Attrs = Attrs(1);
+    pub const DIM: Attrs = Attrs(2);
+    pub const ITALIC: Attrs = Attrs(4);
+    pub const UNDERLINE: Attrs = Attrs(8);
+    pub const STRIKETHROUGH: Attrs = Attrs(16);
+    pub const REVERSE: Attrs = Attrs(32);
+    /// Software blink; resolved by the compositor, never sent to the
+    /// terminal (see [`effects`](crate::render::effects)).
+    pub const BLINK: Attrs = Attrs(64);
+    /// Software pulse between bold and dim; resolved like [`Attrs::BLINK`].
+    pub const PULSE: Attrs = Attrs(128);
+
+    /// Check whether `self` contains all the bits in `other`.
+    pub fn contains(self, other: Attrs) -> bool {
+        (self.0 & other.0) == other.0
+    }
+
+    /// Check whether no attribute bits are set.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Set or clear the bits in `other`.
+    pub fn set(&mut self, other: Attrs, on: bool) {
+        if on {
+            self.0 |= other.0;
+        } else {
+            self.0 &= !other.0;
+        }
+    }
+}
+
+impl BitOr for Attrs {
+    type Output = Attrs;
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Attrs(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Attrs {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl From<TextStyleFlags> for Attrs {
+    /// Set the flags that are `Some(true)`.
+    fn from(flags: TextStyleFlags) -> Self {
+        let mut attrs = Attrs::NONE;
+        attrs.set(Attrs::BOLD, flags.bold.unwrap_or(false));
+        attrs.set(Attrs::DIM, flags.dim.unwrap_or(false));
+        attrs.set(Attrs::ITALIC, flags.italic.unwrap_or(false));
+        attrs.set(Attrs::UNDERLINE, flags.underline.unwrap_or(false));
+        attrs.set(Attrs::STRIKETHROUGH, flags.strikethrough.unwrap_or(false));
+        attrs.set(Attrs::REVERSE, flags.reverse.unwrap_or(false));
+        attrs.set(Attrs::BLINK, flags.blink.unwrap_or(false));
+        attrs.set(Attrs::PULSE, flags.pulse.unwrap_or(false));
+        attrs
+    }
+}
 
 // ---------------------------------------------------------------------------
 // CellStyle
@@ -13,18 +332,16 @@ use crate::css::styles::Styles;
 /// Visual style for a single terminal cell.
 ///
 /// This is a self-contained style type (no gilt dependency) used throughout
-/// the rendering pipeline. Colors are stored as optional strings that can be
-/// parsed as named colors or `#rrggbb` hex values.
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+/// the rendering pipeline. It is `Copy` and a handful of bytes wide; string
+/// colors from CSS are parsed once on conversion, and hyperlink targets are
+/// interned as a [`LinkId`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct CellStyle {
-    pub fg: Option<String>,
-    pub bg: Option<String>,
-    pub bold: bool,
-    pub dim: bool,
-    pub italic: bool,
-    pub underline: bool,
-    pub strikethrough: bool,
-    pub reverse: bool,
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub attrs: Attrs,
+    /// Hyperlink target of the cell, drawn with OSC 8 where supported.
+    pub link: Option<LinkId>,
 }
 
 impl CellStyle {
@@ -33,19 +350,102 @@ impl CellStyle {
         Self::default()
     }
 
+    /// Builder: set the foreground color from a color string. Unparseable
+    /// strings leave the color unset.
+    pub fn with_fg(mut self, color: &str) -> Self {
+        self.fg = Color::parse(color);
+        self
+    }
+
+    /// Builder: set the background color from a color string. Unparseable
+    /// strings leave the color unset.
+    pub fn with_bg(mut self, color: &str) -> Self {
+        self.bg = Color::parse(color);
+        self
+    }
+
+    /// Builder: add attribute flags.
+    pub fn with_attrs(mut self, attrs: Attrs) -> Self {
+        self.attrs |= attrs;
+        self
+    }
+
+    /// Builder: make the cell a hyperlink to `url`. Left unlinked if the
+    /// link table is full.
+    pub fn with_link(mut self, url: &str) -> Self {
+        self.link = LinkId::intern(url);
+        self
+    }
+
+    /// Whether the cell is bold.
+    pub fn bold(&self) -> bool {
+        self.attrs.contains(Attrs::BOLD)
+    }
+
+    /// Whether the cell is dim (faint).
+    pub fn dim(&self) -> bool {
+        self.attrs.contains(Attrs::DIM)
+    }
+
+    /// Whether the cell is italic.
+    pub fn italic(&self) -> bool {
+        self.attrs.contains(Attrs::ITALIC)
+    }
+
+    /// Whether the cell is underlined.
+    pub fn underline(&self) -> bool {
+        self.attrs.contains(Attrs::UNDERLINE)
+    }
+
+    /// Whether the cell is struck through.
+    pub fn strikethrough(&self) -> bool {
+        self.attrs.contains(Attrs::STRIKETHROUGH)
+    }
+
+    /// Whether the cell is drawn with foreground and background swapped.
+    pub fn reverse(&self) -> bool {
+        self.attrs.contains(Attrs::REVERSE)
+    }
+
     /// Convert CSS `Styles` into a `CellStyle`, extracting color, background,
-    /// and text_style flags.
+    /// and text_style flags. A patterned background contributes its base
+    /// color, and an unresolved `color: auto` contrasts with the background
+    /// using the default [`ContrastPair`].
     pub fn from_styles(styles: &Styles) -> Self {
-        let flags = styles.text_style.unwrap_or_default();
+        let attrs = Attrs::from(styles.text_style.unwrap_or_default());
+        let bg = styles.background.as_deref().and_then(background_color);
+        let fg = match styles.color.as_deref() {
+            Some(ContrastPair::AUTO) => bg.map(|bg| ContrastPair::default().pick(bg)),
+            color => color.and_then(Color::parse),
+        };
+        CellStyle {
+            fg,
+            bg,
+            attrs,
+            link: None,
+        }
+    }
+}
+
+impl From<&Styles> for CellStyle {
+    fn from(styles: &Styles) -> Self {
+        Self::from_styles(styles)
+    }
+}
+
+impl From<Attrs> for CellStyle {
+    /// A style with only `attrs` set.
+    fn from(attrs: Attrs) -> Self {
+        CellStyle::new().with_attrs(attrs)
+    }
+}
+
+impl From<Color> for CellStyle {
+    /// A style with only the foreground color set.
+    fn from(fg: Color) -> Self {
         CellStyle {
-            fg: styles.color.clone(),
-            bg: styles.background.clone(),
-            bold: flags.bold.unwrap_or(false),
-            dim: flags.dim.unwrap_or(false),
-            italic: flags.italic.unwrap_or(false),
-            underline: flags.underline.unwrap_or(false),
-            strikethrough: flags.strikethrough.unwrap_or(false),
-            reverse: flags.reverse.unwrap_or(false),
+            fg: Some(fg),
+            ..CellStyle::default()
         }
     }
 }
@@ -55,7 +455,7 @@ impl CellStyle {
 // ---------------------------------------------------------------------------
 
 /// A single terminal cell: one character with associated style.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct StyledCell {
     pub ch: char,
     pub style: CellStyle,
@@ -123,7 +523,7 @@ impl Strip {
     /// Push every character of `text` with the same style.
     pub fn push_str(&mut self, text: &str, style: CellStyle) {
         for ch in text.chars() {
-            self.cells.push(StyledCell::new(ch, style.clone()));
+            self.cells.push(StyledCell::new(ch, style));
         }
     }
 
@@ -145,7 +545,7 @@ impl Strip {
                 if result.cells.is_empty() {
                     result.x_offset = cell_x;
                 }
-                result.cells.push(cell.clone());
+                result.cells.push(*cell);
             }
         }
         result
@@ -166,7 +566,7 @@ impl Strip {
 
     /// The rightmost x position (exclusive) of this strip.
     pub fn right(&self) -> i32 {
-        self.x_offset + self.width()
+        self.x_offset.saturating_add(self.width())
     }
 }
 
@@ -177,19 +577,19 @@ impl Strip {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::css::styles::{Styles, TextStyleFlags};
+    use crate::css::styles::Styles;
 
     fn red_style() -> CellStyle {
         CellStyle {
-            fg: Some("red".into()),
+            fg: Some(Color::Red),
             ..CellStyle::default()
         }
     }
 
     fn blue_bg_bold() -> CellStyle {
         CellStyle {
-            bg: Some("blue".into()),
-            bold: true,
+            bg: Some(Color::Blue),
+            attrs: Attrs::BOLD,
             ..CellStyle::default()
         }
     }
@@ -203,12 +603,7 @@ mod tests {
         let s = CellStyle::default();
         assert!(s.fg.is_none());
         assert!(s.bg.is_none());
-        assert!(!s.bold);
-        assert!(!s.dim);
-        assert!(!s.italic);
-        assert!(!s.underline);
-        assert!(!s.strikethrough);
-        assert!(!s.reverse);
+        assert!(s.attrs.is_empty());
     }
 
     #[test]
@@ -229,8 +624,8 @@ mod tests {
         styles.color = Some("red".into());
         styles.background = Some("#ff00ff".into());
         let cs = CellStyle::from_styles(&styles);
-        assert_eq!(cs.fg, Some("red".into()));
-        assert_eq!(cs.bg, Some("#ff00ff".into()));
+        assert_eq!(cs.fg, Some(Color::Red));
+        assert_eq!(cs.bg, Some(Color::Rgb(255, 0, 255)));
     }
 
     #[test]
@@ -243,14 +638,72 @@ mod tests {
             underline: None,
             strikethrough: Some(true),
             reverse: None,
+            blink: Some(true),
+            pulse: None,
         });
         let cs = CellStyle::from_styles(&styles);
-        assert!(cs.bold);
-        assert!(cs.italic);
-        assert!(!cs.dim);
-        assert!(!cs.underline);
-        assert!(cs.strikethrough);
-        assert!(!cs.reverse);
+        assert!(cs.bold());
+        assert!(cs.italic());
+        assert!(!cs.dim());
+        assert!(!cs.underline());
+        assert!(cs.strikethrough());
+        assert!(!cs.reverse());
+        assert!(cs.attrs.contains(Attrs::BLINK));
+        assert!(!cs.attrs.contains(Attrs::PULSE));
+    }
+
+    #[test]
+    fn cell_style_converts_from_the_old_fields() {
+        let flags = TextStyleFlags {
+            bold: Some(true),
+            underline: Some(true),
+            reverse: Some(false),
+            ..TextStyleFlags::default()
+        };
+        let cs = CellStyle::from(Attrs::from(flags));
+        assert_eq!(cs.attrs, Attrs::BOLD | Attrs::UNDERLINE);
+        assert!(cs.fg.is_none());
+        let cs = CellStyle::from(Color::Red);
+        assert_eq!(cs.fg, Some(Color::Red));
+        assert!(cs.bg.is_none() && cs.attrs.is_empty());
+    }
+
+    #[test]
+    fn cell_style_is_compact() {
+        assert!(std::mem::size_of::<CellStyle>() <= 12);
+        assert!(std::mem::size_of::<StyledCell>() <= 16);
+    }
+
+    #[test]
+    fn cell_style_string_builders() {
+        let cs = CellStyle::new().with_fg("#abc").with_bg("nope");
+        assert_eq!(cs.fg, Some(Color::Rgb(0xaa, 0xbb, 0xcc)));
+        assert_eq!(cs.bg, None);
+        let cs = cs.with_attrs(Attrs::DIM | Attrs::REVERSE);
+        assert!(cs.dim() && cs.reverse() && !cs.bold());
+    }
+
+    // -----------------------------------------------------------------------
+    // Color / Attrs
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn color_round_trips_through_strings() {
+        for text in ["#ff8000", "red", "dark_grey"] {
+            let color: Color = text.parse().unwrap();
+            assert_eq!(color.to_string(), text);
+        }
+        assert_eq!(Color::parse("Gray"), Some(Color::Grey));
+        assert!("#12".parse::<Color>().is_err());
+    }
+
+    #[test]
+    fn attrs_set_and_clear() {
+        let mut attrs = Attrs::BOLD | Attrs::UNDERLINE;
+        attrs.set(Attrs::BOLD, false);
+        attrs.set(Attrs::ITALIC, true);
+        assert!(!attrs.contains(Attrs::BOLD));
+        assert!(attrs.contains(Attrs::UNDERLINE | Attrs::ITALIC));
     }
 
     // -----------------------------------------------------------------------
@@ -261,7 +714,7 @@ mod tests {
     fn styled_cell_new() {
         let cell = StyledCell::new('A', red_style());
         assert_eq!(cell.ch, 'A');
-        assert_eq!(cell.style.fg, Some("red".into()));
+        assert_eq!(cell.style.fg, Some(Color::Red));
     }
 
     #[test]
@@ -274,7 +727,7 @@ mod tests {
     #[test]
     fn styled_cell_blank_styled() {
         let style = blue_bg_bold();
-        let cell = StyledCell::blank_styled(style.clone());
+        let cell = StyledCell::blank_styled(style);
         assert_eq!(cell.ch, ' ');
         assert_eq!(cell.style, style);
     }
@@ -432,4 +885,48 @@ mod tests {
         s.fill(0, CellStyle::default());
         assert_eq!(s.width(), 0);
     }
+
+    // -----------------------------------------------------------------------
+    // ContrastPair
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn luminance_orders_colors() {
+        assert_eq!(Color::Black.luminance(), 0.0);
+        assert!((Color::White.luminance() - 1.0).abs() < 1e-4);
+        assert!(Color::Yellow.luminance() > Color::Blue.luminance());
+    }
+
+    #[test]
+    fn contrast_pair_picks_readable_color() {
+        let pair = ContrastPair::default();
+        assert_eq!(pair.pick(Color::Yellow), Color::Black);
+        assert_eq!(pair.pick(Color::DarkBlue), Color::White);
+        assert_eq!(pair.pick(Color::Rgb(240, 240, 240)), Color::Black);
+
+        let themed = ContrastPair::new(Color::Rgb(250, 250, 240), Color::Rgb(20, 20, 30));
+        assert_eq!(themed.pick(Color::Black), Color::Rgb(250, 250, 240));
+    }
+
+    #[test]
+    fn contrast_pair_resolves_auto() {
+        let mut styles = Styles::new();
+        styles.color = Some("auto".into());
+        styles.background = Some("white".into());
+        ContrastPair::default().resolve(&mut styles);
+        assert_eq!(styles.color.as_deref(), Some("black"));
+
+        let mut unbacked = Styles::new();
+        unbacked.color = Some("auto".into());
+        ContrastPair::default().resolve(&mut unbacked);
+        assert_eq!(unbacked.color, None);
+    }
+
+    #[test]
+    fn cell_style_from_auto_color() {
+        let mut styles = Styles::new();
+        styles.color = Some("auto".into());
+        styles.background = Some("#101020".into());
+        assert_eq!(CellStyle::from_styles(&styles).fg, Some(Color::White));
+    }
 }
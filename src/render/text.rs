@@ -0,0 +1,163 @@
+//! Text measurement and fitting shared by widgets.
+//!
+//! Widths are measured in cells the way [`Strip`](super::strip::Strip) lays
+//! text out, one cell per `char`. [`fit`] shortens a line to a width under a
+//! [`TextOverflow`] policy, and [`wrap_lines`] breaks text between words onto
+//! a limited number of rows, fitting whatever does not fit onto the last one.
+
+use crate::css::styles::TextOverflow;
+
+/// The ellipsis appended by [`TextOverflow::Ellipsis`] and
+/// [`TextOverflow::Shrink`].
+pub const ELLIPSIS: char = '…';
+
+/// The width of `text` in cells.
+pub fn text_width(text: &str) -> usize {
+    text.chars().count()
+}
+
+/// The first `width` cells of `text`.
+fn clip(text: &str, width: usize) -> String {
+    text.chars().take(width).collect()
+}
+
+/// `text` cut to `width - 1` cells plus an ellipsis.
+fn ellipsize(text: &str, width: usize) -> String {
+    if width == 0 {
+        return String::new();
+    }
+    let mut out = clip(text, width - 1);
+    out.push(ELLIPSIS);
+    out
+}
+
+/// Abbreviate words to their initial, last word first, until `text` fits.
+fn shrink(text: &str, width: usize) -> String {
+    let mut words: Vec<String> = text.split_whitespace().map(str::to_owned).collect();
+    for i in (0..words.len()).rev() {
+        if text_width(&words.join(" ")) <= width {
+            break;
+        }
+        if let Some(first) = words[i].chars().next() {
+            if text_width(&words[i]) > 2 {
+                words[i] = format!("{first}.");
+            }
+        }
+    }
+    let shrunk = words.join(" ");
+    if text_width(&shrunk) <= width {
+        shrunk
+    } else {
+        ellipsize(&shrunk, width)
+    }
+}
+
+/// Shorten `text` to at most `width` cells. Text that already fits is
+/// returned unchanged.
+pub fn fit(text: &str, width: usize, overflow: TextOverflow) -> String {
+    if text_width(text) <= width {
+        return text.to_owned();
+    }
+    match overflow {
+        TextOverflow::Clip => clip(text, width),
+        TextOverflow::Ellipsis => ellipsize(text, width),
+        TextOverflow::Shrink => shrink(text, width),
+    }
+}
+
+/// Break `text` between words into lines of at most `width` cells, splitting
+/// words longer than a line.
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    if width == 0 {
+        return Vec::new();
+    }
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        let needed = if line.is_empty() { 0 } else { 1 } + word.len();
+        if !line.is_empty() && text_width(&line) + needed > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        while word.len() > width {
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            lines.push(word.drain(..width).collect());
+        }
+        if !word.is_empty() {
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.extend(word);
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Wrap `text` onto at most `max_lines` lines of `width` cells. When it
+/// needs more, the remaining words join the last line, which is then fitted
+/// with `overflow`.
+pub fn wrap_lines(
+    text: &str,
+    width: usize,
+    max_lines: usize,
+    overflow: TextOverflow,
+) -> Vec<String> {
+    if max_lines == 0 {
+        return Vec::new();
+    }
+    let mut lines = wrap(text, width);
+    if lines.len() > max_lines {
+        let rest = lines.split_off(max_lines - 1).join(" ");
+        lines.push(fit(&rest, width, overflow));
+    }
+    lines
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_policies() {
+        assert_eq!(fit("Save", 10, TextOverflow::Ellipsis), "Save");
+        assert_eq!(fit("Save file", 5, TextOverflow::Clip), "Save ");
+        assert_eq!(fit("Save file", 5, TextOverflow::Ellipsis), "Save…");
+        assert_eq!(fit("Save", 0, TextOverflow::Ellipsis), "");
+    }
+
+    #[test]
+    fn shrink_abbreviates_from_the_end() {
+        let shrink = |width| fit("Save Document Now", width, TextOverflow::Shrink);
+        assert_eq!(shrink(16), "Save Document N.");
+        assert_eq!(shrink(12), "Save D. N.");
+        assert_eq!(shrink(6), "S. D.…");
+    }
+
+    #[test]
+    fn wrap_breaks_between_words() {
+        assert_eq!(wrap("Save the file now", 8), ["Save the", "file now"]);
+        assert_eq!(
+            wrap("Supercalifragilistic", 8),
+            ["Supercal", "ifragili", "stic"]
+        );
+        assert!(wrap("anything", 0).is_empty());
+    }
+
+    #[test]
+    fn wrap_lines_fits_the_last_line() {
+        assert_eq!(
+            wrap_lines("Save the file right now", 8, 2, TextOverflow::Ellipsis),
+            ["Save the", "file ri…"]
+        );
+        assert_eq!(wrap_lines("OK", 8, 3, TextOverflow::Clip), ["OK"]);
+    }
+}
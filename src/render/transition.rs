@@ -0,0 +1,260 @@
+//! Snapshot transitions: cross-fade or slide from old cells to new content.
+//!
+//! Before content changes (e.g. a screen is swapped), the compositor captures
+//! a [`Snapshot`] of the affected region. A [`SnapshotTransition`] then blends
+//! that snapshot with whatever has since been rendered in the same region,
+//! producing overlay strips for each frame until its duration elapses.
+
+use std::time::{Duration, Instant};
+
+use crate::geometry::Region;
+use crate::render::animation::lerp_rgb;
+use crate::render::compositor::{screen_coord, Compositor};
+use crate::render::strip::{CellStyle, Color, Strip, StyledCell};
+
+// ---------------------------------------------------------------------------
+// Snapshot
+// ---------------------------------------------------------------------------
+
+/// A copy of the compositor's cells inside a region.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    region: Region,
+    rows: Vec<Vec<StyledCell>>,
+}
+
+impl Snapshot {
+    /// Capture `region` of `compositor`, clipped to the screen.
+    pub fn capture(compositor: &Compositor, region: Region) -> Self {
+        let region = region.intersection(compositor.screen_region());
+        let columns = region.x.max(0) as usize..region.right().max(0) as usize;
+        let rows = (region.y..region.bottom())
+            .map(|y| match screen_coord(y).and_then(|y| compositor.row(y)) {
+                Some(row) => row[columns.clone()].to_vec(),
+                None => Vec::new(),
+            })
+            .collect();
+        Self { region, rows }
+    }
+
+    /// The captured region.
+    pub fn region(&self) -> Region {
+        self.region
+    }
+
+    /// The captured cell at region-relative `(col, row)`.
+    pub fn cell(&self, col: i32, row: i32) -> Option<&StyledCell> {
+        self.rows.get(row as usize)?.get(col as usize)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// SnapshotTransition
+// ---------------------------------------------------------------------------
+
+/// How the old content gives way to the new.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionEffect {
+    /// Blend colors, switching glyphs halfway through.
+    CrossFade,
+    /// New content pushes in from the right.
+    SlideLeft,
+    /// New content pushes in from the left.
+    SlideRight,
+    /// New content pushes in from the bottom.
+    SlideUp,
+    /// New content pushes in from the top.
+    SlideDown,
+}
+
+/// A running transition from a snapshot to live content.
+#[derive(Debug, Clone)]
+pub struct SnapshotTransition {
+    from: Snapshot,
+    effect: TransitionEffect,
+    start: Instant,
+    duration: Duration,
+}
+
+impl SnapshotTransition {
+    /// Transition away from `from` starting at `start`.
+    pub fn new(
+        from: Snapshot,
+        effect: TransitionEffect,
+        start: Instant,
+        duration: Duration,
+    ) -> Self {
+        Self {
+            from,
+            effect,
+            start,
+            duration,
+        }
+    }
+
+    /// The region the transition covers.
+    pub fn region(&self) -> Region {
+        self.from.region
+    }
+
+    /// The effect in use.
+    pub fn effect(&self) -> TransitionEffect {
+        self.effect
+    }
+
+    /// Linear progress in `0.0..=1.0` at time `now`.
+    pub fn progress(&self, now: Instant) -> f32 {
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+        let elapsed = now.saturating_duration_since(self.start);
+        (elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+    }
+
+    /// Whether the transition has reached its end at time `now`.
+    pub fn is_finished(&self, now: Instant) -> bool {
+        self.progress(now) >= 1.0
+    }
+
+    /// Overlay strips for time `now`, blending the snapshot with the new
+    /// content currently in `compositor`.
+    pub fn frame(&self, compositor: &Compositor, now: Instant) -> Vec<Strip> {
+        let t = self.progress(now);
+        let region = self.from.region;
+        let new_cell = |col: i32, row: i32| {
+            compositor
+                .cell_at(region.x + col, region.y + row)
+                .cloned()
+                .unwrap_or_else(StyledCell::blank)
+        };
+        let old_cell = |col: i32, row: i32| {
+            self.from
+                .cell(col, row)
+                .cloned()
+                .unwrap_or_else(StyledCell::blank)
+        };
+        let (w, h) = (region.width, region.height);
+        let dx = (t * w as f32).round() as i32;
+        let dy = (t * h as f32).round() as i32;
+
+        (0..h)
+            .map(|row| {
+                let mut strip = Strip::new(region.y + row, region.x);
+                for col in 0..w {
+                    let cell = match self.effect {
+                        TransitionEffect::CrossFade => {
+                            cross_fade(&old_cell(col, row), &new_cell(col, row), t)
+                        }
+                        TransitionEffect::SlideLeft if col < w - dx => old_cell(col + dx, row),
+                        TransitionEffect::SlideLeft => new_cell(col - (w - dx), row),
+                        TransitionEffect::SlideRight if col >= dx => old_cell(col - dx, row),
+                        TransitionEffect::SlideRight => new_cell(col + (w - dx), row),
+                        TransitionEffect::SlideUp if row < h - dy => old_cell(col, row + dy),
+                        TransitionEffect::SlideUp => new_cell(col, row - (h - dy)),
+                        TransitionEffect::SlideDown if row >= dy => old_cell(col, row - dy),
+                        TransitionEffect::SlideDown => new_cell(col, row + (h - dy)),
+                    };
+                    strip.push(cell.ch, cell.style);
+                }
+                strip
+            })
+            .collect()
+    }
+}
+
+/// Blend two cells: colors interpolate, glyph and attributes switch at 0.5.
+///
+/// Only RGB colors interpolate; named colors switch at 0.5 like the glyph.
+fn cross_fade(old: &StyledCell, new: &StyledCell, t: f32) -> StyledCell {
+    let base = if t < 0.5 { old } else { new };
+    StyledCell::new(
+        base.ch,
+        CellStyle {
+            fg: blend(old.style.fg, new.style.fg, t),
+            bg: blend(old.style.bg, new.style.bg, t),
+            ..base.style
+        },
+    )
+}
+
+/// Blend two optional colors at progress `t`.
+fn blend(a: Option<Color>, b: Option<Color>, t: f32) -> Option<Color> {
+    match (a.and_then(Color::rgb), b.and_then(Color::rgb)) {
+        (Some(from), Some(to)) => Some(lerp_rgb(from, to, t)),
+        _ if t < 0.5 => a,
+        _ => b,
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn screen(text: &str, bg: &str) -> Compositor {
+        let mut c = Compositor::new(4, 1);
+        let style = CellStyle::new().with_bg(bg);
+        let mut strip = Strip::new(0, 0);
+        strip.push_str(text, style);
+        c.place_strips(&[strip], &Region::new(0, 0, 4, 1));
+        c
+    }
+
+    fn text(strips: &[Strip]) -> String {
+        strips[0].cells.iter().map(|c| c.ch).collect()
+    }
+
+    fn transition(effect: TransitionEffect) -> (SnapshotTransition, Compositor, Instant) {
+        let old = screen("abcd", "#000000");
+        let start = Instant::now();
+        let snapshot = Snapshot::capture(&old, Region::new(0, 0, 4, 1));
+        let t = SnapshotTransition::new(snapshot, effect, start, Duration::from_millis(100));
+        (t, screen("WXYZ", "#ffffff"), start)
+    }
+
+    #[test]
+    fn snapshot_clips_to_screen() {
+        let c = screen("abcd", "#000000");
+        let snap = Snapshot::capture(&c, Region::new(2, 0, 10, 5));
+        assert_eq!(snap.region(), Region::new(2, 0, 2, 1));
+        assert_eq!(snap.cell(1, 0).unwrap().ch, 'd');
+    }
+
+    #[test]
+    fn cross_fade_blends_colors() {
+        let (t, new, start) = transition(TransitionEffect::CrossFade);
+        let quarter = t.frame(&new, start + Duration::from_millis(25));
+        assert_eq!(text(&quarter), "abcd");
+        assert_eq!(
+            quarter[0].cells[0].style.bg,
+            Some(Color::Rgb(0x40, 0x40, 0x40))
+        );
+        let late = t.frame(&new, start + Duration::from_millis(75));
+        assert_eq!(text(&late), "WXYZ");
+    }
+
+    #[test]
+    fn slides_shift_content() {
+        let (t, new, start) = transition(TransitionEffect::SlideLeft);
+        assert_eq!(
+            text(&t.frame(&new, start + Duration::from_millis(50))),
+            "cdWX"
+        );
+        let (t, new, start) = transition(TransitionEffect::SlideRight);
+        assert_eq!(
+            text(&t.frame(&new, start + Duration::from_millis(25))),
+            "Zabc"
+        );
+    }
+
+    #[test]
+    fn finishes_on_new_content() {
+        let (t, new, start) = transition(TransitionEffect::SlideLeft);
+        let end = start + Duration::from_millis(100);
+        assert!(t.is_finished(end));
+        assert_eq!(text(&t.frame(&new, end)), "WXYZ");
+    }
+}
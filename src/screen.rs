@@ -2,32 +2,101 @@
 //!
 //! [`Screen`] owns the DOM, styles, layout engine, compositor, lifecycle tracker,
 //! and focus chain for a single screen of the application. [`FocusChain`] maintains
-//! the tab-order of focusable, visible, non-disabled nodes.
-
-use std::collections::HashMap;
-
-use crate::css::styles::Styles;
+//! the tab-order of focusable, visible, non-disabled nodes, grouped by
+//! `.focus-group` containers. [`FocusPolicy`]
+//! controls how the mouse moves focus. [`Screen::use_region`] exposes a
+//! node's layout region as a signal updated after each layout pass, and
+//! [`Screen::observe_visibility`] reports nodes entering or leaving view.
+//! [`Screen::set_density`] switches the UI density class on the root,
+//! [`Screen::set_mode_layer`] swaps an app mode's root classes and
+//! stylesheets, and [`Screen::set_unit_scale`] sets the size of the `u` unit
+//! in cells.
+//! [`Screen::region_of`], [`Screen::visible_region`], and [`Screen::widget_at`]
+//! answer where a node is on screen, accounting for scrolled containers, and
+//! [`Screen::scroll_wheel`] scrolls the container under the pointer.
+//! [`Screen::draw_scrollbars`] draws the scrollbars of `overflow-y: scroll`
+//! containers, and [`Screen::drag_scrollbar`] scrolls one by its thumb.
+//! The screen keeps each node's `:hover` and `:focus` state in step with the
+//! pointer and the focus chain, restyling nodes whose state changes.
+
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::css::density::{Density, DENSITY_CSS};
+use crate::css::overlay::OVERLAY_CSS;
+use crate::css::parser::parse_css;
+use crate::css::styles::{Overflow, Styles};
 use crate::css::stylesheet::CompiledStylesheet;
-use crate::dom::node::NodeId;
+use crate::dom::node::{NodeData, NodeId};
+use crate::dom::transaction::{DomChanges, Transaction, TransactionError};
 use crate::dom::tree::Dom;
-use crate::layout::LayoutEngine;
+use crate::error::{CssError, LayoutError};
+use crate::event::input::{MouseAction, MouseBtn, MouseEvent};
+use crate::geometry::{Offset, Region, Size};
+use crate::layout::visibility::visible_part;
+use crate::layout::{LayoutEngine, SpatialMap, VisibilityObservers};
+use crate::reactive::{create_signal, ReadSignal, WriteSignal};
+use crate::render::animation::{AnimationBuilder, AnimationError, Animator, StyleTransition};
 use crate::render::compositor::Compositor;
+use crate::render::effects::TextEffects;
+use crate::render::strip::ContrastPair;
 use crate::widget::lifecycle::LifecycleTracker;
+use crate::widget::scroll::{
+    scrollbar_strips, scrollbar_style, ScrollState, ScrollbarState, DEFAULT_SCROLLBAR_SIZE,
+};
+use crate::widget::task::TaskSet;
+use crate::widgets::context_menu::MenuItem;
 
 // ---------------------------------------------------------------------------
 // FocusChain
 // ---------------------------------------------------------------------------
 
+/// The class marking a container as a focus group: arrow keys move focus
+/// among its members, and Tab moves past it as a whole.
+pub const FOCUS_GROUP_CLASS: &str = "focus-group";
+
+/// An active focus scope: the subtree focus is confined to, and the node to
+/// refocus when the scope is popped.
+#[derive(Debug, Clone, Copy)]
+struct FocusScope {
+    root: NodeId,
+    restore: Option<NodeId>,
+}
+
 /// Maintains an ordered list of focusable nodes for tab navigation.
 ///
 /// The chain is rebuilt from the DOM whenever the tree changes. Focus cycles
 /// through the chain in forward (Tab) or backward (Shift+Tab / BackTab) order.
+///
+/// Nodes under a hidden ancestor, and nodes laid out with zero width or
+/// height (see [`set_collapsed`](Self::set_collapsed)), are skipped. By
+/// default focus wraps from the last node to the first; with
+/// [`set_wrap(false)`](Self::set_wrap) it stops at either end.
+///
+/// Focusable nodes inside a [`FOCUS_GROUP_CLASS`] container form a group:
+/// [`focus_next`](Self::focus_next) and [`focus_previous`](Self::focus_previous)
+/// treat the group as one stop and enter it at its first member, while
+/// [`focus_next_in_group`](Self::focus_next_in_group) and
+/// [`focus_previous_in_group`](Self::focus_previous_in_group) move within it.
+///
+/// Focus scopes (modal dialogs, wizards) confine the chain to one subtree.
+/// Scopes nest: the innermost pushed scope is active, and popping it restores
+/// the focus that was current when it was pushed.
 #[derive(Debug)]
 pub struct FocusChain {
     /// Focusable nodes in tab order (depth-first).
     nodes: Vec<NodeId>,
+    /// The focus group of each node in `nodes`, if any.
+    groups: Vec<Option<NodeId>>,
     /// Index of the currently focused node, or `None` if no focus.
     current: Option<usize>,
+    /// Active focus scopes, innermost last.
+    scopes: Vec<FocusScope>,
+    /// Whether moving past either end wraps around.
+    wrap: bool,
+    /// Nodes laid out with no area, skipped on rebuild.
+    collapsed: HashSet<NodeId>,
 }
 
 impl FocusChain {
@@ -35,32 +104,81 @@ impl FocusChain {
     pub fn new() -> Self {
         Self {
             nodes: Vec::new(),
+            groups: Vec::new(),
             current: None,
+            scopes: Vec::new(),
+            wrap: true,
+            collapsed: HashSet::new(),
+        }
+    }
+
+    /// Set whether focus wraps around at either end (builder pattern).
+    pub fn with_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Set whether focus wraps around at either end, or stops there.
+    pub fn set_wrap(&mut self, wrap: bool) {
+        self.wrap = wrap;
+    }
+
+    /// Whether focus wraps around at either end.
+    pub fn wraps(&self) -> bool {
+        self.wrap
+    }
+
+    /// Replace the set of nodes laid out with zero width or height, and
+    /// rebuild so they drop out of (or rejoin) the chain.
+    pub fn set_collapsed(&mut self, dom: &Dom, collapsed: HashSet<NodeId>) {
+        if collapsed != self.collapsed {
+            self.collapsed = collapsed;
+            self.rebuild(dom);
         }
     }
 
     /// Rebuild the focus chain from the DOM.
     ///
-    /// Walks the DOM depth-first from the root and collects all nodes that are
-    /// focusable, visible, and not disabled. If the previously focused node is
-    /// still in the new chain, focus is preserved; otherwise focus is cleared.
+    /// Walks the DOM depth-first from the root (or the active scope's root)
+    /// and collects all nodes that are focusable, visible, and not disabled,
+    /// skipping hidden subtrees and collapsed nodes.
+    /// If the previously focused node is still in the new chain, focus is
+    /// preserved; otherwise focus is cleared. Scopes whose root has left the
+    /// DOM are popped, restoring the focus they saved.
     pub fn rebuild(&mut self, dom: &Dom) {
-        let old_focused = self.current_node();
+        let mut old_focused = self.current_node();
+        while let Some(scope) = self.scopes.last() {
+            if dom.contains(scope.root) {
+                break;
+            }
+            old_focused = scope.restore;
+            self.scopes.pop();
+        }
 
         self.nodes.clear();
+        self.groups.clear();
         self.current = None;
 
-        let root = match dom.root() {
+        let root = match self.scope_root().or_else(|| dom.root()) {
             Some(r) => r,
             None => return,
         };
 
         for id in dom.walk_depth_first(root) {
-            if let Some(data) = dom.get(id) {
-                if data.focusable && data.visible && !data.disabled {
-                    self.nodes.push(id);
-                }
+            let Some(data) = dom.get(id) else { continue };
+            if !data.focusable || data.disabled || self.collapsed.contains(&id) {
+                continue;
+            }
+            let ancestors = dom.ancestors(id);
+            let hidden = |a: &NodeId| dom.get(*a).is_some_and(|n| !n.visible);
+            if !data.visible || ancestors.iter().any(hidden) {
+                continue;
             }
+            let group = ancestors
+                .into_iter()
+                .find(|&a| dom.get(a).is_some_and(|n| n.has_class(FOCUS_GROUP_CLASS)));
+            self.nodes.push(id);
+            self.groups.push(group);
         }
 
         // Try to preserve the previously focused node.
@@ -76,35 +194,124 @@ impl FocusChain {
         self.current.and_then(|idx| self.nodes.get(idx).copied())
     }
 
-    /// Move focus to the next node in the chain. Wraps around.
+    /// Move focus to the next stop in the chain: the next node, or the first
+    /// member of the next focus group. At the end, wraps around or stays put
+    /// depending on [`wraps`](Self::wraps).
     ///
-    /// Returns the newly focused node, or `None` if the chain is empty.
+    /// Returns the focused node, or `None` if the chain is empty.
     pub fn focus_next(&mut self) -> Option<NodeId> {
-        if self.nodes.is_empty() {
-            return None;
-        }
         let next = match self.current {
-            Some(idx) => (idx + 1) % self.nodes.len(),
+            Some(idx) => {
+                let group = self.groups[idx];
+                let mut next = idx;
+                loop {
+                    next = match self.step(next, true) {
+                        Some(n) if n != idx => n,
+                        _ => return self.current_node(),
+                    };
+                    if group.is_none() || self.groups[next] != group {
+                        break;
+                    }
+                }
+                next
+            }
+            None if self.nodes.is_empty() => return None,
             None => 0,
         };
-        self.current = Some(next);
-        self.nodes.get(next).copied()
+        self.focus_index(self.group_start(next))
     }
 
-    /// Move focus to the previous node in the chain. Wraps around.
+    /// Move focus to the previous stop in the chain: the previous node, or
+    /// the first member of the previous focus group. At the start, wraps
+    /// around or stays put depending on [`wraps`](Self::wraps).
     ///
-    /// Returns the newly focused node, or `None` if the chain is empty.
+    /// Returns the focused node, or `None` if the chain is empty.
     pub fn focus_previous(&mut self) -> Option<NodeId> {
-        if self.nodes.is_empty() {
-            return None;
-        }
         let prev = match self.current {
-            Some(0) => self.nodes.len() - 1,
-            Some(idx) => idx - 1,
+            Some(idx) => {
+                let group = self.groups[idx];
+                let mut prev = idx;
+                loop {
+                    prev = match self.step(prev, false) {
+                        Some(p) if p != idx => p,
+                        _ => return self.current_node(),
+                    };
+                    if group.is_none() || self.groups[prev] != group {
+                        break;
+                    }
+                }
+                prev
+            }
+            None if self.nodes.is_empty() => return None,
             None => self.nodes.len() - 1,
         };
-        self.current = Some(prev);
-        self.nodes.get(prev).copied()
+        self.focus_index(self.group_start(prev))
+    }
+
+    /// Move focus to the next member of the focused node's group, wrapping
+    /// or stopping at its end like [`focus_next`](Self::focus_next).
+    ///
+    /// Returns the focused node, or `None` if focus is not in a group.
+    pub fn focus_next_in_group(&mut self) -> Option<NodeId> {
+        self.step_in_group(true)
+    }
+
+    /// Move focus to the previous member of the focused node's group.
+    ///
+    /// Returns the focused node, or `None` if focus is not in a group.
+    pub fn focus_previous_in_group(&mut self) -> Option<NodeId> {
+        self.step_in_group(false)
+    }
+
+    /// The focus group of the focused node, if any.
+    pub fn current_group(&self) -> Option<NodeId> {
+        self.current.and_then(|idx| self.groups[idx])
+    }
+
+    /// The index after (or before) `idx`, wrapping if enabled.
+    fn step(&self, idx: usize, forward: bool) -> Option<usize> {
+        let len = self.nodes.len();
+        match (forward, idx) {
+            (true, i) if i + 1 < len => Some(i + 1),
+            (true, _) => self.wrap.then_some(0),
+            (false, 0) => self.wrap.then_some(len - 1),
+            (false, i) => Some(i - 1),
+        }
+    }
+
+    /// The first index of the group `idx` belongs to, or `idx` itself.
+    fn group_start(&self, idx: usize) -> usize {
+        match self.groups[idx] {
+            Some(group) => self.groups.iter().position(|&g| g == Some(group)).unwrap_or(idx),
+            None => idx,
+        }
+    }
+
+    fn step_in_group(&mut self, forward: bool) -> Option<NodeId> {
+        let idx = self.current?;
+        let group = self.groups[idx]?;
+        let members: Vec<usize> = (0..self.nodes.len())
+            .filter(|&i| self.groups[i] == Some(group))
+            .collect();
+        let pos = members.iter().position(|&i| i == idx)?;
+        let target = match forward {
+            true if pos + 1 < members.len() => pos + 1,
+            true if self.wrap => 0,
+            false if pos > 0 => pos - 1,
+            false if self.wrap => members.len() - 1,
+            _ => pos,
+        };
+        self.focus_index(members[target])
+    }
+
+    fn focus_index(&mut self, idx: usize) -> Option<NodeId> {
+        self.current = Some(idx);
+        self.nodes.get(idx).copied()
+    }
+
+    /// Whether `id` is in the chain (focusable, visible, and enabled).
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.nodes.contains(&id)
     }
 
     /// Focus a specific node by id. Returns `true` if the node was found.
@@ -122,6 +329,43 @@ impl FocusChain {
         self.current = None;
     }
 
+    /// Confine focus to the subtree under `root`, remembering the current
+    /// focus for [`pop_scope`](Self::pop_scope). Focus moves to the first
+    /// focusable node in the scope unless it is already inside. Returns the
+    /// focused node.
+    pub fn push_scope(&mut self, dom: &Dom, root: NodeId) -> Option<NodeId> {
+        self.scopes.push(FocusScope {
+            root,
+            restore: self.current_node(),
+        });
+        self.rebuild(dom);
+        if self.current.is_none() {
+            self.focus_next();
+        }
+        self.current_node()
+    }
+
+    /// Leave the innermost focus scope and restore the focus saved when it
+    /// was pushed (if that node is still focusable). Returns the scope root.
+    pub fn pop_scope(&mut self, dom: &Dom) -> Option<NodeId> {
+        let scope = self.scopes.pop()?;
+        self.rebuild(dom);
+        if let Some(restore) = scope.restore {
+            self.focus_node(restore);
+        }
+        Some(scope.root)
+    }
+
+    /// The root of the active focus scope, if any.
+    pub fn scope_root(&self) -> Option<NodeId> {
+        self.scopes.last().map(|scope| scope.root)
+    }
+
+    /// How many focus scopes are active.
+    pub fn scope_depth(&self) -> usize {
+        self.scopes.len()
+    }
+
     /// Number of focusable nodes in the chain.
     pub fn len(&self) -> usize {
         self.nodes.len()
@@ -139,10 +383,66 @@ impl Default for FocusChain {
     }
 }
 
+// ---------------------------------------------------------------------------
+// FocusPolicy
+// ---------------------------------------------------------------------------
+
+/// How mouse input moves keyboard focus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FocusPolicy {
+    /// Focus the focusable widget under a left click (default: on).
+    pub click_to_focus: bool,
+    /// Focus the focusable widget under the pointer as it moves (default: off).
+    pub focus_follows_mouse: bool,
+}
+
+impl FocusPolicy {
+    /// Create the default policy: click-to-focus only.
+    pub fn new() -> Self {
+        Self {
+            click_to_focus: true,
+            focus_follows_mouse: false,
+        }
+    }
+
+    /// Enable or disable click-to-focus (builder).
+    pub fn with_click_to_focus(mut self, enabled: bool) -> Self {
+        self.click_to_focus = enabled;
+        self
+    }
+
+    /// Enable or disable focus-follows-mouse (builder).
+    pub fn with_focus_follows_mouse(mut self, enabled: bool) -> Self {
+        self.focus_follows_mouse = enabled;
+        self
+    }
+
+    /// Whether `action` should move focus under this policy.
+    fn applies_to(&self, action: MouseAction) -> bool {
+        match action {
+            MouseAction::Down(MouseBtn::Left) => self.click_to_focus,
+            MouseAction::Moved => self.focus_follows_mouse,
+            _ => false,
+        }
+    }
+}
+
+impl Default for FocusPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Screen
 // ---------------------------------------------------------------------------
 
+/// Both halves of a node's region signal.
+type RegionSignal = (ReadSignal<Option<Region>>, WriteSignal<Option<Region>>);
+
+/// Both halves of the focused-node signal.
+type FocusSignal = (ReadSignal<Option<NodeId>>, WriteSignal<Option<NodeId>>);
+
 /// A single screen: DOM, styles, layout, compositor, lifecycle, focus.
 ///
 /// The `Screen` is the central owner of all per-screen state. It is created
@@ -162,6 +462,55 @@ pub struct Screen {
     pub focus: FocusChain,
     /// Compiled CSS stylesheets to apply.
     pub css: Vec<CompiledStylesheet>,
+    /// Running style transitions.
+    pub animator: Animator,
+    /// Hit-testing map of node regions, in paint order.
+    pub spatial: SpatialMap,
+    /// How mouse input moves focus.
+    pub focus_policy: FocusPolicy,
+    /// Background tasks owned by nodes, cancelled when they unmount.
+    pub tasks: TaskSet,
+    /// The text colors `color: auto` resolves to.
+    pub contrast: ContrastPair,
+    /// The clock behind `text-style: blink` and `pulse`.
+    effects: TextEffects,
+    /// Right-click menu items declared per node.
+    context_menus: HashMap<NodeId, Vec<MenuItem>>,
+    /// Containers where dragging on the background draws a selection band.
+    rubber_bands: HashSet<NodeId>,
+    /// Open modal roots, topmost last.
+    modals: Vec<NodeId>,
+    /// Region signals handed out by `use_region`.
+    region_signals: HashMap<NodeId, RegionSignal>,
+    /// Callbacks for nodes entering or leaving view.
+    visibility: VisibilityObservers,
+    /// How far each scrolled container's content is shifted.
+    scroll_offsets: HashMap<NodeId, Offset>,
+    /// Every node in depth-first order, as of the last spatial map rebuild.
+    spatial_order: Vec<NodeId>,
+    /// The DOM structure version the spatial map was last rebuilt for.
+    spatial_version: Option<u64>,
+    /// The node marked `:hover`.
+    hovered: Option<NodeId>,
+    /// The node marked `:focus`, as of the last sync with the focus chain.
+    focused: Option<NodeId>,
+    /// The focused-node signal handed out by `use_focus`.
+    focus_signal: Option<FocusSignal>,
+    /// The active UI density, tagged on the root as a class.
+    density: Density,
+    /// The built-in density rules, cascaded below `css`.
+    density_css: CompiledStylesheet,
+    /// The built-in overlay defaults, cascaded below everything for
+    /// [`overlay_styles`](Screen::overlay_styles).
+    overlay_css: CompiledStylesheet,
+    /// The active mode's root classes.
+    mode_classes: Vec<String>,
+    /// The active mode's stylesheets, cascaded above `css`.
+    mode_css: Vec<Rc<CompiledStylesheet>>,
+    /// How many [`begin_batch`](Screen::begin_batch) calls are open.
+    batch_depth: usize,
+    /// Changes applied while batching, invalidated together at the end.
+    batched: DomChanges,
 }
 
 impl Screen {
@@ -175,20 +524,1070 @@ impl Screen {
             lifecycle: LifecycleTracker::new(),
             focus: FocusChain::new(),
             css: Vec::new(),
+            animator: Animator::new(),
+            spatial: SpatialMap::new(),
+            focus_policy: FocusPolicy::new(),
+            tasks: TaskSet::new(),
+            contrast: ContrastPair::default(),
+            effects: TextEffects::new(),
+            context_menus: HashMap::new(),
+            rubber_bands: HashSet::new(),
+            modals: Vec::new(),
+            region_signals: HashMap::new(),
+            visibility: VisibilityObservers::new(),
+            scroll_offsets: HashMap::new(),
+            spatial_order: Vec::new(),
+            spatial_version: None,
+            hovered: None,
+            focused: None,
+            focus_signal: None,
+            density: Density::default(),
+            density_css: compile_density_css(),
+            overlay_css: compile_overlay_css(),
+            mode_classes: Vec::new(),
+            mode_css: Vec::new(),
+            batch_depth: 0,
+            batched: DomChanges::default(),
+        }
+    }
+
+    /// Resize the screen viewport.
+    ///
+    /// Updates the compositor dimensions and marks the entire screen dirty.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.compositor.resize(width, height);
+    }
+
+    /// Parse `source` and append it to the screen's stylesheets.
+    pub fn add_css(&mut self, source: &str) -> Result<(), CssError> {
+        let sheet = parse_css(source)?;
+        self.css.push(CompiledStylesheet::compile(&sheet, false));
+        Ok(())
+    }
+
+    /// Sync the layout tree with the DOM and computed styles, compute it for
+    /// the current viewport, and refresh the spatial map.
+    pub fn compute_layout(&mut self) -> Result<(), LayoutError> {
+        let (width, height) = (self.compositor.width, self.compositor.height);
+        self.layout.sync_tree(&self.dom, &self.styles, (width, height))?;
+        self.layout.compute(width as f32, height as f32)?;
+        self.update_spatial_map();
+        self.update_collapsed_focus();
+        self.publish_regions();
+        let bounds = Region::new(0, 0, width as i32, height as i32);
+        self.visibility
+            .update(&self.dom, &self.layout, &self.styles, bounds);
+        Ok(())
+    }
+
+    /// Call `callback` with `id` and `true` / `false` whenever the node
+    /// enters or leaves view after a layout pass, clipped by overflowing
+    /// ancestors and the screen. The first layout always reports, and a
+    /// visible node reports `false` when removed.
+    pub fn observe_visibility(&mut self, id: NodeId, callback: impl FnMut(NodeId, bool) + 'static) {
+        self.visibility.observe(id, callback);
+    }
+
+    /// Stop observing `id`'s visibility. Returns how many callbacks were
+    /// dropped.
+    pub fn unobserve_visibility(&mut self, id: NodeId) -> usize {
+        self.visibility.unobserve(id)
+    }
+
+    /// A signal holding `id`'s layout region, `None` until it is laid out.
+    ///
+    /// The signal is written after each [`compute_layout`](Self::compute_layout)
+    /// that moves or resizes the node, and set to `None` when the node is
+    /// removed. Repeated calls return the same signal.
+    pub fn use_region(&mut self, id: NodeId) -> ReadSignal<Option<Region>> {
+        let region = self.layout.get_layout(id);
+        self.region_signals
+            .entry(id)
+            .or_insert_with(|| create_signal(region))
+            .0
+    }
+
+    /// Write each observed node's current region to its signal, if changed.
+    fn publish_regions(&self) {
+        for (&id, (read, write)) in &self.region_signals {
+            let region = self.layout.get_layout(id);
+            if read.get_untracked() != region {
+                write.set(region);
+            }
+        }
+    }
+
+    /// The currently focused node, if any.
+    pub fn focused_node(&self) -> Option<NodeId> {
+        self.focus.current_node()
+    }
+
+    /// Open the subtree under `root` as a modal layer above the rest of the
+    /// screen: focus is confined to it (see
+    /// [`push_focus_scope`](Self::push_focus_scope)) and pointer input
+    /// outside it reaches no widget until it is popped. Modals nest; the
+    /// topmost one takes input.
+    pub fn push_modal(&mut self, root: NodeId) -> Option<NodeId> {
+        self.modals.push(root);
+        self.set_hovered(None);
+        self.compositor.mark_all_dirty();
+        self.push_focus_scope(root)
+    }
+
+    /// Close the topmost modal, restoring the focus from before it opened.
+    /// Returns its root.
+    pub fn pop_modal(&mut self) -> Option<NodeId> {
+        let root = self.modals.pop()?;
+        self.pop_focus_scope();
+        self.compositor.mark_all_dirty();
+        Some(root)
+    }
+
+    /// The root of the topmost modal, if any is open.
+    pub fn modal(&self) -> Option<NodeId> {
+        self.modals.last().copied()
+    }
+
+    /// Whether `id` takes pointer input: always with no modal open, and
+    /// otherwise only inside the topmost modal.
+    pub fn accepts_input(&self, id: NodeId) -> bool {
+        match self.modal() {
+            Some(modal) => id == modal || self.dom.ancestors(id).contains(&modal),
+            None => true,
+        }
+    }
+
+    /// Confine Tab cycling to the subtree under `root` (e.g. a modal dialog).
+    /// See [`FocusChain::push_scope`].
+    pub fn push_focus_scope(&mut self, root: NodeId) -> Option<NodeId> {
+        let focused = self.focus.push_scope(&self.dom, root);
+        self.sync_focus_state();
+        focused
+    }
+
+    /// Leave the innermost focus scope, restoring the previous focus.
+    /// See [`FocusChain::pop_scope`].
+    pub fn pop_focus_scope(&mut self) -> Option<NodeId> {
+        let root = self.focus.pop_scope(&self.dom);
+        self.sync_focus_state();
+        root
+    }
+
+    /// Refresh the spatial map from the current layout, in depth-first order.
+    ///
+    /// Call after computing layout so mouse hit-testing sees current regions.
+    /// Regions are in screen space, shifted by scrolled ancestors. While the
+    /// DOM's shape and every node's visibility are unchanged, only the nodes
+    /// that moved are written; otherwise the map is rebuilt. Either way every
+    /// node's region is computed and compared, so the call is linear in the
+    /// number of nodes. Returns how many entries were written.
+    pub fn update_spatial_map(&mut self) -> usize {
+        if self.spatial_version == Some(self.dom.structure_version()) {
+            if let Some(moved) = self.moved_regions() {
+                for &(id, region) in &moved {
+                    self.spatial.set_region(id, region);
+                }
+                return moved.len();
+            }
+        }
+        self.rebuild_spatial_map()
+    }
+
+    /// Where `id` belongs in the spatial map: its screen region if visible.
+    fn spatial_region(&self, id: NodeId) -> Option<Region> {
+        self.dom.get(id).filter(|n| n.visible)?;
+        self.region_of(id)
+    }
+
+    /// Nodes whose screen region changed since the spatial map was built, or
+    /// `None` if a node entered or left the map (shown, hidden, or laid out
+    /// for the first time), which needs a rebuild. Walks every node.
+    fn moved_regions(&self) -> Option<Vec<(NodeId, Region)>> {
+        // The map holds the visible nodes of `spatial_order`, in order.
+        let mut entries = self.spatial.entries().iter();
+        let mut moved = Vec::new();
+        for &id in &self.spatial_order {
+            let Some(now) = self.spatial_region(id) else {
+                if self.spatial.contains(id) {
+                    return None;
+                }
+                continue;
+            };
+            let &(entry, before) = entries.next()?;
+            if entry != id {
+                return None;
+            }
+            if before != now {
+                moved.push((id, now));
+            }
+        }
+        entries.next().is_none().then_some(moved)
+    }
+
+    /// Rebuild the spatial map from scratch. Returns the number of entries.
+    fn rebuild_spatial_map(&mut self) -> usize {
+        self.spatial_order = match self.dom.root() {
+            Some(root) => self.dom.walk_depth_first(root),
+            None => Vec::new(),
+        };
+        let entries: Vec<(NodeId, Region)> = self
+            .spatial_order
+            .iter()
+            .filter_map(|&id| Some((id, self.spatial_region(id)?)))
+            .collect();
+        self.spatial.update_ordered(&entries);
+        self.spatial_version = Some(self.dom.structure_version());
+        entries.len()
+    }
+
+    /// Record that `container` has scrolled its content by `offset`, so
+    /// geometry queries and hit-testing shift its descendants accordingly.
+    /// Takes effect in the spatial map at the next layout pass. The
+    /// container matches `:scrolled` while the offset is not zero.
+    pub fn set_scroll_offset(&mut self, container: NodeId, offset: Offset) {
+        let scrolled = offset != Offset::new(0, 0);
+        if scrolled {
+            self.scroll_offsets.insert(container, offset);
+        } else {
+            self.scroll_offsets.remove(&container);
+        }
+        self.set_scroll_flag(container, "scrolled", |node| &mut node.scrolled, scrolled);
+    }
+
+    /// Record `container`'s scroll position from its [`ScrollState`]: the
+    /// offset as in [`set_scroll_offset`](Self::set_scroll_offset), and
+    /// `:scroll-end` while nothing more is hidden below or to the right.
+    /// Call whenever the state scrolls or resizes.
+    pub fn set_scroll_state(&mut self, container: NodeId, state: &ScrollState) {
+        self.set_scroll_offset(container, state.offset);
+        let max = state.max_scroll();
+        let end = state.offset.x >= max.x && state.offset.y >= max.y;
+        self.set_scroll_flag(container, "scroll-end", |node| &mut node.scroll_end, end);
+    }
+
+    /// Set one of `container`'s scroll pseudo-class flags, restyling its
+    /// subtree only if the flag changed and some stylesheet uses `pseudo`,
+    /// so scrolling never re-cascades content that no rule depends on.
+    fn set_scroll_flag(
+        &mut self,
+        container: NodeId,
+        pseudo: &str,
+        flag: fn(&mut NodeData) -> &mut bool,
+        value: bool,
+    ) {
+        let Some(node) = self.dom.get_mut(container) else {
+            return;
+        };
+        if *flag(node) == value {
+            return;
+        }
+        *flag(node) = value;
+        let mut sheets = self.css.iter().chain(self.mode_css.iter().map(|css| &**css));
+        if sheets.any(|css| css.uses_pseudo_class(pseudo)) {
+            self.set_state(Some(container), |_| {});
+        }
+    }
+
+    /// How far `container` has scrolled its content.
+    pub fn scroll_offset(&self, container: NodeId) -> Offset {
+        self.scroll_offsets
+            .get(&container)
+            .copied()
+            .unwrap_or(Offset::new(0, 0))
+    }
+
+    /// `container`'s scroll state: its laid-out size as the viewport, the
+    /// extent of its children's layout as the content, and its current
+    /// offset. `None` until it is laid out.
+    pub fn scroll_state(&self, container: NodeId) -> Option<ScrollState> {
+        let viewport = self.layout.get_layout(container)?;
+        let (width, height) = self
+            .dom
+            .children(container)
+            .iter()
+            .filter_map(|&child| self.layout.get_layout(child))
+            .fold((0, 0), |(w, h), r| {
+                (w.max(r.right() - viewport.x), h.max(r.bottom() - viewport.y))
+            });
+        let mut state = ScrollState::new(Size::new(width, height), viewport.size());
+        let offset = self.scroll_offset(container);
+        state.scroll_to(offset.x, offset.y);
+        Some(state)
+    }
+
+    /// The container a scroll wheel turned at `(x, y)` scrolls: the node
+    /// under the pointer or its nearest ancestor with `overflow: scroll` or
+    /// `auto` on the axis that has content to scroll. Horizontal when
+    /// `horizontal` is set (Shift+wheel), vertical otherwise.
+    pub fn scrollable_at(&self, x: u16, y: u16, horizontal: bool) -> Option<NodeId> {
+        let hit = self.widget_at(x, y)?;
+        std::iter::once(hit)
+            .chain(self.dom.ancestors(hit))
+            .find(|&id| {
+                let styles = self.styles.get(&id);
+                let overflow = if horizontal {
+                    styles.and_then(|s| s.overflow_x)
+                } else {
+                    styles.and_then(|s| s.overflow_y)
+                };
+                matches!(overflow, Some(Overflow::Scroll | Overflow::Auto))
+                    && self.scroll_state(id).is_some_and(|state| {
+                        if horizontal {
+                            state.is_scrollable_x()
+                        } else {
+                            state.is_scrollable_y()
+                        }
+                    })
+            })
+    }
+
+    /// Scroll the container under `(x, y)` (see
+    /// [`scrollable_at`](Self::scrollable_at)) by `delta` cells, updating its
+    /// scroll state and hit-testing, and marking it dirty. Returns the
+    /// container if its offset changed.
+    pub fn scroll_wheel(&mut self, x: u16, y: u16, delta: i32, horizontal: bool) -> Option<NodeId> {
+        let container = self.scrollable_at(x, y, horizontal)?;
+        let mut state = self.scroll_state(container)?;
+        let before = state.offset;
+        if horizontal {
+            state.scroll_by(delta, 0);
+        } else {
+            state.scroll_by(0, delta);
+        }
+        if state.offset == before {
+            return None;
+        }
+        self.apply_scroll(container, &state);
+        Some(container)
+    }
+
+    /// Where `container`'s vertical scrollbar is drawn: its rightmost
+    /// `scrollbar-size` columns, when `overflow-y` is `scroll`, or `auto`
+    /// with content taller than the container. `None` if it shows none.
+    ///
+    /// The scrollbar is drawn over the container's content; give the content
+    /// room with padding if it should not be covered.
+    pub fn scrollbar_region(&self, container: NodeId) -> Option<Region> {
+        let styles = self.styles.get(&container)?;
+        let shown = match styles.overflow_y {
+            Some(Overflow::Scroll) => true,
+            Some(Overflow::Auto) => self.scroll_state(container)?.is_scrollable_y(),
+            _ => false,
+        };
+        let size = i32::from(styles.scrollbar_size.unwrap_or(DEFAULT_SCROLLBAR_SIZE));
+        let region = self.region_of(container)?;
+        if !shown || size == 0 || size >= region.width {
+            return None;
+        }
+        Some(Region::new(region.right() - size, region.y, size, region.height))
+    }
+
+    /// The thumb of `container`'s scrollbar as `(start, length)` in rows
+    /// from the top of the track.
+    pub fn scrollbar_thumb(&self, container: NodeId) -> Option<(i32, i32)> {
+        let bar = self.scrollbar_region(container)?;
+        let state = self.scroll_state(container)?;
+        Some(ScrollbarState::from_scroll_state(&state, true).thumb_span(bar.height))
+    }
+
+    /// The container whose visible scrollbar is at `(x, y)`, innermost
+    /// first. Containers outside an open modal are skipped.
+    pub fn scrollbar_at(&self, x: u16, y: u16) -> Option<NodeId> {
+        let (x, y) = (i32::from(x), i32::from(y));
+        let root = self.dom.root()?;
+        self.dom.walk_depth_first(root).into_iter().rev().find(|&id| {
+            self.scrollbar_region(id).is_some_and(|bar| bar.contains(x, y))
+                && self.visible_region(id).is_some_and(|r| r.contains(x, y))
+                && self.accepts_input(id)
+        })
+    }
+
+    /// Scroll `container` so its scrollbar thumb starts `thumb_start` rows
+    /// down the track, as when the thumb is dragged. Returns whether the
+    /// offset changed.
+    pub fn drag_scrollbar(&mut self, container: NodeId, thumb_start: i32) -> bool {
+        let Some(bar) = self.scrollbar_region(container) else {
+            return false;
+        };
+        let Some(mut state) = self.scroll_state(container) else {
+            return false;
+        };
+        let before = state.offset;
+        state.scroll_to_thumb(thumb_start, bar.height);
+        if state.offset == before {
+            return false;
+        }
+        self.apply_scroll(container, &state);
+        true
+    }
+
+    /// Draw the vertical scrollbar of every container that shows one (see
+    /// [`scrollbar_region`](Self::scrollbar_region)) into the compositor, in
+    /// its `scrollbar-color`, clipped to the container's visible part.
+    ///
+    /// Call after widgets are composited, before the overlays and diffing.
+    pub fn draw_scrollbars(&mut self) {
+        let Some(root) = self.dom.root() else {
+            return;
+        };
+        for id in self.dom.walk_depth_first(root) {
+            let Some(bar) = self.scrollbar_region(id) else {
+                continue;
+            };
+            let (Some(state), Some(visible)) = (self.scroll_state(id), self.visible_region(id))
+            else {
+                continue;
+            };
+            let style = scrollbar_style(&self.styles[&id]);
+            let strips = scrollbar_strips(&state, bar, style);
+            self.compositor.place_strips(&strips, &visible);
+        }
+    }
+
+    /// Store `container`'s new scroll state, refresh hit-testing, and mark
+    /// it dirty.
+    fn apply_scroll(&mut self, container: NodeId, state: &ScrollState) {
+        self.set_scroll_state(container, state);
+        self.update_spatial_map();
+        if let Some(region) = self.visible_region(container) {
+            self.compositor.mark_dirty(region);
+        }
+    }
+
+    /// Where `id` is on screen: its layout region shifted by every scrolled
+    /// ancestor. `None` until it is laid out.
+    pub fn region_of(&self, id: NodeId) -> Option<Region> {
+        let region = self.layout.get_layout(id)?;
+        if self.scroll_offsets.is_empty() {
+            return Some(region);
+        }
+        let (dx, dy) = self
+            .dom
+            .ancestors(id)
+            .into_iter()
+            .filter_map(|ancestor| self.scroll_offsets.get(&ancestor))
+            .fold((0, 0), |(dx, dy), offset| (dx + offset.x, dy + offset.y));
+        Some(Region::new(region.x - dx, region.y - dy, region.width, region.height))
+    }
+
+    /// The part of `id` actually on screen: its [`region_of`](Self::region_of)
+    /// clipped by overflowing ancestors and the screen edges. `None` if it is
+    /// not laid out or entirely out of view.
+    pub fn visible_region(&self, id: NodeId) -> Option<Region> {
+        let region = self.region_of(id)?;
+        let (width, height) = (self.compositor.width, self.compositor.height);
+        let bounds = Region::new(0, 0, width as i32, height as i32);
+        let region_of = |ancestor| self.region_of(ancestor);
+        let part = visible_part(region, &self.dom, &region_of, &self.styles, id, bounds);
+        (part.width > 0 && part.height > 0).then_some(part)
+    }
+
+    /// The frontmost visible node at a screen position, skipping nodes
+    /// scrolled or clipped out of view there.
+    pub fn widget_at(&self, x: u16, y: u16) -> Option<NodeId> {
+        let (x, y) = (x as i32, y as i32);
+        self.spatial
+            .hit_test(Offset::new(x, y))
+            .into_iter()
+            .find(|&id| self.visible_region(id).is_some_and(|r| r.contains(x, y)))
+            .filter(|&id| self.accepts_input(id))
+    }
+
+    /// Drop nodes laid out with zero width or height from the focus chain,
+    /// and restore those that regained an area.
+    fn update_collapsed_focus(&mut self) {
+        let collapsed = match self.dom.root() {
+            Some(root) => self
+                .dom
+                .walk_depth_first(root)
+                .into_iter()
+                .filter(|&id| {
+                    self.layout
+                        .get_layout(id)
+                        .is_some_and(|r| r.width <= 0 || r.height <= 0)
+                })
+                .collect(),
+            None => HashSet::new(),
+        };
+        self.focus.set_collapsed(&self.dom, collapsed);
+    }
+
+    /// The focusable node at a screen position: the frontmost node there, or
+    /// its nearest focusable ancestor.
+    pub fn focusable_at(&self, x: u16, y: u16) -> Option<NodeId> {
+        let hit = self.spatial.node_at(Offset::new(x as i32, y as i32))?;
+        if !self.accepts_input(hit) {
+            return None;
+        }
+        std::iter::once(hit)
+            .chain(self.dom.ancestors(hit))
+            .find(|&id| self.focus.contains(id))
+    }
+
+    /// Declare the right-click menu for `id`. An empty list removes it.
+    pub fn set_context_menu(&mut self, id: NodeId, items: Vec<MenuItem>) {
+        if items.is_empty() {
+            self.context_menus.remove(&id);
+        } else {
+            self.context_menus.insert(id, items);
+        }
+    }
+
+    /// The menu items declared for `id`.
+    pub fn context_menu(&self, id: NodeId) -> Option<&[MenuItem]> {
+        self.context_menus.get(&id).map(Vec::as_slice)
+    }
+
+    /// The node whose context menu applies at a screen position: the frontmost
+    /// node there, or its nearest ancestor that declares a menu.
+    pub fn context_menu_at(&self, x: u16, y: u16) -> Option<NodeId> {
+        let hit = self.spatial.node_at(Offset::new(x as i32, y as i32))?;
+        if !self.accepts_input(hit) {
+            return None;
+        }
+        std::iter::once(hit)
+            .chain(self.dom.ancestors(hit))
+            .find(|id| self.context_menus.contains_key(id))
+    }
+
+    /// Turn rubber-band selection on or off for the container `id`.
+    pub fn set_rubber_band(&mut self, id: NodeId, enabled: bool) {
+        if enabled {
+            self.rubber_bands.insert(id);
+        } else {
+            self.rubber_bands.remove(&id);
+        }
+    }
+
+    /// The rubber-band container whose background is at a screen position.
+    /// Positions over one of its children do not count, so children can
+    /// still be clicked and dragged themselves.
+    pub fn rubber_band_at(&self, x: u16, y: u16) -> Option<NodeId> {
+        let hit = self.widget_at(x, y)?;
+        self.rubber_bands.contains(&hit).then_some(hit)
+    }
+
+    /// The children of `parent` whose visible regions intersect `region`,
+    /// in document order.
+    pub fn children_in_region(&self, parent: NodeId, region: Region) -> Vec<NodeId> {
+        self.dom
+            .children(parent)
+            .iter()
+            .copied()
+            .filter(|&child| {
+                self.visible_region(child)
+                    .is_some_and(|part| part.overlaps(region))
+            })
+            .collect()
+    }
+
+    /// Apply the focus policy to a mouse event.
+    ///
+    /// Returns the newly focused node if focus moved. The previously and newly
+    /// focused nodes are marked dirty so `:focus` styles repaint.
+    pub fn handle_mouse_focus(&mut self, event: &MouseEvent) -> Option<NodeId> {
+        if !self.focus_policy.applies_to(event.kind) {
+            return None;
+        }
+        let target = self.focusable_at(event.x, event.y)?;
+        let previous = self.focus.current_node();
+        if previous == Some(target) {
+            return None;
+        }
+        self.focus.focus_node(target);
+        self.sync_focus_state();
+        Some(target)
+    }
+
+    /// The node the pointer is over, as marked for `:hover`.
+    pub fn hovered_node(&self) -> Option<NodeId> {
+        self.hovered
+    }
+
+    /// Mark `id` as hovered (and the previous node as not), restyling both.
+    /// Returns whether the hovered node changed.
+    pub fn set_hovered(&mut self, id: Option<NodeId>) -> bool {
+        if id == self.hovered {
+            return false;
+        }
+        let previous = std::mem::replace(&mut self.hovered, id);
+        self.set_state(previous, |node| node.hovered = false);
+        self.set_state(id, |node| node.hovered = true);
+        true
+    }
+
+    /// Hover the frontmost visible node under the pointer at (`x`, `y`).
+    pub fn update_hover(&mut self, x: u16, y: u16) -> bool {
+        self.set_hovered(self.widget_at(x, y))
+    }
+
+    /// Move the `:focus` mark to the focus chain's current node, restyling
+    /// the nodes that gained or lost it. Call after moving focus through
+    /// [`Screen::focus`]. Returns whether the focused node changed.
+    pub fn sync_focus_state(&mut self) -> bool {
+        let current = self.focus.current_node();
+        if current == self.focused {
+            return false;
+        }
+        let previous = std::mem::replace(&mut self.focused, current);
+        self.set_state(previous, |node| node.focused = false);
+        self.set_state(current, |node| node.focused = true);
+        if let Some((_, write)) = self.focus_signal {
+            write.set(current);
+        }
+        true
+    }
+
+    /// A signal holding the focused node, updated by
+    /// [`sync_focus_state`](Self::sync_focus_state) whenever focus moves.
+    /// Repeated calls return the same signal.
+    pub fn use_focus(&mut self) -> ReadSignal<Option<NodeId>> {
+        let focused = self.focused;
+        self.focus_signal
+            .get_or_insert_with(|| create_signal(focused))
+            .0
+    }
+
+    /// Describe the focused node for a status line, e.g. "Focused: Save
+    /// button": its accessible label, if any, and its widget type in words.
+    /// `None` when nothing is focused.
+    pub fn focus_announcement(&self) -> Option<String> {
+        let node = self.dom.get(self.focused?)?;
+        let mut role = String::new();
+        for (i, ch) in node.widget_type.chars().enumerate() {
+            if ch.is_uppercase() && i > 0 {
+                role.push(' ');
+            }
+            role.extend(ch.to_lowercase());
+        }
+        Some(match &node.aria_label {
+            Some(label) => format!("Focused: {label} {role}"),
+            None => format!("Focused: {role}"),
+        })
+    }
+
+    /// Update a node's pseudo-class state, dropping the computed styles of
+    /// it and its descendants (which selectors like `Panel:hover Button`
+    /// depend on) and marking it dirty.
+    fn set_state(&mut self, id: Option<NodeId>, update: impl FnOnce(&mut NodeData)) {
+        let Some(id) = id else { return };
+        let Some(node) = self.dom.get_mut(id) else {
+            return;
+        };
+        update(node);
+        for descendant in self.dom.walk_depth_first(id) {
+            self.styles.remove(&descendant);
+        }
+        self.mark_node_dirty(id);
+    }
+
+    /// Apply a batch of DOM mutations with a single combined invalidation.
+    ///
+    /// The transaction is applied atomically (see [`Dom::transaction`]). On
+    /// success, lifecycle events are queued, stale styles are dropped, the focus
+    /// chain is rebuilt once, and one dirty region covering every affected node
+    /// is reported to the compositor.
+    pub fn transaction(
+        &mut self,
+        f: impl FnOnce(&mut Transaction),
+    ) -> Result<DomChanges, TransactionError> {
+        let mut tx = Transaction::new();
+        f(&mut tx);
+        self.apply(tx)
+    }
+
+    /// Apply an already-built transaction with a single combined invalidation.
+    ///
+    /// Useful when building the transaction needs to read the DOM, which the
+    /// closure passed to [`Screen::transaction`] cannot borrow.
+    pub fn apply(&mut self, tx: Transaction) -> Result<DomChanges, TransactionError> {
+        let changes = self.dom.apply(tx)?;
+        if self.batch_depth > 0 {
+            self.batched.merge(changes.clone());
+        } else if !changes.is_empty() {
+            self.invalidate(&changes);
+        }
+        Ok(changes)
+    }
+
+    /// Hold back invalidation for transactions applied from now until the
+    /// matching [`end_batch`](Self::end_batch). Batches nest.
+    pub fn begin_batch(&mut self) {
+        self.batch_depth += 1;
+    }
+
+    /// Close a batch. Closing the outermost one invalidates everything the
+    /// batch changed at once, as a single transaction would. Returns whether
+    /// anything was invalidated.
+    pub fn end_batch(&mut self) -> bool {
+        self.batch_depth = self.batch_depth.saturating_sub(1);
+        if self.batch_depth > 0 {
+            return false;
+        }
+        let changes = std::mem::take(&mut self.batched);
+        if changes.is_empty() {
+            return false;
+        }
+        self.invalidate(&changes);
+        true
+    }
+
+    /// Whether a batch is open.
+    pub fn is_batching(&self) -> bool {
+        self.batch_depth > 0
+    }
+
+    /// Cascade styles for every node that has none: new nodes and nodes
+    /// whose classes or state changed since they were last styled. Returns
+    /// how many were restyled.
+    pub fn restyle(&mut self) -> usize {
+        let Some(root) = self.dom.root() else {
+            return 0;
+        };
+        let stale: Vec<NodeId> = self
+            .dom
+            .walk_depth_first(root)
+            .into_iter()
+            .filter(|id| !self.styles.contains_key(id))
+            .collect();
+        for &id in &stale {
+            let styles = self.cascade(id);
+            self.styles.insert(id, styles);
+        }
+        stale.len()
+    }
+
+    /// Cascade every stylesheet for `id` against the current DOM, starting
+    /// from the built-in density rules and ending with the active mode's.
+    pub fn cascade(&self, id: NodeId) -> Styles {
+        self.cascade_in(&self.dom, id, Styles::new())
+    }
+
+    /// Styles for an overlay the app draws itself, such as the perf HUD or a
+    /// context menu, cascaded for a stand-in `widget_type` node under a copy
+    /// of the root: the built-in [`OVERLAY_CSS`] defaults first, then every
+    /// stylesheet [`cascade`](Self::cascade) uses, so app rules such as
+    /// `PerfHud { color: yellow; }` or `.density-compact ContextMenu { .. }`
+    /// apply.
+    pub fn overlay_styles(&self, widget_type: &str) -> Styles {
+        let mut dom = Dom::new();
+        let root = self.dom.root().and_then(|root| self.dom.get(root));
+        let node = match root {
+            Some(data) => {
+                let root = dom.insert(data.clone());
+                dom.insert_child(root, NodeData::new(widget_type))
+            }
+            None => dom.insert(NodeData::new(widget_type)),
+        };
+        let base = self.overlay_css.compute_styles(node, &dom);
+        self.cascade_in(&dom, node, base)
+    }
+
+    /// Cascade the built-in density rules, the app's stylesheets, and the
+    /// active mode's over `base` for `id` in `dom`.
+    fn cascade_in(&self, dom: &Dom, id: NodeId, base: Styles) -> Styles {
+        let base = base.merge(&self.density_css.compute_styles(id, dom));
+        let mut styles = self
+            .css
+            .iter()
+            .chain(self.mode_css.iter().map(|sheet| &**sheet))
+            .fold(base, |acc, sheet| acc.merge(&sheet.compute_styles(id, dom)));
+        self.contrast.resolve(&mut styles);
+        styles
+    }
+
+    /// The active UI density.
+    pub fn density(&self) -> Density {
+        self.density
+    }
+
+    /// Switch the UI density, moving the root's density class and dropping
+    /// computed styles so they are cascaded again. A root inserted later
+    /// through a transaction picks up the class when it mounts.
+    pub fn set_density(&mut self, density: Density) {
+        self.density = density;
+        if self.tag_density_class() {
+            self.styles.clear();
+            self.compositor.mark_all_dirty();
+        }
+    }
+
+    /// Swap the active mode's root classes and stylesheets for `classes`
+    /// and `css` in one step, dropping computed styles so they are cascaded
+    /// again. A root inserted later through a transaction picks up the
+    /// classes when it mounts.
+    pub fn set_mode_layer(&mut self, classes: Vec<String>, css: Vec<Rc<CompiledStylesheet>>) {
+        if let Some(root) = self.dom.root().and_then(|root| self.dom.get_mut(root)) {
+            for class in &self.mode_classes {
+                root.remove_class(class);
+            }
+        }
+        self.mode_classes = classes;
+        self.mode_css = css;
+        self.tag_mode_classes();
+        self.styles.clear();
+        self.compositor.mark_all_dirty();
+    }
+
+    /// Put the active mode's classes on the root.
+    fn tag_mode_classes(&mut self) {
+        if let Some(root) = self.dom.root().and_then(|root| self.dom.get_mut(root)) {
+            for class in &self.mode_classes {
+                root.add_class(class.as_str());
+            }
+        }
+    }
+
+    /// The size of the root unit `1u`, in cells.
+    pub fn unit_scale(&self) -> f32 {
+        self.layout.unit_scale()
+    }
+
+    /// Set the size of the root unit `1u`, in cells (1 by default), so
+    /// paddings and heights written in `u` scale together, e.g. per density.
+    /// Applies at the next layout pass; redraws the whole screen if changed.
+    pub fn set_unit_scale(&mut self, scale: f32) {
+        if self.layout.unit_scale() != scale {
+            self.layout.set_unit_scale(scale);
+            self.compositor.mark_all_dirty();
+        }
+    }
+
+    /// Switch to the next density, returning it.
+    pub fn cycle_density(&mut self) -> Density {
+        self.set_density(self.density.next());
+        self.density
+    }
+
+    /// Put the active density's class on the root, replacing any other.
+    /// Returns whether the root's classes changed.
+    fn tag_density_class(&mut self) -> bool {
+        let density = self.density;
+        let Some(root) = self.dom.root().and_then(|root| self.dom.get_mut(root)) else {
+            return false;
+        };
+        if root.has_class(density.class())
+            && Density::ALL
+                .iter()
+                .all(|d| *d == density || !root.has_class(d.class()))
+        {
+            return false;
+        }
+        for other in Density::ALL {
+            root.remove_class(other.class());
+        }
+        root.add_class(density.class());
+        true
+    }
+
+    /// Add a class to `id`, transitioning its styles over `duration`.
+    ///
+    /// Styles are computed before and after the class change; animatable
+    /// properties (colors, sizes, margin, padding) are interpolated by
+    /// [`Screen::tick_animations`] until the node settles on its new class
+    /// set. Returns `false` if the node does not exist or already has the class.
+    pub fn add_class_animated(&mut self, id: NodeId, class: &str, duration: Duration) -> bool {
+        let has = match self.dom.get(id) {
+            Some(node) => node.has_class(class),
+            None => return false,
+        };
+        !has && self.change_class_animated(id, duration, |node| node.add_class(class))
+    }
+
+    /// Remove a class from `id`, transitioning its styles over `duration`.
+    ///
+    /// The counterpart of [`Screen::add_class_animated`], e.g. for collapsing.
+    pub fn remove_class_animated(&mut self, id: NodeId, class: &str, duration: Duration) -> bool {
+        let has = match self.dom.get(id) {
+            Some(node) => node.has_class(class),
+            None => return false,
+        };
+        has && self.change_class_animated(id, duration, |node| node.remove_class(class))
+    }
+
+    /// Apply a class change to `id` and start a transition across it.
+    fn change_class_animated(
+        &mut self,
+        id: NodeId,
+        duration: Duration,
+        change: impl FnOnce(&mut NodeData),
+    ) -> bool {
+        let now = Instant::now();
+        // A transition already in flight starts from where it currently is.
+        let before = self
+            .animator
+            .styles_at(id, now)
+            .unwrap_or_else(|| self.cascade(id));
+        let Some(node) = self.dom.get_mut(id) else {
+            return false;
+        };
+        change(node);
+        let after = self.cascade(id);
+
+        self.styles.insert(id, before.clone());
+        self.animator
+            .start(id, StyleTransition::new(before, after, now, duration));
+        self.lifecycle.on_update(id);
+        self.mark_node_dirty(id);
+        true
+    }
+
+    /// Advance running style transitions and property animations to `now`.
+    ///
+    /// Writes the interpolated styles into [`Screen::styles`] and marks the
+    /// animating nodes dirty. Returns `true` while any transition is running.
+    pub fn tick_animations(&mut self, now: Instant) -> bool {
+        let mut frame: HashMap<NodeId, Styles> = self.animator.tick(now).into_iter().collect();
+        for animation in self.animator.tick_properties(now) {
+            if !self.dom.contains(animation.node) {
+                continue;
+            }
+            let styles = frame
+                .entry(animation.node)
+                .or_insert_with(|| self.cascade(animation.node));
+            animation.apply(styles, now);
+        }
+        for (id, styles) in frame {
+            if self.dom.contains(id) {
+                self.styles.insert(id, styles);
+                self.mark_node_dirty(id);
+            }
+        }
+        !self.animator.is_empty()
+    }
+
+    /// Advance the blink/pulse clock to `now`.
+    ///
+    /// When the phase flips, every node styled with `text-style: blink` or
+    /// `pulse` is marked dirty, so it is re-rendered before
+    /// [`Compositor::apply_text_effects`] resolves it again. Returns whether
+    /// any node needs further ticks.
+    pub fn tick_text_effects(&mut self, now: Instant) -> bool {
+        if self.effects.tick(now) {
+            let nodes: Vec<NodeId> = self
+                .styles
+                .iter()
+                .filter(|(_, styles)| has_text_effect(styles))
+                .map(|(&id, _)| id)
+                .collect();
+            for id in nodes {
+                self.mark_node_dirty(id);
+            }
+        }
+        self.has_text_effects()
+    }
+
+    /// Whether any node is blinking or pulsing. Always `false` in
+    /// reduced-motion mode.
+    pub fn has_text_effects(&self) -> bool {
+        !self.effects.reduced_motion() && self.styles.values().any(has_text_effect)
+    }
+
+    /// The blink/pulse clock.
+    pub fn text_effects(&self) -> &TextEffects {
+        &self.effects
+    }
+
+    /// Set how long each blink/pulse phase lasts.
+    pub fn set_text_effect_period(&mut self, period: Duration) {
+        self.effects.set_period(period);
+    }
+
+    /// Turn reduced-motion mode on or off: blinking and pulsing text is
+    /// drawn steadily while it is on. Redraws the whole screen if changed.
+    pub fn set_reduced_motion(&mut self, reduced: bool) {
+        if self.effects.reduced_motion() != reduced {
+            self.effects.set_reduced_motion(reduced);
+            self.compositor.mark_all_dirty();
         }
     }
 
-    /// Resize the screen viewport.
+    /// Build `animation` starting now and run it on this screen's animator.
     ///
-    /// Updates the compositor dimensions and marks the entire screen dirty.
-    pub fn resize(&mut self, width: u16, height: u16) {
-        self.compositor.resize(width, height);
+    /// The property's value is written over the node's cascaded styles on
+    /// every [`Screen::tick_animations`] until it finishes.
+    pub fn run_animation(&mut self, animation: AnimationBuilder) -> Result<(), AnimationError> {
+        self.animator.run(animation.build(Instant::now())?);
+        Ok(())
     }
 
-    /// The currently focused node, if any.
-    pub fn focused_node(&self) -> Option<NodeId> {
-        self.focus.current_node()
+    /// Mark the area of `id` dirty, or the whole screen if it has no layout yet.
+    fn mark_node_dirty(&mut self, id: NodeId) {
+        match self.layout.get_layout(id) {
+            Some(region) => self.compositor.mark_dirty(region),
+            None => self.compositor.mark_all_dirty(),
+        }
     }
+
+    /// Run the combined invalidation for a set of DOM changes.
+    fn invalidate(&mut self, changes: &DomChanges) {
+        for &id in &changes.removed {
+            self.styles.remove(&id);
+            self.animator.cancel(id);
+            self.context_menus.remove(&id);
+            self.rubber_bands.remove(&id);
+            self.modals.retain(|&modal| modal != id);
+            if let Some((_, write)) = self.region_signals.remove(&id) {
+                write.set(None);
+            }
+            self.visibility.remove(id);
+            self.scroll_offsets.remove(&id);
+            self.tasks.cancel_node(id);
+            self.lifecycle.on_unmount(id);
+        }
+        for &id in &changes.inserted {
+            self.lifecycle.on_mount(id);
+        }
+        if self.dom.root().is_some_and(|root| changes.inserted.contains(&root)) {
+            self.tag_density_class();
+            self.tag_mode_classes();
+        }
+        for &id in &changes.updated {
+            // Class/visibility changes can alter the cascade; recompute lazily.
+            self.styles.remove(&id);
+            self.lifecycle.on_update(id);
+        }
+
+        self.focus.rebuild(&self.dom);
+        if self.hovered.is_some_and(|id| !self.dom.contains(id)) {
+            self.hovered = None;
+        }
+        if self.focused.is_some_and(|id| !self.dom.contains(id)) {
+            self.focused = None;
+        }
+        self.sync_focus_state();
+
+        // The layout engine has not been re-synced yet, so it still holds the
+        // regions these nodes occupied before the transaction.
+        let dirty = changes
+            .affected()
+            .filter_map(|id| self.layout.get_layout(id))
+            .reduce(Region::union);
+        match dirty {
+            // Inserted nodes have no layout yet, so any insert repaints everything.
+            Some(region) if changes.inserted.is_empty() => self.compositor.mark_dirty(region),
+            _ => self.compositor.mark_all_dirty(),
+        }
+    }
+}
+
+/// Whether `styles` blink or pulse.
+fn has_text_effect(styles: &Styles) -> bool {
+    styles
+        .text_style
+        .is_some_and(|flags| flags.blink == Some(true) || flags.pulse == Some(true))
+}
+
+/// The built-in density rules, compiled at default priority.
+fn compile_density_css() -> CompiledStylesheet {
+    let sheet = parse_css(DENSITY_CSS).expect("built-in density CSS parses");
+    CompiledStylesheet::compile(&sheet, true)
+}
+
+/// The built-in overlay rules, compiled at default priority.
+fn compile_overlay_css() -> CompiledStylesheet {
+    let sheet = parse_css(OVERLAY_CSS).expect("built-in overlay CSS parses");
+    CompiledStylesheet::compile(&sheet, true)
 }
 
 // ===========================================================================
@@ -198,7 +1597,7 @@ impl Screen {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::dom::node::NodeData;
+    use crate::render::strip::Color;
 
     // ── FocusChain ───────────────────────────────────────────────────
 
@@ -407,6 +1806,204 @@ mod tests {
         assert!(chain.current_node().is_none());
     }
 
+    // ── Focus scopes ─────────────────────────────────────────────────
+
+    /// Root with a focusable `A`, and a `Dialog` holding `Ok` and `Cancel`.
+    fn dialog_dom() -> (Dom, [NodeId; 4]) {
+        let mut dom = Dom::new();
+        let root = dom.insert(NodeData::new("Root"));
+        let a = dom.insert_child(root, NodeData::new("A").focusable(true));
+        let dialog = dom.insert_child(root, NodeData::new("Dialog"));
+        let ok = dom.insert_child(dialog, NodeData::new("Ok").focusable(true));
+        let cancel = dom.insert_child(dialog, NodeData::new("Cancel").focusable(true));
+        (dom, [a, dialog, ok, cancel])
+    }
+
+    #[test]
+    fn scope_confines_cycling() {
+        let (dom, [a, dialog, ok, cancel]) = dialog_dom();
+        let mut chain = FocusChain::new();
+        chain.rebuild(&dom);
+        chain.focus_node(a);
+
+        assert_eq!(chain.push_scope(&dom, dialog), Some(ok));
+        assert_eq!(chain.len(), 2);
+        assert_eq!(chain.focus_next(), Some(cancel));
+        assert_eq!(chain.focus_next(), Some(ok));
+        assert_eq!(chain.focus_previous(), Some(cancel));
+        assert!(!chain.focus_node(a));
+
+        assert_eq!(chain.pop_scope(&dom), Some(dialog));
+        assert_eq!(chain.current_node(), Some(a));
+        assert_eq!(chain.len(), 3);
+        assert!(chain.pop_scope(&dom).is_none());
+    }
+
+    #[test]
+    fn nested_scopes_restore_in_order() {
+        let (mut dom, [a, dialog, ok, _]) = dialog_dom();
+        let inner = dom.insert_child(dialog, NodeData::new("Confirm"));
+        let yes = dom.insert_child(inner, NodeData::new("Yes").focusable(true));
+        let mut chain = FocusChain::new();
+        chain.rebuild(&dom);
+        chain.focus_node(a);
+
+        chain.push_scope(&dom, dialog);
+        assert_eq!(chain.push_scope(&dom, inner), Some(yes));
+        assert_eq!(chain.scope_depth(), 2);
+        chain.pop_scope(&dom);
+        assert_eq!(chain.current_node(), Some(ok));
+        chain.pop_scope(&dom);
+        assert_eq!(chain.current_node(), Some(a));
+    }
+
+    #[test]
+    fn removing_scope_root_pops_scope() {
+        let mut screen = Screen::new(80, 24);
+        let (dom, [a, dialog, ..]) = dialog_dom();
+        screen.dom = dom;
+        screen.focus.rebuild(&screen.dom);
+        screen.focus.focus_node(a);
+        screen.push_focus_scope(dialog);
+
+        let mut tx = Transaction::new();
+        tx.remove(dialog);
+        screen.apply(tx).unwrap();
+        assert!(screen.focus.scope_root().is_none());
+        assert_eq!(screen.focused_node(), Some(a));
+    }
+
+    #[test]
+    fn modal_confines_focus_and_pointer() {
+        let mut screen = Screen::new(80, 24);
+        let (dom, [a, dialog, ok, _]) = dialog_dom();
+        screen.dom = dom;
+        screen.spatial.update_ordered(&[
+            (a, Region::new(0, 0, 10, 1)),
+            (dialog, Region::new(20, 5, 20, 5)),
+            (ok, Region::new(22, 6, 4, 1)),
+        ]);
+        screen.focus.rebuild(&screen.dom);
+        screen.focus.focus_node(a);
+
+        assert_eq!(screen.push_modal(dialog), Some(ok));
+        assert_eq!(screen.modal(), Some(dialog));
+        assert_eq!(screen.focusable_at(1, 0), None);
+        assert_eq!(screen.focusable_at(23, 6), Some(ok));
+        assert!(screen.accepts_input(ok) && !screen.accepts_input(a));
+
+        assert_eq!(screen.pop_modal(), Some(dialog));
+        assert_eq!(screen.focused_node(), Some(a));
+        assert_eq!(screen.focusable_at(1, 0), Some(a));
+        assert_eq!(screen.pop_modal(), None);
+    }
+
+    #[test]
+    fn removed_modal_root_leaves_the_modal_stack() {
+        let mut screen = Screen::new(80, 24);
+        let (dom, [_, dialog, _, _]) = dialog_dom();
+        screen.dom = dom;
+        screen.push_modal(dialog);
+
+        screen.set_rubber_band(dialog, false);
+        assert_eq!(screen.modal(), Some(dialog));
+
+        screen.transaction(|tx| tx.remove(dialog)).unwrap();
+        assert_eq!(screen.modal(), None);
+    }
+
+    // ── Wrap, skip rules, and focus groups ───────────────────────────
+
+    #[test]
+    fn without_wrap_focus_stops_at_ends() {
+        let mut dom = Dom::new();
+        let root = dom.insert(NodeData::new("Root"));
+        let a = dom.insert_child(root, NodeData::new("A").focusable(true));
+        let b = dom.insert_child(root, NodeData::new("B").focusable(true));
+        let mut chain = FocusChain::new().with_wrap(false);
+        chain.rebuild(&dom);
+
+        assert_eq!(chain.focus_next(), Some(a));
+        assert_eq!(chain.focus_next(), Some(b));
+        assert_eq!(chain.focus_next(), Some(b));
+        assert_eq!(chain.focus_previous(), Some(a));
+        assert_eq!(chain.focus_previous(), Some(a));
+        chain.set_wrap(true);
+        assert_eq!(chain.focus_previous(), Some(b));
+    }
+
+    #[test]
+    fn hidden_subtrees_and_collapsed_nodes_are_skipped() {
+        let mut screen = Screen::new(20, 10);
+        let root = screen.dom.insert(NodeData::new("Root"));
+        let panel = screen.dom.insert_child(root, NodeData::new("Panel"));
+        screen.dom.get_mut(panel).unwrap().visible = false;
+        let _inner = screen
+            .dom
+            .insert_child(panel, NodeData::new("Inner").focusable(true));
+        let empty = screen.dom.insert_child(root, NodeData::new("A").focusable(true));
+        let b = screen.dom.insert_child(root, NodeData::new("B").focusable(true));
+        for (id, height) in [(root, 10.0), (empty, 0.0), (b, 2.0)] {
+            let mut styles = Styles::new();
+            styles.width = Some(crate::css::scalar::Scalar::cells(20.0));
+            styles.height = Some(crate::css::scalar::Scalar::cells(height));
+            screen.styles.insert(id, styles);
+        }
+        screen.focus.rebuild(&screen.dom);
+        assert_eq!(screen.focus.len(), 2);
+
+        screen.compute_layout().unwrap();
+        assert_eq!(screen.focus.len(), 1);
+        assert!(!screen.focus.contains(empty));
+        assert_eq!(screen.focus.focus_next(), Some(b));
+
+        screen.styles.get_mut(&empty).unwrap().height =
+            Some(crate::css::scalar::Scalar::cells(1.0));
+        screen.compute_layout().unwrap();
+        assert!(screen.focus.contains(empty));
+        assert_eq!(screen.focused_node(), Some(b));
+    }
+
+    /// ```text
+    /// root
+    /// ├── a
+    /// ├── radios (.focus-group)
+    /// │   ├── r1
+    /// │   ├── r2
+    /// │   └── r3
+    /// └── b
+    /// ```
+    #[test]
+    fn tab_moves_between_groups_and_arrows_within() {
+        let mut dom = Dom::new();
+        let root = dom.insert(NodeData::new("Root"));
+        let a = dom.insert_child(root, NodeData::new("A").focusable(true));
+        let radios = dom.insert_child(root, NodeData::new("Group").with_class("focus-group"));
+        let radio =
+            |dom: &mut Dom| dom.insert_child(radios, NodeData::new("Radio").focusable(true));
+        let [r1, r2, r3] = [radio(&mut dom), radio(&mut dom), radio(&mut dom)];
+        let b = dom.insert_child(root, NodeData::new("B").focusable(true));
+        let mut chain = FocusChain::new();
+        chain.rebuild(&dom);
+        chain.focus_node(a);
+
+        assert_eq!(chain.focus_next_in_group(), None);
+        assert_eq!(chain.focus_next(), Some(r1));
+        assert_eq!(chain.current_group(), Some(radios));
+        assert_eq!(chain.focus_next_in_group(), Some(r2));
+        assert_eq!(chain.focus_next_in_group(), Some(r3));
+        assert_eq!(chain.focus_next_in_group(), Some(r1));
+        assert_eq!(chain.focus_previous_in_group(), Some(r3));
+        assert_eq!(chain.focus_next(), Some(b));
+        // Entering a group backwards still lands on its first member.
+        assert_eq!(chain.focus_previous(), Some(r1));
+        assert_eq!(chain.focus_previous(), Some(a));
+
+        chain.set_wrap(false);
+        chain.focus_node(r3);
+        assert_eq!(chain.focus_next_in_group(), Some(r3));
+    }
+
     // ── Screen ───────────────────────────────────────────────────────
 
     #[test]
@@ -442,4 +2039,687 @@ mod tests {
 
         assert_eq!(screen.focused_node(), Some(a));
     }
+
+    #[test]
+    fn transaction_invalidates_once() {
+        let mut screen = Screen::new(80, 24);
+        let root = screen.dom.insert(NodeData::new("List"));
+        let old = screen.dom.insert_child(root, NodeData::new("Item").focusable(true));
+        screen.compositor.clear_dirty();
+
+        let changes = screen
+            .transaction(|tx| {
+                tx.remove(old);
+                for _ in 0..3 {
+                    tx.insert_child(root, NodeData::new("Item").focusable(true));
+                }
+            })
+            .unwrap();
+
+        assert_eq!(changes.inserted.len(), 3);
+        assert_eq!(screen.focus.len(), 3);
+        assert_eq!(screen.compositor.dirty_regions().len(), 1);
+        assert!(!screen.lifecycle.is_mounted(old));
+        assert!(screen.lifecycle.is_mounted(changes.inserted[0]));
+    }
+
+    #[test]
+    fn failed_transaction_leaves_screen_clean() {
+        let mut screen = Screen::new(80, 24);
+        let root = screen.dom.insert(NodeData::new("List"));
+        let gone = screen.dom.insert_child(root, NodeData::new("Item"));
+        screen.dom.remove(gone);
+        screen.compositor.clear_dirty();
+
+        assert!(screen.transaction(|tx| tx.remove(gone)).is_err());
+        assert!(!screen.compositor.is_dirty());
+    }
+
+    #[test]
+    fn batches_invalidate_once_at_the_end() {
+        let mut screen = Screen::new(80, 24);
+        screen.add_css(".wide { width: 10; }").unwrap();
+        let root = screen.dom.insert(NodeData::new("List"));
+        assert_eq!(screen.restyle(), 1);
+        screen.compositor.clear_dirty();
+
+        screen.begin_batch();
+        screen.begin_batch();
+        screen.transaction(|tx| tx.add_class(root, "wide")).unwrap();
+        let item = screen
+            .transaction(|tx| {
+                tx.insert_child(root, NodeData::new("Item"));
+            })
+            .unwrap()
+            .inserted[0];
+        let tmp = screen.dom.insert_child(root, NodeData::new("Tmp"));
+        screen.transaction(|tx| tx.remove(tmp)).unwrap();
+        assert!(!screen.end_batch());
+        assert!(screen.is_batching());
+        assert!(!screen.compositor.is_dirty());
+        assert!(screen.styles.contains_key(&root));
+
+        assert!(screen.end_batch());
+        assert!(screen.compositor.is_dirty());
+        assert!(!screen.styles.contains_key(&root));
+        assert_eq!(screen.restyle(), 2);
+        assert_eq!(screen.styles[&root].width, Some(crate::css::scalar::Scalar::cells(10.0)));
+        assert!(screen.styles.contains_key(&item));
+        assert!(!screen.end_batch());
+    }
+
+    // ── animated class changes ───────────────────────────────────────
+
+    fn animated_screen() -> (Screen, NodeId) {
+        use crate::css::parser::parse_css;
+        let mut screen = Screen::new(80, 24);
+        let css = parse_css(
+            "Panel { height: 2; background: #000000; } \
+             .expanded { height: 10; background: #ffffff; }",
+        )
+        .unwrap();
+        screen.css.push(CompiledStylesheet::compile(&css, false));
+        let panel = screen.dom.insert(NodeData::new("Panel"));
+        (screen, panel)
+    }
+
+    #[test]
+    fn add_class_animated_interpolates_then_settles() {
+        use crate::css::scalar::Scalar;
+        let (mut screen, panel) = animated_screen();
+        let start = Instant::now();
+
+        assert!(screen.add_class_animated(panel, "expanded", Duration::from_millis(100)));
+        assert!(screen.dom.get(panel).unwrap().has_class("expanded"));
+        assert_eq!(screen.styles[&panel].height, Some(Scalar::cells(2.0)));
+
+        assert!(screen.tick_animations(start + Duration::from_millis(50)));
+        let height = screen.styles[&panel].height.unwrap().value;
+        assert!(height > 2.0 && height < 10.0, "{height}");
+
+        assert!(!screen.tick_animations(start + Duration::from_secs(1)));
+        assert_eq!(screen.styles[&panel].height, Some(Scalar::cells(10.0)));
+        assert_eq!(screen.styles[&panel].background.as_deref(), Some("#ffffff"));
+    }
+
+    #[test]
+    fn add_class_animated_noop_when_present() {
+        let (mut screen, panel) = animated_screen();
+        screen.dom.get_mut(panel).unwrap().add_class("expanded");
+        assert!(!screen.add_class_animated(panel, "expanded", Duration::from_millis(100)));
+        assert!(screen.animator.is_empty());
+        assert!(screen.remove_class_animated(panel, "expanded", Duration::from_millis(100)));
+        assert!(screen.animator.is_animating(panel));
+    }
+
+    #[test]
+    fn property_animation_overrides_cascade() {
+        use crate::css::scalar::Scalar;
+        use crate::render::animation::animate;
+        let (mut screen, panel) = animated_screen();
+        let start = Instant::now();
+
+        screen
+            .run_animation(
+                animate(panel)
+                    .style("width")
+                    .from(0.0)
+                    .to(40.0)
+                    .duration(Duration::from_millis(100)),
+            )
+            .unwrap();
+        assert!(screen.tick_animations(start));
+        // Cascaded properties are kept underneath the animated one.
+        assert_eq!(screen.styles[&panel].height, Some(Scalar::cells(2.0)));
+
+        assert!(!screen.tick_animations(start + Duration::from_secs(1)));
+        assert_eq!(screen.styles[&panel].width, Some(Scalar::cells(40.0)));
+        assert!(screen.run_animation(animate(panel).style("bogus")).is_err());
+    }
+
+    // ── focus policy ─────────────────────────────────────────────────
+
+    fn mouse(kind: MouseAction, x: u16, y: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            x,
+            y,
+            modifiers: crate::event::input::Modifiers::NONE,
+        }
+    }
+
+    /// A root with two buttons side by side, each holding a label.
+    fn clickable_screen() -> (Screen, NodeId, NodeId, NodeId) {
+        let mut screen = Screen::new(40, 10);
+        let root = screen.dom.insert(NodeData::new("Root"));
+        let a = screen.dom.insert_child(root, NodeData::new("Button").focusable(true));
+        let b = screen.dom.insert_child(root, NodeData::new("Button").focusable(true));
+        let label = screen.dom.insert_child(b, NodeData::new("Static"));
+        screen.focus.rebuild(&screen.dom);
+        screen.spatial.update_ordered(&[
+            (root, Region::new(0, 0, 40, 10)),
+            (a, Region::new(0, 0, 20, 10)),
+            (b, Region::new(20, 0, 20, 10)),
+            (label, Region::new(22, 2, 5, 1)),
+        ]);
+        (screen, a, b, label)
+    }
+
+    #[test]
+    fn click_focuses_widget_under_pointer() {
+        let (mut screen, a, _, _) = clickable_screen();
+        let focused = screen.handle_mouse_focus(&mouse(MouseAction::Down(MouseBtn::Left), 5, 5));
+        assert_eq!(focused, Some(a));
+        assert_eq!(screen.focused_node(), Some(a));
+        // Clicking the focused widget again is not a change.
+        assert!(screen
+            .handle_mouse_focus(&mouse(MouseAction::Down(MouseBtn::Left), 6, 5))
+            .is_none());
+    }
+
+    #[test]
+    fn click_on_child_focuses_focusable_ancestor() {
+        let (mut screen, _, b, label) = clickable_screen();
+        assert_eq!(screen.spatial.node_at(Offset::new(23, 2)), Some(label));
+        screen.handle_mouse_focus(&mouse(MouseAction::Down(MouseBtn::Left), 23, 2));
+        assert_eq!(screen.focused_node(), Some(b));
+    }
+
+    #[test]
+    fn focus_follows_mouse_is_opt_in() {
+        let (mut screen, _, b, _) = clickable_screen();
+        assert!(screen.handle_mouse_focus(&mouse(MouseAction::Moved, 30, 5)).is_none());
+
+        screen.focus_policy = FocusPolicy::new().with_focus_follows_mouse(true);
+        assert_eq!(screen.handle_mouse_focus(&mouse(MouseAction::Moved, 30, 5)), Some(b));
+    }
+
+    #[test]
+    fn click_to_focus_can_be_disabled() {
+        let (mut screen, _, _, _) = clickable_screen();
+        screen.focus_policy = FocusPolicy::new().with_click_to_focus(false);
+        screen.handle_mouse_focus(&mouse(MouseAction::Down(MouseBtn::Left), 5, 5));
+        assert!(screen.focused_node().is_none());
+    }
+
+    // ── fallible setup ───────────────────────────────────────────────
+
+    #[test]
+    fn add_css_reports_parse_errors() {
+        let mut screen = Screen::new(80, 24);
+        assert!(screen.add_css("Button { color: red; }").is_ok());
+        assert_eq!(screen.css.len(), 1);
+        assert!(matches!(screen.add_css("Button { color"), Err(CssError::Parse(_))));
+        assert_eq!(screen.css.len(), 1);
+    }
+
+    #[test]
+    fn overlay_styles_start_from_builtin_defaults() {
+        let mut screen = Screen::new(80, 24);
+        let hud = screen.overlay_styles("PerfHud");
+        assert_eq!(hud.background.as_deref(), Some("#202020"));
+        assert_eq!(hud.color.as_deref(), Some("#00ff87"));
+
+        screen.dom.insert(NodeData::new("Root").with_class("dark"));
+        screen.add_css("PerfHud { color: yellow; }").unwrap();
+        screen.add_css(".dark > PerfHud { background: black; }").unwrap();
+        let hud = screen.overlay_styles("PerfHud");
+        assert_eq!(hud.color.as_deref(), Some("yellow"));
+        assert_eq!(hud.background.as_deref(), Some("black"));
+        // The stand-in node never enters the screen's DOM.
+        assert_eq!(screen.dom.len(), 1);
+    }
+
+    #[test]
+    fn compute_layout_fills_spatial_map() {
+        let mut screen = Screen::new(20, 10);
+        let root = screen.dom.insert(NodeData::new("Root"));
+        let mut styles = Styles::new();
+        styles.width = Some(crate::css::scalar::Scalar::cells(20.0));
+        styles.height = Some(crate::css::scalar::Scalar::cells(10.0));
+        screen.styles.insert(root, styles);
+        screen.compute_layout().unwrap();
+        assert_eq!(screen.layout.get_layout(root), Some(Region::new(0, 0, 20, 10)));
+        assert_eq!(screen.spatial.node_at(Offset::new(5, 5)), Some(root));
+    }
+
+    #[test]
+    fn spatial_map_updates_only_moved_nodes() {
+        use crate::css::scalar::Scalar;
+
+        let mut screen = Screen::new(40, 10);
+        let root = screen.dom.insert(NodeData::new("Root"));
+        let cells: Vec<NodeId> = (0..4)
+            .map(|_| screen.dom.insert_child(root, NodeData::new("Cell")))
+            .collect();
+        let mut root_styles = Styles::new();
+        root_styles.width = Some(Scalar::cells(40.0));
+        root_styles.height = Some(Scalar::cells(10.0));
+        screen.styles.insert(root, root_styles);
+        for &cell in &cells {
+            let mut styles = Styles::new();
+            styles.height = Some(Scalar::cells(2.0));
+            screen.styles.insert(cell, styles);
+        }
+        screen.compute_layout().unwrap();
+        assert_eq!(screen.spatial.len(), 5);
+        // Nothing moved.
+        assert_eq!(screen.update_spatial_map(), 0);
+
+        // Growing the first cell pushes the others down.
+        screen.styles.get_mut(&cells[0]).unwrap().height = Some(Scalar::cells(4.0));
+        screen.layout.sync_tree(&screen.dom, &screen.styles, (40, 10)).unwrap();
+        screen.layout.compute(40.0, 10.0).unwrap();
+        assert_eq!(screen.update_spatial_map(), 4);
+        assert_eq!(screen.widget_at(0, 7), Some(cells[2]));
+
+        // Hiding a node or changing the tree rebuilds.
+        screen.dom.get_mut(cells[3]).unwrap().visible = false;
+        assert_eq!(screen.update_spatial_map(), 4);
+        screen.dom.remove(cells[2]);
+        screen.layout.sync_tree(&screen.dom, &screen.styles, (40, 10)).unwrap();
+        screen.layout.compute(40.0, 10.0).unwrap();
+        assert_eq!(screen.update_spatial_map(), 3);
+        assert_eq!(screen.widget_at(0, 7), Some(root));
+    }
+
+    #[test]
+    fn region_signal_tracks_layout() {
+        crate::reactive::signal::reset_runtime();
+        let mut screen = Screen::new(20, 10);
+        let root = screen.dom.insert(NodeData::new("Root"));
+        let mut styles = Styles::new();
+        styles.width = Some(crate::css::scalar::Scalar::cells(12.0));
+        styles.height = Some(crate::css::scalar::Scalar::cells(4.0));
+        screen.styles.insert(root, styles);
+
+        let region = screen.use_region(root);
+        assert_eq!(region.get(), None);
+        screen.compute_layout().unwrap();
+        assert_eq!(region.get(), Some(Region::new(0, 0, 12, 4)));
+        assert_eq!(screen.use_region(root).get(), region.get());
+
+        screen.styles.get_mut(&root).unwrap().height =
+            Some(crate::css::scalar::Scalar::cells(6.0));
+        screen.compute_layout().unwrap();
+        assert_eq!(region.get(), Some(Region::new(0, 0, 12, 6)));
+
+        screen.transaction(|tx| tx.remove(root)).unwrap();
+        assert_eq!(region.get(), None);
+    }
+
+    /// A 10x4 list clipping three 3-row items, scrolled down by one item.
+    fn scrolled_list() -> (Screen, NodeId, Vec<NodeId>) {
+        use crate::css::scalar::Scalar;
+        let mut screen = Screen::new(20, 10);
+        let list = screen.dom.insert(NodeData::new("List"));
+        let items: Vec<NodeId> = (0..3)
+            .map(|_| screen.dom.insert_child(list, NodeData::new("Item")))
+            .collect();
+        let mut styles = Styles::new();
+        styles.width = Some(Scalar::cells(10.0));
+        styles.height = Some(Scalar::cells(4.0));
+        styles.overflow_y = Some(crate::css::styles::Overflow::Hidden);
+        screen.styles.insert(list, styles);
+        for &item in &items {
+            let mut styles = Styles::new();
+            styles.min_height = Some(Scalar::cells(3.0));
+            screen.styles.insert(item, styles);
+        }
+        screen.set_scroll_offset(list, Offset::new(0, 3));
+        screen.compute_layout().unwrap();
+        (screen, list, items)
+    }
+
+    #[test]
+    fn wheel_scrolls_nearest_scrollable_ancestor() {
+        let (mut screen, list, items) = scrolled_list();
+        // `overflow: hidden` does not scroll with the wheel.
+        assert_eq!(screen.scrollable_at(1, 1, false), None);
+
+        screen.styles.get_mut(&list).unwrap().overflow_y = Some(Overflow::Auto);
+        let state = screen.scroll_state(list).unwrap();
+        assert_eq!(state.content_size, Size::new(10, 9));
+        assert_eq!(state.max_scroll(), Offset::new(0, 5));
+        // The item under the pointer is not scrollable; its list is.
+        assert_eq!(screen.scrollable_at(1, 1, false), Some(list));
+        assert_eq!(screen.scrollable_at(1, 1, true), None);
+
+        screen.compositor.clear_dirty();
+        assert_eq!(screen.scroll_wheel(1, 1, 3, false), Some(list));
+        assert_eq!(screen.scroll_offset(list), Offset::new(0, 5));
+        assert!(screen.compositor.is_dirty());
+        assert_eq!(screen.widget_at(1, 0), Some(items[1]));
+        // Already at the end: nothing changes.
+        assert_eq!(screen.scroll_wheel(1, 1, 3, false), None);
+        assert_eq!(screen.scroll_wheel(1, 1, -9, false), Some(list));
+        assert_eq!(screen.scroll_offset(list), Offset::new(0, 0));
+    }
+
+    #[test]
+    fn scroll_overflow_draws_a_draggable_scrollbar() {
+        let (mut screen, list, _) = scrolled_list();
+        assert_eq!(screen.scrollbar_region(list), None);
+        let styles = screen.styles.get_mut(&list).unwrap();
+        styles.overflow_y = Some(Overflow::Scroll);
+        styles.scrollbar_color = Some("red".into());
+        // 4 of 9 rows visible, scrolled 3 of 5: a 2-row thumb one row down.
+        assert_eq!(screen.scrollbar_region(list), Some(Region::new(9, 0, 1, 4)));
+        assert_eq!(screen.scrollbar_thumb(list), Some((1, 2)));
+        assert_eq!(screen.scrollbar_at(9, 3), Some(list));
+        assert_eq!(screen.scrollbar_at(8, 3), None);
+
+        screen.draw_scrollbars();
+        let column: String = (0..4)
+            .map(|y| screen.compositor.get_cell(9, y).unwrap().ch)
+            .collect();
+        assert_eq!(column, "│██│");
+        let thumb = screen.compositor.get_cell(9, 1).unwrap();
+        assert_eq!(thumb.style.fg, crate::render::strip::Color::parse("red"));
+
+        assert!(screen.drag_scrollbar(list, 2));
+        assert_eq!(screen.scroll_offset(list), Offset::new(0, 5));
+        assert!(!screen.drag_scrollbar(list, 9));
+        assert!(screen.drag_scrollbar(list, 0));
+        assert_eq!(screen.scroll_offset(list), Offset::new(0, 0));
+
+        screen.styles.get_mut(&list).unwrap().scrollbar_size = Some(2);
+        assert_eq!(screen.scrollbar_region(list), Some(Region::new(8, 0, 2, 4)));
+        screen.styles.get_mut(&list).unwrap().scrollbar_size = Some(0);
+        assert_eq!(screen.scrollbar_region(list), None);
+    }
+
+    #[test]
+    fn scrollbar_behind_a_modal_cannot_be_grabbed() {
+        let (mut screen, list, items) = scrolled_list();
+        screen.styles.get_mut(&list).unwrap().overflow_y = Some(Overflow::Scroll);
+        screen.push_modal(items[0]);
+        assert_eq!(screen.scrollbar_at(9, 3), None);
+        screen.pop_modal();
+        assert_eq!(screen.scrollbar_at(9, 3), Some(list));
+    }
+
+    #[test]
+    fn geometry_accounts_for_scroll_and_clipping() {
+        let (screen, list, items) = scrolled_list();
+        assert_eq!(screen.scroll_offset(list), Offset::new(0, 3));
+        assert_eq!(screen.region_of(items[0]), Some(Region::new(0, -3, 10, 3)));
+        assert_eq!(screen.region_of(items[1]), Some(Region::new(0, 0, 10, 3)));
+        assert_eq!(screen.visible_region(items[0]), None);
+        assert_eq!(screen.visible_region(items[2]), Some(Region::new(0, 3, 10, 1)));
+    }
+
+    #[test]
+    fn widget_at_skips_clipped_content() {
+        let (mut screen, list, items) = scrolled_list();
+        assert_eq!(screen.widget_at(1, 0), Some(items[1]));
+        assert_eq!(screen.widget_at(1, 3), Some(items[2]));
+        assert_eq!(screen.widget_at(1, 5), None);
+
+        screen.set_scroll_offset(list, Offset::new(0, 0));
+        screen.compute_layout().unwrap();
+        assert_eq!(screen.widget_at(1, 0), Some(items[0]));
+    }
+
+    #[test]
+    fn visibility_observed_across_layouts() {
+        use crate::css::scalar::{Scalar, ScalarBox};
+
+        let mut screen = Screen::new(20, 10);
+        let root = screen.dom.insert(NodeData::new("Root"));
+        let node = screen.dom.insert_child(root, NodeData::new("Row"));
+        let mut styles = Styles::new();
+        styles.width = Some(Scalar::cells(20.0));
+        styles.height = Some(Scalar::cells(10.0));
+        screen.styles.insert(root, styles);
+        let mut styles = Styles::new();
+        styles.margin = Some(ScalarBox::new(
+            Scalar::cells(6.0),
+            Scalar::cells(0.0),
+            Scalar::cells(0.0),
+            Scalar::cells(0.0),
+        ));
+        styles.height = Some(Scalar::cells(2.0));
+        screen.styles.insert(node, styles);
+
+        let log = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let sink = log.clone();
+        screen.observe_visibility(node, move |id, visible| sink.borrow_mut().push((id, visible)));
+        screen.compute_layout().unwrap();
+        assert_eq!(*log.borrow(), [(node, true)]);
+
+        screen.resize(20, 5);
+        screen.compute_layout().unwrap();
+        assert_eq!(log.borrow().last(), Some(&(node, false)));
+
+        assert_eq!(screen.unobserve_visibility(node), 1);
+    }
+
+    #[test]
+    fn text_effects_tick_and_resolve() {
+        use crate::render::effects::EffectPhase;
+        use crate::render::strip::{Attrs, CellStyle, Strip};
+
+        let mut screen = Screen::new(10, 2);
+        screen.add_css("Label { text-style: blink; }").unwrap();
+        let label = screen.dom.insert(NodeData::new("Label"));
+        assert!(!screen.has_text_effects());
+        let styles = screen.cascade(label);
+        screen.styles.insert(label, styles);
+        assert!(screen.has_text_effects());
+
+        let mut strip = Strip::new(0, 0);
+        strip.push_str("hi", CellStyle::from_styles(&screen.styles[&label]));
+        screen.compositor.place_strips(&[strip], &Region::new(0, 0, 10, 2));
+        screen.compositor.clear_dirty();
+        let later = Instant::now() + screen.text_effects().period();
+        assert!(screen.tick_text_effects(later));
+        assert_eq!(screen.text_effects().phase(), EffectPhase::Off);
+        assert!(screen.compositor.is_dirty());
+        screen.compositor.apply_text_effects(EffectPhase::Off);
+        let cell = screen.compositor.get_cell(0, 0).unwrap();
+        assert_eq!((cell.ch, cell.style.attrs), (' ', Attrs::NONE));
+
+        screen.set_reduced_motion(true);
+        assert!(!screen.has_text_effects());
+        assert!(!screen.tick_text_effects(later + Duration::from_secs(1)));
+        assert_eq!(screen.text_effects().phase(), EffectPhase::Steady);
+    }
+
+    #[test]
+    fn hover_and_focus_restyle_nodes() {
+        use crate::css::scalar::Scalar;
+
+        let mut screen = Screen::new(20, 10);
+        screen
+            .add_css("Button:hover { height: 2; } Panel:focus Button { width: 5; }")
+            .unwrap();
+        let root = screen.dom.insert(NodeData::new("Panel").focusable(true));
+        let button = screen.dom.insert_child(root, NodeData::new("Button"));
+        screen.focus.rebuild(&screen.dom);
+        screen.styles.insert(button, Styles::new());
+        assert_eq!(screen.cascade(button).height, None);
+
+        assert!(screen.set_hovered(Some(button)));
+        assert!(!screen.styles.contains_key(&button));
+        assert_eq!(screen.cascade(button).height, Some(Scalar::cells(2.0)));
+        assert!(!screen.set_hovered(Some(button)));
+
+        // Focusing the panel restyles its descendants too.
+        screen.styles.insert(button, Styles::new());
+        screen.focus.focus_node(root);
+        assert!(screen.sync_focus_state());
+        assert!(!screen.styles.contains_key(&button));
+        assert_eq!(screen.cascade(button).width, Some(Scalar::cells(5.0)));
+
+        screen.set_hovered(None);
+        screen.focus.clear();
+        screen.sync_focus_state();
+        assert!(!screen.dom.get(button).unwrap().hovered);
+        assert_eq!(screen.cascade(button), Styles::new());
+    }
+
+    #[test]
+    fn focus_signal_follows_focus_changes() {
+        crate::reactive::signal::reset_runtime();
+        let mut screen = Screen::new(20, 10);
+        let root = screen.dom.insert(NodeData::new("Panel"));
+        let a = screen
+            .dom
+            .insert_child(root, NodeData::new("Button").focusable(true));
+        screen.focus.rebuild(&screen.dom);
+        let focus = screen.use_focus();
+        assert_eq!(focus.get_untracked(), None);
+
+        screen.focus.focus_node(a);
+        screen.sync_focus_state();
+        assert_eq!(focus.get_untracked(), Some(a));
+        assert_eq!(screen.use_focus().get_untracked(), Some(a));
+        screen.focus.clear();
+        screen.sync_focus_state();
+        assert_eq!(focus.get_untracked(), None);
+    }
+
+    #[test]
+    fn scroll_state_sets_scroll_pseudo_classes() {
+        use crate::css::scalar::Scalar;
+        use crate::geometry::Size;
+
+        let mut screen = Screen::new(20, 10);
+        screen
+            .add_css("Log:scrolled Header { height: 2; } Log:scroll-end Header { width: 5; }")
+            .unwrap();
+        let log = screen.dom.insert(NodeData::new("Log"));
+        let header = screen.dom.insert_child(log, NodeData::new("Header"));
+        let mut state = ScrollState::new(Size::new(10, 20), Size::new(10, 5));
+        screen.set_scroll_state(log, &state);
+        assert_eq!(screen.cascade(header), Styles::new());
+
+        state.scroll_by(0, 3);
+        screen.styles.insert(header, Styles::new());
+        screen.set_scroll_state(log, &state);
+        assert!(!screen.styles.contains_key(&header));
+        assert_eq!(screen.cascade(header).height, Some(Scalar::cells(2.0)));
+        assert_eq!(screen.cascade(header).width, None);
+
+        state.scroll_to(0, 15);
+        screen.set_scroll_state(log, &state);
+        assert!(screen.dom.get(log).unwrap().scroll_end);
+        assert_eq!(screen.cascade(header).width, Some(Scalar::cells(5.0)));
+
+        // Back at the top: neither applies.
+        state.scroll_to(0, 0);
+        screen.set_scroll_state(log, &state);
+        assert_eq!(screen.cascade(header), Styles::new());
+    }
+
+    #[test]
+    fn unit_scale_resizes_root_units() {
+        let mut screen = Screen::new(40, 20);
+        screen.add_css("Panel { height: 3u; padding: 1u 0; }").unwrap();
+        let root = screen.dom.insert(NodeData::new("Root"));
+        let panel = screen.dom.insert_child(root, NodeData::new("Panel"));
+        for id in [root, panel] {
+            let styles = screen.cascade(id);
+            screen.styles.insert(id, styles);
+        }
+        screen.compute_layout().unwrap();
+        assert_eq!(screen.region_of(panel).unwrap().height, 3);
+
+        screen.set_unit_scale(2.0);
+        assert_eq!(screen.unit_scale(), 2.0);
+        screen.compute_layout().unwrap();
+        assert_eq!(screen.region_of(panel).unwrap().height, 6);
+    }
+
+    #[test]
+    fn focus_announcement_uses_aria_label() {
+        let mut screen = Screen::new(20, 10);
+        let root = screen.dom.insert(NodeData::new("Container"));
+        let save = NodeData::new("Button").focusable(true).with_aria_label("Save");
+        let save = screen.dom.insert_child(root, save);
+        let table = screen.dom.insert_child(root, NodeData::new("DataTable").focusable(true));
+        screen.focus.rebuild(&screen.dom);
+        assert_eq!(screen.focus_announcement(), None);
+
+        screen.focus.focus_node(save);
+        screen.sync_focus_state();
+        assert_eq!(screen.focus_announcement().as_deref(), Some("Focused: Save button"));
+        screen.focus.focus_node(table);
+        screen.sync_focus_state();
+        assert_eq!(screen.focus_announcement().as_deref(), Some("Focused: data table"));
+    }
+
+    #[test]
+    fn density_class_drives_default_rules() {
+        use crate::css::scalar::Scalar;
+
+        let mut screen = Screen::new(20, 10);
+        let root = screen.dom.insert(NodeData::new("Root"));
+        let button = screen.dom.insert_child(root, NodeData::new("Button"));
+        screen.styles.insert(button, Styles::new());
+        assert_eq!(screen.cascade(button).height, None);
+
+        screen.set_density(Density::Compact);
+        assert!(screen.styles.is_empty());
+        assert_eq!(screen.cascade(button).height, Some(Scalar::cells(1.0)));
+
+        // App stylesheets override the built-in rules.
+        screen.add_css(".density-compact Button { height: 2; }").unwrap();
+        assert_eq!(screen.cascade(button).height, Some(Scalar::cells(2.0)));
+
+        assert_eq!(screen.cycle_density(), Density::Normal);
+        let classes = &screen.dom.get(root).unwrap().classes;
+        assert_eq!(classes, &["density-normal"]);
+    }
+
+    #[test]
+    fn mode_layer_swaps_root_classes_and_sheets() {
+        use crate::css::scalar::Scalar;
+        use crate::mode::Mode;
+
+        let mut screen = Screen::new(20, 10);
+        let root = screen.dom.insert(NodeData::new("Root").with_class("app"));
+        let button = screen.dom.insert_child(root, NodeData::new("Button"));
+        screen.add_css("Button { height: 3; }").unwrap();
+        let zen = Mode::new("zen")
+            .with_class("zen")
+            .with_css(".zen Button { height: 1; }")
+            .unwrap();
+
+        screen.set_mode_layer(zen.classes().to_vec(), zen.stylesheets().to_vec());
+        assert_eq!(screen.dom.get(root).unwrap().classes, ["app", "zen"]);
+        // Mode sheets cascade above the app's.
+        assert_eq!(screen.cascade(button).height, Some(Scalar::cells(1.0)));
+
+        screen.styles.insert(button, Styles::new());
+        screen.set_mode_layer(vec!["split".into()], Vec::new());
+        assert!(screen.styles.is_empty());
+        assert_eq!(screen.dom.get(root).unwrap().classes, ["app", "split"]);
+        assert_eq!(screen.cascade(button).height, Some(Scalar::cells(3.0)));
+    }
+
+    #[test]
+    fn root_inserted_later_gets_density_class() {
+        let mut screen = Screen::new(20, 10);
+        screen.set_density(Density::Comfortable);
+        let mut tx = Transaction::new();
+        tx.insert(NodeData::new("Root"));
+        screen.apply(tx).unwrap();
+        let root = screen.dom.root().unwrap();
+        assert!(screen.dom.get(root).unwrap().has_class("density-comfortable"));
+    }
+
+    #[test]
+    fn cascade_resolves_auto_color_with_theme_pair() {
+        let mut screen = Screen::new(20, 10);
+        let root = screen.dom.insert(NodeData::new("Root"));
+        screen
+            .add_css("Root { background: yellow; color: auto; }")
+            .unwrap();
+        assert_eq!(screen.cascade(root).color.as_deref(), Some("black"));
+
+        screen.contrast = ContrastPair::new(Color::White, Color::DarkBlue);
+        assert_eq!(screen.cascade(root).color.as_deref(), Some("dark_blue"));
+    }
 }
@@ -0,0 +1,227 @@
+//! Golden layout assertions: [`assert_region!`](crate::assert_region).
+//!
+//! Layout regression tests mostly compare a node's computed region against
+//! known values. `assert_region!` does that in one line, optionally allowing
+//! each edge to be off by a cell or so where fractional sizes round
+//! differently, and on failure prints the whole layout tree with the node in
+//! question marked, so the cause is visible without a debugger:
+//!
+//! ```ignore
+//! assert_region!(pilot, sidebar, Region::new(0, 1, 20, 22));
+//! assert_region!(pilot, main, Region::new(20, 1, 60, 22), tolerance = 1);
+//! ```
+//!
+//! The first argument is anything holding a laid-out [`Screen`]: a screen, an
+//! [`App`], or a [`Pilot`].
+
+use std::fmt::Write as _;
+
+use crate::app::App;
+use crate::dom::node::NodeId;
+use crate::geometry::Region;
+use crate::screen::Screen;
+
+use super::pilot::Pilot;
+
+// ---------------------------------------------------------------------------
+// AsScreen
+// ---------------------------------------------------------------------------
+
+/// Something holding a laid-out screen to check regions against.
+pub trait AsScreen {
+    /// The screen.
+    fn as_screen(&self) -> &Screen;
+}
+
+impl AsScreen for Screen {
+    fn as_screen(&self) -> &Screen {
+        self
+    }
+}
+
+impl AsScreen for App {
+    fn as_screen(&self) -> &Screen {
+        &self.screen
+    }
+}
+
+impl AsScreen for Pilot {
+    fn as_screen(&self) -> &Screen {
+        &self.app().screen
+    }
+}
+
+impl<T: AsScreen + ?Sized> AsScreen for &T {
+    fn as_screen(&self) -> &Screen {
+        (**self).as_screen()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Checks
+// ---------------------------------------------------------------------------
+
+/// Compare the computed region of `node` with `expected`, allowing each of
+/// x, y, width, and height to differ by up to `tolerance` cells.
+///
+/// On a mismatch, returns a message with both regions and the layout tree.
+pub fn check_region(
+    screen: &Screen,
+    node: NodeId,
+    expected: Region,
+    tolerance: i32,
+) -> Result<(), String> {
+    let actual = screen.region_of(node);
+    let close = |a: i32, b: i32| (a - b).abs() <= tolerance;
+    if actual.is_some_and(|r| {
+        close(r.x, expected.x)
+            && close(r.y, expected.y)
+            && close(r.width, expected.width)
+            && close(r.height, expected.height)
+    }) {
+        return Ok(());
+    }
+    let mut message = format!("region of {} does not match\n", label(screen, node));
+    let tolerance = if tolerance > 0 {
+        format!(" ±{tolerance}")
+    } else {
+        String::new()
+    };
+    let _ = writeln!(
+        message,
+        "  expected: {}{tolerance}",
+        format_region(Some(expected))
+    );
+    let _ = writeln!(message, "    actual: {}", format_region(actual));
+    message.push_str("layout tree:\n");
+    message.push_str(&layout_tree(screen, Some(node)));
+    Err(message)
+}
+
+/// The layout tree as indented text, one node per line with its region,
+/// marking `highlight` with an arrow.
+///
+/// ```text
+/// Screen#screen-1 (0, 0) 40x10
+///   Button#save.primary (0, 0) 12x1  <--
+/// ```
+pub fn layout_tree(screen: &Screen, highlight: Option<NodeId>) -> String {
+    let mut out = String::new();
+    let Some(root) = screen.dom.root() else {
+        out.push_str("(empty)\n");
+        return out;
+    };
+    for id in screen.dom.walk_depth_first(root) {
+        let depth = screen.dom.ancestors(id).len();
+        let marker = if Some(id) == highlight { "  <--" } else { "" };
+        let region = format_region(screen.region_of(id));
+        let _ = writeln!(
+            out,
+            "{:indent$}{} {region}{marker}",
+            "",
+            label(screen, id),
+            indent = depth * 2
+        );
+    }
+    out
+}
+
+/// Panic with [`check_region`]'s message on a mismatch. Used by
+/// [`assert_region!`](crate::assert_region).
+#[track_caller]
+pub fn assert_region_impl(screen: &impl AsScreen, node: NodeId, expected: Region, tolerance: i32) {
+    if let Err(message) = check_region(screen.as_screen(), node, expected, tolerance) {
+        panic!("{message}");
+    }
+}
+
+/// `Type#id.class` for a node.
+fn label(screen: &Screen, id: NodeId) -> String {
+    let Some(data) = screen.dom.get(id) else {
+        return format!("{id:?} (removed)");
+    };
+    let mut label = data.widget_type.clone();
+    if let Some(widget_id) = data.widget_id() {
+        label.push('#');
+        label.push_str(widget_id);
+    }
+    for class in &data.classes {
+        label.push('.');
+        label.push_str(class);
+    }
+    label
+}
+
+fn format_region(region: Option<Region>) -> String {
+    match region {
+        Some(r) => format!("({}, {}) {}x{}", r.x, r.y, r.width, r.height),
+        None => "not laid out".to_owned(),
+    }
+}
+
+/// Assert that a node's computed region matches, optionally within a
+/// tolerance in cells: `assert_region!(pilot, node, Region::new(0, 0, 10, 1))`
+/// or `assert_region!(pilot, node, expected, tolerance = 1)`. On failure the
+/// panic message includes the layout tree.
+#[macro_export]
+macro_rules! assert_region {
+    ($screen:expr, $node:expr, $expected:expr $(,)?) => {
+        $crate::testing::layout::assert_region_impl(&$screen, $node, $expected, 0)
+    };
+    ($screen:expr, $node:expr, $expected:expr, tolerance = $tolerance:expr $(,)?) => {
+        $crate::testing::layout::assert_region_impl(&$screen, $node, $expected, $tolerance)
+    };
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::mount_widget;
+    use crate::widget::WidgetExt;
+    use crate::widgets::Button;
+
+    fn mounted() -> (Pilot, NodeId) {
+        let css = "Button { width: 12; height: 3; }";
+        let (pilot, button) = mount_widget(
+            Button::new("Save").with_id("save").add_class("primary"),
+            css,
+            (40, 10),
+        );
+        (pilot, button.node())
+    }
+
+    #[test]
+    fn exact_and_tolerant_matches_pass() {
+        let (pilot, node) = mounted();
+        crate::assert_region!(pilot, node, Region::new(0, 0, 12, 3));
+        crate::assert_region!(pilot, node, Region::new(1, 0, 11, 4), tolerance = 1);
+        crate::assert_region!(pilot.app(), node, Region::new(0, 0, 12, 3));
+    }
+
+    #[test]
+    fn mismatch_reports_regions_and_tree() {
+        let (pilot, node) = mounted();
+        let message =
+            check_region(pilot.as_screen(), node, Region::new(0, 0, 10, 3), 1).unwrap_err();
+        assert_eq!(
+            message,
+            "region of Button#save.primary does not match\n  \
+             expected: (0, 0) 10x3 ±1\n    \
+             actual: (0, 0) 12x3\n\
+             layout tree:\n\
+             Screen#screen-1 (0, 0) 40x10\n  \
+             Button#save.primary (0, 0) 12x3  <--\n"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "layout tree:")]
+    fn assert_region_panics_on_mismatch() {
+        let (pilot, node) = mounted();
+        crate::assert_region!(pilot, node, Region::new(5, 5, 12, 3), tolerance = 2);
+    }
+}
@@ -1,11 +1,19 @@
-//! Headless testing framework: Pilot, snapshot helpers.
+//! Headless testing framework: Pilot, snapshot helpers, widget mounting,
+//! layout assertions.
 //!
 //! Use the [`Pilot`] to programmatically drive an [`App`](crate::app::App) without
 //! a real terminal. Use [`render_to_string`] and related helpers to capture widget
-//! output as plain text for snapshot-style assertions.
+//! output as plain text for snapshot-style assertions. Use [`mount_widget`] to
+//! host a single widget with a CSS fixture and test it through its
+//! [`WidgetHandle`]. Use [`assert_region!`](crate::assert_region) to check
+//! computed regions in layout tests.
 
+pub mod layout;
+pub mod mount;
 pub mod pilot;
 pub mod snapshot;
 
+pub use layout::{check_region, layout_tree, AsScreen};
+pub use mount::{mount_widget, WidgetHandle};
 pub use pilot::Pilot;
 pub use snapshot::render_to_string;
@@ -0,0 +1,208 @@
+//! Mount a single widget in a headless app for unit tests.
+//!
+//! [`mount_widget`] builds the smallest app that can host a widget: a root
+//! node the size of the terminal with the widget (and its child widgets)
+//! under it, styled by the widget's default CSS plus a test fixture, and laid
+//! out. It returns a [`Pilot`] to drive the app and a typed
+//! [`WidgetHandle`] to reach the widget itself, so third-party widget authors
+//! can test rendering, styling, and the messages their widget emits without
+//! writing an app:
+//!
+//! ```ignore
+//! let (mut pilot, counter) = mount_widget(Counter::new(), "Counter { width: 10; height: 1; }", (20, 5));
+//! assert_eq!(counter.render(&pilot), "Count: 0");
+//!
+//! let changed = counter.update(&mut pilot, |c| c.increment());
+//! counter.post(&mut pilot, changed);
+//! pilot.assert_posted(&CountChanged(1));
+//! ```
+
+use std::marker::PhantomData;
+
+use crate::css::parser::parse_css;
+use crate::css::styles::Styles;
+use crate::css::stylesheet::CompiledStylesheet;
+use crate::dom::node::{NodeData, NodeId};
+use crate::event::message::{Envelope, Message};
+use crate::geometry::Region;
+use crate::widget::reconcile::insert_subtree;
+use crate::widget::Widget;
+
+use super::pilot::Pilot;
+use super::snapshot::strips_to_string;
+
+// ---------------------------------------------------------------------------
+// mount_widget
+// ---------------------------------------------------------------------------
+
+/// Sizes the root node to the terminal.
+const ROOT_CSS: &str = "Screen { width: 100%; height: 100%; }";
+
+/// Mount `widget` alone in a headless app of `size` (columns, rows), styled
+/// by its default CSS and then `css`, and lay it out.
+///
+/// Widgets do not stretch to fill the screen on their own; give the widget a
+/// `width` and `height` in `css` if its content does not size it.
+///
+/// # Panics
+///
+/// Panics if `css` or the widget's default CSS does not parse, or if the
+/// layout cannot be computed, failing the test.
+pub fn mount_widget<W: Widget + 'static>(
+    widget: W,
+    css: &str,
+    size: (u16, u16),
+) -> (Pilot, WidgetHandle<W>) {
+    let mut pilot = Pilot::new(size.0, size.1);
+    let screen = &mut pilot.app_mut().screen;
+    screen
+        .add_css(ROOT_CSS)
+        .expect("the root stylesheet parses");
+    match parse_css(widget.default_css()) {
+        Ok(sheet) => screen.css.push(CompiledStylesheet::compile(&sheet, true)),
+        Err(error) => panic!(
+            "default CSS of {} does not parse: {error}",
+            widget.widget_type()
+        ),
+    }
+    if let Err(error) = screen.add_css(css) {
+        panic!("fixture CSS does not parse: {error}");
+    }
+
+    let root = screen.dom.insert(NodeData::new("Screen"));
+    if let Err(error) = screen.transaction(|tx| insert_subtree(tx, root, &widget)) {
+        panic!("mounting {} failed: {error}", widget.widget_type());
+    }
+    let node = screen.dom.children(root)[0];
+    screen.restyle();
+    if let Err(error) = screen.compute_layout() {
+        panic!("layout failed: {error}");
+    }
+    pilot.mounted = Some(Box::new(widget));
+    let handle = WidgetHandle {
+        node,
+        widget: PhantomData,
+    };
+    (pilot, handle)
+}
+
+// ---------------------------------------------------------------------------
+// WidgetHandle
+// ---------------------------------------------------------------------------
+
+/// A typed handle to the widget mounted by [`mount_widget`].
+///
+/// The widget lives in the [`Pilot`]; the handle borrows it from there.
+#[derive(Debug)]
+pub struct WidgetHandle<W> {
+    node: NodeId,
+    widget: PhantomData<fn() -> W>,
+}
+
+impl<W> Clone for WidgetHandle<W> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<W> Copy for WidgetHandle<W> {}
+
+impl<W: Widget + 'static> WidgetHandle<W> {
+    /// The widget's DOM node.
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// Borrow the mounted widget.
+    pub fn get<'a>(&self, pilot: &'a Pilot) -> &'a W {
+        pilot
+            .mounted
+            .as_ref()
+            .and_then(|w| w.as_any().downcast_ref())
+            .expect("the pilot holds the widget this handle was mounted with")
+    }
+
+    /// Run `f` on the mounted widget and return its result, e.g. the
+    /// message a key or mouse handler produced.
+    pub fn update<R>(&self, pilot: &mut Pilot, f: impl FnOnce(&mut W) -> R) -> R {
+        let widget = pilot
+            .mounted
+            .as_mut()
+            .and_then(|w| w.as_any_mut().downcast_mut())
+            .expect("the pilot holds the widget this handle was mounted with");
+        f(widget)
+    }
+
+    /// Post `message` from the widget's node, as the app would for a message
+    /// the widget emitted.
+    pub fn post(&self, pilot: &mut Pilot, message: impl Message) {
+        pilot
+            .app_mut()
+            .dispatcher
+            .push(Envelope::new(message, self.node));
+    }
+
+    /// The widget's laid-out region on screen.
+    pub fn region(&self, pilot: &Pilot) -> Region {
+        pilot.app().screen.region_of(self.node).unwrap_or_default()
+    }
+
+    /// The widget's computed styles.
+    pub fn styles(&self, pilot: &Pilot) -> Styles {
+        pilot
+            .app()
+            .screen
+            .styles
+            .get(&self.node)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Render the widget at its laid-out size with its computed styles, as
+    /// plain text with trailing spaces trimmed from each row.
+    pub fn render(&self, pilot: &Pilot) -> String {
+        let region = self.region(pilot);
+        let origin = Region::new(0, 0, region.width, region.height);
+        let strips = self.get(pilot).render(origin, &self.styles(pilot));
+        strips_to_string(&strips, region.width, region.height)
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::message::Custom;
+    use crate::widgets::{Button, Static};
+
+    #[test]
+    fn mounts_styles_and_lays_out_the_widget() {
+        let css = "Button { width: 12; height: 1; color: red; }";
+        let (pilot, button) = mount_widget(Button::new("Save"), css, (40, 10));
+        assert_eq!(button.region(&pilot), Region::new(0, 0, 12, 1));
+        assert_eq!(button.styles(&pilot).color.as_deref(), Some("red"));
+        assert_eq!(button.render(&pilot), "    Save");
+        assert_eq!(button.get(&pilot).label(), "Save");
+        assert_eq!(pilot.widget_id(button.node()), Some("button-1"));
+    }
+
+    #[test]
+    fn posted_messages_can_be_asserted() {
+        let css = "Static { width: 10; height: 1; }";
+        let (mut pilot, label) = mount_widget(Static::new("hi"), css, (20, 2));
+        pilot.assert_no_messages();
+        let message = label.update(&mut pilot, |_| Custom::new("ping"));
+        label.post(&mut pilot, message);
+        pilot.assert_posted(&Custom::new("ping"));
+        pilot.assert_no_messages();
+    }
+
+    #[test]
+    #[should_panic(expected = "fixture CSS does not parse")]
+    fn invalid_fixture_css_panics() {
+        mount_widget(Static::new("hi"), "Static { width: ; ", (20, 2));
+    }
+}
@@ -1,14 +1,22 @@
 //! Pilot: programmatic interaction with a headless App.
 //!
 //! The `Pilot` wraps an [`App`](crate::app::App) in headless mode and provides
-//! methods to simulate user input (key presses, mouse clicks, resize), process
-//! messages, and render widgets to text for snapshot testing.
+//! methods to simulate user input (key presses, clicks, drags, resize), process
+//! messages, and render widgets to text for snapshot testing. Nodes can be
+//! looked up by widget id, explicit or generated (`button-2`), and
+//! [`Pilot::duplicate_ids`] reports explicit ids used more than once.
+
+use std::fmt;
 
 use crate::app::{App, AppConfig};
 use crate::css::styles::Styles;
+use crate::dom::node::NodeId;
+use crate::dom::query::DuplicateId;
 use crate::event::input::{
     InputEvent, Key, KeyEvent, Modifiers, MouseAction, MouseBtn, MouseEvent,
 };
+use crate::event::keys::parse_keys;
+use crate::event::message::{Envelope, Message};
 use crate::geometry::Region;
 use crate::render::strip::Strip;
 use crate::widget::Widget;
@@ -35,6 +43,8 @@ use crate::widget::Widget;
 /// ```
 pub struct Pilot {
     app: App,
+    /// The widget mounted by [`mount_widget`](super::mount::mount_widget).
+    pub(super) mounted: Option<Box<dyn Widget>>,
 }
 
 impl Pilot {
@@ -42,6 +52,7 @@ impl Pilot {
     pub fn new(width: u16, height: u16) -> Self {
         Self {
             app: App::new_headless(width, height),
+            mounted: None,
         }
     }
 
@@ -60,7 +71,7 @@ impl Pilot {
     pub fn with_config(config: AppConfig) -> Self {
         let mut app = App::new_headless(80, 24);
         app.config = config;
-        Self { app }
+        Self { app, mounted: None }
     }
 
     // ── Input simulation ─────────────────────────────────────────────
@@ -77,6 +88,46 @@ impl Pilot {
         self.app.handle_input(event);
     }
 
+    /// Simulate the keys written in `keys`, a key descriptor such as
+    /// `"ctrl+s"`, `"alt+enter"` or the sequence `"g g"`, in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `keys` is not a valid descriptor, failing the test.
+    pub fn press(&mut self, keys: &str) {
+        let events = parse_keys(keys).unwrap_or_else(|e| panic!("invalid keys {keys:?}: {e}"));
+        for event in events {
+            self.app.handle_input(InputEvent::Key(event));
+        }
+    }
+
+    /// Inject `event` through the app's input queue, the path terminal input
+    /// read on the input thread takes, and handle it.
+    pub fn inject(&mut self, event: InputEvent) {
+        self.app.input_sender().send(event);
+        self.app.pump_input();
+    }
+
+    /// Simulate a key press handled by a widget's own key handler, e.g.
+    /// `|key| input.handle_key(key)`, posting the messages it returns from
+    /// `sender`. Call [`process`](Self::process) to deliver them.
+    pub fn press_key_on(
+        &mut self,
+        sender: NodeId,
+        key: Key,
+        modifiers: Modifiers,
+        handle: impl FnOnce(&KeyEvent) -> Vec<Box<dyn Message>>,
+    ) {
+        for message in handle(&KeyEvent::new(key, modifiers)) {
+            self.app.dispatcher.push(Envelope {
+                message,
+                sender,
+                target: None,
+                handled: false,
+            });
+        }
+    }
+
     /// Simulate typing each character of `text` as individual key presses.
     ///
     /// Each character is sent as a `Key::Char(ch)` with no modifiers.
@@ -86,15 +137,38 @@ impl Pilot {
         }
     }
 
-    /// Simulate a left-button mouse click at (x, y).
+    /// Simulate a left-button mouse click (press and release) at (x, y).
     pub fn click(&mut self, x: u16, y: u16) {
-        let event = InputEvent::Mouse(MouseEvent {
-            kind: MouseAction::Down(MouseBtn::Left),
+        self.mouse(MouseAction::Down(MouseBtn::Left), x, y);
+        self.mouse(MouseAction::Up(MouseBtn::Left), x, y);
+    }
+
+    /// Simulate two left-button clicks at (x, y) in quick succession.
+    pub fn double_click(&mut self, x: u16, y: u16) {
+        self.click(x, y);
+        self.click(x, y);
+    }
+
+    /// Simulate dragging with the left button held from `from` to `to`, one
+    /// motion event per cell along the way.
+    pub fn drag(&mut self, from: (u16, u16), to: (u16, u16)) {
+        let (mut x, mut y) = from;
+        self.mouse(MouseAction::Down(MouseBtn::Left), x, y);
+        while (x, y) != to {
+            x = step_toward(x, to.0);
+            y = step_toward(y, to.1);
+            self.mouse(MouseAction::Drag(MouseBtn::Left), x, y);
+        }
+        self.mouse(MouseAction::Up(MouseBtn::Left), x, y);
+    }
+
+    fn mouse(&mut self, kind: MouseAction, x: u16, y: u16) {
+        self.app.handle_input(InputEvent::Mouse(MouseEvent {
+            kind,
             x,
             y,
             modifiers: Modifiers::NONE,
-        });
-        self.app.handle_input(event);
+        }));
     }
 
     /// Simulate a terminal resize to the given dimensions.
@@ -106,8 +180,14 @@ impl Pilot {
     // ── Processing ───────────────────────────────────────────────────
 
     /// Process all pending messages in the app's dispatcher.
+    ///
+    /// # Panics
+    ///
+    /// Panics if message handling fails, failing the test.
     pub fn process(&mut self) {
-        self.app.handle_messages();
+        if let Err(error) = self.app.handle_messages() {
+            panic!("message handling failed: {error}");
+        }
     }
 
     /// Simulate one frame: process all pending messages.
@@ -119,6 +199,43 @@ impl Pilot {
         self.process();
     }
 
+    // ── Messages ─────────────────────────────────────────────────────
+
+    /// Remove and return the messages posted but not yet processed.
+    pub fn take_messages(&mut self) -> Vec<Envelope> {
+        self.app.dispatcher.drain()
+    }
+
+    /// Assert that a message equal to `expected` was posted, and drop every
+    /// pending message.
+    ///
+    /// # Panics
+    ///
+    /// Panics, listing the pending messages, if none equals `expected`.
+    pub fn assert_posted<M: Message + PartialEq + fmt::Debug>(&mut self, expected: &M) {
+        let messages = self.take_messages();
+        if !messages
+            .iter()
+            .any(|m| m.downcast_ref::<M>() == Some(expected))
+        {
+            let names: Vec<&str> = messages.iter().map(|m| m.message.message_name()).collect();
+            panic!("expected {expected:?} to be posted; pending: {names:?}");
+        }
+    }
+
+    /// Assert that no message is pending.
+    ///
+    /// # Panics
+    ///
+    /// Panics, listing the pending messages, if there are any.
+    pub fn assert_no_messages(&mut self) {
+        let messages = self.take_messages();
+        if !messages.is_empty() {
+            let names: Vec<&str> = messages.iter().map(|m| m.message.message_name()).collect();
+            panic!("expected no messages; pending: {names:?}");
+        }
+    }
+
     // ── Query ────────────────────────────────────────────────────────
 
     /// Borrow the underlying app immutably.
@@ -136,6 +253,23 @@ impl Pilot {
         !self.app.should_quit()
     }
 
+    /// The node with the given widget id: an explicit id, or a generated one
+    /// such as `button-2` for widgets without one.
+    pub fn node(&self, widget_id: &str) -> Option<NodeId> {
+        self.app.screen.dom.query_by_widget_id(widget_id)
+    }
+
+    /// The widget id of `node`: its explicit id, or its generated one.
+    pub fn widget_id(&self, node: NodeId) -> Option<&str> {
+        self.app.screen.dom.get(node)?.widget_id()
+    }
+
+    /// Explicit ids used by more than one node on the screen, which make
+    /// `#id` selectors ambiguous. Assert this is empty in tests.
+    pub fn duplicate_ids(&self) -> Vec<DuplicateId> {
+        self.app.screen.dom.duplicate_ids()
+    }
+
     // ── Render helpers ───────────────────────────────────────────────
 
     /// Render a widget into strips within a region of the given dimensions.
@@ -157,6 +291,15 @@ impl Pilot {
     }
 }
 
+/// One cell from `from` toward `to`.
+fn step_toward(from: u16, to: u16) -> u16 {
+    match from.cmp(&to) {
+        std::cmp::Ordering::Less => from + 1,
+        std::cmp::Ordering::Equal => from,
+        std::cmp::Ordering::Greater => from - 1,
+    }
+}
+
 // ===========================================================================
 // Tests
 // ===========================================================================
@@ -165,6 +308,7 @@ impl Pilot {
 mod tests {
     use super::*;
     use crate::dom::node::NodeData;
+    use crate::event::binding::BindingAction;
     use crate::widgets::{Button, Footer, Header, Input, Static};
 
     /// Helper: create a pilot with a DOM so key bindings can produce messages.
@@ -249,6 +393,35 @@ mod tests {
         assert_eq!(pilot.app().dispatcher.pending_count(), 1);
     }
 
+    #[test]
+    fn press_sends_descriptor_sequences() {
+        let mut pilot = pilot_with_dom();
+        pilot
+            .app_mut()
+            .bindings
+            .bind_str("g g", BindingAction::Custom("top".into()))
+            .unwrap();
+        pilot.press("g");
+        assert!(pilot.app().dispatcher.is_empty());
+        pilot.press("g");
+        assert_eq!(pilot.app().dispatcher.pending_count(), 1);
+        pilot.press("g g ctrl+c");
+        assert_eq!(pilot.app().dispatcher.pending_count(), 3);
+    }
+
+    #[test]
+    fn inject_goes_through_the_input_queue() {
+        let mut pilot = pilot_with_dom();
+        pilot.inject(InputEvent::Key(KeyEvent::new(Key::Tab, Modifiers::NONE)));
+        assert_eq!(pilot.app().dispatcher.pending_count(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid keys \"ctrl+nope\"")]
+    fn press_panics_on_invalid_descriptor() {
+        Pilot::new(80, 24).press("ctrl+nope");
+    }
+
     // ── Type text ────────────────────────────────────────────────────
 
     #[test]
@@ -272,9 +445,8 @@ mod tests {
     #[test]
     fn click_sends_mouse_event() {
         let mut pilot = Pilot::new(80, 24);
-        // Mouse events are currently unhandled at app level, so no panic
         pilot.click(10, 5);
-        // The app doesn't queue messages for mouse events currently
+        // Without a DOM there is no node to send the Click from.
         assert!(pilot.app().dispatcher.is_empty());
     }
 
@@ -285,6 +457,25 @@ mod tests {
         assert!(pilot.is_running());
     }
 
+    #[test]
+    fn click_double_click_and_drag_send_gestures() {
+        let mut pilot = pilot_with_dom();
+        let names = |pilot: &mut Pilot| -> Vec<String> {
+            let messages = pilot.app.dispatcher.drain();
+            messages
+                .iter()
+                .map(|m| m.message.message_name().to_owned())
+                .collect()
+        };
+        pilot.double_click(3, 3);
+        assert_eq!(names(&mut pilot), ["Click", "Click", "DoubleClick"]);
+        pilot.drag((1, 1), (3, 2));
+        assert_eq!(
+            names(&mut pilot),
+            ["DragStart", "DragMove", "DragMove", "DragEnd"]
+        );
+    }
+
     // ── Resize ───────────────────────────────────────────────────────
 
     #[test]
@@ -424,4 +615,20 @@ mod tests {
         input.insert_char('i');
         assert_eq!(input.value(), "Hi");
     }
+
+    #[test]
+    fn nodes_are_found_by_widget_id() {
+        let mut pilot = pilot_with_dom();
+        let root = pilot.app().screen.dom.root().unwrap();
+        let a = pilot.app().screen.dom.children(root)[0];
+        assert_eq!(pilot.widget_id(a), Some("a-1"));
+        assert_eq!(pilot.node("a-1"), Some(a));
+        assert!(pilot.duplicate_ids().is_empty());
+
+        let dom = &mut pilot.app_mut().screen.dom;
+        let first = dom.insert_child(root, NodeData::new("Button").with_id("ok"));
+        let second = dom.insert_child(root, NodeData::new("Button").with_id("ok"));
+        assert_eq!(pilot.widget_id(second), Some("ok"));
+        assert_eq!(pilot.duplicate_ids()[0].nodes, [first, second]);
+    }
 }
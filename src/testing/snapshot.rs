@@ -96,25 +96,17 @@ pub fn strips_to_string(strips: &[Strip], width: i32, height: i32) -> String {
 /// Reads every cell from the compositor's screen buffer and assembles them into
 /// rows. Each row is right-trimmed of spaces and rows are joined with `'\n'`.
 pub fn compositor_to_string(compositor: &Compositor) -> String {
-    let w = compositor.width;
-    let h = compositor.height;
-
-    if w == 0 || h == 0 {
+    if compositor.width == 0 || compositor.height == 0 {
         return String::new();
     }
 
-    let mut lines = Vec::with_capacity(h as usize);
-
-    for y in 0..h {
-        let mut row = String::with_capacity(w as usize);
-        for x in 0..w {
-            match compositor.get_cell(x, y) {
-                Some(cell) => row.push(cell.ch),
-                None => row.push(' '),
-            }
-        }
-        lines.push(row.trim_end().to_owned());
-    }
+    let lines: Vec<String> = compositor
+        .rows()
+        .map(|row| {
+            let text: String = row.iter().map(|cell| cell.ch).collect();
+            text.trim_end().to_owned()
+        })
+        .collect();
 
     lines.join("\n")
 }
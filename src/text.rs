@@ -0,0 +1,451 @@
+//! Styled text and the markup mini-language.
+//!
+//! A [`Text`] is a run of [`Span`]s, each a piece of text with a style laid
+//! over the widget's CSS style when rendered. Widgets that show a string —
+//! [`Static`](crate::widgets::Static), [`Button`](crate::widgets::Button)
+//! labels, and [`Header`](crate::widgets::Header) titles — parse it as markup,
+//! so a single line can mix styles:
+//!
+//! ```ignore
+//! Static::new("[bold]Status:[/] [green]ok[/] ([dim italic]3 jobs[/])");
+//! Button::new("[u]S[/]ave");
+//! ```
+//!
+//! A tag in square brackets opens a style that lasts until its closing tag:
+//!
+//! - attributes: `bold` (`b`), `dim` (`d`), `italic` (`i`), `underline`
+//!   (`u`), `strike` (`s`), `reverse` (`r`), `blink`, `pulse`
+//! - a foreground color: any color [`Color::parse`] accepts, e.g. `red` or
+//!   `#ff8800`; `on <color>` sets the background
+//! - `link=<url>` makes the text a hyperlink
+//!
+//! `[/]` closes the most recent tag and `[/bold red]` the most recent tag
+//! with exactly that text; tags still open at the end run to the end.
+//! Brackets that cannot start a tag (`[1]`, `[ ]`, a `[` with no `]`) are
+//! kept as written, and `\[` writes a literal `[` (`\\` a literal `\`);
+//! [`escape`] does this for untrusted strings. [`Text::parse`] reports bad
+//! markup, while [`Text::from_markup`], which widgets use, shows it as plain
+//! text instead.
+
+use thiserror::Error;
+
+use crate::css::styles::TextOverflow;
+use crate::render::strip::{Attrs, CellStyle, Color, Strip};
+use crate::render::text::{fit, text_width, wrap_lines};
+
+// ---------------------------------------------------------------------------
+// Errors
+// ---------------------------------------------------------------------------
+
+/// Error returned when markup cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum MarkupError {
+    /// A word in a tag is neither an attribute, a color, nor a link.
+    #[error("unknown style {word:?} in tag [{tag}]")]
+    UnknownStyle { tag: String, word: String },
+    /// A closing tag with no open tag to close.
+    #[error("closing tag [/{0}] does not match an open tag")]
+    UnmatchedClose(String),
+}
+
+// ---------------------------------------------------------------------------
+// Span
+// ---------------------------------------------------------------------------
+
+/// A piece of text with one style.
+///
+/// The style is an overlay: unset colors and links fall back to the style
+/// the text is rendered with, and attributes add to it (see [`overlay`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub text: String,
+    pub style: CellStyle,
+}
+
+/// `top` laid over `base`: its colors and link where set, and the
+/// attributes of both.
+pub fn overlay(base: CellStyle, top: CellStyle) -> CellStyle {
+    CellStyle {
+        fg: top.fg.or(base.fg),
+        bg: top.bg.or(base.bg),
+        attrs: base.attrs | top.attrs,
+        link: top.link.or(base.link),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Text
+// ---------------------------------------------------------------------------
+
+/// Text made of styled [`Span`]s.
+///
+/// Adjacent spans never share a style, so each span renders as one run of
+/// cells.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Text {
+    plain: String,
+    spans: Vec<Span>,
+}
+
+impl Text {
+    /// Create empty text.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Unstyled text, taken as written without parsing markup.
+    pub fn raw(text: impl Into<String>) -> Self {
+        let mut out = Self::new();
+        out.push(&text.into(), CellStyle::default());
+        out
+    }
+
+    /// Parse `markup` into styled text.
+    pub fn parse(markup: &str) -> Result<Self, MarkupError> {
+        let mut text = Self::new();
+        let mut tags: Vec<(&str, CellStyle)> = Vec::new();
+        let mut literal = String::new();
+        let mut rest = markup;
+        while let Some(ch) = rest.chars().next() {
+            if ch == '\\' && (rest[1..].starts_with('[') || rest[1..].starts_with('\\')) {
+                literal.push_str(&rest[1..2]);
+                rest = &rest[2..];
+                continue;
+            }
+            let Some(tag) = (ch == '[').then(|| tag_at(rest)).flatten() else {
+                literal.push(ch);
+                rest = &rest[ch.len_utf8()..];
+                continue;
+            };
+            text.push(&std::mem::take(&mut literal), current_style(&tags));
+            if let Some(name) = tag.strip_prefix('/') {
+                let name = name.trim();
+                let index = match name {
+                    "" => tags.len().checked_sub(1),
+                    _ => tags.iter().rposition(|(open, _)| *open == name),
+                };
+                match index {
+                    Some(index) => {
+                        tags.remove(index);
+                    }
+                    None => return Err(MarkupError::UnmatchedClose(name.to_owned())),
+                }
+            } else {
+                tags.push((tag.trim(), parse_style(tag)?));
+            }
+            rest = &rest[tag.len() + 2..];
+        }
+        text.push(&literal, current_style(&tags));
+        Ok(text)
+    }
+
+    /// Parse `markup`, or take it as unstyled text if it does not parse.
+    pub fn from_markup(markup: &str) -> Self {
+        Self::parse(markup).unwrap_or_else(|_| Self::raw(markup))
+    }
+
+    /// Build text from characters and their styles, merging runs.
+    pub fn from_styled_chars(chars: impl IntoIterator<Item = (char, CellStyle)>) -> Self {
+        let mut text = Self::new();
+        let mut buf = [0; 4];
+        for (ch, style) in chars {
+            text.push(ch.encode_utf8(&mut buf), style);
+        }
+        text
+    }
+
+    /// Append `text` in `style`, extending the last span if it has the same
+    /// style.
+    pub fn push(&mut self, text: &str, style: CellStyle) {
+        if text.is_empty() {
+            return;
+        }
+        self.plain.push_str(text);
+        match self.spans.last_mut() {
+            Some(last) if last.style == style => last.text.push_str(text),
+            _ => self.spans.push(Span {
+                text: text.to_owned(),
+                style,
+            }),
+        }
+    }
+
+    /// Append the spans of `other`.
+    pub fn append(&mut self, other: &Text) {
+        for span in &other.spans {
+            self.push(&span.text, span.style);
+        }
+    }
+
+    /// The styled spans.
+    pub fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+
+    /// The text without styles or markup.
+    pub fn plain(&self) -> &str {
+        &self.plain
+    }
+
+    /// The width of the text in cells.
+    pub fn width(&self) -> usize {
+        text_width(&self.plain)
+    }
+
+    /// Each character with its style.
+    pub fn chars(&self) -> impl Iterator<Item = (char, CellStyle)> + '_ {
+        self.spans
+            .iter()
+            .flat_map(|span| span.text.chars().map(move |ch| (ch, span.style)))
+    }
+
+    /// The text split at `'\n'`, keeping styles across lines.
+    pub fn lines(&self) -> Vec<Text> {
+        let mut lines = vec![Text::new()];
+        for span in &self.spans {
+            for (i, part) in span.text.split('\n').enumerate() {
+                if i > 0 {
+                    lines.push(Text::new());
+                }
+                if let Some(line) = lines.last_mut() {
+                    line.push(part, span.style);
+                }
+            }
+        }
+        lines
+    }
+
+    /// `len` cells starting `start` cells in.
+    pub fn slice(&self, start: usize, len: usize) -> Text {
+        Self::from_styled_chars(self.chars().skip(start).take(len))
+    }
+
+    /// Shorten the text to at most `width` cells, like
+    /// [`fit`](crate::render::text::fit), keeping each character's style.
+    pub fn fit(&self, width: usize, overflow: TextOverflow) -> Text {
+        if self.width() <= width {
+            return self.clone();
+        }
+        let fitted = fit(&self.plain, width, overflow);
+        self.restyle(&[fitted]).pop().unwrap_or_default()
+    }
+
+    /// Wrap the text onto at most `max_lines` lines of `width` cells, like
+    /// [`wrap_lines`](crate::render::text::wrap_lines), keeping each
+    /// character's style.
+    pub fn wrap_lines(&self, width: usize, max_lines: usize, overflow: TextOverflow) -> Vec<Text> {
+        self.restyle(&wrap_lines(&self.plain, width, max_lines, overflow))
+    }
+
+    /// Render the text onto `strip`, each span in its style laid over
+    /// `base`.
+    pub fn push_to(&self, strip: &mut Strip, base: CellStyle) {
+        for span in &self.spans {
+            strip.push_str(&span.text, overlay(base, span.style));
+        }
+    }
+
+    /// Style `lines`, derived from the plain text by dropping or adding
+    /// characters, by matching each character to the next equal one in this
+    /// text within the same word. Characters with no match (ellipses,
+    /// abbreviation dots) take the style of the one before.
+    fn restyle(&self, lines: &[String]) -> Vec<Text> {
+        let source: Vec<(char, CellStyle)> = self.chars().collect();
+        let mut cursor = 0;
+        let mut last = source.first().map(|&(_, style)| style).unwrap_or_default();
+        lines
+            .iter()
+            .map(|line| {
+                Self::from_styled_chars(line.chars().map(|ch| {
+                    let rest = &source[cursor..];
+                    let skip = if ch.is_whitespace() {
+                        0
+                    } else {
+                        rest.iter().take_while(|(c, _)| c.is_whitespace()).count()
+                    };
+                    let found = rest[skip..]
+                        .iter()
+                        .take_while(|(c, _)| ch.is_whitespace() || !c.is_whitespace())
+                        .position(|&(c, _)| c == ch);
+                    if let Some(offset) = found {
+                        cursor += skip + offset + 1;
+                        last = source[cursor - 1].1;
+                    }
+                    (ch, last)
+                }))
+            })
+            .collect()
+    }
+}
+
+impl From<&str> for Text {
+    fn from(markup: &str) -> Self {
+        Self::from_markup(markup)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Parsing helpers
+// ---------------------------------------------------------------------------
+
+/// The contents of the tag starting at `s` (which starts with `[`), if it
+/// is one: up to the next `]`, starting with a letter, `#`, or `/`.
+fn tag_at(s: &str) -> Option<&str> {
+    let body = &s[1..];
+    let end = body.find([']', '['])?;
+    let tag = &body[..end];
+    let starts_tag = tag
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '#' || c == '/');
+    (body[end..].starts_with(']') && starts_tag).then_some(tag)
+}
+
+/// The style of the open tags, innermost last.
+fn current_style(tags: &[(&str, CellStyle)]) -> CellStyle {
+    tags.iter()
+        .fold(CellStyle::default(), |style, &(_, top)| overlay(style, top))
+}
+
+/// The style an opening tag sets.
+fn parse_style(tag: &str) -> Result<CellStyle, MarkupError> {
+    let unknown = |word: &str| MarkupError::UnknownStyle {
+        tag: tag.to_owned(),
+        word: word.to_owned(),
+    };
+    let mut style = CellStyle::default();
+    let mut words = tag.split_whitespace();
+    while let Some(word) = words.next() {
+        if let Some(url) = word.strip_prefix("link=") {
+            style = style.with_link(url);
+            continue;
+        }
+        let attrs = match word.to_ascii_lowercase().as_str() {
+            "bold" | "b" => Attrs::BOLD,
+            "dim" | "d" => Attrs::DIM,
+            "italic" | "i" => Attrs::ITALIC,
+            "underline" | "u" => Attrs::UNDERLINE,
+            "strike" | "strikethrough" | "s" => Attrs::STRIKETHROUGH,
+            "reverse" | "r" => Attrs::REVERSE,
+            "blink" => Attrs::BLINK,
+            "pulse" => Attrs::PULSE,
+            "on" => {
+                let color = words.next().ok_or_else(|| unknown(word))?;
+                style.bg = Some(Color::parse(color).ok_or_else(|| unknown(color))?);
+                continue;
+            }
+            _ => {
+                style.fg = Some(Color::parse(word).ok_or_else(|| unknown(word))?);
+                continue;
+            }
+        };
+        style.attrs |= attrs;
+    }
+    Ok(style)
+}
+
+/// Escape `text` so it renders as written when used as markup.
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if ch == '[' || ch == '\\' {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+    out
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runs(text: &Text) -> Vec<(&str, CellStyle)> {
+        text.spans()
+            .iter()
+            .map(|span| (span.text.as_str(), span.style))
+            .collect()
+    }
+
+    #[test]
+    fn tags_open_nest_and_close() {
+        let text = Text::parse("a [bold red]b [on blue]c[/] d[/] e").unwrap();
+        let bold_red = CellStyle::new().with_fg("red").with_attrs(Attrs::BOLD);
+        assert_eq!(
+            runs(&text),
+            [
+                ("a ", CellStyle::new()),
+                ("b ", bold_red),
+                ("c", bold_red.with_bg("blue")),
+                (" d", bold_red),
+                (" e", CellStyle::new()),
+            ]
+        );
+        assert_eq!(text.plain(), "a b c d e");
+
+        let text = Text::parse("[i]x[u]y[/i]z").unwrap();
+        let underline = CellStyle::new().with_attrs(Attrs::UNDERLINE);
+        assert_eq!(runs(&text)[2], ("z", underline));
+
+        assert_eq!(
+            Text::parse("[bold]x[/red]"),
+            Err(MarkupError::UnmatchedClose("red".into()))
+        );
+        assert!(matches!(
+            Text::parse("[x] done"),
+            Err(MarkupError::UnknownStyle { .. })
+        ));
+        assert_eq!(Text::from_markup("[x] done").plain(), "[x] done");
+    }
+
+    #[test]
+    fn brackets_escape_and_pass_through() {
+        let text = Text::parse(r"\[bold] [1] [ ] a\\b [b").unwrap();
+        assert_eq!(text.plain(), r"[bold] [1] [ ] a\b [b");
+        assert_eq!(text.spans().len(), 1);
+
+        let untrusted = r"[red]C:\dir[/]";
+        assert_eq!(Text::parse(&escape(untrusted)).unwrap().plain(), untrusted);
+    }
+
+    #[test]
+    fn fitting_and_wrapping_keep_styles() {
+        let text = Text::parse("[b]Hello[/] [red]World[/] Foo").unwrap();
+        let bold = CellStyle::new().with_attrs(Attrs::BOLD);
+        let red = CellStyle::new().with_fg("red");
+
+        let fitted = text.fit(8, TextOverflow::Ellipsis);
+        assert_eq!(fitted.plain(), "Hello W…");
+        assert_eq!(
+            runs(&fitted),
+            [("Hello", bold), (" ", CellStyle::new()), ("W…", red)]
+        );
+
+        let shrunk = text.fit(11, TextOverflow::Shrink);
+        assert_eq!(shrunk.plain(), "Hello W. F.");
+        assert_eq!(runs(&shrunk)[2], ("W.", red));
+        assert_eq!(runs(&shrunk)[3], (" F.", CellStyle::new()));
+
+        let lines = text.wrap_lines(5, 3, TextOverflow::Clip);
+        let lines: Vec<_> = lines.iter().map(runs).collect();
+        assert_eq!(
+            lines,
+            [
+                vec![("Hello", bold)],
+                vec![("World", red)],
+                vec![("Foo", CellStyle::new())]
+            ]
+        );
+
+        let mut strip = Strip::new(0, 0);
+        let base = CellStyle::new().with_fg("blue").with_attrs(Attrs::ITALIC);
+        text.slice(4, 3).push_to(&mut strip, base);
+        assert_eq!(strip.cells[0].style, base.with_attrs(Attrs::BOLD));
+        assert_eq!(strip.cells[1].style, base);
+        assert_eq!(strip.cells[2].style.fg, Some(Color::Red));
+    }
+}
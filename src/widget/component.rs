@@ -0,0 +1,374 @@
+//! Components: props-driven, memoized widget trees.
+//!
+//! A [`Component`] turns a props value into a widget tree (typically built with
+//! `view!`). A [`ComponentHost`] mounts it under a DOM node and re-invokes
+//! `render` only when the props change (by `PartialEq`) or a signal read during
+//! the last render is written. Each new tree is reconciled against the DOM, so
+//! unchanged nodes keep their identity, focus, and scroll state, while the
+//! host keeps the new widgets so [`ComponentHost::widget`] draws what the
+//! latest render produced.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::dom::node::NodeId;
+use crate::dom::transaction::{DomChanges, Transaction, TransactionError};
+use crate::dom::tree::Dom;
+use crate::reactive::{create_effect_with_id, dispose_effect, EffectId};
+use crate::screen::Screen;
+use crate::widget::reconcile::reconcile_children;
+use crate::widget::traits::Widget;
+
+// ---------------------------------------------------------------------------
+// Component trait
+// ---------------------------------------------------------------------------
+
+/// A reusable piece of UI driven by props.
+///
+/// # Examples
+///
+/// ```ignore
+/// struct Greeting;
+///
+/// impl Component for Greeting {
+///     type Props = String;
+///
+///     fn render(&self, name: &String) -> Vec<Box<dyn Widget>> {
+///         view! { Static(format!("Hello, {name}!")) }
+///     }
+/// }
+///
+/// let mut host = ComponentHost::new(root, Greeting, "world".to_string());
+/// host.sync(&mut screen)?;
+/// ```
+pub trait Component: 'static {
+    /// Inputs to the component. Re-rendering is skipped when they compare equal.
+    type Props: Clone + PartialEq + 'static;
+
+    /// Build the widget tree for `props`.
+    ///
+    /// Signals read here are tracked; writing any of them schedules a re-render.
+    fn render(&self, props: &Self::Props) -> Vec<Box<dyn Widget>>;
+}
+
+// ---------------------------------------------------------------------------
+// ComponentHost
+// ---------------------------------------------------------------------------
+
+/// Widget tree produced by the latest render, waiting to be reconciled.
+type PendingView = Rc<RefCell<Option<Vec<Box<dyn Widget>>>>>;
+
+/// Mounts a [`Component`] under a DOM node and keeps it up to date.
+///
+/// Rendering happens inside a reactive effect so signal reads are tracked.
+/// The produced tree is held until [`ComponentHost::sync`] reconciles it into
+/// the screen's DOM. Dropping the host stops tracking.
+pub struct ComponentHost<C: Component> {
+    component: Rc<C>,
+    props: C::Props,
+    root: NodeId,
+    pending: PendingView,
+    /// The last reconciled tree.
+    view: Vec<Box<dyn Widget>>,
+    /// Where each mounted node's widget sits in `view`, as child indices.
+    paths: HashMap<NodeId, Vec<usize>>,
+    renders: Rc<Cell<usize>>,
+    effect: EffectId,
+}
+
+impl<C: Component> ComponentHost<C> {
+    /// Render `component` with `props` for mounting as the children of `root`.
+    ///
+    /// Renders once immediately; call [`ComponentHost::sync`] to write the
+    /// result into the DOM.
+    pub fn new(root: NodeId, component: C, props: C::Props) -> Self {
+        let component = Rc::new(component);
+        let pending: PendingView = Rc::new(RefCell::new(None));
+        let renders = Rc::new(Cell::new(0));
+        let effect = Self::track(&component, &props, &pending, &renders);
+        Self {
+            component,
+            props,
+            root,
+            pending,
+            view: Vec::new(),
+            paths: HashMap::new(),
+            renders,
+            effect,
+        }
+    }
+
+    /// Create the effect that renders and stashes the result.
+    fn track(
+        component: &Rc<C>,
+        props: &C::Props,
+        pending: &PendingView,
+        renders: &Rc<Cell<usize>>,
+    ) -> EffectId {
+        let component = component.clone();
+        let props = props.clone();
+        let pending = pending.clone();
+        let renders = renders.clone();
+        create_effect_with_id(move || {
+            let view = component.render(&props);
+            *pending.borrow_mut() = Some(view);
+            renders.set(renders.get() + 1);
+        })
+    }
+
+    /// Replace the props, re-rendering only if they differ from the current ones.
+    ///
+    /// Returns whether a render happened.
+    pub fn set_props(&mut self, props: C::Props) -> bool {
+        if props == self.props {
+            return false;
+        }
+        dispose_effect(self.effect);
+        self.props = props;
+        self.effect = Self::track(&self.component, &self.props, &self.pending, &self.renders);
+        true
+    }
+
+    /// The current props.
+    pub fn props(&self) -> &C::Props {
+        &self.props
+    }
+
+    /// The DOM node whose children this component owns.
+    pub fn root(&self) -> NodeId {
+        self.root
+    }
+
+    /// How many times `render` has run.
+    pub fn render_count(&self) -> usize {
+        self.renders.get()
+    }
+
+    /// The effect that renders the component, for looking up why it last
+    /// re-rendered with [`Runtime::last_run`](crate::reactive::Runtime::last_run).
+    pub fn effect_id(&self) -> EffectId {
+        self.effect
+    }
+
+    /// The widget last rendered for `node`, a node this component mounted.
+    ///
+    /// Reconciling keeps a node whose widget only changed content, so draw
+    /// it from here rather than from a widget built for an earlier render.
+    pub fn widget(&self, node: NodeId) -> Option<&dyn Widget> {
+        let (first, rest) = self.paths.get(&node)?.split_first()?;
+        let mut widget = self.view.get(*first)?.as_ref();
+        for &i in rest {
+            widget = widget.child_widgets().get(i)?.as_ref();
+        }
+        Some(widget)
+    }
+
+    /// Whether a rendered tree is waiting to be reconciled.
+    pub fn needs_sync(&self) -> bool {
+        self.pending.borrow().is_some()
+    }
+
+    /// Reconcile the latest render into the screen's DOM.
+    ///
+    /// Returns `Ok(None)` if nothing was rendered since the last sync, or the
+    /// changes applied (with a single combined invalidation) otherwise.
+    pub fn sync(&mut self, screen: &mut Screen) -> Result<Option<DomChanges>, TransactionError> {
+        let Some(view) = self.pending.borrow_mut().take() else {
+            return Ok(None);
+        };
+        let mut tx = Transaction::new();
+        reconcile_children(&mut tx, &screen.dom, self.root, &view);
+        let changes = screen.apply(tx)?;
+        self.paths.clear();
+        index_widgets(
+            &screen.dom,
+            self.root,
+            &view,
+            &mut Vec::new(),
+            &mut self.paths,
+        );
+        self.view = view;
+        Ok(Some(changes))
+    }
+}
+
+/// Record the path to each widget of `widgets`, which reconciliation has
+/// mounted as the children of `parent` in the same order.
+fn index_widgets(
+    dom: &Dom,
+    parent: NodeId,
+    widgets: &[Box<dyn Widget>],
+    path: &mut Vec<usize>,
+    paths: &mut HashMap<NodeId, Vec<usize>>,
+) {
+    for (i, (&id, widget)) in dom.children(parent).iter().zip(widgets).enumerate() {
+        path.push(i);
+        paths.insert(id, path.clone());
+        index_widgets(dom, id, widget.child_widgets(), path, paths);
+        path.pop();
+    }
+}
+
+impl<C: Component> Drop for ComponentHost<C> {
+    fn drop(&mut self) {
+        dispose_effect(self.effect);
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dom::node::NodeData;
+    use crate::reactive::signal::reset_runtime;
+    use crate::reactive::{create_signal, ReadSignal};
+    use crate::widget::traits::WidgetExt;
+    use crate::widgets::{Button, Static};
+
+    /// Renders `count` buttons, highlighting the one at `selected`.
+    struct Toolbar {
+        selected: ReadSignal<usize>,
+    }
+
+    impl Component for Toolbar {
+        type Props = usize;
+
+        fn render(&self, count: &usize) -> Vec<Box<dyn Widget>> {
+            let selected = self.selected.get();
+            (0..*count)
+                .map(|i| -> Box<dyn Widget> {
+                    if i == selected {
+                        Box::new(Button::new("tool").with_class("selected"))
+                    } else {
+                        Box::new(Button::new("tool"))
+                    }
+                })
+                .collect()
+        }
+    }
+
+    fn setup() -> (Screen, NodeId) {
+        reset_runtime();
+        let mut screen = Screen::new(40, 10);
+        let root = screen.dom.insert(NodeData::new("Root"));
+        (screen, root)
+    }
+
+    #[test]
+    fn initial_sync_mounts_tree() {
+        let (mut screen, root) = setup();
+        let (selected, _) = create_signal(0);
+        let mut host = ComponentHost::new(root, Toolbar { selected }, 3);
+        assert_eq!(host.render_count(), 1);
+
+        let changes = host.sync(&mut screen).unwrap().unwrap();
+        assert_eq!(changes.inserted.len(), 3);
+        assert_eq!(screen.dom.children(root).len(), 3);
+        assert!(host.sync(&mut screen).unwrap().is_none());
+    }
+
+    #[test]
+    fn equal_props_skip_render() {
+        let (_screen, root) = setup();
+        let (selected, _) = create_signal(0);
+        let mut host = ComponentHost::new(root, Toolbar { selected }, 3);
+        assert!(!host.set_props(3));
+        assert_eq!(host.render_count(), 1);
+        assert!(host.set_props(4));
+        assert_eq!(host.render_count(), 2);
+        assert_eq!(*host.props(), 4);
+    }
+
+    #[test]
+    fn signal_change_rerenders_and_diffs() {
+        let (mut screen, root) = setup();
+        let (selected, set_selected) = create_signal(0);
+        let mut host = ComponentHost::new(root, Toolbar { selected }, 3);
+        host.sync(&mut screen).unwrap();
+        let before = screen.dom.children(root).to_vec();
+
+        set_selected.set(2);
+        assert_eq!(host.render_count(), 2);
+        let changes = host.sync(&mut screen).unwrap().unwrap();
+
+        // Same nodes, only the two whose class changed were touched.
+        assert_eq!(screen.dom.children(root), before.as_slice());
+        assert!(changes.inserted.is_empty());
+        assert_eq!(changes.updated, vec![before[0], before[2]]);
+    }
+
+    #[test]
+    fn rerender_with_new_text_updates_the_drawn_widget() {
+        use crate::testing::render_to_string;
+
+        let (mut screen, root) = setup();
+        let (name, set_name) = create_signal("world".to_string());
+
+        struct Greeting {
+            name: ReadSignal<String>,
+        }
+        impl Component for Greeting {
+            type Props = ();
+            fn render(&self, _: &()) -> Vec<Box<dyn Widget>> {
+                vec![Box::new(Static::new(format!(
+                    "Hello, {}!",
+                    self.name.get()
+                )))]
+            }
+        }
+
+        let mut host = ComponentHost::new(root, Greeting { name }, ());
+        host.sync(&mut screen).unwrap();
+        let node = screen.dom.children(root)[0];
+        assert!(render_to_string(host.widget(node).unwrap(), 20, 1).contains("Hello, world!"));
+
+        set_name.set("gilt".to_string());
+        let changes = host.sync(&mut screen).unwrap().unwrap();
+        // The node is kept but updated, and draws the new text.
+        assert_eq!(screen.dom.children(root), [node]);
+        assert_eq!(changes.updated, vec![node]);
+        assert!(render_to_string(host.widget(node).unwrap(), 20, 1).contains("Hello, gilt!"));
+    }
+
+    #[test]
+    fn dropped_host_stops_tracking() {
+        let (_screen, root) = setup();
+        let (selected, set_selected) = create_signal(0);
+        let renders = {
+            let host = ComponentHost::new(root, Toolbar { selected }, 1);
+            host.renders.clone()
+        };
+        set_selected.set(1);
+        assert_eq!(renders.get(), 1);
+    }
+
+    #[test]
+    fn props_change_replaces_children() {
+        let (mut screen, root) = setup();
+
+        struct Label;
+        impl Component for Label {
+            type Props = bool;
+            fn render(&self, button: &bool) -> Vec<Box<dyn Widget>> {
+                if *button {
+                    vec![Box::new(Button::new("x"))]
+                } else {
+                    vec![Box::new(Static::new("x"))]
+                }
+            }
+        }
+
+        let mut host = ComponentHost::new(root, Label, false);
+        host.sync(&mut screen).unwrap();
+        host.set_props(true);
+        let changes = host.sync(&mut screen).unwrap().unwrap();
+        assert_eq!(changes.removed.len(), 1);
+        assert_eq!(changes.inserted.len(), 1);
+        let child = screen.dom.children(root)[0];
+        assert_eq!(screen.dom.get(child).unwrap().widget_type, "Button");
+    }
+}
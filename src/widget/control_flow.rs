@@ -0,0 +1,387 @@
+//! Conditional rendering components: Show, Dynamic and Suspense.
+//!
+//! Both are [`Component`]s, so they mount through a [`ComponentHost`]
+//! like any other and their output is reconciled into the DOM:
+//!
+//! - [`Show`] builds its children only while a `bool` signal is true, and
+//!   unmounts them (or mounts an optional fallback) when it turns false.
+//! - [`Dynamic`] builds one of several subtrees from the current value of a
+//!   signal, typically an enum, swapping them when the value changes.
+//! - [`Suspense`] shows a fallback while any [`Resource`] read by its
+//!   children is loading, and an error slot if one failed.
+//!
+//! Children are built lazily: the builder closures run only for the branch
+//! being shown. Feed a [`create_memo`](crate::reactive::create_memo) as the
+//! signal to re-render only when the derived value actually changes.
+//!
+//! [`ComponentHost`]: crate::widget::component::ComponentHost
+//! [`Resource`]: crate::reactive::Resource
+
+use crate::reactive::resource::{begin_suspense, end_suspense, ResourceStatus};
+use crate::reactive::ReadSignal;
+use crate::widget::component::Component;
+use crate::widget::traits::Widget;
+
+/// Builds a subtree on demand.
+type Children = Box<dyn Fn() -> Vec<Box<dyn Widget>>>;
+
+/// Builds the subtree for a value.
+type Branches<T> = Box<dyn Fn(&T) -> Vec<Box<dyn Widget>>>;
+
+/// Builds the subtree for an error message.
+type ErrorSlot = Box<dyn Fn(&str) -> Vec<Box<dyn Widget>>>;
+
+// ---------------------------------------------------------------------------
+// Show
+// ---------------------------------------------------------------------------
+
+/// Renders its children only while `when` is true.
+///
+/// # Examples
+///
+/// ```ignore
+/// let has_errors = create_memo(move || !errors.with(Vec::is_empty));
+/// let show = Show::new(has_errors, || view! { Static("Fix the errors below") })
+///     .with_fallback(|| view! { Static("All good") });
+/// let mut host = ComponentHost::new(banner, show, ());
+/// host.sync(&mut screen)?;
+/// ```
+pub struct Show {
+    when: ReadSignal<bool>,
+    children: Children,
+    fallback: Option<Children>,
+}
+
+impl Show {
+    /// Show `children` while `when` is true.
+    pub fn new(
+        when: ReadSignal<bool>,
+        children: impl Fn() -> Vec<Box<dyn Widget>> + 'static,
+    ) -> Self {
+        Self {
+            when,
+            children: Box::new(children),
+            fallback: None,
+        }
+    }
+
+    /// Show `fallback` while `when` is false (builder pattern). Without one,
+    /// nothing is mounted.
+    pub fn with_fallback(mut self, fallback: impl Fn() -> Vec<Box<dyn Widget>> + 'static) -> Self {
+        self.fallback = Some(Box::new(fallback));
+        self
+    }
+}
+
+impl Component for Show {
+    type Props = ();
+
+    fn render(&self, _: &()) -> Vec<Box<dyn Widget>> {
+        if self.when.get() {
+            (self.children)()
+        } else {
+            self.fallback
+                .as_ref()
+                .map_or_else(Vec::new, |fallback| fallback())
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Dynamic
+// ---------------------------------------------------------------------------
+
+/// Renders the subtree for the current value of a signal.
+///
+/// # Examples
+///
+/// ```ignore
+/// #[derive(Clone, PartialEq)]
+/// enum Pane { Files, Search, Git }
+///
+/// let dynamic = Dynamic::new(pane, |pane| match pane {
+///     Pane::Files => view! { FileTree() },
+///     Pane::Search => view! { SearchPanel() },
+///     Pane::Git => view! { GitPanel() },
+/// });
+/// ```
+pub struct Dynamic<T: 'static> {
+    value: ReadSignal<T>,
+    render: Branches<T>,
+}
+
+impl<T: 'static> Dynamic<T> {
+    /// Render `value` with `render`, re-rendering whenever it is written.
+    pub fn new(
+        value: ReadSignal<T>,
+        render: impl Fn(&T) -> Vec<Box<dyn Widget>> + 'static,
+    ) -> Self {
+        Self {
+            value,
+            render: Box::new(render),
+        }
+    }
+}
+
+impl<T: 'static> Component for Dynamic<T> {
+    type Props = ();
+
+    fn render(&self, _: &()) -> Vec<Box<dyn Widget>> {
+        self.value.with(|value| (self.render)(value))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Suspense
+// ---------------------------------------------------------------------------
+
+/// Holds back its children until every resource they read has loaded.
+///
+/// The children are built on each render to find out which resources they
+/// read; their output is only mounted once none is loading or failed. A
+/// failure takes precedence over loading and shows the error slot with the
+/// first error's message. Boundaries nest: a resource counts only towards
+/// the innermost one rendering it.
+///
+/// # Examples
+///
+/// ```ignore
+/// let profile = create_resource::<Profile, String>();
+/// let suspense = Suspense::new(move || view! { ProfileCard(profile.get()) })
+///     .with_fallback(|| view! { Static("Loading…") })
+///     .with_error(|message| view! { Static(format!("Failed: {message}")) });
+/// let mut host = ComponentHost::new(pane, suspense, ());
+/// ```
+pub struct Suspense {
+    children: Children,
+    fallback: Option<Children>,
+    error: Option<ErrorSlot>,
+}
+
+impl Suspense {
+    /// Suspend `children` while their resources load.
+    pub fn new(children: impl Fn() -> Vec<Box<dyn Widget>> + 'static) -> Self {
+        Self {
+            children: Box::new(children),
+            fallback: None,
+            error: None,
+        }
+    }
+
+    /// Show `fallback` while loading (builder pattern). Without one, nothing
+    /// is mounted.
+    pub fn with_fallback(mut self, fallback: impl Fn() -> Vec<Box<dyn Widget>> + 'static) -> Self {
+        self.fallback = Some(Box::new(fallback));
+        self
+    }
+
+    /// Show `error` when a resource failed, given its message (builder
+    /// pattern). Without one, nothing is mounted.
+    pub fn with_error(mut self, error: impl Fn(&str) -> Vec<Box<dyn Widget>> + 'static) -> Self {
+        self.error = Some(Box::new(error));
+        self
+    }
+}
+
+impl Component for Suspense {
+    type Props = ();
+
+    fn render(&self, _: &()) -> Vec<Box<dyn Widget>> {
+        begin_suspense();
+        let view = (self.children)();
+        let statuses = end_suspense();
+
+        let failure = statuses.iter().find_map(|status| match status {
+            ResourceStatus::Failed(message) => Some(message.as_str()),
+            _ => None,
+        });
+        if let Some(message) = failure {
+            return self
+                .error
+                .as_ref()
+                .map_or_else(Vec::new, |error| error(message));
+        }
+        if statuses.contains(&ResourceStatus::Loading) {
+            return self
+                .fallback
+                .as_ref()
+                .map_or_else(Vec::new, |fallback| fallback());
+        }
+        view
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use crate::dom::node::{NodeData, NodeId};
+    use crate::reactive::signal::reset_runtime;
+    use crate::reactive::{create_memo, create_resource, create_signal};
+    use crate::screen::Screen;
+    use crate::widget::component::ComponentHost;
+    use crate::widgets::{Button, Static};
+
+    fn setup() -> (Screen, NodeId) {
+        reset_runtime();
+        let mut screen = Screen::new(40, 10);
+        let root = screen.dom.insert(NodeData::new("Root"));
+        (screen, root)
+    }
+
+    fn child_types(screen: &Screen, root: NodeId) -> Vec<String> {
+        screen
+            .dom
+            .children(root)
+            .iter()
+            .map(|&id| screen.dom.get(id).unwrap().widget_type.clone())
+            .collect()
+    }
+
+    #[test]
+    fn show_mounts_and_unmounts_lazily() {
+        let (mut screen, root) = setup();
+        let (visible, set_visible) = create_signal(false);
+        let builds = Rc::new(Cell::new(0));
+        let counter = builds.clone();
+        let show = Show::new(visible, move || -> Vec<Box<dyn Widget>> {
+            counter.set(counter.get() + 1);
+            vec![Box::new(Static::new("hi")), Box::new(Button::new("ok"))]
+        });
+        let mut host = ComponentHost::new(root, show, ());
+        host.sync(&mut screen).unwrap();
+        assert_eq!(builds.get(), 0);
+        assert!(screen.dom.children(root).is_empty());
+
+        set_visible.set(true);
+        let changes = host.sync(&mut screen).unwrap().unwrap();
+        assert_eq!(builds.get(), 1);
+        assert_eq!(changes.inserted.len(), 2);
+        assert_eq!(child_types(&screen, root), ["Static", "Button"]);
+
+        set_visible.set(false);
+        let changes = host.sync(&mut screen).unwrap().unwrap();
+        assert_eq!(changes.removed.len(), 2);
+        assert!(screen.dom.children(root).is_empty());
+    }
+
+    #[test]
+    fn show_renders_fallback_when_false() {
+        let (mut screen, root) = setup();
+        let (visible, set_visible) = create_signal(false);
+        let show = Show::new(visible, || {
+            vec![Box::new(Button::new("on")) as Box<dyn Widget>]
+        })
+        .with_fallback(|| vec![Box::new(Static::new("off"))]);
+        let mut host = ComponentHost::new(root, show, ());
+        host.sync(&mut screen).unwrap();
+        assert_eq!(child_types(&screen, root), ["Static"]);
+        set_visible.set(true);
+        host.sync(&mut screen).unwrap();
+        assert_eq!(child_types(&screen, root), ["Button"]);
+    }
+
+    #[test]
+    fn memo_condition_skips_unchanged_renders() {
+        let (_screen, root) = setup();
+        let (count, set_count) = create_signal(1);
+        let positive = create_memo(move || count.get() > 0);
+        let host = ComponentHost::new(root, Show::new(positive, Vec::new), ());
+        set_count.set(2);
+        set_count.set(3);
+        assert_eq!(host.render_count(), 1);
+        set_count.set(0);
+        assert_eq!(host.render_count(), 2);
+    }
+
+    #[derive(Clone, PartialEq)]
+    enum Pane {
+        Text,
+        Buttons(usize),
+    }
+
+    #[test]
+    fn dynamic_swaps_subtrees() {
+        let (mut screen, root) = setup();
+        let (pane, set_pane) = create_signal(Pane::Text);
+        let dynamic = Dynamic::new(pane, |pane| match pane {
+            Pane::Text => vec![Box::new(Static::new("text")) as Box<dyn Widget>],
+            Pane::Buttons(n) => (0..*n)
+                .map(|_| Box::new(Button::new("b")) as Box<dyn Widget>)
+                .collect(),
+        });
+        let mut host = ComponentHost::new(root, dynamic, ());
+        host.sync(&mut screen).unwrap();
+        assert_eq!(child_types(&screen, root), ["Static"]);
+
+        set_pane.set(Pane::Buttons(2));
+        let changes = host.sync(&mut screen).unwrap().unwrap();
+        assert_eq!(changes.removed.len(), 1);
+        assert_eq!(child_types(&screen, root), ["Button", "Button"]);
+
+        // Same kind of subtree: existing nodes are kept.
+        let before = screen.dom.children(root).to_vec();
+        set_pane.set(Pane::Buttons(3));
+        host.sync(&mut screen).unwrap();
+        assert_eq!(&screen.dom.children(root)[..2], before.as_slice());
+    }
+
+    #[test]
+    fn suspense_waits_for_every_resource() {
+        let (mut screen, root) = setup();
+        let a = create_resource::<&'static str, String>();
+        let b = create_resource::<&'static str, String>();
+        let suspense = Suspense::new(move || {
+            [a.get(), b.get()]
+                .into_iter()
+                .map(|text| Box::new(Static::new(text.unwrap_or("?"))) as Box<dyn Widget>)
+                .collect()
+        })
+        .with_fallback(|| vec![Box::new(Button::new("loading"))]);
+        let mut host = ComponentHost::new(root, suspense, ());
+        host.sync(&mut screen).unwrap();
+        assert_eq!(child_types(&screen, root), ["Button"]);
+
+        a.resolve("a");
+        host.sync(&mut screen).unwrap();
+        assert_eq!(child_types(&screen, root), ["Button"]);
+
+        b.resolve("b");
+        host.sync(&mut screen).unwrap();
+        assert_eq!(child_types(&screen, root), ["Static", "Static"]);
+
+        b.reload();
+        host.sync(&mut screen).unwrap();
+        assert_eq!(child_types(&screen, root), ["Button"]);
+    }
+
+    #[test]
+    fn suspense_shows_error_slot() {
+        let (mut screen, root) = setup();
+        let data = create_resource::<u32, String>();
+        let message = Rc::new(std::cell::RefCell::new(String::new()));
+        let seen = message.clone();
+        let suspense = Suspense::new(move || {
+            data.get();
+            Vec::new()
+        })
+        .with_error(move |error| {
+            *seen.borrow_mut() = error.to_owned();
+            vec![Box::new(Static::new("error"))]
+        });
+        let mut host = ComponentHost::new(root, suspense, ());
+        host.sync(&mut screen).unwrap();
+        assert!(screen.dom.children(root).is_empty());
+
+        data.fail("timed out".into());
+        host.sync(&mut screen).unwrap();
+        assert_eq!(child_types(&screen, root), ["Static"]);
+        assert_eq!(*message.borrow(), "timed out");
+    }
+}
@@ -0,0 +1,296 @@
+//! Lazy, paginated data for large list-like widgets.
+//!
+//! A [`DataSource`] reports how many items exist and produces any requested
+//! range, either immediately or later (for remote data). [`PagedCache`] sits
+//! between a source and a widget: it fetches whole pages covering the visible
+//! window plus a configurable prefetch distance, keeps a bounded number of
+//! pages in memory, and reports which rows are still loading so the widget can
+//! draw placeholders.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+// ---------------------------------------------------------------------------
+// DataSource
+// ---------------------------------------------------------------------------
+
+/// Result of asking a [`DataSource`] for a range of items.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetch<T> {
+    /// The items are available now.
+    Ready(Vec<T>),
+    /// The items will be delivered later via [`PagedCache::fill`].
+    Pending,
+}
+
+/// A possibly remote, possibly huge, indexed collection of items.
+pub trait DataSource {
+    /// The item type.
+    type Item;
+
+    /// Total number of items.
+    fn total(&self) -> usize;
+
+    /// Fetch the items in `range` (which never extends past `total`).
+    fn fetch(&mut self, range: Range<usize>) -> Fetch<Self::Item>;
+}
+
+impl<T: Clone> DataSource for Vec<T> {
+    type Item = T;
+
+    fn total(&self) -> usize {
+        self.len()
+    }
+
+    fn fetch(&mut self, range: Range<usize>) -> Fetch<T> {
+        Fetch::Ready(self[range].to_vec())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// PagedCache
+// ---------------------------------------------------------------------------
+
+/// A page that was requested from the source.
+#[derive(Debug, Clone)]
+enum Page<T> {
+    Loading,
+    Loaded(Vec<T>),
+}
+
+/// Page-granular cache of items fetched from a [`DataSource`].
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut cache = PagedCache::new(100).with_prefetch(2);
+/// cache.request(&mut source, 0..40);
+/// match cache.get(12) {
+///     Some(row) => draw(row),
+///     None => draw_placeholder(),
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PagedCache<T> {
+    pages: HashMap<usize, Page<T>>,
+    page_size: usize,
+    prefetch: usize,
+    max_pages: usize,
+}
+
+impl<T> PagedCache<T> {
+    /// Create a cache fetching `page_size` items at a time, with one page of
+    /// prefetch and room for 64 pages.
+    pub fn new(page_size: usize) -> Self {
+        Self {
+            pages: HashMap::new(),
+            page_size: page_size.max(1),
+            prefetch: 1,
+            max_pages: 64,
+        }
+    }
+
+    /// Number of pages to fetch ahead of the visible window (builder).
+    pub fn with_prefetch(mut self, pages: usize) -> Self {
+        self.prefetch = pages;
+        self
+    }
+
+    /// Maximum number of pages held at once (builder). Pages farthest from
+    /// the visible window are evicted first.
+    pub fn with_max_pages(mut self, pages: usize) -> Self {
+        self.max_pages = pages.max(1);
+        self
+    }
+
+    /// Items per page.
+    pub fn page_size(&self) -> usize {
+        self.page_size
+    }
+
+    /// Make sure every page overlapping `visible`, plus the prefetch pages
+    /// after it, is loaded or loading.
+    pub fn request(&mut self, source: &mut dyn DataSource<Item = T>, visible: Range<usize>) {
+        let total = source.total();
+        if total == 0 {
+            return;
+        }
+        let last_page = (total - 1) / self.page_size;
+        let first = visible.start / self.page_size;
+        let end = visible.end.max(visible.start + 1).min(total);
+        let last = ((end - 1) / self.page_size + self.prefetch).min(last_page);
+
+        for page in first..=last {
+            if self.pages.contains_key(&page) {
+                continue;
+            }
+            let start = page * self.page_size;
+            let range = start..(start + self.page_size).min(total);
+            let state = match source.fetch(range) {
+                Fetch::Ready(items) => Page::Loaded(items),
+                Fetch::Pending => Page::Loading,
+            };
+            self.pages.insert(page, state);
+        }
+        self.evict(first..last + 1);
+    }
+
+    /// Drop pages outside `keep`, farthest first, until within `max_pages`.
+    fn evict(&mut self, keep: Range<usize>) {
+        if self.pages.len() <= self.max_pages {
+            return;
+        }
+        let mut candidates: Vec<usize> = self
+            .pages
+            .keys()
+            .copied()
+            .filter(|page| !keep.contains(page))
+            .collect();
+        let distance = |page: usize| page.abs_diff(keep.start).min(page.abs_diff(keep.end));
+        candidates.sort_by_key(|&page| std::cmp::Reverse(distance(page)));
+        for page in candidates {
+            if self.pages.len() <= self.max_pages {
+                break;
+            }
+            self.pages.remove(&page);
+        }
+    }
+
+    /// Deliver items for a pending fetch that started at `start`.
+    ///
+    /// Deliveries for pages that were evicted or never requested are ignored.
+    /// Returns whether the items were stored.
+    pub fn fill(&mut self, start: usize, items: Vec<T>) -> bool {
+        let page = start / self.page_size;
+        match self.pages.get_mut(&page) {
+            Some(state @ Page::Loading) if start % self.page_size == 0 => {
+                *state = Page::Loaded(items);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The item at `index`, if its page has loaded.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        match self.pages.get(&(index / self.page_size))? {
+            Page::Loaded(items) => items.get(index % self.page_size),
+            Page::Loading => None,
+        }
+    }
+
+    /// Whether `index` belongs to a page that is still loading.
+    pub fn is_loading(&self, index: usize) -> bool {
+        matches!(
+            self.pages.get(&(index / self.page_size)),
+            Some(Page::Loading)
+        )
+    }
+
+    /// Number of pages currently held (loaded or loading).
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Forget every page, e.g. after the underlying data changed.
+    pub fn clear(&mut self) {
+        self.pages.clear();
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts fetches; pending when `remote` is set.
+    struct Numbers {
+        total: usize,
+        remote: bool,
+        fetched: Vec<Range<usize>>,
+    }
+
+    impl DataSource for Numbers {
+        type Item = usize;
+
+        fn total(&self) -> usize {
+            self.total
+        }
+
+        fn fetch(&mut self, range: Range<usize>) -> Fetch<usize> {
+            self.fetched.push(range.clone());
+            if self.remote {
+                Fetch::Pending
+            } else {
+                Fetch::Ready(range.collect())
+            }
+        }
+    }
+
+    fn numbers(total: usize, remote: bool) -> Numbers {
+        Numbers {
+            total,
+            remote,
+            fetched: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn fetches_visible_pages_plus_prefetch() {
+        let mut source = numbers(1_000_000, false);
+        let mut cache = PagedCache::new(50).with_prefetch(1);
+        cache.request(&mut source, 120..140);
+        assert_eq!(source.fetched, vec![100..150, 150..200]);
+        assert_eq!(cache.get(123), Some(&123));
+        assert_eq!(cache.get(99), None);
+
+        cache.request(&mut source, 125..145);
+        assert_eq!(source.fetched.len(), 2);
+    }
+
+    #[test]
+    fn last_page_is_truncated() {
+        let mut source = numbers(120, false);
+        let mut cache = PagedCache::new(50).with_prefetch(5);
+        cache.request(&mut source, 90..120);
+        assert_eq!(source.fetched, vec![50..100, 100..120]);
+        assert_eq!(cache.get(119), Some(&119));
+    }
+
+    #[test]
+    fn pending_pages_load_on_fill() {
+        let mut source = numbers(500, true);
+        let mut cache = PagedCache::new(10).with_prefetch(0);
+        cache.request(&mut source, 0..5);
+        assert!(cache.is_loading(3));
+        assert_eq!(cache.get(3), None);
+
+        assert!(!cache.fill(40, vec![40; 10]));
+        assert!(cache.fill(0, (0..10).collect()));
+        assert!(!cache.is_loading(3));
+        assert_eq!(cache.get(3), Some(&3));
+    }
+
+    #[test]
+    fn evicts_pages_far_from_window() {
+        let mut source = numbers(10_000, false);
+        let mut cache = PagedCache::new(10).with_prefetch(0).with_max_pages(2);
+        cache.request(&mut source, 0..10);
+        cache.request(&mut source, 500..510);
+        cache.request(&mut source, 510..520);
+        assert_eq!(cache.page_count(), 2);
+        assert_eq!(cache.get(5), None);
+        assert_eq!(cache.get(515), Some(&515));
+    }
+
+    #[test]
+    fn vec_is_a_data_source() {
+        let mut source = vec!["a", "b", "c"];
+        let mut cache = PagedCache::new(2);
+        cache.request(&mut source, 0..3);
+        assert_eq!(cache.get(2), Some(&"c"));
+    }
+}
@@ -0,0 +1,101 @@
+//! Incremental filtering for list-like widgets.
+//!
+//! A widget opts in by implementing [`Filterable`]: it exposes the searchable
+//! text of each item and accepts a set of [`FilterMatch`]es to show. The
+//! `FilterBar` widget drives it from keyboard input, matching case-insensitive
+//! substrings and reporting the matched character ranges for highlighting.
+
+use std::ops::Range;
+
+// ---------------------------------------------------------------------------
+// FilterMatch
+// ---------------------------------------------------------------------------
+
+/// An item that matched the filter query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterMatch {
+    /// Index of the item, as used by [`Filterable::item_text`].
+    pub index: usize,
+    /// Matched character ranges within the item text.
+    pub ranges: Vec<Range<usize>>,
+}
+
+/// Find every non-overlapping, case-insensitive occurrence of `query` in
+/// `text`, as character ranges.
+///
+/// Returns `None` if there is no occurrence. An empty query matches with no
+/// ranges.
+pub fn find_matches(query: &str, text: &str) -> Option<Vec<Range<usize>>> {
+    let needle: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    if needle.is_empty() {
+        return Some(Vec::new());
+    }
+    // Compare char by char so ranges stay in the original text's char indices.
+    let hay: Vec<char> = text
+        .chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect();
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i + needle.len() <= hay.len() {
+        if hay[i..i + needle.len()] == needle[..] {
+            ranges.push(i..i + needle.len());
+            i += needle.len();
+        } else {
+            i += 1;
+        }
+    }
+    (!ranges.is_empty()).then_some(ranges)
+}
+
+/// Match `query` against every item of `target`, in index order.
+pub fn filter_items(target: &dyn Filterable, query: &str) -> Vec<FilterMatch> {
+    (0..target.item_count())
+        .filter_map(|index| {
+            find_matches(query, &target.item_text(index))
+                .map(|ranges| FilterMatch { index, ranges })
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Filterable
+// ---------------------------------------------------------------------------
+
+/// A widget whose items can be narrowed by a filter bar.
+pub trait Filterable {
+    /// Number of items that can be searched.
+    fn item_count(&self) -> usize;
+
+    /// Searchable text of the item at `index`.
+    fn item_text(&self, index: usize) -> String;
+
+    /// Show only `matches` (highlighting their ranges), or everything when
+    /// `None`.
+    fn apply_filter(&mut self, matches: Option<&[FilterMatch]>);
+
+    /// Move the cursor or selection to the item at `index`.
+    fn jump_to(&mut self, index: usize);
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_are_case_insensitive_char_ranges() {
+        assert_eq!(find_matches("ab", "xABcab"), Some(vec![1..3, 4..6]));
+        assert_eq!(find_matches("é", "CAFÉ").unwrap()[0], 3..4);
+        assert_eq!(find_matches("zz", "abc"), None);
+        assert_eq!(find_matches("", "abc"), Some(vec![]));
+    }
+
+    #[test]
+    fn occurrences_do_not_overlap() {
+        assert_eq!(find_matches("aa", "aaaa"), Some(vec![0..2, 2..4]));
+    }
+}
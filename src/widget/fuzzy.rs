@@ -0,0 +1,165 @@
+//! Fuzzy matching for search boxes like the command palette.
+//!
+//! A query matches a candidate when its characters appear in the candidate
+//! in order, ignoring case: `"ofl"` matches `"Open File"`. Each match gets a
+//! score so results can be ranked: matches at the start of words and runs of
+//! consecutive characters score higher, and gaps between matched characters
+//! cost a little. [`rank`] filters and sorts a list of candidates.
+
+// ---------------------------------------------------------------------------
+// Scoring
+// ---------------------------------------------------------------------------
+
+/// Score for each matched character.
+const MATCH: i32 = 1;
+/// Bonus for a match at the start of the text or of a word.
+const WORD_START: i32 = 5;
+/// Bonus for a match right after the previous one.
+const CONSECUTIVE: i32 = 3;
+/// Most a single gap between two matches can cost.
+const MAX_GAP_PENALTY: i32 = 3;
+
+/// A successful match of a query against a candidate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// Higher is better.
+    pub score: i32,
+    /// Character indices in the candidate that matched the query, in order.
+    pub positions: Vec<usize>,
+}
+
+/// Match `query` against `text`. Returns `None` if the query's characters do
+/// not all appear in order. An empty query matches everything with score 0.
+///
+/// When a query character occurs several times, the placement with the best
+/// total score wins, so `"dt"` picks the `T` of `DataTable` over its `t`.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+    let needle: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if needle.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            positions: Vec::new(),
+        });
+    }
+    let chars: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = chars
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+    let gain = |i: usize| {
+        MATCH
+            + if is_word_start(&chars, i) {
+                WORD_START
+            } else {
+                0
+            }
+    };
+
+    // best[j][i]: best score with needle[j] matched at text position i, and
+    // the position needle[j - 1] was matched at to get it.
+    let mut best: Vec<Vec<Option<(i32, usize)>>> = vec![vec![None; chars.len()]; needle.len()];
+    for (i, &ch) in lower.iter().enumerate() {
+        if ch == needle[0] {
+            best[0][i] = Some((gain(i), 0));
+        }
+    }
+    for j in 1..needle.len() {
+        for i in j..chars.len() {
+            if lower[i] != needle[j] {
+                continue;
+            }
+            best[j][i] = (j - 1..i)
+                .filter_map(|k| {
+                    let (score, _) = best[j - 1][k]?;
+                    let step = if k + 1 == i {
+                        CONSECUTIVE
+                    } else {
+                        -((i - k - 1) as i32).min(MAX_GAP_PENALTY)
+                    };
+                    Some((score + step + gain(i), k))
+                })
+                .max_by_key(|&(score, k)| (score, std::cmp::Reverse(k)));
+        }
+    }
+
+    let last = needle.len() - 1;
+    let (mut at, score) = best[last]
+        .iter()
+        .enumerate()
+        .filter_map(|(i, entry)| entry.map(|(score, _)| (i, score)))
+        .max_by_key(|&(i, score)| (score, std::cmp::Reverse(i)))?;
+    let mut positions = vec![0; needle.len()];
+    for j in (0..needle.len()).rev() {
+        positions[j] = at;
+        at = best[j][at].map_or(0, |(_, prev)| prev);
+    }
+    Some(FuzzyMatch { score, positions })
+}
+
+/// Whether `chars[i]` starts a word: the first character, one after a
+/// separator, or an upper-case letter after a lower-case one.
+fn is_word_start(chars: &[char], i: usize) -> bool {
+    let Some(prev) = i.checked_sub(1).map(|p| chars[p]) else {
+        return true;
+    };
+    !prev.is_alphanumeric() || (prev.is_lowercase() && chars[i].is_uppercase())
+}
+
+/// Match `query` against every candidate and return the matching indices
+/// with their matches, best score first; equal scores keep their order.
+pub fn rank<'a>(
+    query: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Vec<(usize, FuzzyMatch)> {
+    let mut matches: Vec<(usize, FuzzyMatch)> = candidates
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, text)| fuzzy_match(query, text).map(|m| (i, m)))
+        .collect();
+    matches.sort_by_key(|(_, m)| std::cmp::Reverse(m.score));
+    matches
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_subsequences_ignoring_case() {
+        let m = fuzzy_match("ofl", "Open File").unwrap();
+        assert_eq!(m.positions, [0, 5, 7]);
+        assert!(fuzzy_match("xyz", "Open File").is_none());
+        assert!(fuzzy_match("fo", "Open File").is_none());
+        assert_eq!(fuzzy_match("", "anything").unwrap().score, 0);
+        // Spaces in the query are ignored.
+        assert!(fuzzy_match("op fi", "Open File").is_some());
+    }
+
+    #[test]
+    fn word_starts_and_runs_score_higher() {
+        let start = fuzzy_match("sa", "Save All").unwrap().score;
+        let middle = fuzzy_match("sa", "Disable Autosave").unwrap().score;
+        assert!(start > middle);
+        let run = fuzzy_match("open", "Open Recent").unwrap().score;
+        let scattered = fuzzy_match("open", "Toggle Preview Pane").unwrap().score;
+        assert!(run > scattered);
+        // Camel humps count as word starts.
+        assert_eq!(fuzzy_match("dt", "DataTable").unwrap().positions, [0, 4]);
+    }
+
+    #[test]
+    fn rank_sorts_best_first_and_drops_misses() {
+        let names = ["Toggle Sidebar", "Save File", "Quit", "Save All"];
+        let ranked: Vec<usize> = rank("sa", names).into_iter().map(|(i, _)| i).collect();
+        assert_eq!(ranked, [1, 3, 0]);
+        assert_eq!(rank("", names).len(), 4);
+    }
+}
@@ -0,0 +1,302 @@
+//! Declarative layout files: build widget subtrees from data at runtime.
+//!
+//! A [`LayoutNode`] mirrors one `view!` element — a widget type, an optional
+//! id, CSS classes, string attributes, and children. With the `serde` feature
+//! it derives `Deserialize`, so a layout can be read from TOML, RON, JSON or
+//! any other serde format the application depends on:
+//!
+//! ```ignore
+//! let node: LayoutNode = toml::from_str(r#"
+//!     widget = "Container"
+//!     id = "main"
+//!     [[children]]
+//!     widget = "Button"
+//!     attrs = { label = "Save" }
+//! "#)?;
+//! let widget = LayoutLoader::new(&app.widgets).load(&node)?;
+//! ```
+//!
+//! The [`LayoutLoader`] validates every node against a [`WidgetRegistry`]
+//! (the built-in widgets plus anything plugins registered) before building, so
+//! a typo in a layout file is reported with its widget and attribute name
+//! instead of silently producing an empty tree.
+
+use std::collections::BTreeMap;
+
+use crate::widget::registry::WidgetRegistry;
+use crate::widget::traits::{Widget, WidgetBuilder};
+
+// ---------------------------------------------------------------------------
+// LayoutNode
+// ---------------------------------------------------------------------------
+
+/// One element of a declarative layout: `<widget id class attrs...>children</widget>`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LayoutNode {
+    /// The widget type name (e.g. `"Button"`).
+    pub widget: String,
+    /// Optional CSS id.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub id: Option<String>,
+    /// CSS classes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub classes: Vec<String>,
+    /// Widget-specific attributes, as in `view!` (`label`, `title`, ...).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub attrs: BTreeMap<String, String>,
+    /// Child elements.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub children: Vec<LayoutNode>,
+}
+
+impl LayoutNode {
+    /// Create a node for the given widget type.
+    pub fn new(widget: impl Into<String>) -> Self {
+        Self {
+            widget: widget.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Set the CSS id (builder pattern).
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Add a CSS class (builder pattern).
+    pub fn with_class(mut self, class: impl Into<String>) -> Self {
+        self.classes.push(class.into());
+        self
+    }
+
+    /// Set an attribute (builder pattern).
+    pub fn with_attr(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.attrs.insert(name.into(), value.into());
+        self
+    }
+
+    /// Add a child node (builder pattern).
+    pub fn with_child(mut self, child: LayoutNode) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// The value of an attribute, if set.
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs.get(name).map(String::as_str)
+    }
+
+    /// The value of an attribute that the widget cannot be built without.
+    pub fn required_attr(&self, name: &str) -> Result<&str, LayoutFileError> {
+        self.attr(name).ok_or_else(|| LayoutFileError::MissingAttr {
+            widget: self.widget.clone(),
+            attr: name.to_owned(),
+        })
+    }
+
+    /// Wrap a built widget with this node's id and classes.
+    pub fn decorate<W: Widget + 'static>(&self, widget: W) -> Box<dyn Widget> {
+        let mut classes: Vec<String> = Vec::new();
+        for class in &self.classes {
+            if !classes.contains(class) {
+                classes.push(class.clone());
+            }
+        }
+        Box::new(WidgetBuilder {
+            id: self.id.clone(),
+            classes,
+            ..WidgetBuilder::new(widget)
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// LayoutFileError
+// ---------------------------------------------------------------------------
+
+/// Errors from validating or building a [`LayoutNode`] tree.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum LayoutFileError {
+    /// No constructor is registered for the widget type.
+    #[error("unknown widget type `{0}`")]
+    UnknownWidget(String),
+    /// The widget does not accept an attribute.
+    #[error("`{widget}` has no attribute `{attr}`")]
+    UnknownAttr { widget: String, attr: String },
+    /// A required attribute is missing.
+    #[error("`{widget}` requires attribute `{attr}`")]
+    MissingAttr { widget: String, attr: String },
+    /// An attribute value could not be interpreted.
+    #[error("invalid value `{value}` for `{widget}.{attr}`")]
+    InvalidValue {
+        widget: String,
+        attr: String,
+        value: String,
+    },
+    /// A leaf widget was given children.
+    #[error("`{0}` cannot have children")]
+    UnexpectedChildren(String),
+}
+
+// ---------------------------------------------------------------------------
+// LayoutLoader
+// ---------------------------------------------------------------------------
+
+/// Validates [`LayoutNode`] trees against a [`WidgetRegistry`] and builds them.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut registry = WidgetRegistry::new();
+/// gilt_gauge::register(&mut registry)?;
+/// let widgets = LayoutLoader::new(&registry).load_all(&nodes)?;
+/// ```
+pub struct LayoutLoader<'r> {
+    registry: &'r WidgetRegistry,
+}
+
+impl<'r> LayoutLoader<'r> {
+    /// Create a loader over the widget types in `registry`.
+    pub fn new(registry: &'r WidgetRegistry) -> Self {
+        Self { registry }
+    }
+
+    /// Check a node tree against the registered widget types without
+    /// building it.
+    pub fn validate(&self, node: &LayoutNode) -> Result<(), LayoutFileError> {
+        self.registry.validate(node)?;
+        node.children
+            .iter()
+            .try_for_each(|child| self.validate(child))
+    }
+
+    /// Validate and build a node tree into a widget.
+    pub fn load(&self, node: &LayoutNode) -> Result<Box<dyn Widget>, LayoutFileError> {
+        self.validate(node)?;
+        self.build(node)
+    }
+
+    /// Validate and build a list of top-level nodes, like `view!` does for
+    /// a sequence of elements.
+    pub fn load_all(&self, nodes: &[LayoutNode]) -> Result<Vec<Box<dyn Widget>>, LayoutFileError> {
+        nodes.iter().try_for_each(|node| self.validate(node))?;
+        nodes.iter().map(|node| self.build(node)).collect()
+    }
+
+    fn build(&self, node: &LayoutNode) -> Result<Box<dyn Widget>, LayoutFileError> {
+        let children = node
+            .children
+            .iter()
+            .map(|child| self.build(child))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.registry.build(node, children)
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::registry::WidgetInfo;
+    use crate::widgets::{Button, Static};
+
+    fn sample() -> LayoutNode {
+        LayoutNode::new("Container")
+            .with_id("main")
+            .with_class("vertical")
+            .with_child(LayoutNode::new("Header").with_attr("title", "App"))
+            .with_child(
+                LayoutNode::new("Button")
+                    .with_attr("label", "Save")
+                    .with_class("primary"),
+            )
+    }
+
+    #[test]
+    fn loads_builtin_tree() {
+        let widget = LayoutLoader::new(&WidgetRegistry::new())
+            .load(&sample())
+            .unwrap();
+        assert_eq!(widget.widget_type(), "Container");
+        assert_eq!(widget.css_id(), Some("main"));
+        assert_eq!(widget.css_classes(), ["vertical"]);
+
+        let children = widget.child_widgets();
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0].widget_type(), "Header");
+        assert_eq!(children[1].css_classes(), ["primary"]);
+        let button = children[1]
+            .as_any()
+            .downcast_ref::<WidgetBuilder<Button>>()
+            .unwrap();
+        assert_eq!(button.widget.label(), "Save");
+    }
+
+    #[test]
+    fn rejects_unknown_widget_anywhere_in_tree() {
+        let node = sample().with_child(LayoutNode::new("Sidebar"));
+        assert_eq!(
+            LayoutLoader::new(&WidgetRegistry::new()).load(&node).err(),
+            Some(LayoutFileError::UnknownWidget("Sidebar".into()))
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_attr_and_leaf_children() {
+        let registry = WidgetRegistry::new();
+        let loader = LayoutLoader::new(&registry);
+        let typo = LayoutNode::new("Button").with_attr("lable", "Ok");
+        assert!(matches!(
+            loader.validate(&typo),
+            Err(LayoutFileError::UnknownAttr { attr, .. }) if attr == "lable"
+        ));
+
+        let leaf = LayoutNode::new("Static").with_child(LayoutNode::new("Static"));
+        assert_eq!(
+            loader.validate(&leaf),
+            Err(LayoutFileError::UnexpectedChildren("Static".into()))
+        );
+    }
+
+    #[test]
+    fn missing_required_attr() {
+        let error = LayoutLoader::new(&WidgetRegistry::new())
+            .load(&LayoutNode::new("Button"))
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "`Button` requires attribute `label`");
+    }
+
+    #[test]
+    fn registered_plugin_widgets_load() {
+        let mut registry = WidgetRegistry::empty();
+        registry
+            .register(WidgetInfo::new("Note").with_attrs(&["text"]), |node, _| {
+                Ok(node.decorate(Static::new(node.attr("text").unwrap_or_default())))
+            })
+            .unwrap();
+        let widgets = LayoutLoader::new(&registry)
+            .load_all(&[LayoutNode::new("Note").with_attr("text", "hi")])
+            .unwrap();
+        assert_eq!(widgets[0].widget_type(), "Static");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserializes_with_optional_fields_omitted() {
+        use serde::de::value::{Error, MapDeserializer};
+        use serde::de::Deserialize;
+
+        let fields = [("widget", "Static")];
+        let de = MapDeserializer::<_, Error>::new(fields.into_iter());
+        assert_eq!(
+            LayoutNode::deserialize(de).unwrap(),
+            LayoutNode::new("Static")
+        );
+    }
+}
@@ -1,9 +1,37 @@
-//! Widget system: trait, lifecycle, scrolling.
+//! Widget system: trait, lifecycle, scrolling, components, conditional
+//! rendering, reconciliation, filtering, fuzzy matching, redaction, lazy data sources,
+//! declarative layout files, widget registry, background tasks,
+//! undo history, list selection, rubber-band selection.
 
 pub mod traits;
 pub mod lifecycle;
 pub mod scroll;
+pub mod reconcile;
+pub mod component;
+pub mod control_flow;
+pub mod filter;
+pub mod fuzzy;
+pub mod redact;
+pub mod data_source;
+pub mod layout_file;
+pub mod registry;
+pub mod task;
+pub mod undo;
+pub mod selection;
+pub mod rubber_band;
 
 pub use traits::{Widget, WidgetBuilder, WidgetExt};
 pub use lifecycle::{LifecycleEvent, LifecycleTracker};
 pub use scroll::{ScrollState, ScrollbarState};
+pub use component::{Component, ComponentHost};
+pub use control_flow::{Dynamic, Show, Suspense};
+pub use filter::{FilterMatch, Filterable};
+pub use fuzzy::{fuzzy_match, FuzzyMatch};
+pub use redact::{RedactPattern, Redactor};
+pub use data_source::{DataSource, Fetch, PagedCache};
+pub use layout_file::{LayoutFileError, LayoutLoader, LayoutNode};
+pub use registry::{BuildFn, RegistryError, WidgetInfo, WidgetRegistry, WIDGET_API_VERSION};
+pub use task::{TaskContext, TaskHandle, TaskSet, WidgetContext};
+pub use undo::UndoStack;
+pub use selection::{Selected, SelectionMode, SelectionModel};
+pub use rubber_band::{RegionSelected, RubberBand};
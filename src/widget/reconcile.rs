@@ -0,0 +1,432 @@
+//! Widget tree reconciliation: diff a freshly built widget tree against the DOM.
+//!
+//! Instead of tearing down and re-inserting a subtree on every refresh, the
+//! reconciler walks the new widgets alongside the existing DOM children and
+//! buffers the minimal set of operations into a [`Transaction`]: nodes of the
+//! same type and id are kept (and updated only if their classes, focus or
+//! disabled flags, tooltip, border title, accessible label, or displayed text
+//! changed),
+//! everything else is removed or inserted. Widgets that report
+//! [`Widget::child_keys`] have their children matched by key instead of by
+//! position, so reordered items are moved rather than recreated.
+
+use std::collections::HashMap;
+
+use crate::dom::node::{NodeData, NodeId};
+use crate::dom::transaction::{Target, Transaction};
+use crate::dom::tree::Dom;
+use crate::widget::traits::Widget;
+
+// ---------------------------------------------------------------------------
+// Node construction
+// ---------------------------------------------------------------------------
+
+/// Class added to nodes whose widget is loading.
+pub const LOADING_CLASS: &str = "loading";
+
+/// Build the DOM node data describing a widget (type, id, classes, focus,
+/// disabled and loading state, tooltip, border title, accessible label,
+/// displayed text, key bindings table).
+pub fn node_data(widget: &dyn Widget) -> NodeData {
+    let mut data = NodeData::new(widget.widget_type())
+        .focusable(widget.can_focus())
+        .disabled(widget.is_disabled());
+    if let Some(id) = widget.css_id() {
+        data = data.with_id(id);
+    }
+    data.tooltip = widget.tooltip_text().map(str::to_owned);
+    data.border_title = widget.border_title_text().map(str::to_owned);
+    data.aria_label = widget.aria_label().map(str::to_owned);
+    data.content = widget.content_text().map(str::to_owned);
+    data.bindings = widget.bindings();
+    data = data.with_classes(widget.css_classes().iter().cloned());
+    if widget.is_loading() {
+        data = data.with_class(LOADING_CLASS);
+    }
+    data
+}
+
+/// Buffer insertion of `widget` and its child widgets under `parent`.
+pub fn insert_subtree(tx: &mut Transaction, parent: impl Into<Target>, widget: &dyn Widget) {
+    insert_keyed(tx, parent.into(), widget, None);
+}
+
+/// Buffer insertion of a widget subtree, tagging the top node with `key`.
+fn insert_keyed(tx: &mut Transaction, parent: Target, widget: &dyn Widget, key: Option<&str>) {
+    let mut data = node_data(widget);
+    data.key = key.map(str::to_owned);
+    let node = tx.insert_child(parent, data);
+    let keys = child_keys(widget);
+    for (i, child) in widget.child_widgets().iter().enumerate() {
+        let key = keys.map(|k| k[i].as_str());
+        insert_keyed(tx, node.into(), child.as_ref(), key);
+    }
+}
+
+/// The widget's child keys, if it has exactly one per child.
+fn child_keys(widget: &dyn Widget) -> Option<&[String]> {
+    widget
+        .child_keys()
+        .filter(|keys| keys.len() == widget.child_widgets().len())
+}
+
+// ---------------------------------------------------------------------------
+// Reconciliation
+// ---------------------------------------------------------------------------
+
+/// Whether an existing node can be reused for `widget`.
+fn same_kind(data: &NodeData, widget: &dyn Widget) -> bool {
+    data.widget_type == widget.widget_type() && data.id.as_deref() == widget.css_id()
+}
+
+/// Buffer an update of `id` if its classes, flags, tooltip, border title,
+/// accessible label, or displayed text differ from `widget`.
+fn update_node(tx: &mut Transaction, id: NodeId, data: &NodeData, widget: &dyn Widget) {
+    let new = node_data(widget);
+    if data.classes != new.classes
+        || data.focusable != new.focusable
+        || data.disabled != new.disabled
+        || data.tooltip != new.tooltip
+        || data.border_title != new.border_title
+        || data.aria_label != new.aria_label
+        || data.content != new.content
+    {
+        tx.update(id, move |node| {
+            node.classes = new.classes;
+            node.focusable = new.focusable;
+            node.disabled = new.disabled;
+            node.tooltip = new.tooltip;
+            node.border_title = new.border_title;
+            node.aria_label = new.aria_label;
+            node.content = new.content;
+        });
+    }
+}
+
+/// Reconcile the children of `parent` against `widgets`, by position.
+///
+/// Children are matched in order while they have the same widget type and CSS
+/// id; matched nodes are kept and recursed into. From the first mismatch on,
+/// the remaining old children are removed and the remaining widgets inserted,
+/// which keeps sibling order correct since insertion always appends.
+pub fn reconcile_children(
+    tx: &mut Transaction,
+    dom: &Dom,
+    parent: NodeId,
+    widgets: &[Box<dyn Widget>],
+) {
+    let old = dom.children(parent);
+    let mut matched = 0;
+
+    for (&id, widget) in old.iter().zip(widgets) {
+        let Some(data) = dom.get(id) else { break };
+        if !same_kind(data, widget.as_ref()) {
+            break;
+        }
+        update_node(tx, id, data, widget.as_ref());
+        reconcile_node(tx, dom, id, widget.as_ref());
+        matched += 1;
+    }
+
+    for &id in &old[matched..] {
+        tx.remove(id);
+    }
+    for widget in &widgets[matched..] {
+        insert_subtree(tx, parent, widget.as_ref());
+    }
+}
+
+/// Reconcile the children of the existing node `node` against `widget`'s
+/// child widgets, by key if the widget provides keys and by position otherwise.
+pub fn reconcile_node(tx: &mut Transaction, dom: &Dom, node: NodeId, widget: &dyn Widget) {
+    match child_keys(widget) {
+        Some(keys) => reconcile_keyed(tx, dom, node, keys, widget.child_widgets()),
+        None => reconcile_children(tx, dom, node, widget.child_widgets()),
+    }
+}
+
+/// Reconcile the children of `parent` against `widgets`, matching by key.
+///
+/// Old children whose key reappears (with the same widget type and id) are
+/// reused; all others are removed. Reused nodes that already sit in the right
+/// order stay put; from the first out-of-order or new item on, nodes are
+/// appended in the new order (moving reused ones with a reparent).
+pub fn reconcile_keyed(
+    tx: &mut Transaction,
+    dom: &Dom,
+    parent: NodeId,
+    keys: &[String],
+    widgets: &[Box<dyn Widget>],
+) {
+    let old = dom.children(parent);
+    let mut by_key: HashMap<&str, NodeId> = old
+        .iter()
+        .filter_map(|&id| Some((dom.get(id)?.key.as_deref()?, id)))
+        .collect();
+
+    // Pair each new item with the node it reuses, if any.
+    let reused: Vec<Option<NodeId>> = keys
+        .iter()
+        .zip(widgets)
+        .map(|(key, widget)| {
+            let id = by_key.remove(key.as_str())?;
+            let data = dom.get(id)?;
+            if same_kind(data, widget.as_ref()) {
+                Some(id)
+            } else {
+                by_key.insert(key.as_str(), id);
+                None
+            }
+        })
+        .collect();
+
+    // Remove every old child that is not reused.
+    for &id in old {
+        if !reused.contains(&Some(id)) {
+            tx.remove(id);
+        }
+    }
+
+    // Reused nodes keep their place while they follow the old order and
+    // nothing has been appended yet.
+    let position = |id: NodeId| old.iter().position(|&o| o == id);
+    let mut last_kept = None;
+    let mut appending = false;
+
+    for ((key, widget), slot) in keys.iter().zip(widgets).zip(&reused) {
+        match *slot {
+            Some(id) => {
+                let data = dom.get(id).expect("reused node exists");
+                update_node(tx, id, data, widget.as_ref());
+                reconcile_node(tx, dom, id, widget.as_ref());
+                let pos = position(id);
+                if !appending && pos > last_kept {
+                    last_kept = pos;
+                } else {
+                    appending = true;
+                    tx.reparent(id, parent);
+                }
+            }
+            None => {
+                appending = true;
+                insert_keyed(tx, parent.into(), widget.as_ref(), Some(key));
+            }
+        }
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::traits::WidgetExt;
+    use crate::widgets::{Button, Container, Static};
+
+    fn keyed(items: &[&str]) -> Container {
+        let mut list = Container::new();
+        list.set_children_keyed(items.iter().copied(), |s| *s, |s| Box::new(Static::new(*s)));
+        list
+    }
+
+    fn keys_of(dom: &Dom, parent: NodeId) -> Vec<String> {
+        dom.children(parent)
+            .iter()
+            .map(|&id| dom.get(id).unwrap().key.clone().unwrap())
+            .collect()
+    }
+
+    fn mount(dom: &mut Dom, widgets: &[Box<dyn Widget>]) -> NodeId {
+        let root = dom.insert(NodeData::new("Root"));
+        let mut tx = Transaction::new();
+        reconcile_children(&mut tx, dom, root, widgets);
+        dom.apply(tx).unwrap();
+        root
+    }
+
+    #[test]
+    fn node_data_from_widget() {
+        let data = node_data(&Button::new("Ok").with_id("ok").add_class("primary"));
+        assert_eq!(data.widget_type, "Button");
+        assert_eq!(data.id.as_deref(), Some("ok"));
+        assert_eq!(data.classes, vec!["primary"]);
+        assert!(data.focusable);
+    }
+
+    #[test]
+    fn initial_reconcile_inserts_tree() {
+        let mut dom = Dom::new();
+        let view: Vec<Box<dyn Widget>> = vec![Box::new(
+            Container::new()
+                .with_child(Static::new("a"))
+                .with_child(Static::new("b")),
+        )];
+        let root = mount(&mut dom, &view);
+        let container = dom.children(root)[0];
+        assert_eq!(dom.children(container).len(), 2);
+    }
+
+    #[test]
+    fn matching_tree_produces_no_ops() {
+        let mut dom = Dom::new();
+        let view = || -> Vec<Box<dyn Widget>> {
+            vec![Box::new(Static::new("a")), Box::new(Button::new("b"))]
+        };
+        let root = mount(&mut dom, &view());
+        let mut tx = Transaction::new();
+        reconcile_children(&mut tx, &dom, root, &view());
+        assert!(tx.is_empty());
+    }
+
+    #[test]
+    fn changed_classes_update_in_place() {
+        let mut dom = Dom::new();
+        let root = mount(&mut dom, &[Box::new(Static::new("a"))]);
+        let before = dom.children(root)[0];
+
+        let mut tx = Transaction::new();
+        reconcile_children(
+            &mut tx,
+            &dom,
+            root,
+            &[Box::new(Static::new("a").with_class("on"))],
+        );
+        let changes = dom.apply(tx).unwrap();
+
+        assert_eq!(changes.updated, vec![before]);
+        assert_eq!(dom.children(root), [before]);
+        assert!(dom.get(before).unwrap().has_class("on"));
+    }
+
+    #[test]
+    fn changed_text_updates_in_place() {
+        let mut dom = Dom::new();
+        let root = mount(&mut dom, &[Box::new(Static::new("before"))]);
+        let node = dom.children(root)[0];
+
+        let mut tx = Transaction::new();
+        reconcile_children(&mut tx, &dom, root, &[Box::new(Static::new("after"))]);
+        let changes = dom.apply(tx).unwrap();
+
+        assert_eq!(changes.updated, vec![node]);
+        assert_eq!(dom.get(node).unwrap().content.as_deref(), Some("after"));
+    }
+
+    #[test]
+    fn sugar_state_reaches_the_node() {
+        use crate::reactive::create_signal;
+
+        let (busy, set_busy) = create_signal(false);
+        let view = || -> Vec<Box<dyn Widget>> {
+            vec![Box::new(
+                Button::new("Save")
+                    .tooltip("Write to disk")
+                    .border_title("Actions")
+                    .with_aria_label("Save file")
+                    .loading(busy)
+                    .disabled(busy),
+            )]
+        };
+        let mut dom = Dom::new();
+        let root = mount(&mut dom, &view());
+        let button = dom.children(root)[0];
+        let data = dom.get(button).unwrap();
+        assert_eq!(data.tooltip.as_deref(), Some("Write to disk"));
+        assert_eq!(data.border_title.as_deref(), Some("Actions"));
+        assert_eq!(data.aria_label.as_deref(), Some("Save file"));
+        assert!(!data.disabled && !data.has_class(LOADING_CLASS));
+
+        set_busy.set(true);
+        let mut tx = Transaction::new();
+        reconcile_children(&mut tx, &dom, root, &view());
+        let changes = dom.apply(tx).unwrap();
+        assert_eq!(changes.updated, vec![button]);
+        let data = dom.get(button).unwrap();
+        assert!(data.disabled && data.has_class(LOADING_CLASS));
+    }
+
+    #[test]
+    fn mismatch_replaces_tail() {
+        let mut dom = Dom::new();
+        let root = mount(
+            &mut dom,
+            &[Box::new(Static::new("a")), Box::new(Static::new("b"))],
+        );
+        let first = dom.children(root)[0];
+
+        let mut tx = Transaction::new();
+        let view: Vec<Box<dyn Widget>> = vec![
+            Box::new(Static::new("a")),
+            Box::new(Button::new("b")),
+            Box::new(Static::new("c")),
+        ];
+        reconcile_children(&mut tx, &dom, root, &view);
+        let changes = dom.apply(tx).unwrap();
+
+        assert_eq!(changes.removed.len(), 1);
+        assert_eq!(changes.inserted.len(), 2);
+        let kids = dom.children(root);
+        assert_eq!(kids[0], first);
+        assert_eq!(dom.get(kids[1]).unwrap().widget_type, "Button");
+        assert_eq!(dom.get(kids[2]).unwrap().widget_type, "Static");
+    }
+
+    #[test]
+    fn keyed_initial_insert_sets_keys() {
+        let mut dom = Dom::new();
+        let root = mount(&mut dom, &[Box::new(keyed(&["a", "b"]))]);
+        let list = dom.children(root)[0];
+        assert_eq!(keys_of(&dom, list), ["a", "b"]);
+    }
+
+    #[test]
+    fn keyed_reorder_moves_nodes() {
+        let mut dom = Dom::new();
+        let root = mount(&mut dom, &[Box::new(keyed(&["a", "b", "c"]))]);
+        let list = dom.children(root)[0];
+        let old = dom.children(list).to_vec();
+
+        let mut tx = Transaction::new();
+        reconcile_node(&mut tx, &dom, list, &keyed(&["c", "a", "b"]));
+        let changes = dom.apply(tx).unwrap();
+
+        assert!(changes.inserted.is_empty());
+        assert!(changes.removed.is_empty());
+        assert_eq!(keys_of(&dom, list), ["c", "a", "b"]);
+        assert_eq!(dom.children(list), [old[2], old[0], old[1]]);
+    }
+
+    #[test]
+    fn keyed_insert_and_remove() {
+        let mut dom = Dom::new();
+        let root = mount(&mut dom, &[Box::new(keyed(&["a", "b", "c"]))]);
+        let list = dom.children(root)[0];
+        let old = dom.children(list).to_vec();
+
+        let mut tx = Transaction::new();
+        reconcile_node(&mut tx, &dom, list, &keyed(&["a", "x", "c"]));
+        let changes = dom.apply(tx).unwrap();
+
+        assert_eq!(changes.removed, vec![old[1]]);
+        assert_eq!(changes.inserted.len(), 1);
+        assert_eq!(keys_of(&dom, list), ["a", "x", "c"]);
+        assert_eq!(dom.children(list)[0], old[0]);
+        assert_eq!(dom.children(list)[2], old[2]);
+    }
+
+    #[test]
+    fn keyed_append_leaves_existing_untouched() {
+        let mut dom = Dom::new();
+        let root = mount(&mut dom, &[Box::new(keyed(&["a", "b"]))]);
+        let list = dom.children(root)[0];
+
+        let mut tx = Transaction::new();
+        reconcile_node(&mut tx, &dom, list, &keyed(&["a", "b", "c"]));
+        let changes = dom.apply(tx).unwrap();
+
+        assert_eq!(changes.inserted.len(), 1);
+        assert!(changes.updated.is_empty());
+    }
+}
@@ -0,0 +1,257 @@
+//! Redaction of secrets in displayed text.
+//!
+//! A [`Redactor`] holds a list of [`RedactPattern`]s describing what counts as
+//! a secret (API tokens by prefix, `password=...` style values, or known
+//! literal strings) and masks every match before text is drawn. Widgets that
+//! show arbitrary text, like `DataTable`, run their cells through one, so
+//! screenshots and exports of an ops dashboard don't leak credentials. The
+//! mask is lifted while the redactor is revealed.
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+/// The default mask character.
+pub const MASK: char = '•';
+
+// ---------------------------------------------------------------------------
+// RedactPattern
+// ---------------------------------------------------------------------------
+
+/// What a [`Redactor`] treats as secret.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RedactPattern {
+    /// A word starting with this prefix, e.g. `ghp_` or `sk-`; the prefix
+    /// stays visible so readers can tell what kind of token it was.
+    Prefix(String),
+    /// The value after this key and a `=` or `:` separator, e.g. `password`
+    /// in `password=hunter2` or `token: abc`. Keys match case-insensitively.
+    KeyValue(String),
+    /// An exact secret string.
+    Literal(String),
+}
+
+impl RedactPattern {
+    /// Character ranges of `text` to mask.
+    fn ranges(&self, text: &[char]) -> Vec<Range<usize>> {
+        match self {
+            RedactPattern::Prefix(prefix) => {
+                let prefix: Vec<char> = prefix.chars().collect();
+                find_all(text, &prefix, false)
+                    .filter(|&start| start == 0 || !is_word(text[start - 1]))
+                    .map(|start| start + prefix.len()..word_end(text, start + prefix.len()))
+                    .filter(|range| !range.is_empty())
+                    .collect()
+            }
+            RedactPattern::KeyValue(key) => {
+                let key: Vec<char> = key.chars().collect();
+                find_all(text, &key, true)
+                    .filter_map(|start| {
+                        let mut i = start + key.len();
+                        while text.get(i) == Some(&' ') {
+                            i += 1;
+                        }
+                        if !matches!(text.get(i), Some('=' | ':')) {
+                            return None;
+                        }
+                        i += 1;
+                        while text.get(i) == Some(&' ') {
+                            i += 1;
+                        }
+                        Some(i..value_end(text, i)).filter(|range| !range.is_empty())
+                    })
+                    .collect()
+            }
+            RedactPattern::Literal(secret) => {
+                let secret: Vec<char> = secret.chars().collect();
+                find_all(text, &secret, false)
+                    .map(|start| start..start + secret.len())
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Start indices of every occurrence of `needle` in `text`.
+fn find_all<'a>(
+    text: &'a [char],
+    needle: &'a [char],
+    ignore_case: bool,
+) -> impl Iterator<Item = usize> + 'a {
+    let eq = move |a: &char, b: &char| {
+        if ignore_case {
+            a.to_lowercase().eq(b.to_lowercase())
+        } else {
+            a == b
+        }
+    };
+    (0..text.len().saturating_sub(needle.len()) + 1).filter(move |&start| {
+        !needle.is_empty()
+            && text.len() >= needle.len()
+            && text[start..start + needle.len()]
+                .iter()
+                .zip(needle)
+                .all(|(a, b)| eq(a, b))
+    })
+}
+
+/// Characters that can continue a token.
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '/' | '+' | '=')
+}
+
+fn word_end(text: &[char], start: usize) -> usize {
+    (start..text.len())
+        .find(|&i| !is_word(text[i]))
+        .unwrap_or(text.len())
+}
+
+/// The end of a `key=value` value: up to whitespace or a separator.
+fn value_end(text: &[char], start: usize) -> usize {
+    (start..text.len())
+        .find(|&i| text[i].is_whitespace() || matches!(text[i], ',' | ';' | '&' | '"' | '\''))
+        .unwrap_or(text.len())
+}
+
+// ---------------------------------------------------------------------------
+// Redactor
+// ---------------------------------------------------------------------------
+
+/// Masks secrets in text unless revealed.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut redactor = Redactor::new()
+///     .with_pattern(RedactPattern::Prefix("ghp_".into()))
+///     .with_pattern(RedactPattern::KeyValue("password".into()));
+/// assert_eq!(redactor.redact("password=hunter2"), "password=•••••••");
+/// redactor.set_revealed(true);
+/// assert_eq!(redactor.redact("password=hunter2"), "password=hunter2");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Redactor {
+    patterns: Vec<RedactPattern>,
+    mask: char,
+    revealed: bool,
+}
+
+impl Redactor {
+    /// Create a redactor with no patterns.
+    pub fn new() -> Self {
+        Self {
+            patterns: Vec::new(),
+            mask: MASK,
+            revealed: false,
+        }
+    }
+
+    /// Add a pattern to mask (builder pattern).
+    pub fn with_pattern(mut self, pattern: RedactPattern) -> Self {
+        self.add_pattern(pattern);
+        self
+    }
+
+    /// Set the mask character (builder pattern). Defaults to `•`.
+    pub fn with_mask(mut self, mask: char) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    /// Add a pattern to mask.
+    pub fn add_pattern(&mut self, pattern: RedactPattern) {
+        self.patterns.push(pattern);
+    }
+
+    /// The configured patterns.
+    pub fn patterns(&self) -> &[RedactPattern] {
+        &self.patterns
+    }
+
+    /// Whether secrets are currently shown in the clear.
+    pub fn is_revealed(&self) -> bool {
+        self.revealed
+    }
+
+    /// Show or mask secrets.
+    pub fn set_revealed(&mut self, revealed: bool) {
+        self.revealed = revealed;
+    }
+
+    /// Flip between showing and masking secrets.
+    pub fn toggle_revealed(&mut self) {
+        self.revealed = !self.revealed;
+    }
+
+    /// `text` with every secret masked, one mask character per character.
+    /// Borrowed when nothing needed masking.
+    pub fn redact<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if self.revealed || self.patterns.is_empty() {
+            return Cow::Borrowed(text);
+        }
+        let chars: Vec<char> = text.chars().collect();
+        let mut masked = vec![false; chars.len()];
+        for range in self.patterns.iter().flat_map(|p| p.ranges(&chars)) {
+            masked[range].fill(true);
+        }
+        if !masked.contains(&true) {
+            return Cow::Borrowed(text);
+        }
+        chars
+            .iter()
+            .zip(masked)
+            .map(|(&c, masked)| if masked { self.mask } else { c })
+            .collect::<String>()
+            .into()
+    }
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redactor() -> Redactor {
+        Redactor::new()
+            .with_pattern(RedactPattern::Prefix("ghp_".into()))
+            .with_pattern(RedactPattern::KeyValue("password".into()))
+            .with_pattern(RedactPattern::Literal("s3cr3t".into()))
+    }
+
+    #[test]
+    fn masks_each_pattern_kind() {
+        let r = redactor();
+        assert_eq!(r.redact("push with ghp_ab12 ok"), "push with ghp_•••• ok");
+        assert_eq!(
+            r.redact("PASSWORD: hunter2, user=bob"),
+            "PASSWORD: •••••••, user=bob"
+        );
+        assert_eq!(r.redact("key is s3cr3t."), "key is ••••••.");
+    }
+
+    #[test]
+    fn leaves_other_text_borrowed() {
+        let r = redactor();
+        assert!(matches!(r.redact("nothing here"), Cow::Borrowed(_)));
+        // A prefix inside a longer word is not a token.
+        assert_eq!(r.redact("xghp_abc"), "xghp_abc");
+        assert_eq!(r.redact("password"), "password");
+    }
+
+    #[test]
+    fn reveal_toggle_and_custom_mask() {
+        let mut r = redactor().with_mask('*');
+        assert_eq!(r.redact("password=abc"), "password=***");
+        r.toggle_revealed();
+        assert!(r.is_revealed());
+        assert_eq!(r.redact("password=abc"), "password=abc");
+    }
+}
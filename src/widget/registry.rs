@@ -0,0 +1,418 @@
+//! Widget registry: constructors and capability metadata by type name.
+//!
+//! The [`WidgetRegistry`] maps widget type names to a constructor and a
+//! [`WidgetInfo`] describing what the widget accepts. Layout files and
+//! `view! { in registry; ... }` are built through it, code can create widgets
+//! whose type is only known at runtime with [`WidgetRegistry::create`], and
+//! tooling can list every installed widget with [`WidgetRegistry::infos`].
+//!
+//! Third-party widget crates expose a function that registers their widgets:
+//!
+//! ```ignore
+//! pub fn register(registry: &mut WidgetRegistry) -> Result<(), RegistryError> {
+//!     registry.register(
+//!         WidgetInfo::new("Gauge")
+//!             .with_provider("gilt-gauge", env!("CARGO_PKG_VERSION"))
+//!             .with_attrs(&["value"]),
+//!         |node, _| Ok(node.decorate(Gauge::new(node.attr("value").unwrap_or("0")))),
+//!     )
+//! }
+//! ```
+//!
+//! Each [`WidgetInfo`] carries the registry API version it was written
+//! against; registration fails with [`RegistryError::IncompatibleApi`] when a
+//! plugin targets a newer API than this crate provides.
+
+use std::collections::BTreeMap;
+
+use crate::widget::layout_file::{LayoutFileError, LayoutNode};
+use crate::widget::traits::Widget;
+use crate::widgets::{Button, Container, Footer, Header, Input, Static};
+
+/// The registry API version this crate implements.
+pub const WIDGET_API_VERSION: u32 = 1;
+
+/// The provider name used for the built-in widgets.
+const BUILTIN_PROVIDER: &str = "gilt-tui";
+
+// ---------------------------------------------------------------------------
+// WidgetInfo
+// ---------------------------------------------------------------------------
+
+/// Capability metadata for a registered widget type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WidgetInfo {
+    /// The widget type name, as used in CSS and layout files.
+    pub name: String,
+    /// The crate (or plugin) that provides the widget.
+    pub provider: String,
+    /// The provider's version.
+    pub version: String,
+    /// The registry API version the widget was written against.
+    pub api_version: u32,
+    /// Attributes the widget accepts.
+    pub attrs: Vec<String>,
+    /// Whether the widget may have child elements.
+    pub accepts_children: bool,
+    /// Whether the widget can receive focus.
+    pub focusable: bool,
+}
+
+impl WidgetInfo {
+    /// Metadata for a leaf widget with no attributes, provided by gilt-tui at
+    /// the current API version.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            provider: BUILTIN_PROVIDER.to_owned(),
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            api_version: WIDGET_API_VERSION,
+            attrs: Vec::new(),
+            accepts_children: false,
+            focusable: false,
+        }
+    }
+
+    /// Set the providing crate and its version (builder pattern).
+    pub fn with_provider(
+        mut self,
+        provider: impl Into<String>,
+        version: impl Into<String>,
+    ) -> Self {
+        self.provider = provider.into();
+        self.version = version.into();
+        self
+    }
+
+    /// Set the registry API version the widget targets (builder pattern).
+    pub fn with_api_version(mut self, api_version: u32) -> Self {
+        self.api_version = api_version;
+        self
+    }
+
+    /// Set the accepted attributes (builder pattern).
+    pub fn with_attrs(mut self, attrs: &[&str]) -> Self {
+        self.attrs = attrs.iter().map(|&attr| attr.to_owned()).collect();
+        self
+    }
+
+    /// Allow child elements (builder pattern).
+    pub fn with_children(mut self, accepts_children: bool) -> Self {
+        self.accepts_children = accepts_children;
+        self
+    }
+
+    /// Mark the widget as focusable (builder pattern).
+    pub fn with_focusable(mut self, focusable: bool) -> Self {
+        self.focusable = focusable;
+        self
+    }
+
+    /// Whether the widget accepts an attribute.
+    pub fn accepts_attr(&self, attr: &str) -> bool {
+        self.attrs.iter().any(|a| a == attr)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// RegistryError
+// ---------------------------------------------------------------------------
+
+/// Errors from registering a widget type.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RegistryError {
+    /// The widget targets a registry API this crate does not implement.
+    #[error("`{widget}` requires widget API v{api_version}, but v{supported} is available")]
+    IncompatibleApi {
+        widget: String,
+        api_version: u32,
+        supported: u32,
+    },
+    /// Another provider already registered the type name.
+    #[error("`{widget}` is already registered by `{provider}`")]
+    AlreadyRegistered { widget: String, provider: String },
+}
+
+// ---------------------------------------------------------------------------
+// WidgetRegistry
+// ---------------------------------------------------------------------------
+
+/// Builds a widget from a node and its already built children.
+pub type BuildFn =
+    Box<dyn Fn(&LayoutNode, Vec<Box<dyn Widget>>) -> Result<Box<dyn Widget>, LayoutFileError>>;
+
+/// A registered widget type.
+struct Entry {
+    info: WidgetInfo,
+    build: BuildFn,
+}
+
+/// Widget constructors and metadata, keyed by type name.
+pub struct WidgetRegistry {
+    entries: BTreeMap<String, Entry>,
+}
+
+impl WidgetRegistry {
+    /// Create a registry holding the built-in widgets.
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        registry.replace(
+            WidgetInfo::new("Static").with_attrs(&["content"]),
+            |node, _| Ok(node.decorate(Static::new(node.attr("content").unwrap_or("")))),
+        );
+        registry.replace(
+            WidgetInfo::new("Button")
+                .with_attrs(&["label"])
+                .with_focusable(true),
+            |node, _| Ok(node.decorate(Button::new(node.required_attr("label")?))),
+        );
+        registry.replace(
+            WidgetInfo::new("Header").with_attrs(&["title", "subtitle"]),
+            |node, _| {
+                let mut header = Header::new(node.required_attr("title")?);
+                if let Some(subtitle) = node.attr("subtitle") {
+                    header = header.with_subtitle(subtitle);
+                }
+                Ok(node.decorate(header))
+            },
+        );
+        registry.replace(
+            WidgetInfo::new("Footer").with_attrs(&["content"]),
+            |node, _| Ok(node.decorate(Footer::new(node.attr("content").unwrap_or("")))),
+        );
+        registry.replace(
+            WidgetInfo::new("Input")
+                .with_attrs(&["placeholder", "value"])
+                .with_focusable(true),
+            |node, _| {
+                let mut input = Input::new();
+                if let Some(placeholder) = node.attr("placeholder") {
+                    input = input.with_placeholder(placeholder);
+                }
+                if let Some(value) = node.attr("value") {
+                    input = input.with_value(value);
+                }
+                Ok(node.decorate(input))
+            },
+        );
+        registry.replace(
+            WidgetInfo::new("Container").with_children(true),
+            |node, children| Ok(node.decorate(Container::new().with_children(children))),
+        );
+        registry
+    }
+
+    /// Create a registry with no widget types.
+    pub fn empty() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Register a widget type.
+    ///
+    /// Fails if the widget targets a newer API than [`WIDGET_API_VERSION`],
+    /// or if a different provider already registered the name. A provider
+    /// may re-register its own widgets (e.g. after a plugin reload).
+    pub fn register(
+        &mut self,
+        info: WidgetInfo,
+        build: impl Fn(&LayoutNode, Vec<Box<dyn Widget>>) -> Result<Box<dyn Widget>, LayoutFileError>
+            + 'static,
+    ) -> Result<(), RegistryError> {
+        if info.api_version > WIDGET_API_VERSION {
+            return Err(RegistryError::IncompatibleApi {
+                widget: info.name,
+                api_version: info.api_version,
+                supported: WIDGET_API_VERSION,
+            });
+        }
+        if let Some(existing) = self.entries.get(&info.name) {
+            if existing.info.provider != info.provider {
+                return Err(RegistryError::AlreadyRegistered {
+                    widget: info.name,
+                    provider: existing.info.provider.clone(),
+                });
+            }
+        }
+        self.replace(info, build);
+        Ok(())
+    }
+
+    /// Register a widget type, replacing any existing registration of the
+    /// name regardless of provider.
+    pub fn replace(
+        &mut self,
+        info: WidgetInfo,
+        build: impl Fn(&LayoutNode, Vec<Box<dyn Widget>>) -> Result<Box<dyn Widget>, LayoutFileError>
+            + 'static,
+    ) {
+        self.entries.insert(
+            info.name.clone(),
+            Entry {
+                info,
+                build: Box::new(build),
+            },
+        );
+    }
+
+    /// Remove a widget type, returning its metadata.
+    pub fn unregister(&mut self, name: &str) -> Option<WidgetInfo> {
+        self.entries.remove(name).map(|entry| entry.info)
+    }
+
+    /// Whether a widget type is registered.
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.contains_key(name)
+    }
+
+    /// Metadata for a widget type.
+    pub fn get(&self, name: &str) -> Option<&WidgetInfo> {
+        self.entries.get(name).map(|entry| &entry.info)
+    }
+
+    /// Metadata for every registered widget, sorted by name.
+    pub fn infos(&self) -> impl Iterator<Item = &WidgetInfo> {
+        self.entries.values().map(|entry| &entry.info)
+    }
+
+    /// The registered widgets from one provider, sorted by name.
+    pub fn provided_by<'a>(&'a self, provider: &'a str) -> impl Iterator<Item = &'a WidgetInfo> {
+        self.infos().filter(move |info| info.provider == provider)
+    }
+
+    /// Create a single widget by type name at runtime, validating the
+    /// attributes against its metadata.
+    pub fn create(
+        &self,
+        name: &str,
+        attrs: &[(&str, &str)],
+    ) -> Result<Box<dyn Widget>, LayoutFileError> {
+        let node = attrs
+            .iter()
+            .fold(LayoutNode::new(name), |node, &(attr, value)| {
+                node.with_attr(attr, value)
+            });
+        self.validate(&node)?;
+        self.build(&node, Vec::new())
+    }
+
+    /// Check one node (not its children) against the registered metadata.
+    pub(crate) fn validate(&self, node: &LayoutNode) -> Result<(), LayoutFileError> {
+        let info = &self.entry(node)?.info;
+        if let Some(attr) = node.attrs.keys().find(|a| !info.accepts_attr(a)) {
+            return Err(LayoutFileError::UnknownAttr {
+                widget: node.widget.clone(),
+                attr: attr.clone(),
+            });
+        }
+        if !info.accepts_children && !node.children.is_empty() {
+            return Err(LayoutFileError::UnexpectedChildren(node.widget.clone()));
+        }
+        Ok(())
+    }
+
+    /// Build one node from its already built children.
+    pub(crate) fn build(
+        &self,
+        node: &LayoutNode,
+        children: Vec<Box<dyn Widget>>,
+    ) -> Result<Box<dyn Widget>, LayoutFileError> {
+        (self.entry(node)?.build)(node, children)
+    }
+
+    fn entry(&self, node: &LayoutNode) -> Result<&Entry, LayoutFileError> {
+        self.entries
+            .get(&node.widget)
+            .ok_or_else(|| LayoutFileError::UnknownWidget(node.widget.clone()))
+    }
+}
+
+impl Default for WidgetRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gauge() -> WidgetInfo {
+        WidgetInfo::new("Gauge")
+            .with_provider("gilt-gauge", "0.3.1")
+            .with_attrs(&["value"])
+    }
+
+    fn build_gauge(
+        node: &LayoutNode,
+        _: Vec<Box<dyn Widget>>,
+    ) -> Result<Box<dyn Widget>, LayoutFileError> {
+        Ok(node.decorate(Static::new(node.attr("value").unwrap_or("0"))))
+    }
+
+    #[test]
+    fn builtins_are_registered_with_metadata() {
+        let registry = WidgetRegistry::new();
+        let names: Vec<&str> = registry.infos().map(|info| info.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["Button", "Container", "Footer", "Header", "Input", "Static"]
+        );
+        let button = registry.get("Button").unwrap();
+        assert_eq!(button.provider, "gilt-tui");
+        assert_eq!(button.api_version, WIDGET_API_VERSION);
+        assert!(button.focusable);
+        assert!(registry.get("Container").unwrap().accepts_children);
+    }
+
+    #[test]
+    fn plugin_registration_and_creation() {
+        let mut registry = WidgetRegistry::new();
+        registry.register(gauge(), build_gauge).unwrap();
+        assert_eq!(registry.provided_by("gilt-gauge").count(), 1);
+
+        let widget = registry.create("Gauge", &[("value", "42")]).unwrap();
+        assert_eq!(widget.widget_type(), "Static");
+        assert!(matches!(
+            registry.create("Gauge", &[("max", "9")]),
+            Err(LayoutFileError::UnknownAttr { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_newer_api_versions() {
+        let mut registry = WidgetRegistry::empty();
+        let error = registry
+            .register(
+                gauge().with_api_version(WIDGET_API_VERSION + 1),
+                build_gauge,
+            )
+            .unwrap_err();
+        assert!(matches!(error, RegistryError::IncompatibleApi { .. }));
+        assert!(!registry.contains("Gauge"));
+    }
+
+    #[test]
+    fn name_clashes_between_providers() {
+        let mut registry = WidgetRegistry::new();
+        let clash = WidgetInfo::new("Button").with_provider("other", "1.0.0");
+        assert_eq!(
+            registry.register(clash.clone(), build_gauge),
+            Err(RegistryError::AlreadyRegistered {
+                widget: "Button".into(),
+                provider: "gilt-tui".into(),
+            })
+        );
+        // Same provider may re-register; `replace` overrides anyone.
+        registry.register(gauge(), build_gauge).unwrap();
+        registry.register(gauge(), build_gauge).unwrap();
+        registry.replace(clash, build_gauge);
+        assert_eq!(registry.get("Button").unwrap().provider, "other");
+        assert!(registry.unregister("Button").is_some());
+    }
+}
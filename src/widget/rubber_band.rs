@@ -0,0 +1,188 @@
+//! Rubber-band (drag rectangle) selection.
+//!
+//! A node marked with [`Screen::set_rubber_band`](crate::screen::Screen::set_rubber_band)
+//! becomes a canvas-like area: pressing the left button on its background
+//! (not on a child) and dragging draws a selection rectangle, and releasing
+//! reports the children whose regions intersect it as a [`RegionSelected`]
+//! message sent from the container. Escape cancels a drag in progress.
+//!
+//! [`RubberBand`] holds the drag state; the app owns at most one and draws
+//! its outline over the composited frame with `App::draw_rubber_band`.
+
+use std::any::Any;
+
+use crate::dom::node::NodeId;
+use crate::event::message::Message;
+use crate::geometry::{Offset, Region};
+use crate::render::strip::{CellStyle, Strip};
+
+/// Emitted by a rubber-band container when a drag ends: the children whose
+/// regions intersect the rectangle, in document order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegionSelected(pub Vec<NodeId>);
+
+impl Message for RegionSelected {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn message_name(&self) -> &str {
+        "RegionSelected"
+    }
+}
+
+// ---------------------------------------------------------------------------
+// RubberBand
+// ---------------------------------------------------------------------------
+
+/// A selection rectangle being dragged inside a container.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RubberBand {
+    container: NodeId,
+    /// The container's visible region; the rectangle never leaves it.
+    bounds: Region,
+    origin: Offset,
+    current: Offset,
+}
+
+impl RubberBand {
+    /// Start a drag in `container`, visible at `bounds`, at `(x, y)`.
+    pub fn new(container: NodeId, bounds: Region, x: i32, y: i32) -> Self {
+        let origin = clamp_point(bounds, x, y);
+        Self {
+            container,
+            bounds,
+            origin,
+            current: origin,
+        }
+    }
+
+    /// The container the drag started in.
+    pub fn container(&self) -> NodeId {
+        self.container
+    }
+
+    /// Move the dragged corner to `(x, y)`. Returns whether the rectangle
+    /// changed.
+    pub fn drag_to(&mut self, x: i32, y: i32) -> bool {
+        let point = clamp_point(self.bounds, x, y);
+        let changed = point != self.current;
+        self.current = point;
+        changed
+    }
+
+    /// The rectangle spanned by the press point and the dragged corner,
+    /// both included.
+    pub fn region(&self) -> Region {
+        let (x, y) = (
+            self.origin.x.min(self.current.x),
+            self.origin.y.min(self.current.y),
+        );
+        let right = self.origin.x.max(self.current.x) + 1;
+        let bottom = self.origin.y.max(self.current.y) + 1;
+        Region::new(x, y, right - x, bottom - y)
+    }
+
+    /// Outline strips for the rectangle: box-drawing edges only, so the
+    /// selected widgets stay visible inside.
+    pub fn render(&self, style: CellStyle) -> Vec<Strip> {
+        let region = self.region();
+        let (right, bottom) = (region.right() - 1, region.bottom() - 1);
+        let mut strips = Vec::new();
+        for y in region.y..=bottom {
+            let (left, fill, end) = match (y == region.y, y == bottom) {
+                (true, true) => ('[', '─', ']'),
+                (true, false) => ('┌', '─', '┐'),
+                (false, true) => ('└', '─', '┘'),
+                (false, false) => ('│', ' ', '│'),
+            };
+            if fill == ' ' {
+                for (x, ch) in [(region.x, left), (right, end)] {
+                    let mut strip = Strip::new(y, x);
+                    strip.push(ch, style);
+                    strips.push(strip);
+                }
+                continue;
+            }
+            let mut strip = Strip::new(y, region.x);
+            for x in region.x..=right {
+                let ch = if x == region.x && x < right {
+                    left
+                } else if x == right && x > region.x {
+                    end
+                } else {
+                    fill
+                };
+                strip.push(ch, style);
+            }
+            strips.push(strip);
+        }
+        strips
+    }
+}
+
+/// `(x, y)` moved inside `bounds`.
+fn clamp_point(bounds: Region, x: i32, y: i32) -> Offset {
+    Offset::new(
+        x.clamp(bounds.x, (bounds.right() - 1).max(bounds.x)),
+        y.clamp(bounds.y, (bounds.bottom() - 1).max(bounds.y)),
+    )
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dom::node::NodeData;
+    use crate::dom::tree::Dom;
+
+    fn band(x: i32, y: i32) -> RubberBand {
+        let mut dom = Dom::new();
+        let canvas = dom.insert(NodeData::new("Canvas"));
+        RubberBand::new(canvas, Region::new(10, 5, 20, 10), x, y)
+    }
+
+    #[test]
+    fn region_spans_both_corners_in_any_direction() {
+        let mut band = band(20, 10);
+        assert_eq!(band.region(), Region::new(20, 10, 1, 1));
+        assert!(band.drag_to(15, 8));
+        assert_eq!(band.region(), Region::new(15, 8, 6, 3));
+        assert!(!band.drag_to(15, 8));
+        // Dragging outside the container stops at its edges.
+        band.drag_to(100, -3);
+        assert_eq!(band.region(), Region::new(20, 5, 10, 6));
+    }
+
+    #[test]
+    fn renders_outline_only() {
+        let mut band = band(10, 5);
+        band.drag_to(13, 7);
+        let strips = band.render(CellStyle::default());
+        let rows: Vec<(i32, i32, String)> = strips
+            .iter()
+            .map(|s| (s.y, s.x_offset, s.cells.iter().map(|c| c.ch).collect()))
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                (5, 10, "┌──┐".to_owned()),
+                (6, 10, "│".to_owned()),
+                (6, 13, "│".to_owned()),
+                (7, 10, "└──┘".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn single_row_band() {
+        let mut band = band(12, 6);
+        band.drag_to(14, 6);
+        let strips = band.render(CellStyle::default());
+        assert_eq!(strips.len(), 1);
+        let text: String = strips[0].cells.iter().map(|c| c.ch).collect();
+        assert_eq!(text, "[─]");
+    }
+}
@@ -2,9 +2,16 @@
 //!
 //! `ScrollState` tracks the current scroll position for a scrollable widget,
 //! handling clamping, content/viewport size, and scroll percentages.
-//! `ScrollbarState` provides the data needed to render a scrollbar indicator.
+//! `ScrollbarState` provides the data needed to render a scrollbar indicator,
+//! and [`scrollbar_strips`] draws a vertical scrollbar from it in the colors
+//! and width set by `scrollbar-color` and `scrollbar-size`.
 
+use crate::css::styles::Styles;
 use crate::geometry::{Offset, Size, Region};
+use crate::render::strip::{CellStyle, Strip};
+
+/// Width of a vertical scrollbar, in cells, when `scrollbar-size` is not set.
+pub const DEFAULT_SCROLLBAR_SIZE: u16 = 1;
 
 // ---------------------------------------------------------------------------
 // ScrollState
@@ -55,6 +62,27 @@ impl ScrollState {
         self.scroll_to(self.offset.x + dx, self.offset.y + dy);
     }
 
+    /// Scroll by a relative delta, clamping to valid range, and return the
+    /// part of the delta that could not be applied because an edge was hit.
+    pub fn scroll_by_remainder(&mut self, dx: i32, dy: i32) -> Offset {
+        let before = self.offset;
+        self.scroll_by(dx, dy);
+        Offset::new(
+            dx - (self.offset.x - before.x),
+            dy - (self.offset.y - before.y),
+        )
+    }
+
+    /// Whether content is hidden above the viewport.
+    pub fn has_more_above(&self) -> bool {
+        self.offset.y > 0
+    }
+
+    /// Whether content is hidden below the viewport.
+    pub fn has_more_below(&self) -> bool {
+        self.offset.y < self.max_scroll().y
+    }
+
     /// Whether the content is wider than the viewport (horizontal scrolling possible).
     pub fn is_scrollable_x(&self) -> bool {
         self.content_size.width > self.viewport_size.width
@@ -99,6 +127,20 @@ impl ScrollState {
         }
     }
 
+    /// Scroll vertically so a scrollbar thumb on a `track` cells long
+    /// track starts at `thumb_start`, as when dragging the thumb.
+    pub fn scroll_to_thumb(&mut self, thumb_start: i32, track: i32) {
+        let (_, thumb) = ScrollbarState::from_scroll_state(self, true).thumb_span(track);
+        let room = track - thumb;
+        let y = if room > 0 {
+            let fraction = thumb_start.clamp(0, room) as f32 / room as f32;
+            (fraction * self.max_scroll().y as f32).round() as i32
+        } else {
+            0
+        };
+        self.scroll_to(self.offset.x, y);
+    }
+
     /// Update the content size and re-clamp the offset.
     pub fn set_content_size(&mut self, size: Size) {
         self.content_size = size;
@@ -174,6 +216,52 @@ impl ScrollbarState {
             thumb_size,
         }
     }
+
+    /// The thumb on a track `track` cells long, as `(start, length)` in
+    /// cells. The thumb is always at least one cell long.
+    pub fn thumb_span(&self, track: i32) -> (i32, i32) {
+        if track <= 0 {
+            return (0, 0);
+        }
+        let length = ((self.thumb_size * track as f32).round() as i32).clamp(1, track);
+        let start = (self.thumb_position * (track - length) as f32).round() as i32;
+        (start, length)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Rendering
+// ---------------------------------------------------------------------------
+
+/// The style scrollbars are drawn in: `styles`' own, with `scrollbar-color`
+/// as the foreground if set.
+pub fn scrollbar_style(styles: &Styles) -> CellStyle {
+    let style = CellStyle::from_styles(styles);
+    match styles.scrollbar_color.as_deref() {
+        Some(color) => style.with_fg(color),
+        None => style,
+    }
+}
+
+/// A vertical scrollbar filling `region`: a `█` thumb, sized and placed from
+/// `scroll`, over a `│` track in the region's first column.
+pub fn scrollbar_strips(scroll: &ScrollState, region: Region, style: CellStyle) -> Vec<Strip> {
+    let bar = ScrollbarState::from_scroll_state(scroll, true);
+    let (start, thumb) = bar.thumb_span(region.height);
+    (0..region.height)
+        .map(|row| {
+            let mut strip = Strip::new(region.y + row, region.x);
+            if (start..start + thumb).contains(&row) {
+                for _ in 0..region.width {
+                    strip.push('█', style);
+                }
+            } else {
+                strip.push('│', style);
+                strip.fill(region.width, style);
+            }
+            strip
+        })
+        .collect()
 }
 
 // ===========================================================================
@@ -196,6 +284,17 @@ mod tests {
         assert_eq!(state.viewport_size, Size::new(40, 30));
     }
 
+    #[test]
+    fn scroll_by_remainder_reports_overscroll() {
+        let mut state = ScrollState::new(Size::new(10, 20), Size::new(10, 5));
+        assert!(!state.has_more_above());
+        assert!(state.has_more_below());
+        assert_eq!(state.scroll_by_remainder(0, 12), Offset::new(0, 0));
+        assert_eq!(state.scroll_by_remainder(4, 5), Offset::new(4, 2));
+        assert!(state.has_more_above());
+        assert!(!state.has_more_below());
+    }
+
     #[test]
     fn default_scroll_state() {
         let state = ScrollState::default();
@@ -414,4 +513,42 @@ mod tests {
         assert_eq!(bar.thumb_position, 0.0);
         assert_eq!(bar.thumb_size, 1.0);
     }
+
+    #[test]
+    fn thumb_span_and_dragging_the_thumb() {
+        // 10 rows of 40: a 3-row thumb on a 10-row track.
+        let mut state = ScrollState::new(Size::new(10, 40), Size::new(10, 10));
+        let bar = ScrollbarState::from_scroll_state(&state, true);
+        assert_eq!(bar.thumb_span(10), (0, 3));
+        state.scroll_to_thumb(7, 10);
+        assert_eq!(state.offset.y, 30);
+        state.scroll_to_thumb(3, 10);
+        assert_eq!(state.offset.y, 13);
+        assert_eq!(ScrollbarState::from_scroll_state(&state, true).thumb_span(10), (3, 3));
+        state.scroll_to_thumb(-5, 10);
+        assert_eq!(state.offset.y, 0);
+    }
+
+    // -----------------------------------------------------------------------
+    // Rendering
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn scrollbar_strips_use_size_and_color() {
+        let mut state = ScrollState::new(Size::new(10, 8), Size::new(10, 4));
+        state.scroll_to(0, 4);
+        let mut styles = Styles::new();
+        styles.scrollbar_color = Some("red".into());
+        let style = scrollbar_style(&styles);
+        assert_eq!(style, CellStyle::new().with_fg("red"));
+
+        let strips = scrollbar_strips(&state, Region::new(8, 1, 2, 4), style);
+        let rows: Vec<String> = strips
+            .iter()
+            .map(|s| s.cells.iter().map(|c| c.ch).collect())
+            .collect();
+        assert_eq!(rows, ["│ ", "│ ", "██", "██"]);
+        assert_eq!((strips[0].x_offset, strips[0].y), (8, 1));
+        assert_eq!(strips[3].cells[1].style, style);
+    }
 }
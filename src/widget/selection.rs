@@ -0,0 +1,367 @@
+//! Selection model shared by list-like widgets.
+//!
+//! A [`SelectionModel`] tracks a cursor and a set of selected indices over
+//! `len` items, so every list widget selects the same way:
+//!
+//! - Up/Down, Home/End, and PageUp/PageDown move the cursor and select the
+//!   item under it.
+//! - With Shift held they select the range from the anchor (the last item
+//!   selected without Shift) to the cursor.
+//! - With Ctrl held they move the cursor alone; Space then toggles the item
+//!   under it, and Ctrl+A selects everything.
+//! - Clicks select, Ctrl+click toggles, and Shift+click selects a range.
+//!
+//! In [`SelectionMode::Single`] the modifiers are ignored and at most one
+//! item is selected. Every change is reported as a [`Selected`] message.
+//!
+//! ```ignore
+//! let mut selection = SelectionModel::new(SelectionMode::Multi);
+//! selection.set_len(rows.len());
+//! if let Some(selected) = selection.handle_key(&key) {
+//!     ctx.post(selected);
+//! }
+//! ```
+
+use std::any::Any;
+use std::collections::BTreeSet;
+
+use crate::event::input::{Key, KeyEvent, Modifiers};
+use crate::event::message::Message;
+use crate::render::strip::Attrs;
+
+/// Items PageUp/PageDown move by until a page size is set.
+pub const DEFAULT_PAGE: usize = 10;
+
+/// Emitted when the cursor or the selection changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selected {
+    /// The item under the cursor.
+    pub cursor: usize,
+    /// Every selected item, in ascending order.
+    pub selected: Vec<usize>,
+}
+
+impl Message for Selected {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn message_name(&self) -> &str {
+        "Selected"
+    }
+}
+
+/// How many items can be selected at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SelectionMode {
+    /// One item at a time, always the one under the cursor.
+    #[default]
+    Single,
+    /// Any set of items, built with Ctrl and Shift.
+    Multi,
+}
+
+// ---------------------------------------------------------------------------
+// SelectionModel
+// ---------------------------------------------------------------------------
+
+/// A cursor and a set of selected items over a list of `len` items.
+#[derive(Debug, Clone, Default)]
+pub struct SelectionModel {
+    mode: SelectionMode,
+    len: usize,
+    cursor: usize,
+    /// Where Shift ranges start.
+    anchor: usize,
+    selected: BTreeSet<usize>,
+    page: usize,
+}
+
+impl SelectionModel {
+    /// Create an empty model over no items.
+    pub fn new(mode: SelectionMode) -> Self {
+        Self {
+            mode,
+            page: DEFAULT_PAGE,
+            ..Self::default()
+        }
+    }
+
+    /// The selection mode.
+    pub fn mode(&self) -> SelectionMode {
+        self.mode
+    }
+
+    /// Change the selection mode. Switching to single keeps only the item
+    /// under the cursor selected, if it was.
+    pub fn set_mode(&mut self, mode: SelectionMode) {
+        self.mode = mode;
+        if mode == SelectionMode::Single {
+            let keep = self.selected.contains(&self.cursor);
+            self.selected.clear();
+            if keep {
+                self.selected.insert(self.cursor);
+            }
+        }
+    }
+
+    /// Set how many items PageUp/PageDown move by, e.g. the viewport height.
+    pub fn set_page(&mut self, page: usize) {
+        self.page = page.max(1);
+    }
+
+    /// Number of items.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether there are no items.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Set the number of items, dropping selected items past the end and
+    /// clamping the cursor.
+    pub fn set_len(&mut self, len: usize) {
+        self.len = len;
+        let last = len.saturating_sub(1);
+        self.cursor = self.cursor.min(last);
+        self.anchor = self.anchor.min(last);
+        self.selected.retain(|&i| i < len);
+    }
+
+    /// The item under the cursor.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Every selected item, in ascending order.
+    pub fn selected(&self) -> Vec<usize> {
+        self.selected.iter().copied().collect()
+    }
+
+    /// Whether `index` is selected.
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selected.contains(&index)
+    }
+
+    /// Select only `index` and move the cursor and anchor there.
+    pub fn select(&mut self, index: usize) -> Option<Selected> {
+        self.change(|model| {
+            let index = model.clamp(index)?;
+            model.cursor = index;
+            model.anchor = index;
+            model.selected.clear();
+            model.selected.insert(index);
+            Some(())
+        })
+    }
+
+    /// Flip whether `index` is selected, moving the cursor and anchor there.
+    /// In single mode this is [`select`](Self::select).
+    pub fn toggle(&mut self, index: usize) -> Option<Selected> {
+        if self.mode == SelectionMode::Single {
+            return self.select(index);
+        }
+        self.change(|model| {
+            let index = model.clamp(index)?;
+            model.cursor = index;
+            model.anchor = index;
+            if !model.selected.remove(&index) {
+                model.selected.insert(index);
+            }
+            Some(())
+        })
+    }
+
+    /// Select the items from the anchor to `index`, moving the cursor there.
+    /// In single mode this is [`select`](Self::select).
+    pub fn extend_to(&mut self, index: usize) -> Option<Selected> {
+        if self.mode == SelectionMode::Single {
+            return self.select(index);
+        }
+        self.change(|model| {
+            let index = model.clamp(index)?;
+            model.cursor = index;
+            let (from, to) = (model.anchor.min(index), model.anchor.max(index));
+            model.selected = (from..=to).collect();
+            Some(())
+        })
+    }
+
+    /// Select every item. Only in multi mode.
+    pub fn select_all(&mut self) -> Option<Selected> {
+        if self.mode == SelectionMode::Single {
+            return None;
+        }
+        self.change(|model| {
+            model.selected = (0..model.len).collect();
+            Some(())
+        })
+    }
+
+    /// Deselect everything, leaving the cursor where it is.
+    pub fn clear(&mut self) -> Option<Selected> {
+        self.change(|model| {
+            model.selected.clear();
+            Some(())
+        })
+    }
+
+    /// Handle a click on item `index`: select it, or toggle it with Ctrl,
+    /// or select a range to it with Shift.
+    pub fn click(&mut self, index: usize, modifiers: Modifiers) -> Option<Selected> {
+        if modifiers.contains(Modifiers::SHIFT) {
+            self.extend_to(index)
+        } else if modifiers.contains(Modifiers::CTRL) {
+            self.toggle(index)
+        } else {
+            self.select(index)
+        }
+    }
+
+    /// Handle the selection keys (see the module docs). Returns `None` for
+    /// other keys and for keys that changed nothing.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> Option<Selected> {
+        let ctrl = key.modifiers.contains(Modifiers::CTRL);
+        let target = match key.code {
+            Key::Up => self.cursor.saturating_sub(1),
+            Key::Down => self.cursor + 1,
+            Key::Home => 0,
+            Key::End => self.len.saturating_sub(1),
+            Key::PageUp => self.cursor.saturating_sub(self.page),
+            Key::PageDown => self.cursor + self.page,
+            Key::Char(' ') => return self.toggle(self.cursor),
+            Key::Char('a') if ctrl => return self.select_all(),
+            _ => return None,
+        };
+        if self.mode == SelectionMode::Single {
+            self.select(target)
+        } else if key.modifiers.contains(Modifiers::SHIFT) {
+            self.extend_to(target)
+        } else if ctrl {
+            self.change(|model| {
+                model.cursor = model.clamp(target)?;
+                Some(())
+            })
+        } else {
+            self.select(target)
+        }
+    }
+
+    /// Attributes to draw item `index` with: reversed when selected, and
+    /// underlined under the cursor in multi mode.
+    pub fn attrs(&self, index: usize) -> Attrs {
+        if self.is_selected(index) {
+            Attrs::REVERSE
+        } else if self.mode == SelectionMode::Multi && index == self.cursor && self.len > 0 {
+            Attrs::UNDERLINE
+        } else {
+            Attrs::NONE
+        }
+    }
+
+    /// `index` clamped to the items, or `None` if there are none.
+    fn clamp(&self, index: usize) -> Option<usize> {
+        (self.len > 0).then(|| index.min(self.len - 1))
+    }
+
+    /// Apply `edit` and report the new state if the cursor or the selection
+    /// changed.
+    fn change(&mut self, edit: impl FnOnce(&mut Self) -> Option<()>) -> Option<Selected> {
+        let before = (self.cursor, self.selected.clone());
+        edit(self)?;
+        if before == (self.cursor, self.selected.clone()) {
+            return None;
+        }
+        Some(Selected {
+            cursor: self.cursor,
+            selected: self.selected(),
+        })
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: Key, modifiers: Modifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    fn model(mode: SelectionMode, len: usize) -> SelectionModel {
+        let mut model = SelectionModel::new(mode);
+        model.set_len(len);
+        model
+    }
+
+    #[test]
+    fn single_mode_follows_the_cursor() {
+        let mut list = model(SelectionMode::Single, 5);
+        let moved = list.handle_key(&key(Key::Down, Modifiers::NONE));
+        assert_eq!(
+            moved,
+            Some(Selected {
+                cursor: 1,
+                selected: vec![1]
+            })
+        );
+        // Modifiers are ignored and nothing else gets selected.
+        list.handle_key(&key(Key::End, Modifiers::SHIFT));
+        list.click(0, Modifiers::CTRL);
+        assert_eq!(list.selected(), [0]);
+        assert_eq!(list.select_all(), None);
+        assert_eq!(list.handle_key(&key(Key::Up, Modifiers::NONE)), None);
+        assert_eq!(list.handle_key(&key(Key::Left, Modifiers::NONE)), None);
+    }
+
+    #[test]
+    fn multi_mode_ctrl_and_shift() {
+        let mut list = model(SelectionMode::Multi, 10);
+        list.select(2);
+        // Shift extends from the anchor, in either direction.
+        list.handle_key(&key(Key::Down, Modifiers::SHIFT));
+        list.handle_key(&key(Key::Down, Modifiers::SHIFT));
+        assert_eq!(list.selected(), [2, 3, 4]);
+        list.click(0, Modifiers::SHIFT);
+        assert_eq!(list.selected(), [0, 1, 2]);
+
+        // Ctrl moves the cursor alone; Space toggles under it.
+        list.handle_key(&key(Key::PageDown, Modifiers::CTRL));
+        assert_eq!(list.cursor(), 9);
+        assert_eq!(list.attrs(9), Attrs::UNDERLINE);
+        list.handle_key(&key(Key::Char(' '), Modifiers::NONE));
+        list.click(1, Modifiers::CTRL);
+        assert_eq!(list.selected(), [0, 2, 9]);
+        assert_eq!(list.attrs(2), Attrs::REVERSE);
+
+        assert_eq!(
+            list.handle_key(&key(Key::Char('a'), Modifiers::CTRL))
+                .unwrap()
+                .selected
+                .len(),
+            10
+        );
+        list.handle_key(&key(Key::End, Modifiers::CTRL));
+        list.set_len(3);
+        assert_eq!(list.selected(), [0, 1, 2]);
+        assert_eq!(list.cursor(), 2);
+    }
+
+    #[test]
+    fn empty_and_mode_switch() {
+        let mut list = model(SelectionMode::Multi, 0);
+        assert_eq!(list.handle_key(&key(Key::Down, Modifiers::NONE)), None);
+        assert_eq!(list.select(3), None);
+
+        list.set_len(4);
+        list.select_all();
+        list.set_mode(SelectionMode::Single);
+        assert_eq!(list.selected(), [0]);
+        assert!(list.clear().is_some());
+        assert!(list.clear().is_none());
+    }
+}
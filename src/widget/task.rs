@@ -0,0 +1,326 @@
+//! Per-widget background tasks, cancelled when the widget unmounts.
+//!
+//! A task runs on its own thread and talks back to the app only through its
+//! [`TaskContext`], which posts messages on behalf of the owning node. The
+//! screen's [`TaskSet`] remembers which node spawned each task and cancels
+//! them when that node leaves the DOM; cancelled tasks can no longer post, and
+//! anything already in flight for a removed node is dropped by the app when it
+//! pumps the inbox.
+//!
+//! Cancellation is cooperative: long-running tasks should check
+//! [`TaskContext::is_cancelled`] between steps and return early. Tasks run
+//! on a [`BackgroundThread`] like app workers, so a task that panics posts
+//! [`WorkerFailed`](crate::event::worker::WorkerFailed) from its node instead
+//! of dying silently.
+//!
+//! ```ignore
+//! app.widget_ctx(node).spawn(|ctx| {
+//!     for line in tail_log() {
+//!         if !ctx.post(LogLine(line)) {
+//!             break; // widget unmounted
+//!         }
+//!     }
+//! });
+//! ```
+
+use std::collections::HashMap;
+
+use crate::dom::node::NodeId;
+use crate::event::inbox::InboxSender;
+use crate::event::message::Message;
+use crate::event::worker::BackgroundThread;
+
+// ---------------------------------------------------------------------------
+// TaskHandle
+// ---------------------------------------------------------------------------
+
+/// A handle to a spawned task. Cloning shares the same task.
+#[derive(Debug, Clone)]
+pub struct TaskHandle {
+    node: NodeId,
+    thread: BackgroundThread,
+}
+
+impl TaskHandle {
+    /// The node that owns the task.
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// Ask the task to stop and prevent it from posting further messages.
+    pub fn cancel(&self) {
+        self.thread.cancel();
+    }
+
+    /// Whether the task was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.thread.is_cancelled()
+    }
+
+    /// Whether the task's closure has returned (or panicked).
+    pub fn is_finished(&self) -> bool {
+        self.thread.is_finished()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// TaskContext
+// ---------------------------------------------------------------------------
+
+/// What a running task can see of the app.
+#[derive(Debug)]
+pub struct TaskContext {
+    node: NodeId,
+    thread: BackgroundThread,
+    sender: InboxSender,
+}
+
+impl TaskContext {
+    /// The node that owns the task.
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// Whether the task has been cancelled (usually because its widget
+    /// unmounted).
+    pub fn is_cancelled(&self) -> bool {
+        self.thread.is_cancelled()
+    }
+
+    /// Post a message from the owning node. Returns `false` once the task is
+    /// cancelled or the app has gone away.
+    pub fn post(&self, message: impl Message) -> bool {
+        !self.is_cancelled() && self.sender.send_from(self.node, message)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// TaskSet
+// ---------------------------------------------------------------------------
+
+/// The background tasks owned by nodes on a screen.
+#[derive(Debug, Default)]
+pub struct TaskSet {
+    tasks: HashMap<NodeId, Vec<TaskHandle>>,
+}
+
+impl TaskSet {
+    /// Create an empty task set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `task` on a new thread on behalf of `node`. If it panics,
+    /// [`WorkerFailed`](crate::event::worker::WorkerFailed) is posted from
+    /// `node` unless the task was cancelled.
+    pub fn spawn(
+        &mut self,
+        node: NodeId,
+        sender: InboxSender,
+        task: impl FnOnce(TaskContext) + Send + 'static,
+    ) -> TaskHandle {
+        let thread = BackgroundThread::new();
+        let ctx = TaskContext {
+            node,
+            thread: thread.clone(),
+            sender: sender.clone(),
+        };
+        thread.spawn(
+            move || task(ctx),
+            move |result| {
+                if let Err(failed) = result {
+                    sender.send_from(node, failed);
+                }
+            },
+        );
+        let handle = TaskHandle { node, thread };
+        self.tasks.entry(node).or_default().push(handle.clone());
+        handle
+    }
+
+    /// Cancel and forget every task owned by `node`. Returns how many were
+    /// still running.
+    pub fn cancel_node(&mut self, node: NodeId) -> usize {
+        let Some(handles) = self.tasks.remove(&node) else {
+            return 0;
+        };
+        handles
+            .iter()
+            .filter(|handle| !handle.is_finished())
+            .inspect(|handle| handle.cancel())
+            .count()
+    }
+
+    /// Cancel and forget every task.
+    pub fn cancel_all(&mut self) {
+        for handle in self.tasks.drain().flat_map(|(_, handles)| handles) {
+            handle.cancel();
+        }
+    }
+
+    /// Forget tasks that have finished.
+    pub fn reap(&mut self) {
+        self.tasks.retain(|_, handles| {
+            handles.retain(|handle| !handle.is_finished());
+            !handles.is_empty()
+        });
+    }
+
+    /// The tracked tasks owned by `node`.
+    pub fn tasks_for(&self, node: NodeId) -> &[TaskHandle] {
+        self.tasks.get(&node).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// The number of tracked tasks.
+    pub fn len(&self) -> usize {
+        self.tasks.values().map(Vec::len).sum()
+    }
+
+    /// Whether no tasks are tracked.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// WidgetContext
+// ---------------------------------------------------------------------------
+
+/// Services available to code acting on behalf of one widget.
+///
+/// Obtained from [`App::widget_ctx`](crate::app::App::widget_ctx).
+pub struct WidgetContext<'a> {
+    node: NodeId,
+    tasks: &'a mut TaskSet,
+    sender: InboxSender,
+}
+
+impl<'a> WidgetContext<'a> {
+    pub(crate) fn new(node: NodeId, tasks: &'a mut TaskSet, sender: InboxSender) -> Self {
+        Self {
+            node,
+            tasks,
+            sender,
+        }
+    }
+
+    /// The widget's node.
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// Run `task` in the background; it is cancelled when the widget unmounts.
+    pub fn spawn(&mut self, task: impl FnOnce(TaskContext) + Send + 'static) -> TaskHandle {
+        self.tasks.spawn(self.node, self.sender.clone(), task)
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::inbox::Inbox;
+    use crate::event::message::Refresh;
+    use crate::event::worker::WorkerFailed;
+    use slotmap::SlotMap;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    fn node() -> NodeId {
+        SlotMap::<NodeId, ()>::with_key().insert(())
+    }
+
+    fn wait_until(done: impl Fn() -> bool) {
+        let start = Instant::now();
+        while !done() {
+            assert!(start.elapsed() < Duration::from_secs(5), "timed out");
+            thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn task_posts_on_behalf_of_node() {
+        let inbox = Inbox::new();
+        let mut tasks = TaskSet::new();
+        let id = node();
+        let handle = tasks.spawn(id, inbox.sender(), |ctx| {
+            assert!(ctx.post(Refresh));
+        });
+        wait_until(|| handle.is_finished());
+        let posted = inbox.drain_posted();
+        assert_eq!(posted.len(), 1);
+        assert_eq!(posted[0].0, Some(id));
+
+        tasks.reap();
+        assert!(tasks.is_empty());
+    }
+
+    #[test]
+    fn cancel_node_stops_posting() {
+        let inbox = Inbox::new();
+        let mut tasks = TaskSet::new();
+        let id = node();
+        let (go_tx, go_rx) = mpsc::channel::<()>();
+        let handle = tasks.spawn(id, inbox.sender(), move |ctx| {
+            go_rx.recv().unwrap();
+            assert!(ctx.is_cancelled());
+            assert!(!ctx.post(Refresh));
+        });
+        assert_eq!(tasks.tasks_for(id).len(), 1);
+
+        assert_eq!(tasks.cancel_node(id), 1);
+        assert!(handle.is_cancelled());
+        assert!(tasks.tasks_for(id).is_empty());
+        go_tx.send(()).unwrap();
+        wait_until(|| handle.is_finished());
+        assert!(inbox.drain().is_empty());
+    }
+
+    #[test]
+    fn finished_flag_survives_panics() {
+        let inbox = Inbox::new();
+        let mut tasks = TaskSet::new();
+        let handle = tasks.spawn(node(), inbox.sender(), |_| panic!("task failed"));
+        wait_until(|| handle.is_finished());
+    }
+
+    #[test]
+    fn panics_post_worker_failed_from_the_node() {
+        let inbox = Inbox::new();
+        let mut tasks = TaskSet::new();
+        let id = node();
+        tasks.spawn(id, inbox.sender(), |_| panic!("disk full"));
+        assert!(inbox.wait(Some(Duration::from_secs(5))), "timed out");
+        let posted = inbox.drain_posted();
+        assert_eq!(posted[0].0, Some(id));
+        let failed = posted[0].1.as_any().downcast_ref::<WorkerFailed>().unwrap();
+        assert_eq!(failed.reason, "disk full");
+
+        // A cancelled task's panic is dropped like its messages.
+        let (go_tx, go_rx) = mpsc::channel::<()>();
+        let handle = tasks.spawn(id, inbox.sender(), move |_| {
+            go_rx.recv().unwrap();
+            panic!("after unmount");
+        });
+        tasks.cancel_node(id);
+        go_tx.send(()).unwrap();
+        wait_until(|| handle.is_finished());
+        assert!(inbox.drain().is_empty());
+    }
+
+    #[test]
+    fn widget_context_spawns_for_its_node() {
+        let inbox = Inbox::new();
+        let mut tasks = TaskSet::new();
+        let id = node();
+        let handle = WidgetContext::new(id, &mut tasks, inbox.sender()).spawn(|_| {});
+        assert_eq!(handle.node(), id);
+        assert_eq!(tasks.len(), 1);
+        tasks.cancel_all();
+        assert!(tasks.is_empty());
+    }
+}
@@ -3,12 +3,17 @@
 //! The `Widget` trait is the core abstraction for all UI elements in gilt-tui.
 //! Every widget knows its type name, default CSS, and how to render itself into
 //! strips within a given region. The `WidgetExt` trait adds builder-style
-//! convenience methods for attaching CSS ids and classes.
+//! convenience methods for attaching CSS ids and classes, a tooltip, a border
+//! title, an accessible label, and signals that drive the loading and disabled
+//! states.
 
 use std::any::Any;
+use std::ops::Range;
 
 use crate::css::styles::Styles;
+use crate::event::binding::WidgetBinding;
 use crate::geometry::Region;
+use crate::reactive::ReadSignal;
 use crate::render::strip::Strip;
 
 // ---------------------------------------------------------------------------
@@ -39,6 +44,19 @@ pub trait Widget {
     /// are the fully-resolved CSS styles for this widget (after cascade).
     fn render(&self, region: Region, styles: &Styles) -> Vec<Strip>;
 
+    /// Render only the columns `visible` (absolute x range) of `region`.
+    ///
+    /// Scrolling containers call this with a region wider than the screen.
+    /// The default renders everything and crops; widgets with very wide
+    /// content (long log lines, canvases) override it to produce only the
+    /// visible cells.
+    fn render_window(&self, region: Region, styles: &Styles, visible: Range<i32>) -> Vec<Strip> {
+        self.render(region, styles)
+            .into_iter()
+            .map(|strip| strip.crop(visible.start, visible.end))
+            .collect()
+    }
+
     /// Whether this widget can receive keyboard/mouse focus.
     ///
     /// Defaults to `false`. Override for interactive widgets like buttons and inputs.
@@ -54,6 +72,77 @@ pub trait Widget {
         Vec::new()
     }
 
+    /// The CSS id attached to this widget, if any.
+    ///
+    /// Used when building DOM nodes from widgets.
+    fn css_id(&self) -> Option<&str> {
+        None
+    }
+
+    /// The CSS classes attached to this widget.
+    ///
+    /// Used when building DOM nodes from widgets.
+    fn css_classes(&self) -> &[String] {
+        &[]
+    }
+
+    /// Tooltip text shown when hovering over this widget, if any.
+    fn tooltip_text(&self) -> Option<&str> {
+        None
+    }
+
+    /// Title drawn in this widget's top border, if any.
+    fn border_title_text(&self) -> Option<&str> {
+        None
+    }
+
+    /// Label announced for this widget (e.g. "Save" for a Save button), if
+    /// any. Set with [`WidgetExt::with_aria_label`] when it should differ
+    /// from the visible text.
+    fn aria_label(&self) -> Option<&str> {
+        None
+    }
+
+    /// The text this widget displays, if it shows a single string. The
+    /// reconciler compares it to tell a re-rendered widget with new text
+    /// from an unchanged one.
+    fn content_text(&self) -> Option<&str> {
+        None
+    }
+
+    /// Whether this widget is disabled. Disabled nodes are skipped by focus.
+    fn is_disabled(&self) -> bool {
+        false
+    }
+
+    /// Whether this widget is loading. Loading nodes get the `loading` class.
+    fn is_loading(&self) -> bool {
+        false
+    }
+
+    /// Key bindings active while this widget or a descendant has focus, like
+    /// Textual's `BINDINGS`. Each action is sent as a custom action from the
+    /// widget's node; bindings closer to focus win conflicts.
+    fn bindings(&self) -> &'static [WidgetBinding] {
+        &[]
+    }
+
+    /// Borrow child widgets held by this widget (e.g. a `Container`'s children).
+    ///
+    /// Unlike [`Widget::children`], this does not construct new widgets; it is
+    /// what the framework walks when reconciling a widget tree against the DOM.
+    fn child_widgets(&self) -> &[Box<dyn Widget>] {
+        &[]
+    }
+
+    /// Reconciliation keys for [`Widget::child_widgets`], one per child.
+    ///
+    /// When present, children are matched to existing DOM nodes by key rather
+    /// than by position (see `Container::set_children_keyed`).
+    fn child_keys(&self) -> Option<&[String]> {
+        None
+    }
+
     /// Downcast to `&dyn Any` for runtime type inspection.
     fn as_any(&self) -> &dyn Any;
 
@@ -74,11 +163,7 @@ pub trait WidgetExt: Widget {
     where
         Self: Sized,
     {
-        WidgetBuilder {
-            widget: self,
-            id: Some(id.to_owned()),
-            classes: Vec::new(),
-        }
+        WidgetBuilder::new(self).set_id(id)
     }
 
     /// Wrap this widget with a single CSS class.
@@ -86,11 +171,7 @@ pub trait WidgetExt: Widget {
     where
         Self: Sized,
     {
-        WidgetBuilder {
-            widget: self,
-            id: None,
-            classes: vec![class.to_owned()],
-        }
+        WidgetBuilder::new(self).add_class(class)
     }
 
     /// Wrap this widget with multiple CSS classes.
@@ -98,11 +179,47 @@ pub trait WidgetExt: Widget {
     where
         Self: Sized,
     {
-        WidgetBuilder {
-            widget: self,
-            id: None,
-            classes: classes.iter().map(|c| (*c).to_owned()).collect(),
-        }
+        WidgetBuilder::new(self).add_classes(classes)
+    }
+
+    /// Wrap this widget with a tooltip.
+    fn tooltip(self, text: &str) -> WidgetBuilder<Self>
+    where
+        Self: Sized,
+    {
+        WidgetBuilder::new(self).tooltip(text)
+    }
+
+    /// Wrap this widget with a title for its top border.
+    fn border_title(self, title: &str) -> WidgetBuilder<Self>
+    where
+        Self: Sized,
+    {
+        WidgetBuilder::new(self).border_title(title)
+    }
+
+    /// Wrap this widget with the label announced when it gains focus.
+    fn with_aria_label(self, label: &str) -> WidgetBuilder<Self>
+    where
+        Self: Sized,
+    {
+        WidgetBuilder::new(self).with_aria_label(label)
+    }
+
+    /// Wrap this widget so `signal` drives its loading state.
+    fn loading(self, signal: ReadSignal<bool>) -> WidgetBuilder<Self>
+    where
+        Self: Sized,
+    {
+        WidgetBuilder::new(self).loading(signal)
+    }
+
+    /// Wrap this widget so `signal` drives its disabled state.
+    fn disabled(self, signal: ReadSignal<bool>) -> WidgetBuilder<Self>
+    where
+        Self: Sized,
+    {
+        WidgetBuilder::new(self).disabled(signal)
     }
 }
 
@@ -113,10 +230,25 @@ impl<T: Widget> WidgetExt for T {}
 // WidgetBuilder
 // ---------------------------------------------------------------------------
 
-/// A wrapper around a widget that adds id and class metadata.
+/// A wrapper around a widget that adds id, class, and state metadata.
+///
+/// Created by any `WidgetExt` method, e.g. `with_id`, `tooltip`, or
+/// `loading`. Delegates all other `Widget` methods to the inner widget.
+///
+/// The `loading` and `disabled` signals are read when set, so a builder made
+/// inside a component's `render` re-renders the component when they change,
+/// and the new state reaches the DOM through reconciliation.
 ///
-/// Created by `WidgetExt::with_id`, `with_class`, or `with_classes`.
-/// Delegates all `Widget` methods to the inner widget.
+/// # Examples
+///
+/// ```ignore
+/// let (saving, set_saving) = create_signal(false);
+/// Button::new("Save")
+///     .with_id("save")
+///     .tooltip("Write changes to disk")
+///     .loading(saving)
+///     .disabled(saving)
+/// ```
 #[derive(Debug)]
 pub struct WidgetBuilder<W: Widget> {
     /// The wrapped widget.
@@ -125,9 +257,33 @@ pub struct WidgetBuilder<W: Widget> {
     pub id: Option<String>,
     /// CSS classes.
     pub classes: Vec<String>,
+    /// Optional tooltip text.
+    pub tooltip: Option<String>,
+    /// Optional border title.
+    pub border_title: Option<String>,
+    /// Optional accessible label, overriding the widget's own.
+    pub aria_label: Option<String>,
+    /// Signal driving the loading state.
+    pub loading: Option<ReadSignal<bool>>,
+    /// Signal driving the disabled state.
+    pub disabled: Option<ReadSignal<bool>>,
 }
 
 impl<W: Widget> WidgetBuilder<W> {
+    /// Wrap `widget` without any metadata.
+    pub fn new(widget: W) -> Self {
+        Self {
+            widget,
+            id: None,
+            classes: Vec::new(),
+            tooltip: None,
+            border_title: None,
+            aria_label: None,
+            loading: None,
+            disabled: None,
+        }
+    }
+
     /// Set the CSS id (chainable).
     pub fn set_id(mut self, id: &str) -> Self {
         self.id = Some(id.to_owned());
@@ -153,6 +309,39 @@ impl<W: Widget> WidgetBuilder<W> {
         }
         self
     }
+
+    /// Set the tooltip text (chainable).
+    pub fn tooltip(mut self, text: &str) -> Self {
+        self.tooltip = Some(text.to_owned());
+        self
+    }
+
+    /// Set the border title (chainable).
+    pub fn border_title(mut self, title: &str) -> Self {
+        self.border_title = Some(title.to_owned());
+        self
+    }
+
+    /// Set the accessible label (chainable).
+    pub fn with_aria_label(mut self, label: &str) -> Self {
+        self.aria_label = Some(label.to_owned());
+        self
+    }
+
+    /// Drive the loading state from `signal` (chainable).
+    pub fn loading(mut self, signal: ReadSignal<bool>) -> Self {
+        // Subscribe the running effect, if any, to the signal.
+        signal.get();
+        self.loading = Some(signal);
+        self
+    }
+
+    /// Drive the disabled state from `signal` (chainable).
+    pub fn disabled(mut self, signal: ReadSignal<bool>) -> Self {
+        signal.get();
+        self.disabled = Some(signal);
+        self
+    }
 }
 
 impl<W: Widget + 'static> Widget for WidgetBuilder<W> {
@@ -168,6 +357,10 @@ impl<W: Widget + 'static> Widget for WidgetBuilder<W> {
         self.widget.render(region, styles)
     }
 
+    fn render_window(&self, region: Region, styles: &Styles, visible: Range<i32>) -> Vec<Strip> {
+        self.widget.render_window(region, styles, visible)
+    }
+
     fn can_focus(&self) -> bool {
         self.widget.can_focus()
     }
@@ -176,6 +369,52 @@ impl<W: Widget + 'static> Widget for WidgetBuilder<W> {
         self.widget.children()
     }
 
+    fn css_id(&self) -> Option<&str> {
+        self.id.as_deref().or_else(|| self.widget.css_id())
+    }
+
+    fn css_classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    fn tooltip_text(&self) -> Option<&str> {
+        self.tooltip.as_deref().or_else(|| self.widget.tooltip_text())
+    }
+
+    fn border_title_text(&self) -> Option<&str> {
+        self.border_title
+            .as_deref()
+            .or_else(|| self.widget.border_title_text())
+    }
+
+    fn aria_label(&self) -> Option<&str> {
+        self.aria_label.as_deref().or_else(|| self.widget.aria_label())
+    }
+
+    fn content_text(&self) -> Option<&str> {
+        self.widget.content_text()
+    }
+
+    fn is_disabled(&self) -> bool {
+        self.disabled.map_or_else(|| self.widget.is_disabled(), |s| s.get_untracked())
+    }
+
+    fn is_loading(&self) -> bool {
+        self.loading.map_or_else(|| self.widget.is_loading(), |s| s.get_untracked())
+    }
+
+    fn bindings(&self) -> &'static [WidgetBinding] {
+        self.widget.bindings()
+    }
+
+    fn child_widgets(&self) -> &[Box<dyn Widget>] {
+        self.widget.child_widgets()
+    }
+
+    fn child_keys(&self) -> Option<&[String]> {
+        self.widget.child_keys()
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -457,6 +696,14 @@ mod tests {
         assert_eq!(built.children().len(), 2);
     }
 
+    #[test]
+    fn widget_builder_exposes_css_metadata() {
+        let built = TestLabel::new("x").with_id("t").add_class("primary");
+        assert_eq!(built.css_id(), Some("t"));
+        assert_eq!(built.css_classes(), ["primary"]);
+        assert!(TestLabel::new("x").css_id().is_none());
+    }
+
     #[test]
     fn widget_builder_chainable() {
         let built = TestLabel::new("x")
@@ -485,6 +732,32 @@ mod tests {
         assert_eq!(built.classes, vec!["a", "b", "c"]);
     }
 
+    #[test]
+    fn widget_builder_sugar() {
+        let built = TestLabel::new("x")
+            .with_id("t")
+            .tooltip("Help")
+            .border_title("Title")
+            .add_class("a");
+        assert_eq!(built.css_id(), Some("t"));
+        assert_eq!(built.tooltip_text(), Some("Help"));
+        assert_eq!(built.border_title_text(), Some("Title"));
+        assert_eq!(built.css_classes(), ["a"]);
+        assert!(TestLabel::new("x").tooltip_text().is_none());
+    }
+
+    #[test]
+    fn widget_builder_state_follows_signals() {
+        use crate::reactive::create_signal;
+
+        let (busy, set_busy) = create_signal(false);
+        let built = FocusableWidget.loading(busy).disabled(busy);
+        assert!(!built.is_loading() && !built.is_disabled());
+        set_busy.set(true);
+        assert!(built.is_loading() && built.is_disabled());
+        assert!(!FocusableWidget.is_loading());
+    }
+
     #[test]
     fn widget_builder_as_any() {
         let built = TestLabel::new("test").with_id("t");
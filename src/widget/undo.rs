@@ -0,0 +1,236 @@
+//! Undo history for editable widgets.
+//!
+//! An [`UndoStack`] stores snapshots of a widget's state taken just before
+//! each edit. Edits that follow each other within a short window are grouped,
+//! so undoing a burst of typing removes the whole burst rather than one
+//! character at a time. The history is bounded; the oldest snapshots are
+//! dropped first. A new edit after an undo discards the redo history.
+//!
+//! ```ignore
+//! let before = (self.value.clone(), self.cursor);
+//! self.insert_char(ch);
+//! self.history.record(before);
+//! // ctrl+z
+//! if let Some((value, cursor)) = self.history.undo((self.value.clone(), self.cursor)) {
+//!     self.value = value;
+//!     self.cursor = cursor;
+//! }
+//! ```
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Snapshots kept by default.
+pub const DEFAULT_LIMIT: usize = 100;
+
+/// Edits closer together than this are grouped by default.
+pub const DEFAULT_GROUP_WINDOW: Duration = Duration::from_millis(500);
+
+// ---------------------------------------------------------------------------
+// UndoStack
+// ---------------------------------------------------------------------------
+
+/// Bounded undo/redo history of state snapshots, grouping rapid edits.
+#[derive(Debug, Clone)]
+pub struct UndoStack<T> {
+    undo: VecDeque<T>,
+    redo: Vec<T>,
+    limit: usize,
+    window: Duration,
+    /// When the current group's latest edit was recorded; `None` once the
+    /// group is closed.
+    last_edit: Option<Instant>,
+}
+
+impl<T> UndoStack<T> {
+    /// Create an empty history with the default limit and group window.
+    pub fn new() -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            limit: DEFAULT_LIMIT,
+            window: DEFAULT_GROUP_WINDOW,
+            last_edit: None,
+        }
+    }
+
+    /// Set how many snapshots are kept (builder pattern). At least one is.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit.max(1);
+        self
+    }
+
+    /// Set the window within which edits are grouped (builder pattern).
+    /// A zero window makes every edit its own group.
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Record the state from just before an edit.
+    pub fn record(&mut self, before: T) {
+        self.record_at(before, Instant::now());
+    }
+
+    /// Record the state from just before an edit made at `now`. Within the
+    /// group window of the previous edit the snapshot is dropped, so undo
+    /// returns to the state before the whole group.
+    pub fn record_at(&mut self, before: T, now: Instant) {
+        self.redo.clear();
+        let grouped = self
+            .last_edit
+            .is_some_and(|last| now.saturating_duration_since(last) < self.window);
+        self.last_edit = Some(now);
+        if grouped && !self.undo.is_empty() {
+            return;
+        }
+        self.push_undo(before);
+    }
+
+    /// Push onto the undo history, dropping the oldest entry at the limit.
+    fn push_undo(&mut self, state: T) {
+        while self.undo.len() >= self.limit {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(state);
+    }
+
+    /// Close the current group: the next edit starts a new one however soon
+    /// it follows. Call on anything that is not an edit, like moving the
+    /// cursor.
+    pub fn break_group(&mut self) {
+        self.last_edit = None;
+    }
+
+    /// Step back: returns the state to restore, keeping `current` for redo.
+    pub fn undo(&mut self, current: T) -> Option<T> {
+        let previous = self.undo.pop_back()?;
+        self.redo.push(current);
+        self.last_edit = None;
+        Some(previous)
+    }
+
+    /// Step forward again after an undo: returns the state to restore,
+    /// keeping `current` for undo.
+    pub fn redo(&mut self, current: T) -> Option<T> {
+        let next = self.redo.pop()?;
+        self.push_undo(current);
+        self.last_edit = None;
+        Some(next)
+    }
+
+    /// Whether there is anything to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Whether there is anything to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Number of undo steps available.
+    pub fn len(&self) -> usize {
+        self.undo.len()
+    }
+
+    /// Whether there is nothing to undo.
+    pub fn is_empty(&self) -> bool {
+        self.undo.is_empty()
+    }
+
+    /// Forget all history.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.last_edit = None;
+    }
+}
+
+impl<T> Default for UndoStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_edits_within_window() {
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let mut stack = UndoStack::new();
+        stack.record_at("", at(0));
+        stack.record_at("a", at(100));
+        stack.record_at("ab", at(200));
+        // A pause starts a new group.
+        stack.record_at("abc", at(1000));
+        assert_eq!(stack.len(), 2);
+
+        assert_eq!(stack.undo("abcd"), Some("abc"));
+        assert_eq!(stack.undo("abc"), Some(""));
+        assert_eq!(stack.undo(""), None);
+    }
+
+    #[test]
+    fn redo_and_new_edits_clear_it() {
+        let mut stack = UndoStack::new().with_window(Duration::ZERO);
+        stack.record(1);
+        stack.record(2);
+        assert_eq!(stack.undo(3), Some(2));
+        assert_eq!(stack.undo(2), Some(1));
+        assert!(stack.can_redo());
+        assert_eq!(stack.redo(1), Some(2));
+        assert_eq!(stack.len(), 1);
+
+        stack.record(2);
+        assert!(!stack.can_redo());
+        assert_eq!(stack.redo(5), None);
+    }
+
+    #[test]
+    fn bounded_and_break_group() {
+        let now = Instant::now();
+        let mut stack = UndoStack::new().with_limit(2);
+        stack.record_at(1, now);
+        stack.break_group();
+        stack.record_at(2, now);
+        stack.break_group();
+        stack.record_at(3, now);
+        assert_eq!(stack.len(), 2);
+        assert_eq!(stack.undo(4), Some(3));
+        assert_eq!(stack.undo(3), Some(2));
+        assert!(!stack.can_undo());
+
+        stack.clear();
+        assert!(stack.is_empty() && !stack.can_redo());
+    }
+
+    #[test]
+    fn redo_stays_within_limit() {
+        let now = Instant::now();
+        let mut stack = UndoStack::new().with_limit(2);
+        stack.record_at(1, now);
+        stack.break_group();
+        stack.record_at(2, now);
+        assert_eq!(stack.undo(3), Some(2));
+        assert_eq!(stack.undo(2), Some(1));
+        assert_eq!(stack.redo(1), Some(2));
+        assert_eq!(stack.redo(2), Some(3));
+        assert_eq!(stack.len(), 2);
+
+        // Shrinking the limit trims the history on the next push.
+        let mut stack = stack.with_limit(1);
+        assert_eq!(stack.undo(3), Some(2));
+        assert_eq!(stack.redo(2), Some(3));
+        assert_eq!(stack.len(), 1);
+        assert_eq!(stack.undo(3), Some(2));
+        assert!(!stack.can_undo());
+    }
+}
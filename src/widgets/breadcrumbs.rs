@@ -0,0 +1,254 @@
+//! Breadcrumbs widget: the path to the current screen, one crumb per level.
+//!
+//! The app keeps the crumbs in step with its navigation, pushing a crumb when
+//! it opens a screen and popping when it goes back. Clicking a crumb
+//! truncates the path to that level and reports the index, so the app can
+//! return to it. When the path is wider than the region, the crumbs after the
+//! root collapse into `…` oldest first; if even that is too wide, only the
+//! current crumb is shown, ellipsized as needed.
+
+use std::any::Any;
+
+use crate::css::styles::{Styles, TextOverflow};
+use crate::geometry::Region;
+use crate::render::strip::{Attrs, CellStyle, Strip};
+use crate::render::text::{fit, text_width, ELLIPSIS};
+use crate::widget::traits::Widget;
+
+// ---------------------------------------------------------------------------
+// Breadcrumbs
+// ---------------------------------------------------------------------------
+
+/// A one-line trail of clickable crumbs; the last is the current level and
+/// is drawn bold.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut crumbs = Breadcrumbs::new(["Home", "Projects", "gilt"]);
+/// crumbs.push("Settings");
+/// if let Some(level) = crumbs.click(mouse.x as i32, region) {
+///     app.go_back_to(level);
+/// }
+/// ```
+pub struct Breadcrumbs {
+    crumbs: Vec<String>,
+    separator: String,
+}
+
+impl Breadcrumbs {
+    /// Create breadcrumbs for a path, root first.
+    pub fn new(crumbs: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            crumbs: crumbs.into_iter().map(Into::into).collect(),
+            separator: " › ".into(),
+        }
+    }
+
+    /// Set the text drawn between crumbs (builder pattern). Defaults to
+    /// `" › "`.
+    pub fn with_separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// The crumbs, root first.
+    pub fn crumbs(&self) -> &[String] {
+        &self.crumbs
+    }
+
+    /// The current (last) crumb.
+    pub fn current(&self) -> Option<&str> {
+        self.crumbs.last().map(String::as_str)
+    }
+
+    /// Descend one level.
+    pub fn push(&mut self, crumb: impl Into<String>) {
+        self.crumbs.push(crumb.into());
+    }
+
+    /// Go back one level, returning the crumb left.
+    pub fn pop(&mut self) -> Option<String> {
+        self.crumbs.pop()
+    }
+
+    /// Replace the whole path.
+    pub fn set_path(&mut self, crumbs: impl IntoIterator<Item = impl Into<String>>) {
+        self.crumbs = crumbs.into_iter().map(Into::into).collect();
+    }
+
+    /// Go back to the crumb at `index`, dropping the levels after it.
+    /// Returns `false` if out of range.
+    pub fn navigate_to(&mut self, index: usize) -> bool {
+        if index < self.crumbs.len() {
+            self.crumbs.truncate(index + 1);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The index of the crumb drawn at column `x`, if any. The collapsed
+    /// `…` and the separators are not crumbs.
+    pub fn crumb_at(&self, x: i32, region: Region) -> Option<usize> {
+        let mut start = region.x;
+        for (i, (index, text)) in self
+            .visible(region.width.max(0) as usize)
+            .iter()
+            .enumerate()
+        {
+            if i > 0 {
+                start += text_width(&self.separator) as i32;
+            }
+            let end = start + text_width(text) as i32;
+            if (start..end).contains(&x) {
+                return *index;
+            }
+            start = end;
+        }
+        None
+    }
+
+    /// Navigate to the crumb under a click at column `x`, returning its
+    /// index.
+    pub fn click(&mut self, x: i32, region: Region) -> Option<usize> {
+        let index = self.crumb_at(x, region)?;
+        self.navigate_to(index);
+        Some(index)
+    }
+
+    /// The crumbs that fit in `width` cells, each with its index (`None`
+    /// for the collapsed `…`).
+    fn visible(&self, width: usize) -> Vec<(Option<usize>, String)> {
+        let Some(last) = self.crumbs.len().checked_sub(1) else {
+            return Vec::new();
+        };
+        let separator = text_width(&self.separator);
+        // Hide 0, 1, 2, ... crumbs after the root, always keeping the last.
+        for hidden in 0..last {
+            let mut parts = vec![(Some(0), self.crumbs[0].clone())];
+            if hidden > 0 {
+                parts.push((None, ELLIPSIS.to_string()));
+            }
+            parts.extend((1 + hidden..=last).map(|i| (Some(i), self.crumbs[i].clone())));
+            let total: usize = parts
+                .iter()
+                .map(|(_, text)| text_width(text))
+                .sum::<usize>()
+                + separator * (parts.len() - 1);
+            if total <= width {
+                return parts;
+            }
+        }
+        vec![(
+            Some(last),
+            fit(&self.crumbs[last], width, TextOverflow::Ellipsis),
+        )]
+    }
+}
+
+impl Widget for Breadcrumbs {
+    fn widget_type(&self) -> &str {
+        "Breadcrumbs"
+    }
+
+    fn default_css(&self) -> &str {
+        "Breadcrumbs { height: 1; width: 1fr; }"
+    }
+
+    fn render(&self, region: Region, styles: &Styles) -> Vec<Strip> {
+        if region.width <= 0 || region.height <= 0 {
+            return Vec::new();
+        }
+
+        let style = CellStyle::from_styles(styles);
+        let current = style.with_attrs(Attrs::BOLD);
+        let separator = style.with_attrs(Attrs::DIM);
+        let last = self.crumbs.len().checked_sub(1);
+
+        let mut strip = Strip::new(region.y, region.x);
+        for (i, (index, text)) in self.visible(region.width as usize).iter().enumerate() {
+            if i > 0 {
+                strip.push_str(&self.separator, separator);
+            }
+            let style = if *index == last { current } else { style };
+            strip.push_str(text, style);
+        }
+        strip.fill(region.width, style);
+        vec![strip]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crumbs() -> Breadcrumbs {
+        Breadcrumbs::new(["Home", "Projects", "gilt", "Settings"])
+    }
+
+    fn text(b: &Breadcrumbs, width: i32) -> String {
+        b.render(Region::new(0, 0, width, 1), &Styles::new())[0]
+            .cells
+            .iter()
+            .map(|c| c.ch)
+            .collect()
+    }
+
+    #[test]
+    fn renders_full_path_with_current_bold() {
+        let b = crumbs();
+        assert_eq!(text(&b, 34), "Home › Projects › gilt › Settings ");
+        let strip = &b.render(Region::new(0, 0, 34, 1), &Styles::new())[0];
+        assert!(strip.cells[25].style.bold());
+        assert!(!strip.cells[0].style.bold());
+        assert!(strip.cells[5].style.dim());
+    }
+
+    #[test]
+    fn narrow_regions_collapse_then_ellipsize() {
+        let b = crumbs();
+        assert_eq!(text(&b, 26), "Home › … › gilt › Settings");
+        assert_eq!(text(&b, 25), "Home › … › Settings      ");
+        assert_eq!(text(&b, 18), "Settings          ");
+        assert_eq!(text(&b, 5), "Sett…");
+    }
+
+    #[test]
+    fn click_navigates_back() {
+        let mut b = crumbs();
+        let region = Region::new(2, 0, 20, 1);
+        // "Home › … › Settings" starting at x = 2.
+        assert_eq!(b.crumb_at(2, region), Some(0));
+        assert_eq!(b.crumb_at(6, region), None);
+        assert_eq!(b.crumb_at(9, region), None);
+        assert_eq!(b.click(14, region), Some(3));
+        assert_eq!(b.crumbs().len(), 4);
+        assert_eq!(b.click(3, region), Some(0));
+        assert_eq!(b.crumbs(), ["Home"]);
+    }
+
+    #[test]
+    fn push_pop_and_navigate() {
+        let mut b = Breadcrumbs::new(["Home"]);
+        b.push("Logs");
+        assert_eq!(b.current(), Some("Logs"));
+        assert_eq!(b.pop().as_deref(), Some("Logs"));
+        b.set_path(["A", "B", "C"]);
+        assert!(b.navigate_to(1));
+        assert!(!b.navigate_to(5));
+        assert_eq!(b.crumbs(), ["A", "B"]);
+    }
+}
@@ -1,13 +1,22 @@
 //! Button widget: an interactive, focusable button.
 //!
-//! Renders a label centered within its region. Supports a `disabled` state
-//! that prevents focus.
+//! Renders a label, parsed as [markup](crate::text), centered within its
+//! region. Supports a `disabled` state
+//! that prevents focus and dims the label (`Button:disabled`), either fixed or
+//! following a validity signal ([`Button::enabled_when`]), and an optional keyboard shortcut hint shown dimmed
+//! at the right edge (`Save   Ctrl+S`), either given directly or looked up
+//! from the key bindings for the button's action.
 
 use std::any::Any;
 
-use crate::css::styles::{Styles, TextAlign};
+use crate::css::styles::{Styles, TextAlign, TextWrap};
+use crate::event::binding::KeyBindingRegistry;
+use crate::event::keys::display_keys;
 use crate::geometry::Region;
-use crate::render::strip::{CellStyle, Strip};
+use crate::reactive::ReadSignal;
+use crate::render::strip::{Attrs, CellStyle, Strip};
+use crate::render::text::text_width;
+use crate::text::Text;
 use crate::widget::traits::Widget;
 
 // ---------------------------------------------------------------------------
@@ -24,35 +33,106 @@ use crate::widget::traits::Widget;
 /// ```ignore
 /// let btn = Button::new("Submit");
 /// let disabled_btn = Button::new("Locked").disabled(true);
+/// let danger = Button::new("[bold red]Delete[/] all");
+/// let valid = create_memo(move || !name.get().is_empty() && age.get() > 0);
+/// let submit = Button::new("Submit").enabled_when(valid);
+/// let mut save = Button::new("Save").with_action("save");
+/// save.resolve_shortcut(&app.bindings); // shows "Ctrl+S" if bound
 /// ```
 pub struct Button {
     label: String,
+    text: Text,
     disabled: bool,
+    /// Enables the button only while this is `true`.
+    enabled_when: Option<ReadSignal<bool>>,
+    shortcut: Option<String>,
+    action: Option<String>,
 }
 
 impl Button {
-    /// Create a new button with the given label.
+    /// Create a new button with the given label, parsed as markup.
     pub fn new(label: impl Into<String>) -> Self {
+        let label = label.into();
         Self {
-            label: label.into(),
+            text: Text::from_markup(&label),
+            label,
             disabled: false,
+            enabled_when: None,
+            shortcut: None,
+            action: None,
         }
     }
 
+    /// Show a shortcut hint at the right edge (builder pattern).
+    pub fn with_shortcut(mut self, shortcut: impl Into<String>) -> Self {
+        self.shortcut = Some(shortcut.into());
+        self
+    }
+
+    /// Name the custom binding action the button performs (builder pattern).
+    /// Its bound key becomes the shortcut hint unless one is set explicitly.
+    pub fn with_action(mut self, name: impl Into<String>) -> Self {
+        self.action = Some(name.into());
+        self
+    }
+
     /// Set whether the button is disabled (builder pattern).
     pub fn disabled(mut self, disabled: bool) -> Self {
         self.disabled = disabled;
         self
     }
 
-    /// Return the button label.
+    /// Enable the button only while `valid` is `true`, e.g. a memo over a
+    /// form's fields (builder pattern). Like [`WidgetExt::disabled`], this
+    /// subscribes the running effect, so a component re-renders, and the
+    /// node's `:disabled` state updates, as the form becomes (in)valid.
+    ///
+    /// [`WidgetExt::disabled`]: crate::widget::WidgetExt::disabled
+    pub fn enabled_when(mut self, valid: ReadSignal<bool>) -> Self {
+        valid.get();
+        self.enabled_when = Some(valid);
+        self
+    }
+
+    /// Return the button label as given, markup included.
     pub fn label(&self) -> &str {
         &self.label
     }
 
-    /// Whether the button is disabled.
+    /// Return the styled text the label parsed to.
+    pub fn text(&self) -> &Text {
+        &self.text
+    }
+
+    /// Whether the button is disabled: set so, or its validity signal is
+    /// `false`.
     pub fn is_disabled(&self) -> bool {
         self.disabled
+            || self
+                .enabled_when
+                .is_some_and(|valid| !valid.get_untracked())
+    }
+
+    /// The shortcut hint, if any.
+    pub fn shortcut(&self) -> Option<&str> {
+        self.shortcut.as_deref()
+    }
+
+    /// The custom binding action the button performs, if any.
+    pub fn action(&self) -> Option<&str> {
+        self.action.as_deref()
+    }
+
+    /// Fill in the shortcut hint from `bindings` if none is set and the
+    /// button's action has a bound key.
+    pub fn resolve_shortcut(&mut self, bindings: &KeyBindingRegistry) {
+        if self.shortcut.is_none() {
+            self.shortcut = self
+                .action
+                .as_deref()
+                .and_then(|name| bindings.key_for(name))
+                .map(|key| display_keys(&[key]));
+        }
     }
 }
 
@@ -62,11 +142,24 @@ impl Widget for Button {
     }
 
     fn default_css(&self) -> &str {
-        "Button { height: 3; min-width: 10; text-align: center; }"
+        "Button { height: 3; min-width: 10; text-align: center; }
+         Button:disabled { text-style: dim; }"
     }
 
     fn can_focus(&self) -> bool {
-        !self.disabled
+        !self.is_disabled()
+    }
+
+    fn is_disabled(&self) -> bool {
+        Button::is_disabled(self)
+    }
+
+    fn aria_label(&self) -> Option<&str> {
+        Some(self.text.plain())
+    }
+
+    fn content_text(&self) -> Option<&str> {
+        Some(self.text.plain())
     }
 
     fn render(&self, region: Region, styles: &Styles) -> Vec<Strip> {
@@ -75,49 +168,59 @@ impl Widget for Button {
         }
 
         let style = CellStyle::from_styles(styles);
-        let width = region.width as usize;
 
-        // Determine which row gets the label.
-        let label_row = if region.height >= 3 {
-            1 // middle row for 3+ height
-        } else {
-            0 // first row for short buttons
+        // The shortcut hint takes the right edge, one cell in, and is dropped
+        // when it would leave no room for the label.
+        let shortcut = self
+            .shortcut
+            .as_deref()
+            .filter(|s| text_width(s) + 3 <= region.width as usize);
+        let width = match shortcut {
+            Some(s) => region.width as usize - text_width(s) - 2,
+            None => region.width as usize,
+        };
+
+        // Fit the label: wrapped over every row with `text-wrap: wrap`,
+        // otherwise on one row, shortened per `text-overflow`.
+        let overflow = styles.text_overflow.unwrap_or_default();
+        let lines = match styles.text_wrap.unwrap_or_default() {
+            TextWrap::Wrap => self
+                .text
+                .wrap_lines(width, region.height as usize, overflow),
+            TextWrap::Nowrap => vec![self.text.fit(width, overflow)],
         };
 
+        // Center the label rows vertically: the middle row for a single line
+        // in a 3-row button, the first row for short buttons.
+        let first_row = (region.height - lines.len() as i32).max(0) / 2;
+
         // Determine text alignment — default to center.
         let text_align = styles.text_align.unwrap_or(TextAlign::Center);
 
-        // Truncate label to fit width.
-        let label: String = self.label.chars().take(width).collect();
-        let label_len = label.len();
-
         (0..region.height)
             .map(|row| {
                 let mut strip = Strip::new(region.y + row, region.x);
-                if row == label_row {
+                if let Some(label) = usize::try_from(row - first_row)
+                    .ok()
+                    .and_then(|i| lines.get(i))
+                {
+                    let label_len = label.width();
                     let pad_left = match text_align {
                         TextAlign::Left => 0,
-                        TextAlign::Center => {
-                            if label_len < width {
-                                (width - label_len) / 2
-                            } else {
-                                0
-                            }
-                        }
+                        TextAlign::Center => width.saturating_sub(label_len) / 2,
                         TextAlign::Right => width.saturating_sub(label_len),
                     };
                     // Left padding
-                    for _ in 0..pad_left {
-                        strip.push(' ', style.clone());
-                    }
+                    strip.fill(pad_left as i32, style);
                     // Label text
-                    strip.push_str(&label, style.clone());
-                    // Fill remaining width
-                    strip.fill(region.width, style.clone());
-                } else {
-                    // Empty row — fill with background
-                    strip.fill(region.width, style.clone());
+                    label.push_to(&mut strip, style);
+                    if let (Some(shortcut), true) = (shortcut, row == first_row) {
+                        strip.fill(region.width - 1 - text_width(shortcut) as i32, style);
+                        strip.push_str(shortcut, style.with_attrs(Attrs::DIM));
+                    }
                 }
+                // Fill remaining width with background
+                strip.fill(region.width, style);
                 strip
             })
             .collect()
@@ -139,6 +242,7 @@ impl Widget for Button {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::render::strip::Color;
 
     fn region(w: i32, h: i32) -> Region {
         Region::new(0, 0, w, h)
@@ -175,6 +279,44 @@ mod tests {
         assert!(!b.can_focus());
     }
 
+    #[test]
+    fn enabled_when_follows_signal() {
+        use crate::reactive::signal::reset_runtime;
+        use crate::reactive::{create_memo, create_signal};
+        use crate::widget::reconcile::node_data;
+
+        reset_runtime();
+        let (name, set_name) = create_signal(String::new());
+        let valid = create_memo(move || !name.get().is_empty());
+        let b = Button::new("Submit").enabled_when(valid);
+        assert!(b.is_disabled() && !b.can_focus());
+        assert!(node_data(&b).matches_pseudo_class("disabled"));
+
+        set_name.set("Ada".into());
+        assert!(!b.is_disabled() && b.can_focus());
+        assert!(node_data(&b).matches_pseudo_class("enabled"));
+        // A fixed disabled state wins.
+        assert!(Button::new("x")
+            .disabled(true)
+            .enabled_when(valid)
+            .is_disabled());
+    }
+
+    #[test]
+    fn disabled_style_dims_label() {
+        use crate::css::parser::parse_css;
+        use crate::css::stylesheet::CompiledStylesheet;
+        use crate::dom::tree::Dom;
+        use crate::widget::reconcile::node_data;
+
+        let b = Button::new("OK").disabled(true);
+        let mut dom = Dom::new();
+        let node = dom.insert(node_data(&b));
+        let sheet = CompiledStylesheet::compile(&parse_css(b.default_css()).unwrap(), true);
+        let styles = sheet.compute_styles(node, &dom);
+        assert_eq!(styles.text_style.and_then(|t| t.dim), Some(true));
+    }
+
     #[test]
     fn label_accessor() {
         let b = Button::new("Submit");
@@ -230,6 +372,80 @@ mod tests {
         assert_eq!(strips[1].cells[0].ch, 'V');
     }
 
+    #[test]
+    fn render_wraps_label_over_rows() {
+        let b = Button::new("Save all files now");
+        let mut s = styles();
+        s.text_wrap = Some(TextWrap::Wrap);
+        s.text_overflow = Some(crate::css::styles::TextOverflow::Ellipsis);
+        let rows: Vec<String> = b
+            .render(region(8, 3), &s)
+            .iter()
+            .map(|strip| strip.cells.iter().map(|c| c.ch).collect())
+            .collect();
+        assert_eq!(rows, ["Save all", " files  ", "  now   "]);
+
+        let rows: Vec<String> = b
+            .render(region(8, 2), &s)
+            .iter()
+            .map(|strip| strip.cells.iter().map(|c| c.ch).collect())
+            .collect();
+        assert_eq!(rows, ["Save all", "files n…"]);
+    }
+
+    #[test]
+    fn render_shortcut_right_aligned_and_dim() {
+        let b = Button::new("Save").with_shortcut("Ctrl+S");
+        let strips = b.render(region(16, 3), &styles());
+        let row: String = strips[1].cells.iter().map(|c| c.ch).collect();
+        assert_eq!(row, "  Save   Ctrl+S ");
+        assert!(strips[1].cells[9].style.dim());
+        assert!(!strips[1].cells[2].style.dim());
+
+        // Too narrow for both: the hint is dropped.
+        let strips = b.render(region(8, 1), &styles());
+        let row: String = strips[0].cells.iter().map(|c| c.ch).collect();
+        assert_eq!(row, "  Save  ");
+    }
+
+    #[test]
+    fn render_markup_label_with_plain_aria_label() {
+        let b = Button::new("[b]Save[/] [u]all[/]");
+        assert_eq!(b.aria_label(), Some("Save all"));
+        assert_eq!(b.content_text(), Some("Save all"));
+        let strips = b.render(region(10, 1), &styles());
+        let row: String = strips[0].cells.iter().map(|c| c.ch).collect();
+        assert_eq!(row, " Save all ");
+        assert!(strips[0].cells[1].style.bold() && !strips[0].cells[1].style.underline());
+        assert!(!strips[0].cells[5].style.bold());
+        assert!(strips[0].cells[6].style.underline());
+    }
+
+    #[test]
+    fn shortcut_resolved_from_bindings() {
+        use crate::event::binding::BindingAction;
+        use crate::event::input::{Key, Modifiers};
+
+        let mut bindings = KeyBindingRegistry::new();
+        bindings.bind(
+            Key::Char('s'),
+            Modifiers::CTRL,
+            BindingAction::Custom("save".into()),
+        );
+        let mut save = Button::new("Save").with_action("save");
+        save.resolve_shortcut(&bindings);
+        assert_eq!(save.action(), Some("save"));
+        assert_eq!(save.shortcut(), Some("Ctrl+S"));
+
+        let mut explicit = Button::new("Save").with_action("save").with_shortcut("F2");
+        explicit.resolve_shortcut(&bindings);
+        assert_eq!(explicit.shortcut(), Some("F2"));
+
+        let mut unbound = Button::new("Open").with_action("open");
+        unbound.resolve_shortcut(&bindings);
+        assert_eq!(unbound.shortcut(), None);
+    }
+
     #[test]
     fn render_zero_region() {
         let b = Button::new("OK");
@@ -256,7 +472,7 @@ mod tests {
         let mut s = styles();
         s.color = Some("green".into());
         let strips = b.render(region(10, 3), &s);
-        assert_eq!(strips[1].cells[4].style.fg, Some("green".into()));
+        assert_eq!(strips[1].cells[4].style.fg, Some(Color::Green));
     }
 
     #[test]
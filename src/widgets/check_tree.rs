@@ -0,0 +1,612 @@
+//! CheckTree widget: a collapsible tree with a tri-state checkbox per node.
+//!
+//! Only leaves store a checked flag. A parent's state is derived from the
+//! leaves beneath it: checked when all are, unchecked when none are, and
+//! partial otherwise. Toggling a parent checks or unchecks its whole subtree.
+//! Every mutation returns a [`SelectionChanged`] message describing which
+//! leaves flipped, ready to be posted to the app.
+
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use crate::css::styles::Styles;
+use crate::event::message::Message;
+use crate::geometry::Region;
+use crate::render::strip::{Attrs, CellStyle, Strip};
+use crate::widget::filter::{FilterMatch, Filterable};
+use crate::widget::traits::Widget;
+
+// ---------------------------------------------------------------------------
+// CheckState
+// ---------------------------------------------------------------------------
+
+/// The checkbox state of a tree node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckState {
+    /// Neither the node nor any leaf beneath it is checked.
+    Unchecked,
+    /// The node, or every leaf beneath it, is checked.
+    Checked,
+    /// Some but not all leaves beneath the node are checked.
+    Partial,
+}
+
+impl CheckState {
+    /// The checkbox glyph for this state.
+    pub fn glyph(self) -> &'static str {
+        match self {
+            CheckState::Unchecked => "[ ]",
+            CheckState::Checked => "[x]",
+            CheckState::Partial => "[-]",
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// SelectionChanged
+// ---------------------------------------------------------------------------
+
+/// Reports which leaves a CheckTree operation checked or unchecked.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SelectionChanged {
+    /// Leaves that became checked.
+    pub checked: Vec<usize>,
+    /// Leaves that became unchecked.
+    pub unchecked: Vec<usize>,
+}
+
+impl SelectionChanged {
+    /// Whether nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.checked.is_empty() && self.unchecked.is_empty()
+    }
+}
+
+impl Message for SelectionChanged {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn message_name(&self) -> &str {
+        "SelectionChanged"
+    }
+}
+
+// ---------------------------------------------------------------------------
+// CheckTree
+// ---------------------------------------------------------------------------
+
+/// One node in the tree arena.
+#[derive(Debug, Clone)]
+struct Entry {
+    label: String,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    checked: bool,
+    expanded: bool,
+}
+
+/// A tree of labelled nodes with tri-state checkboxes.
+///
+/// Nodes are identified by the index returned when they are added.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut tree = CheckTree::new();
+/// let docs = tree.add_root("Documents");
+/// tree.add_child(docs, "report.pdf");
+/// let photos = tree.add_child(docs, "Photos");
+/// tree.add_child(photos, "beach.jpg");
+///
+/// let change = tree.toggle(photos);
+/// assert_eq!(tree.state(docs), CheckState::Partial);
+/// app.dispatcher.push(Envelope::new(change, tree_node));
+/// ```
+pub struct CheckTree {
+    entries: Vec<Entry>,
+    roots: Vec<usize>,
+    cursor: usize,
+    /// Matched label ranges while a filter is active.
+    filter: Option<HashMap<usize, Vec<Range<usize>>>>,
+}
+
+impl CheckTree {
+    /// Create an empty tree.
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            roots: Vec::new(),
+            cursor: 0,
+            filter: None,
+        }
+    }
+
+    fn push(&mut self, parent: Option<usize>, label: String) -> usize {
+        let id = self.entries.len();
+        self.entries.push(Entry {
+            label,
+            parent,
+            children: Vec::new(),
+            checked: false,
+            expanded: true,
+        });
+        id
+    }
+
+    /// Add a top-level node, returning its id.
+    pub fn add_root(&mut self, label: impl Into<String>) -> usize {
+        let id = self.push(None, label.into());
+        self.roots.push(id);
+        id
+    }
+
+    /// Add a node under `parent`, returning its id.
+    ///
+    /// The new leaf inherits the parent's checked flag if the parent was a
+    /// checked leaf until now.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `parent` is not a node in this tree.
+    pub fn add_child(&mut self, parent: usize, label: impl Into<String>) -> usize {
+        let inherited = self.entries[parent].children.is_empty() && self.entries[parent].checked;
+        let id = self.push(Some(parent), label.into());
+        self.entries[id].checked = inherited;
+        self.entries[parent].children.push(id);
+        id
+    }
+
+    /// Number of nodes.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the tree has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// A node's label.
+    pub fn label(&self, id: usize) -> Option<&str> {
+        self.entries.get(id).map(|e| e.label.as_str())
+    }
+
+    /// A node's parent.
+    pub fn parent(&self, id: usize) -> Option<usize> {
+        self.entries.get(id).and_then(|e| e.parent)
+    }
+
+    /// A node's children.
+    pub fn children(&self, id: usize) -> &[usize] {
+        self.entries.get(id).map_or(&[], |e| &e.children)
+    }
+
+    /// Leaf ids in the subtree rooted at `id` (just `id` if it is a leaf).
+    fn leaves(&self, id: usize) -> Vec<usize> {
+        let mut out = Vec::new();
+        let mut stack = vec![id];
+        while let Some(node) = stack.pop() {
+            let children = &self.entries[node].children;
+            if children.is_empty() {
+                out.push(node);
+            } else {
+                stack.extend(children.iter().rev());
+            }
+        }
+        out
+    }
+
+    /// A node's checkbox state, aggregated from its leaves.
+    pub fn state(&self, id: usize) -> CheckState {
+        if id >= self.entries.len() {
+            return CheckState::Unchecked;
+        }
+        let leaves = self.leaves(id);
+        let checked = leaves.iter().filter(|&&l| self.entries[l].checked).count();
+        if checked == 0 {
+            CheckState::Unchecked
+        } else if checked == leaves.len() {
+            CheckState::Checked
+        } else {
+            CheckState::Partial
+        }
+    }
+
+    /// All checked leaves, in id order.
+    pub fn checked(&self) -> Vec<usize> {
+        self.all_leaves()
+            .into_iter()
+            .filter(|&id| self.entries[id].checked)
+            .collect()
+    }
+
+    /// Set the leaves in `ids` to `checked`, recording what flipped.
+    fn set_leaves(
+        &mut self,
+        ids: impl IntoIterator<Item = usize>,
+        checked: bool,
+    ) -> SelectionChanged {
+        let mut change = SelectionChanged::default();
+        for id in ids {
+            let entry = &mut self.entries[id];
+            if entry.checked != checked {
+                entry.checked = checked;
+                if checked {
+                    change.checked.push(id);
+                } else {
+                    change.unchecked.push(id);
+                }
+            }
+        }
+        change.checked.sort_unstable();
+        change.unchecked.sort_unstable();
+        change
+    }
+
+    /// Check or uncheck a node and its whole subtree.
+    pub fn set_checked(&mut self, id: usize, checked: bool) -> SelectionChanged {
+        if id >= self.entries.len() {
+            return SelectionChanged::default();
+        }
+        let leaves = self.leaves(id);
+        self.set_leaves(leaves, checked)
+    }
+
+    /// Toggle a node: unchecked and partial nodes become checked, checked
+    /// nodes become unchecked.
+    pub fn toggle(&mut self, id: usize) -> SelectionChanged {
+        let checked = self.state(id) != CheckState::Checked;
+        self.set_checked(id, checked)
+    }
+
+    /// Check every node.
+    pub fn check_all(&mut self) -> SelectionChanged {
+        let leaves = self.all_leaves();
+        self.set_leaves(leaves, true)
+    }
+
+    /// Uncheck every node.
+    pub fn uncheck_all(&mut self) -> SelectionChanged {
+        let leaves = self.all_leaves();
+        self.set_leaves(leaves, false)
+    }
+
+    /// Every leaf id, in id order.
+    fn all_leaves(&self) -> Vec<usize> {
+        (0..self.entries.len())
+            .filter(|&id| self.entries[id].children.is_empty())
+            .collect()
+    }
+
+    /// Invert the checked flag of every leaf.
+    pub fn invert(&mut self) -> SelectionChanged {
+        let (on, off): (Vec<usize>, Vec<usize>) = self
+            .all_leaves()
+            .into_iter()
+            .partition(|&id| self.entries[id].checked);
+        let mut change = self.set_leaves(off, true);
+        change.unchecked = self.set_leaves(on, false).unchecked;
+        change
+    }
+
+    /// Check every leaf whose label matches `predicate`.
+    pub fn check_where(&mut self, predicate: impl Fn(&str) -> bool) -> SelectionChanged {
+        let matching: Vec<usize> = self
+            .all_leaves()
+            .into_iter()
+            .filter(|&id| predicate(&self.entries[id].label))
+            .collect();
+        self.set_leaves(matching, true)
+    }
+
+    // -- expansion and cursor ------------------------------------------------
+
+    /// Whether a node's children are shown.
+    pub fn is_expanded(&self, id: usize) -> bool {
+        self.entries.get(id).is_some_and(|e| e.expanded)
+    }
+
+    /// Show or hide a node's children.
+    pub fn set_expanded(&mut self, id: usize, expanded: bool) {
+        if let Some(entry) = self.entries.get_mut(id) {
+            entry.expanded = expanded;
+        }
+        // Keep the cursor on a visible row.
+        if !self.visible().iter().any(|&(row, _)| row == self.cursor) {
+            self.cursor = id;
+        }
+    }
+
+    /// Visible nodes in display order, with their depth.
+    ///
+    /// While a filter is active, only matches and their ancestors are shown,
+    /// regardless of expansion.
+    fn visible(&self) -> Vec<(usize, usize)> {
+        let shown: Option<HashSet<usize>> = self.filter.as_ref().map(|matches| {
+            let mut shown = HashSet::new();
+            for &id in matches.keys() {
+                let mut node = Some(id);
+                while let Some(n) = node.filter(|&n| shown.insert(n)) {
+                    node = self.entries[n].parent;
+                }
+            }
+            shown
+        });
+
+        let mut out = Vec::new();
+        let mut stack: Vec<(usize, usize)> = self.roots.iter().rev().map(|&r| (r, 0)).collect();
+        while let Some((id, depth)) = stack.pop() {
+            if shown.as_ref().is_some_and(|shown| !shown.contains(&id)) {
+                continue;
+            }
+            out.push((id, depth));
+            let entry = &self.entries[id];
+            if entry.expanded || shown.is_some() {
+                stack.extend(entry.children.iter().rev().map(|&c| (c, depth + 1)));
+            }
+        }
+        out
+    }
+
+    /// The node under the cursor, if the tree is not empty.
+    pub fn cursor(&self) -> Option<usize> {
+        (!self.entries.is_empty()).then_some(self.cursor)
+    }
+
+    /// Move the cursor to the next visible row.
+    pub fn cursor_down(&mut self) {
+        let rows = self.visible();
+        if let Some(pos) = rows.iter().position(|&(id, _)| id == self.cursor) {
+            if let Some(&(next, _)) = rows.get(pos + 1) {
+                self.cursor = next;
+            }
+        }
+    }
+
+    /// Move the cursor to the previous visible row.
+    pub fn cursor_up(&mut self) {
+        let rows = self.visible();
+        if let Some(pos) = rows.iter().position(|&(id, _)| id == self.cursor) {
+            if pos > 0 {
+                self.cursor = rows[pos - 1].0;
+            }
+        }
+    }
+
+    /// Toggle the node under the cursor.
+    pub fn toggle_cursor(&mut self) -> SelectionChanged {
+        match self.cursor() {
+            Some(id) => self.toggle(id),
+            None => SelectionChanged::default(),
+        }
+    }
+}
+
+impl Default for CheckTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Filterable for CheckTree {
+    fn item_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn item_text(&self, index: usize) -> String {
+        self.entries[index].label.clone()
+    }
+
+    fn apply_filter(&mut self, matches: Option<&[FilterMatch]>) {
+        self.filter = matches.map(|matches| {
+            matches
+                .iter()
+                .map(|m| (m.index, m.ranges.clone()))
+                .collect()
+        });
+    }
+
+    fn jump_to(&mut self, index: usize) {
+        if index >= self.entries.len() {
+            return;
+        }
+        let mut parent = self.entries[index].parent;
+        while let Some(p) = parent {
+            self.entries[p].expanded = true;
+            parent = self.entries[p].parent;
+        }
+        self.cursor = index;
+    }
+}
+
+impl Widget for CheckTree {
+    fn widget_type(&self) -> &str {
+        "CheckTree"
+    }
+
+    fn default_css(&self) -> &str {
+        "CheckTree { width: 1fr; height: 1fr; }"
+    }
+
+    fn can_focus(&self) -> bool {
+        true
+    }
+
+    fn render(&self, region: Region, styles: &Styles) -> Vec<Strip> {
+        if region.width <= 0 || region.height <= 0 {
+            return Vec::new();
+        }
+
+        let style = CellStyle::from_styles(styles);
+        let highlight = style.with_attrs(Attrs::REVERSE);
+        let rows = self.visible();
+        let width = region.width as usize;
+
+        (0..region.height)
+            .map(|row| {
+                let mut strip = Strip::new(region.y + row, region.x);
+                if let Some(&(id, depth)) = rows.get(row as usize) {
+                    let entry = &self.entries[id];
+                    let marker = match (entry.children.is_empty(), entry.expanded) {
+                        (true, _) => ' ',
+                        (false, true) => '▾',
+                        (false, false) => '▸',
+                    };
+                    let prefix =
+                        format!("{}{marker} {} ", "  ".repeat(depth), self.state(id).glyph());
+                    let row_style = if id == self.cursor {
+                        &highlight
+                    } else {
+                        &style
+                    };
+                    let matched = row_style.with_attrs(Attrs::BOLD | Attrs::UNDERLINE);
+                    let ranges = self.filter.as_ref().and_then(|f| f.get(&id));
+                    let label = entry.label.chars().enumerate().map(|(i, ch)| {
+                        let hit = ranges.is_some_and(|r| r.iter().any(|r| r.contains(&i)));
+                        (ch, if hit { &matched } else { row_style })
+                    });
+                    let cells = prefix.chars().map(|ch| (ch, row_style)).chain(label);
+                    for (ch, cell_style) in cells.take(width) {
+                        strip.push(ch, *cell_style);
+                    }
+                }
+                strip.fill(region.width, style);
+                strip
+            })
+            .collect()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// backup
+    /// ├─ docs
+    /// │  ├─ a.txt
+    /// │  └─ b.txt
+    /// └─ c.txt
+    fn tree() -> (CheckTree, [usize; 5]) {
+        let mut tree = CheckTree::new();
+        let backup = tree.add_root("backup");
+        let docs = tree.add_child(backup, "docs");
+        let a = tree.add_child(docs, "a.txt");
+        let b = tree.add_child(docs, "b.txt");
+        let c = tree.add_child(backup, "c.txt");
+        (tree, [backup, docs, a, b, c])
+    }
+
+    #[test]
+    fn parent_state_aggregates_leaves() {
+        let (mut tree, [backup, docs, a, b, _]) = tree();
+        assert_eq!(tree.state(backup), CheckState::Unchecked);
+        tree.toggle(a);
+        assert_eq!(tree.state(docs), CheckState::Partial);
+        assert_eq!(tree.state(backup), CheckState::Partial);
+        tree.toggle(b);
+        assert_eq!(tree.state(docs), CheckState::Checked);
+        assert_eq!(tree.state(backup), CheckState::Partial);
+    }
+
+    #[test]
+    fn toggling_parent_cascades_and_reports_changes() {
+        let (mut tree, [backup, docs, a, b, c]) = tree();
+        tree.toggle(a);
+        let change = tree.toggle(backup);
+        assert_eq!(change.checked, vec![b, c]);
+        assert!(change.unchecked.is_empty());
+        assert_eq!(tree.state(backup), CheckState::Checked);
+
+        let change = tree.toggle(docs);
+        assert_eq!(change.unchecked, vec![a, b]);
+        assert_eq!(tree.checked(), vec![c]);
+    }
+
+    #[test]
+    fn bulk_operations() {
+        let (mut tree, [backup, _, a, b, c]) = tree();
+        assert_eq!(tree.check_all().checked, vec![a, b, c]);
+        assert!(tree.check_all().is_empty());
+        assert_eq!(tree.uncheck_all().unchecked, vec![a, b, c]);
+
+        tree.check_where(|label| label.starts_with('a'));
+        assert_eq!(tree.checked(), vec![a]);
+        let change = tree.invert();
+        assert_eq!(change.checked, vec![b, c]);
+        assert_eq!(change.unchecked, vec![a]);
+        assert_eq!(tree.state(backup), CheckState::Partial);
+    }
+
+    #[test]
+    fn cursor_skips_collapsed_children() {
+        let (mut tree, [backup, docs, _, _, c]) = tree();
+        assert_eq!(tree.cursor(), Some(backup));
+        tree.cursor_down();
+        assert_eq!(tree.cursor(), Some(docs));
+        tree.set_expanded(docs, false);
+        tree.cursor_down();
+        assert_eq!(tree.cursor(), Some(c));
+        tree.cursor_up();
+        assert_eq!(tree.toggle_cursor().checked.len(), 2);
+    }
+
+    #[test]
+    fn render_shows_glyphs_and_indent() {
+        let (mut tree, [_, _, a, _, _]) = tree();
+        tree.toggle(a);
+        let strips = tree.render(Region::new(0, 0, 20, 6), &Styles::new());
+        let rows: Vec<String> = strips
+            .iter()
+            .map(|s| {
+                s.cells
+                    .iter()
+                    .map(|c| c.ch)
+                    .collect::<String>()
+                    .trim_end()
+                    .to_owned()
+            })
+            .collect();
+        assert_eq!(rows[0], "▾ [-] backup");
+        assert_eq!(rows[1], "  ▾ [-] docs");
+        assert_eq!(rows[2], "      [x] a.txt");
+        assert_eq!(rows[4], "    [ ] c.txt");
+        assert_eq!(rows[5], "");
+        assert!(strips[0].cells[0].style.reverse());
+    }
+
+    #[test]
+    fn filter_shows_matches_with_ancestors() {
+        use crate::widget::filter::filter_items;
+
+        let (mut tree, [backup, docs, _, b, c]) = tree();
+        tree.set_expanded(docs, false);
+        let matches = filter_items(&tree, "B.T");
+        tree.apply_filter(Some(&matches));
+        let rows: Vec<usize> = tree.visible().iter().map(|&(id, _)| id).collect();
+        assert_eq!(rows, vec![backup, docs, b]);
+
+        let strips = tree.render(Region::new(0, 0, 20, 3), &Styles::new());
+        // "      [ ] b.txt": the match starts at column 10.
+        assert!(strips[2].cells[10].style.underline());
+        assert!(!strips[2].cells[9].style.underline());
+
+        tree.jump_to(b);
+        tree.apply_filter(None);
+        assert_eq!(tree.cursor(), Some(b));
+        assert!(tree.is_expanded(docs));
+        assert_eq!(tree.visible().last(), Some(&(c, 1)));
+    }
+}
@@ -0,0 +1,240 @@
+//! CodeView widget: a block of code with a copy affordance.
+//!
+//! The block's text is drawn as-is, one source line per row, with a `copy`
+//! target in the top-right corner. Clicking the target or pressing `c` while
+//! the view is focused returns a [`CopyToClipboard`] message carrying the
+//! whole block; the app copies it (see `App::copy_to_clipboard`), and the
+//! target reads `copied ✓` for a moment as confirmation.
+
+use std::any::Any;
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+use crate::css::styles::Styles;
+use crate::event::input::{Key, KeyEvent, Modifiers, MouseAction, MouseBtn, MouseEvent};
+use crate::event::message::CopyToClipboard;
+use crate::geometry::Region;
+use crate::render::strip::{Attrs, CellStyle, Strip};
+use crate::render::text::text_width;
+use crate::t;
+use crate::widget::traits::Widget;
+
+/// How long the copy target shows its confirmation by default.
+const FLASH: Duration = Duration::from_millis(1500);
+
+// ---------------------------------------------------------------------------
+// CodeView
+// ---------------------------------------------------------------------------
+
+/// A read-only block of code that can be copied in one action.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut snippet = CodeView::new("cargo add gilt-tui").with_language("sh");
+/// if let Some(CopyToClipboard(text)) = snippet.handle_mouse(&mouse, region) {
+///     app.copy_to_clipboard(&text)?;
+/// }
+/// ```
+pub struct CodeView {
+    code: String,
+    language: Option<String>,
+    flash: Duration,
+    /// When the block was last copied, for the confirmation flash.
+    copied_at: Cell<Option<Instant>>,
+}
+
+impl CodeView {
+    /// Create a view of `code`.
+    pub fn new(code: impl Into<String>) -> Self {
+        Self {
+            code: code.into(),
+            language: None,
+            flash: FLASH,
+            copied_at: Cell::new(None),
+        }
+    }
+
+    /// Set the language the code is written in (builder pattern).
+    pub fn with_language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Set how long the copy confirmation shows (builder pattern).
+    pub fn with_flash(mut self, flash: Duration) -> Self {
+        self.flash = flash;
+        self
+    }
+
+    /// The code.
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// The language, if set.
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    /// Replace the code.
+    pub fn set_code(&mut self, code: impl Into<String>) {
+        self.code = code.into();
+    }
+
+    /// Copy the block: returns the message to send to the clipboard and
+    /// starts the confirmation flash.
+    pub fn copy(&self) -> CopyToClipboard {
+        self.copied_at.set(Some(Instant::now()));
+        CopyToClipboard(self.code.clone())
+    }
+
+    /// Whether the copy confirmation is showing at `now`.
+    pub fn is_flashing(&self, now: Instant) -> bool {
+        self.copied_at
+            .get()
+            .is_some_and(|at| now.saturating_duration_since(at) < self.flash)
+    }
+
+    /// The label of the copy target right now.
+    fn target_label(&self) -> String {
+        let key = if self.is_flashing(Instant::now()) {
+            "code-view.copied"
+        } else {
+            "code-view.copy"
+        };
+        format!(" {} ", t!(key))
+    }
+
+    /// Where the copy target is drawn within `region`, if it fits.
+    pub fn copy_target(&self, region: Region) -> Option<Region> {
+        let width = text_width(&self.target_label()) as i32;
+        (region.height > 0 && width <= region.width)
+            .then(|| Region::new(region.x + region.width - width, region.y, width, 1))
+    }
+
+    /// Handle a mouse event for the view at `region`: a left click on the
+    /// copy target copies the block.
+    pub fn handle_mouse(&self, event: &MouseEvent, region: Region) -> Option<CopyToClipboard> {
+        let target = self.copy_target(region)?;
+        let (x, y) = (event.x as i32, event.y as i32);
+        let hit = y == target.y && (target.x..target.x + target.width).contains(&x);
+        (event.kind == MouseAction::Down(MouseBtn::Left) && hit).then(|| self.copy())
+    }
+
+    /// Handle a key while focused: `c` copies the block.
+    pub fn handle_key(&self, key: &KeyEvent) -> Option<CopyToClipboard> {
+        (key.code == Key::Char('c') && key.modifiers == Modifiers::NONE).then(|| self.copy())
+    }
+}
+
+impl Widget for CodeView {
+    fn widget_type(&self) -> &str {
+        "CodeView"
+    }
+
+    fn default_css(&self) -> &str {
+        "CodeView { width: 1fr; height: auto; background: #1e1e1e; }"
+    }
+
+    fn can_focus(&self) -> bool {
+        true
+    }
+
+    fn render(&self, region: Region, styles: &Styles) -> Vec<Strip> {
+        if region.width <= 0 || region.height <= 0 {
+            return Vec::new();
+        }
+        let style = CellStyle::from_styles(styles);
+        let target = self.copy_target(region);
+        let mut lines = self.code.lines();
+        (0..region.height)
+            .map(|row| {
+                let mut strip = Strip::new(region.y + row, region.x);
+                // The first row leaves room for the copy target.
+                let end = match target {
+                    Some(target) if row == 0 => target.x - region.x,
+                    _ => region.width,
+                };
+                if let Some(line) = lines.next() {
+                    let line: String = line.chars().take(end.max(0) as usize).collect();
+                    strip.push_str(&line, style);
+                }
+                strip.fill(end, style);
+                if row == 0 && target.is_some() {
+                    strip.push_str(&self.target_label(), style.with_attrs(Attrs::REVERSE));
+                }
+                strip
+            })
+            .collect()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(view: &CodeView, width: i32, height: i32) -> Vec<String> {
+        view.render(Region::new(0, 0, width, height), &Styles::new())
+            .iter()
+            .map(|strip| strip.cells.iter().map(|c| c.ch).collect())
+            .collect()
+    }
+
+    #[test]
+    fn renders_code_with_copy_target() {
+        let view = CodeView::new("fn main() {\n    run();\n}").with_language("rust");
+        assert_eq!(
+            rows(&view, 20, 3),
+            [
+                "fn main() {    copy ",
+                "    run();          ",
+                "}                   "
+            ]
+        );
+        assert_eq!(view.language(), Some("rust"));
+    }
+
+    #[test]
+    fn click_and_key_copy_then_flash() {
+        let view = CodeView::new("ls -la");
+        let region = Region::new(0, 2, 20, 1);
+        let click = |x| MouseEvent {
+            kind: MouseAction::Down(MouseBtn::Left),
+            x,
+            y: 2,
+            modifiers: Modifiers::NONE,
+        };
+        assert_eq!(view.handle_mouse(&click(3), region), None);
+        assert_eq!(
+            view.handle_mouse(&click(17), region),
+            Some(CopyToClipboard("ls -la".into()))
+        );
+        assert!(view.is_flashing(Instant::now()));
+        assert!(rows(&view, 20, 1)[0].ends_with(" copied ✓ "));
+
+        let key = KeyEvent::new(Key::Char('c'), Modifiers::NONE);
+        assert!(view.handle_key(&key).is_some());
+        let ctrl_c = KeyEvent::new(Key::Char('c'), Modifiers::CTRL);
+        assert!(view.handle_key(&ctrl_c).is_none());
+    }
+
+    #[test]
+    fn flash_expires() {
+        let view = CodeView::new("x").with_flash(Duration::from_millis(10));
+        view.copy();
+        assert!(!view.is_flashing(Instant::now() + Duration::from_millis(20)));
+    }
+}
@@ -0,0 +1,368 @@
+//! CommandPalette widget: a fuzzy-searchable list of app commands.
+//!
+//! Commands are registered with `App::register_command`; the default `Ctrl+P`
+//! binding opens a [`CommandPalette`] over the screen. Typing narrows the list
+//! with [`fuzzy`](crate::widget::fuzzy) matching, best match first, with the
+//! matched characters in bold. Up/Down move the highlight, Enter runs the
+//! highlighted command's action, and Escape or a click outside closes the
+//! palette. While it is open it receives all key and mouse input.
+
+use std::any::Any;
+
+use crate::css::styles::{Styles, TextOverflow};
+use crate::event::binding::{BindingAction, KeyBindingRegistry};
+use crate::event::keys::display_keys;
+use crate::geometry::Region;
+use crate::render::strip::{Attrs, CellStyle, Strip};
+use crate::render::text::{fit, text_width};
+use crate::widget::fuzzy::{rank, FuzzyMatch};
+use crate::widget::traits::Widget;
+
+/// The most results listed at once.
+pub const MAX_RESULTS: usize = 10;
+
+/// The widest the palette gets, in cells.
+const MAX_WIDTH: i32 = 60;
+
+// ---------------------------------------------------------------------------
+// Command
+// ---------------------------------------------------------------------------
+
+/// A named action that can be run from the command palette.
+#[derive(Debug, Clone)]
+pub struct Command {
+    /// Text shown and searched in the palette.
+    pub name: String,
+    pub action: BindingAction,
+    /// Shortcut hint shown dimmed at the right edge, e.g. `Ctrl+S`.
+    pub shortcut: Option<String>,
+}
+
+impl Command {
+    /// Create a command.
+    pub fn new(name: impl Into<String>, action: BindingAction) -> Self {
+        Self {
+            name: name.into(),
+            action,
+            shortcut: None,
+        }
+    }
+
+    /// Fill in the shortcut hint from `bindings` if none is set and the
+    /// action is a custom action with a bound key.
+    pub fn resolve_shortcut(&mut self, bindings: &KeyBindingRegistry) {
+        if self.shortcut.is_none() {
+            if let BindingAction::Custom(name) = &self.action {
+                self.shortcut = bindings.key_for(name).map(|key| display_keys(&[key]));
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// CommandPalette
+// ---------------------------------------------------------------------------
+
+/// An open command palette.
+///
+/// # Examples
+///
+/// ```ignore
+/// app.register_command("Toggle sidebar", BindingAction::Custom("toggle-sidebar".into()));
+/// app.open_command_palette();
+/// ```
+#[derive(Debug, Clone)]
+pub struct CommandPalette {
+    commands: Vec<Command>,
+    query: String,
+    /// Matching command indices, best first, at most [`MAX_RESULTS`].
+    matches: Vec<(usize, FuzzyMatch)>,
+    selected: usize,
+    /// The area the palette is centered in.
+    bounds: Region,
+}
+
+impl CommandPalette {
+    /// Open a palette listing `commands`, centered near the top of `bounds`.
+    pub fn new(commands: Vec<Command>, bounds: Region) -> Self {
+        let mut palette = Self {
+            commands,
+            query: String::new(),
+            matches: Vec::new(),
+            selected: 0,
+            bounds,
+        };
+        palette.refilter();
+        palette
+    }
+
+    /// Where the palette is drawn: the query row plus one row per result (or
+    /// one for "no matches").
+    pub fn region(&self) -> Region {
+        let width = (self.bounds.width - 4).clamp(0, MAX_WIDTH);
+        let height = (1 + self.matches.len().max(1) as i32).min(self.bounds.height);
+        let x = self.bounds.x + (self.bounds.width - width) / 2;
+        let y = self.bounds.y + (self.bounds.height - height).min(1);
+        Region::new(x, y, width, height)
+    }
+
+    /// Every command the palette searches.
+    pub fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+
+    /// The search text typed so far.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Replace the search text.
+    pub fn set_query(&mut self, query: impl Into<String>) {
+        self.query = query.into();
+        self.refilter();
+    }
+
+    /// Append a typed character to the search text.
+    pub fn push_char(&mut self, ch: char) {
+        self.query.push(ch);
+        self.refilter();
+    }
+
+    /// Delete the last character of the search text. Returns whether there
+    /// was one.
+    pub fn pop_char(&mut self) -> bool {
+        let popped = self.query.pop().is_some();
+        if popped {
+            self.refilter();
+        }
+        popped
+    }
+
+    /// The listed results as `(command index, match)`, best first.
+    pub fn matches(&self) -> &[(usize, FuzzyMatch)] {
+        &self.matches
+    }
+
+    /// Index of the highlighted result in [`matches`](Self::matches).
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// The highlighted command, if any command matches.
+    pub fn selected_command(&self) -> Option<&Command> {
+        let &(index, _) = self.matches.get(self.selected)?;
+        self.commands.get(index)
+    }
+
+    /// Highlight the next result, wrapping around.
+    pub fn select_next(&mut self) {
+        if !self.matches.is_empty() {
+            self.selected = (self.selected + 1) % self.matches.len();
+        }
+    }
+
+    /// Highlight the previous result, wrapping around.
+    pub fn select_previous(&mut self) {
+        if !self.matches.is_empty() {
+            self.selected = (self.selected + self.matches.len() - 1) % self.matches.len();
+        }
+    }
+
+    /// Highlight the result at `index`. Returns `false` if out of range.
+    pub fn select(&mut self, index: usize) -> bool {
+        if index < self.matches.len() {
+            self.selected = index;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The result index at screen position `(x, y)`, if on a result row.
+    pub fn result_at(&self, x: i32, y: i32) -> Option<usize> {
+        let region = self.region();
+        if !region.contains(x, y) || y == region.y {
+            return None;
+        }
+        let index = (y - region.y - 1) as usize;
+        (index < self.matches.len()).then_some(index)
+    }
+
+    /// Match the query against every command again and reset the highlight.
+    fn refilter(&mut self) {
+        let names = self.commands.iter().map(|c| c.name.as_str());
+        self.matches = rank(&self.query, names);
+        self.matches.truncate(MAX_RESULTS);
+        self.selected = 0;
+    }
+
+    /// One result row: the name with matched characters in bold, then the
+    /// shortcut hint right-aligned and dimmed.
+    fn render_result(&self, row: usize, region: Region, style: CellStyle) -> Strip {
+        let (index, found) = &self.matches[row];
+        let command = &self.commands[*index];
+        let style = if row == self.selected {
+            style.with_attrs(Attrs::REVERSE)
+        } else {
+            style
+        };
+        let width = (region.width - 2).max(0) as usize;
+        let shortcut = command
+            .shortcut
+            .as_deref()
+            .filter(|s| width > text_width(&command.name) + 1 + text_width(s));
+        let name_width = width - shortcut.map_or(0, |s| text_width(s) + 1);
+        let name = fit(&command.name, name_width, TextOverflow::Ellipsis);
+
+        let mut strip = Strip::new(region.y + 1 + row as i32, region.x);
+        strip.push(' ', style);
+        for (i, ch) in name.chars().enumerate() {
+            let matched = found.positions.contains(&i);
+            let attrs = style.attrs | if matched { Attrs::BOLD } else { Attrs::NONE };
+            strip.push(ch, style.with_attrs(attrs));
+        }
+        if let Some(shortcut) = shortcut {
+            strip.fill(region.width - 1 - text_width(shortcut) as i32, style);
+            strip.push_str(shortcut, style.with_attrs(style.attrs | Attrs::DIM));
+        }
+        strip.fill(region.width, style);
+        strip
+    }
+}
+
+impl Widget for CommandPalette {
+    fn widget_type(&self) -> &str {
+        "CommandPalette"
+    }
+
+    fn default_css(&self) -> &str {
+        "CommandPalette { background: #262626; color: #e0e0e0; }"
+    }
+
+    fn render(&self, region: Region, styles: &Styles) -> Vec<Strip> {
+        if region.width <= 0 || region.height <= 0 {
+            return Vec::new();
+        }
+        let style = CellStyle::from_styles(styles);
+        let dim = style.with_attrs(style.attrs | Attrs::DIM);
+        let width = (region.width - 2).max(0) as usize;
+
+        let mut prompt = Strip::new(region.y, region.x);
+        prompt.push_str("> ", style);
+        if self.query.is_empty() {
+            prompt.push_str(&fit("Search commands", width, TextOverflow::Clip), dim);
+        } else {
+            prompt.push_str(&fit(&self.query, width, TextOverflow::Clip), style);
+        }
+        prompt.fill(region.width, style);
+        let mut strips = vec![prompt];
+
+        let rows = (region.height - 1).max(0) as usize;
+        if self.matches.is_empty() && rows > 0 {
+            let mut strip = Strip::new(region.y + 1, region.x);
+            strip.push(' ', dim);
+            strip.push_str(&fit("No matching commands", width, TextOverflow::Clip), dim);
+            strip.fill(region.width, style);
+            strips.push(strip);
+        }
+        strips.extend(
+            (0..self.matches.len().min(rows)).map(|row| self.render_result(row, region, style)),
+        );
+        strips
+    }
+
+    fn can_focus(&self) -> bool {
+        true
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn palette() -> CommandPalette {
+        let commands = vec![
+            Command::new("Toggle Sidebar", BindingAction::Custom("sidebar".into())),
+            Command::new("Save File", BindingAction::Custom("save".into())),
+            Command::new("Quit", BindingAction::Quit),
+        ];
+        CommandPalette::new(commands, Region::new(0, 0, 40, 20))
+    }
+
+    fn row_text(strip: &Strip) -> String {
+        strip
+            .cells
+            .iter()
+            .map(|c| c.ch)
+            .collect::<String>()
+            .trim_end()
+            .to_owned()
+    }
+
+    #[test]
+    fn typing_filters_and_ranks_commands() {
+        let mut p = palette();
+        assert_eq!(p.matches().len(), 3);
+        assert_eq!(p.region(), Region::new(2, 1, 36, 4));
+
+        p.push_char('s');
+        p.push_char('a');
+        let names: Vec<&str> = p
+            .matches()
+            .iter()
+            .map(|(i, _)| p.commands()[*i].name.as_str())
+            .collect();
+        assert_eq!(names, ["Save File", "Toggle Sidebar"]);
+        p.select_next();
+        assert_eq!(p.selected_command().unwrap().name, "Toggle Sidebar");
+
+        // Editing the query resets the highlight.
+        assert!(p.pop_char());
+        assert_eq!(p.query(), "s");
+        assert_eq!(p.selected(), 0);
+        p.set_query("zzz");
+        assert!(p.selected_command().is_none());
+        assert_eq!(p.region().height, 2);
+    }
+
+    #[test]
+    fn renders_prompt_results_and_shortcuts() {
+        let mut p = palette();
+        let mut bindings = KeyBindingRegistry::new();
+        bindings
+            .bind_str("ctrl+s", BindingAction::Custom("save".into()))
+            .unwrap();
+        for command in &mut p.commands {
+            command.resolve_shortcut(&bindings);
+        }
+        p.set_query("sf");
+        let region = p.region();
+        let strips = p.render(region, &Styles::new());
+        assert_eq!(row_text(&strips[0]), "> sf");
+        let row = &strips[1];
+        assert_eq!(row_text(row), " Save File                   Ctrl+S");
+        assert!(row.cells[0].style.attrs.contains(Attrs::REVERSE));
+        // The matched "S" and "F" are bold, the rest is not.
+        assert!(row.cells[1].style.attrs.contains(Attrs::BOLD));
+        assert!(!row.cells[2].style.attrs.contains(Attrs::BOLD));
+        assert!(row.cells[6].style.attrs.contains(Attrs::BOLD));
+        assert_eq!(p.result_at(region.x + 3, region.y + 1), Some(0));
+        assert_eq!(p.result_at(region.x + 3, region.y), None);
+
+        p.set_query("zzz");
+        let strips = p.render(p.region(), &Styles::new());
+        assert_eq!(row_text(&strips[1]), " No matching commands");
+    }
+}
@@ -9,6 +9,7 @@ use std::any::Any;
 
 use crate::css::styles::Styles;
 use crate::geometry::Region;
+use crate::render::fill::paint_background;
 use crate::render::strip::{CellStyle, Strip};
 use crate::widget::traits::Widget;
 
@@ -32,6 +33,8 @@ use crate::widget::traits::Widget;
 /// ```
 pub struct Container {
     children: Vec<Box<dyn Widget>>,
+    /// One key per child when the children were set with `set_children_keyed`.
+    keys: Option<Vec<String>>,
     id: Option<String>,
     classes: Vec<String>,
 }
@@ -41,6 +44,7 @@ impl Container {
     pub fn new() -> Self {
         Self {
             children: Vec::new(),
+            keys: None,
             id: None,
             classes: Vec::new(),
         }
@@ -63,9 +67,52 @@ impl Container {
     /// Add a child widget (builder pattern).
     pub fn with_child(mut self, child: impl Widget + 'static) -> Self {
         self.children.push(Box::new(child));
+        self.keys = None;
         self
     }
 
+    /// Append already boxed child widgets (builder pattern).
+    pub fn with_children(mut self, children: impl IntoIterator<Item = Box<dyn Widget>>) -> Self {
+        self.children.extend(children);
+        self.keys = None;
+        self
+    }
+
+    /// Replace the children with one widget per item, each tagged with a key.
+    ///
+    /// When the container is next reconciled against the DOM (see
+    /// [`reconcile_node`](crate::widget::reconcile::reconcile_node)), existing
+    /// child nodes are matched by key: matching nodes are reused and moved
+    /// into the new order rather than recreated, so focus and per-node state
+    /// survive a refresh. Keys should be unique among siblings.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// list.set_children_keyed(&users, |u| u.id, |u| Box::new(Static::new(&u.name)));
+    /// let mut tx = Transaction::new();
+    /// reconcile_node(&mut tx, &screen.dom, list_node, &list);
+    /// screen.apply(tx)?;
+    /// ```
+    pub fn set_children_keyed<T, K: ToString>(
+        &mut self,
+        items: impl IntoIterator<Item = T>,
+        key_fn: impl Fn(&T) -> K,
+        build_fn: impl Fn(&T) -> Box<dyn Widget>,
+    ) {
+        let (keys, children) = items
+            .into_iter()
+            .map(|item| (key_fn(&item).to_string(), build_fn(&item)))
+            .unzip();
+        self.keys = Some(keys);
+        self.children = children;
+    }
+
+    /// The child keys, if the children were set with `set_children_keyed`.
+    pub fn keys(&self) -> Option<&[String]> {
+        self.keys.as_deref()
+    }
+
     /// Set the CSS id (builder pattern).
     pub fn with_id(mut self, id: &str) -> Self {
         self.id = Some(id.to_owned());
@@ -90,6 +137,7 @@ impl Container {
     ///
     /// Used by the framework during DOM construction.
     pub fn take_children(&mut self) -> Vec<Box<dyn Widget>> {
+        self.keys = None;
         std::mem::take(&mut self.children)
     }
 
@@ -131,13 +179,31 @@ impl Widget for Container {
 
         // Container renders only background fill strips.
         let style = CellStyle::from_styles(styles);
-        (0..region.height)
+        let mut strips: Vec<Strip> = (0..region.height)
             .map(|row| {
                 let mut strip = Strip::new(region.y + row, region.x);
-                strip.fill(region.width, style.clone());
+                strip.fill(region.width, style);
                 strip
             })
-            .collect()
+            .collect();
+        paint_background(&mut strips, region, styles);
+        strips
+    }
+
+    fn css_id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    fn css_classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    fn child_widgets(&self) -> &[Box<dyn Widget>] {
+        &self.children
+    }
+
+    fn child_keys(&self) -> Option<&[String]> {
+        self.keys.as_deref()
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -156,6 +222,7 @@ impl Widget for Container {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::render::strip::Color;
     use crate::widgets::static_widget::Static;
 
     fn region(w: i32, h: i32) -> Region {
@@ -250,11 +317,21 @@ mod tests {
             assert_eq!(strip.width(), 5);
             for cell in &strip.cells {
                 assert_eq!(cell.ch, ' ');
-                assert_eq!(cell.style.bg, Some("blue".into()));
+                assert_eq!(cell.style.bg, Some(Color::Blue));
             }
         }
     }
 
+    #[test]
+    fn render_paints_patterned_background() {
+        let c = Container::new();
+        let mut s = styles();
+        s.background = Some("stripes(red, blue)".into());
+        let strips = c.render(region(2, 3), &s);
+        let column: Vec<_> = strips.iter().map(|strip| strip.cells[1].style.bg).collect();
+        assert_eq!(column, [Some(Color::Red), Some(Color::Blue), Some(Color::Red)]);
+    }
+
     #[test]
     fn render_zero_region() {
         let c = Container::new();
@@ -296,4 +373,16 @@ mod tests {
         assert!(c.id().is_none());
         assert!(c.classes().is_empty());
     }
+
+    #[test]
+    fn set_children_keyed_records_keys() {
+        let mut c = Container::new().with_child(Static::new("old"));
+        c.set_children_keyed([3, 1, 2], |n| n * 10, |n| Box::new(Static::new(n.to_string())));
+        assert_eq!(c.child_count(), 3);
+        assert_eq!(c.keys().unwrap(), ["30", "10", "20"]);
+        assert_eq!(c.child_keys().unwrap().len(), c.child_widgets().len());
+
+        let c = c.with_child(Static::new("unkeyed"));
+        assert!(c.keys().is_none());
+    }
 }
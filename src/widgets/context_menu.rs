@@ -0,0 +1,408 @@
+//! ContextMenu widget: a positioned right-click menu overlay.
+//!
+//! Menu items are declared per node with `Screen::set_context_menu`. On a
+//! right-click the app opens a [`ContextMenu`] at the pointer, routes keyboard
+//! navigation to it while it is open, and dispatches the chosen item's message
+//! targeted at the node the menu was opened for.
+//!
+//! Items can show a keyboard shortcut hint right-aligned after the label,
+//! either given directly or looked up from the app's key bindings when the
+//! menu opens.
+
+use std::any::Any;
+
+use crate::css::styles::{Styles, TextOverflow};
+use crate::dom::node::NodeId;
+use crate::event::binding::KeyBindingRegistry;
+use crate::event::keys::display_keys;
+use crate::event::message::Message;
+use crate::geometry::Region;
+use crate::render::strip::{Attrs, CellStyle, Strip};
+use crate::render::text::{fit, text_width};
+use crate::widget::traits::Widget;
+
+// ---------------------------------------------------------------------------
+// MenuItem
+// ---------------------------------------------------------------------------
+
+/// A single context menu entry: a label and the message it produces.
+#[derive(Clone)]
+pub struct MenuItem {
+    /// Text shown in the menu.
+    pub label: String,
+    /// Factory for the message dispatched when the item is chosen.
+    pub action: fn() -> Box<dyn Message>,
+    /// Shortcut hint shown dimmed at the right edge, e.g. `Ctrl+S`.
+    pub shortcut: Option<String>,
+    /// Name of the custom binding action this item mirrors; its bound key
+    /// becomes the shortcut hint unless one is set explicitly.
+    pub action_name: Option<String>,
+}
+
+impl MenuItem {
+    /// Create a menu item.
+    pub fn new(label: impl Into<String>, action: fn() -> Box<dyn Message>) -> Self {
+        Self {
+            label: label.into(),
+            action,
+            shortcut: None,
+            action_name: None,
+        }
+    }
+
+    /// Show a shortcut hint (builder pattern).
+    pub fn with_shortcut(mut self, shortcut: impl Into<String>) -> Self {
+        self.shortcut = Some(shortcut.into());
+        self
+    }
+
+    /// Take the shortcut hint from the key bound to the custom action `name`
+    /// (builder pattern).
+    pub fn with_action_name(mut self, name: impl Into<String>) -> Self {
+        self.action_name = Some(name.into());
+        self
+    }
+
+    /// Fill in the shortcut hint from `bindings` if none is set and the
+    /// item's action name has a bound key.
+    pub fn resolve_shortcut(&mut self, bindings: &KeyBindingRegistry) {
+        if self.shortcut.is_none() {
+            self.shortcut = self
+                .action_name
+                .as_deref()
+                .and_then(|name| bindings.key_for(name))
+                .map(|key| display_keys(&[key]));
+        }
+    }
+}
+
+/// Minimum blank cells between a label and its shortcut hint.
+const SHORTCUT_GAP: usize = 3;
+
+/// Width of the right-aligned shortcut column: the widest hint.
+fn shortcut_column(items: &[MenuItem]) -> usize {
+    items
+        .iter()
+        .filter_map(|item| item.shortcut.as_deref())
+        .map(text_width)
+        .max()
+        .unwrap_or(0)
+}
+
+impl std::fmt::Debug for MenuItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MenuItem")
+            .field("label", &self.label)
+            .field("shortcut", &self.shortcut)
+            .field("action_name", &self.action_name)
+            .finish_non_exhaustive()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// ContextMenu
+// ---------------------------------------------------------------------------
+
+/// An open context menu.
+///
+/// # Examples
+///
+/// ```ignore
+/// app.screen.set_context_menu(file_row, vec![
+///     MenuItem::new("Open", || Box::new(Custom::new("open"))),
+///     MenuItem::new("Delete", || Box::new(Custom::new("delete"))),
+/// ]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ContextMenu {
+    items: Vec<MenuItem>,
+    origin: NodeId,
+    region: Region,
+    selected: usize,
+}
+
+impl ContextMenu {
+    /// Open a menu for `origin` with its top-left corner at `(x, y)`.
+    ///
+    /// The menu is shifted left/up as needed to fit inside `bounds`.
+    pub fn new(origin: NodeId, items: Vec<MenuItem>, x: i32, y: i32, bounds: Region) -> Self {
+        let label_width = items
+            .iter()
+            .map(|item| text_width(&item.label))
+            .max()
+            .unwrap_or(0);
+        let content_width = match shortcut_column(&items) {
+            0 => label_width,
+            shortcuts => label_width + SHORTCUT_GAP + shortcuts,
+        };
+        let width = (content_width as i32 + 2).min(bounds.width);
+        let height = (items.len() as i32).min(bounds.height);
+        let x = x.min(bounds.right() - width).max(bounds.x);
+        let y = y.min(bounds.bottom() - height).max(bounds.y);
+        Self {
+            items,
+            origin,
+            region: Region::new(x, y, width, height),
+            selected: 0,
+        }
+    }
+
+    /// The node the menu was opened for.
+    pub fn origin(&self) -> NodeId {
+        self.origin
+    }
+
+    /// Where the menu is drawn.
+    pub fn region(&self) -> Region {
+        self.region
+    }
+
+    /// The menu entries.
+    pub fn items(&self) -> &[MenuItem] {
+        &self.items
+    }
+
+    /// Index of the highlighted item.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// The highlighted item, if the menu is not empty.
+    pub fn selected_item(&self) -> Option<&MenuItem> {
+        self.items.get(self.selected)
+    }
+
+    /// Highlight the next item, wrapping around.
+    pub fn select_next(&mut self) {
+        if !self.items.is_empty() {
+            self.selected = (self.selected + 1) % self.items.len();
+        }
+    }
+
+    /// Highlight the previous item, wrapping around.
+    pub fn select_previous(&mut self) {
+        if !self.items.is_empty() {
+            self.selected = (self.selected + self.items.len() - 1) % self.items.len();
+        }
+    }
+
+    /// The item index at screen position `(x, y)`, if inside the menu.
+    pub fn item_at(&self, x: i32, y: i32) -> Option<usize> {
+        if self.region.contains(x, y) {
+            Some((y - self.region.y) as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Highlight the item at `index`. Returns `false` if out of range.
+    pub fn select(&mut self, index: usize) -> bool {
+        if index < self.items.len() {
+            self.selected = index;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl Widget for ContextMenu {
+    fn widget_type(&self) -> &str {
+        "ContextMenu"
+    }
+
+    fn default_css(&self) -> &str {
+        "ContextMenu { background: #303030; color: #e0e0e0; }"
+    }
+
+    fn render(&self, region: Region, styles: &Styles) -> Vec<Strip> {
+        if region.width <= 0 || region.height <= 0 {
+            return Vec::new();
+        }
+
+        let style = CellStyle::from_styles(styles);
+        let highlight = style.with_attrs(Attrs::REVERSE);
+        let width = (region.width - 2).max(0) as usize;
+        let shortcut_width = shortcut_column(&self.items);
+
+        self.items
+            .iter()
+            .take(region.height as usize)
+            .enumerate()
+            .map(|(row, item)| {
+                let style = if row == self.selected {
+                    &highlight
+                } else {
+                    &style
+                };
+                let mut strip = Strip::new(region.y + row as i32, region.x);
+                strip.push(' ', *style);
+                // Shortcuts are right-aligned in a shared column and dropped
+                // when the menu is too narrow to keep a gap after the label.
+                let shortcut = item
+                    .shortcut
+                    .as_deref()
+                    .filter(|_| width > shortcut_width + SHORTCUT_GAP);
+                match shortcut {
+                    Some(shortcut) => {
+                        let label_width = width - shortcut_width - SHORTCUT_GAP;
+                        strip.push_str(&fit(&item.label, label_width, TextOverflow::Clip), *style);
+                        strip.fill(
+                            (region.width - 1 - text_width(shortcut) as i32).max(0),
+                            *style,
+                        );
+                        strip.push_str(shortcut, style.with_attrs(Attrs::DIM));
+                    }
+                    None => strip.push_str(&fit(&item.label, width, TextOverflow::Clip), *style),
+                }
+                strip.fill(region.width, *style);
+                strip
+            })
+            .collect()
+    }
+
+    fn can_focus(&self) -> bool {
+        true
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dom::node::NodeData;
+    use crate::dom::tree::Dom;
+    use crate::event::message::Custom;
+
+    fn menu(x: i32, y: i32) -> ContextMenu {
+        let mut dom = Dom::new();
+        let node = dom.insert(NodeData::new("Static"));
+        let items = vec![
+            MenuItem::new("Open", || Box::new(Custom::new("open"))),
+            MenuItem::new("Rename", || Box::new(Custom::new("rename"))),
+            MenuItem::new("Delete", || Box::new(Custom::new("delete"))),
+        ];
+        ContextMenu::new(node, items, x, y, Region::new(0, 0, 40, 10))
+    }
+
+    #[test]
+    fn sized_to_longest_label() {
+        let m = menu(5, 2);
+        assert_eq!(m.region(), Region::new(5, 2, 8, 3));
+    }
+
+    #[test]
+    fn clamped_inside_bounds() {
+        let m = menu(38, 9);
+        assert_eq!(m.region(), Region::new(32, 7, 8, 3));
+    }
+
+    #[test]
+    fn navigation_wraps() {
+        let mut m = menu(0, 0);
+        m.select_previous();
+        assert_eq!(m.selected(), 2);
+        m.select_next();
+        assert_eq!(m.selected(), 0);
+        assert_eq!(m.selected_item().unwrap().label, "Open");
+    }
+
+    #[test]
+    fn item_at_maps_rows() {
+        let m = menu(5, 2);
+        assert_eq!(m.item_at(6, 3), Some(1));
+        assert_eq!(m.item_at(4, 3), None);
+        assert_eq!(m.item_at(6, 5), None);
+    }
+
+    #[test]
+    fn render_highlights_selection() {
+        let mut m = menu(0, 0);
+        m.select_next();
+        let strips = m.render(m.region(), &Styles::new());
+        assert_eq!(strips.len(), 3);
+        assert!(!strips[0].cells[0].style.reverse());
+        assert!(strips[1].cells[0].style.reverse());
+        assert_eq!(strips[1].cells[1].ch, 'R');
+        assert_eq!(strips[1].width(), 8);
+    }
+
+    // ── Shortcut hints ───────────────────────────────────────────────
+
+    fn shortcut_menu(bindings: &KeyBindingRegistry) -> ContextMenu {
+        let mut dom = Dom::new();
+        let node = dom.insert(NodeData::new("Static"));
+        let mut items = vec![
+            MenuItem::new("Save", || Box::new(Custom::new("save"))).with_action_name("save"),
+            MenuItem::new("Close", || Box::new(Custom::new("close"))).with_shortcut("Esc"),
+            MenuItem::new("Rename", || Box::new(Custom::new("rename"))),
+        ];
+        for item in &mut items {
+            item.resolve_shortcut(bindings);
+        }
+        ContextMenu::new(node, items, 0, 0, Region::new(0, 0, 40, 10))
+    }
+
+    fn rows(m: &ContextMenu) -> Vec<String> {
+        m.render(m.region(), &Styles::new())
+            .iter()
+            .map(|strip| strip.cells.iter().map(|c| c.ch).collect())
+            .collect()
+    }
+
+    #[test]
+    fn shortcuts_resolve_from_bindings() {
+        let mut bindings = KeyBindingRegistry::new();
+        bindings.bind(
+            crate::event::input::Key::Char('s'),
+            crate::event::input::Modifiers::CTRL,
+            crate::event::binding::BindingAction::Custom("save".into()),
+        );
+        let m = shortcut_menu(&bindings);
+        assert_eq!(m.items()[0].shortcut.as_deref(), Some("Ctrl+S"));
+        assert_eq!(m.items()[1].shortcut.as_deref(), Some("Esc"));
+        assert_eq!(m.items()[2].shortcut, None);
+        assert_eq!(m.region().width, 17);
+        assert_eq!(
+            rows(&m),
+            [
+                " Save     Ctrl+S ",
+                " Close       Esc ",
+                " Rename          "
+            ]
+        );
+    }
+
+    #[test]
+    fn shortcuts_render_dimmed() {
+        let m = shortcut_menu(&KeyBindingRegistry::new());
+        let strips = m.render(m.region(), &Styles::new());
+        assert_eq!(m.items()[0].shortcut, None);
+        let dim: String = strips[1]
+            .cells
+            .iter()
+            .filter(|c| c.style.dim())
+            .map(|c| c.ch)
+            .collect();
+        assert_eq!(dim, "Esc");
+    }
+
+    #[test]
+    fn shortcuts_dropped_when_narrow() {
+        let m = shortcut_menu(&KeyBindingRegistry::new());
+        let strips = m.render(Region::new(0, 0, 8, 3), &Styles::new());
+        let row: String = strips[1].cells.iter().map(|c| c.ch).collect();
+        assert_eq!(row, " Close  ");
+    }
+}
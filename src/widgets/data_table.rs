@@ -0,0 +1,1126 @@
+//! DataTable widget: a header row over rows of text cells.
+//!
+//! Columns can be resized by dragging the `│` separator to the right of a
+//! header and reordered by dragging a header onto another column. Keyboard
+//! users get the same operations: Left/Right pick a column, Ctrl+Left/Right
+//! resize it, Alt+Left/Right move it. Every completed change yields a
+//! [`ColumnsChanged`] message carrying the new [`ColumnLayout`], which an app
+//! can store and later hand back to [`DataTable::restore_layout`]. A
+//! [`Redactor`] set with [`DataTable::with_redactor`] masks secrets in cells.
+//!
+//! Columns align their text with [`Column::with_align`].
+//! [`DataTable::with_formatter`] turns a column's raw values into styled
+//! [`Span`]s and [`DataTable::with_row_style`] restyles whole rows, so
+//! numeric dashboards can keep raw values in the table and style them at
+//! render time.
+//!
+//! Rows are selected with the shared [`SelectionModel`] conventions through
+//! [`DataTable::handle_row_key`] and [`DataTable::handle_row_click`].
+//!
+//! Rows come from a [`DataSource`] of cell vectors. [`DataTable::new`] keeps
+//! them in memory (a `Vec<Vec<String>>`, filled with
+//! [`DataTable::add_row`]); [`DataTable::from_source`] reads any other source
+//! a page at a time, like `ListView`, drawing a dimmed placeholder for rows
+//! that are still loading.
+//!
+//! The table is [`Filterable`]: a `FilterBar` narrows it to the rows whose
+//! displayed cells match its query, after formatting and redaction, so
+//! masked secrets can't be found by searching. Rows from a remote source
+//! can only match once fetched.
+
+use std::any::Any;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::css::styles::{Styles, TextAlign};
+use crate::event::input::{Key, KeyEvent, Modifiers, MouseAction, MouseBtn, MouseEvent};
+use crate::event::message::Message;
+use crate::geometry::Region;
+use crate::render::strip::{Attrs, CellStyle, Strip};
+use crate::render::text::text_width;
+use crate::t;
+use crate::widget::data_source::{DataSource, PagedCache};
+use crate::widget::filter::{FilterMatch, Filterable};
+use crate::widget::redact::Redactor;
+use crate::widget::selection::{Selected, SelectionMode, SelectionModel};
+use crate::widget::traits::Widget;
+use crate::widgets::status_bar::Span;
+
+/// Joins a row's cells into the text a filter searches. No query can
+/// contain it, so matches never span two cells.
+const CELL_BREAK: char = '\n';
+
+/// Narrowest width a column can be resized to.
+pub const MIN_COLUMN_WIDTH: u16 = 3;
+
+/// Turns a raw cell value into the text and style to display.
+type Formatter = Box<dyn Fn(&str) -> Span>;
+
+/// Restyles a row from its raw cells, starting from the table's style.
+type RowStyle = Box<dyn Fn(&[String], CellStyle) -> CellStyle>;
+
+/// The in-memory rows behind [`DataTable::new`].
+type LocalRows = Vec<Vec<String>>;
+
+/// A row source the table can downcast, to reach [`LocalRows`] directly.
+trait RowSource: DataSource<Item = Vec<String>> {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<S: DataSource<Item = Vec<String>> + 'static> RowSource for S {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Column / ColumnLayout
+// ---------------------------------------------------------------------------
+
+/// A table column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Column {
+    /// Stable identifier used when saving and restoring layouts.
+    pub key: String,
+    /// Header text.
+    pub label: String,
+    /// Width in cells, excluding the separator.
+    pub width: u16,
+    /// How the header and cells are aligned within the width.
+    pub align: TextAlign,
+}
+
+impl Column {
+    /// Create a left-aligned column.
+    pub fn new(key: impl Into<String>, label: impl Into<String>, width: u16) -> Self {
+        Self {
+            key: key.into(),
+            label: label.into(),
+            width: width.max(MIN_COLUMN_WIDTH),
+            align: TextAlign::Left,
+        }
+    }
+
+    /// Set the alignment (builder).
+    pub fn with_align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self
+    }
+}
+
+/// Column order and widths, as `(key, width)` pairs in display order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnLayout {
+    /// Columns in display order.
+    pub columns: Vec<(String, u16)>,
+}
+
+/// Emitted when the user finishes resizing or reordering columns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnsChanged(pub ColumnLayout);
+
+impl Message for ColumnsChanged {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn message_name(&self) -> &str {
+        "ColumnsChanged"
+    }
+}
+
+// ---------------------------------------------------------------------------
+// DataTable
+// ---------------------------------------------------------------------------
+
+/// An in-progress mouse drag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Drag {
+    /// Dragging the separator after display position `pos`.
+    Resize {
+        pos: usize,
+        start_x: i32,
+        start_width: u16,
+    },
+    /// Dragging the header at display position `pos`.
+    Move { pos: usize },
+}
+
+/// A table of text cells with resizable, reorderable columns.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut table = DataTable::new(vec![
+///     Column::new("name", "Name", 12),
+///     Column::new("size", "Size", 8),
+/// ]);
+/// table.add_row(vec!["Cargo.toml".into(), "1.2K".into()]);
+/// if let Some(layout) = settings.get("files-table") {
+///     table.restore_layout(layout);
+/// }
+/// ```
+pub struct DataTable {
+    columns: Vec<Column>,
+    /// Display order, as indices into `columns`.
+    order: Vec<usize>,
+    /// Cells per row, indexed like `columns`.
+    source: Box<dyn RowSource>,
+    /// Pages fetched from `source`, unless it holds [`LocalRows`].
+    cache: PagedCache<Vec<String>>,
+    /// Position of the first visible row among the shown rows.
+    offset: usize,
+    /// Number of visible body rows.
+    viewport: usize,
+    /// The rows shown while a filter is active, in ascending index order.
+    filter: Option<Vec<FilterMatch>>,
+    focused_column: usize,
+    drag: Option<Drag>,
+    /// Layout when the current drag started, to detect no-op drags.
+    drag_origin: ColumnLayout,
+    /// Masks secrets in cells, if set.
+    redactor: Option<Redactor>,
+    /// Cell formatters, by column key.
+    formatters: HashMap<String, Formatter>,
+    /// Per-row restyling, if set.
+    row_style: Option<RowStyle>,
+    /// Selected rows.
+    selection: SelectionModel,
+}
+
+impl DataTable {
+    /// Create a table with the given columns and no rows, kept in memory.
+    pub fn new(columns: Vec<Column>) -> Self {
+        Self::from_source(columns, LocalRows::new())
+    }
+
+    /// Create a table whose rows are fetched from `source`, 100 rows per
+    /// page, once [`DataTable::set_viewport`] says how many are visible.
+    /// Cells follow the order columns were declared in.
+    pub fn from_source(
+        columns: Vec<Column>,
+        source: impl DataSource<Item = Vec<String>> + 'static,
+    ) -> Self {
+        let order = (0..columns.len()).collect();
+        let mut selection = SelectionModel::new(SelectionMode::Single);
+        selection.set_len(source.total());
+        Self {
+            columns,
+            order,
+            source: Box::new(source),
+            cache: PagedCache::new(100),
+            offset: 0,
+            viewport: 0,
+            filter: None,
+            focused_column: 0,
+            drag: None,
+            drag_origin: ColumnLayout::default(),
+            redactor: None,
+            formatters: HashMap::new(),
+            row_style: None,
+            selection,
+        }
+    }
+
+    /// Append a row; cells follow the order columns were declared in.
+    /// Returns `false`, adding nothing, if the table reads its rows from a
+    /// [`DataSource`] other than memory.
+    pub fn add_row(&mut self, cells: Vec<String>) -> bool {
+        let Some(rows) = self.source.as_any_mut().downcast_mut::<LocalRows>() else {
+            return false;
+        };
+        rows.push(cells);
+        let len = rows.len();
+        self.selection.set_len(len);
+        true
+    }
+
+    /// Add a row (builder).
+    pub fn with_row(mut self, cells: Vec<String>) -> Self {
+        self.add_row(cells);
+        self
+    }
+
+    /// Allow one or many selected rows (builder). Defaults to one.
+    pub fn with_selection_mode(mut self, mode: SelectionMode) -> Self {
+        self.selection.set_mode(mode);
+        self
+    }
+
+    /// Set the page size used when fetching (builder).
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.cache = PagedCache::new(page_size);
+        self
+    }
+
+    /// Set how many pages to fetch beyond the viewport (builder).
+    ///
+    /// Apply after [`DataTable::with_page_size`], which resets it.
+    pub fn with_prefetch(mut self, pages: usize) -> Self {
+        self.cache = self.cache.with_prefetch(pages);
+        self
+    }
+
+    /// Mask secrets in cells with `redactor` (builder).
+    pub fn with_redactor(mut self, redactor: Redactor) -> Self {
+        self.redactor = Some(redactor);
+        self
+    }
+
+    /// Display the cells of column `key` through `formatter`, e.g. to add
+    /// units or color negative numbers (builder). A span without a style
+    /// keeps the row's style.
+    pub fn with_formatter(
+        mut self,
+        key: impl Into<String>,
+        formatter: impl Fn(&str) -> Span + 'static,
+    ) -> Self {
+        self.formatters.insert(key.into(), Box::new(formatter));
+        self
+    }
+
+    /// Style each row from its raw cells (builder). `style` gets the cells
+    /// in declared column order and the table's style, and returns the
+    /// row's style.
+    pub fn with_row_style(
+        mut self,
+        style: impl Fn(&[String], CellStyle) -> CellStyle + 'static,
+    ) -> Self {
+        self.row_style = Some(Box::new(style));
+        self
+    }
+
+    /// The text and style of the cell at (`row`, declared column `col`),
+    /// after formatting and redaction.
+    fn cell<'a>(
+        &self,
+        row: &'a [String],
+        col: usize,
+        row_style: CellStyle,
+    ) -> (Cow<'a, str>, CellStyle) {
+        let raw = row.get(col).map_or("", String::as_str);
+        let (text, style) = match self.formatters.get(&self.columns[col].key) {
+            Some(format) => {
+                let span = format(raw);
+                (Cow::Owned(span.text), span.style.unwrap_or(row_style))
+            }
+            None => (Cow::Borrowed(raw), row_style),
+        };
+        let text = match &self.redactor {
+            Some(redactor) => Cow::Owned(redactor.redact(&text).into_owned()),
+            None => text,
+        };
+        (text, style)
+    }
+
+    /// The redactor applied to cells, e.g. to toggle revealing secrets.
+    pub fn redactor_mut(&mut self) -> Option<&mut Redactor> {
+        self.redactor.as_mut()
+    }
+
+    /// Number of rows.
+    pub fn row_count(&self) -> usize {
+        self.source.total()
+    }
+
+    /// The cells of row `index`, if loaded.
+    pub fn row(&self, index: usize) -> Option<&[String]> {
+        match self.source.as_any().downcast_ref::<LocalRows>() {
+            Some(rows) => rows.get(index),
+            None => self.cache.get(index),
+        }
+        .map(Vec::as_slice)
+    }
+
+    /// Mutable access to the data source.
+    pub fn source_mut(&mut self) -> &mut dyn DataSource<Item = Vec<String>> {
+        self.source.as_mut()
+    }
+
+    /// Position of the first visible row among the shown rows.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Number of rows shown: the filter's matches, or every row.
+    fn shown(&self) -> usize {
+        self.filter.as_ref().map_or(self.row_count(), Vec::len)
+    }
+
+    /// Source index of the shown row at `pos`.
+    fn shown_index(&self, pos: usize) -> usize {
+        self.filter
+            .as_ref()
+            .map_or(pos, |matches| matches[pos].index)
+    }
+
+    /// Position of row `index` among the shown rows, if it is shown.
+    fn shown_pos(&self, index: usize) -> Option<usize> {
+        match &self.filter {
+            Some(matches) => matches.binary_search_by_key(&index, |m| m.index).ok(),
+            None => Some(index),
+        }
+    }
+
+    /// Fetch whatever the current window needs. Memory-backed rows are read
+    /// in place and never cached.
+    fn request_window(&mut self) {
+        if self.source.as_any().is::<LocalRows>() {
+            return;
+        }
+        let end = self.offset + self.viewport.max(1);
+        if self.filter.is_none() {
+            self.cache.request(self.source.as_mut(), self.offset..end);
+            return;
+        }
+        for pos in self.offset..end.min(self.shown()) {
+            let index = self.shown_index(pos);
+            self.cache.request(self.source.as_mut(), index..index + 1);
+        }
+    }
+
+    /// Set the number of visible body rows (the table's height minus the
+    /// header) and fetch the window.
+    pub fn set_viewport(&mut self, rows: usize) {
+        self.viewport = rows;
+        self.request_window();
+    }
+
+    /// Scroll so the shown row at `row` is the first visible one (clamped).
+    pub fn scroll_to(&mut self, row: usize) {
+        let last = self.shown().saturating_sub(self.viewport.max(1));
+        self.offset = row.min(last);
+        self.request_window();
+    }
+
+    /// Scroll so the cursor row is visible, once the viewport is known and
+    /// if the row is shown.
+    fn reveal_cursor(&mut self) {
+        if self.viewport == 0 {
+            return;
+        }
+        let Some(cursor) = self.shown_pos(self.selection.cursor()) else {
+            return;
+        };
+        let height = self.viewport;
+        if cursor < self.offset {
+            self.scroll_to(cursor);
+        } else if cursor >= self.offset + height {
+            self.scroll_to(cursor + 1 - height);
+        }
+    }
+
+    /// Deliver rows for a pending fetch that started at `start`.
+    pub fn deliver(&mut self, start: usize, rows: Vec<Vec<String>>) -> bool {
+        self.cache.fill(start, rows)
+    }
+
+    /// Discard cached rows and re-read the source (e.g. after its total
+    /// changed).
+    pub fn refresh(&mut self) {
+        self.cache.clear();
+        self.filter = None;
+        self.selection.set_len(self.row_count());
+        self.scroll_to(self.offset);
+    }
+
+    /// The row selection.
+    pub fn selection(&self) -> &SelectionModel {
+        &self.selection
+    }
+
+    /// Select the shown row `delta` rows away from the cursor (clamped).
+    fn step(&mut self, delta: isize) -> Option<Selected> {
+        let last = self.shown().checked_sub(1)?;
+        let pos = match self.shown_pos(self.selection.cursor()) {
+            Some(pos) => pos.saturating_add_signed(delta).min(last),
+            None => 0,
+        };
+        let selected = self.selection.select(self.shown_index(pos));
+        self.reveal_cursor();
+        selected
+    }
+
+    /// Handle the row selection keys (see [`SelectionModel::handle_key`]),
+    /// scrolling the cursor into view.
+    ///
+    /// While a filter is active, the arrow, page and Home/End keys move
+    /// between the shown rows and select the row they land on.
+    pub fn handle_row_key(&mut self, key: &KeyEvent) -> Option<Selected> {
+        if self.filter.is_some() {
+            let page = self.viewport.max(1) as isize;
+            let delta = match key.code {
+                Key::Up => Some(-1),
+                Key::Down => Some(1),
+                Key::PageUp => Some(-page),
+                Key::PageDown => Some(page),
+                Key::Home => Some(isize::MIN),
+                Key::End => Some(isize::MAX),
+                _ => None,
+            };
+            if let Some(delta) = delta {
+                return self.step(delta);
+            }
+        }
+        if self.viewport > 0 {
+            self.selection.set_page(self.viewport);
+        }
+        let selected = self.selection.handle_key(key);
+        self.reveal_cursor();
+        selected
+    }
+
+    /// Handle a click on a body row of the table at `region`, selecting it
+    /// (toggling with Ctrl, or selecting a range with Shift).
+    pub fn handle_row_click(&mut self, event: &MouseEvent, region: Region) -> Option<Selected> {
+        let (x, y) = (event.x as i32, event.y as i32);
+        if event.kind != MouseAction::Down(MouseBtn::Left) || !region.contains(x, y) {
+            return None;
+        }
+        let pos = self.offset + usize::try_from(y - region.y - 1).ok()?;
+        if pos >= self.shown() {
+            return None;
+        }
+        self.selection.click(self.shown_index(pos), event.modifiers)
+    }
+
+    /// Columns in display order.
+    pub fn columns(&self) -> Vec<&Column> {
+        self.order.iter().map(|&i| &self.columns[i]).collect()
+    }
+
+    /// Display position of the keyboard-selected column.
+    pub fn focused_column(&self) -> usize {
+        self.focused_column
+    }
+
+    /// The current order and widths.
+    pub fn layout(&self) -> ColumnLayout {
+        ColumnLayout {
+            columns: self
+                .columns()
+                .into_iter()
+                .map(|c| (c.key.clone(), c.width))
+                .collect(),
+        }
+    }
+
+    /// Apply a saved layout. Unknown keys are ignored, and columns missing
+    /// from the layout keep their relative order after the known ones.
+    pub fn restore_layout(&mut self, layout: &ColumnLayout) {
+        let mut order = Vec::with_capacity(self.columns.len());
+        for (key, width) in &layout.columns {
+            if let Some(i) = self.columns.iter().position(|c| &c.key == key) {
+                if !order.contains(&i) {
+                    self.columns[i].width = (*width).max(MIN_COLUMN_WIDTH);
+                    order.push(i);
+                }
+            }
+        }
+        for i in self.order.clone() {
+            if !order.contains(&i) {
+                order.push(i);
+            }
+        }
+        self.order = order;
+    }
+
+    /// Set the width of the column at display position `pos`.
+    ///
+    /// Returns the resulting change, or `None` if nothing changed.
+    pub fn resize_column(&mut self, pos: usize, width: u16) -> Option<ColumnsChanged> {
+        let column = &mut self.columns[*self.order.get(pos)?];
+        let width = width.max(MIN_COLUMN_WIDTH);
+        if column.width == width {
+            return None;
+        }
+        column.width = width;
+        Some(ColumnsChanged(self.layout()))
+    }
+
+    /// Move the column at display position `from` to position `to`.
+    ///
+    /// Returns the resulting change, or `None` if nothing changed.
+    pub fn move_column(&mut self, from: usize, to: usize) -> Option<ColumnsChanged> {
+        if from == to || from >= self.order.len() || to >= self.order.len() {
+            return None;
+        }
+        let column = self.order.remove(from);
+        self.order.insert(to, column);
+        if self.focused_column == from {
+            self.focused_column = to;
+        }
+        Some(ColumnsChanged(self.layout()))
+    }
+
+    /// Left edge of each column and the x of its trailing separator,
+    /// relative to the table.
+    fn column_spans(&self) -> Vec<(i32, i32)> {
+        let mut x = 0;
+        self.columns()
+            .into_iter()
+            .map(|c| {
+                let span = (x, x + c.width as i32);
+                x += c.width as i32 + 1;
+                span
+            })
+            .collect()
+    }
+
+    /// Handle a mouse event inside the table at `region`.
+    ///
+    /// Returns a change once a drag completes.
+    pub fn handle_mouse(&mut self, event: &MouseEvent, region: Region) -> Option<ColumnsChanged> {
+        let x = event.x as i32 - region.x;
+        let on_header = event.y as i32 == region.y;
+        let spans = self.column_spans();
+
+        match (event.kind, self.drag) {
+            (MouseAction::Down(MouseBtn::Left), _) if on_header => {
+                self.drag_origin = self.layout();
+                if let Some(pos) = spans.iter().position(|&(_, sep)| sep == x) {
+                    self.drag = Some(Drag::Resize {
+                        pos,
+                        start_x: x,
+                        start_width: self.columns[self.order[pos]].width,
+                    });
+                } else if let Some(pos) = spans.iter().position(|&(l, sep)| (l..sep).contains(&x)) {
+                    self.drag = Some(Drag::Move { pos });
+                    self.focused_column = pos;
+                }
+                None
+            }
+            (
+                MouseAction::Drag(MouseBtn::Left),
+                Some(Drag::Resize {
+                    pos,
+                    start_x,
+                    start_width,
+                }),
+            ) => {
+                let width = (start_width as i32 + x - start_x).clamp(0, u16::MAX as i32) as u16;
+                self.columns[self.order[pos]].width = width.max(MIN_COLUMN_WIDTH);
+                None
+            }
+            (MouseAction::Drag(MouseBtn::Left), Some(Drag::Move { pos })) => {
+                // Reorder live as the header passes over other columns.
+                if let Some(target) = spans.iter().position(|&(l, sep)| (l..=sep).contains(&x)) {
+                    if self.move_column(pos, target).is_some() {
+                        self.drag = Some(Drag::Move { pos: target });
+                    }
+                }
+                None
+            }
+            (MouseAction::Up(MouseBtn::Left), Some(_)) => {
+                self.drag = None;
+                let layout = self.layout();
+                (layout != self.drag_origin).then_some(ColumnsChanged(layout))
+            }
+            _ => None,
+        }
+    }
+
+    /// Keyboard fallback: Left/Right select a column, Ctrl+Left/Right resize
+    /// it, Alt+Left/Right move it.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> Option<ColumnsChanged> {
+        let delta: i32 = match key.code {
+            Key::Left => -1,
+            Key::Right => 1,
+            _ => return None,
+        };
+        let pos = self.focused_column;
+        if key.modifiers.contains(Modifiers::CTRL) {
+            let width = self.columns.get(*self.order.get(pos)?)?.width as i32 + delta;
+            self.resize_column(pos, width.max(0) as u16)
+        } else if key.modifiers.contains(Modifiers::ALT) {
+            let to = pos as i32 + delta;
+            if to < 0 {
+                return None;
+            }
+            self.move_column(pos, to as usize)
+        } else {
+            let last = self.order.len().saturating_sub(1) as i32;
+            self.focused_column = (pos as i32 + delta).clamp(0, last) as usize;
+            None
+        }
+    }
+}
+
+impl Filterable for DataTable {
+    fn item_count(&self) -> usize {
+        self.row_count()
+    }
+
+    /// The row's displayed cells, in display order, or nothing if the row
+    /// has not been fetched.
+    fn item_text(&self, index: usize) -> String {
+        let Some(row) = self.row(index) else {
+            return String::new();
+        };
+        let cells: Vec<_> = self
+            .order
+            .iter()
+            .map(|&i| self.cell(row, i, CellStyle::default()).0)
+            .collect();
+        cells.join(&CELL_BREAK.to_string())
+    }
+
+    fn apply_filter(&mut self, matches: Option<&[FilterMatch]>) {
+        self.filter = matches.map(|matches| {
+            let mut matches = matches.to_vec();
+            matches.sort_by_key(|m| m.index);
+            matches
+        });
+        self.scroll_to(0);
+        self.reveal_cursor();
+    }
+
+    fn jump_to(&mut self, index: usize) {
+        if index < self.row_count() {
+            self.selection.select(index);
+            self.reveal_cursor();
+        }
+    }
+}
+
+impl Widget for DataTable {
+    fn widget_type(&self) -> &str {
+        "DataTable"
+    }
+
+    fn default_css(&self) -> &str {
+        "DataTable { width: 1fr; height: 1fr; }"
+    }
+
+    fn can_focus(&self) -> bool {
+        true
+    }
+
+    fn render(&self, region: Region, styles: &Styles) -> Vec<Strip> {
+        if region.width <= 0 || region.height <= 0 {
+            return Vec::new();
+        }
+
+        let style = CellStyle::from_styles(styles);
+        let header = style.with_attrs(Attrs::BOLD);
+        let focused = header.with_attrs(Attrs::REVERSE);
+        let loading = style.with_attrs(Attrs::DIM);
+
+        // Cells carry the filter's matched char ranges within their text.
+        type Cells<'a> = Vec<(Cow<'a, str>, CellStyle, Vec<Range<usize>>)>;
+        let line = |y: i32, cells: Cells, sep_style: &CellStyle| {
+            let mut strip = Strip::new(y, region.x);
+            for (pos, ((text, cell_style, ranges), column)) in
+                cells.into_iter().zip(self.columns()).enumerate()
+            {
+                let width = column.width as usize;
+                let text: String = text.chars().take(width).collect();
+                let start = strip.width();
+                let slack = width.saturating_sub(text_width(&text)) as i32;
+                let indent = match column.align {
+                    TextAlign::Left => 0,
+                    TextAlign::Center => slack / 2,
+                    TextAlign::Right => slack,
+                };
+                strip.fill(start + indent, cell_style);
+                let matched = cell_style.with_attrs(Attrs::BOLD | Attrs::UNDERLINE);
+                for (i, ch) in text.chars().enumerate() {
+                    let hit = ranges.iter().any(|r| r.contains(&i));
+                    strip.push(ch, if hit { matched } else { cell_style });
+                }
+                strip.fill(start + width as i32, cell_style);
+                if pos + 1 < self.order.len() {
+                    strip.push('│', *sep_style);
+                }
+            }
+            strip.fill(region.width, style);
+            strip
+        };
+
+        let mut strips = Vec::with_capacity(region.height as usize);
+        let titles = self
+            .columns()
+            .into_iter()
+            .enumerate()
+            .map(|(pos, c)| {
+                let s = if pos == self.focused_column {
+                    focused
+                } else {
+                    header
+                };
+                (Cow::Borrowed(c.label.as_str()), s, Vec::new())
+            })
+            .collect();
+        strips.push(line(region.y, titles, &header));
+
+        let body_rows = self.shown().saturating_sub(self.offset);
+        for pos in (self.offset..).take((region.height as usize - 1).min(body_rows)) {
+            let y = region.y + strips.len() as i32;
+            let row = self.shown_index(pos);
+            let Some(cells) = self.row(row) else {
+                let mut strip = Strip::new(y, region.x);
+                let placeholder = t!("data-table.loading");
+                let text: String = placeholder.chars().take(region.width as usize).collect();
+                strip.push_str(&text, loading);
+                strip.fill(region.width, loading);
+                strips.push(strip);
+                continue;
+            };
+            let row_style = match &self.row_style {
+                Some(restyle) => restyle(cells, style),
+                None => style,
+            };
+            let attrs = self.selection.attrs(row);
+            let row_style = row_style.with_attrs(attrs);
+            let ranges = self.filter.as_ref().map_or(&[][..], |f| &f[pos].ranges);
+            // Each cell's text starts after the previous cells and a break.
+            let mut start = 0;
+            let cells = self
+                .order
+                .iter()
+                .map(|&i| {
+                    let (text, cell_style) = self.cell(cells, i, row_style);
+                    let len = text.chars().count();
+                    let local = ranges
+                        .iter()
+                        .filter(|r| r.start < start + len && r.end > start)
+                        .map(|r| r.start.saturating_sub(start)..r.end.min(start + len) - start)
+                        .collect();
+                    start += len + 1;
+                    (text, cell_style.with_attrs(attrs), local)
+                })
+                .collect();
+            strips.push(line(y, cells, &row_style));
+        }
+        while strips.len() < region.height as usize {
+            let mut strip = Strip::new(region.y + strips.len() as i32, region.x);
+            strip.fill(region.width, style);
+            strips.push(strip);
+        }
+        strips
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::strip::Color;
+    use crate::widget::data_source::Fetch;
+    use std::cell::RefCell;
+    use std::ops::Range;
+    use std::rc::Rc;
+
+    fn table() -> DataTable {
+        DataTable::new(vec![
+            Column::new("name", "Name", 6),
+            Column::new("size", "Size", 4),
+            Column::new("kind", "Kind", 4),
+        ])
+        .with_row(vec!["a.rs".into(), "1K".into(), "rust".into()])
+    }
+
+    fn mouse(kind: MouseAction, x: u16, y: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            x,
+            y,
+            modifiers: Modifiers::NONE,
+        }
+    }
+
+    fn text(strip: &Strip) -> String {
+        strip.cells.iter().map(|c| c.ch).collect()
+    }
+
+    fn keys(table: &DataTable) -> Vec<&str> {
+        table.columns().iter().map(|c| c.key.as_str()).collect()
+    }
+
+    #[test]
+    fn render_header_and_rows() {
+        let strips = table().render(Region::new(0, 0, 16, 3), &Styles::new());
+        assert_eq!(text(&strips[0]), "Name  │Size│Kind");
+        assert_eq!(text(&strips[1]), "a.rs  │1K  │rust");
+        assert!(strips[0].cells[7].style.bold());
+        assert_eq!(text(&strips[2]).trim(), "");
+    }
+
+    #[test]
+    fn redactor_masks_cells_until_revealed() {
+        let mut t = table().with_redactor(
+            Redactor::new()
+                .with_pattern(crate::widget::redact::RedactPattern::Literal("1K".into())),
+        );
+        let region = Region::new(0, 0, 16, 2);
+        assert_eq!(
+            text(&t.render(region, &Styles::new())[1]),
+            "a.rs  │••  │rust"
+        );
+        t.redactor_mut().unwrap().set_revealed(true);
+        assert_eq!(
+            text(&t.render(region, &Styles::new())[1]),
+            "a.rs  │1K  │rust"
+        );
+    }
+
+    #[test]
+    fn drag_separator_resizes() {
+        let mut t = table();
+        let region = Region::new(2, 1, 30, 5);
+        // The first separator sits at table x=6, screen x=8.
+        assert!(t
+            .handle_mouse(&mouse(MouseAction::Down(MouseBtn::Left), 8, 1), region)
+            .is_none());
+        t.handle_mouse(&mouse(MouseAction::Drag(MouseBtn::Left), 11, 1), region);
+        let change = t
+            .handle_mouse(&mouse(MouseAction::Up(MouseBtn::Left), 11, 1), region)
+            .unwrap();
+        assert_eq!(change.0.columns[0], ("name".into(), 9));
+
+        t.handle_mouse(&mouse(MouseAction::Down(MouseBtn::Left), 11, 1), region);
+        t.handle_mouse(&mouse(MouseAction::Drag(MouseBtn::Left), 0, 1), region);
+        assert_eq!(t.columns()[0].width, MIN_COLUMN_WIDTH);
+    }
+
+    #[test]
+    fn drag_header_reorders() {
+        let mut t = table();
+        let region = Region::new(0, 0, 30, 5);
+        t.handle_mouse(&mouse(MouseAction::Down(MouseBtn::Left), 1, 0), region);
+        assert!(t
+            .handle_mouse(&mouse(MouseAction::Up(MouseBtn::Left), 1, 0), region)
+            .is_none());
+        t.handle_mouse(&mouse(MouseAction::Down(MouseBtn::Left), 1, 0), region);
+        t.handle_mouse(&mouse(MouseAction::Drag(MouseBtn::Left), 13, 0), region);
+        assert_eq!(keys(&t), vec!["size", "kind", "name"]);
+        let change = t
+            .handle_mouse(&mouse(MouseAction::Up(MouseBtn::Left), 13, 0), region)
+            .unwrap();
+        assert_eq!(change.0, t.layout());
+
+        let strips = t.render(Region::new(0, 0, 16, 2), &Styles::new());
+        assert_eq!(text(&strips[1]), "1K  │rust│a.rs  ");
+    }
+
+    #[test]
+    fn keyboard_fallbacks() {
+        let mut t = table();
+        let ctrl = |code| KeyEvent::new(code, Modifiers::CTRL);
+        let alt = |code| KeyEvent::new(code, Modifiers::ALT);
+        assert!(t
+            .handle_key(&KeyEvent::new(Key::Right, Modifiers::NONE))
+            .is_none());
+        assert_eq!(t.focused_column(), 1);
+        assert_eq!(t.handle_key(&ctrl(Key::Right)).unwrap().0.columns[1].1, 5);
+        t.handle_key(&alt(Key::Left)).unwrap();
+        assert_eq!(keys(&t), vec!["size", "name", "kind"]);
+        assert_eq!(t.focused_column(), 0);
+    }
+
+    #[test]
+    fn layout_round_trips() {
+        let mut t = table();
+        t.move_column(2, 0);
+        t.resize_column(1, 10);
+        let saved = t.layout();
+
+        let mut restored = table();
+        restored.restore_layout(&saved);
+        assert_eq!(restored.layout(), saved);
+
+        let partial = ColumnLayout {
+            columns: vec![("kind".into(), 7), ("bogus".into(), 3)],
+        };
+        let mut t = table();
+        t.restore_layout(&partial);
+        assert_eq!(keys(&t), vec!["kind", "name", "size"]);
+        assert_eq!(t.columns()[0].width, 7);
+    }
+
+    #[test]
+    fn columns_align_headers_and_cells() {
+        let t = DataTable::new(vec![
+            Column::new("name", "Name", 6),
+            Column::new("size", "Size", 6).with_align(TextAlign::Right),
+            Column::new("kind", "Kind", 6).with_align(TextAlign::Center),
+        ])
+        .with_row(vec!["a.rs".into(), "1K".into(), "rs".into()]);
+        let strips = t.render(Region::new(0, 0, 20, 2), &Styles::new());
+        assert_eq!(text(&strips[0]), "Name  │  Size│ Kind ");
+        assert_eq!(text(&strips[1]), "a.rs  │    1K│  rs  ");
+    }
+
+    #[test]
+    fn formatters_and_row_styles() {
+        let t = DataTable::new(vec![
+            Column::new("item", "Item", 4),
+            Column::new("delta", "Delta", 6).with_align(TextAlign::Right),
+        ])
+        .with_row(vec!["a".into(), "-3".into()])
+        .with_row(vec!["b".into(), "12".into()])
+        .with_formatter("delta", |value| {
+            let n: i64 = value.parse().unwrap_or(0);
+            if n < 0 {
+                Span::styled(format!("{n:+}"), CellStyle::new().with_fg("red"))
+            } else {
+                Span::new(format!("{n:+}"))
+            }
+        })
+        .with_row_style(|cells, style| match cells[0].as_str() {
+            "b" => style.with_attrs(Attrs::DIM),
+            _ => style,
+        });
+        let strips = t.render(Region::new(0, 0, 11, 3), &Styles::new());
+        assert_eq!(text(&strips[1]), "a   │    -3");
+        assert_eq!(text(&strips[2]), "b   │   +12");
+        assert_eq!(strips[1].cells[10].style.fg, Color::parse("red"));
+        assert!(!strips[1].cells[0].style.dim());
+        // Spans without a style keep the row's style.
+        assert!(strips[2].cells[0].style.dim());
+        assert!(strips[2].cells[10].style.dim());
+    }
+
+    #[test]
+    fn rows_select_with_keys_and_clicks() {
+        let mut t = DataTable::new(vec![Column::new("n", "N", 3)])
+            .with_selection_mode(SelectionMode::Multi)
+            .with_row(vec!["a".into()])
+            .with_row(vec!["b".into()])
+            .with_row(vec!["c".into()]);
+        let region = Region::new(0, 0, 3, 4);
+        let click = |y, modifiers| MouseEvent {
+            kind: MouseAction::Down(MouseBtn::Left),
+            x: 1,
+            y,
+            modifiers,
+        };
+        // The header row is not a row.
+        assert_eq!(t.handle_row_click(&click(0, Modifiers::NONE), region), None);
+        t.handle_row_click(&click(1, Modifiers::NONE), region);
+        let selected = t
+            .handle_row_click(&click(3, Modifiers::SHIFT), region)
+            .unwrap();
+        assert_eq!(selected.selected, [0, 1, 2]);
+        t.handle_row_key(&KeyEvent::new(Key::Char(' '), Modifiers::NONE));
+        assert_eq!(t.selection().selected(), [0, 1]);
+
+        let strips = t.render(region, &Styles::new());
+        assert!(strips[1].cells[0].style.attrs.contains(Attrs::REVERSE));
+        assert!(!strips[3].cells[0].style.attrs.contains(Attrs::REVERSE));
+    }
+
+    /// A thousand-row source that answers asynchronously.
+    #[derive(Default)]
+    struct Remote {
+        requests: Rc<RefCell<Vec<Range<usize>>>>,
+    }
+
+    impl DataSource for Remote {
+        type Item = Vec<String>;
+
+        fn total(&self) -> usize {
+            1_000
+        }
+
+        fn fetch(&mut self, range: Range<usize>) -> Fetch<Vec<String>> {
+            self.requests.borrow_mut().push(range);
+            Fetch::Pending
+        }
+    }
+
+    #[test]
+    fn remote_rows_load_a_page_at_a_time() {
+        let remote = Remote::default();
+        let requests = remote.requests.clone();
+        let mut t = DataTable::from_source(vec![Column::new("n", "N", 8)], remote)
+            .with_page_size(10)
+            .with_prefetch(0);
+        assert_eq!(t.row_count(), 1_000);
+        assert!(requests.borrow().is_empty());
+        t.set_viewport(2);
+        // Memory-only APIs refuse a remote source.
+        assert!(!t.add_row(vec!["x".into()]));
+
+        let region = Region::new(0, 0, 8, 3);
+        let strips = t.render(region, &Styles::new());
+        assert_eq!(text(&strips[1]), "Loading…");
+        assert!(strips[1].cells[0].style.dim());
+
+        let page = (0..10).map(|i| vec![format!("row {i}")]).collect();
+        assert!(t.deliver(0, page));
+        let strips = t.render(region, &Styles::new());
+        assert_eq!(text(&strips[1]), "row 0   ");
+        assert_eq!(text(&strips[2]), "row 1   ");
+
+        // Moving the cursor past the window scrolls and fetches.
+        t.handle_row_key(&KeyEvent::new(Key::End, Modifiers::NONE));
+        assert_eq!(t.offset(), 998);
+        assert_eq!(*requests.borrow(), vec![0..10, 990..1_000]);
+        let click = MouseEvent {
+            kind: MouseAction::Down(MouseBtn::Left),
+            x: 1,
+            y: 1,
+            modifiers: Modifiers::NONE,
+        };
+        let selected = t.handle_row_click(&click, region).unwrap();
+        assert_eq!(selected.selected, [998]);
+    }
+
+    #[test]
+    fn filter_matches_displayed_cells() {
+        use crate::widget::filter::filter_items;
+
+        let mut t = table()
+            .with_row(vec!["b.md".into(), "2K".into(), "doc".into()])
+            .with_row(vec!["c.rs".into(), "3K".into(), "rust".into()])
+            .with_redactor(
+                Redactor::new()
+                    .with_pattern(crate::widget::redact::RedactPattern::Literal("3K".into())),
+            );
+        t.set_viewport(2);
+        // Masked cells are searched as displayed.
+        assert!(filter_items(&t, "3K").is_empty());
+        let matches = filter_items(&t, "RS");
+        assert_eq!(matches.len(), 2);
+        t.apply_filter(Some(&matches));
+
+        let region = Region::new(0, 0, 16, 3);
+        let strips = t.render(region, &Styles::new());
+        assert_eq!(text(&strips[1]), "a.rs  │1K  │rust");
+        assert_eq!(text(&strips[2]), "c.rs  │••  │rust");
+        // "rs" is highlighted in the name cell only.
+        assert!(strips[1].cells[2].style.underline());
+        assert!(!strips[1].cells[1].style.underline());
+        assert!(!strips[1].cells[12].style.underline());
+
+        t.handle_row_key(&KeyEvent::new(Key::Down, Modifiers::NONE));
+        assert_eq!(t.selection().cursor(), 2);
+        let click = mouse(MouseAction::Down(MouseBtn::Left), 1, 1);
+        assert_eq!(t.handle_row_click(&click, region).unwrap().selected, [0]);
+
+        t.jump_to(1);
+        t.apply_filter(None);
+        assert_eq!(t.selection().cursor(), 1);
+        assert_eq!(
+            text(&t.render(region, &Styles::new())[2]),
+            "b.md  │2K  │doc "
+        );
+    }
+}
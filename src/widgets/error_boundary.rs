@@ -0,0 +1,302 @@
+//! ErrorBoundary widget: isolates a failing subtree.
+//!
+//! The boundary wraps a child widget and renders it as-is until something
+//! goes wrong: a panic inside the child's `render`, a panic inside an event
+//! handler run through [`ErrorBoundary::handle`], or an error reported with
+//! [`ErrorBoundary::fail`]. From then on it renders a fallback showing the
+//! error message instead of the child, and the rest of the app carries on.
+//! [`ErrorBoundary::retry`] clears the error (after running the retry hook,
+//! if any) so the child gets another chance on the next render.
+//!
+//! Panics are still reported by the process panic hook before being caught;
+//! install a quieter hook if that output would disturb the terminal.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::css::styles::{Styles, TextOverflow};
+use crate::geometry::Region;
+use crate::render::strip::{CellStyle, Strip};
+use crate::render::text::fit;
+use crate::t;
+use crate::widget::traits::Widget;
+
+/// Builds the widget shown in place of a failed child.
+type Fallback = Box<dyn Fn(&str) -> Box<dyn Widget>>;
+
+/// Resets a failed child before it is rendered again.
+type RetryHook = Box<dyn FnMut(&mut dyn Widget)>;
+
+// ---------------------------------------------------------------------------
+// ErrorBoundary
+// ---------------------------------------------------------------------------
+
+/// Renders its child, or a fallback once the child has failed.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut chart = ErrorBoundary::new(Chart::new(series))
+///     .with_fallback(|message| Box::new(Static::new(format!("Chart failed: {message}"))))
+///     .with_retry(|chart| chart.as_any_mut().downcast_mut::<Chart>().unwrap().reset());
+///
+/// chart.handle(|chart| chart.as_any_mut().downcast_mut::<Chart>().unwrap().zoom(2));
+/// if chart.error().is_some() && retry_pressed {
+///     chart.retry();
+/// }
+/// ```
+pub struct ErrorBoundary {
+    child: Box<dyn Widget>,
+    error: RefCell<Option<String>>,
+    fallback: Option<Fallback>,
+    on_retry: Option<RetryHook>,
+}
+
+impl ErrorBoundary {
+    /// Guard `child`.
+    pub fn new(child: impl Widget + 'static) -> Self {
+        Self {
+            child: Box::new(child),
+            error: RefCell::new(None),
+            fallback: None,
+            on_retry: None,
+        }
+    }
+
+    /// Build the widget shown with the error message once the child has
+    /// failed (builder pattern). Defaults to a line with the
+    /// `error-boundary.error` message, `Error: <message>` in English.
+    pub fn with_fallback(mut self, fallback: impl Fn(&str) -> Box<dyn Widget> + 'static) -> Self {
+        self.fallback = Some(Box::new(fallback));
+        self
+    }
+
+    /// Run `hook` on the child when retrying, e.g. to reset the state that
+    /// made it fail (builder pattern).
+    pub fn with_retry(mut self, hook: impl FnMut(&mut dyn Widget) + 'static) -> Self {
+        self.on_retry = Some(Box::new(hook));
+        self
+    }
+
+    /// The wrapped widget.
+    pub fn child(&self) -> &dyn Widget {
+        self.child.as_ref()
+    }
+
+    /// The wrapped widget, mutably.
+    pub fn child_mut(&mut self) -> &mut dyn Widget {
+        self.child.as_mut()
+    }
+
+    /// The message of the failure being shown, if any.
+    pub fn error(&self) -> Option<String> {
+        self.error.borrow().clone()
+    }
+
+    /// Record a failure, switching to the fallback. The first failure is
+    /// kept until [`retry`](Self::retry).
+    pub fn fail(&self, message: impl Into<String>) {
+        self.error
+            .borrow_mut()
+            .get_or_insert_with(|| message.into());
+    }
+
+    /// Run an event handler against the child, catching a panic as a
+    /// failure. Returns the handler's result, or `None` if it panicked or the
+    /// boundary has already failed.
+    pub fn handle<R>(&mut self, f: impl FnOnce(&mut dyn Widget) -> R) -> Option<R> {
+        if self.error.get_mut().is_some() {
+            return None;
+        }
+        let child = self.child.as_mut();
+        match panic::catch_unwind(AssertUnwindSafe(|| f(child))) {
+            Ok(result) => Some(result),
+            Err(payload) => {
+                self.fail(panic_message(payload.as_ref()));
+                None
+            }
+        }
+    }
+
+    /// Clear the failure so the child renders again, running the retry hook
+    /// first. Returns whether there was a failure to clear.
+    pub fn retry(&mut self) -> bool {
+        if self.error.get_mut().take().is_none() {
+            return false;
+        }
+        if let Some(hook) = &mut self.on_retry {
+            hook(self.child.as_mut());
+        }
+        true
+    }
+
+    fn render_fallback(&self, message: &str, region: Region, styles: &Styles) -> Vec<Strip> {
+        if let Some(fallback) = &self.fallback {
+            return fallback(message).render(region, styles);
+        }
+        let style = CellStyle::from_styles(styles);
+        let text = t!("error-boundary.error", message = message);
+        (0..region.height)
+            .map(|row| {
+                let mut strip = Strip::new(region.y + row, region.x);
+                if row == 0 {
+                    let line = fit(&text, region.width as usize, TextOverflow::Ellipsis);
+                    strip.push_str(&line, style);
+                }
+                strip.fill(region.width, style);
+                strip
+            })
+            .collect()
+    }
+}
+
+/// The text of a panic payload.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_owned()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "widget panicked".to_owned()
+    }
+}
+
+impl Widget for ErrorBoundary {
+    fn widget_type(&self) -> &str {
+        "ErrorBoundary"
+    }
+
+    fn default_css(&self) -> &str {
+        "ErrorBoundary { width: 1fr; height: 1fr; }"
+    }
+
+    fn can_focus(&self) -> bool {
+        self.error.borrow().is_none() && self.child.can_focus()
+    }
+
+    fn render(&self, region: Region, styles: &Styles) -> Vec<Strip> {
+        if region.width <= 0 || region.height <= 0 {
+            return Vec::new();
+        }
+        if self.error.borrow().is_none() {
+            let rendered =
+                panic::catch_unwind(AssertUnwindSafe(|| self.child.render(region, styles)));
+            match rendered {
+                Ok(strips) => return strips,
+                Err(payload) => self.fail(panic_message(payload.as_ref())),
+            }
+        }
+        let message = self.error().unwrap_or_default();
+        self.render_fallback(&message, region, styles)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::Static;
+
+    /// Panics while rendering when `broken` is set.
+    struct Flaky {
+        broken: bool,
+    }
+
+    impl Widget for Flaky {
+        fn widget_type(&self) -> &str {
+            "Flaky"
+        }
+
+        fn can_focus(&self) -> bool {
+            true
+        }
+
+        fn render(&self, region: Region, styles: &Styles) -> Vec<Strip> {
+            if self.broken {
+                panic!("chart data missing");
+            }
+            Static::new("ok").render(region, styles)
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+    }
+
+    fn flaky(boundary: &mut ErrorBoundary) -> &mut Flaky {
+        boundary.child_mut().as_any_mut().downcast_mut().unwrap()
+    }
+
+    fn first_row(boundary: &ErrorBoundary, width: i32) -> String {
+        boundary.render(Region::new(0, 0, width, 2), &Styles::new())[0]
+            .cells
+            .iter()
+            .map(|c| c.ch)
+            .collect()
+    }
+
+    #[test]
+    fn render_panic_shows_fallback() {
+        let boundary = ErrorBoundary::new(Flaky { broken: true });
+        assert_eq!(first_row(&boundary, 30), "Error: chart data missing     ");
+        assert_eq!(boundary.error().as_deref(), Some("chart data missing"));
+        assert!(!boundary.can_focus());
+        assert_eq!(first_row(&boundary, 10), "Error: ch…");
+    }
+
+    #[test]
+    fn healthy_child_renders_normally() {
+        let boundary = ErrorBoundary::new(Flaky { broken: false });
+        assert_eq!(first_row(&boundary, 4), "ok  ");
+        assert!(boundary.can_focus());
+        assert_eq!(boundary.error(), None);
+    }
+
+    #[test]
+    fn handler_panic_is_caught() {
+        let mut boundary = ErrorBoundary::new(Flaky { broken: false });
+        assert_eq!(boundary.handle(|_| 5), Some(5));
+        let result: Option<()> = boundary.handle(|_| panic!("{} clicks", 3));
+        assert_eq!(result, None);
+        assert_eq!(boundary.error().as_deref(), Some("3 clicks"));
+        assert_eq!(boundary.handle(|_| 5), None);
+    }
+
+    #[test]
+    fn retry_runs_hook_and_rerenders() {
+        let mut boundary = ErrorBoundary::new(Flaky { broken: true })
+            .with_fallback(|message| Box::new(Static::new(format!("! {message}"))))
+            .with_retry(|child| {
+                child.as_any_mut().downcast_mut::<Flaky>().unwrap().broken = false;
+            });
+        assert_eq!(first_row(&boundary, 6), "! char");
+        assert!(boundary.retry());
+        assert!(!flaky(&mut boundary).broken);
+        assert_eq!(first_row(&boundary, 4), "ok  ");
+        assert!(!boundary.retry());
+    }
+
+    #[test]
+    fn reported_errors_keep_the_first() {
+        let boundary = ErrorBoundary::new(Flaky { broken: false });
+        boundary.fail("disk full");
+        boundary.fail("second");
+        assert_eq!(boundary.error().as_deref(), Some("disk full"));
+    }
+}
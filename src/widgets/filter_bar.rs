@@ -0,0 +1,257 @@
+//! FilterBar widget: a `/`-activated search line for [`Filterable`] widgets.
+//!
+//! While closed, the bar only reacts to `/`. Once open, typed characters
+//! refine the query and the target is re-filtered on every keystroke. Enter
+//! jumps to the first match and closes the bar; Esc clears the filter and
+//! closes it. Other keys pass through so the target can still be navigated.
+
+use std::any::Any;
+
+use crate::css::styles::Styles;
+use crate::event::input::{Key, KeyEvent, Modifiers};
+use crate::geometry::Region;
+use crate::render::strip::{CellStyle, Strip};
+use crate::t;
+use crate::widget::filter::{filter_items, Filterable};
+use crate::widget::traits::Widget;
+use crate::widgets::input::Input;
+
+// ---------------------------------------------------------------------------
+// FilterBar
+// ---------------------------------------------------------------------------
+
+/// A one-line filter input attached to a list-like widget.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut bar = FilterBar::new();
+/// // In the key handler of the focused tree:
+/// if !bar.handle_key(&key, &mut tree) {
+///     // not consumed: regular tree navigation
+/// }
+/// ```
+pub struct FilterBar {
+    input: Input,
+    open: bool,
+    matches: usize,
+}
+
+impl FilterBar {
+    /// Create a closed filter bar.
+    pub fn new() -> Self {
+        Self {
+            input: Input::new(),
+            open: false,
+            matches: 0,
+        }
+    }
+
+    /// Whether the bar is accepting input.
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// The current query.
+    pub fn query(&self) -> &str {
+        self.input.value()
+    }
+
+    /// Number of items matching the current query.
+    pub fn match_count(&self) -> usize {
+        self.matches
+    }
+
+    /// Open the bar with an empty query.
+    pub fn open(&mut self) {
+        self.open = true;
+        self.input.clear();
+        self.matches = 0;
+    }
+
+    /// Close the bar and remove the filter from `target`.
+    pub fn close(&mut self, target: &mut dyn Filterable) {
+        self.open = false;
+        self.input.clear();
+        self.matches = 0;
+        target.apply_filter(None);
+    }
+
+    /// Re-run the query against `target`.
+    fn refilter(&mut self, target: &mut dyn Filterable) {
+        if self.query().is_empty() {
+            self.matches = target.item_count();
+            target.apply_filter(None);
+        } else {
+            let matches = filter_items(target, self.query());
+            self.matches = matches.len();
+            target.apply_filter(Some(&matches));
+        }
+    }
+
+    /// Route a key press. Returns `true` if the bar consumed it.
+    pub fn handle_key(&mut self, key: &KeyEvent, target: &mut dyn Filterable) -> bool {
+        let plain =
+            !key.modifiers.contains(Modifiers::CTRL) && !key.modifiers.contains(Modifiers::ALT);
+        if !self.open {
+            if key.code == Key::Char('/') && plain {
+                self.open();
+                self.refilter(target);
+                return true;
+            }
+            return false;
+        }
+
+        match key.code {
+            Key::Char(ch) if plain => {
+                self.input.insert_char(ch);
+                self.refilter(target);
+            }
+            Key::Backspace => {
+                self.input.delete_char();
+                self.refilter(target);
+            }
+            Key::Escape => self.close(target),
+            Key::Enter => {
+                let first = filter_items(target, self.query()).first().map(|m| m.index);
+                self.close(target);
+                if let Some(index) = first {
+                    target.jump_to(index);
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+impl Default for FilterBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for FilterBar {
+    fn widget_type(&self) -> &str {
+        "FilterBar"
+    }
+
+    fn default_css(&self) -> &str {
+        "FilterBar { height: 1; dock: bottom; width: 1fr; }"
+    }
+
+    fn render(&self, region: Region, styles: &Styles) -> Vec<Strip> {
+        if region.width <= 0 || region.height <= 0 || !self.open {
+            return Vec::new();
+        }
+
+        let style = CellStyle::from_styles(styles);
+        let width = region.width as usize;
+        let count = format!(" {}", t!("filter-bar.matches", count = self.matches));
+        let line = format!("/{}", self.query());
+        let room = width.saturating_sub(count.chars().count());
+        // Keep the end of a long query visible.
+        let skip = line.chars().count().saturating_sub(room);
+
+        let mut strip = Strip::new(region.y, region.x);
+        strip.push_str(&line.chars().skip(skip).collect::<String>(), style);
+        let used = strip.width();
+        if (used as usize) + count.chars().count() <= width {
+            strip.fill(region.width - count.chars().count() as i32, style);
+            strip.push_str(&count, style);
+        }
+        strip.fill(region.width, style);
+        vec![strip]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::check_tree::CheckTree;
+
+    fn key(code: Key) -> KeyEvent {
+        KeyEvent::new(code, Modifiers::NONE)
+    }
+
+    fn type_str(bar: &mut FilterBar, tree: &mut CheckTree, text: &str) {
+        for ch in text.chars() {
+            assert!(bar.handle_key(&key(Key::Char(ch)), tree));
+        }
+    }
+
+    fn tree() -> CheckTree {
+        let mut tree = CheckTree::new();
+        let root = tree.add_root("src");
+        tree.add_child(root, "main.rs");
+        tree.add_child(root, "lib.rs");
+        tree.add_child(root, "README.md");
+        tree
+    }
+
+    #[test]
+    fn slash_opens_and_typing_filters() {
+        let mut bar = FilterBar::new();
+        let mut tree = tree();
+        assert!(!bar.handle_key(&key(Key::Char('x')), &mut tree));
+        assert!(bar.handle_key(&key(Key::Char('/')), &mut tree));
+        assert!(bar.is_open());
+        type_str(&mut bar, &mut tree, ".rs");
+        assert_eq!(bar.query(), ".rs");
+        assert_eq!(bar.match_count(), 2);
+        assert!(bar.handle_key(&key(Key::Backspace), &mut tree));
+        assert!(bar.handle_key(&key(Key::Backspace), &mut tree));
+        assert_eq!(bar.match_count(), 3);
+        // Navigation keys fall through to the target.
+        assert!(!bar.handle_key(&key(Key::Down), &mut tree));
+    }
+
+    #[test]
+    fn enter_jumps_to_first_match() {
+        let mut bar = FilterBar::new();
+        let mut tree = tree();
+        bar.handle_key(&key(Key::Char('/')), &mut tree);
+        type_str(&mut bar, &mut tree, "lib");
+        assert!(bar.handle_key(&key(Key::Enter), &mut tree));
+        assert!(!bar.is_open());
+        assert_eq!(tree.cursor(), Some(2));
+    }
+
+    #[test]
+    fn escape_clears_filter() {
+        let mut bar = FilterBar::new();
+        let mut tree = tree();
+        bar.handle_key(&key(Key::Char('/')), &mut tree);
+        type_str(&mut bar, &mut tree, "md");
+        assert!(bar.handle_key(&key(Key::Escape), &mut tree));
+        assert!(!bar.is_open());
+        assert_eq!(bar.query(), "");
+        assert_eq!(tree.cursor(), Some(0));
+    }
+
+    #[test]
+    fn render_shows_query_and_count() {
+        let mut bar = FilterBar::new();
+        let mut tree = tree();
+        assert!(bar
+            .render(Region::new(0, 0, 20, 1), &Styles::new())
+            .is_empty());
+        bar.handle_key(&key(Key::Char('/')), &mut tree);
+        type_str(&mut bar, &mut tree, "rs");
+        let strip = &bar.render(Region::new(0, 0, 20, 1), &Styles::new())[0];
+        let text: String = strip.cells.iter().map(|c| c.ch).collect();
+        assert_eq!(text, "/rs        2 matches");
+    }
+}
@@ -1,13 +1,20 @@
 //! Footer widget: app footer bar with left-aligned content.
 //!
 //! The footer renders its content left-aligned on one line, padded to fill
-//! the region width. Typically docked to the bottom of the screen.
+//! the region width. Typically docked to the bottom of the screen. Given the
+//! app's focus announcement signal, it shows the announcement ("Focused: Save
+//! button") in place of its content while one is up. Given the app's binding
+//! hints signal, it lists the key bindings active for the focused widget
+//! ("Ctrl+S Save  F1 Help") in place of its content, keys in bold.
 
 use std::any::Any;
 
 use crate::css::styles::Styles;
+use crate::event::binding::BindingHint;
 use crate::geometry::Region;
-use crate::render::strip::{CellStyle, Strip};
+use crate::reactive::ReadSignal;
+use crate::render::strip::{Attrs, CellStyle, Strip};
+use crate::render::text::fit;
 use crate::widget::traits::Widget;
 
 // ---------------------------------------------------------------------------
@@ -21,18 +28,71 @@ use crate::widget::traits::Widget;
 /// # Examples
 ///
 /// ```ignore
-/// let ft = Footer::new("Press Q to quit");
+/// let ft = Footer::new("Press Q to quit").with_announcements(app.focus_announcements());
+/// let ft = Footer::new("").with_bindings(app.binding_hints());
 /// ```
 pub struct Footer {
     content: String,
+    announcements: Option<ReadSignal<Option<String>>>,
+    bindings: Option<ReadSignal<Vec<BindingHint>>>,
 }
 
+/// Space between two binding hints.
+const HINT_GAP: &str = "  ";
+
 impl Footer {
     /// Create a new footer with the given content.
     pub fn new(content: impl Into<String>) -> Self {
         Self {
             content: content.into(),
+            announcements: None,
+            bindings: None,
+        }
+    }
+
+    /// Show announcements from `signal` in place of the content while it
+    /// holds one (builder pattern).
+    pub fn with_announcements(mut self, signal: ReadSignal<Option<String>>) -> Self {
+        signal.get();
+        self.announcements = Some(signal);
+        self
+    }
+
+    /// List the binding hints from `signal` in place of the content while
+    /// there are any (builder pattern). Announcements still take precedence.
+    pub fn with_bindings(mut self, signal: ReadSignal<Vec<BindingHint>>) -> Self {
+        signal.get();
+        self.bindings = Some(signal);
+        self
+    }
+
+    /// The binding hints currently listed; empty when the content or an
+    /// announcement is shown instead.
+    pub fn hints(&self) -> Vec<BindingHint> {
+        if self.announcement().is_some() {
+            return Vec::new();
         }
+        self.bindings
+            .map(|signal| signal.get_untracked())
+            .unwrap_or_default()
+    }
+
+    /// The text currently shown: the announcement, if one is up, the
+    /// binding hints, if any, or the content.
+    pub fn text(&self) -> String {
+        if let Some(text) = self.announcement() {
+            return text;
+        }
+        let hints = self.hints();
+        if hints.is_empty() {
+            return self.content.clone();
+        }
+        let hints: Vec<String> = hints.iter().map(BindingHint::to_string).collect();
+        hints.join(HINT_GAP)
+    }
+
+    fn announcement(&self) -> Option<String> {
+        self.announcements.and_then(|signal| signal.get_untracked())
     }
 
     /// Return the footer content.
@@ -50,6 +110,10 @@ impl Widget for Footer {
         "Footer { height: 1; dock: bottom; width: 1fr; }"
     }
 
+    fn content_text(&self) -> Option<&str> {
+        Some(&self.content)
+    }
+
     fn render(&self, region: Region, styles: &Styles) -> Vec<Strip> {
         if region.width <= 0 || region.height <= 0 {
             return Vec::new();
@@ -57,11 +121,27 @@ impl Widget for Footer {
 
         let style = CellStyle::from_styles(styles);
         let width = region.width as usize;
-
-        // Render content left-aligned, truncated to width, padded with spaces.
-        let truncated: String = self.content.chars().take(width).collect();
         let mut strip = Strip::new(region.y, region.x);
-        strip.push_str(&truncated, style.clone());
+
+        let hints = self.hints();
+        if hints.is_empty() {
+            // Render content left-aligned, fitted to width, padded with spaces.
+            let overflow = styles.text_overflow.unwrap_or_default();
+            let truncated = fit(&self.text(), width, overflow);
+            strip.push_str(&truncated, style);
+        } else {
+            // Keys in bold, then the description; cut off at the edge.
+            let key_style = style.with_attrs(style.attrs | Attrs::BOLD);
+            for (i, hint) in hints.iter().enumerate() {
+                if i > 0 {
+                    strip.push_str(HINT_GAP, style);
+                }
+                strip.push_str(&hint.keys, key_style);
+                strip.push(' ', style);
+                strip.push_str(&hint.description, style);
+            }
+            strip.cells.truncate(width);
+        }
         strip.fill(region.width, style);
 
         vec![strip]
@@ -83,6 +163,7 @@ impl Widget for Footer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::render::strip::Color;
 
     fn region(w: i32, h: i32) -> Region {
         Region::new(0, 0, w, h)
@@ -132,6 +213,16 @@ mod tests {
         assert_eq!(strips[0].cells[4].ch, ' '); // "This " -> 'T','h','i','s',' '
     }
 
+    #[test]
+    fn render_shrinks_long_content() {
+        let f = Footer::new("Press Escape to quit");
+        let mut s = styles();
+        s.text_overflow = Some(crate::css::styles::TextOverflow::Shrink);
+        let strips = f.render(region(12, 1), &s);
+        let text: String = strips[0].cells.iter().map(|c| c.ch).collect();
+        assert_eq!(text, "P. E. to q. ");
+    }
+
     #[test]
     fn render_zero_region() {
         let f = Footer::new("x");
@@ -145,7 +236,7 @@ mod tests {
         let mut s = styles();
         s.background = Some("gray".into());
         let strips = f.render(region(10, 1), &s);
-        assert_eq!(strips[0].cells[0].style.bg, Some("gray".into()));
+        assert_eq!(strips[0].cells[0].style.bg, Some(Color::Grey));
     }
 
     #[test]
@@ -165,6 +256,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn announcement_replaces_content_while_up() {
+        use crate::reactive::create_signal;
+        use crate::reactive::signal::reset_runtime;
+
+        reset_runtime();
+        let (announcement, set_announcement) = create_signal(None);
+        let f = Footer::new("Press Q to quit").with_announcements(announcement);
+        let line = |f: &Footer| -> String {
+            let strips = f.render(region(24, 1), &styles());
+            strips[0].cells.iter().map(|c| c.ch).collect()
+        };
+        assert_eq!(line(&f).trim_end(), "Press Q to quit");
+        set_announcement.set(Some("Focused: Save button".into()));
+        assert_eq!(line(&f).trim_end(), "Focused: Save button");
+        set_announcement.set(None);
+        assert_eq!(f.text(), "Press Q to quit");
+    }
+
+    #[test]
+    fn binding_hints_replace_content_with_bold_keys() {
+        use crate::reactive::create_signal;
+        use crate::reactive::signal::reset_runtime;
+
+        reset_runtime();
+        let (hints, set_hints) = create_signal(Vec::new());
+        let (announcement, set_announcement) = create_signal(None);
+        let f = Footer::new("Ready")
+            .with_bindings(hints)
+            .with_announcements(announcement);
+        assert_eq!(f.text(), "Ready");
+
+        let hint = |keys: &str, description: &str| BindingHint {
+            keys: keys.into(),
+            description: description.into(),
+        };
+        set_hints.set(vec![hint("Ctrl+S", "Save"), hint("F1", "Help")]);
+        let strips = f.render(region(14, 1), &styles());
+        let text: String = strips[0].cells.iter().map(|c| c.ch).collect();
+        assert_eq!(text, "Ctrl+S Save  F");
+        assert!(strips[0].cells[0].style.attrs.contains(Attrs::BOLD));
+        assert!(!strips[0].cells[7].style.attrs.contains(Attrs::BOLD));
+
+        set_announcement.set(Some("Focused: editor".into()));
+        assert_eq!(f.text(), "Focused: editor");
+        assert!(f.hints().is_empty());
+    }
+
     #[test]
     fn as_any_downcast() {
         let f = Footer::new("test");
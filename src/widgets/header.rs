@@ -2,15 +2,102 @@
 //!
 //! The header renders a title centered on the first row. If a subtitle is
 //! provided and the region has at least 2 rows, the subtitle is centered
-//! on the second row.
+//! on the second row. Both are parsed as [markup](crate::text).
+//!
+//! The first row can also carry an icon and clickable tabs on the left and a
+//! clock on the right; the title is then centered in the space between them.
+//! Each of these is a [`HeaderPart`] with its own style overrides.
+//!
+//! The clock shows UTC unless shifted with [`Header::with_utc_offset`]; there
+//! is no time zone lookup. [`Header::start_clock`] keeps it current from a
+//! one-second interval on the app.
 
 use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::app::App;
+use crate::css::styles::{Styles, TextOverflow};
+use crate::dom::node::NodeId;
+use crate::event::timer::TimerHandle;
 
-use crate::css::styles::Styles;
 use crate::geometry::Region;
-use crate::render::strip::{CellStyle, Strip};
+use crate::render::fill::paint_background;
+use crate::render::strip::{Attrs, CellStyle, Strip};
+use crate::text::Text;
 use crate::widget::traits::Widget;
 
+// ---------------------------------------------------------------------------
+// Parts
+// ---------------------------------------------------------------------------
+
+/// A stylable sub-element of the header's first row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HeaderPart {
+    /// The app icon slot at the left edge.
+    Icon,
+    /// The centered title.
+    Title,
+    /// An inactive tab.
+    Tab,
+    /// The active tab (reversed unless overridden).
+    ActiveTab,
+    /// The clock at the right edge.
+    Clock,
+}
+
+/// How the header clock is formatted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClockFormat {
+    /// `HH:MM`
+    #[default]
+    HoursMinutes,
+    /// `HH:MM:SS`
+    HoursMinutesSeconds,
+}
+
+impl ClockFormat {
+    /// Format seconds since midnight.
+    fn format(self, seconds_of_day: u64) -> String {
+        let (h, m, s) = (
+            seconds_of_day / 3600,
+            seconds_of_day / 60 % 60,
+            seconds_of_day % 60,
+        );
+        match self {
+            ClockFormat::HoursMinutes => format!("{h:02}:{m:02}"),
+            ClockFormat::HoursMinutesSeconds => format!("{h:02}:{m:02}:{s:02}"),
+        }
+    }
+}
+
+/// Clock settings and the last displayed time.
+#[derive(Debug, Clone)]
+struct Clock {
+    format: ClockFormat,
+    utc_offset: i64,
+    text: String,
+}
+
+impl Clock {
+    /// Set the clock to `now`. Returns whether the text changed.
+    fn update(&mut self, now: SystemTime) -> bool {
+        let secs = now
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs() as i64);
+        let text = self
+            .format
+            .format((secs + self.utc_offset).rem_euclid(86_400) as u64);
+        if text == self.text {
+            return false;
+        }
+        self.text = text;
+        true
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Header
 // ---------------------------------------------------------------------------
@@ -22,11 +109,25 @@ use crate::widget::traits::Widget;
 /// # Examples
 ///
 /// ```ignore
-/// let hdr = Header::new("My App").with_subtitle("v1.0");
+/// let hdr = Header::new("My App")
+///     .with_subtitle("v1.0")
+///     .with_icon("🚀")
+///     .with_tabs(["Home", "Logs"])
+///     .with_clock(ClockFormat::HoursMinutes)
+///     .with_utc_offset(2 * 3600);
+///
+/// // Tick every second, repainting the header when the minute changes.
+/// let clock = hdr.start_clock(&mut app, header_node);
 /// ```
 pub struct Header {
     title: String,
     subtitle: Option<String>,
+    icon: Option<String>,
+    tabs: Vec<String>,
+    active_tab: usize,
+    /// Shared with the interval from [`Header::start_clock`].
+    clock: Option<Rc<RefCell<Clock>>>,
+    part_styles: HashMap<HeaderPart, Styles>,
 }
 
 impl Header {
@@ -35,6 +136,11 @@ impl Header {
         Self {
             title: title.into(),
             subtitle: None,
+            icon: None,
+            tabs: Vec::new(),
+            active_tab: 0,
+            clock: None,
+            part_styles: HashMap::new(),
         }
     }
 
@@ -44,6 +150,49 @@ impl Header {
         self
     }
 
+    /// Show an icon or emoji at the left edge (builder pattern).
+    pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Show tabs after the icon, the first one active (builder pattern).
+    pub fn with_tabs<S: Into<String>>(mut self, tabs: impl IntoIterator<Item = S>) -> Self {
+        self.set_tabs(tabs);
+        self
+    }
+
+    /// Show a clock at the right edge, in UTC (builder pattern). It reads
+    /// `--:--` until [`start_clock`](Self::start_clock) or the first
+    /// [`update_clock`](Self::update_clock).
+    pub fn with_clock(mut self, format: ClockFormat) -> Self {
+        let text = match format {
+            ClockFormat::HoursMinutes => "--:--",
+            ClockFormat::HoursMinutesSeconds => "--:--:--",
+        };
+        self.clock = Some(Rc::new(RefCell::new(Clock {
+            format,
+            utc_offset: 0,
+            text: text.into(),
+        })));
+        self
+    }
+
+    /// Shift the clock from UTC by `seconds`, e.g. to the local offset
+    /// (builder pattern).
+    pub fn with_utc_offset(self, seconds: i64) -> Self {
+        if let Some(clock) = &self.clock {
+            clock.borrow_mut().utc_offset = seconds;
+        }
+        self
+    }
+
+    /// Override the style of one part of the header (builder pattern).
+    pub fn with_part_style(mut self, part: HeaderPart, styles: Styles) -> Self {
+        self.part_styles.insert(part, styles);
+        self
+    }
+
     /// Return the title.
     pub fn title(&self) -> &str {
         &self.title
@@ -53,24 +202,128 @@ impl Header {
     pub fn subtitle(&self) -> Option<&str> {
         self.subtitle.as_deref()
     }
+
+    /// Return the icon, if any.
+    pub fn icon(&self) -> Option<&str> {
+        self.icon.as_deref()
+    }
+
+    /// Replace the tabs, e.g. to mirror the app's screens. The active tab is
+    /// kept when still in range.
+    pub fn set_tabs<S: Into<String>>(&mut self, tabs: impl IntoIterator<Item = S>) {
+        self.tabs = tabs.into_iter().map(Into::into).collect();
+        if self.active_tab >= self.tabs.len() {
+            self.active_tab = 0;
+        }
+    }
+
+    /// The tab labels.
+    pub fn tabs(&self) -> &[String] {
+        &self.tabs
+    }
+
+    /// The index of the active tab, if there are tabs.
+    pub fn active_tab(&self) -> Option<usize> {
+        (!self.tabs.is_empty()).then_some(self.active_tab)
+    }
+
+    /// Make a tab active. Out-of-range indices are ignored.
+    pub fn set_active_tab(&mut self, index: usize) {
+        if index < self.tabs.len() {
+            self.active_tab = index;
+        }
+    }
+
+    /// The tab under screen column `x` when the header occupies `region`.
+    pub fn tab_at(&self, x: i32, region: Region) -> Option<usize> {
+        self.tab_spans(region)
+            .into_iter()
+            .position(|(start, end)| (start..end).contains(&x))
+    }
+
+    /// Activate the tab under a click at column `x`, returning its index.
+    pub fn click_tab(&mut self, x: i32, region: Region) -> Option<usize> {
+        let index = self.tab_at(x, region)?;
+        self.active_tab = index;
+        Some(index)
+    }
+
+    /// The text the clock currently shows, if enabled.
+    pub fn clock_text(&self) -> Option<String> {
+        self.clock.as_ref().map(|clock| clock.borrow().text.clone())
+    }
+
+    /// Set the clock to `now`. Returns whether the displayed text changed,
+    /// so callers driving this from a timer only repaint when needed.
+    pub fn update_clock(&mut self, now: SystemTime) -> bool {
+        self.clock
+            .as_ref()
+            .is_some_and(|clock| clock.borrow_mut().update(now))
+    }
+
+    /// Keep the clock current: sets it now, then registers a one-second
+    /// interval on `app` that updates it and repaints `node` (this header's
+    /// node) whenever the shown time changes. Returns `None`, registering
+    /// nothing, if the clock is not enabled; cancel the handle to stop it.
+    pub fn start_clock(&self, app: &mut App, node: NodeId) -> Option<TimerHandle> {
+        let clock = Rc::clone(self.clock.as_ref()?);
+        clock.borrow_mut().update(SystemTime::now());
+        Some(app.set_interval(Duration::from_secs(1), move |app| {
+            if clock.borrow_mut().update(SystemTime::now()) {
+                if let Some(region) = app.screen.region_of(node) {
+                    app.screen.compositor.mark_dirty(region);
+                }
+            }
+        }))
+    }
+
+    /// The cell style for one part: the header style with the part's
+    /// overrides on top.
+    fn part_style(&self, part: HeaderPart, styles: &Styles) -> CellStyle {
+        let style = match self.part_styles.get(&part) {
+            Some(overrides) => CellStyle::from_styles(&styles.merge(overrides)),
+            None => CellStyle::from_styles(styles),
+        };
+        if part == HeaderPart::ActiveTab && !self.part_styles.contains_key(&part) {
+            style.with_attrs(Attrs::REVERSE)
+        } else {
+            style
+        }
+    }
+
+    /// Screen columns `[start, end)` of each tab label.
+    fn tab_spans(&self, region: Region) -> Vec<(i32, i32)> {
+        let mut x = region.x + self.icon.as_ref().map_or(0, |icon| icon_width(icon));
+        self.tabs
+            .iter()
+            .map(|tab| {
+                let start = x;
+                x += tab.chars().count() as i32 + 2;
+                (start, x)
+            })
+            .collect()
+    }
+}
+
+/// Columns taken by the icon slot: the icon and a trailing space.
+fn icon_width(icon: &str) -> i32 {
+    icon.chars().count() as i32 + 1
 }
 
-/// Center `text` within `width` characters, returning a String padded with
-/// spaces on both sides. Truncates if text is wider than width.
-fn center_text(text: &str, width: usize) -> String {
-    let truncated: String = text.chars().take(width).collect();
-    let text_len = truncated.chars().count();
+/// Center the markup `text` within `width` characters, padded with unstyled
+/// spaces on both sides. Text wider than `width` is shortened per `overflow`.
+fn center_text(text: &str, width: usize, overflow: TextOverflow) -> Text {
+    let fitted = Text::from_markup(text).fit(width, overflow);
+    let text_len = fitted.width();
     if text_len >= width {
-        return truncated;
+        return fitted;
     }
     let pad_left = (width - text_len) / 2;
     let pad_right = width - text_len - pad_left;
-    format!(
-        "{}{}{}",
-        " ".repeat(pad_left),
-        truncated,
-        " ".repeat(pad_right)
-    )
+    let mut centered = Text::raw(" ".repeat(pad_left));
+    centered.append(&fitted);
+    centered.push(&" ".repeat(pad_right), CellStyle::default());
+    centered
 }
 
 impl Widget for Header {
@@ -89,24 +342,46 @@ impl Widget for Header {
 
         let style = CellStyle::from_styles(styles);
         let width = region.width as usize;
+        let overflow = styles.text_overflow.unwrap_or_default();
         let mut strips = Vec::new();
 
-        // Title on row 0
-        let title_text = center_text(&self.title, width);
-        let mut title_strip = Strip::new(region.y, region.x);
-        title_strip.push_str(&title_text, style.clone());
-        strips.push(title_strip);
+        // Row 0: [icon] [tabs]  title  [clock]
+        let mut row = Strip::new(region.y, region.x);
+        if let Some(icon) = &self.icon {
+            row.push_str(icon, self.part_style(HeaderPart::Icon, styles));
+            row.push(' ', style);
+        }
+        for (i, tab) in self.tabs.iter().enumerate() {
+            let part = if i == self.active_tab {
+                HeaderPart::ActiveTab
+            } else {
+                HeaderPart::Tab
+            };
+            row.push_str(&format!(" {tab} "), self.part_style(part, styles));
+        }
+        let clock = self
+            .clock
+            .as_ref()
+            .map(|clock| format!(" {} ", clock.borrow().text))
+            .unwrap_or_default();
+        let left = row.width();
+        let middle = (width as i32 - left - clock.chars().count() as i32).max(0);
+        center_text(&self.title, middle as usize, overflow)
+            .push_to(&mut row, self.part_style(HeaderPart::Title, styles));
+        row.push_str(&clock, self.part_style(HeaderPart::Clock, styles));
+        row.fill(region.width, style);
+        strips.push(row);
 
         // Subtitle on row 1 (if set and region is tall enough)
         if let Some(ref subtitle) = self.subtitle {
             if region.height >= 2 {
-                let sub_text = center_text(subtitle, width);
                 let mut sub_strip = Strip::new(region.y + 1, region.x);
-                sub_strip.push_str(&sub_text, style.clone());
+                center_text(subtitle, width, overflow).push_to(&mut sub_strip, style);
                 strips.push(sub_strip);
             }
         }
 
+        paint_background(&mut strips, region, styles);
         strips
     }
 
@@ -126,6 +401,7 @@ impl Widget for Header {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::render::strip::Color;
 
     fn region(w: i32, h: i32) -> Region {
         Region::new(0, 0, w, h)
@@ -197,6 +473,27 @@ mod tests {
         assert_eq!(strips[0].width(), 5);
     }
 
+    #[test]
+    fn render_ellipsizes_long_title() {
+        let h = Header::new("Very Long Title").with_clock(ClockFormat::HoursMinutes);
+        let mut s = styles();
+        s.text_overflow = Some(TextOverflow::Ellipsis);
+        let strips = h.render(region(15, 1), &s);
+        let text: String = strips[0].cells.iter().map(|c| c.ch).collect();
+        assert_eq!(text, "Very Lo… --:-- ");
+    }
+
+    #[test]
+    fn render_markup_title_and_subtitle() {
+        let h = Header::new("[b]My[/] App").with_subtitle("[i]v1[/]");
+        let strips = h.render(region(10, 2), &styles());
+        let title: String = strips[0].cells.iter().map(|c| c.ch).collect();
+        assert_eq!(title, "  My App  ");
+        assert!(strips[0].cells[2].style.bold() && !strips[0].cells[5].style.bold());
+        assert!(strips[1].cells[4].style.italic() && !strips[1].cells[3].style.italic());
+        assert_eq!(h.title(), "[b]My[/] App");
+    }
+
     #[test]
     fn title_and_subtitle_accessors() {
         let h = Header::new("T").with_subtitle("S");
@@ -213,7 +510,7 @@ mod tests {
         let mut s = styles();
         s.background = Some("cyan".into());
         let strips = h.render(region(10, 1), &s);
-        assert_eq!(strips[0].cells[0].style.bg, Some("cyan".into()));
+        assert_eq!(strips[0].cells[0].style.bg, Some(Color::Cyan));
     }
 
     #[test]
@@ -223,4 +520,81 @@ mod tests {
         let downcasted = any_ref.downcast_ref::<Header>().unwrap();
         assert_eq!(downcasted.title(), "test");
     }
+
+    // ── icon, tabs, clock ────────────────────────────────────────────
+
+    fn row_text(h: &Header, width: i32) -> String {
+        h.render(region(width, 1), &styles())[0]
+            .cells
+            .iter()
+            .map(|c| c.ch)
+            .collect()
+    }
+
+    #[test]
+    fn render_icon_tabs_and_clock() {
+        let mut h = Header::new("App")
+            .with_icon("*")
+            .with_tabs(["A", "Bb"])
+            .with_clock(ClockFormat::HoursMinutes)
+            .with_utc_offset(3600);
+        assert_eq!(row_text(&h, 24), "*  A  Bb   App    --:-- ");
+
+        let noon = UNIX_EPOCH + std::time::Duration::from_secs(11 * 3600 + 5 * 60 + 9);
+        assert!(h.update_clock(noon));
+        assert!(!h.update_clock(noon + std::time::Duration::from_secs(1)));
+        assert_eq!(h.clock_text().as_deref(), Some("12:05"));
+        assert!(row_text(&h, 24).ends_with(" 12:05 "));
+    }
+
+    #[test]
+    fn start_clock_ticks_every_second() {
+        use crate::app::App;
+        use crate::dom::node::NodeData;
+        use std::time::{Duration, Instant};
+
+        let mut app = App::new_headless(40, 5);
+        let node = app.screen.dom.insert(NodeData::new("Header"));
+        assert!(Header::new("App").start_clock(&mut app, node).is_none());
+        assert_eq!(app.pending_timers(), 0);
+
+        let h = Header::new("App").with_clock(ClockFormat::HoursMinutesSeconds);
+        let handle = h.start_clock(&mut app, node).unwrap();
+        // The clock is set right away, not left at the placeholder.
+        assert_ne!(h.clock_text().as_deref(), Some("--:--:--"));
+        assert_eq!(app.pending_timers(), 1);
+        assert_eq!(app.run_timers(Instant::now() + Duration::from_secs(1)), 1);
+        handle.cancel();
+        assert_eq!(app.run_timers(Instant::now() + Duration::from_secs(3)), 0);
+    }
+
+    #[test]
+    fn clicking_tabs_activates_them() {
+        let mut h = Header::new("App").with_icon("*").with_tabs(["A", "Bb"]);
+        let r = Region::new(10, 0, 30, 1);
+        assert_eq!(h.tab_at(10, r), None);
+        assert_eq!(h.tab_at(12, r), Some(0));
+        assert_eq!(h.click_tab(16, r), Some(1));
+        assert_eq!(h.active_tab(), Some(1));
+        assert_eq!(h.click_tab(25, r), None);
+
+        h.set_tabs(["Only"]);
+        assert_eq!(h.active_tab(), Some(0));
+    }
+
+    #[test]
+    fn part_styles_override_header_style() {
+        let mut clock = styles();
+        clock.color = Some("yellow".into());
+        let h = Header::new("")
+            .with_tabs(["A", "B"])
+            .with_clock(ClockFormat::HoursMinutes)
+            .with_part_style(HeaderPart::Clock, clock);
+        let strips = h.render(region(16, 1), &styles());
+        let cells = &strips[0].cells;
+        assert!(cells[1].style.attrs.contains(Attrs::REVERSE));
+        assert!(!cells[4].style.attrs.contains(Attrs::REVERSE));
+        assert_eq!(cells[15 - 3].style.fg, Some(Color::Yellow));
+        assert_eq!(cells[7].style.fg, None);
+    }
 }
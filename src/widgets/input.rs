@@ -2,13 +2,59 @@
 //!
 //! Supports cursor movement, character insertion/deletion, placeholder text,
 //! and password masking mode.
+//!
+//! [`Input::handle_key`] maps keys to edits: left/right and home/end move the
+//! cursor, ctrl+left/right jump by word, backspace and delete remove a
+//! character, ctrl+backspace removes the word before the cursor, and
+//! ctrl+z / ctrl+y undo and redo edits (see [`UndoStack`]). Words
+//! are runs of characters that are not word separators
+//! ([`Input::with_word_separators`]). Each key returns the [`Changed`] and
+//! [`CursorMoved`] messages describing what it did, for the app to post.
 
 use std::any::Any;
 
 use crate::css::styles::Styles;
+use crate::event::input::{Key, KeyEvent, Modifiers};
+use crate::event::message::Message;
 use crate::geometry::Region;
-use crate::render::strip::{CellStyle, Strip};
+use crate::render::strip::{Attrs, CellStyle, Strip};
 use crate::widget::traits::Widget;
+use crate::widget::undo::UndoStack;
+
+/// Characters that separate words by default.
+pub const DEFAULT_WORD_SEPARATORS: &str = " \t-_.,;:/\\()[]{}<>\"'";
+
+/// Emitted when a key changes the input's value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Changed {
+    /// The new value.
+    pub value: String,
+}
+
+impl Message for Changed {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn message_name(&self) -> &str {
+        "Input.Changed"
+    }
+}
+
+/// Emitted when a key moves the input's cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CursorMoved {
+    /// The new cursor position (byte offset).
+    pub position: usize,
+}
+
+impl Message for CursorMoved {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn message_name(&self) -> &str {
+        "Input.CursorMoved"
+    }
+}
 
 // ---------------------------------------------------------------------------
 // Input
@@ -31,6 +77,9 @@ pub struct Input {
     placeholder: String,
     cursor_position: usize,
     password: bool,
+    word_separators: String,
+    /// Value and cursor from before each edit made through `handle_key`.
+    history: UndoStack<(String, usize)>,
 }
 
 impl Input {
@@ -41,6 +90,8 @@ impl Input {
             placeholder: String::new(),
             cursor_position: 0,
             password: false,
+            word_separators: DEFAULT_WORD_SEPARATORS.to_owned(),
+            history: UndoStack::new(),
         }
     }
 
@@ -63,6 +114,20 @@ impl Input {
         self
     }
 
+    /// Set the characters that separate words for word movement and deletion
+    /// (builder pattern).
+    pub fn with_word_separators(mut self, separators: impl Into<String>) -> Self {
+        self.word_separators = separators.into();
+        self
+    }
+
+    /// Use `history` for undo and redo, e.g. to change its limit or grouping
+    /// window (builder pattern).
+    pub fn with_history(mut self, history: UndoStack<(String, usize)>) -> Self {
+        self.history = history;
+        self
+    }
+
     /// Return the current value.
     pub fn value(&self) -> &str {
         &self.value
@@ -130,11 +195,90 @@ impl Input {
         self.cursor_position = self.value.len();
     }
 
+    /// Move the cursor to the start of the current or previous word.
+    pub fn move_word_left(&mut self) {
+        self.cursor_position = self.word_start();
+    }
+
+    /// Move the cursor to the end of the current or next word.
+    pub fn move_word_right(&mut self) {
+        self.cursor_position = self.word_end();
+    }
+
+    /// Delete from the start of the current or previous word to the cursor
+    /// (ctrl+backspace).
+    pub fn delete_word_back(&mut self) {
+        let start = self.word_start();
+        self.value.drain(start..self.cursor_position);
+        self.cursor_position = start;
+    }
+
+    /// Undo the last group of edits. Returns whether there was one.
+    pub fn undo(&mut self) -> bool {
+        let current = (self.value.clone(), self.cursor_position);
+        self.restore(|history| history.undo(current))
+    }
+
+    /// Redo the last undone group of edits. Returns whether there was one.
+    pub fn redo(&mut self) -> bool {
+        let current = (self.value.clone(), self.cursor_position);
+        self.restore(|history| history.redo(current))
+    }
+
     /// Return the cursor position (byte offset).
     pub fn cursor_position(&self) -> usize {
         self.cursor_position
     }
 
+    /// Handle a key while focused, returning the messages describing the
+    /// edit: [`Changed`] if the value changed, then [`CursorMoved`] if the
+    /// cursor moved. Keys the input does not use return nothing.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> Vec<Box<dyn Message>> {
+        let (value, cursor) = (self.value.clone(), self.cursor_position);
+        let ctrl = key.modifiers.contains(Modifiers::CTRL);
+        match key.code {
+            Key::Char('z') if ctrl => {
+                self.undo();
+            }
+            Key::Char('y') if ctrl => {
+                self.redo();
+            }
+            Key::Char(ch) if !ctrl && !key.modifiers.contains(Modifiers::ALT) => {
+                self.insert_char(ch)
+            }
+            Key::Backspace if ctrl => self.delete_word_back(),
+            Key::Backspace => self.delete_char(),
+            Key::Delete => self.delete_forward(),
+            Key::Left if ctrl => self.move_word_left(),
+            Key::Right if ctrl => self.move_word_right(),
+            Key::Left => self.move_cursor_left(),
+            Key::Right => self.move_cursor_right(),
+            Key::Home => self.move_cursor_home(),
+            Key::End => self.move_cursor_end(),
+            _ => return Vec::new(),
+        }
+
+        let mut messages: Vec<Box<dyn Message>> = Vec::new();
+        let changed = self.value != value;
+        let undoing = ctrl && matches!(key.code, Key::Char('z' | 'y'));
+        if changed && !undoing {
+            self.history.record((value, cursor));
+        } else if !undoing {
+            self.history.break_group();
+        }
+        if changed {
+            messages.push(Box::new(Changed {
+                value: self.value.clone(),
+            }));
+        }
+        if self.cursor_position != cursor {
+            messages.push(Box::new(CursorMoved {
+                position: self.cursor_position,
+            }));
+        }
+        messages
+    }
+
     // -----------------------------------------------------------------------
     // Internal helpers
     // -----------------------------------------------------------------------
@@ -157,6 +301,44 @@ impl Input {
         pos
     }
 
+    /// Restore the state `step` takes from the history, if any.
+    fn restore(
+        &mut self,
+        step: impl FnOnce(&mut UndoStack<(String, usize)>) -> Option<(String, usize)>,
+    ) -> bool {
+        let Some((value, cursor)) = step(&mut self.history) else {
+            return false;
+        };
+        self.value = value;
+        self.cursor_position = cursor;
+        true
+    }
+
+    /// Whether `ch` separates words.
+    fn is_separator(&self, ch: char) -> bool {
+        self.word_separators.contains(ch)
+    }
+
+    /// The start of the word at or before the cursor: separators just before
+    /// the cursor are skipped, then the word itself.
+    fn word_start(&self) -> usize {
+        let before = &self.value[..self.cursor_position];
+        let mut chars = before.char_indices().rev().peekable();
+        while chars.next_if(|&(_, ch)| self.is_separator(ch)).is_some() {}
+        while chars.next_if(|&(_, ch)| !self.is_separator(ch)).is_some() {}
+        chars.peek().map_or(0, |&(i, ch)| i + ch.len_utf8())
+    }
+
+    /// The end of the word at or after the cursor: separators just after the
+    /// cursor are skipped, then the word itself.
+    fn word_end(&self) -> usize {
+        let after = &self.value[self.cursor_position..];
+        let mut chars = after.char_indices().peekable();
+        while chars.next_if(|&(_, ch)| self.is_separator(ch)).is_some() {}
+        while chars.next_if(|&(_, ch)| !self.is_separator(ch)).is_some() {}
+        self.cursor_position + chars.peek().map_or(after.len(), |&(i, _)| i)
+    }
+
     /// Display string: either the value (possibly masked) or the placeholder.
     fn display_text(&self) -> String {
         if self.value.is_empty() {
@@ -201,16 +383,16 @@ impl Widget for Input {
 
         // Placeholder text is rendered dim.
         if is_placeholder {
-            style.dim = true;
+            style.attrs.set(Attrs::DIM, true);
         }
 
         let mut strip = Strip::new(region.y, region.x);
         let truncated: String = display.chars().take(width).collect();
-        strip.push_str(&truncated, style.clone());
+        strip.push_str(&truncated, style);
 
         // Reset dim for fill padding if we used it for placeholder.
         if is_placeholder {
-            style.dim = false;
+            style.attrs.set(Attrs::DIM, false);
         }
         strip.fill(region.width, style);
 
@@ -283,7 +465,7 @@ mod tests {
         let text: String = strips[0].cells.iter().take(12).map(|c| c.ch).collect();
         assert_eq!(text, "Type here...");
         // Placeholder is rendered dim
-        assert!(strips[0].cells[0].style.dim);
+        assert!(strips[0].cells[0].style.dim());
     }
 
     #[test]
@@ -458,6 +640,145 @@ mod tests {
         assert_eq!(i.cursor_position(), 0);
     }
 
+    // -----------------------------------------------------------------------
+    // Word navigation
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn word_jumps_skip_separators() {
+        let mut i = Input::new().with_value("let foo.bar = 1");
+        i.move_word_left();
+        assert_eq!(i.cursor_position(), 14);
+        i.move_word_left();
+        i.move_word_left();
+        assert_eq!(i.cursor_position(), 8);
+        i.move_word_left();
+        assert_eq!(i.cursor_position(), 4);
+        i.move_word_right();
+        assert_eq!(i.cursor_position(), 7);
+        i.move_word_right();
+        assert_eq!(i.cursor_position(), 11);
+        i.move_cursor_home();
+        i.move_word_left();
+        assert_eq!(i.cursor_position(), 0);
+    }
+
+    #[test]
+    fn delete_word_back_and_custom_separators() {
+        let mut i = Input::new().with_value("path/to/caf\u{00e9}");
+        i.delete_word_back();
+        assert_eq!(i.value(), "path/to/");
+        i.delete_word_back();
+        assert_eq!(i.value(), "path/");
+
+        let mut i = Input::new()
+            .with_value("path/to/file")
+            .with_word_separators(" ");
+        i.delete_word_back();
+        assert_eq!(i.value(), "");
+    }
+
+    #[test]
+    fn handle_key_reports_changes() {
+        let name = |messages: Vec<Box<dyn Message>>| -> Vec<String> {
+            messages
+                .iter()
+                .map(|m| m.message_name().to_owned())
+                .collect()
+        };
+        let key = |code, modifiers| KeyEvent::new(code, modifiers);
+        let mut i = Input::new().with_value("ab");
+        assert_eq!(
+            name(i.handle_key(&key(Key::Char('c'), Modifiers::NONE))),
+            ["Input.Changed", "Input.CursorMoved"]
+        );
+        assert_eq!(
+            name(i.handle_key(&key(Key::Home, Modifiers::NONE))),
+            ["Input.CursorMoved"]
+        );
+        assert_eq!(
+            name(i.handle_key(&key(Key::Delete, Modifiers::NONE))),
+            ["Input.Changed"]
+        );
+        // Nothing to do, and keys the input does not use.
+        assert!(i.handle_key(&key(Key::Left, Modifiers::NONE)).is_empty());
+        assert!(i
+            .handle_key(&key(Key::Char('a'), Modifiers::CTRL))
+            .is_empty());
+        assert!(i.handle_key(&key(Key::Enter, Modifiers::NONE)).is_empty());
+        assert_eq!(i.value(), "bc");
+    }
+
+    #[test]
+    fn ctrl_z_and_ctrl_y_undo_typing_bursts() {
+        let key = |code, modifiers| KeyEvent::new(code, modifiers);
+        let mut i = Input::new().with_value("x");
+        for ch in "abc".chars() {
+            i.handle_key(&key(Key::Char(ch), Modifiers::NONE));
+        }
+        // Moving the cursor closes the burst.
+        i.handle_key(&key(Key::Home, Modifiers::NONE));
+        i.handle_key(&key(Key::Char('>'), Modifiers::NONE));
+        assert_eq!(i.value(), ">xabc");
+
+        let undo = key(Key::Char('z'), Modifiers::CTRL);
+        assert_eq!(i.handle_key(&undo).len(), 2);
+        assert_eq!((i.value(), i.cursor_position()), ("xabc", 0));
+        i.handle_key(&undo);
+        assert_eq!((i.value(), i.cursor_position()), ("x", 1));
+        assert!(i.handle_key(&undo).is_empty());
+
+        i.handle_key(&key(Key::Char('y'), Modifiers::CTRL));
+        assert_eq!(i.value(), "xabc");
+        assert!(i.redo());
+        assert_eq!(i.value(), ">xabc");
+        assert!(!i.redo());
+    }
+
+    #[test]
+    fn pilot_drives_word_editing() {
+        use crate::dom::node::NodeData;
+        use crate::testing::pilot::Pilot;
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let mut pilot = Pilot::new(80, 24);
+        let dom = &mut pilot.app_mut().screen.dom;
+        let root = dom.insert(NodeData::new("Screen"));
+        let node = dom.insert_child(root, NodeData::new("Input"));
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let changes = log.clone();
+        pilot
+            .app_mut()
+            .on::<Changed>(root, "Input", move |changed, _| {
+                changes.borrow_mut().push(changed.value.clone());
+                true
+            })
+            .unwrap();
+        let moves = Rc::new(RefCell::new(Vec::new()));
+        let positions = moves.clone();
+        pilot
+            .app_mut()
+            .on::<CursorMoved>(root, "Input", move |moved, _| {
+                positions.borrow_mut().push(moved.position);
+                true
+            })
+            .unwrap();
+
+        let mut input = Input::new().with_value("hello big world");
+        let mut press = |pilot: &mut Pilot, key, modifiers| {
+            pilot.press_key_on(node, key, modifiers, |key| input.handle_key(key));
+            pilot.process();
+        };
+        press(&mut pilot, Key::Left, Modifiers::CTRL);
+        press(&mut pilot, Key::Backspace, Modifiers::CTRL);
+        press(&mut pilot, Key::End, Modifiers::NONE);
+        press(&mut pilot, Key::End, Modifiers::NONE);
+
+        assert_eq!(*log.borrow(), ["hello world"]);
+        assert_eq!(*moves.borrow(), [10, 6, 11]);
+    }
+
     // -----------------------------------------------------------------------
     // Rendering
     // -----------------------------------------------------------------------
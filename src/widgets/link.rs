@@ -0,0 +1,181 @@
+//! Link widget: text that opens a URL.
+//!
+//! The text is drawn with the URL attached to every cell, so terminals that
+//! support OSC 8 make it a real hyperlink (and the driver underlines it on
+//! those that don't). The widget also handles clicks and Enter itself,
+//! returning a [`LinkClicked`] message the app can act on, e.g. by launching
+//! a browser, which is the only way to follow a link on terminals without
+//! OSC 8 or with mouse capture enabled.
+
+use std::any::Any;
+
+use crate::css::styles::{Styles, TextOverflow};
+use crate::event::input::{Key, KeyEvent, MouseAction, MouseBtn, MouseEvent};
+use crate::event::message::Message;
+use crate::geometry::Region;
+use crate::render::strip::{CellStyle, Strip};
+use crate::render::text::{fit, text_width};
+use crate::widget::traits::Widget;
+
+/// Emitted when a link is activated by click or Enter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkClicked(pub String);
+
+impl Message for LinkClicked {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn message_name(&self) -> &str {
+        "LinkClicked"
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Link
+// ---------------------------------------------------------------------------
+
+/// A single line of hyperlinked text.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut docs = Link::new("Read the docs", "https://docs.rs/gilt-tui");
+/// if let Some(LinkClicked(url)) = docs.handle_mouse(&mouse, region) {
+///     open_in_browser(&url);
+/// }
+/// ```
+pub struct Link {
+    text: String,
+    url: String,
+}
+
+impl Link {
+    /// Create a link showing `text` that points at `url`.
+    pub fn new(text: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            url: url.into(),
+        }
+    }
+
+    /// Create a link that shows its own URL.
+    pub fn url_only(url: impl Into<String>) -> Self {
+        let url = url.into();
+        Self::new(url.clone(), url)
+    }
+
+    /// The displayed text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The target URL.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Handle a mouse event for the link at `region`: a left click on the
+    /// text activates it.
+    pub fn handle_mouse(&self, event: &MouseEvent, region: Region) -> Option<LinkClicked> {
+        let width = (text_width(&self.text) as i32).min(region.width);
+        let (x, y) = (event.x as i32, event.y as i32);
+        let hit = y == region.y && (region.x..region.x + width).contains(&x);
+        (event.kind == MouseAction::Down(MouseBtn::Left) && hit)
+            .then(|| LinkClicked(self.url.clone()))
+    }
+
+    /// Handle a key while focused: Enter activates the link.
+    pub fn handle_key(&self, key: &KeyEvent) -> Option<LinkClicked> {
+        (key.code == Key::Enter).then(|| LinkClicked(self.url.clone()))
+    }
+}
+
+impl Widget for Link {
+    fn widget_type(&self) -> &str {
+        "Link"
+    }
+
+    fn default_css(&self) -> &str {
+        "Link { height: 1; width: auto; color: cyan; }"
+    }
+
+    fn can_focus(&self) -> bool {
+        true
+    }
+
+    fn render(&self, region: Region, styles: &Styles) -> Vec<Strip> {
+        if region.width <= 0 || region.height <= 0 {
+            return Vec::new();
+        }
+        let style = CellStyle::from_styles(styles);
+        let text = fit(&self.text, region.width as usize, TextOverflow::Ellipsis);
+        let mut strip = Strip::new(region.y, region.x);
+        strip.push_str(&text, style.with_link(&self.url));
+        strip.fill(region.width, style);
+        vec![strip]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::input::Modifiers;
+    use crate::render::link::LinkId;
+
+    fn click(x: u16, y: u16) -> MouseEvent {
+        MouseEvent {
+            kind: MouseAction::Down(MouseBtn::Left),
+            x,
+            y,
+            modifiers: Modifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn text_cells_carry_the_link() {
+        let link = Link::new("docs", "https://docs.rs");
+        let strip = &link.render(Region::new(0, 0, 6, 1), &Styles::new())[0];
+        let id = LinkId::intern("https://docs.rs");
+        assert!(strip.cells[..4].iter().all(|c| c.style.link == id));
+        assert_eq!(strip.cells[4].style.link, None);
+    }
+
+    #[test]
+    fn click_on_text_activates() {
+        let link = Link::url_only("https://x.io");
+        let region = Region::new(2, 1, 20, 1);
+        assert_eq!(
+            link.handle_mouse(&click(3, 1), region),
+            Some(LinkClicked("https://x.io".into()))
+        );
+        // Past the end of the text, or on another row.
+        assert_eq!(link.handle_mouse(&click(15, 1), region), None);
+        assert_eq!(link.handle_mouse(&click(3, 0), region), None);
+    }
+
+    #[test]
+    fn enter_activates() {
+        let link = Link::new("home", "https://example.com");
+        let enter = KeyEvent::new(Key::Enter, Modifiers::NONE);
+        assert_eq!(
+            link.handle_key(&enter),
+            Some(LinkClicked("https://example.com".into()))
+        );
+        assert_eq!(
+            link.handle_key(&KeyEvent::new(Key::Char('x'), Modifiers::NONE)),
+            None
+        );
+    }
+}
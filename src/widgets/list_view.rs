@@ -0,0 +1,511 @@
+//! ListView widget: a scrolling, cursor-driven list over a [`DataSource`].
+//!
+//! Only the rows in the viewport (plus prefetch) are ever requested from the
+//! source, so the list can page through millions of remote items. Rows whose
+//! page is still loading are drawn with a dimmed placeholder. Selection
+//! follows the shared [`SelectionModel`] conventions, with one or many rows
+//! selectable.
+//!
+//! The list is [`Filterable`], so a `FilterBar` can narrow it to the rows
+//! matching a query. Only rows that have been fetched can match: searching a
+//! remote source would mean fetching all of it.
+
+use std::any::Any;
+
+use crate::css::styles::Styles;
+use crate::event::input::{Key, KeyEvent, MouseAction, MouseBtn, MouseEvent};
+use crate::geometry::{Region, Size};
+use crate::render::strip::{Attrs, CellStyle, Strip};
+use crate::t;
+use crate::widget::data_source::{DataSource, PagedCache};
+use crate::widget::filter::{FilterMatch, Filterable};
+use crate::widget::scroll::ScrollState;
+use crate::widget::selection::{Selected, SelectionMode, SelectionModel};
+use crate::widget::traits::Widget;
+
+// ---------------------------------------------------------------------------
+// ListView
+// ---------------------------------------------------------------------------
+
+/// A vertical list of text rows backed by a lazily fetched data source.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut list = ListView::new(RemoteLog::connect(url)).with_prefetch(3);
+/// list.set_viewport(20);
+/// // Later, when the fetch for rows 100.. completes:
+/// list.deliver(100, rows);
+/// ```
+pub struct ListView {
+    source: Box<dyn DataSource<Item = String>>,
+    cache: PagedCache<String>,
+    scroll: ScrollState,
+    selection: SelectionModel,
+    placeholder: Option<String>,
+    /// The rows shown while a filter is active, in ascending index order.
+    filter: Option<Vec<FilterMatch>>,
+}
+
+impl ListView {
+    /// Create a list over `source`, fetching 100 rows per page.
+    pub fn new(source: impl DataSource<Item = String> + 'static) -> Self {
+        let total = source.total();
+        let mut selection = SelectionModel::new(SelectionMode::Single);
+        selection.set_len(total);
+        selection.select(0);
+        Self {
+            source: Box::new(source),
+            cache: PagedCache::new(100),
+            scroll: ScrollState::new(Size::new(0, total as i32), Size::new(0, 0)),
+            selection,
+            placeholder: None,
+            filter: None,
+        }
+    }
+
+    /// Allow one or many selected rows (builder). Defaults to one.
+    pub fn with_selection_mode(mut self, mode: SelectionMode) -> Self {
+        self.selection.set_mode(mode);
+        self
+    }
+
+    /// Set the page size used when fetching (builder).
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.cache = PagedCache::new(page_size);
+        self
+    }
+
+    /// Set how many pages to fetch beyond the viewport (builder).
+    ///
+    /// Apply after [`ListView::with_page_size`], which resets it.
+    pub fn with_prefetch(mut self, pages: usize) -> Self {
+        self.cache = self.cache.with_prefetch(pages);
+        self
+    }
+
+    /// Set the text drawn for rows that are still loading (builder).
+    /// Defaults to the `list-view.loading` message.
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = Some(placeholder.into());
+        self
+    }
+
+    /// Total number of rows in the source.
+    pub fn total(&self) -> usize {
+        self.source.total()
+    }
+
+    /// Position of the first visible row among the shown rows.
+    pub fn offset(&self) -> usize {
+        self.scroll.offset.y as usize
+    }
+
+    /// Number of rows shown: the filter's matches, or every row.
+    fn shown(&self) -> usize {
+        self.filter.as_ref().map_or(self.total(), Vec::len)
+    }
+
+    /// Source index of the shown row at `pos`.
+    fn shown_index(&self, pos: usize) -> usize {
+        self.filter
+            .as_ref()
+            .map_or(pos, |matches| matches[pos].index)
+    }
+
+    /// Position of row `index` among the shown rows, if it is shown.
+    fn shown_pos(&self, index: usize) -> Option<usize> {
+        match &self.filter {
+            Some(matches) => matches.binary_search_by_key(&index, |m| m.index).ok(),
+            None => Some(index),
+        }
+    }
+
+    /// Index of the row under the cursor.
+    pub fn cursor(&self) -> usize {
+        self.selection.cursor()
+    }
+
+    /// Indices of the selected rows, in ascending order.
+    pub fn selected(&self) -> Vec<usize> {
+        self.selection.selected()
+    }
+
+    /// The row at `index`, if loaded.
+    pub fn row(&self, index: usize) -> Option<&str> {
+        self.cache.get(index).map(String::as_str)
+    }
+
+    /// Mutable access to the data source.
+    pub fn source_mut(&mut self) -> &mut dyn DataSource<Item = String> {
+        self.source.as_mut()
+    }
+
+    /// Fetch whatever the current window needs.
+    fn request_window(&mut self) {
+        let start = self.offset();
+        let end = start + self.scroll.viewport_size.height.max(1) as usize;
+        if self.filter.is_none() {
+            self.cache.request(self.source.as_mut(), start..end);
+            return;
+        }
+        for pos in start..end.min(self.shown()) {
+            let index = self.shown_index(pos);
+            self.cache.request(self.source.as_mut(), index..index + 1);
+        }
+    }
+
+    /// Set the number of visible rows and fetch the window.
+    pub fn set_viewport(&mut self, height: i32) {
+        self.scroll.set_viewport_size(Size::new(0, height.max(0)));
+        self.request_window();
+    }
+
+    /// Scroll so `row` is the first visible row (clamped).
+    pub fn scroll_to(&mut self, row: usize) {
+        self.scroll.scroll_to(0, row as i32);
+        self.request_window();
+    }
+
+    /// Scroll by `delta` rows.
+    pub fn scroll_by(&mut self, delta: i32) {
+        self.scroll.scroll_by(0, delta);
+        self.request_window();
+    }
+
+    /// Select only `row` and move the cursor there, scrolling it into view.
+    pub fn select(&mut self, row: usize) -> Option<Selected> {
+        let selected = self.selection.select(row);
+        self.reveal_cursor();
+        selected
+    }
+
+    /// Move the cursor down one shown row.
+    pub fn cursor_down(&mut self) {
+        self.step(1);
+    }
+
+    /// Move the cursor up one shown row.
+    pub fn cursor_up(&mut self) {
+        self.step(-1);
+    }
+
+    /// Select the shown row `delta` rows away from the cursor (clamped).
+    fn step(&mut self, delta: isize) -> Option<Selected> {
+        let last = self.shown().checked_sub(1)?;
+        let pos = match self.shown_pos(self.cursor()) {
+            Some(pos) => pos.saturating_add_signed(delta).min(last),
+            None => 0,
+        };
+        self.select(self.shown_index(pos))
+    }
+
+    /// Handle the selection keys (see [`SelectionModel::handle_key`]),
+    /// scrolling the cursor into view.
+    ///
+    /// While a filter is active, the arrow, page and Home/End keys move
+    /// between the shown rows and select the row they land on.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> Option<Selected> {
+        let height = self.scroll.viewport_size.height.max(1) as usize;
+        if self.filter.is_some() {
+            let page = height as isize;
+            let delta = match key.code {
+                Key::Up => Some(-1),
+                Key::Down => Some(1),
+                Key::PageUp => Some(-page),
+                Key::PageDown => Some(page),
+                Key::Home => Some(isize::MIN),
+                Key::End => Some(isize::MAX),
+                _ => None,
+            };
+            if let Some(delta) = delta {
+                return self.step(delta);
+            }
+        }
+        self.selection.set_page(height);
+        let selected = self.selection.handle_key(key);
+        self.reveal_cursor();
+        selected
+    }
+
+    /// Handle a click on the list at `region`: selects the row under the
+    /// pointer, toggling it with Ctrl or selecting a range with Shift.
+    pub fn handle_mouse(&mut self, event: &MouseEvent, region: Region) -> Option<Selected> {
+        let (x, y) = (event.x as i32, event.y as i32);
+        if event.kind != MouseAction::Down(MouseBtn::Left) || !region.contains(x, y) {
+            return None;
+        }
+        let pos = self.offset() + (y - region.y) as usize;
+        if pos >= self.shown() {
+            return None;
+        }
+        self.selection.click(self.shown_index(pos), event.modifiers)
+    }
+
+    /// Scroll so the cursor row is visible, if it is shown.
+    fn reveal_cursor(&mut self) {
+        let Some(cursor) = self.shown_pos(self.cursor()) else {
+            return;
+        };
+        let height = self.scroll.viewport_size.height.max(1) as usize;
+        if cursor < self.offset() {
+            self.scroll_to(cursor);
+        } else if cursor >= self.offset() + height {
+            self.scroll_to(cursor + 1 - height);
+        }
+    }
+
+    /// Deliver rows for a pending fetch that started at `start`.
+    pub fn deliver(&mut self, start: usize, rows: Vec<String>) -> bool {
+        self.cache.fill(start, rows)
+    }
+
+    /// Discard cached rows and re-read the source (e.g. after its total
+    /// changed).
+    pub fn refresh(&mut self) {
+        self.cache.clear();
+        self.filter = None;
+        let total = self.total() as i32;
+        self.scroll.set_content_size(Size::new(0, total));
+        self.selection.set_len(self.total());
+        self.request_window();
+    }
+}
+
+impl Filterable for ListView {
+    fn item_count(&self) -> usize {
+        self.total()
+    }
+
+    /// The row's text, or nothing if it has not been fetched.
+    fn item_text(&self, index: usize) -> String {
+        self.row(index).unwrap_or_default().to_owned()
+    }
+
+    fn apply_filter(&mut self, matches: Option<&[FilterMatch]>) {
+        self.filter = matches.map(|matches| {
+            let mut matches = matches.to_vec();
+            matches.sort_by_key(|m| m.index);
+            matches
+        });
+        let shown = self.shown() as i32;
+        self.scroll.set_content_size(Size::new(0, shown));
+        self.scroll_to(0);
+        self.reveal_cursor();
+    }
+
+    fn jump_to(&mut self, index: usize) {
+        if index < self.total() {
+            self.select(index);
+        }
+    }
+}
+
+impl Widget for ListView {
+    fn widget_type(&self) -> &str {
+        "ListView"
+    }
+
+    fn default_css(&self) -> &str {
+        "ListView { width: 1fr; height: 1fr; }"
+    }
+
+    fn can_focus(&self) -> bool {
+        true
+    }
+
+    fn render(&self, region: Region, styles: &Styles) -> Vec<Strip> {
+        if region.width <= 0 || region.height <= 0 {
+            return Vec::new();
+        }
+
+        let style = CellStyle::from_styles(styles);
+        let loading = style.with_attrs(Attrs::DIM);
+        let width = region.width as usize;
+        let shown = self.shown();
+        let placeholder = self
+            .placeholder
+            .clone()
+            .unwrap_or_else(|| t!("list-view.loading"));
+
+        (0..region.height)
+            .map(|row| {
+                let pos = self.offset() + row as usize;
+                let mut strip = Strip::new(region.y + row, region.x);
+                if pos < shown {
+                    let index = self.shown_index(pos);
+                    let (text, row_style) = match self.row(index) {
+                        Some(text) => (text, &style),
+                        None => (placeholder.as_str(), &loading),
+                    };
+                    let row_style = row_style.with_attrs(self.selection.attrs(index));
+                    let matched = row_style.with_attrs(Attrs::BOLD | Attrs::UNDERLINE);
+                    let ranges = self.filter.as_ref().map(|f| &f[pos].ranges);
+                    for (i, ch) in text.chars().take(width).enumerate() {
+                        let hit = ranges.is_some_and(|r| r.iter().any(|r| r.contains(&i)));
+                        strip.push(ch, if hit { matched } else { row_style });
+                    }
+                    strip.fill(region.width, row_style);
+                }
+                strip.fill(region.width, style);
+                strip
+            })
+            .collect()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::data_source::Fetch;
+    use std::cell::RefCell;
+    use std::ops::Range;
+    use std::rc::Rc;
+
+    /// A million-row source that answers asynchronously.
+    #[derive(Default)]
+    struct Remote {
+        requests: Rc<RefCell<Vec<Range<usize>>>>,
+    }
+
+    impl DataSource for Remote {
+        type Item = String;
+
+        fn total(&self) -> usize {
+            1_000_000
+        }
+
+        fn fetch(&mut self, range: Range<usize>) -> Fetch<String> {
+            self.requests.borrow_mut().push(range);
+            Fetch::Pending
+        }
+    }
+
+    fn rows(list: &ListView, height: i32) -> Vec<String> {
+        list.render(Region::new(0, 0, 12, height), &Styles::new())
+            .iter()
+            .map(|s| s.cells.iter().map(|c| c.ch).collect::<String>())
+            .map(|s| s.trim_end().to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn placeholders_until_delivered() {
+        let mut list = ListView::new(Remote::default())
+            .with_page_size(10)
+            .with_prefetch(0);
+        list.set_viewport(3);
+        assert_eq!(rows(&list, 3), vec!["Loading…"; 3]);
+        assert!(
+            list.render(Region::new(0, 0, 12, 3), &Styles::new())[1].cells[0]
+                .style
+                .dim()
+        );
+
+        let page: Vec<String> = (0..10).map(|i| format!("row {i}")).collect();
+        assert!(list.deliver(0, page));
+        assert_eq!(rows(&list, 3), vec!["row 0", "row 1", "row 2"]);
+    }
+
+    #[test]
+    fn scrolling_requests_only_the_window() {
+        let remote = Remote::default();
+        let requests = remote.requests.clone();
+        let mut list = ListView::new(remote).with_page_size(50).with_prefetch(1);
+        list.set_viewport(20);
+        list.scroll_to(999_990);
+        // The source only ever saw page-sized requests near the viewport.
+        assert_eq!(*requests.borrow(), vec![0..50, 50..100, 999_950..1_000_000]);
+        assert_eq!(list.offset(), 999_980);
+    }
+
+    #[test]
+    fn cursor_scrolls_into_view() {
+        let items: Vec<String> = (0..100).map(|i| i.to_string()).collect();
+        let mut list = ListView::new(items);
+        list.set_viewport(5);
+        list.select(7);
+        assert_eq!(list.offset(), 3);
+        list.cursor_up();
+        list.select(2);
+        assert_eq!(list.offset(), 2);
+        assert_eq!(rows(&list, 1), vec!["2"]);
+    }
+
+    #[test]
+    fn multi_selection_keys_and_clicks() {
+        use crate::event::input::{Key, Modifiers};
+
+        let items: Vec<String> = (0..100).map(|i| i.to_string()).collect();
+        let mut list = ListView::new(items).with_selection_mode(SelectionMode::Multi);
+        list.set_viewport(5);
+        for _ in 0..6 {
+            list.handle_key(&KeyEvent::new(Key::Down, Modifiers::SHIFT));
+        }
+        assert_eq!(list.selected(), (0..=6).collect::<Vec<_>>());
+        assert_eq!(list.offset(), 2);
+
+        let click = MouseEvent {
+            kind: MouseAction::Down(MouseBtn::Left),
+            x: 1,
+            y: 1,
+            modifiers: Modifiers::CTRL,
+        };
+        let region = Region::new(0, 0, 12, 5);
+        let selected = list.handle_mouse(&click, region).unwrap();
+        assert_eq!(selected.cursor, 3);
+        assert_eq!(selected.selected, [0, 1, 2, 4, 5, 6]);
+        let strips = list.render(region, &Styles::new());
+        assert!(strips[0].cells[0].style.attrs.contains(Attrs::REVERSE));
+        assert!(strips[1].cells[0].style.attrs.contains(Attrs::UNDERLINE));
+    }
+
+    #[test]
+    fn filter_shows_loaded_matches() {
+        use crate::event::input::Modifiers;
+        use crate::widget::filter::filter_items;
+
+        let items: Vec<String> = (0..300).map(|i| format!("item {i}")).collect();
+        let mut list = ListView::new(items).with_prefetch(0);
+        list.set_viewport(3);
+        // Only the first page has been fetched, so "item 250" cannot match.
+        let matches = filter_items(&list, "5");
+        assert_eq!(matches.len(), 19);
+        list.apply_filter(Some(&matches));
+        assert_eq!(rows(&list, 3), vec!["item 5", "item 15", "item 25"]);
+        let strips = list.render(Region::new(0, 0, 12, 3), &Styles::new());
+        assert!(strips[1].cells[6].style.underline());
+        assert!(!strips[1].cells[5].style.underline());
+
+        // Keys move between the matches, scrolling through them.
+        list.handle_key(&KeyEvent::new(Key::Down, Modifiers::NONE));
+        list.handle_key(&KeyEvent::new(Key::End, Modifiers::NONE));
+        assert_eq!(list.cursor(), 95);
+        assert_eq!(list.offset(), 16);
+        let click = MouseEvent {
+            kind: MouseAction::Down(MouseBtn::Left),
+            x: 0,
+            y: 0,
+            modifiers: Modifiers::NONE,
+        };
+        let selected = list.handle_mouse(&click, Region::new(0, 0, 12, 3));
+        assert_eq!(selected.unwrap().cursor, 75);
+
+        list.jump_to(59);
+        list.apply_filter(None);
+        assert_eq!(list.cursor(), 59);
+        assert_eq!(rows(&list, 3), vec!["item 57", "item 58", "item 59"]);
+    }
+}
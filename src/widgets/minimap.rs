@@ -0,0 +1,274 @@
+//! Minimap widget: a shrunken overview of a large scrollable view.
+//!
+//! A [`Minimap`] draws the text of a scrollable sibling (a `ScrollView`
+//! around a `CodeView`, say) scaled down to fit its own height, each cell
+//! shaded by how much text it covers, and highlights the rows the viewport
+//! currently shows. It follows scrolling through the sibling's offset signal
+//! (`ScrollView::observe_offset`), so it never needs to be told explicitly.
+//! Clicking a row returns a [`MinimapJump`] to the matching position, which
+//! the app applies to the sibling.
+
+use std::any::Any;
+
+use crate::css::styles::Styles;
+use crate::event::input::{MouseAction, MouseBtn, MouseEvent};
+use crate::event::message::Message;
+use crate::geometry::{Offset, Region};
+use crate::reactive::ReadSignal;
+use crate::render::strip::{Attrs, CellStyle, Strip};
+use crate::widget::traits::Widget;
+
+/// Source columns shown per minimap column.
+const X_SCALE: usize = 2;
+
+/// Shades for increasing text density within a cell.
+const SHADES: [char; 4] = [' ', '░', '▒', '▓'];
+
+/// Emitted when the minimap is clicked: scroll the sibling so its top row is
+/// this line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MinimapJump(pub i32);
+
+impl Message for MinimapJump {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn message_name(&self) -> &str {
+        "MinimapJump"
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Minimap
+// ---------------------------------------------------------------------------
+
+/// An overview of scrollable text with the visible part highlighted.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut view = ScrollView::new(CodeView::new(&source), content_size);
+/// let mut map = Minimap::new(view.observe_offset(), 24).with_content(&source);
+/// if let Some(MinimapJump(top)) = map.handle_mouse(&mouse, map_region) {
+///     view.scroll_to(0, top);
+/// }
+/// ```
+pub struct Minimap {
+    lines: Vec<String>,
+    offset: ReadSignal<Offset>,
+    viewport_height: i32,
+}
+
+impl Minimap {
+    /// Create a minimap following `offset`, for a viewport `viewport_height`
+    /// rows tall.
+    pub fn new(offset: ReadSignal<Offset>, viewport_height: i32) -> Self {
+        Self {
+            lines: Vec::new(),
+            offset,
+            viewport_height: viewport_height.max(1),
+        }
+    }
+
+    /// Set the text to overview (builder pattern).
+    pub fn with_content(mut self, text: &str) -> Self {
+        self.set_content(text);
+        self
+    }
+
+    /// Replace the text to overview.
+    pub fn set_content(&mut self, text: &str) {
+        self.lines = text.lines().map(str::to_owned).collect();
+    }
+
+    /// Update the height of the sibling's viewport.
+    pub fn set_viewport_height(&mut self, height: i32) {
+        self.viewport_height = height.max(1);
+    }
+
+    /// Source lines shown per minimap row, for a minimap `height` rows tall.
+    pub fn scale(&self, height: i32) -> usize {
+        let height = height.max(1) as usize;
+        self.lines.len().div_ceil(height).max(1)
+    }
+
+    /// The minimap rows that cover the sibling's visible lines.
+    pub fn viewport_rows(&self, height: i32) -> std::ops::Range<i32> {
+        let scale = self.scale(height) as i32;
+        let top = self.offset.get().y;
+        let bottom = top + self.viewport_height;
+        top / scale..(bottom + scale - 1) / scale
+    }
+
+    /// Handle a mouse event for the minimap at `region`: a left click or drag
+    /// centers the viewport on the clicked row.
+    pub fn handle_mouse(&self, event: &MouseEvent, region: Region) -> Option<MinimapJump> {
+        let pressed = matches!(
+            event.kind,
+            MouseAction::Down(MouseBtn::Left) | MouseAction::Drag(MouseBtn::Left)
+        );
+        let (x, y) = (event.x as i32, event.y as i32);
+        if !pressed || !region.contains(x, y) {
+            return None;
+        }
+        let line = (y - region.y) * self.scale(region.height) as i32;
+        let max_top = (self.lines.len() as i32 - self.viewport_height).max(0);
+        Some(MinimapJump(
+            (line - self.viewport_height / 2).clamp(0, max_top),
+        ))
+    }
+
+    /// The shade for the block of `scale` lines starting at `first`, columns
+    /// `col * X_SCALE ..`.
+    fn shade(&self, first: usize, scale: usize, col: usize) -> char {
+        let start = col * X_SCALE;
+        let filled: usize = self
+            .lines
+            .iter()
+            .skip(first)
+            .take(scale)
+            .map(|line| {
+                line.chars()
+                    .skip(start)
+                    .take(X_SCALE)
+                    .filter(|c| !c.is_whitespace())
+                    .count()
+            })
+            .sum();
+        let area = scale * X_SCALE;
+        SHADES[(filled * (SHADES.len() - 1)).div_ceil(area)]
+    }
+}
+
+impl Widget for Minimap {
+    fn widget_type(&self) -> &str {
+        "Minimap"
+    }
+
+    fn default_css(&self) -> &str {
+        "Minimap { width: 12; height: 1fr; }"
+    }
+
+    fn render(&self, region: Region, styles: &Styles) -> Vec<Strip> {
+        if region.width <= 0 || region.height <= 0 {
+            return Vec::new();
+        }
+        let style = CellStyle::from_styles(styles);
+        let highlight = style.with_attrs(Attrs::REVERSE);
+        let scale = self.scale(region.height);
+        let visible = self.viewport_rows(region.height);
+        (0..region.height)
+            .map(|row| {
+                let mut strip = Strip::new(region.y + row, region.x);
+                let style = if visible.contains(&row) {
+                    highlight
+                } else {
+                    style
+                };
+                let first = row as usize * scale;
+                for col in 0..region.width as usize {
+                    strip.push(self.shade(first, scale, col), style);
+                }
+                strip
+            })
+            .collect()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::input::Modifiers;
+    use crate::reactive::create_signal;
+    use crate::reactive::signal::reset_runtime;
+
+    /// Twenty lines: even ones full, odd ones empty.
+    fn source() -> String {
+        (0..20)
+            .map(|i| if i % 2 == 0 { "xxxxxxxx" } else { "" })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn rows(map: &Minimap, width: i32, height: i32) -> Vec<Strip> {
+        map.render(Region::new(0, 0, width, height), &Styles::new())
+    }
+
+    #[test]
+    fn shrinks_content_and_shades_density() {
+        reset_runtime();
+        let (offset, _) = create_signal(Offset::new(0, 0));
+        let map = Minimap::new(offset, 4).with_content(&source());
+        assert_eq!(map.scale(5), 4);
+        let strips = rows(&map, 5, 5);
+        let text: String = strips[0].cells.iter().map(|c| c.ch).collect();
+        // Half of each 4x2 block is text.
+        assert_eq!(text, "▒▒▒▒ ");
+        assert_eq!(strips.len(), 5);
+    }
+
+    #[test]
+    fn highlight_follows_offset_signal() {
+        reset_runtime();
+        let (offset, set_offset) = create_signal(Offset::new(0, 0));
+        let map = Minimap::new(offset, 4).with_content(&source());
+        let reversed = |strips: &[Strip]| -> Vec<bool> {
+            strips
+                .iter()
+                .map(|s| s.cells[0].style.attrs.contains(Attrs::REVERSE))
+                .collect()
+        };
+        assert_eq!(
+            reversed(&rows(&map, 2, 5)),
+            [true, false, false, false, false]
+        );
+        set_offset.set(Offset::new(0, 10));
+        assert_eq!(map.viewport_rows(5), 2..4);
+        assert_eq!(
+            reversed(&rows(&map, 2, 5)),
+            [false, false, true, true, false]
+        );
+    }
+
+    #[test]
+    fn click_jumps_to_row() {
+        reset_runtime();
+        let (offset, _) = create_signal(Offset::new(0, 0));
+        let map = Minimap::new(offset, 4).with_content(&source());
+        let region = Region::new(30, 2, 5, 5);
+        let click = |kind, y| MouseEvent {
+            kind,
+            x: 31,
+            y,
+            modifiers: Modifiers::NONE,
+        };
+        let down = MouseAction::Down(MouseBtn::Left);
+        assert_eq!(
+            map.handle_mouse(&click(down, 4), region),
+            Some(MinimapJump(6))
+        );
+        assert_eq!(
+            map.handle_mouse(&click(down, 2), region),
+            Some(MinimapJump(0))
+        );
+        assert_eq!(
+            map.handle_mouse(&click(down, 6), region),
+            Some(MinimapJump(14))
+        );
+        assert_eq!(map.handle_mouse(&click(down, 9), region), None);
+        let up = MouseAction::Up(MouseBtn::Left);
+        assert_eq!(map.handle_mouse(&click(up, 4), region), None);
+    }
+}
@@ -1,4 +1,8 @@
-//! Built-in widgets: Static, Container, Button, Header, Footer, Input.
+//! Built-in widgets: Static, Container, Button, Header, Footer, Input, PerfHud,
+//! ContextMenu, StatusBar, MultiProgress, CheckTree,
+//! FilterBar, ListView, DataTable, ScrollView, ScrollableContainer, Panel,
+//! Breadcrumbs, ErrorBoundary, Secret, Link, CodeView, Minimap, Tabs,
+//! CommandPalette.
 
 pub mod static_widget;
 pub mod container;
@@ -6,10 +10,50 @@ pub mod button;
 pub mod header;
 pub mod footer;
 pub mod input;
+pub mod perf_hud;
+pub mod context_menu;
+pub mod status_bar;
+pub mod multi_progress;
+pub mod check_tree;
+pub mod filter_bar;
+pub mod list_view;
+pub mod data_table;
+pub mod scroll_view;
+pub mod scrollable_container;
+pub mod panel;
+pub mod breadcrumbs;
+pub mod error_boundary;
+pub mod secret;
+pub mod link;
+pub mod code_view;
+pub mod minimap;
+pub mod tabs;
+pub mod command_palette;
+#[cfg(feature = "stress")]
+pub mod stress;
 
 pub use static_widget::Static;
 pub use container::Container;
 pub use button::Button;
-pub use header::Header;
+pub use header::{ClockFormat, Header, HeaderPart};
 pub use footer::Footer;
 pub use input::Input;
+pub use perf_hud::PerfHud;
+pub use context_menu::{ContextMenu, MenuItem};
+pub use status_bar::{Segment, Span, StatusBar};
+pub use multi_progress::{MultiProgress, ProgressHandle};
+pub use check_tree::{CheckState, CheckTree, SelectionChanged};
+pub use filter_bar::FilterBar;
+pub use list_view::ListView;
+pub use data_table::{Column, ColumnLayout, ColumnsChanged, DataTable};
+pub use scroll_view::ScrollView;
+pub use scrollable_container::{ScrollAction, ScrollableContainer};
+pub use panel::Panel;
+pub use breadcrumbs::Breadcrumbs;
+pub use error_boundary::ErrorBoundary;
+pub use secret::Secret;
+pub use link::{Link, LinkClicked};
+pub use code_view::CodeView;
+pub use minimap::{Minimap, MinimapJump};
+pub use tabs::{TabActivated, TabPane, Tabs};
+pub use command_palette::{Command, CommandPalette};
@@ -0,0 +1,471 @@
+//! MultiProgress widget: a dynamic list of progress bars keyed by task id.
+//!
+//! Tasks are added, updated, finished, and removed by id. Each task hands out
+//! a [`ProgressHandle`] that is `Send + Sync`, so background workers can report
+//! progress directly; the widget reads the shared state when it renders.
+//! Given the app's [`InboxSender`], handles wake the loop on every update, and
+//! [`MultiProgress::take_changed`] tells the loop the widget needs a repaint.
+//! Completed tasks can be collapsed into a single summary line.
+
+use std::any::Any;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::css::styles::Styles;
+use crate::event::inbox::InboxSender;
+use crate::geometry::Region;
+use crate::render::fill::paint_background;
+use crate::render::strip::{CellStyle, Strip};
+use crate::t;
+use crate::widget::traits::Widget;
+
+// ---------------------------------------------------------------------------
+// Task state
+// ---------------------------------------------------------------------------
+
+/// The state of one tracked task.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskProgress {
+    /// Task id.
+    pub id: String,
+    /// Label shown before the bar.
+    pub label: String,
+    /// Units of work completed.
+    pub completed: u64,
+    /// Total units of work (0 means unknown).
+    pub total: u64,
+    /// Whether the task has finished.
+    pub finished: bool,
+}
+
+impl TaskProgress {
+    /// Completion ratio in `0.0..=1.0`. Finished tasks are always 1.0.
+    pub fn fraction(&self) -> f32 {
+        if self.finished {
+            1.0
+        } else if self.total == 0 {
+            0.0
+        } else {
+            (self.completed as f32 / self.total as f32).min(1.0)
+        }
+    }
+}
+
+/// Task list shared between the widget and its handles.
+type Shared = Arc<Mutex<Vec<TaskProgress>>>;
+
+/// Lock the shared task list, recovering from a poisoned lock.
+fn lock(shared: &Shared) -> MutexGuard<'_, Vec<TaskProgress>> {
+    shared.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// ---------------------------------------------------------------------------
+// ProgressHandle
+// ---------------------------------------------------------------------------
+
+/// A thread-safe handle for reporting progress on one task.
+///
+/// Updates through a handle whose task was removed are ignored.
+#[derive(Debug, Clone)]
+pub struct ProgressHandle {
+    id: String,
+    shared: Shared,
+    changed: Arc<AtomicBool>,
+    waker: Option<InboxSender>,
+}
+
+impl ProgressHandle {
+    /// Wake the app's loop through `sender` on every update (builder
+    /// pattern). Handles from a [`MultiProgress::with_waker`] list already
+    /// have one.
+    pub fn with_waker(mut self, sender: InboxSender) -> Self {
+        self.waker = Some(sender);
+        self
+    }
+
+    /// Apply `f` to this handle's task, if it still exists, then flag the
+    /// widget for a repaint and wake the loop.
+    fn with_task(&self, f: impl FnOnce(&mut TaskProgress)) {
+        let updated = lock(&self.shared)
+            .iter_mut()
+            .find(|t| t.id == self.id)
+            .map(f)
+            .is_some();
+        if !updated {
+            return;
+        }
+        self.changed.store(true, Ordering::Release);
+        if let Some(waker) = &self.waker {
+            waker.wake();
+        }
+    }
+
+    /// The task id.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Set the completed amount.
+    pub fn set(&self, completed: u64) {
+        self.with_task(|t| t.completed = completed);
+    }
+
+    /// Add to the completed amount.
+    pub fn advance(&self, delta: u64) {
+        self.with_task(|t| t.completed = t.completed.saturating_add(delta));
+    }
+
+    /// Change the total amount of work.
+    pub fn set_total(&self, total: u64) {
+        self.with_task(|t| t.total = total);
+    }
+
+    /// Change the label.
+    pub fn set_label(&self, label: impl Into<String>) {
+        let label = label.into();
+        self.with_task(|t| t.label = label);
+    }
+
+    /// Mark the task finished.
+    pub fn finish(&self) {
+        self.with_task(|t| {
+            t.finished = true;
+            t.completed = t.completed.max(t.total);
+        });
+    }
+}
+
+// ---------------------------------------------------------------------------
+// MultiProgress
+// ---------------------------------------------------------------------------
+
+/// A set of progress bars, one per task, in insertion order.
+///
+/// # Examples
+///
+/// ```ignore
+/// let progress = MultiProgress::new().with_waker(app.inbox_sender());
+/// let handle = progress.add("dl-1", "Downloading", 100);
+/// std::thread::spawn(move || {
+///     for _ in 0..100 {
+///         handle.advance(1);
+///     }
+///     handle.finish();
+/// });
+/// ```
+pub struct MultiProgress {
+    tasks: Shared,
+    collapse_completed: bool,
+    /// Set by handle updates, cleared by [`MultiProgress::take_changed`].
+    changed: Arc<AtomicBool>,
+    waker: Option<InboxSender>,
+}
+
+impl MultiProgress {
+    /// Create an empty progress list that collapses completed tasks.
+    pub fn new() -> Self {
+        Self {
+            tasks: Arc::new(Mutex::new(Vec::new())),
+            collapse_completed: true,
+            changed: Arc::new(AtomicBool::new(false)),
+            waker: None,
+        }
+    }
+
+    /// Give every handle this list hands out a waker for the app's loop, so
+    /// updates from worker threads wake it (builder pattern).
+    pub fn with_waker(mut self, sender: InboxSender) -> Self {
+        self.waker = Some(sender);
+        self
+    }
+
+    /// Whether a handle updated a task since the last call. The loop checks
+    /// this after waking to repaint the widget's region.
+    pub fn take_changed(&self) -> bool {
+        self.changed.swap(false, Ordering::AcqRel)
+    }
+
+    /// A handle for the task `id`, sharing this list's state and waker.
+    fn make_handle(&self, id: String) -> ProgressHandle {
+        ProgressHandle {
+            id,
+            shared: self.tasks.clone(),
+            changed: self.changed.clone(),
+            waker: self.waker.clone(),
+        }
+    }
+
+    /// Whether completed tasks collapse into a summary line (builder).
+    pub fn with_collapse_completed(mut self, collapse: bool) -> Self {
+        self.collapse_completed = collapse;
+        self
+    }
+
+    /// Start tracking a task, returning its handle.
+    ///
+    /// Adding an id that already exists resets that task in place.
+    pub fn add(
+        &self,
+        id: impl Into<String>,
+        label: impl Into<String>,
+        total: u64,
+    ) -> ProgressHandle {
+        let task = TaskProgress {
+            id: id.into(),
+            label: label.into(),
+            completed: 0,
+            total,
+            finished: false,
+        };
+        let id = task.id.clone();
+        let mut tasks = lock(&self.tasks);
+        match tasks.iter_mut().find(|t| t.id == id) {
+            Some(existing) => *existing = task,
+            None => tasks.push(task),
+        }
+        self.make_handle(id)
+    }
+
+    /// A handle for an existing task.
+    pub fn handle(&self, id: &str) -> Option<ProgressHandle> {
+        lock(&self.tasks)
+            .iter()
+            .any(|t| t.id == id)
+            .then(|| self.make_handle(id.to_owned()))
+    }
+
+    /// Set the completed amount of a task. Returns `false` if it is unknown.
+    pub fn update(&self, id: &str, completed: u64) -> bool {
+        self.handle(id).map(|h| h.set(completed)).is_some()
+    }
+
+    /// Mark a task finished. Returns `false` if it is unknown.
+    pub fn finish(&self, id: &str) -> bool {
+        self.handle(id).map(|h| h.finish()).is_some()
+    }
+
+    /// Stop tracking a task. Returns its final state if it existed.
+    pub fn remove(&self, id: &str) -> Option<TaskProgress> {
+        let mut tasks = lock(&self.tasks);
+        let index = tasks.iter().position(|t| t.id == id)?;
+        Some(tasks.remove(index))
+    }
+
+    /// Remove every finished task.
+    pub fn clear_finished(&self) {
+        lock(&self.tasks).retain(|t| !t.finished);
+    }
+
+    /// A snapshot of a task's state.
+    pub fn task(&self, id: &str) -> Option<TaskProgress> {
+        lock(&self.tasks).iter().find(|t| t.id == id).cloned()
+    }
+
+    /// A snapshot of all tasks, in insertion order.
+    pub fn tasks(&self) -> Vec<TaskProgress> {
+        lock(&self.tasks).clone()
+    }
+
+    /// Number of tracked tasks.
+    pub fn len(&self) -> usize {
+        lock(&self.tasks).len()
+    }
+
+    /// Whether no tasks are tracked.
+    pub fn is_empty(&self) -> bool {
+        lock(&self.tasks).is_empty()
+    }
+
+    /// Text lines to display, one per row.
+    fn lines(&self, width: usize) -> Vec<String> {
+        let tasks = lock(&self.tasks);
+        let mut lines = Vec::new();
+        let mut done = 0;
+        for task in tasks.iter() {
+            if task.finished && self.collapse_completed {
+                done += 1;
+            } else {
+                lines.push(task_line(task, width));
+            }
+        }
+        if done > 0 {
+            lines.push(t!("multi-progress.completed", count = done));
+        }
+        lines
+    }
+}
+
+impl Default for MultiProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Format one task as `label [████░░░░] 50%`, fitted to `width`.
+fn task_line(task: &TaskProgress, width: usize) -> String {
+    let percent = format!(
+        "{:>4}",
+        format!("{}%", (task.fraction() * 100.0).round() as u32)
+    );
+    let label: String = task.label.chars().take(width / 3).collect();
+    // label + space + [ + bar + ] + space + percent
+    let bar_width = width.saturating_sub(label.chars().count() + 4 + percent.len());
+    let filled = (task.fraction() * bar_width as f32).round() as usize;
+    format!(
+        "{label} [{}{}] {percent}",
+        "█".repeat(filled),
+        "░".repeat(bar_width - filled)
+    )
+}
+
+impl Widget for MultiProgress {
+    fn widget_type(&self) -> &str {
+        "MultiProgress"
+    }
+
+    fn default_css(&self) -> &str {
+        "MultiProgress { width: 1fr; height: auto; }"
+    }
+
+    fn render(&self, region: Region, styles: &Styles) -> Vec<Strip> {
+        if region.width <= 0 || region.height <= 0 {
+            return Vec::new();
+        }
+
+        let style = CellStyle::from_styles(styles);
+        let width = region.width as usize;
+        let lines = self.lines(width);
+
+        let mut strips: Vec<Strip> = (0..region.height)
+            .map(|row| {
+                let mut strip = Strip::new(region.y + row, region.x);
+                if let Some(line) = lines.get(row as usize) {
+                    let text: String = line.chars().take(width).collect();
+                    strip.push_str(&text, style);
+                }
+                strip.fill(region.width, style);
+                strip
+            })
+            .collect();
+        paint_background(&mut strips, region, styles);
+        strips
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(progress: &MultiProgress, width: i32, height: i32, y: usize) -> String {
+        progress.render(Region::new(0, 0, width, height), &Styles::new())[y]
+            .cells
+            .iter()
+            .map(|c| c.ch)
+            .collect()
+    }
+
+    #[test]
+    fn add_update_finish_remove() {
+        let progress = MultiProgress::new();
+        let handle = progress.add("a", "Alpha", 10);
+        handle.advance(4);
+        assert_eq!(progress.task("a").unwrap().completed, 4);
+        assert!(progress.update("a", 7));
+        assert_eq!(progress.task("a").unwrap().fraction(), 0.7);
+        assert!(progress.finish("a"));
+        assert!(progress.task("a").unwrap().finished);
+        assert!(progress.remove("a").is_some());
+        assert!(progress.is_empty());
+        assert!(!progress.update("a", 1));
+    }
+
+    #[test]
+    fn handle_reports_from_worker_thread() {
+        let progress = MultiProgress::new();
+        let handle = progress.add("job", "Job", 100);
+        std::thread::spawn(move || {
+            for _ in 0..100 {
+                handle.advance(1);
+            }
+            handle.finish();
+        })
+        .join()
+        .unwrap();
+        let task = progress.task("job").unwrap();
+        assert_eq!(task.completed, 100);
+        assert!(task.finished);
+    }
+
+    #[test]
+    fn worker_updates_wake_the_app_and_flag_a_repaint() {
+        use crate::app::App;
+        use crate::event::message::Custom;
+        use std::sync::mpsc;
+        use std::time::Duration;
+
+        let app = App::new_headless(40, 5);
+        let progress = MultiProgress::new().with_waker(app.inbox_sender());
+        let handle = progress.add("job", "Job", 10);
+        assert!(!progress.take_changed());
+        let (release, hold) = mpsc::channel::<()>();
+        let _worker = app.spawn_worker(move |_| {
+            handle.advance(3);
+            // Keep the worker's own result message from waking the loop
+            // until the handle's wake has been seen.
+            let _ = hold.recv();
+            Custom::new("done")
+        });
+
+        assert!(app.wait_external(Some(Duration::from_secs(5))));
+        assert_eq!(progress.task("job").unwrap().completed, 3);
+        assert!(progress.take_changed());
+        assert!(!progress.take_changed());
+        release.send(()).unwrap();
+    }
+
+    #[test]
+    fn render_bar_and_percent() {
+        let progress = MultiProgress::new();
+        progress.add("a", "dl", 4).set(2);
+        let line = row(&progress, 20, 1, 0);
+        assert_eq!(line, "dl [█████░░░░░]  50%");
+    }
+
+    #[test]
+    fn completed_tasks_collapse() {
+        let progress = MultiProgress::new();
+        progress.add("a", "one", 1).finish();
+        progress.add("b", "two", 1).finish();
+        progress.add("c", "three", 10);
+        assert!(row(&progress, 30, 3, 0).starts_with("three ["));
+        assert!(row(&progress, 30, 3, 1).starts_with("✓ 2 completed"));
+
+        let expanded = MultiProgress::new().with_collapse_completed(false);
+        expanded.add("a", "one", 1).finish();
+        assert!(row(&expanded, 30, 1, 0).ends_with("100%"));
+    }
+
+    #[test]
+    fn re_adding_resets_in_place() {
+        let progress = MultiProgress::new();
+        progress.add("a", "first", 10).set(5);
+        progress.add("b", "second", 10);
+        progress.add("a", "again", 20);
+        let tasks = progress.tasks();
+        assert_eq!(tasks[0].label, "again");
+        assert_eq!(tasks[0].completed, 0);
+        assert_eq!(tasks.len(), 2);
+    }
+}
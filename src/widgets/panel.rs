@@ -0,0 +1,447 @@
+//! Panel widget: a floating, movable, resizable window.
+//!
+//! Panels are positioned absolutely in screen coordinates and drawn above the
+//! normal flow, ordered by z-index. They are dragged by the title bar, resized
+//! by the right edge, bottom edge, or bottom-right corner, and can be moved
+//! (Alt+arrows) or resized (Alt+Shift+arrows) from the keyboard. Every change
+//! keeps the panel inside the bounds it is given and at least its minimum size.
+
+use std::any::Any;
+
+use crate::css::border::BorderGlyphs;
+use crate::css::styles::Styles;
+use crate::event::input::{Key, KeyEvent, Modifiers, MouseAction, MouseBtn, MouseEvent};
+use crate::geometry::{Region, Size};
+use crate::render::strip::{CellStyle, Strip};
+use crate::widget::traits::Widget;
+
+// ---------------------------------------------------------------------------
+// Grab
+// ---------------------------------------------------------------------------
+
+/// What part of the panel a mouse drag holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GrabKind {
+    Move,
+    ResizeRight,
+    ResizeBottom,
+    ResizeCorner,
+}
+
+/// An in-progress mouse drag.
+#[derive(Debug, Clone, Copy)]
+struct Grab {
+    kind: GrabKind,
+    x: i32,
+    y: i32,
+    start: Region,
+}
+
+// ---------------------------------------------------------------------------
+// Panel
+// ---------------------------------------------------------------------------
+
+/// A floating window with a title bar, border, and optional content widget.
+///
+/// # Examples
+///
+/// ```ignore
+/// let panel = Panel::new("Inspector", Region::new(10, 4, 30, 12))
+///     .with_content(Static::new("Selected: none"))
+///     .with_min_size(Size::new(12, 4));
+/// app.add_panel(panel);
+/// ```
+pub struct Panel {
+    title: String,
+    region: Region,
+    min_size: Size,
+    z_index: i32,
+    content: Option<Box<dyn Widget>>,
+    border: BorderGlyphs,
+    grab: Option<Grab>,
+}
+
+impl Panel {
+    /// The smallest size a panel can have: a border and one content cell.
+    pub const MIN_SIZE: Size = Size::new(3, 3);
+
+    /// Create a panel with the given title and screen region.
+    pub fn new(title: impl Into<String>, region: Region) -> Self {
+        Self {
+            title: title.into(),
+            region,
+            min_size: Self::MIN_SIZE,
+            z_index: 0,
+            content: None,
+            border: BorderGlyphs::THIN,
+            grab: None,
+        }
+    }
+
+    /// Set the content widget (builder pattern).
+    pub fn with_content(mut self, content: impl Widget + 'static) -> Self {
+        self.content = Some(Box::new(content));
+        self
+    }
+
+    /// Set the minimum size, never smaller than [`Panel::MIN_SIZE`]
+    /// (builder pattern).
+    pub fn with_min_size(mut self, size: Size) -> Self {
+        self.min_size = Size::new(
+            size.width.max(Self::MIN_SIZE.width),
+            size.height.max(Self::MIN_SIZE.height),
+        );
+        self
+    }
+
+    /// Set the border glyphs (builder pattern). A `border` in the panel's
+    /// styles takes precedence.
+    pub fn with_border(mut self, border: BorderGlyphs) -> Self {
+        self.border = border;
+        self
+    }
+
+    /// Set the stacking order; higher values draw on top (builder pattern).
+    pub fn with_z_index(mut self, z_index: i32) -> Self {
+        self.z_index = z_index;
+        self
+    }
+
+    /// The title shown in the top border.
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Where the panel is drawn, border included.
+    pub fn region(&self) -> Region {
+        self.region
+    }
+
+    /// The area inside the border.
+    pub fn inner_region(&self) -> Region {
+        Region::new(
+            self.region.x + 1,
+            self.region.y + 1,
+            (self.region.width - 2).max(0),
+            (self.region.height - 2).max(0),
+        )
+    }
+
+    /// The stacking order.
+    pub fn z_index(&self) -> i32 {
+        self.z_index
+    }
+
+    /// Change the stacking order.
+    pub fn set_z_index(&mut self, z_index: i32) {
+        self.z_index = z_index;
+    }
+
+    /// The content widget, if any.
+    pub fn content(&self) -> Option<&dyn Widget> {
+        self.content.as_deref()
+    }
+
+    /// Whether a mouse drag is moving or resizing the panel.
+    pub fn is_dragging(&self) -> bool {
+        self.grab.is_some()
+    }
+
+    /// Set the region, clamped to the minimum size and to `bounds`.
+    pub fn set_region(&mut self, region: Region, bounds: Region) {
+        let width = region.width.max(self.min_size.width).min(bounds.width);
+        let height = region.height.max(self.min_size.height).min(bounds.height);
+        let x = region.x.min(bounds.right() - width).max(bounds.x);
+        let y = region.y.min(bounds.bottom() - height).max(bounds.y);
+        self.region = Region::new(x, y, width, height);
+    }
+
+    /// Move the panel by `(dx, dy)` cells, staying inside `bounds`.
+    pub fn move_by(&mut self, dx: i32, dy: i32, bounds: Region) {
+        let r = self.region;
+        self.set_region(Region::new(r.x + dx, r.y + dy, r.width, r.height), bounds);
+    }
+
+    /// Grow or shrink the panel by `(dw, dh)` cells, keeping its top-left
+    /// corner and staying inside `bounds`.
+    pub fn resize_by(&mut self, dw: i32, dh: i32, bounds: Region) {
+        let r = self.region;
+        let bounds = Region::new(r.x, r.y, bounds.right() - r.x, bounds.bottom() - r.y);
+        self.set_region(Region::new(r.x, r.y, r.width + dw, r.height + dh), bounds);
+    }
+
+    /// Handle a mouse event. Returns `true` if the panel consumed it (the
+    /// pointer is over the panel or a drag is in progress).
+    pub fn handle_mouse(&mut self, event: &MouseEvent, bounds: Region) -> bool {
+        let (x, y) = (event.x as i32, event.y as i32);
+        match event.kind {
+            MouseAction::Down(MouseBtn::Left) if self.region.contains(x, y) => {
+                self.grab = self.grab_kind_at(x, y).map(|kind| Grab {
+                    kind,
+                    x,
+                    y,
+                    start: self.region,
+                });
+                true
+            }
+            MouseAction::Drag(MouseBtn::Left) => {
+                let Some(grab) = self.grab else {
+                    return self.region.contains(x, y);
+                };
+                let (dx, dy) = (x - grab.x, y - grab.y);
+                let s = grab.start;
+                let region = match grab.kind {
+                    GrabKind::Move => Region::new(s.x + dx, s.y + dy, s.width, s.height),
+                    GrabKind::ResizeRight => Region::new(s.x, s.y, s.width + dx, s.height),
+                    GrabKind::ResizeBottom => Region::new(s.x, s.y, s.width, s.height + dy),
+                    GrabKind::ResizeCorner => Region::new(s.x, s.y, s.width + dx, s.height + dy),
+                };
+                if grab.kind == GrabKind::Move {
+                    self.set_region(region, bounds);
+                } else {
+                    self.region = s;
+                    self.resize_by(region.width - s.width, region.height - s.height, bounds);
+                }
+                true
+            }
+            MouseAction::Up(MouseBtn::Left) if self.grab.is_some() => {
+                self.grab = None;
+                true
+            }
+            _ => self.region.contains(x, y),
+        }
+    }
+
+    /// Handle a key event: Alt+arrows move the panel, Alt+Shift+arrows
+    /// resize it. Returns `true` if the key was used.
+    pub fn handle_key(&mut self, event: &KeyEvent, bounds: Region) -> bool {
+        if !event.modifiers.contains(Modifiers::ALT) {
+            return false;
+        }
+        let (dx, dy) = match event.code {
+            Key::Left => (-1, 0),
+            Key::Right => (1, 0),
+            Key::Up => (0, -1),
+            Key::Down => (0, 1),
+            _ => return false,
+        };
+        if event.modifiers.contains(Modifiers::SHIFT) {
+            self.resize_by(dx, dy, bounds);
+        } else {
+            self.move_by(dx, dy, bounds);
+        }
+        true
+    }
+
+    /// Which grab handle is at `(x, y)`, if any. The bottom-right corner
+    /// resizes both ways, the title row moves the panel.
+    fn grab_kind_at(&self, x: i32, y: i32) -> Option<GrabKind> {
+        let right = x == self.region.right() - 1;
+        let bottom = y == self.region.bottom() - 1;
+        match (right, bottom) {
+            (true, true) => Some(GrabKind::ResizeCorner),
+            (true, false) if y > self.region.y => Some(GrabKind::ResizeRight),
+            (false, true) => Some(GrabKind::ResizeBottom),
+            _ if y == self.region.y => Some(GrabKind::Move),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Debug for Panel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Panel")
+            .field("title", &self.title)
+            .field("region", &self.region)
+            .field("z_index", &self.z_index)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Widget for Panel {
+    fn widget_type(&self) -> &str {
+        "Panel"
+    }
+
+    fn default_css(&self) -> &str {
+        "Panel { background: #202020; color: #e0e0e0; }"
+    }
+
+    fn render(&self, region: Region, styles: &Styles) -> Vec<Strip> {
+        if region.width < 2 || region.height < 2 {
+            return Vec::new();
+        }
+
+        let style = CellStyle::from_styles(styles);
+        let inner = Region::new(
+            region.x + 1,
+            region.y + 1,
+            region.width - 2,
+            region.height - 2,
+        );
+        let content = self
+            .content
+            .as_ref()
+            .map(|widget| widget.render(inner, styles))
+            .unwrap_or_default();
+
+        let glyphs = styles
+            .border
+            .as_ref()
+            .and_then(|border| border.kind.glyphs())
+            .unwrap_or(self.border);
+
+        // Title bar: ┌ Title ───┐
+        let mut top = Strip::new(region.y, region.x);
+        top.push(glyphs.top_left, style);
+        let title: String = self
+            .title
+            .chars()
+            .take((region.width - 4).max(0) as usize)
+            .collect();
+        if !title.is_empty() {
+            top.push(' ', style);
+            top.push_str(&title, style);
+            top.push(' ', style);
+        }
+        while top.width() < region.width - 1 {
+            top.push(glyphs.horizontal, style);
+        }
+        top.push(glyphs.top_right, style);
+
+        let mut strips = vec![top];
+        for y in inner.y..inner.bottom() {
+            let mut strip = Strip::new(y, region.x);
+            strip.push(glyphs.vertical, style);
+            if let Some(row) = content.iter().find(|strip| strip.y == y) {
+                let row = row.crop(inner.x, inner.right());
+                let lead = (row.x_offset - inner.x).max(0);
+                strip.fill(1 + lead, style);
+                strip.cells.extend(row.cells.iter().copied());
+            }
+            strip.fill(region.width - 1, style);
+            strip.push(glyphs.vertical, style);
+            strips.push(strip);
+        }
+
+        let mut bottom = Strip::new(region.bottom() - 1, region.x);
+        bottom.push(glyphs.bottom_left, style);
+        while bottom.width() < region.width - 1 {
+            bottom.push(glyphs.horizontal, style);
+        }
+        bottom.push(glyphs.bottom_right, style);
+        strips.push(bottom);
+        strips
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::css::styles::{Border, BorderKind};
+    use crate::widgets::Static;
+
+    const SCREEN: Region = Region::new(0, 0, 40, 20);
+
+    fn panel() -> Panel {
+        Panel::new("Tools", Region::new(5, 5, 10, 6)).with_min_size(Size::new(6, 4))
+    }
+
+    fn mouse(kind: MouseAction, x: u16, y: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            x,
+            y,
+            modifiers: Modifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn drag_title_bar_moves_within_bounds() {
+        let mut p = panel();
+        assert!(p.handle_mouse(&mouse(MouseAction::Down(MouseBtn::Left), 7, 5), SCREEN));
+        assert!(p.is_dragging());
+        p.handle_mouse(&mouse(MouseAction::Drag(MouseBtn::Left), 10, 7), SCREEN);
+        assert_eq!(p.region(), Region::new(8, 7, 10, 6));
+
+        p.handle_mouse(&mouse(MouseAction::Drag(MouseBtn::Left), 60, 60), SCREEN);
+        assert_eq!(p.region(), Region::new(30, 14, 10, 6));
+        p.handle_mouse(&mouse(MouseAction::Up(MouseBtn::Left), 60, 60), SCREEN);
+        assert!(!p.is_dragging());
+    }
+
+    #[test]
+    fn drag_corner_resizes_with_minimum() {
+        let mut p = panel();
+        p.handle_mouse(&mouse(MouseAction::Down(MouseBtn::Left), 14, 10), SCREEN);
+        p.handle_mouse(&mouse(MouseAction::Drag(MouseBtn::Left), 18, 12), SCREEN);
+        assert_eq!(p.region(), Region::new(5, 5, 14, 8));
+
+        p.handle_mouse(&mouse(MouseAction::Drag(MouseBtn::Left), 0, 0), SCREEN);
+        assert_eq!(p.region(), Region::new(5, 5, 6, 4));
+    }
+
+    #[test]
+    fn body_clicks_are_consumed_without_grabbing() {
+        let mut p = panel();
+        assert!(p.handle_mouse(&mouse(MouseAction::Down(MouseBtn::Left), 8, 7), SCREEN));
+        assert!(!p.is_dragging());
+        assert!(!p.handle_mouse(&mouse(MouseAction::Down(MouseBtn::Left), 1, 1), SCREEN));
+    }
+
+    #[test]
+    fn keyboard_moves_and_resizes() {
+        let mut p = panel();
+        let alt = |code| KeyEvent::new(code, Modifiers::ALT);
+        let alt_shift = |code| KeyEvent::new(code, Modifiers::ALT | Modifiers::SHIFT);
+
+        assert!(p.handle_key(&alt(Key::Right), SCREEN));
+        assert!(p.handle_key(&alt(Key::Up), SCREEN));
+        assert_eq!(p.region(), Region::new(6, 4, 10, 6));
+        assert!(p.handle_key(&alt_shift(Key::Left), SCREEN));
+        assert_eq!(p.region(), Region::new(6, 4, 9, 6));
+        assert!(!p.handle_key(&KeyEvent::new(Key::Right, Modifiers::NONE), SCREEN));
+    }
+
+    #[test]
+    fn render_draws_border_title_and_content() {
+        let p = Panel::new("Log", Region::new(0, 0, 9, 3)).with_content(Static::new("hello world"));
+        let strips = p.render(p.region(), &Styles::new());
+        let text: Vec<String> = strips
+            .iter()
+            .map(|s| s.cells.iter().map(|c| c.ch).collect())
+            .collect();
+        assert_eq!(text, ["┌ Log ──┐", "│hello w│", "└───────┘"]);
+    }
+
+    #[test]
+    fn render_uses_custom_border_glyphs() {
+        let p = Panel::new("", Region::new(0, 0, 4, 3)).with_border(BorderGlyphs::ROUND);
+        let rows = |styles: &Styles| -> Vec<String> {
+            p.render(p.region(), styles)
+                .iter()
+                .map(|s| s.cells.iter().map(|c| c.ch).collect())
+                .collect()
+        };
+        assert_eq!(rows(&Styles::new()), ["╭──╮", "│  │", "╰──╯"]);
+
+        let mut styles = Styles::new();
+        styles.border = Some(Border {
+            kind: BorderKind::Custom(BorderGlyphs::parse("┍┑┕┙━│").unwrap()),
+            color: None,
+        });
+        assert_eq!(rows(&styles), ["┍━━┑", "│  │", "┕━━┙"]);
+    }
+}
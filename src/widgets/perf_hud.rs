@@ -0,0 +1,164 @@
+//! PerfHud widget: performance overlay with FPS and frame breakdown.
+//!
+//! The HUD is a plain text panel built from a [`FrameStats`] snapshot and the
+//! reactive runtime's arena sizes. The app overlays it in the top-right corner
+//! when enabled; it can also be mounted like any other widget.
+
+use std::any::Any;
+use std::time::Duration;
+
+use crate::css::styles::Styles;
+use crate::geometry::Region;
+use crate::reactive::RuntimeStats;
+use crate::render::stats::FrameStats;
+use crate::render::strip::{CellStyle, Strip};
+use crate::widget::traits::Widget;
+
+// ---------------------------------------------------------------------------
+// PerfHud
+// ---------------------------------------------------------------------------
+
+/// Width of the HUD panel in cells.
+pub const HUD_WIDTH: i32 = 26;
+
+/// A read-only performance overlay.
+///
+/// # Examples
+///
+/// ```ignore
+/// let hud = PerfHud::new(&app.stats, &runtime_stats());
+/// ```
+pub struct PerfHud {
+    lines: Vec<String>,
+}
+
+impl PerfHud {
+    /// Build the HUD text from a stats snapshot.
+    pub fn new(stats: &FrameStats, runtime: &RuntimeStats) -> Self {
+        let t = &stats.last;
+        let lines = vec![
+            format!("FPS {:>5.1}  frame {:>6}", stats.fps(), fmt_ms(t.total())),
+            format!("event  {:>8}", fmt_ms(t.event)),
+            format!("style  {:>8}", fmt_ms(t.style)),
+            format!("layout {:>8}", fmt_ms(t.layout)),
+            format!("render {:>8}", fmt_ms(t.render)),
+            format!("flush  {:>8}", fmt_ms(t.flush)),
+            format!("cells  {:>8}", stats.cells_updated),
+            format!(
+                "sig {} eff {}/{}",
+                runtime.signals, runtime.active_effects, runtime.effects
+            ),
+        ];
+        Self { lines }
+    }
+
+    /// The rendered text lines.
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Number of rows the HUD needs.
+    pub fn height(&self) -> i32 {
+        self.lines.len() as i32
+    }
+}
+
+/// Format a duration as milliseconds with two decimals.
+fn fmt_ms(d: Duration) -> String {
+    format!("{:.2}ms", d.as_secs_f64() * 1000.0)
+}
+
+impl Widget for PerfHud {
+    fn widget_type(&self) -> &str {
+        "PerfHud"
+    }
+
+    fn default_css(&self) -> &str {
+        "PerfHud { width: 26; height: 8; dock: right; background: #202020; color: #00ff87; }"
+    }
+
+    fn render(&self, region: Region, styles: &Styles) -> Vec<Strip> {
+        if region.width <= 0 || region.height <= 0 {
+            return Vec::new();
+        }
+
+        let style = CellStyle::from_styles(styles);
+        let width = region.width as usize;
+
+        (0..region.height)
+            .map(|row| {
+                let mut strip = Strip::new(region.y + row, region.x);
+                if let Some(line) = self.lines.get(row as usize) {
+                    let text: String = line.chars().take(width).collect();
+                    strip.push_str(&text, style);
+                }
+                strip.fill(region.width, style);
+                strip
+            })
+            .collect()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::stats::FrameTimings;
+    use std::time::Instant;
+
+    fn sample() -> PerfHud {
+        let mut stats = FrameStats::new();
+        stats.record_frame(
+            FrameTimings {
+                layout: Duration::from_micros(1500),
+                ..FrameTimings::default()
+            },
+            120,
+            Instant::now(),
+        );
+        let runtime = RuntimeStats {
+            signals: 4,
+            effects: 3,
+            active_effects: 2,
+        };
+        PerfHud::new(&stats, &runtime)
+    }
+
+    #[test]
+    fn lines_include_breakdown() {
+        let hud = sample();
+        assert_eq!(hud.height(), 8);
+        assert!(hud.lines()[0].starts_with("FPS   1.0"));
+        assert!(hud.lines()[3].contains("1.50ms"));
+        assert!(hud.lines()[6].contains("120"));
+        assert_eq!(hud.lines()[7], "sig 4 eff 2/3");
+    }
+
+    #[test]
+    fn lines_fit_hud_width() {
+        let hud = sample();
+        for line in hud.lines() {
+            assert!(line.chars().count() as i32 <= HUD_WIDTH, "{line}");
+        }
+    }
+
+    #[test]
+    fn render_fills_region() {
+        let hud = sample();
+        let strips = hud.render(Region::new(5, 0, HUD_WIDTH, 8), &Styles::new());
+        assert_eq!(strips.len(), 8);
+        assert!(strips.iter().all(|s| s.width() == HUD_WIDTH));
+        assert_eq!(strips[0].x_offset, 5);
+    }
+}
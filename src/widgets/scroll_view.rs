@@ -0,0 +1,423 @@
+//! ScrollView widget: a viewport onto a child larger than the screen.
+//!
+//! The child is laid out at its full content size, offset by the scroll
+//! position, and asked via [`Widget::render_window`] for only the columns that
+//! fall inside the viewport. Children that override `render_window` (such as
+//! `Static`) never materialize off-screen cells, so very wide content stays
+//! cheap to scroll horizontally.
+//!
+//! The vertical scrollbar follows `overflow-y` (`scroll` always, `auto` when
+//! the content is taller than the viewport) and sits in a one-column gutter,
+//! which `scrollbar-gutter: stable` keeps reserved even without a scrollbar.
+//! `scrollbar-size` sets the gutter's width and `scrollbar-color` the bar's.
+//! When no scrollbar is drawn, `scroll-indicator: shadow | marker` hints at
+//! content above or below the viewport. `overscroll-behavior` decides whether
+//! scroll input past an edge is absorbed ([`ScrollView::scroll_input`]).
+//!
+//! [`ScrollView::observe_offset`] exposes the scroll position as a signal, so
+//! app code can react to scrolling (e.g. show a "back to top" button).
+
+use std::any::Any;
+
+use crate::css::styles::{Overflow, OverscrollBehavior, ScrollIndicator, ScrollbarGutter, Styles};
+use crate::geometry::{Offset, Region, Size};
+use crate::reactive::{create_signal, ReadSignal, WriteSignal};
+use crate::render::strip::{Attrs, CellStyle, Strip};
+use crate::widget::scroll::{
+    scrollbar_strips, scrollbar_style, ScrollState, DEFAULT_SCROLLBAR_SIZE,
+};
+use crate::widget::traits::Widget;
+
+// ---------------------------------------------------------------------------
+// ScrollView
+// ---------------------------------------------------------------------------
+
+/// A scrollable viewport around a single child widget.
+///
+/// # Examples
+///
+/// ```ignore
+/// let log = Static::new(very_long_lines);
+/// let mut view = ScrollView::new(log, Size::new(10_000, 500));
+/// view.set_viewport(Size::new(80, 24));
+/// view.scroll_by(40, 0);
+/// ```
+pub struct ScrollView {
+    child: Box<dyn Widget>,
+    scroll: ScrollState,
+    offset_signal: Option<(ReadSignal<Offset>, WriteSignal<Offset>)>,
+}
+
+impl ScrollView {
+    /// Wrap `child`, whose full content is `content` cells in size.
+    pub fn new(child: impl Widget + 'static, content: Size) -> Self {
+        Self {
+            child: Box::new(child),
+            scroll: ScrollState::new(content, Size::new(0, 0)),
+            offset_signal: None,
+        }
+    }
+
+    /// A signal holding the scroll offset, written whenever it changes.
+    /// Repeated calls return the same signal.
+    pub fn observe_offset(&mut self) -> ReadSignal<Offset> {
+        let offset = self.scroll.offset;
+        self.offset_signal
+            .get_or_insert_with(|| create_signal(offset))
+            .0
+    }
+
+    /// Write the offset to the observed signal, if any and if it moved.
+    fn publish_offset(&self) {
+        if let Some((read, write)) = self.offset_signal {
+            if read.get_untracked() != self.scroll.offset {
+                write.set(self.scroll.offset);
+            }
+        }
+    }
+
+    /// The scroll position and sizes.
+    pub fn scroll_state(&self) -> &ScrollState {
+        &self.scroll
+    }
+
+    /// The wrapped widget.
+    pub fn child(&self) -> &dyn Widget {
+        self.child.as_ref()
+    }
+
+    /// The wrapped widget, mutably.
+    pub fn child_mut(&mut self) -> &mut dyn Widget {
+        self.child.as_mut()
+    }
+
+    /// Update the child's content size, re-clamping the offset.
+    pub fn set_content_size(&mut self, content: Size) {
+        self.scroll.set_content_size(content);
+        self.publish_offset();
+    }
+
+    /// Update the visible size, re-clamping the offset.
+    pub fn set_viewport(&mut self, viewport: Size) {
+        self.scroll.set_viewport_size(viewport);
+        self.publish_offset();
+    }
+
+    /// Scroll to an absolute offset (clamped).
+    pub fn scroll_to(&mut self, x: i32, y: i32) {
+        self.scroll.scroll_to(x, y);
+        self.publish_offset();
+    }
+
+    /// Scroll by a relative amount (clamped).
+    pub fn scroll_by(&mut self, dx: i32, dy: i32) {
+        self.scroll.scroll_by(dx, dy);
+        self.publish_offset();
+    }
+
+    /// Apply scroll input (wheel, keys) by `(dx, dy)`. Returns whether the
+    /// input was consumed: always with [`OverscrollBehavior::Clamp`], and with
+    /// [`OverscrollBehavior::None`] only if the view actually moved, so an
+    /// enclosing scrollable can take input past the edge.
+    pub fn scroll_input(&mut self, dx: i32, dy: i32, behavior: OverscrollBehavior) -> bool {
+        let remainder = self.scroll.scroll_by_remainder(dx, dy);
+        self.publish_offset();
+        match behavior {
+            OverscrollBehavior::Clamp => true,
+            OverscrollBehavior::None => remainder.x != dx || remainder.y != dy,
+        }
+    }
+
+    /// Whether a vertical scrollbar is drawn with these styles.
+    fn shows_scrollbar(&self, styles: &Styles) -> bool {
+        match styles.overflow_y {
+            Some(Overflow::Scroll) => true,
+            Some(Overflow::Auto) => self.scroll.is_scrollable_y(),
+            _ => false,
+        }
+    }
+}
+
+/// The strip for row `y`, normalized to span exactly `view`'s columns.
+fn full_row(strips: &mut Vec<Strip>, y: i32, view: Region, style: CellStyle) -> &mut Strip {
+    let existing = strips.iter().position(|strip| strip.y == y);
+    let mut row = Strip::new(y, view.x);
+    if let Some(index) = existing {
+        let old = strips.remove(index);
+        row.fill(old.x_offset - view.x, style);
+        row.cells.extend(old.cells);
+    }
+    row.fill(view.width, style);
+    strips.push(row);
+    strips.last_mut().expect("just pushed")
+}
+
+/// Hint at hidden content on the first/last visible rows.
+fn apply_indicator(
+    strips: &mut Vec<Strip>,
+    indicator: ScrollIndicator,
+    scroll: &ScrollState,
+    view: Region,
+    style: CellStyle,
+) {
+    let edges = [
+        (view.y, scroll.has_more_above(), "↑ more…"),
+        (view.bottom() - 1, scroll.has_more_below(), "↓ more…"),
+    ];
+    for (y, more, marker) in edges {
+        if !more {
+            continue;
+        }
+        let row = full_row(strips, y, view, style);
+        match indicator {
+            ScrollIndicator::None => {}
+            ScrollIndicator::Shadow => {
+                for cell in &mut row.cells {
+                    cell.style.attrs.set(Attrs::DIM, true);
+                }
+            }
+            ScrollIndicator::Marker => {
+                let len = marker.chars().count().min(row.cells.len());
+                let start = row.cells.len() - len;
+                for (cell, ch) in row.cells[start..].iter_mut().zip(marker.chars()) {
+                    cell.ch = ch;
+                    cell.style = style.with_attrs(Attrs::DIM);
+                }
+            }
+        }
+    }
+}
+
+impl Widget for ScrollView {
+    fn widget_type(&self) -> &str {
+        "ScrollView"
+    }
+
+    fn default_css(&self) -> &str {
+        "ScrollView { width: 1fr; height: 1fr; }"
+    }
+
+    fn can_focus(&self) -> bool {
+        true
+    }
+
+    fn render(&self, region: Region, styles: &Styles) -> Vec<Strip> {
+        if region.width <= 0 || region.height <= 0 {
+            return Vec::new();
+        }
+
+        let size = styles.scrollbar_size.unwrap_or(DEFAULT_SCROLLBAR_SIZE) as i32;
+        let scrollbar = self.shows_scrollbar(styles);
+        let gutter = (scrollbar || styles.scrollbar_gutter == Some(ScrollbarGutter::Stable))
+            && region.width > size;
+        let view = if gutter {
+            Region::new(region.x, region.y, region.width - size, region.height)
+        } else {
+            region
+        };
+
+        let offset = self.scroll.offset;
+        let content = self.scroll.content_size;
+        let child_region = Region::new(
+            view.x - offset.x,
+            view.y - offset.y,
+            content.width.max(view.width),
+            content.height.max(view.height),
+        );
+
+        let mut strips: Vec<Strip> = self
+            .child
+            .render_window(child_region, styles, view.x..view.right())
+            .into_iter()
+            .filter(|strip| strip.y >= view.y && strip.y < view.bottom())
+            // Crop again in case the child ignored the window.
+            .map(|strip| strip.crop(view.x, view.right()))
+            .collect();
+
+        let style = CellStyle::from_styles(styles);
+        if !scrollbar {
+            let indicator = styles.scroll_indicator.unwrap_or_default();
+            if indicator != ScrollIndicator::None {
+                apply_indicator(&mut strips, indicator, &self.scroll, view, style);
+            }
+        }
+        let bar = Region::new(view.right(), region.y, size, region.height);
+        if scrollbar && gutter {
+            strips.extend(scrollbar_strips(&self.scroll, bar, scrollbar_style(styles)));
+        } else if gutter {
+            strips.extend((bar.y..bar.bottom()).map(|y| {
+                let mut strip = Strip::new(y, bar.x);
+                strip.fill(bar.width, style);
+                strip
+            }));
+        }
+        strips
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::Static;
+    use std::cell::Cell;
+    use std::ops::Range;
+
+    fn text(strip: &Strip) -> String {
+        strip.cells.iter().map(|c| c.ch).collect()
+    }
+
+    fn view() -> ScrollView {
+        let line: String = ('a'..='z').cycle().take(1000).collect();
+        let content = format!("{line}\n{line}\n{line}");
+        let mut view = ScrollView::new(Static::new(content), Size::new(1000, 3));
+        view.set_viewport(Size::new(5, 2));
+        view
+    }
+
+    #[test]
+    fn renders_visible_column_window() {
+        let mut view = view();
+        view.scroll_by(27, 1);
+        let strips = view.render(Region::new(10, 4, 5, 2), &Styles::new());
+        assert_eq!(strips.len(), 2);
+        assert_eq!(text(&strips[0]), "bcdef");
+        assert_eq!(strips[0].x_offset, 10);
+        assert_eq!(strips[0].y, 4);
+    }
+
+    #[test]
+    fn offset_is_clamped() {
+        let mut view = view();
+        view.scroll_to(5000, 10);
+        assert_eq!(view.scroll_state().offset.x, 995);
+        assert_eq!(view.scroll_state().offset.y, 1);
+    }
+
+    #[test]
+    fn child_only_asked_for_visible_columns() {
+        struct Probe(Cell<Option<Range<i32>>>);
+
+        impl Widget for Probe {
+            fn widget_type(&self) -> &str {
+                "Probe"
+            }
+            fn render(&self, _region: Region, _styles: &Styles) -> Vec<Strip> {
+                unreachable!("ScrollView renders through render_window")
+            }
+            fn render_window(&self, _: Region, _: &Styles, visible: Range<i32>) -> Vec<Strip> {
+                self.0.set(Some(visible));
+                Vec::new()
+            }
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+            fn as_any_mut(&mut self) -> &mut dyn Any {
+                self
+            }
+        }
+
+        let mut view = ScrollView::new(Probe(Cell::new(None)), Size::new(100_000, 1));
+        view.set_viewport(Size::new(80, 1));
+        view.scroll_by(50_000, 0);
+        view.render(Region::new(0, 0, 80, 1), &Styles::new());
+        let probe = view.child().as_any().downcast_ref::<Probe>().unwrap();
+        assert_eq!(probe.0.take(), Some(0..80));
+    }
+
+    fn tall_view() -> ScrollView {
+        let content = (0..10)
+            .map(|i| format!("line{i}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut view = ScrollView::new(Static::new(content), Size::new(6, 10));
+        view.set_viewport(Size::new(8, 4));
+        view
+    }
+
+    #[test]
+    fn auto_overflow_draws_scrollbar_in_gutter() {
+        let mut view = tall_view();
+        view.scroll_by(0, 6);
+        let mut styles = Styles::new();
+        styles.overflow_y = Some(Overflow::Auto);
+        let strips = view.render(Region::new(0, 0, 8, 4), &styles);
+        let bar: String = strips
+            .iter()
+            .filter(|s| s.x_offset == 7)
+            .map(|s| s.cells[0].ch)
+            .collect();
+        assert_eq!(bar, "││██");
+    }
+
+    #[test]
+    fn stable_gutter_reserves_column_without_scrollbar() {
+        let view = tall_view();
+        let mut styles = Styles::new();
+        styles.scrollbar_gutter = Some(ScrollbarGutter::Stable);
+        let strips = view.render(Region::new(0, 0, 8, 4), &styles);
+        assert!(strips.iter().all(|s| s.right() <= 7 || s.x_offset == 7));
+        assert!(strips
+            .iter()
+            .filter(|s| s.x_offset == 7)
+            .all(|s| s.cells[0].ch == ' '));
+    }
+
+    #[test]
+    fn markers_show_hidden_content() {
+        let mut view = tall_view();
+        view.scroll_by(0, 3);
+        let mut styles = Styles::new();
+        styles.scroll_indicator = Some(ScrollIndicator::Marker);
+        let strips = view.render(Region::new(0, 0, 8, 4), &styles);
+        let row = |y| text(strips.iter().find(|s| s.y == y).unwrap());
+        assert_eq!(row(0), "l↑ more…");
+        assert_eq!(row(3), "l↓ more…");
+
+        styles.scroll_indicator = Some(ScrollIndicator::Shadow);
+        let strips = view.render(Region::new(0, 0, 8, 4), &styles);
+        let top = strips.iter().find(|s| s.y == 0).unwrap();
+        assert!(top.cells.iter().all(|c| c.style.dim()));
+    }
+
+    #[test]
+    fn overscroll_none_leaves_edge_input_unconsumed() {
+        let mut view = tall_view();
+        assert!(view.scroll_input(0, 2, OverscrollBehavior::None));
+        assert!(view.scroll_input(0, -5, OverscrollBehavior::None));
+        assert_eq!(view.scroll_state().offset.y, 0);
+        assert!(!view.scroll_input(0, -1, OverscrollBehavior::None));
+        assert!(view.scroll_input(0, -1, OverscrollBehavior::Clamp));
+    }
+
+    #[test]
+    fn offset_signal_follows_scrolling() {
+        crate::reactive::signal::reset_runtime();
+        let mut view = tall_view();
+        let offset = view.observe_offset();
+        let writes = std::rc::Rc::new(Cell::new(0));
+        let counter = writes.clone();
+        crate::reactive::create_effect(move || {
+            offset.get();
+            counter.set(counter.get() + 1);
+        });
+        view.scroll_by(0, 2);
+        assert_eq!(offset.get(), Offset::new(0, 2));
+        view.scroll_to(0, 2);
+        view.scroll_input(0, -9, OverscrollBehavior::Clamp);
+        assert_eq!(offset.get(), Offset::new(0, 0));
+        assert_eq!(writes.get(), 3);
+        assert_eq!(view.observe_offset().get(), Offset::new(0, 0));
+    }
+}
@@ -0,0 +1,265 @@
+//! ScrollableContainer widget: a [`ScrollView`] with keyboard scrolling built in.
+//!
+//! Wrapping content in a `ScrollableContainer` gives it a focusable viewport,
+//! a scrollbar that appears when the content overflows (`overflow-y: auto`
+//! unless the styles say otherwise), and default key bindings:
+//!
+//! | Key                 | Action                 |
+//! |---------------------|------------------------|
+//! | Up / Down           | one line               |
+//! | Left / Right        | one column             |
+//! | PageUp / PageDown   | one viewport height    |
+//! | Home / End          | top / bottom           |
+//!
+//! Bindings can be replaced or removed per container.
+
+use std::any::Any;
+use std::collections::HashMap;
+
+use crate::css::styles::{Overflow, Styles};
+use crate::event::input::{Key, KeyEvent, Modifiers};
+use crate::geometry::{Offset, Region, Size};
+use crate::reactive::ReadSignal;
+use crate::render::strip::Strip;
+use crate::widget::scroll::ScrollState;
+use crate::widget::traits::Widget;
+use crate::widgets::scroll_view::ScrollView;
+
+// ---------------------------------------------------------------------------
+// ScrollAction
+// ---------------------------------------------------------------------------
+
+/// A scroll movement a key can be bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScrollAction {
+    LineUp,
+    LineDown,
+    ColumnLeft,
+    ColumnRight,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+}
+
+impl ScrollAction {
+    /// The default bindings installed on every [`ScrollableContainer`].
+    pub const DEFAULT_BINDINGS: [(Key, ScrollAction); 8] = [
+        (Key::Up, ScrollAction::LineUp),
+        (Key::Down, ScrollAction::LineDown),
+        (Key::Left, ScrollAction::ColumnLeft),
+        (Key::Right, ScrollAction::ColumnRight),
+        (Key::PageUp, ScrollAction::PageUp),
+        (Key::PageDown, ScrollAction::PageDown),
+        (Key::Home, ScrollAction::Home),
+        (Key::End, ScrollAction::End),
+    ];
+}
+
+// ---------------------------------------------------------------------------
+// ScrollableContainer
+// ---------------------------------------------------------------------------
+
+/// A focusable, keyboard-scrollable viewport around a child widget.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut logs = ScrollableContainer::new(Static::new(log_text), Size::new(80, 400));
+/// logs.set_viewport(Size::new(80, 20));
+/// logs.handle_key(&KeyEvent::new(Key::PageDown, Modifiers::NONE));
+/// ```
+pub struct ScrollableContainer {
+    view: ScrollView,
+    bindings: HashMap<(Key, Modifiers), ScrollAction>,
+}
+
+impl ScrollableContainer {
+    /// Wrap `child`, whose full content is `content` cells in size.
+    pub fn new(child: impl Widget + 'static, content: Size) -> Self {
+        Self {
+            view: ScrollView::new(child, content),
+            bindings: ScrollAction::DEFAULT_BINDINGS
+                .into_iter()
+                .map(|(key, action)| ((key, Modifiers::NONE), action))
+                .collect(),
+        }
+    }
+
+    /// Bind a key to a scroll action, replacing any existing binding
+    /// (builder pattern).
+    pub fn with_binding(mut self, key: Key, modifiers: Modifiers, action: ScrollAction) -> Self {
+        self.bindings.insert((key, modifiers), action);
+        self
+    }
+
+    /// Remove a key binding, returning its action.
+    pub fn unbind(&mut self, key: Key, modifiers: Modifiers) -> Option<ScrollAction> {
+        self.bindings.remove(&(key, modifiers))
+    }
+
+    /// The action bound to a key event, if any.
+    pub fn binding(&self, event: &KeyEvent) -> Option<ScrollAction> {
+        self.bindings.get(&(event.code, event.modifiers)).copied()
+    }
+
+    /// The underlying scroll view.
+    pub fn view(&self) -> &ScrollView {
+        &self.view
+    }
+
+    /// The underlying scroll view, mutably.
+    pub fn view_mut(&mut self) -> &mut ScrollView {
+        &mut self.view
+    }
+
+    /// A signal holding the scroll offset. See [`ScrollView::observe_offset`].
+    pub fn observe_offset(&mut self) -> ReadSignal<Offset> {
+        self.view.observe_offset()
+    }
+
+    /// The scroll position and sizes.
+    pub fn scroll_state(&self) -> &ScrollState {
+        self.view.scroll_state()
+    }
+
+    /// Update the child's content size, re-clamping the offset.
+    pub fn set_content_size(&mut self, content: Size) {
+        self.view.set_content_size(content);
+    }
+
+    /// Update the visible size, re-clamping the offset.
+    pub fn set_viewport(&mut self, viewport: Size) {
+        self.view.set_viewport(viewport);
+    }
+
+    /// Perform a scroll action.
+    pub fn apply(&mut self, action: ScrollAction) {
+        let page = self.scroll_state().viewport_size.height.max(1);
+        let x = self.scroll_state().offset.x;
+        match action {
+            ScrollAction::LineUp => self.view.scroll_by(0, -1),
+            ScrollAction::LineDown => self.view.scroll_by(0, 1),
+            ScrollAction::ColumnLeft => self.view.scroll_by(-1, 0),
+            ScrollAction::ColumnRight => self.view.scroll_by(1, 0),
+            ScrollAction::PageUp => self.view.scroll_by(0, -page),
+            ScrollAction::PageDown => self.view.scroll_by(0, page),
+            ScrollAction::Home => self.view.scroll_to(x, 0),
+            ScrollAction::End => self.view.scroll_to(x, i32::MAX),
+        }
+    }
+
+    /// Handle a key press through the bindings. Returns whether a binding
+    /// matched.
+    pub fn handle_key(&mut self, event: &KeyEvent) -> bool {
+        let Some(action) = self.binding(event) else {
+            return false;
+        };
+        self.apply(action);
+        true
+    }
+}
+
+impl Widget for ScrollableContainer {
+    fn widget_type(&self) -> &str {
+        "ScrollableContainer"
+    }
+
+    fn default_css(&self) -> &str {
+        "ScrollableContainer { width: 1fr; height: 1fr; overflow-y: auto; }"
+    }
+
+    fn can_focus(&self) -> bool {
+        true
+    }
+
+    fn render(&self, region: Region, styles: &Styles) -> Vec<Strip> {
+        if styles.overflow_y.is_some() {
+            return self.view.render(region, styles);
+        }
+        let mut styles = styles.clone();
+        styles.overflow_y = Some(Overflow::Auto);
+        self.view.render(region, &styles)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::Static;
+
+    fn key(code: Key) -> KeyEvent {
+        KeyEvent::new(code, Modifiers::NONE)
+    }
+
+    fn container(lines: i32) -> ScrollableContainer {
+        let text = (0..lines)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut c = ScrollableContainer::new(Static::new(text), Size::new(10, lines));
+        c.set_viewport(Size::new(9, 4));
+        c
+    }
+
+    #[test]
+    fn default_bindings_scroll() {
+        let mut c = container(20);
+        assert!(c.handle_key(&key(Key::Down)));
+        assert_eq!(c.scroll_state().offset, Offset::new(0, 1));
+        assert!(c.handle_key(&key(Key::PageDown)));
+        assert_eq!(c.scroll_state().offset.y, 5);
+        assert!(c.handle_key(&key(Key::End)));
+        assert_eq!(c.scroll_state().offset.y, 16);
+        assert!(c.handle_key(&key(Key::Right)));
+        assert_eq!(c.scroll_state().offset, Offset::new(1, 16));
+        assert!(c.handle_key(&key(Key::Home)));
+        assert_eq!(c.scroll_state().offset, Offset::new(1, 0));
+        assert!(!c.handle_key(&key(Key::Enter)));
+    }
+
+    #[test]
+    fn bindings_can_be_replaced_and_removed() {
+        let mut c =
+            container(20).with_binding(Key::Char('j'), Modifiers::NONE, ScrollAction::LineDown);
+        assert!(c.handle_key(&key(Key::Char('j'))));
+        assert_eq!(
+            c.unbind(Key::Down, Modifiers::NONE),
+            Some(ScrollAction::LineDown)
+        );
+        assert!(!c.handle_key(&key(Key::Down)));
+        assert_eq!(c.scroll_state().offset.y, 1);
+    }
+
+    #[test]
+    fn scrollbar_appears_only_on_overflow() {
+        let last_column = |c: &ScrollableContainer| -> String {
+            c.render(Region::new(0, 0, 10, 4), &Styles::new())
+                .iter()
+                .filter(|strip| strip.x_offset == 9)
+                .map(|strip| strip.cells[0].ch)
+                .collect()
+        };
+        assert_eq!(last_column(&container(20)), "█│││");
+        assert_eq!(last_column(&container(3)), "");
+    }
+
+    #[test]
+    fn is_focusable_with_overflow_css() {
+        let c = container(1);
+        assert!(c.can_focus());
+        assert!(c.default_css().contains("overflow-y: auto"));
+    }
+}
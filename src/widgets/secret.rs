@@ -0,0 +1,175 @@
+//! Secret widget: a value shown masked until revealed.
+//!
+//! Masked, the widget draws a fixed run of mask characters so the length of
+//! the secret isn't leaked either, optionally followed by its last few
+//! characters so operators can tell keys apart. Revealed, it draws the value
+//! as-is.
+
+use std::any::Any;
+
+use crate::css::styles::{Styles, TextOverflow};
+use crate::geometry::Region;
+use crate::render::strip::{CellStyle, Strip};
+use crate::render::text::fit;
+use crate::widget::redact::MASK;
+use crate::widget::traits::Widget;
+
+/// Mask characters drawn for a hidden value, whatever its length.
+const MASK_LEN: usize = 8;
+
+// ---------------------------------------------------------------------------
+// Secret
+// ---------------------------------------------------------------------------
+
+/// A single-line secret value with a reveal toggle.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut key = Secret::new(api_key).with_visible_suffix(4);
+/// // Draws "••••••••f3a9".
+/// key.toggle_revealed();
+/// ```
+pub struct Secret {
+    value: String,
+    revealed: bool,
+    mask: char,
+    visible_suffix: usize,
+}
+
+impl Secret {
+    /// Create a masked secret.
+    pub fn new(value: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            revealed: false,
+            mask: MASK,
+            visible_suffix: 0,
+        }
+    }
+
+    /// Set the mask character (builder pattern). Defaults to `•`.
+    pub fn with_mask(mut self, mask: char) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    /// Keep the last `count` characters visible while masked (builder
+    /// pattern). Values no longer than `count` are masked entirely.
+    pub fn with_visible_suffix(mut self, count: usize) -> Self {
+        self.visible_suffix = count;
+        self
+    }
+
+    /// The secret value.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Replace the secret value.
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        self.value = value.into();
+    }
+
+    /// Whether the value is shown in the clear.
+    pub fn is_revealed(&self) -> bool {
+        self.revealed
+    }
+
+    /// Show or mask the value.
+    pub fn set_revealed(&mut self, revealed: bool) {
+        self.revealed = revealed;
+    }
+
+    /// Flip between showing and masking the value.
+    pub fn toggle_revealed(&mut self) {
+        self.revealed = !self.revealed;
+    }
+
+    /// The text drawn for the current state.
+    pub fn display_text(&self) -> String {
+        if self.revealed {
+            return self.value.clone();
+        }
+        let mut text: String = std::iter::repeat_n(self.mask, MASK_LEN).collect();
+        let len = self.value.chars().count();
+        if self.visible_suffix > 0 && len > self.visible_suffix {
+            text.extend(self.value.chars().skip(len - self.visible_suffix));
+        }
+        text
+    }
+}
+
+impl Widget for Secret {
+    fn widget_type(&self) -> &str {
+        "Secret"
+    }
+
+    fn default_css(&self) -> &str {
+        "Secret { height: 1; width: 1fr; }"
+    }
+
+    fn render(&self, region: Region, styles: &Styles) -> Vec<Strip> {
+        if region.width <= 0 || region.height <= 0 {
+            return Vec::new();
+        }
+        let style = CellStyle::from_styles(styles);
+        let text = fit(
+            &self.display_text(),
+            region.width as usize,
+            TextOverflow::Ellipsis,
+        );
+        let mut strip = Strip::new(region.y, region.x);
+        strip.push_str(&text, style);
+        strip.fill(region.width, style);
+        vec![strip]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(secret: &Secret, width: i32) -> String {
+        secret.render(Region::new(0, 0, width, 1), &Styles::new())[0]
+            .cells
+            .iter()
+            .map(|c| c.ch)
+            .collect()
+    }
+
+    #[test]
+    fn masked_hides_length() {
+        let short = Secret::new("abc");
+        let long = Secret::new("a-much-longer-secret");
+        assert_eq!(text(&short, 10), "••••••••  ");
+        assert_eq!(text(&long, 10), "••••••••  ");
+    }
+
+    #[test]
+    fn suffix_and_reveal() {
+        let mut key = Secret::new("sk-12345f3a9")
+            .with_mask('*')
+            .with_visible_suffix(4);
+        assert_eq!(key.display_text(), "********f3a9");
+        assert_eq!(
+            Secret::new("f3a9").with_visible_suffix(4).display_text(),
+            "••••••••"
+        );
+        key.toggle_revealed();
+        assert!(key.is_revealed());
+        assert_eq!(text(&key, 14), "sk-12345f3a9  ");
+    }
+}
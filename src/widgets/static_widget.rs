@@ -1,13 +1,16 @@
 //! Static widget: displays fixed text content.
 //!
 //! The simplest widget in gilt-tui. It renders one or more lines of
-//! immutable text within the given region, applying CSS-derived styles.
+//! immutable text within the given region, applying CSS-derived styles. The
+//! content is [markup](crate::text), so parts of it can be styled inline.
 
 use std::any::Any;
+use std::ops::Range;
 
 use crate::css::styles::Styles;
 use crate::geometry::Region;
 use crate::render::strip::{CellStyle, Strip};
+use crate::text::Text;
 use crate::widget::traits::Widget;
 
 // ---------------------------------------------------------------------------
@@ -17,29 +20,39 @@ use crate::widget::traits::Widget;
 /// A widget that displays fixed, non-interactive text content.
 ///
 /// Lines are split on `'\n'`. Each line is rendered as one [`Strip`], truncated
-/// to the region width and limited to the region height.
+/// to the region width and limited to the region height. Markup tags style
+/// parts of a line on top of the CSS style.
 ///
 /// # Examples
 ///
 /// ```ignore
 /// let label = Static::new("Hello, world!");
+/// let status = Static::new("Build [bold green]passed[/] in 3s");
 /// ```
 pub struct Static {
     content: String,
+    text: Text,
 }
 
 impl Static {
-    /// Create a new `Static` widget with the given text content.
+    /// Create a new `Static` widget with the given content, parsed as markup.
     pub fn new(content: impl Into<String>) -> Self {
+        let content = content.into();
         Self {
-            content: content.into(),
+            text: Text::from_markup(&content),
+            content,
         }
     }
 
-    /// Return the text content.
+    /// Return the content as given, markup included.
     pub fn content(&self) -> &str {
         &self.content
     }
+
+    /// Return the styled text the content parsed to.
+    pub fn text(&self) -> &Text {
+        &self.text
+    }
 }
 
 impl Widget for Static {
@@ -47,6 +60,10 @@ impl Widget for Static {
         "Static"
     }
 
+    fn content_text(&self) -> Option<&str> {
+        Some(&self.content)
+    }
+
     fn render(&self, region: Region, styles: &Styles) -> Vec<Strip> {
         if region.width <= 0 || region.height <= 0 {
             return Vec::new();
@@ -56,15 +73,41 @@ impl Widget for Static {
         let max_width = region.width as usize;
         let max_height = region.height as usize;
 
-        self.content
-            .split('\n')
+        self.text
+            .lines()
+            .iter()
             .take(max_height)
             .enumerate()
             .map(|(i, line)| {
                 let mut strip = Strip::new(region.y + i as i32, region.x);
-                let truncated: String = line.chars().take(max_width).collect();
-                strip.push_str(&truncated, style.clone());
-                strip.fill(region.width, style.clone());
+                line.slice(0, max_width).push_to(&mut strip, style);
+                strip.fill(region.width, style);
+                strip
+            })
+            .collect()
+    }
+
+    fn render_window(&self, region: Region, styles: &Styles, visible: Range<i32>) -> Vec<Strip> {
+        let start = visible.start.max(region.x);
+        let end = visible.end.min(region.right());
+        if end <= start || region.height <= 0 {
+            return Vec::new();
+        }
+
+        // Only the visible slice of each line is materialized.
+        let style = CellStyle::from_styles(styles);
+        let skip = (start - region.x) as usize;
+        let width = (end - start) as usize;
+
+        self.text
+            .lines()
+            .iter()
+            .take(region.height as usize)
+            .enumerate()
+            .map(|(i, line)| {
+                let mut strip = Strip::new(region.y + i as i32, start);
+                line.slice(skip, width).push_to(&mut strip, style);
+                strip.fill(width as i32, style);
                 strip
             })
             .collect()
@@ -86,6 +129,7 @@ impl Widget for Static {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::render::strip::Color;
 
     fn region(w: i32, h: i32) -> Region {
         Region::new(0, 0, w, h)
@@ -182,13 +226,39 @@ mod tests {
         assert!(strips.is_empty());
     }
 
+    #[test]
+    fn render_window_materializes_visible_columns_only() {
+        let w = Static::new("abcdefghij\nklmnopqrst");
+        let strips = w.render_window(Region::new(-3, 0, 10, 2), &styles(), 0..4);
+        assert_eq!(strips.len(), 2);
+        assert_eq!(strips[1].x_offset, 0);
+        let text: String = strips[1].cells.iter().map(|c| c.ch).collect();
+        assert_eq!(text, "nopq");
+    }
+
     #[test]
     fn render_applies_styles() {
         let w = Static::new("Hi");
         let mut s = styles();
         s.color = Some("red".into());
         let strips = w.render(region(5, 1), &s);
-        assert_eq!(strips[0].cells[0].style.fg, Some("red".into()));
+        assert_eq!(strips[0].cells[0].style.fg, Some(Color::Red));
+    }
+
+    #[test]
+    fn render_markup_styles_runs_over_css() {
+        let w = Static::new("a [bold]b[/] [on blue]c[/]\\[");
+        let mut s = styles();
+        s.color = Some("red".into());
+        let strips = w.render(region(6, 1), &s);
+        let text: String = strips[0].cells.iter().map(|c| c.ch).collect();
+        assert_eq!(text, "a b c[");
+        let cells = &strips[0].cells;
+        assert!(!cells[0].style.bold() && cells[2].style.bold());
+        assert_eq!(cells[2].style.fg, Some(Color::Red));
+        assert_eq!(cells[4].style.bg, Some(Color::Blue));
+        assert_eq!(cells[5].style.bg, None);
+        assert_eq!(w.text().plain(), "a b c[");
     }
 
     #[test]
@@ -0,0 +1,376 @@
+//! StatusBar widget: a one-line bar with left, center, and right segments.
+//!
+//! Each segment holds either fixed styled spans or a reactive string signal,
+//! and carries a priority. When the bar is too narrow for everything, the
+//! lowest-priority segments are dropped first; if a single segment still does
+//! not fit it is truncated with an ellipsis.
+
+use std::any::Any;
+
+use crate::css::styles::Styles;
+use crate::geometry::Region;
+use crate::reactive::ReadSignal;
+use crate::render::strip::{CellStyle, Strip};
+use crate::widget::traits::Widget;
+
+// ---------------------------------------------------------------------------
+// Span / Segment
+// ---------------------------------------------------------------------------
+
+/// A run of text with an optional style override.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Span {
+    /// The text.
+    pub text: String,
+    /// Style for this span; `None` uses the bar's style.
+    pub style: Option<CellStyle>,
+}
+
+impl Span {
+    /// A span in the bar's own style.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            style: None,
+        }
+    }
+
+    /// A span with its own style.
+    pub fn styled(text: impl Into<String>, style: CellStyle) -> Self {
+        Self {
+            text: text.into(),
+            style: Some(style),
+        }
+    }
+}
+
+/// Where a segment's content comes from.
+#[derive(Debug, Clone)]
+enum Content {
+    Spans(Vec<Span>),
+    Signal(ReadSignal<String>),
+}
+
+/// One status bar segment.
+#[derive(Debug, Clone)]
+pub struct Segment {
+    content: Content,
+    priority: u8,
+}
+
+impl Segment {
+    /// A segment with fixed text.
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::spans(vec![Span::new(text)])
+    }
+
+    /// A segment made of styled spans.
+    pub fn spans(spans: Vec<Span>) -> Self {
+        Self {
+            content: Content::Spans(spans),
+            priority: 0,
+        }
+    }
+
+    /// A segment that shows the current value of a signal.
+    ///
+    /// Rendering reads the signal, so an effect driving the render loop
+    /// re-runs when it changes.
+    pub fn signal(signal: ReadSignal<String>) -> Self {
+        Self {
+            content: Content::Signal(signal),
+            priority: 0,
+        }
+    }
+
+    /// Set the truncation priority (builder). Higher priorities are kept
+    /// longer when the bar is too narrow; the default is 0.
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// The truncation priority.
+    pub fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    /// The segment's current spans.
+    fn resolve(&self) -> Vec<Span> {
+        match &self.content {
+            Content::Spans(spans) => spans.clone(),
+            Content::Signal(signal) => vec![Span::new(signal.get())],
+        }
+    }
+}
+
+/// Display width of a list of spans.
+fn spans_width(spans: &[Span]) -> i32 {
+    spans.iter().map(|s| s.text.chars().count() as i32).sum()
+}
+
+// ---------------------------------------------------------------------------
+// StatusBar
+// ---------------------------------------------------------------------------
+
+/// Which part of the bar a segment belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Left,
+    Center,
+    Right,
+}
+
+/// A one-line status bar with left, center, and right segment groups.
+///
+/// # Examples
+///
+/// ```ignore
+/// let (mode, set_mode) = create_signal("NORMAL".to_string());
+/// let bar = StatusBar::new()
+///     .with_left(Segment::signal(mode).with_priority(10))
+///     .with_center(Segment::text("main.rs"))
+///     .with_right(Segment::text("Ln 12, Col 4").with_priority(5));
+/// ```
+pub struct StatusBar {
+    segments: Vec<(Side, Segment)>,
+    separator: String,
+}
+
+impl StatusBar {
+    /// Create an empty status bar.
+    pub fn new() -> Self {
+        Self {
+            segments: Vec::new(),
+            separator: " │ ".into(),
+        }
+    }
+
+    /// Append a segment to the left group (builder).
+    pub fn with_left(mut self, segment: Segment) -> Self {
+        self.segments.push((Side::Left, segment));
+        self
+    }
+
+    /// Append a segment to the center group (builder).
+    pub fn with_center(mut self, segment: Segment) -> Self {
+        self.segments.push((Side::Center, segment));
+        self
+    }
+
+    /// Append a segment to the right group (builder).
+    pub fn with_right(mut self, segment: Segment) -> Self {
+        self.segments.push((Side::Right, segment));
+        self
+    }
+
+    /// Set the text placed between segments of the same group (builder).
+    pub fn with_separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Number of segments across all groups.
+    pub fn segment_count(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Join the kept segments of one side into a span list.
+    fn group(&self, side: Side, resolved: &[(Side, Vec<Span>)], keep: &[bool]) -> Vec<Span> {
+        let mut out = Vec::new();
+        for ((s, spans), &kept) in resolved.iter().zip(keep) {
+            if *s != side || !kept {
+                continue;
+            }
+            if !out.is_empty() {
+                out.push(Span::new(self.separator.clone()));
+            }
+            out.extend(spans.iter().cloned());
+        }
+        out
+    }
+
+    /// Lay out the groups for `width`, returning `(x, spans)` runs.
+    fn layout(&self, width: i32) -> Vec<(i32, Vec<Span>)> {
+        let resolved: Vec<(Side, Vec<Span>)> = self
+            .segments
+            .iter()
+            .map(|(side, seg)| (*side, seg.resolve()))
+            .collect();
+        let mut keep = vec![true; resolved.len()];
+
+        loop {
+            let left = self.group(Side::Left, &resolved, &keep);
+            let center = self.group(Side::Center, &resolved, &keep);
+            let right = self.group(Side::Right, &resolved, &keep);
+            let widths = [
+                spans_width(&left),
+                spans_width(&center),
+                spans_width(&right),
+            ];
+            let groups = widths.iter().filter(|&&w| w > 0).count() as i32;
+            let needed: i32 = widths.iter().sum::<i32>() + (groups - 1).max(0);
+
+            let kept = keep.iter().filter(|&&k| k).count();
+            if needed <= width || kept <= 1 {
+                let [lw, cw, rw] = widths;
+                let cx = ((width - cw) / 2)
+                    .max(lw + 1)
+                    .min(width - rw - 1 - cw)
+                    .max(0);
+                return vec![(0, left), (cx, center), ((width - rw).max(0), right)];
+            }
+
+            // Drop the lowest-priority kept segment (the last one on ties).
+            let drop = (0..keep.len())
+                .filter(|&i| keep[i])
+                .min_by_key(|&i| (self.segments[i].1.priority, std::cmp::Reverse(i)))
+                .expect("at least two segments kept");
+            keep[drop] = false;
+        }
+    }
+}
+
+impl Default for StatusBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for StatusBar {
+    fn widget_type(&self) -> &str {
+        "StatusBar"
+    }
+
+    fn default_css(&self) -> &str {
+        "StatusBar { height: 1; dock: bottom; width: 1fr; }"
+    }
+
+    fn render(&self, region: Region, styles: &Styles) -> Vec<Strip> {
+        if region.width <= 0 || region.height <= 0 {
+            return Vec::new();
+        }
+
+        let style = CellStyle::from_styles(styles);
+        let mut cells = vec![(' ', style); region.width as usize];
+
+        for (x, spans) in self.layout(region.width) {
+            let mut col = x;
+            let total = spans_width(&spans);
+            // Truncate a lone segment that is still wider than the bar.
+            let limit = if x + total > region.width {
+                region.width - 1
+            } else {
+                region.width
+            };
+            for span in &spans {
+                let span_style = span.style.unwrap_or(style);
+                for ch in span.text.chars() {
+                    if col >= limit {
+                        break;
+                    }
+                    cells[col as usize] = (ch, span_style);
+                    col += 1;
+                }
+            }
+            if limit < region.width && col == limit {
+                cells[limit as usize] = ('…', style);
+            }
+        }
+
+        let mut strip = Strip::new(region.y, region.x);
+        for (ch, cell_style) in cells {
+            strip.push(ch, cell_style);
+        }
+        vec![strip]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reactive::create_signal;
+    use crate::reactive::signal::reset_runtime;
+    use crate::render::strip::Attrs;
+
+    fn text(bar: &StatusBar, width: i32) -> String {
+        bar.render(Region::new(0, 0, width, 1), &Styles::new())[0]
+            .cells
+            .iter()
+            .map(|c| c.ch)
+            .collect()
+    }
+
+    fn bar() -> StatusBar {
+        StatusBar::new()
+            .with_left(Segment::text("NORMAL").with_priority(10))
+            .with_center(Segment::text("main.rs").with_priority(1))
+            .with_right(Segment::text("12:4").with_priority(5))
+    }
+
+    #[test]
+    fn places_left_center_right() {
+        let out = text(&bar(), 30);
+        assert_eq!(out.chars().count(), 30);
+        assert!(out.starts_with("NORMAL"));
+        assert!(out.ends_with("12:4"));
+        assert_eq!(out.find("main.rs"), Some(11));
+    }
+
+    #[test]
+    fn drops_lowest_priority_when_narrow() {
+        let out = text(&bar(), 14);
+        assert_eq!(out, "NORMAL    12:4");
+        let out = text(&bar(), 8);
+        assert_eq!(out, "NORMAL  ");
+    }
+
+    #[test]
+    fn truncates_last_segment_with_ellipsis() {
+        let out = text(&bar(), 4);
+        assert_eq!(out, "NOR…");
+    }
+
+    #[test]
+    fn separator_joins_group_segments() {
+        let bar = StatusBar::new()
+            .with_left(Segment::text("a"))
+            .with_left(Segment::text("b"))
+            .with_separator("|");
+        assert!(text(&bar, 10).starts_with("a|b"));
+    }
+
+    #[test]
+    fn signal_segment_reflects_updates() {
+        reset_runtime();
+        let (mode, set_mode) = create_signal("INSERT".to_string());
+        let bar = StatusBar::new().with_left(Segment::signal(mode));
+        assert!(text(&bar, 10).starts_with("INSERT"));
+        set_mode.set("VISUAL".into());
+        assert!(text(&bar, 10).starts_with("VISUAL"));
+    }
+
+    #[test]
+    fn styled_spans_keep_their_style() {
+        let bold = CellStyle::new().with_attrs(Attrs::BOLD);
+        let bar = StatusBar::new().with_left(Segment::spans(vec![
+            Span::styled("E", bold),
+            Span::new("rr"),
+        ]));
+        let strip = &bar.render(Region::new(0, 0, 5, 1), &Styles::new())[0];
+        assert!(strip.cells[0].style.bold());
+        assert!(!strip.cells[1].style.bold());
+    }
+}
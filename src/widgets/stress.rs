@@ -0,0 +1,201 @@
+//! StressGrid widget: a synthetic workload for profiling the render pipeline.
+//!
+//! Renders an N×M grid of colored cells whose colors change every tick. The
+//! tick counter lives in a signal, so the grid exercises the reactive runtime
+//! as well as rendering, compositing, and diffing. Only compiled with the
+//! `stress` feature.
+
+use std::any::Any;
+
+use crate::css::styles::Styles;
+use crate::geometry::Region;
+use crate::reactive::{create_signal, ReadSignal, WriteSignal};
+use crate::render::strip::{CellStyle, Color, Strip};
+use crate::widget::traits::Widget;
+
+// ---------------------------------------------------------------------------
+// StressGrid
+// ---------------------------------------------------------------------------
+
+/// Palette cycled through by the grid cells.
+const PALETTE: &[Color] = &[
+    Color::Rgb(0xe9, 0x45, 0x60),
+    Color::Rgb(0x0f, 0x34, 0x60),
+    Color::Rgb(0x16, 0x21, 0x3e),
+    Color::Rgb(0x53, 0x35, 0x4a),
+    Color::Rgb(0xf0, 0xa5, 0x00),
+    Color::Rgb(0x00, 0xad, 0xb5),
+    Color::Rgb(0x5c, 0x2a, 0x9d),
+    Color::Rgb(0x2e, 0xcc, 0x71),
+];
+
+/// A grid of constantly changing colored cells.
+///
+/// Each cell is `cell_width` columns wide; every call to [`StressGrid::tick`]
+/// bumps the tick signal and changes every cell's color.
+///
+/// # Examples
+///
+/// ```ignore
+/// let grid = StressGrid::new(40, 20);
+/// loop {
+///     grid.tick();
+///     // render, composite, diff...
+/// }
+/// ```
+pub struct StressGrid {
+    cols: u16,
+    rows: u16,
+    cell_width: u16,
+    tick: ReadSignal<u64>,
+    set_tick: WriteSignal<u64>,
+}
+
+impl StressGrid {
+    /// Create a grid with `cols` × `rows` cells.
+    pub fn new(cols: u16, rows: u16) -> Self {
+        let (tick, set_tick) = create_signal(0u64);
+        Self {
+            cols,
+            rows,
+            cell_width: 2,
+            tick,
+            set_tick,
+        }
+    }
+
+    /// Set the width of each cell in columns (builder pattern).
+    pub fn with_cell_width(mut self, width: u16) -> Self {
+        self.cell_width = width.max(1);
+        self
+    }
+
+    /// Advance the animation by one step.
+    pub fn tick(&self) {
+        self.set_tick.update(|t| *t = t.wrapping_add(1));
+    }
+
+    /// The tick signal, for effects that want to follow the grid.
+    pub fn tick_signal(&self) -> ReadSignal<u64> {
+        self.tick
+    }
+
+    /// Grid dimensions in cells as `(cols, rows)`.
+    pub fn dimensions(&self) -> (u16, u16) {
+        (self.cols, self.rows)
+    }
+
+    /// Color of the cell at `(col, row)` for the given tick.
+    fn color_at(tick: u64, col: u16, row: u16) -> Color {
+        let idx = (tick as usize)
+            .wrapping_add(col as usize * 7)
+            .wrapping_add(row as usize * 13);
+        PALETTE[idx % PALETTE.len()]
+    }
+}
+
+impl Widget for StressGrid {
+    fn widget_type(&self) -> &str {
+        "StressGrid"
+    }
+
+    fn default_css(&self) -> &str {
+        "StressGrid { width: 1fr; height: 1fr; }"
+    }
+
+    fn render(&self, region: Region, _styles: &Styles) -> Vec<Strip> {
+        if region.width <= 0 || region.height <= 0 {
+            return Vec::new();
+        }
+
+        let tick = self.tick.get();
+        let rows = (self.rows as i32).min(region.height);
+        let cell_width = self.cell_width as i32;
+        let cols = (self.cols as i32).min(region.width / cell_width);
+
+        (0..rows)
+            .map(|row| {
+                let mut strip = Strip::new(region.y + row, region.x);
+                for col in 0..cols {
+                    let style = CellStyle {
+                        bg: Some(Self::color_at(tick, col as u16, row as u16)),
+                        ..CellStyle::new()
+                    };
+                    for _ in 0..cell_width {
+                        strip.push(' ', style);
+                    }
+                }
+                strip.fill(region.width, CellStyle::new());
+                strip
+            })
+            .collect()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reactive::create_effect;
+    use crate::reactive::signal::reset_runtime;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn render_dimensions() {
+        reset_runtime();
+        let grid = StressGrid::new(4, 3);
+        let strips = grid.render(Region::new(0, 0, 20, 10), &Styles::new());
+        assert_eq!(strips.len(), 3);
+        assert_eq!(strips[0].width(), 20);
+        assert!(strips[0].cells[7].style.bg.is_some());
+        assert!(strips[0].cells[8].style.bg.is_none());
+    }
+
+    #[test]
+    fn tick_changes_colors() {
+        reset_runtime();
+        let grid = StressGrid::new(4, 1);
+        let region = Region::new(0, 0, 8, 1);
+        let before = grid.render(region, &Styles::new());
+        grid.tick();
+        let after = grid.render(region, &Styles::new());
+        assert_ne!(before[0].cells[0].style, after[0].cells[0].style);
+    }
+
+    #[test]
+    fn tick_notifies_effects() {
+        reset_runtime();
+        let grid = StressGrid::new(1, 1);
+        let runs = Rc::new(Cell::new(0));
+        let runs_c = runs.clone();
+        let tick = grid.tick_signal();
+        create_effect(move || {
+            tick.get();
+            runs_c.set(runs_c.get() + 1);
+        });
+        grid.tick();
+        grid.tick();
+        assert_eq!(runs.get(), 3);
+    }
+
+    #[test]
+    fn clipped_to_region() {
+        reset_runtime();
+        let grid = StressGrid::new(100, 100).with_cell_width(1);
+        let strips = grid.render(Region::new(0, 0, 5, 2), &Styles::new());
+        assert_eq!(strips.len(), 2);
+        assert_eq!(strips[1].width(), 5);
+    }
+}
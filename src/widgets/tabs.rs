@@ -0,0 +1,475 @@
+//! Tabs widget: a row of tab labels switching between [`TabPane`] children.
+//!
+//! [`Tabs`] draws one label per pane on its first row and lays its panes out
+//! below it. Only the active pane carries the [`ACTIVE_CLASS`] class; the
+//! default CSS gives every other pane `display: none`, so inactive panes take
+//! no space and the layout engine skips their subtrees entirely. Switching
+//! tabs therefore only changes the classes of the two panes involved, and
+//! only the newly active pane is laid out.
+//!
+//! Left/Right (see [`Tabs::handle_key`]) and clicks on the labels (see
+//! [`Tabs::handle_mouse`]) switch panes and return a [`TabActivated`]
+//! message. Reconcile the widget afterwards to push the class change to the
+//! DOM. Style the active pane with `TabPane.active`.
+
+use std::any::Any;
+
+use crate::css::styles::Styles;
+use crate::event::input::{Key, KeyEvent, MouseAction, MouseBtn, MouseEvent};
+use crate::event::message::Message;
+use crate::geometry::Region;
+use crate::render::fill::paint_background;
+use crate::render::strip::{Attrs, CellStyle, Strip};
+use crate::render::text::text_width;
+use crate::widget::traits::Widget;
+
+/// Class carried by the active [`TabPane`].
+pub const ACTIVE_CLASS: &str = "active";
+
+/// Emitted when a different tab becomes active.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TabActivated {
+    /// Index of the active pane.
+    pub index: usize,
+    /// Title of the active pane.
+    pub title: String,
+}
+
+impl Message for TabActivated {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn message_name(&self) -> &str {
+        "TabActivated"
+    }
+}
+
+// ---------------------------------------------------------------------------
+// TabPane
+// ---------------------------------------------------------------------------
+
+/// One page of a [`Tabs`] widget: a titled container for child widgets.
+pub struct TabPane {
+    title: String,
+    children: Vec<Box<dyn Widget>>,
+    id: Option<String>,
+    classes: Vec<String>,
+}
+
+impl TabPane {
+    /// Create an empty pane labelled `title`.
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            children: Vec::new(),
+            id: None,
+            classes: Vec::new(),
+        }
+    }
+
+    /// Add a child widget (builder pattern).
+    pub fn with_child(mut self, child: impl Widget + 'static) -> Self {
+        self.children.push(Box::new(child));
+        self
+    }
+
+    /// Set the CSS id (builder pattern).
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = Some(id.to_owned());
+        self
+    }
+
+    /// Add a CSS class (builder pattern).
+    pub fn with_class(mut self, class: &str) -> Self {
+        self.add_class(class);
+        self
+    }
+
+    /// The label shown for this pane.
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Whether this is the active pane.
+    pub fn is_active(&self) -> bool {
+        self.classes.iter().any(|c| c == ACTIVE_CLASS)
+    }
+
+    fn set_active(&mut self, active: bool) {
+        if active {
+            self.add_class(ACTIVE_CLASS);
+        } else {
+            self.classes.retain(|c| c != ACTIVE_CLASS);
+        }
+    }
+
+    fn add_class(&mut self, class: &str) {
+        if !self.classes.iter().any(|c| c == class) {
+            self.classes.push(class.to_owned());
+        }
+    }
+}
+
+impl Widget for TabPane {
+    fn widget_type(&self) -> &str {
+        "TabPane"
+    }
+
+    fn default_css(&self) -> &str {
+        "TabPane { display: none; layout: vertical; width: 1fr; height: 1fr; } \
+         TabPane.active { display: block; }"
+    }
+
+    fn render(&self, region: Region, styles: &Styles) -> Vec<Strip> {
+        if region.width <= 0 || region.height <= 0 {
+            return Vec::new();
+        }
+        let style = CellStyle::from_styles(styles);
+        let mut strips: Vec<Strip> = (0..region.height)
+            .map(|row| {
+                let mut strip = Strip::new(region.y + row, region.x);
+                strip.fill(region.width, style);
+                strip
+            })
+            .collect();
+        paint_background(&mut strips, region, styles);
+        strips
+    }
+
+    fn css_id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    fn css_classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    fn child_widgets(&self) -> &[Box<dyn Widget>] {
+        &self.children
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tabs
+// ---------------------------------------------------------------------------
+
+/// A row of tab labels above the active [`TabPane`].
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut tabs = Tabs::new()
+///     .with_pane(TabPane::new("General").with_child(Static::new("...")))
+///     .with_pane(TabPane::new("Advanced").with_child(Input::new()));
+/// if let Some(activated) = tabs.handle_key(&key) {
+///     let mut tx = Transaction::new();
+///     reconcile_node(&mut tx, &screen.dom, tabs_node, &tabs);
+///     screen.apply(tx)?;
+/// }
+/// ```
+pub struct Tabs {
+    /// Always `TabPane`s.
+    panes: Vec<Box<dyn Widget>>,
+    active: usize,
+    id: Option<String>,
+    classes: Vec<String>,
+}
+
+impl Tabs {
+    /// Create tabs with no panes.
+    pub fn new() -> Self {
+        Self {
+            panes: Vec::new(),
+            active: 0,
+            id: None,
+            classes: Vec::new(),
+        }
+    }
+
+    /// Add a pane (builder pattern). The first pane added is active.
+    pub fn with_pane(mut self, mut pane: TabPane) -> Self {
+        pane.set_active(self.panes.len() == self.active);
+        self.panes.push(Box::new(pane));
+        self
+    }
+
+    /// Set the CSS id (builder pattern).
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = Some(id.to_owned());
+        self
+    }
+
+    /// Add a CSS class (builder pattern).
+    pub fn with_class(mut self, class: &str) -> Self {
+        let class = class.to_owned();
+        if !self.classes.contains(&class) {
+            self.classes.push(class);
+        }
+        self
+    }
+
+    /// Number of panes.
+    pub fn len(&self) -> usize {
+        self.panes.len()
+    }
+
+    /// Whether there are no panes.
+    pub fn is_empty(&self) -> bool {
+        self.panes.is_empty()
+    }
+
+    /// Index of the active pane.
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    /// The pane at `index`.
+    pub fn pane(&self, index: usize) -> Option<&TabPane> {
+        self.panes.get(index)?.as_any().downcast_ref()
+    }
+
+    /// The pane titles, in order.
+    pub fn titles(&self) -> Vec<&str> {
+        (0..self.len())
+            .filter_map(|i| self.pane(i).map(TabPane::title))
+            .collect()
+    }
+
+    /// Make the pane at `index` active. Returns `None` if it already was or
+    /// is out of range.
+    pub fn set_active(&mut self, index: usize) -> Option<TabActivated> {
+        if index == self.active || index >= self.len() {
+            return None;
+        }
+        for i in [self.active, index] {
+            if let Some(pane) = self.pane_mut(i) {
+                pane.set_active(i == index);
+            }
+        }
+        self.active = index;
+        Some(TabActivated {
+            index,
+            title: self.pane(index)?.title.clone(),
+        })
+    }
+
+    /// Handle Left/Right, which move to the previous/next tab, wrapping.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> Option<TabActivated> {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+        match key.code {
+            Key::Left => self.set_active((self.active + len - 1) % len),
+            Key::Right => self.set_active((self.active + 1) % len),
+            _ => None,
+        }
+    }
+
+    /// Handle a left click on a tab label, for tabs at `region`.
+    pub fn handle_mouse(&mut self, event: &MouseEvent, region: Region) -> Option<TabActivated> {
+        if event.kind != MouseAction::Down(MouseBtn::Left) || event.y as i32 != region.y {
+            return None;
+        }
+        let index = self.tab_at(event.x as i32, region)?;
+        self.set_active(index)
+    }
+
+    /// The index of the tab whose label is drawn at column `x`.
+    pub fn tab_at(&self, x: i32, region: Region) -> Option<usize> {
+        let mut start = region.x;
+        for (i, title) in self.titles().into_iter().enumerate() {
+            let end = start + text_width(&label(title)) as i32;
+            if (start..end).contains(&x) && x < region.right() {
+                return Some(i);
+            }
+            start = end;
+        }
+        None
+    }
+
+    fn pane_mut(&mut self, index: usize) -> Option<&mut TabPane> {
+        self.panes.get_mut(index)?.as_any_mut().downcast_mut()
+    }
+}
+
+/// The text drawn for a tab titled `title`.
+fn label(title: &str) -> String {
+    format!(" {title} ")
+}
+
+impl Default for Tabs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for Tabs {
+    fn widget_type(&self) -> &str {
+        "Tabs"
+    }
+
+    fn default_css(&self) -> &str {
+        "Tabs { layout: vertical; width: 1fr; height: 1fr; padding: 1 0 0 0; }"
+    }
+
+    fn can_focus(&self) -> bool {
+        true
+    }
+
+    fn render(&self, region: Region, styles: &Styles) -> Vec<Strip> {
+        if region.width <= 0 || region.height <= 0 {
+            return Vec::new();
+        }
+        let style = CellStyle::from_styles(styles);
+        let active = style.with_attrs(Attrs::REVERSE | Attrs::BOLD);
+        let mut strips: Vec<Strip> = (0..region.height)
+            .map(|row| Strip::new(region.y + row, region.x))
+            .collect();
+        for (i, title) in self.titles().into_iter().enumerate() {
+            let style = if i == self.active { active } else { style };
+            strips[0].push_str(&label(title), style);
+        }
+        strips[0].cells.truncate(region.width as usize);
+        for strip in &mut strips {
+            strip.fill(region.width, style);
+        }
+        paint_background(&mut strips, region, styles);
+        strips
+    }
+
+    fn css_id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    fn css_classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    fn child_widgets(&self) -> &[Box<dyn Widget>] {
+        &self.panes
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dom::node::NodeData;
+    use crate::dom::transaction::Transaction;
+    use crate::event::input::Modifiers;
+    use crate::screen::Screen;
+    use crate::widget::reconcile::{insert_subtree, reconcile_node};
+    use crate::widgets::static_widget::Static;
+
+    fn tabs() -> Tabs {
+        Tabs::new()
+            .with_pane(TabPane::new("One").with_child(Static::new("first")))
+            .with_pane(TabPane::new("Two").with_child(Static::new("second")))
+            .with_pane(TabPane::new("Three"))
+    }
+
+    fn key(code: Key) -> KeyEvent {
+        KeyEvent::new(code, Modifiers::NONE)
+    }
+
+    #[test]
+    fn keys_cycle_and_classes_follow() {
+        let mut tabs = tabs();
+        assert!(tabs.pane(0).unwrap().is_active());
+        let activated = tabs.handle_key(&key(Key::Left));
+        assert_eq!(
+            activated,
+            Some(TabActivated {
+                index: 2,
+                title: "Three".into()
+            })
+        );
+        assert!(!tabs.pane(0).unwrap().is_active());
+        assert_eq!(tabs.pane(2).unwrap().css_classes(), [ACTIVE_CLASS]);
+        assert_eq!(tabs.handle_key(&key(Key::Right)).unwrap().index, 0);
+        assert_eq!(tabs.handle_key(&key(Key::Up)), None);
+        assert_eq!(tabs.set_active(0), None);
+        assert_eq!(tabs.set_active(7), None);
+    }
+
+    #[test]
+    fn clicks_on_labels_switch_tabs() {
+        let mut tabs = tabs();
+        let region = Region::new(2, 1, 30, 10);
+        // " One " covers columns 2..7, " Two " 7..12.
+        let strips = tabs.render(region, &Styles::new());
+        let text: String = strips[0].cells.iter().map(|c| c.ch).collect();
+        assert!(text.starts_with(" One  Two  Three "));
+        assert!(strips[0].cells[2].style.attrs.contains(Attrs::REVERSE));
+
+        let click = |x, y| MouseEvent {
+            kind: MouseAction::Down(MouseBtn::Left),
+            x,
+            y,
+            modifiers: Modifiers::NONE,
+        };
+        assert_eq!(tabs.handle_mouse(&click(8, 1), region).unwrap().index, 1);
+        assert_eq!(tabs.handle_mouse(&click(8, 2), region), None);
+        assert_eq!(tabs.handle_mouse(&click(29, 1), region), None);
+        assert_eq!(tabs.tab_at(12, region), Some(2));
+    }
+
+    #[test]
+    fn only_the_active_pane_is_laid_out() {
+        let mut tabs = tabs();
+        let mut screen = Screen::new(40, 10);
+        let css = format!(
+            "Screen {{ width: 40; height: 10; }} {} {}",
+            tabs.default_css(),
+            TabPane::new("").default_css()
+        );
+        screen.add_css(&css).unwrap();
+        let mut tx = Transaction::new();
+        let root = tx.insert(NodeData::new("Screen"));
+        insert_subtree(&mut tx, root, &tabs);
+        screen.apply(tx).unwrap();
+        let root = screen.dom.root().unwrap();
+        let node = screen.dom.children(root)[0];
+        let panes = screen.dom.children(node).to_vec();
+
+        let layout = |screen: &mut Screen| -> Vec<i32> {
+            for id in screen.dom.walk_depth_first(root) {
+                let styles = screen.cascade(id);
+                screen.styles.insert(id, styles);
+            }
+            screen.compute_layout().unwrap();
+            panes
+                .iter()
+                .map(|&pane| screen.region_of(pane).map_or(0, |r| r.height))
+                .collect()
+        };
+        assert_eq!(layout(&mut screen), [9, 0, 0]);
+
+        tabs.set_active(1);
+        let mut tx = Transaction::new();
+        reconcile_node(&mut tx, &screen.dom, node, &tabs);
+        screen.apply(tx).unwrap();
+        assert!(screen.dom.get(panes[1]).unwrap().has_class(ACTIVE_CLASS));
+        assert_eq!(layout(&mut screen), [0, 9, 0]);
+    }
+}
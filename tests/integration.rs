@@ -265,6 +265,43 @@ fn test_full_lifecycle() {
     assert!(!pilot.is_running());
 }
 
+// ---------------------------------------------------------------------------
+// view! through a widget registry
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "macros")]
+#[test]
+fn test_view_builds_through_registry() {
+    use gilt_tui::view;
+    use gilt_tui::widget::layout_file::LayoutFileError;
+    use gilt_tui::widget::registry::{WidgetInfo, WidgetRegistry};
+
+    let mut registry = WidgetRegistry::new();
+    registry
+        .register(
+            WidgetInfo::new("Gauge")
+                .with_provider("gilt-gauge", "0.1.0")
+                .with_attrs(&["value"]),
+            |node, _| Ok(node.decorate(Static::new(node.required_attr("value")?))),
+        )
+        .unwrap();
+
+    let widgets = view! {
+        in registry;
+        <Container id="main">
+            <Gauge value="42" />
+        </Container>
+        <Button label="OK" />
+    }
+    .unwrap();
+    assert_eq!(widgets.len(), 2);
+    assert_eq!(widgets[0].widget_type(), "Container");
+    assert_eq!(widgets[1].widget_type(), "Button");
+
+    let unknown = view! { in registry; <Chart /> };
+    assert!(matches!(unknown, Err(LayoutFileError::UnknownWidget(name)) if name == "Chart"));
+}
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------